@@ -1,7 +1,7 @@
 //! Benchmarks for memory usage and allocation patterns.
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use ged_io::{indexed::IndexedGedcomData, GedcomBuilder, GedcomWriter};
+use ged_io::{borrowed::GedcomDataRef, indexed::IndexedGedcomData, GedcomBuilder, GedcomWriter};
 use std::fs;
 
 /// Benchmark memory usage during parsing by measuring allocation patterns
@@ -42,6 +42,40 @@ fn bench_parse_memory(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark owned against borrowed (zero-copy) parsing
+fn bench_parse_borrowed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_borrowed");
+
+    let files = [
+        ("sample", "tests/fixtures/sample.ged"),
+        ("washington", "tests/fixtures/washington.ged"),
+    ];
+
+    for (name, path) in files {
+        if let Ok(content) = fs::read_to_string(path) {
+            group.throughput(Throughput::Bytes(content.len() as u64));
+
+            group.bench_with_input(BenchmarkId::new("owned", name), &content, |b, content| {
+                b.iter(|| {
+                    GedcomBuilder::new()
+                        .build_from_str(black_box(content))
+                        .unwrap()
+                });
+            });
+
+            group.bench_with_input(
+                BenchmarkId::new("borrowed", name),
+                &content,
+                |b, content| {
+                    b.iter(|| GedcomDataRef::parse(black_box(content)).unwrap());
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
 /// Benchmark clone operations (indicative of memory usage)
 fn bench_clone_memory(c: &mut Criterion) {
     let mut group = c.benchmark_group("clone_memory");
@@ -351,7 +385,7 @@ fn bench_struct_sizes(c: &mut Criterion) {
                 if ind.xref.is_some() {
                     total += 1;
                 }
-                if ind.name.is_some() {
+                if !ind.names.is_empty() {
                     total += 1;
                 }
                 if ind.sex.is_some() {
@@ -386,6 +420,7 @@ fn bench_struct_sizes(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_parse_memory,
+    bench_parse_borrowed,
     bench_clone_memory,
     bench_string_allocations,
     bench_vec_growth,
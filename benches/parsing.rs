@@ -1,7 +1,10 @@
 //! Benchmarks for GEDCOM parsing performance.
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use ged_io::{Gedcom, GedcomBuilder};
+use ged_io::{
+    fixtures::{synthetic, Fixtures},
+    Gedcom, GedcomBuilder,
+};
 use std::fs;
 
 /// Benchmark parsing with the original Gedcom::new() API
@@ -109,7 +112,7 @@ fn bench_parse_synthetic(c: &mut Criterion) {
     let sizes = [10, 100, 500, 1000];
 
     for &count in &sizes {
-        let content = generate_synthetic_gedcom(count);
+        let content = synthetic(count);
         let size = content.len();
         group.throughput(Throughput::Bytes(size as u64));
         group.bench_with_input(
@@ -128,61 +131,53 @@ fn bench_parse_synthetic(c: &mut Criterion) {
     group.finish();
 }
 
-/// Generate synthetic GEDCOM data with a specified number of individuals
-fn generate_synthetic_gedcom(individual_count: usize) -> String {
-    let mut gedcom = String::with_capacity(individual_count * 200);
-
-    gedcom.push_str("0 HEAD\n");
-    gedcom.push_str("1 GEDC\n");
-    gedcom.push_str("2 VERS 5.5.1\n");
-    gedcom.push_str("1 CHAR UTF-8\n");
-
-    // Add individuals
-    for i in 1..=individual_count {
-        gedcom.push_str(&format!("0 @I{i}@ INDI\n"));
-        gedcom.push_str(&format!("1 NAME Person{i} /Family{}/\n", i % 100));
-        if i % 2 == 0 {
-            gedcom.push_str("1 SEX F\n");
-        } else {
-            gedcom.push_str("1 SEX M\n");
-        }
-        gedcom.push_str("1 BIRT\n");
-        gedcom.push_str(&format!(
-            "2 DATE {} JAN {}\n",
-            (i % 28) + 1,
-            1900 + (i % 100)
-        ));
-        gedcom.push_str(&format!(
-            "2 PLAC City{}, State{}, Country{}\n",
-            i % 50,
-            i % 10,
-            i % 5
-        ));
+/// Benchmark sequential against parallel parsing of a large synthetic file
+#[cfg(feature = "parallel")]
+fn bench_parse_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_parallel");
+
+    let content = synthetic(10_000);
+    group.throughput(Throughput::Bytes(content.len() as u64));
+
+    for parallel in [false, true] {
+        let name = if parallel { "parallel" } else { "sequential" };
+        group.bench_with_input(BenchmarkId::new(name, 10_000), &content, |b, content| {
+            b.iter(|| {
+                GedcomBuilder::new()
+                    .parallel(parallel)
+                    .build_from_str(black_box(content))
+                    .unwrap()
+            });
+        });
     }
 
-    // Add some families
-    let family_count = individual_count / 4;
-    for i in 1..=family_count {
-        let husb = i * 2 - 1;
-        let wife = i * 2;
-        gedcom.push_str(&format!("0 @F{i}@ FAM\n"));
-        if husb <= individual_count {
-            gedcom.push_str(&format!("1 HUSB @I{husb}@\n"));
-        }
-        if wife <= individual_count {
-            gedcom.push_str(&format!("1 WIFE @I{wife}@\n"));
-        }
-        // Add some children
-        let child_start = individual_count / 2 + i;
-        if child_start <= individual_count {
-            gedcom.push_str(&format!("1 CHIL @I{child_start}@\n"));
-        }
-    }
+    // On one thread the parallel parser gains nothing, so this measures the
+    // cost of splitting the input and parsing each record on its own.
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    group.bench_with_input(
+        BenchmarkId::new("parallel_1_thread", 10_000),
+        &content,
+        |b, content| {
+            b.iter(|| {
+                pool.install(|| {
+                    GedcomBuilder::new()
+                        .parallel(true)
+                        .build_from_str(black_box(content))
+                        .unwrap()
+                })
+            });
+        },
+    );
 
-    gedcom.push_str("0 TRLR");
-    gedcom
+    group.finish();
 }
 
+#[cfg(not(feature = "parallel"))]
+fn bench_parse_parallel(_c: &mut Criterion) {}
+
 /// Benchmark parsing speed per line
 fn bench_parse_lines_per_second(c: &mut Criterion) {
     let mut group = c.benchmark_group("parse_lines_per_second");
@@ -209,13 +204,39 @@ fn bench_parse_lines_per_second(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark the corpora in `GED_IO_CORPUS_DIR`, if it is set
+fn bench_parse_corpus(c: &mut Criterion) {
+    let fixtures = Fixtures::new();
+    let Ok(names) = fixtures.available() else {
+        return;
+    };
+    let mut group = c.benchmark_group("parse_corpus");
+
+    for name in names {
+        if let Ok(content) = fixtures.load(&name) {
+            group.throughput(Throughput::Bytes(content.len() as u64));
+            group.bench_with_input(BenchmarkId::new("file", &name), &content, |b, content| {
+                b.iter(|| {
+                    GedcomBuilder::new()
+                        .build_from_str(black_box(content))
+                        .unwrap()
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_parse_original_api,
     bench_parse_builder_api,
     bench_parse_with_validation,
     bench_parse_synthetic,
+    bench_parse_parallel,
     bench_parse_lines_per_second,
+    bench_parse_corpus,
 );
 
 criterion_main!(benches);
@@ -0,0 +1,341 @@
+//! Asynchronous streaming parser for GEDCOM files.
+//!
+//! [`AsyncGedcomStreamParser`](crate::async_stream::AsyncGedcomStreamParser)
+//! is the async counterpart of
+//! [`GedcomStreamParser`](crate::GedcomStreamParser): it reads from a
+//! [`tokio::io::AsyncBufRead`] source and yields one [`GedcomRecord`] at a
+//! time through the [`Stream`](futures_core::Stream) trait, so a server can
+//! ingest uploads without blocking a thread per file. Like the synchronous parser, it buffers only
+//! the record being read and requires UTF-8 input.
+//!
+//! Requires the `async` feature to be enabled.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), ged_io::GedcomError> {
+//! use ged_io::{async_stream::AsyncGedcomStreamParser, stream::GedcomRecord};
+//!
+//! let source: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR\n";
+//! let mut parser = AsyncGedcomStreamParser::new(source);
+//!
+//! while let Some(record) = parser.next_record().await {
+//!     if let GedcomRecord::Individual(indi) = record? {
+//!         println!("Found: {:?}", indi.full_name());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use tokio::io::AsyncBufRead;
+
+use crate::{
+    stream::{end_of_input, parse_record_text, GedcomRecord},
+    tokenizer::{is_trailer_line, is_trailer_padding},
+    GedcomError, ParseWarning,
+};
+
+/// An asynchronous, record-at-a-time GEDCOM parser.
+///
+/// Records are yielded in file order, the header first. After an error the
+/// stream ends.
+#[allow(clippy::struct_excessive_bools)]
+pub struct AsyncGedcomStreamParser<R: AsyncBufRead + Unpin> {
+    reader: R,
+    /// Bytes of the line being read
+    line_buffer: Vec<u8>,
+    /// Text of the record being accumulated
+    record_buffer: String,
+    /// Current line number for error reporting
+    line_number: u32,
+    /// Whether we've finished parsing
+    finished: bool,
+    /// Whether the header declared GEDCOM 7.0, which changes `@` unescaping
+    gedcom_7: bool,
+    /// Whether the `0 TRLR` line has been read
+    trailer_seen: bool,
+    /// Whether trailer problems are errors rather than warnings
+    strict: bool,
+    /// Recoverable problems found so far
+    warnings: Vec<ParseWarning>,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncGedcomStreamParser<R> {
+    /// Creates a new asynchronous streaming parser.
+    ///
+    /// Nothing is read until the first record is requested.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line_buffer: Vec::with_capacity(256),
+            record_buffer: String::with_capacity(4096),
+            line_number: 0,
+            finished: false,
+            gedcom_7: false,
+            trailer_seen: false,
+            strict: false,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Enables or disables strict mode.
+    ///
+    /// Trailer problems are handled as in
+    /// [`GedcomStreamParser::strict_mode`](crate::GedcomStreamParser::strict_mode).
+    #[must_use]
+    pub fn strict_mode(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Returns the recoverable problems found so far.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Reads and parses the next record.
+    ///
+    /// This is a convenience over the [`Stream`] implementation for callers
+    /// that do not use a stream combinator library.
+    pub async fn next_record(&mut self) -> Option<Result<GedcomRecord, GedcomError>> {
+        std::future::poll_fn(|cx| self.poll_record(cx)).await
+    }
+
+    fn poll_record(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<GedcomRecord, GedcomError>>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        let result = match ready!(self.poll_record_text(cx)) {
+            Ok(Some(text)) => parse_record_text(&text, self.gedcom_7, self.line_number),
+            Ok(None) => {
+                self.finished = true;
+                return Poll::Ready(None);
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(record) => {
+                if let GedcomRecord::Header(header) = &record {
+                    self.gedcom_7 = header.is_gedcom_7();
+                }
+                Poll::Ready(Some(Ok(record)))
+            }
+            Err(e) => {
+                self.finished = true;
+                Poll::Ready(Some(Err(e)))
+            }
+        }
+    }
+
+    /// Reads lines until a complete record is buffered.
+    ///
+    /// A record ends where the next level 0 line starts; that line is kept
+    /// in the buffer as the start of the following record.
+    fn poll_record_text(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<String>, GedcomError>> {
+        loop {
+            if self.trailer_seen {
+                return self.poll_after_trailer(cx);
+            }
+
+            let Some(mut line) = ready!(self.poll_line(cx))? else {
+                if let Some(record) = self.take_record() {
+                    return Poll::Ready(Ok(Some(record)));
+                }
+                return Poll::Ready(self.end_of_input(Some(ParseWarning::MissingTrailer)));
+            };
+            self.line_number += 1;
+            if self.line_number == 1 {
+                if let Some(stripped) = line.strip_prefix('\u{FEFF}') {
+                    line = stripped.to_string();
+                }
+            }
+
+            let trimmed = line.trim_start();
+            let starts_record =
+                trimmed.starts_with('0') && trimmed[1..].starts_with(char::is_whitespace);
+            if starts_record {
+                if is_trailer_line(&line) {
+                    self.trailer_seen = true;
+                    if let Some(record) = self.take_record() {
+                        return Poll::Ready(Ok(Some(record)));
+                    }
+                    continue;
+                }
+                if !self.record_buffer.is_empty() {
+                    let record = std::mem::replace(&mut self.record_buffer, line);
+                    return Poll::Ready(Ok(Some(record)));
+                }
+            }
+            self.record_buffer.push_str(&line);
+        }
+    }
+
+    /// Reads past the trailer, looking for content after it.
+    fn poll_after_trailer(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<String>, GedcomError>> {
+        loop {
+            let line = match ready!(self.poll_line(cx)) {
+                Ok(Some(line)) => line,
+                Ok(None) => return Poll::Ready(self.end_of_input(None)),
+                // Binary junk after the trailer is content like any other.
+                Err(GedcomError::EncodingError(_)) => String::from("\u{FFFD}"),
+                Err(e) => return Poll::Ready(Err(e)),
+            };
+            self.line_number += 1;
+            if !line.chars().all(is_trailer_padding) {
+                let warning = ParseWarning::ContentAfterTrailer {
+                    line: self.line_number,
+                };
+                return Poll::Ready(self.end_of_input(Some(warning)));
+            }
+        }
+    }
+
+    fn end_of_input(
+        &mut self,
+        warning: Option<ParseWarning>,
+    ) -> Result<Option<String>, GedcomError> {
+        end_of_input(warning, self.strict, &mut self.warnings)?;
+        Ok(None)
+    }
+
+    fn take_record(&mut self) -> Option<String> {
+        if self.record_buffer.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.record_buffer))
+        }
+    }
+
+    /// Reads one line, including its terminator, or `None` at end of input.
+    fn poll_line(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<String>, GedcomError>> {
+        loop {
+            let available = ready!(Pin::new(&mut self.reader).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                if self.line_buffer.is_empty() {
+                    return Poll::Ready(Ok(None));
+                }
+                break;
+            }
+
+            if let Some(end) = available.iter().position(|&b| b == b'\n') {
+                self.line_buffer.extend_from_slice(&available[..=end]);
+                Pin::new(&mut self.reader).consume(end + 1);
+                break;
+            }
+            let len = available.len();
+            self.line_buffer.extend_from_slice(available);
+            Pin::new(&mut self.reader).consume(len);
+        }
+
+        let bytes = std::mem::take(&mut self.line_buffer);
+        match String::from_utf8(bytes) {
+            Ok(line) => Poll::Ready(Ok(Some(line))),
+            Err(_) => Poll::Ready(Err(GedcomError::EncodingError(format!(
+                "Streaming parser requires UTF-8 input; invalid UTF-8 at line {}",
+                self.line_number + 1
+            )))),
+        }
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> Stream for AsyncGedcomStreamParser<R> {
+    type Item = Result<GedcomRecord, GedcomError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_record(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GedcomData;
+
+    /// A reader that hands out one byte per read, to exercise partial lines.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl tokio::io::AsyncRead for Trickle<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            if let Some((first, rest)) = self.0.split_first() {
+                buf.put_slice(&[*first]);
+                self.0 = rest;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_stream_parser() {
+        let source = "\u{FEFF}0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 @F1@ FAM\n1 HUSB @I1@\n0 TRLR\n";
+        let reader = tokio::io::BufReader::with_capacity(1, Trickle(source.as_bytes()));
+        let mut parser = AsyncGedcomStreamParser::new(reader);
+
+        let mut records = Vec::new();
+        while let Some(record) = parser.next_record().await {
+            records.push(record.unwrap());
+        }
+        assert_eq!(records.len(), 3);
+        assert!(records[0].is_header());
+
+        let data: GedcomData = records.into_iter().collect();
+        assert_eq!(data.individuals[0].full_name().as_deref(), Some("John Doe"));
+        assert_eq!(data.families[0].individual1.as_deref(), Some("@I1@"));
+    }
+
+    #[tokio::test]
+    async fn test_async_stream_parser_errors_end_stream() {
+        let source: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME \xFF\n0 TRLR\n";
+        let mut parser = AsyncGedcomStreamParser::new(source);
+
+        assert!(parser.next_record().await.unwrap().is_ok());
+        assert!(matches!(
+            parser.next_record().await,
+            Some(Err(GedcomError::EncodingError(_)))
+        ));
+        assert!(parser.next_record().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_async_stream_parser_trailer_warnings() {
+        let source: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 TRLR\n0 @I1@ INDI\n";
+        let mut parser = AsyncGedcomStreamParser::new(source);
+        assert!(parser.next_record().await.unwrap().is_ok());
+        assert!(parser.next_record().await.is_none());
+        assert_eq!(
+            parser.warnings(),
+            [ParseWarning::ContentAfterTrailer { line: 5 }]
+        );
+
+        let source: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n";
+        let mut parser = AsyncGedcomStreamParser::new(source).strict_mode(true);
+        assert!(parser.next_record().await.unwrap().is_ok());
+        assert!(matches!(
+            parser.next_record().await,
+            Some(Err(GedcomError::InvalidFormat(_)))
+        ));
+    }
+}
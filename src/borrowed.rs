@@ -0,0 +1,566 @@
+//! Zero-copy parsing into a borrowed record tree.
+//!
+//! [`GedcomDataRef`](crate::borrowed::GedcomDataRef) is a read-only
+//! counterpart of [`GedcomData`](crate::types::GedcomData) for analytics over
+//! large files. Instead of the typed model it keeps each record as a tree of
+//! [`NodeRef`](crate::borrowed::NodeRef)s whose xrefs, tags and values are
+//! slices of the source text, so parsing allocates little more than the tree
+//! itself. Only values that differ from the source are owned: those joined
+//! from `CONC`/`CONT` lines, and those with `@@` escapes.
+//!
+//! The records can be read through typed views:
+//! [`GedcomDataRef::individual_refs`](crate::borrowed::GedcomDataRef::individual_refs)
+//! and
+//! [`GedcomDataRef::family_refs`](crate::borrowed::GedcomDataRef::family_refs)
+//! return [`IndividualRef`](crate::borrowed::IndividualRef)s and
+//! [`FamilyRef`](crate::borrowed::FamilyRef)s, which find names, events and
+//! links in the tree, still without copying.
+//!
+//! Records are sorted by type like in `GedcomData`; anything else found at
+//! level 0 is kept in
+//! [`GedcomDataRef::other`](crate::borrowed::GedcomDataRef::other). As with
+//! the in-memory parser, a missing trailer or content after it is recorded in
+//! [`GedcomDataRef::warnings`](crate::borrowed::GedcomDataRef::warnings).
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use ged_io::borrowed::GedcomDataRef;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1900\n0 @I2@ INDI\n1 NAME Jane /Doe/\n0 TRLR";
+//! let data = GedcomDataRef::parse(source).unwrap();
+//!
+//! let mut surnames: HashMap<&str, usize> = HashMap::new();
+//! for individual in &data.individuals {
+//!     if let Some(name) = individual.value_at("NAME") {
+//!         let surname = name.split('/').nth(1).unwrap_or_default();
+//!         *surnames.entry(surname).or_default() += 1;
+//!     }
+//! }
+//! assert_eq!(surnames["Doe"], 2);
+//! assert_eq!(data.individuals[0].value_at("BIRT.DATE"), Some("1900"));
+//!
+//! let john = data.individual_refs().next().unwrap();
+//! assert_eq!(john.surname(), Some("Doe"));
+//! assert_eq!(john.birth().and_then(|birth| birth.date()), Some("1900"));
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use std::borrow::Cow;
+
+use crate::{
+    lines::RawLine,
+    tokenizer::{is_trailer_line, is_trailer_padding},
+    util::unescape_at_signs,
+    version::detect_version,
+    GedcomError, ParseWarning,
+};
+
+/// Number of characters scanned for the header version
+const VERSION_SCAN_LEN: usize = 1000;
+
+/// One GEDCOM structure and its substructures, borrowing from the source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct NodeRef<'a> {
+    /// The nesting level, 0 for records.
+    pub level: u8,
+    /// The cross-reference identifier, with its `@` delimiters.
+    pub xref: Option<&'a str>,
+    /// The tag.
+    pub tag: &'a str,
+    /// The value, with continuation lines joined and `@@` unescaped.
+    pub value: Option<Cow<'a, str>>,
+    /// The substructures, in file order.
+    pub children: Vec<NodeRef<'a>>,
+    /// The line the structure starts on.
+    pub line: u32,
+}
+
+impl<'a> NodeRef<'a> {
+    /// Returns the value as a string slice.
+    #[must_use]
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Returns the first substructure with the given tag.
+    #[must_use]
+    pub fn child(&self, tag: &str) -> Option<&NodeRef<'a>> {
+        self.children.iter().find(|c| c.tag == tag)
+    }
+
+    /// Returns every substructure with the given tag.
+    pub fn children_with_tag<'s>(&'s self, tag: &'s str) -> impl Iterator<Item = &'s NodeRef<'a>> {
+        self.children.iter().filter(move |c| c.tag == tag)
+    }
+
+    /// Returns the value at a dot-separated tag path, such as `BIRT.DATE`.
+    ///
+    /// Each step follows the first substructure with the tag.
+    #[must_use]
+    pub fn value_at(&self, path: &str) -> Option<&str> {
+        path.split('.')
+            .try_fold(self, |node, tag| node.child(tag))?
+            .value()
+    }
+}
+
+/// A typed view of an `INDI` record of a [`GedcomDataRef`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndividualRef<'r, 'a> {
+    node: &'r NodeRef<'a>,
+}
+
+impl<'r, 'a> IndividualRef<'r, 'a> {
+    /// Returns the record as a tree.
+    #[must_use]
+    pub fn node(&self) -> &'r NodeRef<'a> {
+        self.node
+    }
+
+    /// Returns the cross-reference identifier, with its `@` delimiters.
+    #[must_use]
+    pub fn xref(&self) -> Option<&'a str> {
+        self.node.xref
+    }
+
+    /// Returns the first name as written, such as `John /Doe/`.
+    #[must_use]
+    pub fn name(&self) -> Option<&'r str> {
+        self.node.child("NAME")?.value()
+    }
+
+    /// Returns every name as written.
+    pub fn names(&self) -> impl Iterator<Item = &'r str> {
+        self.values("NAME")
+    }
+
+    /// Returns the given names of the first name, from its `GIVN`
+    /// substructure or else the text before the surname.
+    #[must_use]
+    pub fn given(&self) -> Option<&'r str> {
+        let name = self.node.child("NAME")?;
+        name.child("GIVN")
+            .and_then(NodeRef::value)
+            .or_else(|| Some(name.value()?.split('/').next()?.trim()))
+            .filter(|given| !given.is_empty())
+    }
+
+    /// Returns the surname of the first name, from its `SURN` substructure
+    /// or else the text between slashes.
+    #[must_use]
+    pub fn surname(&self) -> Option<&'r str> {
+        let name = self.node.child("NAME")?;
+        name.child("SURN")
+            .and_then(NodeRef::value)
+            .or_else(|| Some(name.value()?.split('/').nth(1)?.trim()))
+            .filter(|surname| !surname.is_empty())
+    }
+
+    /// Returns the sex, such as `M` or `F`.
+    #[must_use]
+    pub fn sex(&self) -> Option<&'r str> {
+        self.node.child("SEX")?.value()
+    }
+
+    /// Returns the first birth event.
+    #[must_use]
+    pub fn birth(&self) -> Option<EventRef<'r, 'a>> {
+        self.event("BIRT")
+    }
+
+    /// Returns the first death event.
+    #[must_use]
+    pub fn death(&self) -> Option<EventRef<'r, 'a>> {
+        self.event("DEAT")
+    }
+
+    /// Returns the first event with the given tag, such as `CHR`.
+    #[must_use]
+    pub fn event(&self, tag: &str) -> Option<EventRef<'r, 'a>> {
+        self.node.child(tag).map(|node| EventRef { node })
+    }
+
+    /// Returns the xrefs of the families the individual is a child in.
+    pub fn child_of(&self) -> impl Iterator<Item = &'r str> {
+        self.values("FAMC")
+    }
+
+    /// Returns the xrefs of the families the individual is a spouse in.
+    pub fn spouse_of(&self) -> impl Iterator<Item = &'r str> {
+        self.values("FAMS")
+    }
+
+    fn values(self, tag: &'static str) -> impl Iterator<Item = &'r str> {
+        self.node.children_with_tag(tag).filter_map(NodeRef::value)
+    }
+}
+
+/// A typed view of a `FAM` record of a [`GedcomDataRef`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FamilyRef<'r, 'a> {
+    node: &'r NodeRef<'a>,
+}
+
+impl<'r, 'a> FamilyRef<'r, 'a> {
+    /// Returns the record as a tree.
+    #[must_use]
+    pub fn node(&self) -> &'r NodeRef<'a> {
+        self.node
+    }
+
+    /// Returns the cross-reference identifier, with its `@` delimiters.
+    #[must_use]
+    pub fn xref(&self) -> Option<&'a str> {
+        self.node.xref
+    }
+
+    /// Returns the xref of the husband.
+    #[must_use]
+    pub fn husband(&self) -> Option<&'r str> {
+        self.node.child("HUSB")?.value()
+    }
+
+    /// Returns the xref of the wife.
+    #[must_use]
+    pub fn wife(&self) -> Option<&'r str> {
+        self.node.child("WIFE")?.value()
+    }
+
+    /// Returns the xrefs of the children, in file order.
+    pub fn children(&self) -> impl Iterator<Item = &'r str> {
+        self.node
+            .children_with_tag("CHIL")
+            .filter_map(NodeRef::value)
+    }
+
+    /// Returns the first marriage event.
+    #[must_use]
+    pub fn marriage(&self) -> Option<EventRef<'r, 'a>> {
+        self.event("MARR")
+    }
+
+    /// Returns the first event with the given tag, such as `DIV`.
+    #[must_use]
+    pub fn event(&self, tag: &str) -> Option<EventRef<'r, 'a>> {
+        self.node.child(tag).map(|node| EventRef { node })
+    }
+}
+
+/// A typed view of an event of an [`IndividualRef`] or a [`FamilyRef`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventRef<'r, 'a> {
+    node: &'r NodeRef<'a>,
+}
+
+impl<'r, 'a> EventRef<'r, 'a> {
+    /// Returns the event as a tree.
+    #[must_use]
+    pub fn node(&self) -> &'r NodeRef<'a> {
+        self.node
+    }
+
+    /// Returns the tag, such as `BIRT`.
+    #[must_use]
+    pub fn tag(&self) -> &'a str {
+        self.node.tag
+    }
+
+    /// Returns the date as written.
+    #[must_use]
+    pub fn date(&self) -> Option<&'r str> {
+        self.node.child("DATE")?.value()
+    }
+
+    /// Returns the place name.
+    #[must_use]
+    pub fn place(&self) -> Option<&'r str> {
+        self.node.child("PLAC")?.value()
+    }
+}
+
+/// GEDCOM records parsed without copying, borrowing from the source text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GedcomDataRef<'a> {
+    /// The header record.
+    pub header: Option<NodeRef<'a>>,
+    /// `SUBM` records.
+    pub submitters: Vec<NodeRef<'a>>,
+    /// `SUBN` records.
+    pub submissions: Vec<NodeRef<'a>>,
+    /// `INDI` records.
+    pub individuals: Vec<NodeRef<'a>>,
+    /// `FAM` records.
+    pub families: Vec<NodeRef<'a>>,
+    /// `REPO` records.
+    pub repositories: Vec<NodeRef<'a>>,
+    /// `SOUR` records.
+    pub sources: Vec<NodeRef<'a>>,
+    /// `OBJE` records.
+    pub multimedia: Vec<NodeRef<'a>>,
+    /// `SNOTE` records, and the `NOTE` records of GEDCOM 5.5.1.
+    pub shared_notes: Vec<NodeRef<'a>>,
+    /// Records with any other tag, such as user-defined ones.
+    pub other: Vec<NodeRef<'a>>,
+    /// Recoverable problems found while parsing.
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl<'a> GedcomDataRef<'a> {
+    /// Parses GEDCOM text into borrowed records.
+    ///
+    /// The input must already be decoded; see
+    /// [`decode_gedcom_bytes`](crate::decode_gedcom_bytes) for byte input.
+    /// Blank lines are skipped, and a UTF-8 byte order mark is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError::ParseError` if a line has no level or tag, or
+    /// skips a level.
+    pub fn parse(content: &'a str) -> Result<GedcomDataRef<'a>, GedcomError> {
+        let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+        let head: String = content.chars().take(VERSION_SCAN_LEN).collect();
+        let gedcom_7 = detect_version(&head).is_v7();
+
+        let mut data = GedcomDataRef::default();
+        let mut open: Vec<NodeRef<'a>> = Vec::new();
+        let mut lines = split_lines(content).zip(1..);
+
+        while let Some((text, number)) = lines.next() {
+            if text.trim().is_empty() {
+                continue;
+            }
+            let line = RawLine::parse(text, number)?;
+            let value = line.value.map(|v| unescape(v, gedcom_7));
+
+            if let Some(parent) = open.last_mut() {
+                if line.level == parent.level + 1 && matches!(line.tag, "CONT" | "CONC") {
+                    let mut joined = parent.value.take().unwrap_or_default().into_owned();
+                    if line.tag == "CONT" {
+                        joined.push('\n');
+                    }
+                    joined.push_str(value.as_deref().unwrap_or_default());
+                    parent.value = Some(Cow::Owned(joined));
+                    continue;
+                }
+            }
+
+            while open.last().is_some_and(|node| node.level >= line.level) {
+                close(&mut open, &mut data);
+            }
+            let expected = open.last().map_or(0, |parent| parent.level + 1);
+            if line.level != expected {
+                return Err(GedcomError::ParseError {
+                    line: number,
+                    message: format!("Expected level {expected}, found level {}", line.level),
+                });
+            }
+
+            if line.level == 0 && is_trailer_line(text) {
+                let after = lines
+                    .find(|(text, _)| !text.chars().all(is_trailer_padding))
+                    .map(|(_, line)| ParseWarning::ContentAfterTrailer { line });
+                data.warnings.extend(after);
+                return Ok(data);
+            }
+
+            open.push(NodeRef {
+                level: line.level,
+                xref: line.xref,
+                tag: line.tag,
+                value,
+                children: Vec::new(),
+                line: number,
+            });
+        }
+
+        while !open.is_empty() {
+            close(&mut open, &mut data);
+        }
+        data.warnings.push(ParseWarning::MissingTrailer);
+        Ok(data)
+    }
+
+    /// Returns a typed view of each `INDI` record.
+    pub fn individual_refs(&self) -> impl Iterator<Item = IndividualRef<'_, 'a>> {
+        self.individuals.iter().map(|node| IndividualRef { node })
+    }
+
+    /// Returns a typed view of each `FAM` record.
+    pub fn family_refs(&self) -> impl Iterator<Item = FamilyRef<'_, 'a>> {
+        self.families.iter().map(|node| FamilyRef { node })
+    }
+
+    /// Finds an individual by its xref.
+    #[must_use]
+    pub fn individual(&self, xref: &str) -> Option<IndividualRef<'_, 'a>> {
+        self.individual_refs().find(|i| i.xref() == Some(xref))
+    }
+
+    /// Finds a family by its xref.
+    #[must_use]
+    pub fn family(&self, xref: &str) -> Option<FamilyRef<'_, 'a>> {
+        self.family_refs().find(|f| f.xref() == Some(xref))
+    }
+
+    /// Finds a record of any type by its xref.
+    #[must_use]
+    pub fn find(&self, xref: &str) -> Option<&NodeRef<'a>> {
+        self.records().find(|r| r.xref == Some(xref))
+    }
+
+    /// Returns every record, the header first, grouped by type.
+    pub fn records(&self) -> impl Iterator<Item = &NodeRef<'a>> {
+        self.header.iter().chain(
+            [
+                &self.submitters,
+                &self.submissions,
+                &self.individuals,
+                &self.families,
+                &self.repositories,
+                &self.sources,
+                &self.multimedia,
+                &self.shared_notes,
+                &self.other,
+            ]
+            .into_iter()
+            .flatten(),
+        )
+    }
+
+    fn add_record(&mut self, record: NodeRef<'a>) {
+        let records = match record.tag {
+            "HEAD" => {
+                self.header = Some(record);
+                return;
+            }
+            "SUBM" => &mut self.submitters,
+            "SUBN" => &mut self.submissions,
+            "INDI" => &mut self.individuals,
+            "FAM" => &mut self.families,
+            "REPO" => &mut self.repositories,
+            "SOUR" => &mut self.sources,
+            "OBJE" => &mut self.multimedia,
+            "SNOTE" | "NOTE" => &mut self.shared_notes,
+            _ => &mut self.other,
+        };
+        records.push(record);
+    }
+}
+
+/// Closes the innermost open structure, attaching it to its parent.
+fn close<'a>(open: &mut Vec<NodeRef<'a>>, data: &mut GedcomDataRef<'a>) {
+    if let Some(node) = open.pop() {
+        match open.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => data.add_record(node),
+        }
+    }
+}
+
+/// Splits text into lines, accepting LF, CRLF and CR-only line endings.
+fn split_lines(content: &str) -> impl Iterator<Item = &str> {
+    let separator = if content.contains('\n') { '\n' } else { '\r' };
+    content
+        .split(separator)
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+fn unescape(value: &str, gedcom_7: bool) -> Cow<'_, str> {
+    if value.contains("@@") {
+        Cow::Owned(unescape_at_signs(value, gedcom_7))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_parse_tree() {
+        let source = "\
+            0 HEAD\r\n\
+            1 GEDC\r\n\
+            2 VERS 5.5.1\r\n\
+            0 @I1@ INDI\r\n\
+            1 NAME John /Doe/\r\n\
+            1 NOTE First\r\n\
+            2 CONT second\r\n\
+            2 CONC  line\r\n\
+            1 EMAIL john@@example.com\r\n\
+            0 @F1@ FAM\r\n\
+            1 HUSB @I1@\r\n\
+            0 _LOC Custom\r\n\
+            0 TRLR\r\n";
+        let data = GedcomDataRef::parse(source).unwrap();
+
+        assert_eq!(
+            data.header.as_ref().unwrap().value_at("GEDC.VERS"),
+            Some("5.5.1")
+        );
+        let john = &data.individuals[0];
+        assert_eq!(john.xref, Some("@I1@"));
+        assert!(matches!(
+            john.child("NAME").unwrap().value,
+            Some(Cow::Borrowed(_))
+        ));
+        assert_eq!(john.value_at("NOTE"), Some("First\nsecond line"));
+        assert_eq!(john.value_at("EMAIL"), Some("john@example.com"));
+        assert_eq!(john.children.len(), 3);
+
+        assert_eq!(data.find("@F1@").unwrap().value_at("HUSB"), Some("@I1@"));
+        assert_eq!(data.other[0].tag, "_LOC");
+        assert_eq!(data.records().count(), 4);
+        assert!(data.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_borrowed_typed_views() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n1 NAME Johnny\n1 SEX M\n\
+            1 BIRT\n2 DATE 1 JAN 1900\n2 PLAC Salem\n1 FAMS @F1@\n\
+            0 @I2@ INDI\n1 NAME Jane\n2 GIVN Jane\n2 SURN Roe\n1 FAMC @F1@\n\
+            0 @F1@ FAM\n1 HUSB @I1@\n1 CHIL @I2@\n1 MARR\n2 PLAC Boston\n0 TRLR";
+        let data = GedcomDataRef::parse(source).unwrap();
+
+        let john = data.individual("@I1@").unwrap();
+        assert_eq!(john.name(), Some("John /Doe/"));
+        assert_eq!(john.names().collect::<Vec<_>>(), ["John /Doe/", "Johnny"]);
+        assert_eq!((john.given(), john.surname()), (Some("John"), Some("Doe")));
+        assert_eq!(john.sex(), Some("M"));
+        let birth = john.birth().unwrap();
+        assert_eq!((birth.tag(), birth.date()), ("BIRT", Some("1 JAN 1900")));
+        assert_eq!(birth.place(), Some("Salem"));
+        assert!(john.death().is_none());
+        assert_eq!(john.spouse_of().collect::<Vec<_>>(), ["@F1@"]);
+
+        let jane = data.individual_refs().nth(1).unwrap();
+        assert_eq!((jane.given(), jane.surname()), (Some("Jane"), Some("Roe")));
+        assert_eq!(jane.child_of().collect::<Vec<_>>(), ["@F1@"]);
+        assert_eq!(jane.node().tag, "INDI");
+
+        let family = data.family("@F1@").unwrap();
+        assert_eq!((family.husband(), family.wife()), (Some("@I1@"), None));
+        assert_eq!(family.children().collect::<Vec<_>>(), ["@I2@"]);
+        assert_eq!(family.marriage().and_then(|m| m.place()), Some("Boston"));
+        assert_eq!(data.family_refs().count(), 1);
+        assert!(data.individual("@F1@").is_none());
+    }
+
+    #[test]
+    fn test_borrowed_parse_errors_and_warnings() {
+        let err = GedcomDataRef::parse("0 HEAD\n2 VERS 5.5\n0 TRLR").unwrap_err();
+        assert!(matches!(err, GedcomError::ParseError { line: 2, .. }));
+
+        let data = GedcomDataRef::parse("0 HEAD\n0 @I1@ INDI\n1 NAME A").unwrap();
+        assert_eq!(data.individuals[0].value_at("NAME"), Some("A"));
+        assert_eq!(data.warnings, vec![ParseWarning::MissingTrailer]);
+    }
+}
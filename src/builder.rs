@@ -21,13 +21,30 @@
 //! # }
 //! ```
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "unicode-normalization")]
+use crate::encoding::normalize::UnicodeForm;
 use crate::{
-    encoding::{decode_gedcom_bytes, GedcomEncoding},
+    dialect::Dialect,
+    encoding::{
+        decode_borrowed, decode_with_encoding, read_limited, repair::repair_encoding,
+        validate_gedcom7_utf8, GedcomEncoding,
+    },
+    parser::{TagHandler, TagHandlers},
+    report::InvisibleCharacterPolicy,
+    stream::RecordSettings,
     tokenizer::Tokenizer,
-    types::GedcomData,
-    GedcomError,
+    types::{DuplicateXrefPolicy, GedcomData},
+    util::StringInterner,
+    GedcomError, ParseWarning,
 };
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::Path;
 use std::str::Chars;
+use std::sync::Arc;
 
 /// Configuration options for GEDCOM parsing.
 ///
@@ -35,6 +52,7 @@ use std::str::Chars;
 /// processes GEDCOM data. It is used internally by `GedcomBuilder`.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct ParserConfig {
     /// When true, the parser will fail on any non-standard or unknown tags.
     /// When false, unknown tags are skipped or stored as custom data.
@@ -63,6 +81,51 @@ pub struct ParserConfig {
     /// When true, original spacing and formatting in text values is preserved.
     /// When false, text may be normalized.
     pub preserve_formatting: bool,
+
+    /// When true, byte input that declares GEDCOM 7.0 is checked for strict
+    /// UTF-8 conformance before decoding, and any problem is reported as an error.
+    pub strict_utf8: bool,
+
+    /// What to do with records of the same type that share an xref.
+    pub duplicate_xrefs: DuplicateXrefPolicy,
+
+    /// When true, LDS ordinances are dropped from individuals and families.
+    pub skip_lds_ordinances: bool,
+
+    /// What to do with stray byte order marks and zero-width characters.
+    pub invisible_characters: InvisibleCharacterPolicy,
+
+    /// The application whose non-standard GEDCOM to expect.
+    pub dialect: Dialect,
+
+    /// When true, the source text of each record is kept in
+    /// [`GedcomData::raw_records`].
+    pub keep_raw: bool,
+
+    /// When true, the lines of each record and structure are kept in
+    /// [`GedcomData::positions`].
+    pub track_positions: bool,
+
+    /// When true, records that fail to parse are skipped and recorded in
+    /// the warnings instead of failing the whole parse.
+    pub recover: bool,
+
+    /// When true, byte input is decoded line by line, repairing a mislabeled
+    /// encoding, lines in another encoding and double-encoded UTF-8.
+    pub repair_encoding: bool,
+
+    /// When true, equal place names, surnames and custom tags share one
+    /// string while records are built.
+    pub intern_strings: bool,
+
+    /// When true, records are parsed concurrently on the rayon thread pool.
+    #[cfg(feature = "parallel")]
+    pub parallel: bool,
+
+    /// The Unicode normalization form to convert the text to before
+    /// parsing, if any.
+    #[cfg(feature = "unicode-normalization")]
+    pub unicode_form: Option<UnicodeForm>,
 }
 
 impl Default for ParserConfig {
@@ -75,6 +138,20 @@ impl Default for ParserConfig {
             date_validation: false,
             max_file_size: None,
             preserve_formatting: true,
+            strict_utf8: false,
+            duplicate_xrefs: DuplicateXrefPolicy::KeepAll,
+            skip_lds_ordinances: false,
+            invisible_characters: InvisibleCharacterPolicy::Report,
+            dialect: Dialect::Standard,
+            keep_raw: false,
+            track_positions: false,
+            recover: false,
+            repair_encoding: false,
+            intern_strings: false,
+            #[cfg(feature = "parallel")]
+            parallel: false,
+            #[cfg(feature = "unicode-normalization")]
+            unicode_form: None,
         }
     }
 }
@@ -108,6 +185,7 @@ impl Default for ParserConfig {
 #[derive(Debug, Clone, Default)]
 pub struct GedcomBuilder {
     config: ParserConfig,
+    tag_handlers: TagHandlers,
 }
 
 impl GedcomBuilder {
@@ -121,6 +199,18 @@ impl GedcomBuilder {
     /// - `date_validation`: false
     /// - `max_file_size`: None (unlimited)
     /// - `preserve_formatting`: true
+    /// - `strict_utf8`: false
+    /// - `duplicate_xrefs`: `KeepAll`
+    /// - `skip_lds_ordinances`: false
+    /// - `invisible_characters`: `Report`
+    /// - `dialect`: `Standard`
+    /// - `keep_raw`: false
+    /// - `track_positions`: false
+    /// - `recover`: false
+    /// - `repair_encoding`: false
+    /// - `intern_strings`: false
+    /// - `parallel`: false (with the `parallel` feature)
+    /// - `unicode_form`: None (with the `unicode-normalization` feature)
     ///
     /// # Example
     ///
@@ -131,8 +221,32 @@ impl GedcomBuilder {
     /// ```
     #[must_use]
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `GedcomBuilder` with the given configuration, such as
+    /// one saved with serde.
+    ///
+    /// Tag handlers are not part of the configuration; register them with
+    /// [`tag_handler`](Self::tag_handler).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomBuilder, ParserConfig};
+    ///
+    /// let config = ParserConfig {
+    ///     strict_mode: true,
+    ///     ..ParserConfig::default()
+    /// };
+    /// let builder = GedcomBuilder::with_config(config);
+    /// assert!(builder.config().strict_mode);
+    /// ```
+    #[must_use]
+    pub fn with_config(config: ParserConfig) -> Self {
         Self {
-            config: ParserConfig::default(),
+            config,
+            tag_handlers: TagHandlers::default(),
         }
     }
 
@@ -142,6 +256,12 @@ impl GedcomBuilder {
     /// tags or structural issues. When disabled (default), the parser is more
     /// lenient and will attempt to continue parsing despite minor issues.
     ///
+    /// Issues the lenient parser recovers from are recorded in
+    /// [`GedcomData::warnings`]; strict mode reports the first of them as an
+    /// error instead. This covers a missing `0 TRLR` trailer and content after
+    /// it, which the [streaming parser](crate::GedcomStreamParser::strict_mode)
+    /// handles the same way.
+    ///
     /// # Arguments
     ///
     /// * `enabled` - Whether to enable strict mode
@@ -305,6 +425,452 @@ impl GedcomBuilder {
         self
     }
 
+    /// Enables or disables strict UTF-8 validation for GEDCOM 7.0 input.
+    ///
+    /// GEDCOM 7.0 requires UTF-8. When enabled, [`build_from_bytes`](Self::build_from_bytes)
+    /// scans input that declares version 7 for invalid sequences, banned control
+    /// characters and a missing final newline, and fails with
+    /// `GedcomError::EncodingError` listing every byte offset instead of
+    /// decoding through a fallback.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to validate UTF-8 strictly
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let bytes = b"0 HEAD\n1 GEDC\n2 VERS 7.0\n0 TRLR";
+    /// let result = GedcomBuilder::new()
+    ///     .strict_utf8(true)
+    ///     .build_from_bytes(bytes);
+    /// assert!(result.is_err());
+    /// ```
+    #[must_use]
+    pub fn strict_utf8(mut self, enabled: bool) -> Self {
+        self.config.strict_utf8 = enabled;
+        self
+    }
+
+    /// Sets the policy for records of the same type that share an xref.
+    ///
+    /// By default every record is kept, so lookups by xref only find the
+    /// first one. The policy is applied once all records are read, before
+    /// reference validation, and each duplicate it resolves is recorded as a
+    /// [`ParseWarning::DuplicateXref`] in [`GedcomData::warnings`].
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - What to do with duplicated xrefs
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{DuplicateXrefPolicy, GedcomBuilder};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n0 @I1@ INDI\n0 TRLR";
+    /// let data = GedcomBuilder::new()
+    ///     .duplicate_xrefs(DuplicateXrefPolicy::KeepFirst)
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    /// assert_eq!(data.individuals.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn duplicate_xrefs(mut self, policy: DuplicateXrefPolicy) -> Self {
+        self.config.duplicate_xrefs = policy;
+        self
+    }
+
+    /// Enables or disables skipping of LDS ordinances.
+    ///
+    /// When enabled, the `BAPL`, `CONL`, `ENDL`, `INIL`, `SLGC` and `SLGS`
+    /// structures of individuals and families are left out of the result,
+    /// as many users must do before sharing a file. See
+    /// [`GedcomData::remove_lds_ordinances`].
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to skip LDS ordinances
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 BAPL\n2 DATE 1900\n0 TRLR";
+    /// let data = GedcomBuilder::new()
+    ///     .skip_lds_ordinances(true)
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    /// assert!(data.individuals[0].lds_ordinances.is_empty());
+    /// ```
+    #[must_use]
+    pub fn skip_lds_ordinances(mut self, enabled: bool) -> Self {
+        self.config.skip_lds_ordinances = enabled;
+        self
+    }
+
+    /// Sets what to do with stray byte order marks and zero-width characters.
+    ///
+    /// Each one found is listed in
+    /// [`ParseReport::invisible_characters`](crate::report::ParseReport::invisible_characters)
+    /// either way; [`InvisibleCharacterPolicy::Strip`] also removes them from
+    /// tags, xrefs and values. See [`is_invisible`](crate::report::is_invisible)
+    /// for the characters matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Whether to keep or strip invisible characters
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{report::InvisibleCharacterPolicy, GedcomBuilder};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME\u{200B} John /Doe/\n0 TRLR";
+    /// let data = GedcomBuilder::new()
+    ///     .invisible_characters(InvisibleCharacterPolicy::Strip)
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    /// assert_eq!(data.individuals[0].full_name().as_deref(), Some("John Doe"));
+    /// assert_eq!(data.report.invisible_characters[0].line, 5);
+    /// ```
+    #[must_use]
+    pub fn invisible_characters(mut self, policy: InvisibleCharacterPolicy) -> Self {
+        self.config.invisible_characters = policy;
+        self
+    }
+
+    /// Sets the application whose non-standard GEDCOM to expect.
+    ///
+    /// The vendor tags and quirks of the dialect are turned into standard
+    /// structures while parsing; see the [`dialect`](crate::dialect) module
+    /// for what each one changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The application that wrote the file
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{dialect::Dialect, types::individual::name::NameType, GedcomBuilder};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+    ///     0 @I1@ INDI\n1 NAME Jane /Doe/\n2 _MARNM Smith\n0 TRLR";
+    /// let data = GedcomBuilder::new()
+    ///     .dialect(Dialect::RootsMagic)
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    /// assert_eq!(data.individuals[0].names[1].name_type, Some(NameType::Married));
+    /// ```
+    #[must_use]
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.config.dialect = dialect;
+        self
+    }
+
+    /// Enables or disables keeping the source text of each record.
+    ///
+    /// When enabled, the text of every level 0 record is kept in
+    /// [`GedcomData::raw_records`] with the lines it spans, exactly as the
+    /// file had it, so that it can be shown or written out again for
+    /// debugging and audits. See the [`raw`](crate::raw) module.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to keep the source text of each record
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+    /// let data = GedcomBuilder::new().keep_raw(true).build_from_str(source).unwrap();
+    ///
+    /// let raw = data.raw_record("@I1@").unwrap();
+    /// assert_eq!(raw.text, "0 @I1@ INDI\n1 NAME John /Doe/\n");
+    /// assert_eq!(raw.line, 4);
+    /// ```
+    #[must_use]
+    pub fn keep_raw(mut self, enabled: bool) -> Self {
+        self.config.keep_raw = enabled;
+        self
+    }
+
+    /// Enables or disables tracking where each structure is in the source.
+    ///
+    /// When enabled, the first and last line of every record and of every
+    /// structure beneath it are kept in [`GedcomData::positions`], so that
+    /// problems found later can be traced back to the lines of the file.
+    /// See the [`position`](crate::position) module.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to keep the lines of each structure
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 BIRT\n2 DATE 1900\n0 TRLR";
+    /// let data = GedcomBuilder::new()
+    ///     .track_positions(true)
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    ///
+    /// let date = data.position("@I1@").and_then(|span| span.find("BIRT/DATE"));
+    /// assert_eq!(date.map(|span| span.line), Some(6));
+    /// ```
+    #[must_use]
+    pub fn track_positions(mut self, enabled: bool) -> Self {
+        self.config.track_positions = enabled;
+        self
+    }
+
+    /// Enables or disables recovery from malformed records.
+    ///
+    /// When enabled, a level 0 record that fails to parse is left out
+    /// instead of failing the whole file, and parsing goes on with the next
+    /// record. Each skipped record is recorded in [`GedcomData::warnings`]
+    /// as a [`ParseWarning::SkippedRecord`] with its text, its lines and the
+    /// error. In strict mode the first skipped record is reported as an
+    /// error instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to skip records that fail to parse
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomBuilder, ParseWarning};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+    ///     0 @I1@ INDI\n0 @X1@ BOGUS\n1 NOTE lost\n0 @I2@ INDI\n0 TRLR";
+    /// let data = GedcomBuilder::new().recover(true).build_from_str(source).unwrap();
+    ///
+    /// assert_eq!(data.individuals.len(), 2);
+    /// let ParseWarning::SkippedRecord { line, end_line, text, .. } = &data.warnings[0] else {
+    ///     panic!("expected a skipped record");
+    /// };
+    /// assert_eq!((*line, *end_line), (5, 6));
+    /// assert_eq!(text, "0 @X1@ BOGUS\n1 NOTE lost\n");
+    /// ```
+    #[must_use]
+    pub fn recover(mut self, enabled: bool) -> Self {
+        self.config.recover = enabled;
+        self
+    }
+
+    /// Enables or disables repairing byte input that does not match its
+    /// declared encoding.
+    ///
+    /// When enabled, the bytes are decoded with [`repair_encoding`]: a `CHAR`
+    /// line that names the wrong encoding, lines in another encoding than the
+    /// rest of the file and UTF-8 text encoded twice, such as `JosÃ©`, are
+    /// fixed. Each repair is recorded in [`GedcomData::warnings`] as a
+    /// [`ParseWarning::Encoding`]. In strict mode the first repair is
+    /// reported as an error instead.
+    ///
+    /// Applies to [`build_from_bytes`](Self::build_from_bytes),
+    /// [`build_from_path`](Self::build_from_path),
+    /// [`build_from_reader`](Self::build_from_reader) and `build_from_mmap`.
+    /// Text input is not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to repair the encoding of byte input
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let bytes = "0 HEAD\n1 CHAR UTF-8\n0 @I1@ INDI\n1 NAME JosÃ© /Doe/\n0 TRLR\n";
+    /// let data = GedcomBuilder::new()
+    ///     .repair_encoding(true)
+    ///     .build_from_bytes(bytes.as_bytes())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(data.individuals[0].full_name().as_deref(), Some("José Doe"));
+    /// assert_eq!(data.diagnostics()[0].code, "double-encoded-utf8");
+    /// ```
+    #[must_use]
+    pub fn repair_encoding(mut self, enabled: bool) -> Self {
+        self.config.repair_encoding = enabled;
+        self
+    }
+
+    /// Enables or disables sharing repeated values while parsing.
+    ///
+    /// When enabled, every place name (`PLAC`), surname (`SURN`) and custom
+    /// tag equal to one already read points to the same string instead of a
+    /// copy of its own. Large files repeat a few thousand such values across
+    /// hundreds of thousands of records, so this saves memory at the cost of
+    /// a lookup per value. The parsed data compares equal either way. To
+    /// count the values of a file already parsed, see
+    /// [`GedcomData::intern_values`].
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to share repeated values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+    ///     0 @I1@ INDI\n1 BIRT\n2 PLAC Boston\n\
+    ///     0 @I2@ INDI\n1 BIRT\n2 PLAC Boston\n0 TRLR";
+    /// let data = GedcomBuilder::new()
+    ///     .intern_strings(true)
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    ///
+    /// let place = |i: usize| data.individuals[i].events[0].place.clone().unwrap().value.unwrap();
+    /// assert!(Arc::ptr_eq(&place(0), &place(1)));
+    /// ```
+    #[must_use]
+    pub fn intern_strings(mut self, enabled: bool) -> Self {
+        self.config.intern_strings = enabled;
+        self
+    }
+
+    /// Enables or disables parallel parsing.
+    ///
+    /// When enabled, the input is split into level 0 records, which are then
+    /// parsed concurrently on the rayon thread pool and reassembled in their
+    /// original order. This pays off for large files with many records on
+    /// several cores; small files, or any file on a single core, parse faster
+    /// sequentially. The result is the same either way.
+    ///
+    /// Applies to every `build_from_*` method; [`build`](Self::build), which
+    /// reads from a character iterator, always parses sequentially.
+    ///
+    /// The records are also parsed sequentially, without an error, when
+    /// [`recover`](Self::recover) is enabled, which has its own record by
+    /// record pass, or when a handler is registered with
+    /// [`tag_handler`](Self::tag_handler), so that it sees the tags in file
+    /// order.
+    ///
+    /// Requires the `parallel` feature to be enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to parse records in parallel
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+    /// let data = GedcomBuilder::new()
+    ///     .parallel(true)
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    /// assert_eq!(data.individuals.len(), 1);
+    /// ```
+    #[cfg(feature = "parallel")]
+    #[must_use]
+    pub fn parallel(mut self, enabled: bool) -> Self {
+        self.config.parallel = enabled;
+        self
+    }
+
+    /// Sets the Unicode normalization form to convert the text to before
+    /// parsing.
+    ///
+    /// ANSEL files decode to letters followed by combining accents, while
+    /// most software expects the precomposed letters of
+    /// [`UnicodeForm::Nfc`]; normalizing makes names and places compare
+    /// equal however they were encoded. Applies to every `build_*` method.
+    /// The raw text kept with [`keep_raw`](Self::keep_raw) is left as read.
+    ///
+    /// Requires the `unicode-normalization` feature to be enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `form` - The normalization form of the parsed strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{encoding::normalize::UnicodeForm, GedcomBuilder};
+    ///
+    /// let bytes = b"0 HEAD\n1 CHAR ANSEL\n0 @I1@ INDI\n1 NAME Zo\xE8e /Dupr\xE2e/\n0 TRLR\n";
+    /// let data = GedcomBuilder::new()
+    ///     .normalize_unicode(UnicodeForm::Nfc)
+    ///     .build_from_bytes(bytes)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     data.individuals[0].full_name().as_deref(),
+    ///     Some("Zo\u{eb} Dupr\u{e9}")
+    /// );
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalize_unicode(mut self, form: UnicodeForm) -> Self {
+        self.config.unicode_form = Some(form);
+        self
+    }
+
+    /// Registers a handler offered the custom tags of every structure as
+    /// they are parsed.
+    ///
+    /// The structures the handler takes go to it instead of the
+    /// `custom_data` of the enclosing structure, unless it hands them back.
+    /// Handlers are asked in the order they were registered. Records are
+    /// parsed on one thread when any handler is registered, even with
+    /// `parallel` enabled, so handlers see the tags in
+    /// file order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use ged_io::{parser::TagHandler, types::custom::UserDefinedTag, GedcomBuilder};
+    ///
+    /// #[derive(Default)]
+    /// struct MilitaryService(Mutex<Vec<String>>);
+    ///
+    /// impl TagHandler for MilitaryService {
+    ///     fn handles(&self, tag: &str) -> bool {
+    ///         tag == "_MILT"
+    ///     }
+    ///
+    ///     fn handle(&self, tag: UserDefinedTag, _level: u8) -> Option<UserDefinedTag> {
+    ///         self.0.lock().unwrap().extend(tag.value);
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+    ///     0 @I1@ INDI\n1 BIRT\n2 _MILT Navy\n1 _MILT Army\n0 TRLR";
+    /// let service = Arc::new(MilitaryService::default());
+    /// let data = GedcomBuilder::new()
+    ///     .tag_handler(service.clone())
+    ///     .build_from_str(source)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(*service.0.lock().unwrap(), ["Navy", "Army"]);
+    /// assert!(data.individuals[0].custom_data.is_empty());
+    /// ```
+    #[must_use]
+    pub fn tag_handler(mut self, handler: Arc<dyn TagHandler>) -> Self {
+        self.tag_handlers.push(handler);
+        self
+    }
+
     /// Returns a reference to the current parser configuration.
     ///
     /// This can be used to inspect the configuration before building.
@@ -351,10 +917,94 @@ impl GedcomBuilder {
     /// # }
     /// ```
     pub fn build(self, chars: Chars<'_>) -> Result<GedcomData, GedcomError> {
+        let source = chars.as_str();
+        let text = self.normalize_text(source);
+        let chars = match &text {
+            Cow::Borrowed(_) => chars,
+            Cow::Owned(text) => text.chars(),
+        };
+        let interner = self.interner();
+        if self.config.recover {
+            let settings = RecordSettings {
+                invisible_characters: self.config.invisible_characters,
+                tag_handlers: &self.tag_handlers,
+                interner: interner.as_ref(),
+            };
+            let data = crate::recover::parse(chars.as_str(), &settings);
+            return self.finish(data, source);
+        }
+
         let mut tokenizer = Tokenizer::new(chars);
+        tokenizer.set_invisible_characters(self.config.invisible_characters);
+        tokenizer.set_tag_handlers(self.tag_handlers.clone());
+        tokenizer.set_interner(interner);
         tokenizer.next_token()?;
 
         let data = GedcomData::new(&mut tokenizer, 0)?;
+        self.finish(data, source)
+    }
+
+    /// Returns where to share repeated values, if they are to be shared.
+    fn interner(&self) -> Option<Arc<StringInterner>> {
+        self.config
+            .intern_strings
+            .then(|| Arc::new(StringInterner::new()))
+    }
+
+    /// Applies the dialect fixes and the Unicode normalization to the text
+    /// to parse.
+    fn normalize_text<'a>(&self, source: &'a str) -> Cow<'a, str> {
+        #[allow(unused_mut)] // Only reassigned with Unicode normalization.
+        let mut text = self.config.dialect.normalize_text(source);
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(form) = self.config.unicode_form {
+            if let Cow::Owned(normalized) = form.normalize(&text) {
+                text = Cow::Owned(normalized);
+            }
+        }
+        text
+    }
+
+    /// Parses text already in memory, in parallel when so configured.
+    fn build_text(self, content: &str) -> Result<GedcomData, GedcomError> {
+        #[cfg(feature = "parallel")]
+        if self.config.parallel && !self.config.recover && self.tag_handlers.is_empty() {
+            let text = self.normalize_text(content);
+            let data = crate::parallel::parse(
+                &text,
+                self.config.invisible_characters,
+                self.interner().as_ref(),
+            )?;
+            return self.finish(data, content);
+        }
+
+        self.build(content.chars())
+    }
+
+    /// Applies the post-parse policies: strict mode, the raw text and
+    /// positions of the records in `source`, duplicate xrefs, LDS ordinances, the dialect and
+    /// validation.
+    fn finish(&self, mut data: GedcomData, source: &str) -> Result<GedcomData, GedcomError> {
+        if self.config.strict_mode {
+            if let Some(warning) = data.warnings.first() {
+                return Err(warning.to_error());
+            }
+        }
+        if self.config.keep_raw {
+            data.raw_records = crate::raw::records(source);
+        }
+        if self.config.track_positions {
+            data.positions = crate::position::spans(source);
+        }
+        if self.config.duplicate_xrefs != DuplicateXrefPolicy::KeepAll {
+            let duplicates = data.resolve_duplicate_xrefs(self.config.duplicate_xrefs)?;
+            data.warnings
+                .extend(duplicates.into_iter().map(ParseWarning::DuplicateXref));
+        }
+        if self.config.skip_lds_ordinances {
+            data.remove_lds_ordinances();
+        }
+        self.config.dialect.normalize(&mut data);
 
         // Post-parse validation if enabled
         if self.config.validate_references {
@@ -407,10 +1057,36 @@ impl GedcomBuilder {
             }
         }
 
-        // Decode bytes to UTF-8 string
-        let (content, _encoding) = decode_gedcom_bytes(bytes)?;
+        self.build_decoded(bytes)
+    }
 
-        self.build(content.chars())
+    /// Decodes bytes and parses them, borrowing UTF-8 input instead of copying it.
+    fn build_decoded(self, bytes: &[u8]) -> Result<GedcomData, GedcomError> {
+        if self.config.strict_utf8 {
+            if let Some(report) = validate_gedcom7_utf8(bytes) {
+                if !report.is_valid() {
+                    return Err(GedcomError::EncodingError(format!(
+                        "GEDCOM 7.0 requires valid UTF-8: {report}"
+                    )));
+                }
+            }
+        }
+
+        if self.config.repair_encoding {
+            let repair = repair_encoding(bytes)?;
+            if self.config.strict_mode {
+                if let Some(issue) = repair.issues.first() {
+                    return Err(ParseWarning::Encoding(*issue).to_error());
+                }
+            }
+            let mut data = self.build_text(&repair.content)?;
+            data.warnings
+                .splice(0..0, repair.issues.into_iter().map(ParseWarning::Encoding));
+            return Ok(data);
+        }
+
+        let (content, _) = decode_borrowed(bytes)?;
+        self.build_text(&content)
     }
 
     /// Builds the parser and parses the GEDCOM data from raw bytes with a specific encoding.
@@ -456,9 +1132,9 @@ impl GedcomBuilder {
         }
 
         // Decode bytes with specified encoding
-        let (content, _) = crate::encoding::decode_with_encoding(bytes, encoding)?;
+        let (content, _) = decode_with_encoding(bytes, encoding)?;
 
-        self.build(content.chars())
+        self.build_text(&content)
     }
 
     /// Builds the parser and parses the GEDCOM data from a string.
@@ -496,7 +1172,174 @@ impl GedcomBuilder {
             }
         }
 
-        self.build(content.chars())
+        self.build_text(content)
+    }
+
+    /// Builds the parser and parses the GEDCOM data from a file on disk.
+    ///
+    /// The file size is checked against [`max_file_size`](Self::max_file_size)
+    /// from its metadata before anything is read, and its encoding is detected
+    /// as in [`build_from_bytes`](Self::build_from_bytes).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the GEDCOM file
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GedcomError::InFile`] naming the path and wrapping the cause if:
+    /// - The file cannot be read
+    /// - The file is larger than the configured size limit
+    /// - The encoding cannot be detected or decoded
+    /// - The GEDCOM data is malformed
+    /// - Validation fails (when strict mode or validation options are enabled)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let data = GedcomBuilder::new()
+    ///     .build_from_path("tests/fixtures/simple.ged")?;
+    /// println!("Found {} individuals", data.individuals.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_from_path<P: AsRef<Path>>(self, path: P) -> Result<GedcomData, GedcomError> {
+        let path = path.as_ref();
+        self.build_from_path_inner(path)
+            .map_err(|e| GedcomError::InFile {
+                path: path.display().to_string(),
+                source: Box::new(e),
+            })
+    }
+
+    fn build_from_path_inner(self, path: &Path) -> Result<GedcomData, GedcomError> {
+        if let Some(max_size) = self.config.max_file_size {
+            let size = usize::try_from(std::fs::metadata(path)?.len()).unwrap_or(usize::MAX);
+            if size > max_size {
+                return Err(GedcomError::FileSizeLimitExceeded { size, max_size });
+            }
+        }
+
+        self.build_decoded(&std::fs::read(path)?)
+    }
+
+    /// Builds the parser and parses a memory-mapped GEDCOM file.
+    ///
+    /// The file is mapped instead of read, and UTF-8 content is parsed
+    /// straight from the mapped pages, so a multi-hundred-megabyte archive is
+    /// never copied into a `String`. Other encodings are detected as in
+    /// [`build_from_bytes`](Self::build_from_bytes) and decoded from the
+    /// mapping. Use [`build_from_path`](Self::build_from_path) to read the
+    /// file into memory instead.
+    ///
+    /// Requires the `mmap` feature to be enabled.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated or replaced in place, by this
+    /// process or any other, until the call returns. The parser reads the
+    /// mapped pages directly, so a concurrent change is undefined behavior: a
+    /// truncated file faults on access, and changed bytes can break the UTF-8
+    /// validity the parser relies on.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GedcomError::InFile`] naming the path and wrapping the cause if:
+    /// - The file cannot be opened or mapped
+    /// - The file is larger than the configured size limit
+    /// - The encoding cannot be detected or decoded
+    /// - The GEDCOM data is malformed
+    /// - Validation fails (when strict mode or validation options are enabled)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mmap")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// // SAFETY: nothing modifies the fixture while it is parsed.
+    /// let data = unsafe { GedcomBuilder::new().build_from_mmap("tests/fixtures/simple.ged")? };
+    /// println!("Found {} individuals", data.individuals.len());
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "mmap"))]
+    /// # fn main() {}
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub unsafe fn build_from_mmap<P: AsRef<Path>>(
+        self,
+        path: P,
+    ) -> Result<GedcomData, GedcomError> {
+        let path = path.as_ref();
+        // SAFETY: the caller upholds the contract documented above.
+        unsafe { self.build_from_mmap_inner(path) }.map_err(|e| GedcomError::InFile {
+            path: path.display().to_string(),
+            source: Box::new(e),
+        })
+    }
+
+    /// # Safety
+    ///
+    /// As for [`build_from_mmap`](Self::build_from_mmap).
+    #[cfg(feature = "mmap")]
+    unsafe fn build_from_mmap_inner(self, path: &Path) -> Result<GedcomData, GedcomError> {
+        let file = std::fs::File::open(path)?;
+        if let Some(max_size) = self.config.max_file_size {
+            let size = usize::try_from(file.metadata()?.len()).unwrap_or(usize::MAX);
+            if size > max_size {
+                return Err(GedcomError::FileSizeLimitExceeded { size, max_size });
+            }
+        }
+
+        // SAFETY: the map is only read, and is dropped before returning. The
+        // caller guarantees that the file is not modified meanwhile.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        self.build_decoded(&map)
+    }
+
+    /// Builds the parser and parses the GEDCOM data from a reader.
+    ///
+    /// The input is read to the end and then handled as in
+    /// [`build_from_bytes`](Self::build_from_bytes): the encoding is detected
+    /// from the whole input, and UTF-8 is parsed from the bytes read without
+    /// another copy. Use [`decode_reader`](crate::encoding::decode_reader)
+    /// to transcode a legacy file chunk
+    /// by chunk instead, with the limits of detecting from a prefix.
+    ///
+    /// [`max_file_size`](Self::max_file_size) is enforced while reading.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source of the GEDCOM bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if:
+    /// - Reading fails
+    /// - The input is larger than the configured size limit
+    /// - The encoding cannot be detected or decoded
+    /// - The GEDCOM data is malformed
+    /// - Validation fails (when strict mode or validation options are enabled)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n1 CHAR ISO-8859-1\n0 @I1@ INDI\n1 NAME Ren\xE9 /Roy/\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_reader(bytes)?;
+    /// assert_eq!(data.individuals[0].full_name().as_deref(), Some("René Roy"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_from_reader<R: Read>(self, reader: R) -> Result<GedcomData, GedcomError> {
+        let bytes = read_limited(reader, self.config.max_file_size)?;
+        self.build_decoded(&bytes)
     }
 
     /// Builds the parser and parses the GEDCOM data from a GEDZIP archive.
@@ -646,6 +1489,7 @@ impl GedcomBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::individual::Individual;
 
     #[test]
     fn test_builder_default() {
@@ -747,6 +1591,20 @@ mod tests {
             date_validation: true,
             max_file_size: Some(1000),
             preserve_formatting: false,
+            strict_utf8: true,
+            duplicate_xrefs: DuplicateXrefPolicy::Rename,
+            skip_lds_ordinances: true,
+            invisible_characters: InvisibleCharacterPolicy::Strip,
+            dialect: Dialect::Legacy,
+            keep_raw: true,
+            track_positions: true,
+            recover: true,
+            repair_encoding: true,
+            intern_strings: true,
+            #[cfg(feature = "parallel")]
+            parallel: true,
+            #[cfg(feature = "unicode-normalization")]
+            unicode_form: Some(UnicodeForm::Nfc),
         };
         let cloned = config.clone();
         assert_eq!(config.strict_mode, cloned.strict_mode);
@@ -754,6 +1612,305 @@ mod tests {
         assert_eq!(config.date_validation, cloned.date_validation);
         assert_eq!(config.max_file_size, cloned.max_file_size);
         assert_eq!(config.preserve_formatting, cloned.preserve_formatting);
+        assert_eq!(config.strict_utf8, cloned.strict_utf8);
+        assert_eq!(config.dialect, cloned.dialect);
+        assert_eq!(config.keep_raw, cloned.keep_raw);
+        assert_eq!(config.track_positions, cloned.track_positions);
+        assert_eq!(config.recover, cloned.recover);
+        assert_eq!(config.repair_encoding, cloned.repair_encoding);
+        assert_eq!(config.intern_strings, cloned.intern_strings);
+    }
+
+    #[test]
+    fn test_builder_keep_raw() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME Jane /Doe/\n2 _MARNM Smith\n0 @X1@ BOGUS\n0 TRLR";
+        let data = GedcomBuilder::new()
+            .recover(true)
+            .build_from_str(source)
+            .unwrap();
+        assert!(data.raw_records.is_empty());
+
+        let data = GedcomBuilder::new()
+            .keep_raw(true)
+            .recover(true)
+            .dialect(Dialect::RootsMagic)
+            .build_from_str(source)
+            .unwrap();
+        // The text is kept as written, before the dialect fixes, and for
+        // skipped records too.
+        assert_eq!(data.individuals[0].names.len(), 2);
+        let raw = data.raw_record("@I1@").unwrap();
+        assert_eq!(raw.text, "0 @I1@ INDI\n1 NAME Jane /Doe/\n2 _MARNM Smith\n");
+        assert_eq!((raw.line, raw.end_line), (4, 6));
+        assert_eq!(data.raw_record("@X1@").unwrap().tag, "BOGUS");
+        assert_eq!(data.raw_records.len(), 3);
+    }
+
+    #[test]
+    fn test_builder_recover() {
+        use crate::ParseWarning;
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n0 @X1@ BOGUS\n0 @I2@ INDI\n0 TRLR";
+        assert!(GedcomBuilder::new().build_from_str(source).is_err());
+
+        let data = GedcomBuilder::new()
+            .recover(true)
+            .build_from_str(source)
+            .unwrap();
+        assert_eq!(data.individuals.len(), 2);
+        assert!(matches!(
+            data.warnings[..],
+            [ParseWarning::SkippedRecord {
+                line: 6,
+                end_line: 6,
+                ..
+            }]
+        ));
+        assert_eq!(data.diagnostics()[0].code, "skipped-record");
+
+        let result = GedcomBuilder::new()
+            .recover(true)
+            .strict_mode(true)
+            .build_from_str(source);
+        assert!(matches!(
+            result,
+            Err(GedcomError::ParseError { line: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn test_builder_intern_strings() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n2 SURN Doe\n1 BIRT\n2 PLAC Salem\n1 _FLAG a\n\
+            0 @I2@ INDI\n1 NAME Jane /Doe/\n2 SURN Doe\n1 DEAT\n2 PLAC Salem\n1 _FLAG b\n\
+            0 TRLR";
+        let shared = |data: &GedcomData| {
+            let [john, jane] = &data.individuals[..] else {
+                panic!("expected two individuals");
+            };
+            let surname = |i: &Individual| i.names[0].surname.clone().unwrap();
+            let place = |i: &Individual| i.events[0].place.clone().unwrap().value.unwrap();
+            let tag = |i: &Individual| Arc::clone(&i.custom_data[0].tag);
+            [
+                Arc::ptr_eq(&surname(john), &surname(jane)),
+                Arc::ptr_eq(&place(john), &place(jane)),
+                Arc::ptr_eq(&tag(john), &tag(jane)),
+            ]
+        };
+
+        let plain = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert_eq!(shared(&plain), [false; 3]);
+
+        let interned = GedcomBuilder::new()
+            .intern_strings(true)
+            .build_from_str(source)
+            .unwrap();
+        assert_eq!(shared(&interned), [true; 3]);
+        assert_eq!(interned, plain);
+
+        let recovered = GedcomBuilder::new()
+            .intern_strings(true)
+            .recover(true)
+            .build_from_str(source)
+            .unwrap();
+        assert_eq!(shared(&recovered), [true; 3]);
+
+        #[cfg(feature = "parallel")]
+        {
+            let parallel = GedcomBuilder::new()
+                .intern_strings(true)
+                .parallel(true)
+                .build_from_str(source)
+                .unwrap();
+            assert_eq!(shared(&parallel), [true; 3]);
+        }
+    }
+
+    #[test]
+    fn test_builder_repair_encoding() {
+        use crate::encoding::repair::{EncodingIssue, EncodingIssueKind};
+
+        let mut bytes =
+            b"0 HEAD\n1 CHAR ANSEL\n0 @I1@ INDI\n1 NAME Ren\xC3\xA9 /Dupr\xC3\xA9/\n".to_vec();
+        bytes.extend_from_slice("0 @I2@ INDI\n1 NAME JosÃ© /Garc\u{ed}a/\n0 TRLR\n".as_bytes());
+
+        let data = GedcomBuilder::new()
+            .repair_encoding(true)
+            .build_from_bytes(&bytes)
+            .unwrap();
+        assert_eq!(
+            data.individuals[0].full_name().as_deref(),
+            Some("René Dupré")
+        );
+        assert_eq!(
+            data.individuals[1].full_name().as_deref(),
+            Some("José García")
+        );
+        assert_eq!(
+            data.warnings,
+            [
+                ParseWarning::Encoding(EncodingIssue {
+                    line: 2,
+                    kind: EncodingIssueKind::Mislabeled {
+                        declared: GedcomEncoding::Ansel,
+                        actual: GedcomEncoding::Utf8,
+                    },
+                }),
+                ParseWarning::Encoding(EncodingIssue {
+                    line: 6,
+                    kind: EncodingIssueKind::DoubleEncoded,
+                }),
+            ]
+        );
+        assert_eq!(data.diagnostics()[0].code, "mislabeled-encoding");
+
+        let result = GedcomBuilder::new()
+            .repair_encoding(true)
+            .strict_mode(true)
+            .build_from_bytes(&bytes);
+        assert!(matches!(result, Err(GedcomError::EncodingError(_))));
+
+        let mojibake = "0 HEAD\n1 CHAR UTF-8\n0 @I1@ INDI\n1 NAME JosÃ© /Doe/\n0 TRLR\n";
+        let path = std::env::temp_dir().join(format!("ged_io_mojibake_{}.ged", std::process::id()));
+        std::fs::write(&path, mojibake).unwrap();
+        let from_path = GedcomBuilder::new()
+            .repair_encoding(true)
+            .build_from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        let from_reader = GedcomBuilder::new()
+            .repair_encoding(true)
+            .build_from_reader(mojibake.as_bytes())
+            .unwrap();
+        for data in [from_path.unwrap(), from_reader] {
+            assert_eq!(data.individuals[0].full_name().as_deref(), Some("José Doe"));
+            assert_eq!(data.diagnostics()[0].code, "double-encoded-utf8");
+        }
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_builder_normalize_unicode() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME Zo\u{eb} /Dupre\u{301}/\n0 TRLR\n";
+        let data = GedcomBuilder::new()
+            .normalize_unicode(UnicodeForm::Nfd)
+            .keep_raw(true)
+            .build_from_str(source)
+            .unwrap();
+        assert_eq!(
+            data.individuals[0].full_name().as_deref(),
+            Some("Zoe\u{308} Dupre\u{301}")
+        );
+        // The raw text is kept as read
+        assert!(data.raw_record("@I1@").unwrap().text.contains("Zo\u{eb}"));
+
+        let data = GedcomBuilder::new()
+            .normalize_unicode(UnicodeForm::Nfc)
+            .build_from_str(source)
+            .unwrap();
+        assert_eq!(
+            data.individuals[0].full_name().as_deref(),
+            Some("Zo\u{eb} Dupr\u{e9}")
+        );
+    }
+
+    #[test]
+    fn test_build_from_path() {
+        let data = GedcomBuilder::new()
+            .build_from_path("tests/fixtures/simple.ged")
+            .unwrap();
+        assert!(!data.individuals.is_empty());
+
+        match GedcomBuilder::new()
+            .max_file_size(10)
+            .build_from_path("tests/fixtures/simple.ged")
+        {
+            Err(GedcomError::InFile { path, source }) => {
+                assert_eq!(path, "tests/fixtures/simple.ged");
+                assert!(matches!(
+                    *source,
+                    GedcomError::FileSizeLimitExceeded { max_size: 10, .. }
+                ));
+            }
+            other => panic!("Expected InFile error, got {other:?}"),
+        }
+
+        match GedcomBuilder::new().build_from_path("tests/fixtures/missing.ged") {
+            Err(GedcomError::InFile { source, .. }) => {
+                assert!(matches!(*source, GedcomError::IoError(_)));
+            }
+            other => panic!("Expected InFile error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_build_from_path_and_reader_detect_from_whole_input() {
+        // The first non-ASCII byte comes after the first few kilobytes.
+        let mut bytes = b"0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 NOTE ".to_vec();
+        bytes.extend_from_slice("x".repeat(5000).as_bytes());
+        bytes.extend_from_slice(b"\n0 @I1@ INDI\n1 NAME Ren\xE9 /Roy/\n0 TRLR\n");
+        let path =
+            std::env::temp_dir().join(format!("ged_io_late_latin1_{}.ged", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+        let from_path = GedcomBuilder::new().build_from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let from_bytes = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
+        let from_reader = GedcomBuilder::new()
+            .build_from_reader(bytes.as_slice())
+            .unwrap();
+        assert_eq!(
+            from_bytes.individuals[0].full_name().as_deref(),
+            Some("René Roy")
+        );
+        assert_eq!(from_path.unwrap(), from_bytes);
+        assert_eq!(from_reader, from_bytes);
+
+        match GedcomBuilder::new()
+            .max_file_size(10)
+            .build_from_reader(bytes.as_slice())
+        {
+            Err(GedcomError::FileSizeLimitExceeded { max_size: 10, .. }) => {}
+            other => panic!("Expected FileSizeLimitExceeded, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_build_from_mmap() {
+        // SAFETY: nothing modifies the files while they are parsed.
+        let build_from_mmap =
+            |builder: GedcomBuilder, path: &Path| unsafe { builder.build_from_mmap(path) };
+        for path in ["tests/fixtures/simple.ged", "tests/fixtures/allged.ged"] {
+            let mapped = build_from_mmap(GedcomBuilder::new(), Path::new(path)).unwrap();
+            let read = GedcomBuilder::new().build_from_path(path).unwrap();
+            assert_eq!(mapped, read, "{path}");
+        }
+
+        let latin1 = std::env::temp_dir().join("ged_io_mmap_latin1.ged");
+        std::fs::write(
+            &latin1,
+            b"0 HEAD\n1 GEDC\n2 VERS 5.5\n1 CHAR ISO-8859-1\n0 @I1@ INDI\n1 NAME Ren\xE9 /Roy/\n0 TRLR\n",
+        )
+        .unwrap();
+        let data = build_from_mmap(GedcomBuilder::new(), &latin1).unwrap();
+        std::fs::remove_file(&latin1).unwrap();
+        assert_eq!(data.individuals[0].full_name().as_deref(), Some("René Roy"));
+
+        match build_from_mmap(
+            GedcomBuilder::new().max_file_size(10),
+            Path::new("tests/fixtures/simple.ged"),
+        ) {
+            Err(GedcomError::InFile { source, .. }) => {
+                assert!(matches!(
+                    *source,
+                    GedcomError::FileSizeLimitExceeded { max_size: 10, .. }
+                ));
+            }
+            other => panic!("Expected InFile error, got {other:?}"),
+        }
     }
 
     #[test]
@@ -781,4 +1938,122 @@ mod tests {
         let cloned = builder.clone();
         assert!(cloned.config().strict_mode);
     }
+
+    #[test]
+    fn test_builder_strict_utf8_reports_offsets() {
+        let bytes = b"0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NAME A\x01\xC3/B/\n0 TRLR\n";
+
+        match GedcomBuilder::new()
+            .strict_utf8(true)
+            .build_from_bytes(bytes)
+        {
+            Err(GedcomError::EncodingError(msg)) => {
+                assert!(msg.contains("disallowed character U+0001 at byte 45"));
+                assert!(msg.contains("invalid UTF-8 sequence at byte 46"));
+            }
+            other => panic!("Expected EncodingError, got {other:?}"),
+        }
+
+        // A reader is validated on its raw bytes, BOM included.
+        let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+        with_bom.extend_from_slice(bytes);
+        match GedcomBuilder::new()
+            .strict_utf8(true)
+            .build_from_reader(with_bom.as_slice())
+        {
+            Err(GedcomError::EncodingError(msg)) => {
+                assert!(msg.contains("disallowed character U+0001 at byte 48"));
+                assert!(msg.contains("invalid UTF-8 sequence at byte 49"));
+            }
+            other => panic!("Expected EncodingError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_builder_strict_utf8_ignores_gedcom5() {
+        let bytes = b"0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR ANSEL\n0 TRLR";
+        assert!(GedcomBuilder::new()
+            .strict_utf8(true)
+            .build_from_bytes(bytes)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_builder_reports_duplicate_xrefs() {
+        use crate::types::{DuplicateXref, RecordType};
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n0 @I1@ INDI\n1 NAME Jane /Doe/\n0 TRLR";
+        let data = GedcomBuilder::new()
+            .duplicate_xrefs(DuplicateXrefPolicy::Rename)
+            .build_from_str(source)
+            .unwrap();
+        let renamed = data.individuals[1].xref.clone();
+        assert_eq!(
+            data.warnings,
+            [ParseWarning::DuplicateXref(DuplicateXref {
+                record_type: RecordType::Individual,
+                xref: "@I1@".to_string(),
+                renamed_to: renamed.clone(),
+            })]
+        );
+        let diagnostics = data.diagnostics();
+        assert_eq!(diagnostics[0].code, "duplicate-xref");
+        assert!(diagnostics[0].message.ends_with(&renamed.unwrap()));
+
+        let data = GedcomBuilder::new()
+            .duplicate_xrefs(DuplicateXrefPolicy::KeepFirst)
+            .build_from_str(source)
+            .unwrap();
+        assert_eq!(data.individuals.len(), 1);
+        assert_eq!(data.warnings[0].code(), "duplicate-xref");
+
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert!(data.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_builder_tag_handler() {
+        use crate::types::custom::UserDefinedTag;
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct Dna(Mutex<Vec<(u8, String)>>);
+
+        impl TagHandler for Dna {
+            fn handles(&self, tag: &str) -> bool {
+                tag == "_DNA"
+            }
+
+            fn handle(&self, tag: UserDefinedTag, level: u8) -> Option<UserDefinedTag> {
+                let test = tag.value.clone().unwrap_or_default();
+                self.0.lock().unwrap().push((level, test));
+                // Keep the record-level one in the custom data too.
+                (level == 0).then_some(tag)
+            }
+        }
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 _DNA Y-DNA\n2 _LAB Example\n1 NAME John /Doe/\n2 _DNA mtDNA\n\
+            1 _COLOR blue\n0 _DNA atDNA\n0 TRLR";
+        let dna = Arc::new(Dna::default());
+        let data = GedcomBuilder::new()
+            .tag_handler(dna.clone())
+            .build_from_str(source)
+            .unwrap();
+
+        assert_eq!(
+            *dna.0.lock().unwrap(),
+            [
+                (1, "Y-DNA".to_string()),
+                (2, "mtDNA".to_string()),
+                (0, "atDNA".to_string())
+            ]
+        );
+        let individual = &data.individuals[0];
+        assert_eq!(individual.custom_data.len(), 1);
+        assert_eq!(&*individual.custom_data[0].tag, "_COLOR");
+        assert!(individual.names[0].custom_data.is_empty());
+        assert_eq!(&*data.custom_data[0].tag, "_DNA");
+    }
 }
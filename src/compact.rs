@@ -0,0 +1,493 @@
+//! Releasing memory left over after editing.
+//!
+//! Removing, merging and relinking records leaves the vectors and strings of
+//! a dataset with more capacity than they use: a `Vec` keeps its allocation
+//! when records are dropped from it, and a pointer rewritten in place keeps
+//! the buffer of the longest xref it ever held. A long-running process that
+//! edits the same dataset for hours can call
+//! [`GedcomData::compact`](crate::types::GedcomData::compact) now and then to
+//! hand that memory back. For an
+//! [`IndexedGedcomData`](crate::indexed::IndexedGedcomData),
+//! [`compact`](crate::indexed::IndexedGedcomData::compact) also rebuilds the
+//! indexes, and [`InternedValues::compact`](crate::intern::InternedValues::compact)
+//! drops the strings no occurrence refers to any more.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Doe/\n0 @I2@ INDI\n0 @I3@ INDI\n0 @I4@ INDI\n0 TRLR";
+//! let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+//! data.individuals.truncate(1);
+//!
+//! assert!(data.compact() > 0);
+//! assert_eq!(data.individuals.capacity(), 1);
+//! // Nothing is left to reclaim.
+//! assert_eq!(data.compact(), 0);
+//! ```
+
+use std::mem::size_of;
+
+use crate::types::{
+    custom::UserDefinedTag,
+    date::{change_date::ChangeDate, Date},
+    event::detail::Detail,
+    family::{child::FamilyChild, Family},
+    individual::{
+        association::Association, family_link::FamilyLink, gender::Gender, name::Name, Individual,
+    },
+    multimedia::{user::UserReferenceNumber, Multimedia},
+    note::{Note, NoteRef},
+    repository::{citation::Citation as RepositoryCitation, Repository},
+    shared_note::SharedNote,
+    source::{citation::Citation, Source},
+    submission::Submission,
+    submitter::Submitter,
+    GedcomData,
+};
+
+/// Shrinks allocations to fit their contents, returning the bytes released.
+pub(crate) trait Compact {
+    fn compact(&mut self) -> usize;
+}
+
+/// Shrinks a vector without visiting its elements, returning the bytes
+/// released.
+pub(crate) fn shrink<T>(vec: &mut Vec<T>) -> usize {
+    let spare = vec.capacity() - vec.len();
+    vec.shrink_to_fit();
+    (spare - (vec.capacity() - vec.len())) * size_of::<T>()
+}
+
+impl Compact for String {
+    fn compact(&mut self) -> usize {
+        let before = self.capacity();
+        self.shrink_to_fit();
+        before - self.capacity()
+    }
+}
+
+impl<T: Compact> Compact for Vec<T> {
+    fn compact(&mut self) -> usize {
+        self.iter_mut().map(Compact::compact).sum::<usize>() + shrink(self)
+    }
+}
+
+impl<T: Compact> Compact for Option<T> {
+    fn compact(&mut self) -> usize {
+        self.as_mut().map_or(0, Compact::compact)
+    }
+}
+
+impl<T: Compact> Compact for Box<T> {
+    fn compact(&mut self) -> usize {
+        (**self).compact()
+    }
+}
+
+impl Compact for UserDefinedTag {
+    fn compact(&mut self) -> usize {
+        self.value.compact() + self.children.compact()
+    }
+}
+
+impl Compact for Note {
+    fn compact(&mut self) -> usize {
+        self.value.compact() + shrink(&mut self.translations) + self.citation.compact()
+    }
+}
+
+impl Compact for NoteRef {
+    fn compact(&mut self) -> usize {
+        match self {
+            NoteRef::Inline(note) => note.compact(),
+            NoteRef::Shared(xref) => xref.compact(),
+        }
+    }
+}
+
+impl Compact for Citation {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + shrink(&mut self.texts)
+            + self.page.compact()
+            + self.notes.compact()
+            + self.multimedia.compact()
+            + self.custom_data.compact()
+    }
+}
+
+impl Compact for RepositoryCitation {
+    fn compact(&mut self) -> usize {
+        self.xref.compact() + self.notes.compact() + self.custom_data.compact()
+    }
+}
+
+impl Compact for Multimedia {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + shrink(&mut self.files)
+            + self.title.compact()
+            + self.source_citation.compact()
+            + self.notes.compact()
+            + self.vendor_uids.compact()
+            + shrink(&mut self.external_ids)
+    }
+}
+
+impl Compact for Association {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + self.notes.compact()
+            + self.citations.compact()
+            + self.custom_data.compact()
+    }
+}
+
+impl Compact for Detail {
+    fn compact(&mut self) -> usize {
+        self.notes.compact()
+            + self.family_link.compact()
+            + shrink(&mut self.family_event_details)
+            + self.citations.compact()
+            + self.multimedia.compact()
+            + self.associations.compact()
+    }
+}
+
+impl Compact for FamilyLink {
+    fn compact(&mut self) -> usize {
+        self.xref.compact() + self.notes.compact() + self.custom_data.compact()
+    }
+}
+
+impl Compact for FamilyChild {
+    fn compact(&mut self) -> usize {
+        self.xref.compact() + self.custom_data.compact()
+    }
+}
+
+impl Compact for Name {
+    fn compact(&mut self) -> usize {
+        self.value.compact()
+            + self.notes.compact()
+            + self.source.compact()
+            + shrink(&mut self.phonetic)
+            + shrink(&mut self.romanized)
+            + shrink(&mut self.translations)
+            + self.custom_data.compact()
+    }
+}
+
+impl Compact for Date {
+    fn compact(&mut self) -> usize {
+        self.value.compact() + self.time.compact() + self.phrase.compact()
+    }
+}
+
+impl Compact for ChangeDate {
+    fn compact(&mut self) -> usize {
+        self.date.compact() + self.notes.compact()
+    }
+}
+
+impl Compact for Gender {
+    fn compact(&mut self) -> usize {
+        self.fact.compact() + self.sources.compact() + self.custom_data.compact()
+    }
+}
+
+impl Compact for UserReferenceNumber {
+    fn compact(&mut self) -> usize {
+        self.value.compact() + self.user_reference_type.compact()
+    }
+}
+
+impl Compact for Individual {
+    fn compact(&mut self) -> usize {
+        // Destructured so that a new field cannot be left out unnoticed.
+        let Individual {
+            xref,
+            names,
+            sex,
+            families,
+            attributes,
+            source,
+            events,
+            multimedia,
+            last_updated,
+            notes,
+            change_date,
+            creation_date,
+            custom_data,
+            non_events,
+            lds_ordinances,
+            associations,
+            uid,
+            vendor_uids,
+            restriction,
+            user_reference_numbers,
+            automated_record_id,
+            ancestral_file_number,
+            aliases,
+            ancestor_interest,
+            descendant_interest,
+            external_ids,
+        } = self;
+        xref.compact()
+            + names.compact()
+            + sex.compact()
+            + families.compact()
+            + shrink(attributes)
+            + source.compact()
+            + events.compact()
+            + multimedia.compact()
+            + last_updated.compact()
+            + notes.compact()
+            + change_date.compact()
+            + creation_date.compact()
+            + custom_data.compact()
+            + shrink(non_events)
+            + shrink(lds_ordinances)
+            + associations.compact()
+            + uid.compact()
+            + vendor_uids.compact()
+            + restriction.compact()
+            + user_reference_numbers.compact()
+            + automated_record_id.compact()
+            + ancestral_file_number.compact()
+            + aliases.compact()
+            + ancestor_interest.compact()
+            + descendant_interest.compact()
+            + shrink(external_ids)
+    }
+}
+
+impl Compact for Family {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + self.individual1.compact()
+            + self.individual2.compact()
+            + self.family_event.compact()
+            + self.children.compact()
+            + self.child_details.compact()
+            + self.events.compact()
+            + self.sources.compact()
+            + self.multimedia.compact()
+            + self.notes.compact()
+            + self.custom_data.compact()
+            + shrink(&mut self.non_events)
+            + shrink(&mut self.lds_ordinances)
+            + self.vendor_uids.compact()
+            + shrink(&mut self.external_ids)
+            + self.associations.compact()
+    }
+}
+
+impl Compact for Source {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + self.data.events.compact()
+            + self.data.notes.compact()
+            + self.multimedia.compact()
+            + self.notes.compact()
+            + self.repo_citations.compact()
+            + self.custom_data.compact()
+            + self.vendor_uids.compact()
+            + shrink(&mut self.external_ids)
+    }
+}
+
+impl Compact for Repository {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + self.notes.compact()
+            + shrink(&mut self.external_ids)
+            + self.custom_data.compact()
+    }
+}
+
+impl Compact for Submitter {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + shrink(&mut self.multimedia)
+            + self.notes.compact()
+            + self.custom_data.compact()
+    }
+}
+
+impl Compact for Submission {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + self.submitter_ref.compact()
+            + self.notes.compact()
+            + self.custom.compact()
+    }
+}
+
+impl Compact for SharedNote {
+    fn compact(&mut self) -> usize {
+        self.xref.compact()
+            + self.text.compact()
+            + shrink(&mut self.translations)
+            + self.source_citations.compact()
+            + shrink(&mut self.external_ids)
+            + self.custom_data.compact()
+    }
+}
+
+impl GedcomData {
+    /// Shrinks the record collections, the lists inside each record and the
+    /// pointers between records to fit their contents, and returns the
+    /// number of bytes released.
+    ///
+    /// The figure counts the spare capacity given back to the allocator; the
+    /// memory the process holds may shrink by less, depending on the
+    /// allocator. The data itself is unchanged.
+    pub fn compact(&mut self) -> usize {
+        self.submitters.compact()
+            + self.submissions.compact()
+            + self.individuals.compact()
+            + self.families.compact()
+            + self.repositories.compact()
+            + self.sources.compact()
+            + self.multimedia.compact()
+            + self.shared_notes.compact()
+            + self.custom_data.compact()
+            + shrink(&mut self.warnings)
+            + shrink(&mut self.raw_records)
+            + shrink(&mut self.positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_compact_after_edits() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n1 FAMS @F1@\n\
+            0 @I2@ INDI\n1 NAME Jane /Roe/\n1 FAMS @F1@\n\
+            0 @I3@ INDI\n1 FAMC @F1@\n0 @I4@ INDI\n1 FAMC @F1@\n\
+            0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n1 CHIL @I3@\n1 CHIL @I4@\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        data.compact();
+        let original = data.clone();
+
+        let mut long = String::with_capacity(64);
+        long.push_str("@I1@");
+        data.families[0].individual1 = Some(long);
+        data.families[0].children.pop();
+        data.individuals.pop();
+
+        let pointer = 60;
+        let child = size_of::<String>();
+        let individual = size_of::<Individual>();
+        assert_eq!(data.compact(), pointer + child + individual);
+        assert_eq!(data.families[0].individual1.as_ref().unwrap().capacity(), 4);
+        assert_eq!(data.families[0].children.capacity(), 1);
+
+        assert_eq!(data.individuals[..], original.individuals[..3]);
+        assert_eq!(data.compact(), 0);
+    }
+
+    /// The strings of an individual, down to its sex and record dates.
+    fn strings(individual: &mut Individual) -> [&mut String; 13] {
+        fn date(date: &mut Option<ChangeDate>) -> &mut Option<String> {
+            &mut date.as_mut().unwrap().date.as_mut().unwrap().value
+        }
+        let refn = &mut individual.user_reference_numbers[0];
+        [
+            &mut individual.xref,
+            &mut individual.last_updated,
+            &mut individual.uid,
+            &mut individual.restriction,
+            &mut refn.value,
+            &mut refn.user_reference_type,
+            &mut individual.automated_record_id,
+            &mut individual.ancestral_file_number,
+            &mut individual.ancestor_interest,
+            &mut individual.descendant_interest,
+            &mut individual.sex.as_mut().unwrap().fact,
+            date(&mut individual.change_date),
+            date(&mut individual.creation_date),
+        ]
+        .map(|string| string.as_mut().unwrap())
+    }
+
+    #[test]
+    fn test_compact_individual_every_field() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 RESN privacy\n1 NAME John /Doe/\n1 SEX M\n2 FACT x\n\
+            1 BIRT\n2 DATE 1900\n1 OCCU Smith\n1 NO MARR\n1 BAPL\n2 TEMP SLAKE\n\
+            1 FAMC @F1@\n1 ASSO @I2@\n2 RELA Friend\n1 ALIA @I2@\n1 ANCI @U1@\n1 DESI @U1@\n\
+            1 SOUR @S1@\n1 OBJE @M1@\n1 NOTE A note\n1 REFN 42\n2 TYPE card\n1 RIN 7\n\
+            1 AFN 1AB-2CD\n1 UID 0123\n1 _UID ABCD\n1 EXID 123\n2 TYPE http://x\n\
+            1 _COLOR red\n1 CREA\n2 DATE 1 JAN 2000\n1 CHAN\n2 DATE 2 JAN 2000\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let mut individual = data.individuals.remove(0);
+        individual.last_updated = Some("2 JAN 2000".to_string());
+
+        // The strings are checked below; every other field must be filled.
+        let Individual {
+            xref: _,
+            names,
+            sex: _,
+            families,
+            attributes,
+            source,
+            events,
+            multimedia,
+            last_updated: _,
+            notes,
+            change_date: _,
+            creation_date: _,
+            custom_data,
+            non_events,
+            lds_ordinances,
+            associations,
+            uid: _,
+            vendor_uids,
+            restriction: _,
+            user_reference_numbers: _,
+            automated_record_id: _,
+            ancestral_file_number: _,
+            aliases,
+            ancestor_interest: _,
+            descendant_interest: _,
+            external_ids,
+        } = &individual;
+        assert!(
+            !names.is_empty()
+                && !families.is_empty()
+                && !attributes.is_empty()
+                && !source.is_empty()
+                && !events.is_empty()
+                && !multimedia.is_empty()
+                && !notes.is_empty()
+                && !custom_data.is_empty()
+                && !non_events.is_empty()
+                && !lds_ordinances.is_empty()
+                && !associations.is_empty()
+                && !vendor_uids.is_empty()
+                && !aliases.is_empty()
+                && !external_ids.is_empty()
+        );
+
+        for string in strings(&mut individual) {
+            string.reserve(16);
+        }
+
+        let original = individual.clone();
+        assert!(individual.compact() >= 13 * 16);
+        assert_eq!(individual, original);
+        for string in strings(&mut individual) {
+            assert_eq!(string.capacity(), string.len(), "{string}");
+        }
+        assert_eq!(individual.compact(), 0);
+    }
+}
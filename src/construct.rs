@@ -0,0 +1,617 @@
+//! Building records from scratch.
+//!
+//! The record types mirror the GEDCOM structures field for field, which
+//! makes them awkward to fill in by hand: a name needs its full value as
+//! well as its parts, an event needs a dozen empty fields, and a citation
+//! needs its source pointer. The builders here fill in what is needed, so
+//! that programs generating GEDCOM from another database only say what they
+//! know.
+//!
+//! The builders only build the records. Links between them go both ways,
+//! so give each member of a family the matching
+//! [`child_of`](crate::construct::IndividualBuilder::child_of) or
+//! [`spouse_in`](crate::construct::IndividualBuilder::spouse_in) link, or
+//! add the records first and link them with the methods of the
+//! [`edit`](crate::edit) module.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::construct::{EventBuilder, FamilyBuilder, IndividualBuilder, SourceBuilder};
+//! use ged_io::types::{event::Event, individual::gender::GenderType, GedcomData};
+//! use ged_io::GedcomWriter;
+//!
+//! let mut data = GedcomData::default();
+//! data.add_source(SourceBuilder::new("@S1@").title("Parish register").build());
+//! data.add_individual(
+//!     IndividualBuilder::new("@I1@")
+//!         .given("John")
+//!         .surname("Doe")
+//!         .sex(GenderType::Male)
+//!         .event(
+//!             EventBuilder::new(Event::Birth)
+//!                 .date("12 MAR 1850")
+//!                 .place("Leeds, England")
+//!                 .citation("@S1@", Some("f. 12")),
+//!         )
+//!         .spouse_in("@F1@")
+//!         .build(),
+//! );
+//! data.add_family(
+//!     FamilyBuilder::new("@F1@")
+//!         .husband("@I1@")
+//!         .event(EventBuilder::new(Event::Marriage).date("1875"))
+//!         .build(),
+//! );
+//!
+//! assert_eq!(data.individuals[0].full_name().as_deref(), Some("John Doe"));
+//! let gedcom = GedcomWriter::new().write_to_string(&data).unwrap();
+//! assert!(gedcom.contains("1 NAME John /Doe/\n"));
+//! assert!(gedcom.contains("2 PLAC Leeds, England\n"));
+//! ```
+
+use std::sync::Arc;
+
+use crate::types::{
+    date::Date,
+    event::{
+        detail::{Detail, EventValue},
+        Event,
+    },
+    family::Family,
+    individual::{
+        family_link::{FamilyLink, FamilyLinkType},
+        gender::{Gender, GenderType},
+        name::Name,
+        Individual,
+    },
+    note::{Note, NoteRef},
+    place::Place,
+    repository::citation::Citation as RepositoryCitation,
+    source::{citation::Citation, Source},
+};
+
+/// Builds an [`Individual`].
+#[derive(Clone, Debug)]
+pub struct IndividualBuilder {
+    individual: Individual,
+    name: Name,
+}
+
+impl IndividualBuilder {
+    /// Starts an individual record with the given xref, such as `@I1@`.
+    #[must_use]
+    pub fn new(xref: &str) -> Self {
+        Self {
+            individual: Individual {
+                xref: Some(xref.to_string()),
+                ..Individual::default()
+            },
+            name: Name::default(),
+        }
+    }
+
+    /// Sets the given names.
+    #[must_use]
+    pub fn given(mut self, given: &str) -> Self {
+        self.name.given = Some(given.to_string());
+        self
+    }
+
+    /// Sets the surname.
+    #[must_use]
+    pub fn surname(mut self, surname: &str) -> Self {
+        self.name.surname = Some(Arc::from(surname));
+        self
+    }
+
+    /// Sets the name prefix, such as a title.
+    #[must_use]
+    pub fn name_prefix(mut self, prefix: &str) -> Self {
+        self.name.prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Sets the surname prefix, such as `van` or `de`.
+    #[must_use]
+    pub fn surname_prefix(mut self, prefix: &str) -> Self {
+        self.name.surname_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Sets the name suffix, such as `Jr.`.
+    #[must_use]
+    pub fn name_suffix(mut self, suffix: &str) -> Self {
+        self.name.suffix = Some(suffix.to_string());
+        self
+    }
+
+    /// Sets the nickname.
+    #[must_use]
+    pub fn nickname(mut self, nickname: &str) -> Self {
+        self.name.nickname = Some(nickname.to_string());
+        self
+    }
+
+    /// Sets the sex.
+    #[must_use]
+    pub fn sex(mut self, sex: GenderType) -> Self {
+        self.individual.sex = Some(Gender {
+            value: sex,
+            fact: None,
+            sources: Vec::new(),
+            custom_data: Vec::new(),
+        });
+        self
+    }
+
+    /// Adds an event, such as a birth or a census.
+    #[must_use]
+    pub fn event(mut self, event: impl Into<Detail>) -> Self {
+        self.individual.events.push(event.into());
+        self
+    }
+
+    /// Adds a citation of the source `source`, with an optional page.
+    #[must_use]
+    pub fn citation(mut self, source: &str, page: Option<&str>) -> Self {
+        self.individual.source.push(citation(source, page));
+        self
+    }
+
+    /// Adds a note.
+    #[must_use]
+    pub fn note(mut self, text: &str) -> Self {
+        self.individual.notes.push(note(text));
+        self
+    }
+
+    /// Adds a link to the family the individual is a child in.
+    #[must_use]
+    pub fn child_of(mut self, family: &str) -> Self {
+        self.individual
+            .families
+            .push(family_link(family, FamilyLinkType::Child));
+        self
+    }
+
+    /// Adds a link to a family the individual is a spouse in.
+    #[must_use]
+    pub fn spouse_in(mut self, family: &str) -> Self {
+        self.individual
+            .families
+            .push(family_link(family, FamilyLinkType::Spouse));
+        self
+    }
+
+    /// Returns the individual. The full name is made from the parts, as in
+    /// `Dr. John /van Doe/ Jr.`, unless no part was set.
+    #[must_use]
+    pub fn build(self) -> Individual {
+        let mut individual = self.individual;
+        let name = self.name;
+        let parts = [
+            name.prefix.as_deref(),
+            name.given.as_deref(),
+            name.surname.as_deref(),
+            name.surname_prefix.as_deref(),
+            name.suffix.as_deref(),
+            name.nickname.as_deref(),
+        ];
+        if parts.iter().any(Option::is_some) {
+            let surname = match (name.surname_prefix.as_deref(), name.surname.as_deref()) {
+                (Some(prefix), Some(surname)) => Some(format!("/{prefix} {surname}/")),
+                (Some(only), None) | (None, Some(only)) => Some(format!("/{only}/")),
+                (None, None) => None,
+            };
+            let value: Vec<&str> = [&name.prefix, &name.given, &surname, &name.suffix]
+                .into_iter()
+                .filter_map(Option::as_deref)
+                .collect();
+            individual.names.push(Name {
+                value: Some(value.join(" ")).filter(|v| !v.is_empty()),
+                ..name
+            });
+        }
+        individual
+    }
+}
+
+/// Builds a [`Family`].
+#[derive(Clone, Debug)]
+pub struct FamilyBuilder {
+    family: Family,
+}
+
+impl FamilyBuilder {
+    /// Starts a family record with the given xref, such as `@F1@`.
+    #[must_use]
+    pub fn new(xref: &str) -> Self {
+        Self {
+            family: Family {
+                xref: Some(xref.to_string()),
+                ..Family::default()
+            },
+        }
+    }
+
+    /// Sets the first spouse, written as `HUSB`.
+    #[must_use]
+    pub fn husband(mut self, individual: &str) -> Self {
+        self.family.individual1 = Some(individual.to_string());
+        self
+    }
+
+    /// Sets the second spouse, written as `WIFE`.
+    #[must_use]
+    pub fn wife(mut self, individual: &str) -> Self {
+        self.family.individual2 = Some(individual.to_string());
+        self
+    }
+
+    /// Adds a child, after those already added.
+    #[must_use]
+    pub fn child(mut self, individual: &str) -> Self {
+        self.family.add_child(individual.to_string());
+        self
+    }
+
+    /// Adds an event, such as a marriage.
+    #[must_use]
+    pub fn event(mut self, event: impl Into<Detail>) -> Self {
+        self.family.add_event(event.into());
+        self
+    }
+
+    /// Adds a citation of the source `source`, with an optional page.
+    #[must_use]
+    pub fn citation(mut self, source: &str, page: Option<&str>) -> Self {
+        self.family.add_source(citation(source, page));
+        self
+    }
+
+    /// Adds a note.
+    #[must_use]
+    pub fn note(mut self, text: &str) -> Self {
+        self.family.add_note(note(text));
+        self
+    }
+
+    /// Returns the family.
+    #[must_use]
+    pub fn build(self) -> Family {
+        self.family
+    }
+}
+
+/// Builds a [`Source`].
+#[derive(Clone, Debug)]
+pub struct SourceBuilder {
+    source: Source,
+}
+
+impl SourceBuilder {
+    /// Starts a source record with the given xref, such as `@S1@`.
+    #[must_use]
+    pub fn new(xref: &str) -> Self {
+        Self {
+            source: Source {
+                xref: Some(xref.to_string()),
+                ..Source::default()
+            },
+        }
+    }
+
+    /// Sets the title.
+    #[must_use]
+    pub fn title(mut self, title: &str) -> Self {
+        self.source.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the author.
+    #[must_use]
+    pub fn author(mut self, author: &str) -> Self {
+        self.source.author = Some(author.to_string());
+        self
+    }
+
+    /// Sets the short title used to sort and find the source.
+    #[must_use]
+    pub fn abbreviation(mut self, abbreviation: &str) -> Self {
+        self.source.abbreviation = Some(abbreviation.to_string());
+        self
+    }
+
+    /// Sets when and where the source was published.
+    #[must_use]
+    pub fn publication(mut self, publication: &str) -> Self {
+        self.source.publication_facts = Some(publication.to_string());
+        self
+    }
+
+    /// Sets a transcription of the source's text.
+    #[must_use]
+    pub fn text(mut self, text: &str) -> Self {
+        self.source.citation_from_source = Some(text.to_string());
+        self
+    }
+
+    /// Adds the repository `repository` that holds the source, with an
+    /// optional call number.
+    #[must_use]
+    pub fn repository(mut self, repository: &str, call_number: Option<&str>) -> Self {
+        self.source.repo_citations.push(RepositoryCitation {
+            xref: repository.to_string(),
+            call_number: call_number.map(str::to_string),
+            ..RepositoryCitation::default()
+        });
+        self
+    }
+
+    /// Adds a note.
+    #[must_use]
+    pub fn note(mut self, text: &str) -> Self {
+        self.source.notes.push(note(text));
+        self
+    }
+
+    /// Returns the source.
+    #[must_use]
+    pub fn build(self) -> Source {
+        self.source
+    }
+}
+
+/// Builds an event [`Detail`] for an individual or a family.
+#[derive(Clone, Debug)]
+pub struct EventBuilder {
+    detail: Detail,
+}
+
+impl EventBuilder {
+    /// Starts an event of the given kind.
+    #[must_use]
+    pub fn new(event: Event) -> Self {
+        Self {
+            detail: Detail {
+                event,
+                value: EventValue::None,
+                date: None,
+                place: None,
+                notes: Vec::new(),
+                family_link: None,
+                family_event_details: Vec::new(),
+                event_type: None,
+                citations: Vec::new(),
+                multimedia: Vec::new(),
+                sort_date: None,
+                associations: Vec::new(),
+                cause: None,
+                restriction: None,
+                age: None,
+                agency: None,
+                religion: None,
+                address: None,
+                phone: Vec::new(),
+                email: Vec::new(),
+                fax: Vec::new(),
+                website: Vec::new(),
+            },
+        }
+    }
+
+    /// Sets the date, in GEDCOM form, such as `12 MAR 1850` or `ABT 1900`.
+    #[must_use]
+    pub fn date(mut self, date: &str) -> Self {
+        self.detail.date = Some(Date {
+            value: Some(date.to_string()),
+            ..Date::default()
+        });
+        self
+    }
+
+    /// Sets the place, from the smallest jurisdiction to the largest.
+    #[must_use]
+    pub fn place(mut self, place: &str) -> Self {
+        self.detail.place = Some(Place {
+            value: Some(Arc::from(place)),
+            ..Place::default()
+        });
+        self
+    }
+
+    /// Sets the description written on the event line, as for a
+    /// [`Event::Residence`] or a [`Event::Event`].
+    #[must_use]
+    pub fn description(mut self, description: &str) -> Self {
+        self.detail.value = EventValue::Description(description.to_string());
+        self
+    }
+
+    /// Sets the type, which says what a [`Event::Event`] is.
+    #[must_use]
+    pub fn event_type(mut self, event_type: &str) -> Self {
+        self.detail.event_type = Some(event_type.to_string());
+        self
+    }
+
+    /// Sets the cause.
+    #[must_use]
+    pub fn cause(mut self, cause: &str) -> Self {
+        self.detail.cause = Some(cause.to_string());
+        self
+    }
+
+    /// Sets the age of the individual at the event, such as `42y`.
+    #[must_use]
+    pub fn age(mut self, age: &str) -> Self {
+        self.detail.age = Some(age.to_string());
+        self
+    }
+
+    /// Adds a citation of the source `source`, with an optional page.
+    #[must_use]
+    pub fn citation(mut self, source: &str, page: Option<&str>) -> Self {
+        self.detail.add_citation(citation(source, page));
+        self
+    }
+
+    /// Adds a note.
+    #[must_use]
+    pub fn note(mut self, text: &str) -> Self {
+        self.detail.notes.push(note(text));
+        self
+    }
+
+    /// Returns the event. An event with no description, date or place is
+    /// marked as known to have happened, which the writer records as `Y`
+    /// where the version allows it.
+    #[must_use]
+    pub fn build(self) -> Detail {
+        let mut detail = self.detail;
+        if detail.value == EventValue::None && detail.date.is_none() && detail.place.is_none() {
+            detail.value = EventValue::Asserted;
+        }
+        detail
+    }
+}
+
+impl From<EventBuilder> for Detail {
+    fn from(builder: EventBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn citation(source: &str, page: Option<&str>) -> Citation {
+    Citation {
+        xref: source.to_string(),
+        texts: Vec::new(),
+        page: page.map(str::to_string),
+        data: None,
+        notes: Vec::new(),
+        certainty_assessment: None,
+        submitter_registered_rfn: None,
+        multimedia: Vec::new(),
+        custom_data: Vec::new(),
+        event_type: None,
+        event_phrase: None,
+        role: None,
+        role_phrase: None,
+    }
+}
+
+fn note(text: &str) -> NoteRef {
+    NoteRef::Inline(Note {
+        value: Some(text.to_string()),
+        ..Note::default()
+    })
+}
+
+fn family_link(family: &str, family_link_type: FamilyLinkType) -> FamilyLink {
+    FamilyLink {
+        xref: family.to_string(),
+        family_link_type,
+        pedigree_linkage_type: None,
+        pedigree_phrase: None,
+        child_linkage_status: None,
+        status_phrase: None,
+        adopted_by: None,
+        adopted_by_phrase: None,
+        notes: Vec::new(),
+        custom_data: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::GedcomData, GedcomBuilder, GedcomWriter};
+
+    #[test]
+    fn test_name_parts() {
+        let individual = IndividualBuilder::new("@I1@")
+            .name_prefix("Dr.")
+            .given("Jan")
+            .surname_prefix("van")
+            .surname("Dijk")
+            .name_suffix("Jr.")
+            .build();
+        let name = &individual.names[0];
+        assert_eq!(name.value.as_deref(), Some("Dr. Jan /van Dijk/ Jr."));
+        assert_eq!(name.surname.as_deref(), Some("Dijk"));
+
+        let individual = IndividualBuilder::new("@I2@").build();
+        assert!(individual.names.is_empty());
+        let individual = IndividualBuilder::new("@I3@").given("Ann").build();
+        assert_eq!(individual.names[0].value.as_deref(), Some("Ann"));
+    }
+
+    #[test]
+    fn test_built_records_round_trip() {
+        let mut data = GedcomData::default();
+        data.add_source(
+            SourceBuilder::new("@S1@")
+                .title("Census 1881")
+                .author("GRO")
+                .publication("London, 1881")
+                .text("John Doe, head, 31")
+                .repository("@R1@", Some("RG11/1234"))
+                .build(),
+        );
+        data.add_individual(
+            IndividualBuilder::new("@I1@")
+                .given("John")
+                .surname("Doe")
+                .sex(GenderType::Male)
+                .event(EventBuilder::new(Event::Birth).date("1850").place("Leeds"))
+                .event(EventBuilder::new(Event::Death))
+                .event(
+                    EventBuilder::new(Event::Event)
+                        .event_type("Apprenticeship")
+                        .description("Cooper"),
+                )
+                .citation("@S1@", Some("f. 3"))
+                .spouse_in("@F1@")
+                .build(),
+        );
+        data.add_individual(
+            IndividualBuilder::new("@I2@")
+                .given("Jim")
+                .child_of("@F1@")
+                .build(),
+        );
+        data.add_family(
+            FamilyBuilder::new("@F1@")
+                .husband("@I1@")
+                .child("@I2@")
+                .event(EventBuilder::new(Event::Marriage).date("1875"))
+                .build(),
+        );
+
+        let gedcom = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(gedcom.contains("1 DEAT Y\n"), "{gedcom}");
+        let parsed = GedcomBuilder::new().build_from_str(&gedcom).unwrap();
+
+        let john = parsed.find_individual("@I1@").unwrap();
+        assert_eq!(john.birth_place(), Some("Leeds"));
+        assert_eq!(john.events[2].event_type.as_deref(), Some("Apprenticeship"));
+        assert_eq!(john.events[2].value.description(), Some("Cooper"));
+        assert_eq!(john.source[0].page.as_deref(), Some("f. 3"));
+        assert_eq!(john.families[0].family_link_type, FamilyLinkType::Spouse);
+        let family = parsed.find_family("@F1@").unwrap();
+        assert_eq!(family.children, vec!["@I2@".to_string()]);
+        assert_eq!(family.events[0].event, Event::Marriage);
+        let source = &parsed.sources[0];
+        assert_eq!(source.title.as_deref(), Some("Census 1881"));
+        assert_eq!(source.publication_facts.as_deref(), Some("London, 1881"));
+        assert_eq!(
+            source.citation_from_source.as_deref(),
+            Some("John Doe, head, 31")
+        );
+        assert_eq!(
+            source.repo_citations[0].call_number.as_deref(),
+            Some("RG11/1234")
+        );
+    }
+}
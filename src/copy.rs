@@ -0,0 +1,843 @@
+//! Copying records from one dataset into another.
+//!
+//! [`GedcomData::copy_records_into`] deep-copies selected records into
+//! another dataset under xrefs that are free there, and rewrites the
+//! pointers inside the copies to match. It is the building block for
+//! moving a branch of a tree into another file, splitting a file in two, or
+//! sharing part of a tree with a relative.
+//!
+//! Pointers to records that are not copied along are removed from the copies,
+//! so the target never gets dangling links. With
+//! [`XrefRemap::include_dependencies`](crate::copy::XrefRemap::include_dependencies),
+//! the sources, repositories, multimedia and shared note records that the
+//! copies cite are copied too.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{copy::XrefRemap, types::GedcomData, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Doe/\n1 SOUR @S1@\n1 FAMS @F1@\n\
+//!     0 @F1@ FAM\n1 HUSB @I1@\n\
+//!     0 @S1@ SOUR\n1 TITL Parish register\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let mut other = GedcomData::default();
+//! other.individuals.push(data.individuals[0].clone());
+//!
+//! let remap = XrefRemap {
+//!     include_dependencies: true,
+//!     ..XrefRemap::default()
+//! };
+//! let copied = data.copy_records_into(&mut other, &["@I1@"], &remap).unwrap();
+//! assert_eq!(copied["@I1@"], "@I1_2@");
+//! assert_eq!(copied["@S1@"], "@S1@");
+//!
+//! let copy = &other.individuals[1];
+//! assert_eq!(copy.source[0].xref, "@S1@");
+//! // The family was not copied, so the link to it is gone.
+//! assert!(copy.families.is_empty());
+//! ```
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::{
+        date::change_date::ChangeDate,
+        event::detail::Detail,
+        family::Family,
+        fresh_xref,
+        individual::{association::Association, Individual},
+        multimedia::Multimedia,
+        note::NoteRef,
+        repository::Repository,
+        shared_note::SharedNote,
+        source::citation::Citation,
+        source::Source,
+        submission::Submission,
+        submitter::Submitter,
+        GedcomData, RecordType,
+    },
+    util::is_pointer,
+    GedcomError,
+};
+
+/// How [`GedcomData::copy_records_into`] names and selects the copies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct XrefRemap {
+    /// Prepended to the xref of every copy, e.g. `B` turns `@I1@` into
+    /// `@BI1@` (default: none).
+    pub prefix: String,
+    /// Also copy the source, repository, multimedia and shared note records
+    /// that the copied records point to, and the ones those point to
+    /// (default: false).
+    pub include_dependencies: bool,
+}
+
+impl GedcomData {
+    /// Copies the records with the given xrefs into `other`, and returns the
+    /// xref of each copy keyed by the xref of its original.
+    ///
+    /// Each copy keeps its xref, with the [`prefix`](XrefRemap::prefix) of
+    /// `remap`, unless `other` already has a record with that xref; it then
+    /// gets a fresh one such as `@I1_2@`. Pointers between copied records
+    /// are rewritten to the new xrefs, and pointers to records that were not
+    /// copied are removed. Records already in `other` are not changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if there is no record with one
+    /// of the xrefs. `other` is not changed then.
+    pub fn copy_records_into(
+        &self,
+        other: &mut GedcomData,
+        xrefs: &[&str],
+        remap: &XrefRemap,
+    ) -> Result<BTreeMap<String, String>, GedcomError> {
+        let mut selected: Vec<(RecordType, String)> = Vec::new();
+        for &xref in xrefs {
+            let record_type = self
+                .record_type_of(xref)
+                .ok_or_else(|| GedcomError::InvalidFormat(format!("No record with xref {xref}")))?;
+            if !selected.iter().any(|(_, x)| x == xref) {
+                selected.push((record_type, xref.to_string()));
+            }
+        }
+
+        if remap.include_dependencies {
+            let mut i = 0;
+            while i < selected.len() {
+                let (record_type, xref) = selected[i].clone();
+                self.visit_links(record_type, &xref, &mut |link_type, link| {
+                    let dependency = matches!(
+                        link_type,
+                        RecordType::Source
+                            | RecordType::Repository
+                            | RecordType::Multimedia
+                            | RecordType::SharedNote
+                    );
+                    if dependency
+                        && self.record_type_of(link) == Some(link_type)
+                        && !selected.iter().any(|(_, x)| x == link)
+                    {
+                        selected.push((link_type, link.clone()));
+                    }
+                    true
+                });
+                i += 1;
+            }
+        }
+
+        let mut taken = other.xrefs_in_use();
+        let mut copied = BTreeMap::new();
+        for (_, xref) in &selected {
+            let candidate = format!("@{}{}@", remap.prefix, xref.trim_matches('@'));
+            let new = if taken.insert(candidate.clone()) {
+                candidate
+            } else {
+                fresh_xref(&mut taken, &candidate)
+            };
+            copied.insert(xref.clone(), new);
+        }
+
+        let mut rewrite = |_: RecordType, link: &mut String| match copied.get(link.as_str()) {
+            Some(new) => {
+                link.clone_from(new);
+                true
+            }
+            None => false,
+        };
+        for (record_type, xref) in &selected {
+            self.copy_record(other, *record_type, xref, &copied[xref], &mut rewrite);
+        }
+        Ok(copied)
+    }
+
+    /// Copies the records with the given xrefs, and the submitter of the
+    /// header, into a new dataset with the header of this one.
+    pub(crate) fn extract_records(
+        &self,
+        xrefs: &[&str],
+        include_dependencies: bool,
+    ) -> Result<GedcomData, GedcomError> {
+        let mut xrefs = xrefs.to_vec();
+        if let Some(submitter) = self
+            .header
+            .as_ref()
+            .and_then(|h| h.submitter_tag.as_deref())
+        {
+            if self.find_submitter(submitter).is_some() {
+                xrefs.push(submitter);
+            }
+        }
+        let mut extracted = GedcomData {
+            header: self.header.clone(),
+            ..GedcomData::default()
+        };
+        let remap = XrefRemap {
+            include_dependencies,
+            ..XrefRemap::default()
+        };
+        self.copy_records_into(&mut extracted, &xrefs, &remap)?;
+        Ok(extracted)
+    }
+
+    /// Returns the type of the record with the given xref.
+    fn record_type_of(&self, xref: &str) -> Option<RecordType> {
+        [
+            (
+                RecordType::Individual,
+                find(&self.individuals, xref).is_some(),
+            ),
+            (RecordType::Family, find(&self.families, xref).is_some()),
+            (RecordType::Source, find(&self.sources, xref).is_some()),
+            (
+                RecordType::Repository,
+                find(&self.repositories, xref).is_some(),
+            ),
+            (
+                RecordType::Multimedia,
+                find(&self.multimedia, xref).is_some(),
+            ),
+            (
+                RecordType::SharedNote,
+                find(&self.shared_notes, xref).is_some(),
+            ),
+            (
+                RecordType::Submitter,
+                find(&self.submitters, xref).is_some(),
+            ),
+            (
+                RecordType::Submission,
+                find(&self.submissions, xref).is_some(),
+            ),
+        ]
+        .into_iter()
+        .find_map(|(record_type, found)| found.then_some(record_type))
+    }
+
+    /// Appends a copy of the record to `other` under the xref `new`, with
+    /// `visit` applied to its pointers.
+    fn copy_record(
+        &self,
+        other: &mut GedcomData,
+        record_type: RecordType,
+        xref: &str,
+        new: &str,
+        visit: &mut Visit<'_>,
+    ) {
+        fn copy<T: Record>(
+            from: &[T],
+            to: &mut Vec<T>,
+            xref: &str,
+            new: &str,
+            visit: &mut Visit<'_>,
+        ) {
+            if let Some(record) = find(from, xref) {
+                let mut record = record.clone();
+                *record.xref_mut() = Some(new.to_string());
+                record.links(&mut Links::new(visit));
+                to.push(record);
+            }
+        }
+        let (to, v) = (other, visit);
+        match record_type {
+            RecordType::Individual => copy(&self.individuals, &mut to.individuals, xref, new, v),
+            RecordType::Family => copy(&self.families, &mut to.families, xref, new, v),
+            RecordType::Source => copy(&self.sources, &mut to.sources, xref, new, v),
+            RecordType::Repository => copy(&self.repositories, &mut to.repositories, xref, new, v),
+            RecordType::Multimedia => copy(&self.multimedia, &mut to.multimedia, xref, new, v),
+            RecordType::SharedNote => copy(&self.shared_notes, &mut to.shared_notes, xref, new, v),
+            RecordType::Submitter => copy(&self.submitters, &mut to.submitters, xref, new, v),
+            RecordType::Submission => copy(&self.submissions, &mut to.submissions, xref, new, v),
+            RecordType::Custom => {}
+        }
+    }
+
+    /// Calls `visit` with every pointer of every record.
+    pub(crate) fn visit_all_links(&mut self, visit: &mut Visit<'_>) {
+        self.visit_all_links_stamping(visit, None);
+    }
+
+    /// Calls `visit` with every pointer of every record, and gives the
+    /// records it removes a pointer from the change date `stamp`, if any.
+    pub(crate) fn visit_all_links_stamping(
+        &mut self,
+        visit: &mut Visit<'_>,
+        stamp: Option<&ChangeDate>,
+    ) {
+        self.walk_links(&mut Links::new(visit), stamp);
+    }
+
+    /// Calls `media` with every multimedia link embedded in a record, however
+    /// deep: under events, citations, names, attributes, associations and
+    /// LDS ordinances alike.
+    pub(crate) fn visit_all_media(&mut self, media: &mut dyn FnMut(&mut Multimedia)) {
+        let mut links = Links {
+            pointers: &mut |_, _| true,
+            media: Some(media),
+        };
+        self.walk_links(&mut links, None);
+    }
+
+    /// Walks the links of every record, stamping as
+    /// [`visit_all_links_stamping`](Self::visit_all_links_stamping) does.
+    fn walk_links(&mut self, links: &mut Links<'_, '_>, stamp: Option<&ChangeDate>) {
+        fn on<T: Record>(records: &mut [T], links: &mut Links<'_, '_>, stamp: Option<&ChangeDate>) {
+            let Links { pointers, media } = links;
+            for record in records {
+                let mut removed = false;
+                record.links(&mut Links {
+                    pointers: &mut |record_type, pointer| {
+                        let keep = pointers(record_type, pointer);
+                        removed |= !keep;
+                        keep
+                    },
+                    // Not `as_deref_mut`, which cannot shorten the
+                    // lifetime of the trait object.
+                    media: match media {
+                        Some(media) => Some(&mut **media),
+                        None => None,
+                    },
+                });
+                if let Some(stamp) = stamp.filter(|_| removed) {
+                    record.set_change_date(stamp.clone());
+                }
+            }
+        }
+        on(&mut self.submitters, links, stamp);
+        on(&mut self.submissions, links, stamp);
+        on(&mut self.individuals, links, stamp);
+        on(&mut self.families, links, stamp);
+        on(&mut self.repositories, links, stamp);
+        on(&mut self.sources, links, stamp);
+        on(&mut self.multimedia, links, stamp);
+        on(&mut self.shared_notes, links, stamp);
+    }
+
+    /// Calls `visit` with every pointer in a copy of the record.
+    fn visit_links(&self, record_type: RecordType, xref: &str, visit: &mut Visit<'_>) {
+        fn on<T: Record>(records: &[T], xref: &str, visit: &mut Visit<'_>) {
+            if let Some(record) = find(records, xref) {
+                record.clone().links(&mut Links::new(visit));
+            }
+        }
+        match record_type {
+            RecordType::Individual => on(&self.individuals, xref, visit),
+            RecordType::Family => on(&self.families, xref, visit),
+            RecordType::Source => on(&self.sources, xref, visit),
+            RecordType::Repository => on(&self.repositories, xref, visit),
+            RecordType::Multimedia => on(&self.multimedia, xref, visit),
+            RecordType::SharedNote => on(&self.shared_notes, xref, visit),
+            RecordType::Submitter => on(&self.submitters, xref, visit),
+            RecordType::Submission => on(&self.submissions, xref, visit),
+            RecordType::Custom => {}
+        }
+    }
+}
+
+/// Called with the type of record a pointer targets and the pointer itself,
+/// which it may rewrite. Returning false removes the pointer.
+pub(crate) type Visit<'a> = dyn FnMut(RecordType, &mut String) -> bool + 'a;
+
+/// What a walk over the links of a record calls: `pointers` with every
+/// pointer, and `media`, if any, with every multimedia link embedded in it
+/// before its own pointers.
+pub(crate) struct Links<'a, 'b> {
+    pointers: &'a mut Visit<'b>,
+    media: Option<&'a mut dyn FnMut(&mut Multimedia)>,
+}
+
+impl<'a, 'b> Links<'a, 'b> {
+    fn new(pointers: &'a mut Visit<'b>) -> Self {
+        Self {
+            pointers,
+            media: None,
+        }
+    }
+
+    fn visit(&mut self, record_type: RecordType, pointer: &mut String) -> bool {
+        (self.pointers)(record_type, pointer)
+    }
+}
+
+pub(crate) fn find<'a, T: Record>(records: &'a [T], xref: &str) -> Option<&'a T> {
+    records.iter().find(|r| r.xref() == Some(xref))
+}
+
+/// A top-level record whose pointers can be visited.
+pub(crate) trait Record: Clone {
+    fn xref(&self) -> Option<&str>;
+    fn xref_mut(&mut self) -> &mut Option<String>;
+    fn links(&mut self, links: &mut Links<'_, '_>);
+    fn change_date(&self) -> Option<&ChangeDate>;
+    fn set_change_date(&mut self, date: ChangeDate);
+}
+
+fn link(xref: &mut Option<String>, record_type: RecordType, links: &mut Links<'_, '_>) {
+    if let Some(pointer) = xref {
+        if !links.visit(record_type, pointer) {
+            *xref = None;
+        }
+    }
+}
+
+fn notes_links(notes: &mut Vec<NoteRef>, links: &mut Links<'_, '_>) {
+    notes.retain_mut(|note| match note {
+        NoteRef::Shared(xref) => links.visit(RecordType::SharedNote, xref),
+        NoteRef::Inline(_) => true,
+    });
+}
+
+fn change_date_links(change_date: Option<&mut ChangeDate>, links: &mut Links<'_, '_>) {
+    if let Some(change_date) = change_date {
+        notes_links(&mut change_date.notes, links);
+    }
+}
+
+fn citation_links(citation: &mut Citation, links: &mut Links<'_, '_>) -> bool {
+    // A `@VOID@` citation names no record, like an inline description.
+    if is_pointer(&citation.xref)
+        && citation.xref != "@VOID@"
+        && !links.visit(RecordType::Source, &mut citation.xref)
+    {
+        return false;
+    }
+    media_links(&mut citation.multimedia, links);
+    notes_links(&mut citation.notes, links);
+    true
+}
+
+fn citations_links(citations: &mut Vec<Citation>, links: &mut Links<'_, '_>) {
+    citations.retain_mut(|citation| citation_links(citation, links));
+}
+
+fn media_links(media: &mut Vec<Multimedia>, links: &mut Links<'_, '_>) {
+    media.retain_mut(|media| {
+        if let Some(ref mut on_media) = links.media {
+            on_media(media);
+        }
+        if let Some(ref mut pointer) = media.xref {
+            if !links.visit(RecordType::Multimedia, pointer) {
+                return false;
+            }
+        }
+        if let Some(ref mut citation) = media.source_citation {
+            if !citation_links(citation, links) {
+                media.source_citation = None;
+            }
+        }
+        notes_links(&mut media.notes, links);
+        true
+    });
+}
+
+fn event_links(event: &mut Detail, links: &mut Links<'_, '_>) {
+    citations_links(&mut event.citations, links);
+    media_links(&mut event.multimedia, links);
+    notes_links(&mut event.notes, links);
+    if let Some(ref mut place) = event.place {
+        citations_links(&mut place.citations, links);
+        notes_links(&mut place.notes, links);
+    }
+    if let Some(ref mut family_link) = event.family_link {
+        if links.visit(RecordType::Family, &mut family_link.xref) {
+            notes_links(&mut family_link.notes, links);
+        } else {
+            event.family_link = None;
+        }
+    }
+    associations_links(&mut event.associations, links);
+}
+
+fn associations_links(associations: &mut Vec<Association>, links: &mut Links<'_, '_>) {
+    associations.retain_mut(|association| {
+        citations_links(&mut association.citations, links);
+        notes_links(&mut association.notes, links);
+        // A `@VOID@` pointer names no record; its phrase says who is meant.
+        association.xref == "@VOID@" || links.visit(RecordType::Individual, &mut association.xref)
+    });
+}
+
+impl Record for Individual {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_ref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(date);
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        self.families.retain_mut(|f| {
+            notes_links(&mut f.notes, links);
+            links.visit(RecordType::Family, &mut f.xref)
+        });
+        citations_links(&mut self.source, links);
+        media_links(&mut self.multimedia, links);
+        notes_links(&mut self.notes, links);
+        for name in &mut self.names {
+            citations_links(&mut name.source, links);
+            notes_links(&mut name.notes, links);
+        }
+        if let Some(ref mut sex) = self.sex {
+            citations_links(&mut sex.sources, links);
+        }
+        for event in &mut self.events {
+            event_links(event, links);
+        }
+        for attribute in &mut self.attributes {
+            citations_links(&mut attribute.sources, links);
+            notes_links(&mut attribute.notes, links);
+            if let Some(ref mut place) = attribute.place {
+                citations_links(&mut place.citations, links);
+                notes_links(&mut place.notes, links);
+            }
+        }
+        for ordinance in &mut self.lds_ordinances {
+            link(&mut ordinance.family_xref, RecordType::Family, links);
+            citations_links(&mut ordinance.source_citations, links);
+            notes_links(&mut ordinance.notes, links);
+        }
+        for non_event in &mut self.non_events {
+            citations_links(&mut non_event.source_citations, links);
+            notes_links(&mut non_event.notes, links);
+        }
+        associations_links(&mut self.associations, links);
+        change_date_links(self.change_date.as_mut(), links);
+        self.aliases
+            .retain_mut(|alias| links.visit(RecordType::Individual, alias));
+        link(&mut self.ancestor_interest, RecordType::Submitter, links);
+        link(&mut self.descendant_interest, RecordType::Submitter, links);
+    }
+}
+
+impl Record for Family {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_ref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(date);
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        link(&mut self.individual1, RecordType::Individual, links);
+        link(&mut self.individual2, RecordType::Individual, links);
+        self.children
+            .retain_mut(|child| links.visit(RecordType::Individual, child));
+        self.child_details
+            .retain_mut(|child| links.visit(RecordType::Individual, &mut child.xref));
+        for event in self.family_event.iter_mut().chain(&mut self.events) {
+            event_links(event, links);
+        }
+        citations_links(&mut self.sources, links);
+        media_links(&mut self.multimedia, links);
+        notes_links(&mut self.notes, links);
+        for ordinance in &mut self.lds_ordinances {
+            citations_links(&mut ordinance.source_citations, links);
+            notes_links(&mut ordinance.notes, links);
+        }
+        for non_event in &mut self.non_events {
+            citations_links(&mut non_event.source_citations, links);
+            notes_links(&mut non_event.notes, links);
+        }
+        associations_links(&mut self.associations, links);
+        change_date_links(self.change_date.as_mut(), links);
+    }
+}
+
+impl Record for Source {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_deref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(Box::new(date));
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        media_links(&mut self.multimedia, links);
+        notes_links(&mut self.notes, links);
+        notes_links(&mut self.data.notes, links);
+        self.repo_citations.retain_mut(|r| {
+            notes_links(&mut r.notes, links);
+            links.visit(RecordType::Repository, &mut r.xref)
+        });
+        change_date_links(self.change_date.as_deref_mut(), links);
+    }
+}
+
+impl Record for Repository {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_ref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(date);
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        notes_links(&mut self.notes, links);
+        change_date_links(self.change_date.as_mut(), links);
+    }
+}
+
+impl Record for Multimedia {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_ref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(date);
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        if let Some(ref mut citation) = self.source_citation {
+            if !citation_links(citation, links) {
+                self.source_citation = None;
+            }
+        }
+        notes_links(&mut self.notes, links);
+        change_date_links(self.change_date.as_mut(), links);
+    }
+}
+
+impl Record for SharedNote {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_ref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(date);
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        citations_links(&mut self.source_citations, links);
+        change_date_links(self.change_date.as_mut(), links);
+    }
+}
+
+impl Record for Submitter {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_ref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(date);
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        self.multimedia.retain_mut(|media| match media.xref {
+            Some(ref mut pointer) => links.visit(RecordType::Multimedia, pointer),
+            None => true,
+        });
+        notes_links(&mut self.notes, links);
+        change_date_links(self.change_date.as_mut(), links);
+    }
+}
+
+impl Record for Submission {
+    fn xref(&self) -> Option<&str> {
+        self.xref.as_deref()
+    }
+
+    fn change_date(&self) -> Option<&ChangeDate> {
+        self.change_date.as_ref()
+    }
+
+    fn set_change_date(&mut self, date: ChangeDate) {
+        self.change_date = Some(date);
+    }
+
+    fn xref_mut(&mut self) -> &mut Option<String> {
+        &mut self.xref
+    }
+
+    fn links(&mut self, links: &mut Links<'_, '_>) {
+        link(&mut self.submitter_ref, RecordType::Submitter, links);
+        notes_links(&mut self.notes, links);
+        change_date_links(self.change_date.as_mut(), links);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const SAMPLE: &str = "\
+        0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+        0 @I1@ INDI\n1 NAME John /Doe/\n1 FAMS @F1@\n1 OBJE @M1@\n\
+        1 BIRT\n2 DATE 1850\n2 SOUR @S1@\n\
+        0 @I2@ INDI\n1 NAME Jane /Roe/\n1 FAMS @F1@\n\
+        0 @I3@ INDI\n1 NAME Jim /Doe/\n1 FAMC @F1@\n1 ASSO @I4@\n2 RELA Godfather\n\
+        0 @I4@ INDI\n1 NAME Joe /Bloggs/\n\
+        0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n1 CHIL @I3@\n\
+        0 @S1@ SOUR\n1 TITL Parish register\n1 REPO @R1@\n\
+        0 @R1@ REPO\n1 NAME Archive\n\
+        0 @M1@ OBJE\n1 FILE photo.jpg\n\
+        0 TRLR";
+
+    fn sample() -> GedcomData {
+        GedcomBuilder::new().build_from_str(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn test_copy_branch_rewrites_and_prunes_links() {
+        let data = sample();
+        let mut other = sample();
+        let remap = XrefRemap {
+            prefix: "B".to_string(),
+            ..XrefRemap::default()
+        };
+        let copied = data
+            .copy_records_into(&mut other, &["@F1@", "@I1@", "@I3@"], &remap)
+            .unwrap();
+        assert_eq!(copied.len(), 3);
+        assert_eq!(copied["@I1@"], "@BI1@");
+        assert_eq!(other.individuals.len(), 6);
+        assert_eq!(other.families.len(), 2);
+        assert_eq!(other.sources.len(), 1);
+
+        let family = other.find_family("@BF1@").unwrap();
+        assert_eq!(family.individual1.as_deref(), Some("@BI1@"));
+        assert_eq!(family.individual2, None);
+        assert_eq!(family.children, vec!["@BI3@".to_string()]);
+
+        let john = other.find_individual("@BI1@").unwrap();
+        assert_eq!(john.families[0].xref, "@BF1@");
+        assert!(john.multimedia.is_empty());
+        assert!(john.events[0].citations.is_empty());
+        assert!(other
+            .find_individual("@BI3@")
+            .unwrap()
+            .associations
+            .is_empty());
+
+        // The originals in the target are untouched.
+        assert_eq!(other.individuals[..4], data.individuals[..]);
+    }
+
+    #[test]
+    fn test_copy_with_shared_notes() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 SNOTE @N1@\n1 BIRT\n2 SNOTE @N2@\n\
+            0 @N1@ SNOTE Emigrated in 1850\n0 @N2@ SNOTE Born at sea\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let mut other = GedcomData::default();
+        data.copy_records_into(&mut other, &["@I1@"], &XrefRemap::default())
+            .unwrap();
+        assert!(other.individuals[0].notes.is_empty());
+        assert!(other.individuals[0].events[0].notes.is_empty());
+
+        let mut other = GedcomData::default();
+        other.shared_notes.push(data.shared_notes[1].clone());
+        let remap = XrefRemap {
+            include_dependencies: true,
+            ..XrefRemap::default()
+        };
+        data.copy_records_into(&mut other, &["@I1@"], &remap)
+            .unwrap();
+        assert_eq!(other.shared_notes.len(), 3);
+        let copy = &other.individuals[0];
+        assert_eq!(copy.notes[0].text(&other), Some("Emigrated in 1850"));
+        assert_eq!(copy.events[0].notes[0].shared_xref(), Some("@N2_2@"));
+    }
+
+    #[test]
+    fn test_copy_with_dependencies_avoids_collisions() {
+        let data = sample();
+        let mut other = GedcomData::default();
+        other.sources.push(data.sources[0].clone());
+        let remap = XrefRemap {
+            include_dependencies: true,
+            ..XrefRemap::default()
+        };
+        let copied = data
+            .copy_records_into(&mut other, &["@I1@"], &remap)
+            .unwrap();
+        assert_eq!(
+            copied.into_iter().collect::<Vec<_>>(),
+            [
+                ("@I1@", "@I1@"),
+                ("@M1@", "@M1@"),
+                ("@R1@", "@R1@"),
+                ("@S1@", "@S1_2@"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_copy_unknown_xref() {
+        let data = sample();
+        let mut other = GedcomData::default();
+        let result = data.copy_records_into(&mut other, &["@I1@", "@X9@"], &XrefRemap::default());
+        assert!(matches!(result, Err(GedcomError::InvalidFormat(_))));
+        assert!(other.is_empty());
+    }
+}
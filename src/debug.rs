@@ -102,7 +102,7 @@ impl fmt::Debug for IndividualDebug<'_> {
             debug.field("xref", xref);
         }
 
-        if let Some(ref name) = self.0.name {
+        if let Some(name) = self.0.names.first() {
             if let Some(ref value) = name.value {
                 debug.field("name", value);
             }
@@ -227,7 +227,7 @@ impl fmt::Debug for MultimediaDebug<'_> {
             debug.field("title", title);
         }
 
-        if let Some(ref file) = self.0.file {
+        if let Some(file) = self.0.file() {
             if let Some(ref value) = file.value {
                 debug.field("file", value);
             }
@@ -577,7 +577,7 @@ mod tests {
         let note = Note {
             value: Some(long_content),
             mime: None,
-            translation: None,
+            translations: Vec::new(),
             citation: None,
             language: None,
         };
@@ -593,16 +593,18 @@ mod tests {
         let name = Name {
             value: Some("John /Doe/".to_string()),
             given: Some("John".to_string()),
-            surname: Some("Doe".to_string()),
+            surname: Some("Doe".into()),
             prefix: None,
             surname_prefix: None,
-            note: None,
+            notes: Vec::new(),
             suffix: Some("Jr.".to_string()),
             nickname: None,
             source: Vec::new(),
             name_type: None,
+            type_phrase: None,
             phonetic: Vec::new(),
             romanized: Vec::new(),
+            translations: Vec::new(),
             custom_data: Vec::new(),
         };
 
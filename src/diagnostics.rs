@@ -0,0 +1,392 @@
+//! Problems found in GEDCOM data, in one machine-readable shape.
+//!
+//! Parse warnings, UTF-8 conformance issues, duplicate xrefs and errors each
+//! convert into a [`Diagnostic`](crate::diagnostics::Diagnostic) carrying a
+//! stable `code` (such as `missing-trailer`), a severity and, when known, the
+//! line, byte offset or path of the problem. With the `serde` feature enabled
+//! diagnostics serialize as flat objects, and with the `json` feature
+//! `to_sarif` writes them as a SARIF 2.1.0 log, which CI systems such as
+//! GitHub code scanning display directly.
+//!
+//! Codes never change meaning once released; new problems get new codes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::diagnostics::{Diagnostic, Severity};
+//! use ged_io::GedcomBuilder;
+//!
+//! let data = GedcomBuilder::new().build_from_str("0 HEAD\n1 GEDC\n2 VERS 5.5\n").unwrap();
+//!
+//! let diagnostics = data.diagnostics();
+//! assert_eq!(diagnostics[0].code, "missing-trailer");
+//! assert_eq!(diagnostics[0].severity, Severity::Warning);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    encoding::{
+        repair::{EncodingIssue, EncodingIssueKind},
+        Utf8Issue, Utf8IssueKind,
+    },
+    types::{DuplicateXref, GedcomData},
+    GedcomError, ParseWarning,
+};
+
+/// How serious a problem is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Severity {
+    /// The data could not be read, or breaks the specification.
+    Error,
+    /// The data was read, but something in it is probably wrong.
+    Warning,
+    /// Something worth knowing that needs no fix.
+    Note,
+}
+
+impl Severity {
+    /// Returns the SARIF `level` of the severity.
+    #[must_use]
+    pub fn sarif_level(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+/// One problem, with its stable code and location.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Diagnostic {
+    /// The stable, kebab-case identifier of the kind of problem.
+    pub code: String,
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// A human-readable description.
+    pub message: String,
+    /// The file the problem is in, when it was parsed from a path.
+    pub file: Option<String>,
+    /// The line number of the problem.
+    pub line: Option<u32>,
+    /// The byte offset of the problem in the input.
+    pub offset: Option<usize>,
+    /// The path of the problem in the record tree, such as
+    /// `$.families[0].children[1]`.
+    pub path: Option<String>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic without a location.
+    #[must_use]
+    pub fn new(code: &str, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            severity,
+            message: message.into(),
+            file: None,
+            line: None,
+            offset: None,
+            path: None,
+        }
+    }
+
+    fn at_line(mut self, line: impl TryInto<u32>) -> Self {
+        self.line = line.try_into().ok();
+        self
+    }
+}
+
+impl ParseWarning {
+    /// Returns the stable code of the warning.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseWarning::MissingTrailer => "missing-trailer",
+            ParseWarning::ContentAfterTrailer { .. } => "content-after-trailer",
+            ParseWarning::SkippedRecord { .. } => "skipped-record",
+            ParseWarning::Encoding(issue) => issue.kind.code(),
+            ParseWarning::DuplicateXref(_) => "duplicate-xref",
+        }
+    }
+}
+
+impl From<&ParseWarning> for Diagnostic {
+    fn from(warning: &ParseWarning) -> Self {
+        let diagnostic = Diagnostic::new(warning.code(), Severity::Warning, warning.to_string());
+        match warning {
+            ParseWarning::MissingTrailer => diagnostic,
+            ParseWarning::ContentAfterTrailer { line } => diagnostic.at_line(*line),
+            // The data of the record is lost.
+            ParseWarning::SkippedRecord { line, .. } => Diagnostic {
+                severity: Severity::Error,
+                ..diagnostic.at_line(*line)
+            },
+            ParseWarning::Encoding(issue) => diagnostic.at_line(issue.line),
+            ParseWarning::DuplicateXref(duplicate) => Diagnostic::from(duplicate),
+        }
+    }
+}
+
+impl GedcomError {
+    /// Returns the stable code of the error; a [`GedcomError::InFile`] has
+    /// the code of the error it wraps.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            GedcomError::ParseError { .. } => "parse-error",
+            GedcomError::InvalidFormat(_) => "invalid-format",
+            GedcomError::EncodingError(_) => "encoding-error",
+            GedcomError::InvalidTag { .. } => "invalid-tag",
+            GedcomError::UnexpectedLevel { .. } => "unexpected-level",
+            GedcomError::MissingRequiredValue { .. } => "missing-required-value",
+            GedcomError::InvalidValueFormat { .. } => "invalid-value-format",
+            GedcomError::FileSizeLimitExceeded { .. } => "file-size-limit-exceeded",
+            GedcomError::IoError(_) => "io-error",
+            GedcomError::InFile { source, .. } => source.code(),
+            GedcomError::InvalidStructure { .. } => "invalid-structure",
+        }
+    }
+}
+
+impl From<&GedcomError> for Diagnostic {
+    fn from(error: &GedcomError) -> Self {
+        if let GedcomError::InFile { path, source } = error {
+            let mut diagnostic = Diagnostic::from(source.as_ref());
+            diagnostic.file = Some(path.clone());
+            return diagnostic;
+        }
+        let diagnostic = Diagnostic::new(error.code(), Severity::Error, error.to_string());
+        match error {
+            GedcomError::ParseError { line, .. } => diagnostic.at_line(*line),
+            GedcomError::InvalidTag { line, .. }
+            | GedcomError::UnexpectedLevel { line, .. }
+            | GedcomError::MissingRequiredValue { line, .. }
+            | GedcomError::InvalidValueFormat { line, .. } => diagnostic.at_line(*line),
+            GedcomError::InvalidStructure { path, .. } => Diagnostic {
+                path: Some(path.clone()),
+                ..diagnostic
+            },
+            _ => diagnostic,
+        }
+    }
+}
+
+impl Utf8IssueKind {
+    /// Returns the stable code of the issue.
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            Utf8IssueKind::InvalidSequence => "invalid-utf8",
+            Utf8IssueKind::DisallowedCharacter(_) => "disallowed-character",
+            Utf8IssueKind::MissingFinalNewline => "missing-final-newline",
+        }
+    }
+}
+
+impl EncodingIssueKind {
+    /// Returns the stable code of the issue.
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            EncodingIssueKind::Mislabeled { .. } => "mislabeled-encoding",
+            EncodingIssueKind::MixedEncoding { .. } => "mixed-encoding",
+            EncodingIssueKind::DoubleEncoded => "double-encoded-utf8",
+        }
+    }
+}
+
+impl From<&EncodingIssue> for Diagnostic {
+    fn from(issue: &EncodingIssue) -> Self {
+        Diagnostic::new(issue.kind.code(), Severity::Warning, issue.to_string()).at_line(issue.line)
+    }
+}
+
+impl From<&Utf8Issue> for Diagnostic {
+    fn from(issue: &Utf8Issue) -> Self {
+        let severity = match issue.kind {
+            Utf8IssueKind::MissingFinalNewline => Severity::Warning,
+            _ => Severity::Error,
+        };
+        Diagnostic {
+            offset: Some(issue.offset),
+            ..Diagnostic::new(issue.kind.code(), severity, issue.to_string())
+        }
+    }
+}
+
+impl From<&DuplicateXref> for Diagnostic {
+    fn from(duplicate: &DuplicateXref) -> Self {
+        Diagnostic::new("duplicate-xref", Severity::Warning, duplicate.to_string())
+    }
+}
+
+impl GedcomData {
+    /// Returns the parse warnings of the data as diagnostics.
+    #[must_use]
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.warnings.iter().map(Diagnostic::from).collect()
+    }
+}
+
+/// Writes diagnostics as a SARIF 2.1.0 log with a single run.
+///
+/// Diagnostics without a file are reported against `artifact`, typically
+/// the path of the file that was checked. Each distinct code becomes a rule
+/// of the `ged_io` tool, lines become regions and tree paths become logical
+/// locations.
+///
+/// Requires the `json` feature to be enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::diagnostics::to_sarif;
+/// use ged_io::GedcomBuilder;
+///
+/// let data = GedcomBuilder::new().build_from_str("0 HEAD\n1 GEDC\n2 VERS 5.5\n").unwrap();
+/// let log = to_sarif(&data.diagnostics(), "family.ged");
+/// assert!(log.contains(r#""ruleId": "missing-trailer""#));
+/// ```
+#[cfg(feature = "json")]
+#[must_use]
+pub fn to_sarif(diagnostics: &[Diagnostic], artifact: &str) -> String {
+    use serde_json::{json, Value};
+
+    let mut rules: Vec<&str> = Vec::new();
+    for diagnostic in diagnostics {
+        if !rules.contains(&diagnostic.code.as_str()) {
+            rules.push(&diagnostic.code);
+        }
+    }
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut physical = json!({
+                "artifactLocation": { "uri": diagnostic.file.as_deref().unwrap_or(artifact) },
+            });
+            let mut region = serde_json::Map::new();
+            if let Some(line) = diagnostic.line {
+                region.insert("startLine".to_string(), json!(line.max(1)));
+            }
+            if let Some(offset) = diagnostic.offset {
+                region.insert("byteOffset".to_string(), json!(offset));
+            }
+            if !region.is_empty() {
+                physical["region"] = Value::Object(region);
+            }
+            let mut location = json!({ "physicalLocation": physical });
+            if let Some(path) = &diagnostic.path {
+                location["logicalLocations"] = json!([{ "fullyQualifiedName": path }]);
+            }
+            json!({
+                "ruleId": diagnostic.code,
+                "level": diagnostic.severity.sarif_level(),
+                "message": { "text": diagnostic.message },
+                "locations": [location],
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ged_io",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encoding::validate_utf8_strict, GedcomBuilder};
+
+    #[test]
+    fn test_diagnostics_from_problems() {
+        let data = GedcomBuilder::new()
+            .build_from_str("0 HEAD\n1 GEDC\n2 VERS 5.5\n0 TRLR\n0 @I1@ INDI\n")
+            .unwrap();
+        let diagnostics = data.diagnostics();
+        assert_eq!(diagnostics[0].code, "content-after-trailer");
+        assert_eq!(diagnostics[0].line, Some(5));
+
+        let error = GedcomError::InFile {
+            path: "family.ged".to_string(),
+            source: Box::new(GedcomError::InvalidTag {
+                line: 7,
+                tag: "FOO".to_string(),
+            }),
+        };
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code, "invalid-tag");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.file.as_deref(), Some("family.ged"));
+        assert_eq!(diagnostic.line, Some(7));
+
+        let error = GedcomError::InvalidStructure {
+            path: "$.families[0]".to_string(),
+            message: "missing xref".to_string(),
+        };
+        assert_eq!(
+            Diagnostic::from(&error).path.as_deref(),
+            Some("$.families[0]")
+        );
+
+        let report = validate_utf8_strict(b"0 HEAD\n\xFF");
+        let codes: Vec<Diagnostic> = report.issues.iter().map(Diagnostic::from).collect();
+        assert_eq!(codes[0].code, "invalid-utf8");
+        assert_eq!(codes[0].offset, Some(7));
+        assert_eq!(codes[1].severity, Severity::Warning);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_to_sarif() {
+        let diagnostics = [
+            Diagnostic {
+                line: Some(3),
+                ..Diagnostic::new("invalid-tag", Severity::Error, "Invalid tag")
+            },
+            Diagnostic {
+                path: Some("$.individuals[0]".to_string()),
+                ..Diagnostic::new("invalid-structure", Severity::Error, "Bad")
+            },
+            Diagnostic::new("invalid-tag", Severity::Warning, "Again"),
+        ];
+        let log: serde_json::Value =
+            serde_json::from_str(&to_sarif(&diagnostics, "tree.ged")).unwrap();
+
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            3
+        );
+        assert_eq!(
+            results[1]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "$.individuals[0]"
+        );
+        assert_eq!(results[2]["level"], "warning");
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "tree.ged"
+        );
+    }
+}
@@ -0,0 +1,463 @@
+//! Reading and writing the non-standard GEDCOM of particular applications.
+//!
+//! Genealogy programs each bend the standard in their own way: they record
+//! married names and military service under tags of their own, break long
+//! notes with `CONC` lines at the wrong level, or write a baptism as a
+//! generic `EVEN` with `TYPE Baptism`. A
+//! [`Dialect`](crate::dialect::Dialect), selected with
+//! [`GedcomBuilder::dialect`](crate::GedcomBuilder::dialect), knows the
+//! habits of one vendor and turns them into the standard structures while
+//! parsing:
+//!
+//! | Quirk | Ancestry | Family Tree Maker | Gramps | Geneanet | Legacy | RootsMagic |
+//! |-------|:-:|:-:|:-:|:-:|:-:|:-:|
+//! | `CONC`/`CONT` at the level of the text they continue | ✅ | ✅ | | ✅ | | |
+//! | `_SDATE` for the sort date `SDATE` | | | | | ✅ | ✅ |
+//! | `_MARNM` for a married name | ✅ | ✅ | | | ✅ | ✅ |
+//! | `_MILT` or `_MILI` for military service | ✅ | ✅ | | | ✅ | |
+//! | `EVEN` whose `TYPE` names a standard event | ✅ | ✅ | ✅ | ✅ | ✅ | ✅ |
+//!
+//! A married name becomes a `NAME` of type `MARRIED` and military service an
+//! `EVEN` of type `Military`. Structures a dialect cannot make sense of are
+//! left as they were.
+//!
+//! [`GedcomWriter::dialect`](crate::GedcomWriter::dialect) goes the other
+//! way, so that a file imports cleanly into the program. GEDCOM 5.5.1 output
+//! declares `CHAR UTF-8`, is wrapped at 255 characters a line, and uses the
+//! vendor tags above for married names, military service and sort dates.
+//! Family Tree Maker, Legacy and `RootsMagic` get Windows line endings, and
+//! [`Dialect::FamilySearch`](crate::dialect::Dialect::FamilySearch) gets no
+//! custom structures, which its tree import rejects.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{dialect::Dialect, types::event::Event, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 SOUR FTM\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME Jane /Doe/\n2 _MARNM Smith\n\
+//!     1 NOTE Served in the na\n1 CONC vy\n1 EVEN\n2 TYPE Burial\n0 TRLR";
+//! let data = GedcomBuilder::new()
+//!     .dialect(Dialect::FamilyTreeMaker)
+//!     .build_from_str(source)
+//!     .unwrap();
+//!
+//! let jane = &data.individuals[0];
+//! assert_eq!(jane.names[1].value.as_deref(), Some("Jane /Smith/"));
+//! assert_eq!(jane.notes[0].text(&data), Some("Served in the navy"));
+//! assert_eq!(jane.events[0].event, Event::Burial);
+//! ```
+
+use std::{borrow::Cow, fmt::Write, sync::Arc};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    tokenizer::Tokenizer,
+    types::{
+        custom::UserDefinedTag,
+        event::{detail::Detail, Event},
+        individual::{
+            name::{Name, NameType},
+            Individual,
+        },
+        GedcomData,
+    },
+};
+
+/// The application that wrote a file, for reading its non-standard GEDCOM.
+///
+/// See the [module documentation](self) for what each dialect changes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Dialect {
+    /// Standard GEDCOM, read as it is (the default).
+    #[default]
+    Standard,
+    /// Ancestry.com tree exports.
+    Ancestry,
+    /// Family Tree Maker.
+    FamilyTreeMaker,
+    /// Gramps.
+    Gramps,
+    /// Geneanet.
+    Geneanet,
+    /// Legacy Family Tree.
+    Legacy,
+    /// `RootsMagic`.
+    RootsMagic,
+    /// `FamilySearch` Family Tree.
+    FamilySearch,
+}
+
+/// The habits of a dialect.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default)]
+pub(crate) struct Quirks {
+    /// `CONC` and `CONT` lines at the level of the line they continue.
+    pub(crate) continuation_levels: bool,
+    /// `_SDATE` instead of `SDATE`.
+    pub(crate) sort_dates: bool,
+    /// `_MARNM` for married names.
+    pub(crate) married_names: bool,
+    /// `_MILT` or `_MILI` for military service.
+    pub(crate) military: bool,
+    /// `EVEN` with a `TYPE` naming a standard event.
+    pub(crate) typed_events: bool,
+    /// `CR LF` line endings.
+    pub(crate) windows_line_endings: bool,
+    /// No custom structures at all.
+    pub(crate) no_custom_data: bool,
+}
+
+/// The `TYPE` of the `EVEN` standing for military service.
+pub(crate) const MILITARY_SERVICE: &str = "Military";
+
+/// The individual events a generic `EVEN` may stand for.
+const INDIVIDUAL_EVENTS: [&str; 22] = [
+    "ADOP", "BIRT", "BAPM", "BARM", "BASM", "BLES", "BURI", "CENS", "CHR", "CHRA", "CONF", "CREM",
+    "DEAT", "EMIG", "FCOM", "GRAD", "IMMI", "NATU", "ORDN", "RETI", "PROB", "WILL",
+];
+
+/// The family events a generic `EVEN` may stand for.
+const FAMILY_EVENTS: [&str; 12] = [
+    "ANUL", "CENS", "DIV", "DIVF", "ENGA", "MARB", "MARC", "MARL", "MARR", "MARS", "RESI", "SEP",
+];
+
+impl Dialect {
+    pub(crate) fn quirks(self) -> Quirks {
+        match self {
+            Dialect::Standard => Quirks::default(),
+            Dialect::Ancestry | Dialect::FamilyTreeMaker => Quirks {
+                continuation_levels: true,
+                married_names: true,
+                military: true,
+                typed_events: true,
+                windows_line_endings: self == Dialect::FamilyTreeMaker,
+                ..Quirks::default()
+            },
+            Dialect::Gramps => Quirks {
+                typed_events: true,
+                ..Quirks::default()
+            },
+            Dialect::Geneanet => Quirks {
+                continuation_levels: true,
+                typed_events: true,
+                ..Quirks::default()
+            },
+            Dialect::Legacy => Quirks {
+                sort_dates: true,
+                married_names: true,
+                military: true,
+                typed_events: true,
+                windows_line_endings: true,
+                ..Quirks::default()
+            },
+            Dialect::RootsMagic => Quirks {
+                sort_dates: true,
+                married_names: true,
+                typed_events: true,
+                windows_line_endings: true,
+                ..Quirks::default()
+            },
+            Dialect::FamilySearch => Quirks {
+                no_custom_data: true,
+                ..Quirks::default()
+            },
+        }
+    }
+
+    /// Fixes the line-level habits of the dialect in the text of a file,
+    /// without adding or removing lines.
+    pub(crate) fn normalize_text(self, text: &str) -> Cow<'_, str> {
+        let quirks = self.quirks();
+        if !quirks.continuation_levels && !quirks.sort_dates {
+            return Cow::Borrowed(text);
+        }
+
+        let mut normalized = String::with_capacity(text.len());
+        let mut changed = false;
+        let mut text_level = None;
+        for line in text.split_inclusive('\n') {
+            let Some((level, tag_start)) = split_line(line) else {
+                normalized.push_str(line);
+                continue;
+            };
+            let rest = &line[tag_start..];
+            let tag = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+            let continuation = matches!(tag, "CONC" | "CONT");
+
+            match text_level {
+                Some(parent) if continuation && quirks.continuation_levels && level <= parent => {
+                    let indent = line.len() - line.trim_start().len();
+                    normalized.push_str(&line[..indent]);
+                    let _ = write!(normalized, "{} ", parent + 1);
+                    normalized.push_str(rest);
+                    changed = true;
+                }
+                _ if tag == "_SDATE" && quirks.sort_dates => {
+                    normalized.push_str(&line[..tag_start]);
+                    normalized.push_str(&rest[1..]);
+                    changed = true;
+                }
+                _ => normalized.push_str(line),
+            }
+            if !continuation {
+                text_level = Some(level);
+            }
+        }
+
+        if changed {
+            Cow::Owned(normalized)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// Turns the vendor structures of the dialect into standard ones.
+    pub(crate) fn normalize(self, data: &mut GedcomData) {
+        let quirks = self.quirks();
+        for individual in &mut data.individuals {
+            if quirks.married_names {
+                married_names(individual);
+            }
+            if quirks.military {
+                military_service(individual);
+            }
+            if quirks.typed_events {
+                typed_events(&mut individual.events, &INDIVIDUAL_EVENTS);
+            }
+        }
+        if quirks.typed_events {
+            for family in &mut data.families {
+                typed_events(&mut family.events, &FAMILY_EVENTS);
+            }
+        }
+    }
+}
+
+/// Returns the level of a GEDCOM line and the offset of its tag, after any
+/// xref.
+fn split_line(line: &str) -> Option<(u8, usize)> {
+    let start = line.len() - line.trim_start().len();
+    let body = &line[start..];
+    let digits = body.find(|c: char| !c.is_ascii_digit())?;
+    let level = body[..digits].parse().ok()?;
+
+    let mut offset = start + digits;
+    let skip_spaces = |offset: usize| {
+        offset + line[offset..].len() - line[offset..].trim_start_matches([' ', '\t']).len()
+    };
+    offset = skip_spaces(offset);
+    if line[offset..].starts_with('@') {
+        offset += line[offset..].find(char::is_whitespace)?;
+        offset = skip_spaces(offset);
+    }
+    Some((level, offset))
+}
+
+/// Adds a married name for each `_MARNM` under the names of `individual` or
+/// directly under it.
+fn married_names(individual: &mut Individual) {
+    let mut married = Vec::new();
+    for name in &mut individual.names {
+        let given = given_name(name);
+        take_custom(&mut name.custom_data, "_MARNM", |tag| {
+            let name = married_name(given.as_deref(), tag.value.as_deref()?)?;
+            married.push(name);
+            Some(())
+        });
+    }
+    let given = individual.names.first().and_then(given_name);
+    take_custom(&mut individual.custom_data, "_MARNM", |tag| {
+        let name = married_name(given.as_deref(), tag.value.as_deref()?)?;
+        married.push(name);
+        Some(())
+    });
+    individual.names.extend(married);
+}
+
+/// Returns the given name of `name`, from `GIVN` or its payload.
+fn given_name(name: &Name) -> Option<String> {
+    name.given.clone().or_else(|| {
+        let given = name.value.as_deref()?.split('/').next()?.trim();
+        (!given.is_empty()).then(|| given.to_string())
+    })
+}
+
+/// Builds a married name from a `_MARNM` payload, which is either a surname
+/// or a whole name with the surname between slashes.
+fn married_name(given: Option<&str>, value: &str) -> Option<Name> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let value = match given {
+        _ if value.contains('/') => value.to_string(),
+        Some(given) => format!("{given} /{value}/"),
+        None => format!("/{value}/"),
+    };
+    let surname = value.split('/').nth(1).map(str::trim);
+    let surname = surname.filter(|s| !s.is_empty()).map(Arc::from);
+    Some(Name {
+        value: Some(value),
+        surname,
+        name_type: Some(NameType::Married),
+        ..Name::default()
+    })
+}
+
+/// Turns the `_MILT` and `_MILI` structures of `individual` into `EVEN`
+/// structures of type `Military`.
+fn military_service(individual: &mut Individual) {
+    let mut events = Vec::new();
+    for tag in ["_MILT", "_MILI"] {
+        take_custom(&mut individual.custom_data, tag, |tag| {
+            let mut event = reparse_event(tag)?;
+            event
+                .event_type
+                .get_or_insert_with(|| MILITARY_SERVICE.to_string());
+            events.push(event);
+            Some(())
+        });
+    }
+    individual.events.extend(events);
+}
+
+/// Reads a custom structure as an `EVEN` with the same payload and
+/// substructures.
+fn reparse_event(tag: &UserDefinedTag) -> Option<Detail> {
+    fn write_lines(text: &mut String, level: u8, tag: &str, custom: &UserDefinedTag) {
+        let _ = write!(text, "{level} {tag}");
+        if let Some(value) = &custom.value {
+            let _ = write!(text, " {value}");
+        }
+        text.push('\n');
+        for child in &custom.children {
+            write_lines(text, level + 1, &child.tag, child);
+        }
+    }
+
+    let mut text = String::new();
+    write_lines(&mut text, 1, "EVEN", tag);
+    let mut tokenizer = Tokenizer::new(text.chars());
+    tokenizer.next_token().ok()?;
+    tokenizer.next_token().ok()?;
+    Detail::new(&mut tokenizer, 1, "EVEN").ok()
+}
+
+/// Gives each generic `EVEN` whose `TYPE` names one of `tags`, by tag or by
+/// name, the event it names.
+fn typed_events(events: &mut [Detail], tags: &[&str]) {
+    for event in events.iter_mut().filter(|e| e.event == Event::Event) {
+        let Some(kind) = event.event_type.as_deref() else {
+            continue;
+        };
+        let kind: String = kind.chars().filter(|c| !c.is_whitespace()).collect();
+        let named = tags.iter().map(|tag| Detail::from_tag(tag)).find(|named| {
+            kind.eq_ignore_ascii_case(&format!("{named:?}"))
+                || kind.eq_ignore_ascii_case(crate::writer::event_to_tag(named))
+        });
+        if let Some(named) = named {
+            event.event = named;
+            event.event_type = None;
+        }
+    }
+}
+
+/// Removes the custom structures tagged `tag` that `take` accepts.
+#[allow(clippy::vec_box)] // The type of the `custom_data` fields.
+fn take_custom<F>(custom_data: &mut Vec<Box<UserDefinedTag>>, tag: &str, mut take: F)
+where
+    F: FnMut(&UserDefinedTag) -> Option<()>,
+{
+    custom_data.retain(|custom| &*custom.tag != tag || take(custom).is_none());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    fn build(dialect: Dialect, records: &str) -> GedcomData {
+        let source = format!("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n{records}0 TRLR\n");
+        GedcomBuilder::new()
+            .dialect(dialect)
+            .build_from_str(&source)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        let text = "0 @N1@ NOTE First\r\n1 CONC  second\r\n1 CONT third\r\n\
+            0 @I1@ INDI\r\n1 BIRT\r\n2 _SDATE 1900\r\n";
+        assert_eq!(
+            Dialect::Ancestry.normalize_text(text),
+            "0 @N1@ NOTE First\r\n1 CONC  second\r\n1 CONT third\r\n\
+            0 @I1@ INDI\r\n1 BIRT\r\n2 _SDATE 1900\r\n"
+        );
+        assert_eq!(
+            Dialect::FamilyTreeMaker.normalize_text("1 NOTE First\n1 CONC  second\n1 CONT third\n"),
+            "1 NOTE First\n2 CONC  second\n2 CONT third\n"
+        );
+        assert_eq!(
+            Dialect::RootsMagic.normalize_text(text),
+            "0 @N1@ NOTE First\r\n1 CONC  second\r\n1 CONT third\r\n\
+            0 @I1@ INDI\r\n1 BIRT\r\n2 SDATE 1900\r\n"
+        );
+        assert!(matches!(
+            Dialect::Standard.normalize_text(text),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_married_names() {
+        let records = "0 @I1@ INDI\n1 NAME Jane /Doe/\n2 _MARNM Smith\n\
+            1 _MARNM Jane /Brown/\n1 _MARNM\n";
+        let data = build(Dialect::Legacy, records);
+        let names = &data.individuals[0].names;
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[1].value.as_deref(), Some("Jane /Smith/"));
+        assert_eq!(names[1].surname.as_deref(), Some("Smith"));
+        assert_eq!(names[1].name_type, Some(NameType::Married));
+        assert_eq!(names[2].value.as_deref(), Some("Jane /Brown/"));
+        assert!(names[0].custom_data.is_empty());
+        // The empty one says nothing and is kept.
+        assert_eq!(data.individuals[0].custom_data.len(), 1);
+
+        let data = build(Dialect::Gramps, records);
+        assert_eq!(data.individuals[0].names.len(), 1);
+    }
+
+    #[test]
+    fn test_military_service_and_typed_events() {
+        let records = "0 @I1@ INDI\n1 _MILT Navy\n2 DATE 1942\n2 SOUR @S1@\n\
+            1 EVEN\n2 TYPE Bar Mitzvah\n1 EVEN\n2 TYPE Graduation party\n\
+            0 @F1@ FAM\n1 EVEN\n2 TYPE marr\n0 @S1@ SOUR\n";
+        let data = build(Dialect::Ancestry, records);
+        let events = &data.individuals[0].events;
+        assert_eq!(events[0].event, Event::BarMitzvah);
+        assert_eq!(events[0].event_type, None);
+        assert_eq!(events[1].event, Event::Event);
+        assert_eq!(events[1].event_type.as_deref(), Some("Graduation party"));
+
+        let service = &events[2];
+        assert_eq!(service.event, Event::Event);
+        assert_eq!(service.event_type.as_deref(), Some("Military"));
+        assert_eq!(service.value.description(), Some("Navy"));
+        assert_eq!(
+            service.date.as_ref().unwrap().value.as_deref(),
+            Some("1942")
+        );
+        assert_eq!(service.citations[0].xref, "@S1@");
+        assert!(data.individuals[0].custom_data.is_empty());
+
+        assert_eq!(data.families[0].events[0].event, Event::Marriage);
+
+        let data = build(Dialect::Standard, records);
+        assert_eq!(data.individuals[0].events[0].event, Event::Event);
+        assert_eq!(&*data.individuals[0].custom_data[0].tag, "_MILT");
+    }
+}
@@ -0,0 +1,393 @@
+//! Differences between two GEDCOM datasets.
+//!
+//! [`diff`](crate::diff::diff) compares records by xref and lists the records added, removed
+//! or modified between an old and a new dataset. Changes within a record
+//! are given per field, keyed by a tag path such as `BIRT.DATE`; a tag
+//! repeated among its siblings is numbered from its second occurrence, as
+//! in `RESI[2].PLAC`. Records are compared in the canonical form hashed by
+//! [`GedcomData::digest`], so differences in line endings, line wrapping or
+//! encoding are not reported.
+//!
+//! [`Changeset::to_unified`](crate::diff::Changeset::to_unified) renders the
+//! changes as text resembling a unified diff, for people reviewing a sync
+//! between applications, and [`merge3`](crate::diff::merge3) combines two
+//! datasets edited from a common base.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{diff::{diff, ChangeKind}, GedcomBuilder};
+//!
+//! let old = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1850\n0 TRLR";
+//! let new = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1851\n0 @I2@ INDI\n0 TRLR";
+//! let old = GedcomBuilder::new().build_from_str(old).unwrap();
+//! let new = GedcomBuilder::new().build_from_str(new).unwrap();
+//!
+//! let changes = diff(&old, &new);
+//! assert_eq!(changes.records.len(), 2);
+//! assert_eq!(changes.records[0].kind, ChangeKind::Modified);
+//! assert_eq!(changes.records[0].fields[0].path, "BIRT.DATE");
+//! assert_eq!(changes.records[1].kind, ChangeKind::Added);
+//!
+//! assert!(changes.to_unified().contains("-BIRT.DATE 1850\n+BIRT.DATE 1851\n"));
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{types::GedcomData, GedcomWriter};
+
+pub mod three_way;
+
+pub use three_way::{merge3, Conflict, ConflictKind, ThreeWayMerge};
+
+/// How a record differs between the two datasets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ChangeKind {
+    /// The record exists only in the new dataset.
+    Added,
+    /// The record exists only in the old dataset.
+    Removed,
+    /// The record exists in both, with different content.
+    Modified,
+}
+
+/// A change to one field of a record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FieldChange {
+    /// The tag path of the field within its record, e.g. `BIRT.DATE`.
+    pub path: String,
+    /// The value in the old dataset, if the field existed there. Fields
+    /// without a payload have an empty value.
+    pub old: Option<String>,
+    /// The value in the new dataset, if the field exists there.
+    pub new: Option<String>,
+}
+
+/// A record that differs between the two datasets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordChange {
+    /// The xref of the record, or its tag for the header.
+    pub key: String,
+    /// The record tag, e.g. `INDI` or `FAM`.
+    pub tag: String,
+    /// Whether the record was added, removed or modified.
+    pub kind: ChangeKind,
+    /// The changed fields, in record order. Added and removed records list
+    /// all of their fields.
+    pub fields: Vec<FieldChange>,
+}
+
+/// The differences between two datasets, as returned by [`diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Changeset {
+    /// Changed records: those of the old dataset first, in its order, then
+    /// those added by the new one.
+    pub records: Vec<RecordChange>,
+}
+
+impl Changeset {
+    /// Returns true if the datasets have the same content.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the changed records of the given kind.
+    pub fn of_kind(&self, kind: ChangeKind) -> impl Iterator<Item = &RecordChange> {
+        self.records.iter().filter(move |r| r.kind == kind)
+    }
+
+    /// Renders the changes as text resembling a unified diff.
+    ///
+    /// Each record starts with a `@@ key tag @@` line, followed by a `-`
+    /// line per old field value and a `+` line per new one. Multi-line
+    /// values are shown with escaped line breaks.
+    #[must_use]
+    pub fn to_unified(&self) -> String {
+        let mut out = String::new();
+        for record in &self.records {
+            let _ = writeln!(out, "@@ {} {} @@", record.key, record.tag);
+            for field in &record.fields {
+                for (sign, value) in [('-', &field.old), ('+', &field.new)] {
+                    if let Some(value) = value {
+                        let _ = write!(out, "{sign}{}", field.path);
+                        if !value.is_empty() {
+                            let _ = write!(out, " {}", value.replace('\n', "\\n"));
+                        }
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Compares two datasets record by record.
+#[must_use]
+pub fn diff(old: &GedcomData, new: &GedcomData) -> Changeset {
+    let old_records = canonical_records(old);
+    let new_records = canonical_records(new);
+    let new_by_key: HashMap<&str, &Record> =
+        new_records.iter().map(|r| (r.key.as_str(), r)).collect();
+    let old_keys: HashSet<&str> = old_records.iter().map(|r| r.key.as_str()).collect();
+
+    let mut records = Vec::new();
+    for record in &old_records {
+        match new_by_key.get(record.key.as_str()) {
+            None => records.push(record.whole(ChangeKind::Removed)),
+            Some(other) => {
+                let fields = field_changes(&record.all_fields(), &other.all_fields());
+                if !fields.is_empty() || record.tag != other.tag {
+                    records.push(RecordChange {
+                        key: record.key.clone(),
+                        tag: other.tag.clone(),
+                        kind: ChangeKind::Modified,
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    records.extend(
+        new_records
+            .iter()
+            .filter(|r| !old_keys.contains(r.key.as_str()))
+            .map(|r| r.whole(ChangeKind::Added)),
+    );
+    Changeset { records }
+}
+
+/// A top-level record flattened into tag paths and values.
+struct Record {
+    key: String,
+    tag: String,
+    /// The payload of the record line, such as the text of a shared note.
+    value: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Record {
+    /// Returns the fields, preceded by the record payload keyed by the
+    /// record tag if there is one.
+    fn all_fields(&self) -> Vec<(String, String)> {
+        let value = (!self.value.is_empty()).then(|| (self.tag.clone(), self.value.clone()));
+        value
+            .into_iter()
+            .chain(self.fields.iter().cloned())
+            .collect()
+    }
+
+    fn whole(&self, kind: ChangeKind) -> RecordChange {
+        let fields = self
+            .all_fields()
+            .into_iter()
+            .map(|(path, value)| {
+                let value = Some(value);
+                let (old, new) = match kind {
+                    ChangeKind::Removed => (value, None),
+                    _ => (None, value),
+                };
+                FieldChange { path, old, new }
+            })
+            .collect();
+        RecordChange {
+            key: self.key.clone(),
+            tag: self.tag.clone(),
+            kind,
+            fields,
+        }
+    }
+}
+
+/// Flattens the canonical form of `data` into records, without the trailer.
+fn canonical_records(data: &GedcomData) -> Vec<Record> {
+    let mut text = String::new();
+    // Writing to a string never fails.
+    let _ = GedcomWriter::canonical().write_to(&mut text, data);
+
+    let mut records: Vec<Record> = Vec::new();
+    let mut keys: HashSet<String> = HashSet::new();
+    // The path segment at each level, and how often each tag was seen
+    // under the current parent at that level.
+    let mut segments: Vec<String> = Vec::new();
+    let mut seen: Vec<HashMap<String, usize>> = Vec::new();
+
+    for line in text.lines() {
+        let mut parts = line.splitn(2, ' ');
+        let Some(level) = parts.next().and_then(|l| l.parse::<usize>().ok()) else {
+            continue;
+        };
+        let rest = parts.next().unwrap_or_default();
+        let (xref, rest) = match rest.strip_prefix('@') {
+            Some(_) if level == 0 => rest
+                .split_once(' ')
+                .map_or((None, rest), |(x, r)| (Some(x), r)),
+            _ => (None, rest),
+        };
+        let (tag, value) = rest.split_once(' ').unwrap_or((rest, ""));
+
+        if level == 0 {
+            if tag == "TRLR" {
+                break;
+            }
+            let mut key = xref.unwrap_or(tag).to_string();
+            let mut n = 2;
+            while !keys.insert(key.clone()) {
+                key = format!("{}#{n}", xref.unwrap_or(tag));
+                n += 1;
+            }
+            records.push(Record {
+                key,
+                tag: tag.to_string(),
+                value: value.to_string(),
+                fields: Vec::new(),
+            });
+            segments.clear();
+            seen = vec![HashMap::new()];
+            continue;
+        }
+        let Some(record) = records.last_mut() else {
+            continue;
+        };
+        if matches!(tag, "CONT" | "CONC") {
+            let last = match record.fields.last_mut() {
+                Some((_, last)) => last,
+                None => &mut record.value,
+            };
+            if tag == "CONT" {
+                last.push('\n');
+            }
+            last.push_str(value);
+            continue;
+        }
+
+        segments.truncate(level - 1);
+        seen.truncate(level);
+        seen.resize_with(level, HashMap::new);
+        let count = seen[level - 1].entry(tag.to_string()).or_insert(0);
+        *count += 1;
+        segments.push(if *count == 1 {
+            tag.to_string()
+        } else {
+            format!("{tag}[{count}]")
+        });
+        seen.push(HashMap::new());
+        record.fields.push((segments.join("."), value.to_string()));
+    }
+    records
+}
+
+/// Lists the fields whose values differ, in the order of `old` and then of
+/// the fields only in `new`.
+fn field_changes(old: &[(String, String)], new: &[(String, String)]) -> Vec<FieldChange> {
+    let new_values: HashMap<&str, &str> =
+        new.iter().map(|(p, v)| (p.as_str(), v.as_str())).collect();
+    let old_paths: HashSet<&str> = old.iter().map(|(p, _)| p.as_str()).collect();
+
+    let mut changes: Vec<FieldChange> = old
+        .iter()
+        .filter(|(path, value)| new_values.get(path.as_str()) != Some(&value.as_str()))
+        .map(|(path, value)| FieldChange {
+            path: path.clone(),
+            old: Some(value.clone()),
+            new: new_values.get(path.as_str()).map(|v| (*v).to_string()),
+        })
+        .collect();
+    changes.extend(
+        new.iter()
+            .filter(|(path, _)| !old_paths.contains(path.as_str()))
+            .map(|(path, value)| FieldChange {
+                path: path.clone(),
+                old: None,
+                new: Some(value.clone()),
+            }),
+    );
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    fn parse(source: &str) -> GedcomData {
+        GedcomBuilder::new().build_from_str(source).unwrap()
+    }
+
+    #[test]
+    fn test_diff_identical_and_reformatted() {
+        let lf = parse(
+            "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NOTE Line one\n2 CONT Line two\n0 TRLR",
+        );
+        let crlf = parse("0 HEAD\r\n1 GEDC\r\n2 VERS 5.5.1\r\n0 @I1@ INDI\r\n1 NOTE Line \r\n2 CONC one\r\n2 CONT Line two\r\n0 TRLR");
+        assert!(diff(&lf, &lf).is_empty());
+        assert!(diff(&lf, &crlf).is_empty());
+        assert_eq!(diff(&lf, &crlf).to_unified(), "");
+    }
+
+    #[test]
+    fn test_diff_records_and_fields() {
+        let old = parse(
+            "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+             0 @I1@ INDI\n1 NAME Ann /Roe/\n1 RESI\n2 PLAC York\n1 RESI\n2 PLAC Leeds\n\
+             0 @I2@ INDI\n1 NAME Bob /Roe/\n\
+             0 TRLR",
+        );
+        let new = parse(
+            "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+             0 @I1@ INDI\n1 NAME Ann /Roe/\n1 SEX F\n1 RESI\n2 PLAC York\n1 RESI\n2 PLAC Hull\n\
+             0 @F1@ FAM\n1 WIFE @I1@\n\
+             0 TRLR",
+        );
+        let changes = diff(&old, &new);
+        let kinds: Vec<_> = changes
+            .records
+            .iter()
+            .map(|r| (r.key.as_str(), r.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("@I1@", ChangeKind::Modified),
+                ("@I2@", ChangeKind::Removed),
+                ("@F1@", ChangeKind::Added),
+            ]
+        );
+
+        let modified = &changes.records[0];
+        assert_eq!(
+            modified.fields,
+            vec![
+                FieldChange {
+                    path: "RESI[2].PLAC".to_string(),
+                    old: Some("Leeds".to_string()),
+                    new: Some("Hull".to_string()),
+                },
+                FieldChange {
+                    path: "SEX".to_string(),
+                    old: None,
+                    new: Some("F".to_string()),
+                },
+            ]
+        );
+        assert_eq!(changes.of_kind(ChangeKind::Removed).count(), 1);
+
+        let text = changes.to_unified();
+        assert!(
+            text.starts_with("@@ @I1@ INDI @@\n-RESI[2].PLAC Leeds\n+RESI[2].PLAC Hull\n+SEX F\n")
+        );
+        assert!(text.contains("@@ @I2@ INDI @@\n-NAME Bob /Roe/\n"));
+        assert!(text.ends_with("@@ @F1@ FAM @@\n+WIFE @I1@\n"));
+    }
+}
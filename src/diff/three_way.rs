@@ -0,0 +1,440 @@
+//! Three-way merges of GEDCOM datasets.
+//!
+//! [`merge3`] combines two datasets edited independently from a common
+//! base, the way version control systems merge branches: a change made on
+//! one side only is taken as is, and a field changed differently on both
+//! sides is a [`Conflict`]. Records are matched by xref and fields by the
+//! tag paths of [`diff`](super::diff), so both sides must keep the xrefs of
+//! the base.
+//!
+//! Conflicts are resolved by a [`MergePolicy`] so that the merge always
+//! produces a dataset, and are listed for a person to review.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{diff::{merge3, ConflictKind}, merge::MergePolicy, GedcomBuilder};
+//!
+//! let parse = |source: &str| GedcomBuilder::new().build_from_str(source).unwrap();
+//! let base = parse("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1850\n0 TRLR");
+//! let ours = parse("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n1 SEX M\n1 BIRT\n2 DATE 1851\n0 TRLR");
+//! let theirs = parse("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1852\n2 PLAC York\n0 TRLR");
+//!
+//! let result = merge3(&base, &ours, &theirs, MergePolicy::PreferFirst).unwrap();
+//! let john = &result.merged.individuals[0];
+//! assert!(john.sex.is_some());
+//! assert!(john.events[0].place.is_some());
+//!
+//! assert_eq!(result.conflicts.len(), 1);
+//! assert_eq!(result.conflicts[0].kind, ConflictKind::BothModified);
+//! assert_eq!(result.conflicts[0].path.as_deref(), Some("BIRT.DATE"));
+//! assert_eq!(result.conflicts[0].theirs.as_deref(), Some("1852"));
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{canonical_records, Record};
+use crate::{merge::MergePolicy, types::GedcomData, GedcomBuilder, GedcomError};
+
+/// How the two sides of a merge disagree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConflictKind {
+    /// Both sides changed the value differently.
+    BothModified,
+    /// Both sides added a different value where the base had none.
+    BothAdded,
+    /// One side changed the value or record, the other removed it.
+    ModifiedAndRemoved,
+}
+
+/// A change that could not be merged automatically.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Conflict {
+    /// The xref of the record, or its tag for the header.
+    pub key: String,
+    /// The record tag, e.g. `INDI` or `FAM`.
+    pub tag: String,
+    /// How the sides disagree.
+    pub kind: ConflictKind,
+    /// The tag path of the field, or `None` if the conflict is about the
+    /// whole record.
+    pub path: Option<String>,
+    /// The value in the base, if the field existed there.
+    pub base: Option<String>,
+    /// The value on our side, if the field exists there.
+    pub ours: Option<String>,
+    /// The value on their side, if the field exists there.
+    pub theirs: Option<String>,
+}
+
+/// The outcome of [`merge3`].
+#[derive(Clone, Debug)]
+pub struct ThreeWayMerge {
+    /// The merged dataset, with conflicts resolved by the policy.
+    pub merged: GedcomData,
+    /// The conflicts, in record order.
+    pub conflicts: Vec<Conflict>,
+}
+
+impl ThreeWayMerge {
+    /// Returns true if the two sides had no conflicting changes.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merges the changes made to `base` in `ours` and in `theirs`.
+///
+/// Records come in the order of `ours`, followed by those added in
+/// `theirs`. Within a record, fields added in `theirs` come after their
+/// siblings. Conflicting values are taken from `ours` under
+/// [`MergePolicy::PreferFirst`] and from `theirs` under
+/// [`MergePolicy::PreferSecond`].
+///
+/// # Errors
+///
+/// Returns an error if the merged records cannot be read back, which can
+/// happen when the merge combines structures that are not valid together.
+pub fn merge3(
+    base: &GedcomData,
+    ours: &GedcomData,
+    theirs: &GedcomData,
+    policy: MergePolicy,
+) -> Result<ThreeWayMerge, GedcomError> {
+    let base = canonical_records(base);
+    let ours = canonical_records(ours);
+    let theirs = canonical_records(theirs);
+    let by_key = |records: &[Record]| -> HashMap<String, usize> {
+        records
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.key.clone(), i))
+            .collect()
+    };
+    let (base_keys, our_keys, their_keys) = (by_key(&base), by_key(&ours), by_key(&theirs));
+
+    let mut merger = Merger {
+        policy,
+        conflicts: Vec::new(),
+    };
+    let mut text = String::new();
+    let keys = ours.iter().map(|r| &r.key).chain(
+        theirs
+            .iter()
+            .map(|r| &r.key)
+            .filter(|key| !our_keys.contains_key(*key)),
+    );
+    let removed_by_ours = base
+        .iter()
+        .map(|r| &r.key)
+        .filter(|key| !our_keys.contains_key(*key));
+    let mut merged_keys = HashSet::new();
+    for key in keys.chain(removed_by_ours) {
+        if !merged_keys.insert(key) {
+            continue;
+        }
+        let base = base_keys.get(key).map(|&i| &base[i]);
+        let ours = our_keys.get(key).map(|&i| &ours[i]);
+        let theirs = their_keys.get(key).map(|&i| &theirs[i]);
+        if let Some(record) = merger.record(base, ours, theirs) {
+            write_record(&mut text, &record);
+        }
+    }
+    text.push_str("0 TRLR\n");
+
+    Ok(ThreeWayMerge {
+        merged: GedcomBuilder::new().build_from_str(&text)?,
+        conflicts: merger.conflicts,
+    })
+}
+
+/// The fields of a merged record, in tag path form.
+struct Merged {
+    key: String,
+    tag: String,
+    fields: Vec<(String, String)>,
+}
+
+struct Merger {
+    policy: MergePolicy,
+    conflicts: Vec<Conflict>,
+}
+
+impl Merger {
+    fn record(
+        &mut self,
+        base: Option<&Record>,
+        ours: Option<&Record>,
+        theirs: Option<&Record>,
+    ) -> Option<Merged> {
+        let (key, tag) = ours.or(theirs).or(base).map(|r| (&r.key, &r.tag))?;
+        let merged = |record: &Record| Merged {
+            key: record.key.clone(),
+            tag: record.tag.clone(),
+            fields: record.all_fields(),
+        };
+        let state = |record: Option<&Record>| record.map(|r| (r.tag.clone(), r.all_fields()));
+        let (base_state, our_state, their_state) = (state(base), state(ours), state(theirs));
+
+        // Changes on one side only, or the same change on both sides.
+        match resolve(
+            base_state.as_ref(),
+            our_state.as_ref(),
+            their_state.as_ref(),
+        ) {
+            Ok(Some(_)) if our_state == their_state || base_state == their_state => {
+                return ours.map(merged);
+            }
+            Ok(Some(_)) => return theirs.map(merged),
+            Ok(None) => return None,
+            Err(_) => {}
+        }
+
+        let (Some(ours), Some(theirs)) = (ours, theirs) else {
+            self.conflicts.push(Conflict {
+                key: key.clone(),
+                tag: tag.clone(),
+                kind: ConflictKind::ModifiedAndRemoved,
+                path: None,
+                base: None,
+                ours: None,
+                theirs: None,
+            });
+            return self.prefer(ours, theirs).map(merged);
+        };
+        if ours.tag != theirs.tag {
+            self.conflicts.push(Conflict {
+                key: key.clone(),
+                tag: tag.clone(),
+                kind: if base.is_some() {
+                    ConflictKind::BothModified
+                } else {
+                    ConflictKind::BothAdded
+                },
+                path: None,
+                base: base.map(|r| r.tag.clone()),
+                ours: Some(ours.tag.clone()),
+                theirs: Some(theirs.tag.clone()),
+            });
+            return self.prefer(Some(ours), Some(theirs)).map(merged);
+        }
+
+        let fields = |state: Option<(String, Vec<(String, String)>)>| {
+            state.map(|(_, fields)| fields).unwrap_or_default()
+        };
+        Some(Merged {
+            key: ours.key.clone(),
+            tag: ours.tag.clone(),
+            fields: self.fields(
+                ours,
+                &fields(base_state),
+                &fields(our_state),
+                &fields(their_state),
+            ),
+        })
+    }
+
+    /// Merges the fields of a record present on both sides.
+    fn fields(
+        &mut self,
+        record: &Record,
+        base: &[(String, String)],
+        ours: &[(String, String)],
+        theirs: &[(String, String)],
+    ) -> Vec<(String, String)> {
+        let values = |fields: &[(String, String)]| -> HashMap<String, String> {
+            fields.iter().cloned().collect()
+        };
+        let (base_values, our_values, their_values) = (values(base), values(ours), values(theirs));
+
+        let mut merged = Vec::new();
+        let paths = ours.iter().map(|(path, _)| path).chain(
+            theirs
+                .iter()
+                .chain(base)
+                .map(|(path, _)| path)
+                .filter(|path| !our_values.contains_key(*path)),
+        );
+        let mut done = HashSet::new();
+        for path in paths {
+            if !done.insert(path) {
+                continue;
+            }
+            let base = base_values.get(path);
+            let ours = our_values.get(path);
+            let theirs = their_values.get(path);
+            let value = match resolve(base, ours, theirs) {
+                Ok(value) => value,
+                Err(kind) => {
+                    self.conflicts.push(Conflict {
+                        key: record.key.clone(),
+                        tag: record.tag.clone(),
+                        kind,
+                        path: Some(path.clone()),
+                        base: base.cloned(),
+                        ours: ours.cloned(),
+                        theirs: theirs.cloned(),
+                    });
+                    self.prefer(ours, theirs)
+                }
+            };
+            if let Some(value) = value {
+                merged.push((path.clone(), value.clone()));
+            }
+        }
+        merged
+    }
+
+    fn prefer<T>(&self, ours: Option<T>, theirs: Option<T>) -> Option<T> {
+        match self.policy {
+            MergePolicy::PreferFirst => ours,
+            MergePolicy::PreferSecond => theirs,
+        }
+    }
+}
+
+/// Returns the merged value of a field, or how the sides conflict.
+fn resolve<'a, T: PartialEq>(
+    base: Option<&'a T>,
+    ours: Option<&'a T>,
+    theirs: Option<&'a T>,
+) -> Result<Option<&'a T>, ConflictKind> {
+    if ours == theirs || base == theirs {
+        Ok(ours)
+    } else if base == ours {
+        Ok(theirs)
+    } else if base.is_none() {
+        Err(ConflictKind::BothAdded)
+    } else if ours.is_none() || theirs.is_none() {
+        Err(ConflictKind::ModifiedAndRemoved)
+    } else {
+        Err(ConflictKind::BothModified)
+    }
+}
+
+/// Writes a merged record back as GEDCOM lines. Fields whose parent was
+/// removed are dropped.
+fn write_record(out: &mut String, record: &Merged) {
+    let xref = record.key.split('#').next().unwrap_or_default();
+    let mut fields = record.fields.as_slice();
+    let mut line = if xref.starts_with('@') {
+        format!("{xref} {}", record.tag)
+    } else {
+        record.tag.clone()
+    };
+    if let Some((path, value)) = fields.first() {
+        if *path == record.tag {
+            if !value.is_empty() {
+                line = format!("{line} {value}");
+            }
+            fields = &fields[1..];
+        }
+    }
+    write_value(out, 0, &line);
+    write_children(out, fields, "", 1);
+}
+
+fn write_children(out: &mut String, fields: &[(String, String)], parent: &str, level: usize) {
+    for (path, value) in fields {
+        let (field_parent, segment) = path.rsplit_once('.').unwrap_or(("", path));
+        if field_parent != parent {
+            continue;
+        }
+        let tag = segment.split('[').next().unwrap_or(segment);
+        if value.is_empty() {
+            write_value(out, level, tag);
+        } else {
+            write_value(out, level, &format!("{tag} {value}"));
+        }
+        write_children(out, fields, path, level + 1);
+    }
+}
+
+/// Writes a line, continuing a multi-line value with `CONT` lines.
+fn write_value(out: &mut String, level: usize, line: &str) {
+    let mut lines = line.split('\n');
+    let _ = writeln!(out, "{level} {}", lines.next().unwrap_or_default());
+    for next in lines {
+        let _ = write!(out, "{} CONT", level + 1);
+        if !next.is_empty() {
+            let _ = write!(out, " {next}");
+        }
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff;
+
+    fn parse(records: &str) -> GedcomData {
+        let source = format!("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n{records}0 TRLR");
+        GedcomBuilder::new().build_from_str(&source).unwrap()
+    }
+
+    #[test]
+    fn test_merge3_clean() {
+        let base = parse("0 @I1@ INDI\n1 NAME Ann /Roe/\n0 @I2@ INDI\n1 NAME Bob /Roe/\n");
+        let ours = parse("0 @I1@ INDI\n1 NAME Ann /Roe/\n1 SEX F\n0 @I2@ INDI\n1 NAME Bob /Roe/\n0 @F1@ FAM\n1 WIFE @I1@\n");
+        let theirs = parse("0 @I1@ INDI\n1 NAME Ann /Roe/\n1 OCCU Weaver\n0 @N1@ SNOTE Shared\n");
+
+        let result = merge3(&base, &ours, &theirs, MergePolicy::PreferFirst).unwrap();
+        assert!(result.is_clean());
+        let expected = parse(
+            "0 @I1@ INDI\n1 NAME Ann /Roe/\n1 SEX F\n1 OCCU Weaver\n\
+             0 @F1@ FAM\n1 WIFE @I1@\n0 @N1@ SNOTE Shared\n",
+        );
+        assert!(diff(&expected, &result.merged).is_empty());
+        assert_eq!(result.merged.shared_notes[0].text, "Shared");
+
+        // Merging a side with itself changes nothing.
+        let result = merge3(&base, &ours, &base, MergePolicy::PreferSecond).unwrap();
+        assert!(result.is_clean());
+        assert!(diff(&ours, &result.merged).is_empty());
+    }
+
+    #[test]
+    fn test_merge3_conflicts() {
+        let base = parse(
+            "0 @I1@ INDI\n1 NAME Ann /Roe/\n1 BIRT\n2 DATE 1850\n0 @I2@ INDI\n1 NAME Bob /Roe/\n",
+        );
+        let ours = parse("0 @I1@ INDI\n1 NAME Ann /Roe/\n1 BIRT\n2 DATE 1851\n1 SEX F\n");
+        let theirs = parse("0 @I1@ INDI\n1 NAME Ann /Roe/\n1 BIRT\n2 DATE 1852\n1 SEX U\n0 @I2@ INDI\n1 NAME Robert /Roe/\n");
+
+        let result = merge3(&base, &ours, &theirs, MergePolicy::PreferSecond).unwrap();
+        let conflicts: Vec<_> = result
+            .conflicts
+            .iter()
+            .map(|c| (c.key.as_str(), c.path.as_deref(), c.kind))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                ("@I1@", Some("SEX"), ConflictKind::BothAdded),
+                ("@I1@", Some("BIRT.DATE"), ConflictKind::BothModified),
+                ("@I2@", None, ConflictKind::ModifiedAndRemoved),
+            ]
+        );
+        assert_eq!(result.conflicts[1].base.as_deref(), Some("1850"));
+        assert_eq!(result.conflicts[1].ours.as_deref(), Some("1851"));
+
+        // Their side wins every conflict.
+        let expected = parse(
+            "0 @I1@ INDI\n1 NAME Ann /Roe/\n1 BIRT\n2 DATE 1852\n1 SEX U\n0 @I2@ INDI\n1 NAME Robert /Roe/\n",
+        );
+        assert!(diff(&expected, &result.merged).is_empty());
+
+        let result = merge3(&base, &ours, &theirs, MergePolicy::PreferFirst).unwrap();
+        assert!(diff(&ours, &result.merged).is_empty());
+    }
+}
@@ -0,0 +1,396 @@
+//! Content digests of whole datasets.
+//!
+//! [`GedcomData::digest`] hashes a canonical form of the data with SHA-256,
+//! so that a recipient can check that a file was not modified in transit, or
+//! that it matches a published checksum. The canonical form is the output of
+//! the writer with fixed settings: LF line endings, no `CONC` wrapping, and
+//! no digest in the header. It does not depend on the line endings, wrapping
+//! or encoding of the file the data was read from, only on its content.
+//!
+//! [`WriterConfig::embed_digest`](crate::WriterConfig::embed_digest) stores
+//! the digest in the header as a `_SHA256` extension, which
+//! [`GedcomData::verify_digest`] checks after the file is read back.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{GedcomBuilder, GedcomWriter};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source)?;
+//!
+//! let output = GedcomWriter::new().embed_digest(true).write_to_string(&data)?;
+//! assert!(output.contains(&format!("1 _SHA256 {}", data.digest())));
+//!
+//! let received = GedcomBuilder::new().build_from_str(&output)?;
+//! assert_eq!(received.verify_digest(), Some(true));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Write};
+
+use crate::{types::GedcomData, GedcomWriter};
+
+/// The header extension tag holding an embedded digest.
+pub const DIGEST_TAG: &str = "_SHA256";
+
+impl GedcomData {
+    /// Returns the SHA-256 digest of the canonical form of this data, as 64
+    /// lowercase hexadecimal digits.
+    ///
+    /// A digest embedded in the header is not part of the canonical form, so
+    /// embedding one does not change the result.
+    #[must_use]
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        // Hashing never fails, so neither does writing to it.
+        let _ = GedcomWriter::canonical().write_to(&mut hasher, self);
+        to_hex(&hasher.finish())
+    }
+
+    /// Returns the digest embedded in the header, if any.
+    #[must_use]
+    pub fn embedded_digest(&self) -> Option<&str> {
+        self.header
+            .as_ref()?
+            .custom_data
+            .iter()
+            .find(|tag| &*tag.tag == DIGEST_TAG)?
+            .value
+            .as_deref()
+    }
+
+    /// Checks the digest embedded in the header against the content.
+    ///
+    /// Returns `None` if the header has no digest, and otherwise whether it
+    /// matches [`digest`](Self::digest), ignoring case.
+    #[must_use]
+    pub fn verify_digest(&self) -> Option<bool> {
+        let embedded = self.embedded_digest()?;
+        Some(embedded.trim().eq_ignore_ascii_case(&self.digest()))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// An incremental SHA-256 hasher, as specified by FIPS 180-4.
+struct Sha256 {
+    state: [u32; 8],
+    block: [u8; 64],
+    filled: usize,
+    length: u64,
+}
+
+const K: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+impl Sha256 {
+    fn new() -> Self {
+        Self {
+            state: [
+                0x6a09_e667,
+                0xbb67_ae85,
+                0x3c6e_f372,
+                0xa54f_f53a,
+                0x510e_527f,
+                0x9b05_688c,
+                0x1f83_d9ab,
+                0x5be0_cd19,
+            ],
+            block: [0; 64],
+            filled: 0,
+            length: 0,
+        }
+    }
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len() as u64);
+        while !bytes.is_empty() {
+            let take = (64 - self.filled).min(bytes.len());
+            self.block[self.filled..self.filled + take].copy_from_slice(&bytes[..take]);
+            self.filled += take;
+            bytes = &bytes[take..];
+            if self.filled == 64 {
+                self.compress();
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn finish(mut self) -> [u8; 32] {
+        let bits = self.length.wrapping_mul(8);
+        self.update(&[0x80]);
+        while self.filled != 56 {
+            self.update(&[0]);
+        }
+        self.update(&bits.to_be_bytes());
+
+        let mut digest = [0; 32];
+        for (chunk, word) in digest.chunks_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    // Variable names follow FIPS 180-4.
+    #[allow(clippy::many_single_char_names)]
+    fn compress(&mut self) {
+        let mut w = [0u32; 64];
+        for (word, chunk) in w.iter_mut().zip(self.block.chunks(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for (k, w) in K.iter().zip(w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (state, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+}
+
+impl Write for Sha256 {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    fn sha256_hex(input: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(input);
+        to_hex(&hasher.finish())
+    }
+
+    #[test]
+    fn test_sha256_known_vectors() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            sha256_hex(&[b'a'; 1000]),
+            "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+        );
+        // The two-block and one million "a" messages of FIPS 180-2.
+        assert_eq!(
+            sha256_hex(
+                b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                  hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
+            ),
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1"
+        );
+        assert_eq!(
+            sha256_hex(&vec![b'a'; 1_000_000]),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    fn test_sha256_padding_boundaries() {
+        // 55 bytes leave just room for the padding in the last block, 56
+        // need another; the digests are those of Python's hashlib.
+        for (len, expected) in [
+            (
+                55,
+                "9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318",
+            ),
+            (
+                56,
+                "b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a",
+            ),
+            (
+                57,
+                "f13b2d724659eb3bf47f2dd6af1accc87b81f09f59f2b75e5c0bed6589dfe8c6",
+            ),
+            (
+                63,
+                "7d3e74a05d7db15bce4ad9ec0658ea98e3f06eeecf16b4c6fff2da457ddc2f34",
+            ),
+            (
+                64,
+                "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb",
+            ),
+            (
+                65,
+                "635361c48bb9eab14198e76ea8ab7f1a41685d6ad62aa9146d301d4f17eb0ae0",
+            ),
+            (
+                119,
+                "31eba51c313a5c08226adf18d4a359cfdfd8d2e816b13f4af952f7ea6584dcfb",
+            ),
+            (
+                120,
+                "2f3d335432c70b580af0e8e1b3674a7c020d683aa5f73aaaedfdc55af904c21c",
+            ),
+        ] {
+            let input = vec![b'a'; len];
+            assert_eq!(sha256_hex(&input), expected, "{len} bytes");
+
+            // The split between updates does not matter.
+            for split in [1, 55, 56, 64] {
+                let mut hasher = Sha256::new();
+                for chunk in input.chunks(split) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(to_hex(&hasher.finish()), expected, "{len} bytes by {split}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_digest_ignores_line_endings_and_wrapping() {
+        let lf = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n1 NOTE Long t\n2 CONC ext\n0 TRLR";
+        let crlf = "0 HEAD\r\n1 GEDC\r\n2 VERS 5.5.1\r\n0 @I1@ INDI\r\n1 NAME John /Doe/\r\n1 NOTE Long text\r\n0 TRLR";
+        let a = GedcomBuilder::new().build_from_str(lf).unwrap();
+        let b = GedcomBuilder::new().build_from_str(crlf).unwrap();
+        assert_eq!(a.digest(), b.digest());
+        assert_eq!(a.digest().len(), 64);
+
+        let mut changed = a.clone();
+        changed.individuals[0].xref = Some("@I2@".to_string());
+        assert_ne!(changed.digest(), a.digest());
+    }
+
+    #[test]
+    fn test_embedded_digest_round_trip() {
+        let source =
+            "0 HEAD\n1 GEDC\n2 VERS 7.0\n1 _SHA256 0000\n0 @I1@ INDI\n1 NAME Ann /Roe/\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert_eq!(data.embedded_digest(), Some("0000"));
+        assert_eq!(data.verify_digest(), Some(false));
+
+        let output = GedcomWriter::new()
+            .embed_digest(true)
+            .write_to_string(&data)
+            .unwrap();
+        assert_eq!(output.matches("_SHA256").count(), 1);
+
+        let received = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(received.embedded_digest(), Some(data.digest().as_str()));
+        assert_eq!(received.verify_digest(), Some(true));
+        assert_eq!(received.digest(), data.digest());
+
+        let tampered = output.replace("Ann", "Anne");
+        let tampered = GedcomBuilder::new().build_from_str(&tampered).unwrap();
+        assert_eq!(tampered.verify_digest(), Some(false));
+
+        let plain = GedcomWriter::new().write_to_string(&received).unwrap();
+        let plain = GedcomBuilder::new().build_from_str(&plain).unwrap();
+        assert_eq!(plain.verify_digest(), Some(true));
+        assert_eq!(GedcomData::default().verify_digest(), None);
+    }
+}
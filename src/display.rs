@@ -25,6 +25,9 @@ impl fmt::Display for GedcomData {
 
         if let Some(ref header) = self.header {
             writeln!(f, "{header}")?;
+            if let Some(text) = header.note.as_ref().and_then(|n| self.note_text(n)) {
+                writeln!(f, "  Note: {}", truncate(text))?;
+            }
         }
 
         if !self.individuals.is_empty() {
@@ -62,6 +65,17 @@ impl fmt::Display for GedcomData {
             }
         }
 
+        if !self.shared_notes.is_empty() {
+            writeln!(f, "\nShared Notes ({}):", self.shared_notes.len())?;
+            for note in &self.shared_notes {
+                let text = truncate(self.shared_note_text(note));
+                match note.xref {
+                    Some(ref xref) => writeln!(f, "  {xref}: {text}")?,
+                    None => writeln!(f, "  {text}")?,
+                }
+            }
+        }
+
         if !self.submitters.is_empty() {
             writeln!(f, "\nSubmitters ({}):", self.submitters.len())?;
             for submitter in &self.submitters {
@@ -114,7 +128,7 @@ impl fmt::Display for Individual {
         }
 
         // Display name
-        if let Some(ref name) = self.name {
+        if let Some(name) = self.names.first() {
             write!(f, "{name}")?;
         } else {
             write!(f, "(Unknown Name)")?;
@@ -132,25 +146,17 @@ impl fmt::Display for Individual {
 
         for event in &self.events {
             match event.event {
-                crate::types::event::Event::Birth => {
-                    if birth_date.is_none() {
-                        birth_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Birth if birth_date.is_none() => {
+                    birth_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
-                crate::types::event::Event::Baptism => {
-                    if baptism_date.is_none() {
-                        baptism_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Baptism if baptism_date.is_none() => {
+                    baptism_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
-                crate::types::event::Event::Death => {
-                    if death_date.is_none() {
-                        death_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Death if death_date.is_none() => {
+                    death_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
-                crate::types::event::Event::Burial => {
-                    if inhumation_date.is_none() {
-                        inhumation_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Burial if inhumation_date.is_none() => {
+                    inhumation_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
                 _ => {}
             }
@@ -193,7 +199,7 @@ impl fmt::Display for Name {
                 parts.push(surname_prefix.clone());
             }
             if let Some(ref surname) = self.surname {
-                parts.push(surname.clone());
+                parts.push(surname.to_string());
             }
             if let Some(ref suffix) = self.suffix {
                 parts.push(suffix.clone());
@@ -243,30 +249,20 @@ impl fmt::Display for Family {
 
         for event in &self.events {
             match event.event {
-                crate::types::event::Event::Marriage => {
-                    if marriage_date.is_none() {
-                        marriage_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Marriage if marriage_date.is_none() => {
+                    marriage_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
-                crate::types::event::Event::Engagement => {
-                    if engagement_date.is_none() {
-                        engagement_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Engagement if engagement_date.is_none() => {
+                    engagement_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
-                crate::types::event::Event::Separated => {
-                    if separated_date.is_none() {
-                        separated_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Separated if separated_date.is_none() => {
+                    separated_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
-                crate::types::event::Event::Divorce => {
-                    if divorce_date.is_none() {
-                        divorce_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Divorce if divorce_date.is_none() => {
+                    divorce_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
-                crate::types::event::Event::Annulment => {
-                    if annulment_date.is_none() {
-                        annulment_date = event.date.as_ref().and_then(|d| d.value.as_deref());
-                    }
+                crate::types::event::Event::Annulment if annulment_date.is_none() => {
+                    annulment_date = event.date.as_ref().and_then(|d| d.value.as_deref());
                 }
                 _ => {}
             }
@@ -340,7 +336,7 @@ impl fmt::Display for Multimedia {
 
         if let Some(ref title) = self.title {
             write!(f, "\"{title}\"")?;
-        } else if let Some(ref file) = self.file {
+        } else if let Some(file) = self.file() {
             if let Some(ref file_value) = file.value {
                 write!(f, "{file_value}")?;
             } else {
@@ -395,13 +391,7 @@ impl fmt::Display for Submission {
 impl fmt::Display for Note {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(ref value) = self.value {
-            // Truncate long notes for display
-            const MAX_LEN: usize = 100;
-            if value.len() > MAX_LEN {
-                write!(f, "{}...", &value[..MAX_LEN])?;
-            } else {
-                write!(f, "{value}")?;
-            }
+            write!(f, "{}", truncate(value))?;
         } else {
             write!(f, "(Empty Note)")?;
         }
@@ -410,6 +400,15 @@ impl fmt::Display for Note {
     }
 }
 
+/// Shortens long note text for display.
+fn truncate(text: &str) -> String {
+    const MAX_LEN: usize = 100;
+    match text.char_indices().nth(MAX_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -576,7 +575,7 @@ mod tests {
         let mut gedcom = Gedcom::new(sample.chars()).unwrap();
         let data = gedcom.parse_data().unwrap();
 
-        let name = data.individuals[0].name.as_ref().unwrap();
+        let name = &data.individuals[0].names[0];
         let display = format!("{name}");
         assert!(display.contains("Robert"));
         assert!(display.contains("Johnson"));
@@ -630,7 +629,7 @@ mod tests {
         let note = Note {
             value: Some(long_note),
             mime: None,
-            translation: None,
+            translations: Vec::new(),
             citation: None,
             language: None,
         };
@@ -639,4 +638,33 @@ mod tests {
         assert!(display.ends_with("..."));
         assert!(display.len() < 110); // 100 chars + "..."
     }
+
+    #[test]
+    fn test_display_uses_preferred_language() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            1 NOTE Family papers\n\
+            2 LANG en\n\
+            2 TRAN Familienpapiere\n\
+            3 LANG de\n\
+            0 @N1@ SNOTE Emigrated in 1850\n\
+            1 LANG en\n\
+            1 TRAN 1850 ausgewandert\n\
+            2 LANG de\n\
+            0 TRLR";
+
+        let mut gedcom = Gedcom::new(sample.chars()).unwrap();
+        let mut data = gedcom.parse_data().unwrap();
+
+        let display = format!("{data}");
+        assert!(display.contains("  Note: Family papers\n"));
+        assert!(display.contains("Shared Notes (1):\n  @N1@: Emigrated in 1850\n"));
+
+        data.preferred_languages = vec!["de-CH".to_string(), "de".to_string()];
+        let display = format!("{data}");
+        assert!(display.contains("  Note: Familienpapiere\n"));
+        assert!(display.contains("  @N1@: 1850 ausgewandert\n"));
+    }
 }
@@ -0,0 +1,707 @@
+//! Editing the links between records.
+//!
+//! A family and its members point at each other: the family names its
+//! spouses and children (`HUSB`, `WIFE` and `CHIL`), and each member has a
+//! `FAMS` or `FAMC` link back. The methods here change both sides at once,
+//! so that code building or editing a tree cannot leave one side behind.
+//!
+//! The records they change get a change date (`CHAN`) with the current time
+//! of [`GedcomData::change_clock`](crate::types::GedcomData::change_clock),
+//! and the records they add a creation date (`CREA`) as well, so that
+//! [`GedcomData::touched_since`] can list what an editing session changed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{edit::RemoveOptions, types::event::spouse::Spouse, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Doe/\n0 @I2@ INDI\n1 NAME Jim /Doe/\n\
+//!     0 @F1@ FAM\n0 @S1@ SOUR\n1 TITL Register\n0 TRLR";
+//! let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! data.set_spouse("@F1@", "@I1@", Spouse::Spouse1).unwrap();
+//! data.add_child_to_family("@F1@", "@I2@").unwrap();
+//! data.attach_citation("@I2@", "@S1@", Some("p. 12")).unwrap();
+//! assert_eq!(data.families[0].individual1.as_deref(), Some("@I1@"));
+//! assert_eq!(data.individuals[1].families[0].xref, "@F1@");
+//!
+//! let options = RemoveOptions {
+//!     remove_empty_families: true,
+//! };
+//! let removed = data.remove_individual("@I2@", &options).unwrap();
+//! assert_eq!(removed, vec!["@I2@", "@F1@"]);
+//! assert!(data.individuals[0].families.is_empty());
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    copy::Record,
+    renumber::XrefScheme,
+    types::{
+        date::{change_date::ChangeDate, Date},
+        event::spouse::Spouse,
+        family::Family,
+        individual::{
+            family_link::{FamilyLink, FamilyLinkType},
+            Individual,
+        },
+        source::citation::Citation,
+        GedcomData, RecordType,
+    },
+    GedcomError,
+};
+
+/// The English month abbreviations of GEDCOM dates.
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// A moment in UTC, to the second, as recorded in a change date.
+///
+/// Timestamps order chronologically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timestamp {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 59.
+    pub second: u8,
+}
+
+impl Timestamp {
+    /// Returns midnight at the start of the given day.
+    #[must_use]
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0,
+        }
+    }
+
+    /// Returns the moment `seconds` after the Unix epoch.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_unix_seconds(seconds: u64) -> Self {
+        let (days, time) = ((seconds / 86_400) as i64, seconds % 86_400);
+        // Howard Hinnant's `civil_from_days`, for days since 1970-01-01.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+            hour: (time / 3600) as u8,
+            minute: (time / 60 % 60) as u8,
+            second: (time % 60) as u8,
+        }
+    }
+
+    /// Returns the current time of the system clock.
+    #[must_use]
+    pub fn now() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_unix_seconds(seconds)
+    }
+
+    /// Reads a change date such as `DATE 5 MAR 2024` with `TIME 14:03:07`.
+    /// A missing time is taken as midnight, and fractions of a second are
+    /// ignored.
+    ///
+    /// Returns `None` unless the date is an exact Gregorian date.
+    #[must_use]
+    pub fn from_change_date(change_date: &ChangeDate) -> Option<Self> {
+        let date = change_date.date.as_ref()?;
+        let mut parts = date.value.as_deref()?.split_whitespace();
+        let day: u8 = parts.next()?.parse().ok()?;
+        let month = parts.next()?.to_ascii_uppercase();
+        let month = MONTHS.iter().position(|m| *m == month)?;
+        let year: i32 = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        let mut timestamp = Self::new(year, u8::try_from(month + 1).ok()?, day);
+        if let Some(time) = date.time.as_deref() {
+            let time = time.split('.').next().unwrap_or(time);
+            let mut fields = time.split(':').map(str::parse::<u8>);
+            timestamp.hour = fields.next()?.ok()?;
+            timestamp.minute = fields.next()?.ok()?;
+            timestamp.second = fields.next().transpose().ok()?.unwrap_or(0);
+        }
+        Some(timestamp)
+    }
+
+    /// Returns the change date recording this moment.
+    #[must_use]
+    pub fn to_change_date(self) -> ChangeDate {
+        let month = MONTHS[usize::from(self.month.clamp(1, 12)) - 1];
+        ChangeDate {
+            date: Some(Date {
+                value: Some(format!("{} {month} {}", self.day, self.year)),
+                time: Some(format!(
+                    "{:02}:{:02}:{:02}",
+                    self.hour, self.minute, self.second
+                )),
+                phrase: None,
+            }),
+            notes: Vec::new(),
+        }
+    }
+}
+
+/// Where the change dates that the editing methods record come from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ChangeClock {
+    /// The system clock (the default).
+    #[default]
+    System,
+    /// Always the same moment, for reproducible output and tests.
+    Fixed(Timestamp),
+    /// Change dates are left as they are.
+    Off,
+}
+
+impl ChangeClock {
+    /// Returns the current time of this clock, or `None` if it is off.
+    #[must_use]
+    pub fn now(self) -> Option<Timestamp> {
+        match self {
+            ChangeClock::System => Some(Timestamp::now()),
+            ChangeClock::Fixed(timestamp) => Some(timestamp),
+            ChangeClock::Off => None,
+        }
+    }
+}
+
+/// What else [`GedcomData::remove_individual`] removes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct RemoveOptions {
+    /// Also remove the families of the individual that are left with one
+    /// member or none (default: false).
+    pub remove_empty_families: bool,
+}
+
+impl GedcomData {
+    /// Adds the individual `child` to the children of `family`, and a
+    /// `FAMC` link to `family` to the individual. Links already there are
+    /// not repeated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if there is no such family or
+    /// individual.
+    pub fn add_child_to_family(&mut self, family: &str, child: &str) -> Result<(), GedcomError> {
+        let family_index = self.family_index(family)?;
+        let child_index = self.individual_index(child)?;
+        let stamp = self.change_stamp();
+        let record = &mut self.families[family_index];
+        if !record.children.iter().any(|c| c == child) {
+            record.children.push(child.to_string());
+            touch(&mut record.change_date, stamp.as_ref());
+        }
+        let individual = &mut self.individuals[child_index];
+        if add_link(individual, family, FamilyLinkType::Child) {
+            touch(&mut individual.change_date, stamp.as_ref());
+        }
+        Ok(())
+    }
+
+    /// Makes `individual` the given spouse of `family`, with a `FAMS` link
+    /// back, and returns the individual it replaces, if any.
+    ///
+    /// The replaced individual loses its `FAMS` link to the family. If
+    /// `individual` was the other spouse, that place is emptied.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if there is no such family or
+    /// individual.
+    pub fn set_spouse(
+        &mut self,
+        family: &str,
+        individual: &str,
+        spouse: Spouse,
+    ) -> Result<Option<String>, GedcomError> {
+        let family_index = self.family_index(family)?;
+        let individual_index = self.individual_index(individual)?;
+        let stamp = self.change_stamp();
+        let record = &mut self.families[family_index];
+        let (place, other) = match spouse {
+            Spouse::Spouse1 => (&mut record.individual1, &mut record.individual2),
+            Spouse::Spouse2 => (&mut record.individual2, &mut record.individual1),
+        };
+        let moved = other.as_deref() == Some(individual);
+        if moved {
+            *other = None;
+        }
+        let previous = place.replace(individual.to_string());
+        if moved || previous.as_deref() != Some(individual) {
+            touch(&mut record.change_date, stamp.as_ref());
+        }
+        let replaced = previous.filter(|previous| previous != individual);
+
+        if let Some(ref previous) = replaced {
+            if let Some(previous) = self
+                .individuals
+                .iter_mut()
+                .find(|i| i.xref.as_deref() == Some(previous))
+            {
+                let links = previous.families.len();
+                previous.families.retain(|link| {
+                    link.xref != family || link.family_link_type != FamilyLinkType::Spouse
+                });
+                if previous.families.len() != links {
+                    touch(&mut previous.change_date, stamp.as_ref());
+                }
+            }
+        }
+        let individual = &mut self.individuals[individual_index];
+        if add_link(individual, family, FamilyLinkType::Spouse) {
+            touch(&mut individual.change_date, stamp.as_ref());
+        }
+        Ok(replaced)
+    }
+
+    /// Removes the individual `xref` and every pointer to it, and returns
+    /// the xrefs of the removed records: the individual first, then any
+    /// families removed as `options` asks.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if there is no such individual.
+    pub fn remove_individual(
+        &mut self,
+        xref: &str,
+        options: &RemoveOptions,
+    ) -> Result<Vec<String>, GedcomError> {
+        let index = self.individual_index(xref)?;
+        let mut own: Vec<String> = self.individuals[index]
+            .families
+            .iter()
+            .map(|link| link.xref.clone())
+            .collect();
+        own.extend(
+            self.families
+                .iter()
+                .filter(|f| {
+                    let spouses = [&f.individual1, &f.individual2];
+                    spouses.into_iter().flatten().any(|s| s == xref)
+                        || f.children.iter().any(|c| c == xref)
+                })
+                .filter_map(|f| f.xref.clone()),
+        );
+        self.individuals.remove(index);
+        let stamp = self.change_stamp();
+        self.visit_all_links_stamping(
+            &mut |record_type, link| record_type != RecordType::Individual || link != xref,
+            stamp.as_ref(),
+        );
+
+        let mut removed = vec![xref.to_string()];
+        if options.remove_empty_families {
+            let empty: Vec<String> = self
+                .families
+                .iter()
+                .filter_map(|f| f.xref.clone())
+                .filter(|f| own.contains(f))
+                .filter(|f| {
+                    self.find_family(f).is_some_and(|family| {
+                        let spouses = [&family.individual1, &family.individual2];
+                        spouses.into_iter().flatten().count() + family.children.len() <= 1
+                    })
+                })
+                .collect();
+            self.families
+                .retain(|f| !f.xref.as_ref().is_some_and(|x| empty.contains(x)));
+            self.visit_all_links_stamping(
+                &mut |record_type, link| record_type != RecordType::Family || !empty.contains(link),
+                stamp.as_ref(),
+            );
+            removed.extend(empty);
+        }
+        Ok(removed)
+    }
+
+    /// Cites the source `source` on the individual or family `record`, with
+    /// an optional page.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if there is no such source, or
+    /// no individual or family `record`.
+    pub fn attach_citation(
+        &mut self,
+        record: &str,
+        source: &str,
+        page: Option<&str>,
+    ) -> Result<(), GedcomError> {
+        if self.find_source(source).is_none() {
+            return Err(GedcomError::InvalidFormat(format!(
+                "No source with xref {source}"
+            )));
+        }
+        let citation = Citation {
+            xref: source.to_string(),
+            page: page.map(str::to_string),
+            ..Citation::default()
+        };
+        let stamp = self.change_stamp();
+        let is_record = |x: &Option<String>| x.as_deref() == Some(record);
+        if let Some(individual) = self.individuals.iter_mut().find(|i| is_record(&i.xref)) {
+            individual.add_source_citation(citation);
+            touch(&mut individual.change_date, stamp.as_ref());
+        } else if let Some(family) = self.families.iter_mut().find(|f| is_record(&f.xref)) {
+            family.add_source(citation);
+            touch(&mut family.change_date, stamp.as_ref());
+        } else {
+            return Err(GedcomError::InvalidFormat(format!(
+                "No individual or family with xref {record}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Adds an individual created by the program, such as one made with an
+    /// [`IndividualBuilder`](crate::construct::IndividualBuilder), and
+    /// returns its xref. An individual without one is given an unused
+    /// xref. The creation and change dates are set to the current time of
+    /// the [`change_clock`](GedcomData::change_clock).
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if the xref is already in use.
+    pub fn insert_individual(&mut self, mut individual: Individual) -> Result<String, GedcomError> {
+        let xref = self.claim_xref(individual.xref.take(), RecordType::Individual)?;
+        individual.xref = Some(xref.clone());
+        if let Some(stamp) = self.change_stamp() {
+            individual.creation_date = Some(stamp.clone());
+            individual.change_date = Some(stamp);
+        }
+        self.individuals.push(individual);
+        Ok(xref)
+    }
+
+    /// Adds a family created by the program, as
+    /// [`insert_individual`](Self::insert_individual) does an individual.
+    ///
+    /// The members are not given links to the family; add them with
+    /// [`set_spouse`](Self::set_spouse) and
+    /// [`add_child_to_family`](Self::add_child_to_family).
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if the xref is already in use.
+    pub fn insert_family(&mut self, mut family: Family) -> Result<String, GedcomError> {
+        let xref = self.claim_xref(family.xref.take(), RecordType::Family)?;
+        family.xref = Some(xref.clone());
+        if let Some(stamp) = self.change_stamp() {
+            family.creation_date = Some(stamp.clone());
+            family.change_date = Some(stamp);
+        }
+        self.families.push(family);
+        Ok(xref)
+    }
+
+    /// Returns the type and xref of every record changed at or after
+    /// `since`, by its change date, in the order of
+    /// [`RecordType`]: individuals first.
+    ///
+    /// Records without a change date, or with one that is not an exact
+    /// date, are left out.
+    #[must_use]
+    pub fn touched_since(&self, since: Timestamp) -> Vec<(RecordType, &str)> {
+        fn on<'a, T: Record>(
+            records: &'a [T],
+            record_type: RecordType,
+            since: Timestamp,
+            touched: &mut Vec<(RecordType, &'a str)>,
+        ) {
+            for record in records {
+                let changed = record
+                    .change_date()
+                    .and_then(Timestamp::from_change_date)
+                    .is_some_and(|changed| changed >= since);
+                if let (true, Some(xref)) = (changed, record.xref()) {
+                    touched.push((record_type, xref));
+                }
+            }
+        }
+        let mut touched = Vec::new();
+        on(
+            &self.individuals,
+            RecordType::Individual,
+            since,
+            &mut touched,
+        );
+        on(&self.families, RecordType::Family, since, &mut touched);
+        on(&self.sources, RecordType::Source, since, &mut touched);
+        on(
+            &self.repositories,
+            RecordType::Repository,
+            since,
+            &mut touched,
+        );
+        on(
+            &self.multimedia,
+            RecordType::Multimedia,
+            since,
+            &mut touched,
+        );
+        on(
+            &self.shared_notes,
+            RecordType::SharedNote,
+            since,
+            &mut touched,
+        );
+        on(&self.submitters, RecordType::Submitter, since, &mut touched);
+        on(
+            &self.submissions,
+            RecordType::Submission,
+            since,
+            &mut touched,
+        );
+        touched
+    }
+
+    /// Returns the change date to give edited records, if any.
+    fn change_stamp(&self) -> Option<ChangeDate> {
+        self.change_clock.now().map(Timestamp::to_change_date)
+    }
+
+    /// Returns `xref`, or an unused xref for the record type if there is
+    /// none.
+    fn claim_xref(
+        &self,
+        xref: Option<String>,
+        record_type: RecordType,
+    ) -> Result<String, GedcomError> {
+        match xref {
+            Some(xref) if self.xrefs_in_use().contains(&xref) => Err(GedcomError::InvalidFormat(
+                format!("Xref {xref} is already in use"),
+            )),
+            Some(xref) => Ok(xref),
+            None => Ok(self.new_xref(record_type, &XrefScheme::default())),
+        }
+    }
+
+    fn individual_index(&self, xref: &str) -> Result<usize, GedcomError> {
+        self.individuals
+            .iter()
+            .position(|i| i.xref.as_deref() == Some(xref))
+            .ok_or_else(|| GedcomError::InvalidFormat(format!("No individual with xref {xref}")))
+    }
+
+    fn family_index(&self, xref: &str) -> Result<usize, GedcomError> {
+        self.families
+            .iter()
+            .position(|f| f.xref.as_deref() == Some(xref))
+            .ok_or_else(|| GedcomError::InvalidFormat(format!("No family with xref {xref}")))
+    }
+}
+
+/// Sets a change date to `stamp`, if there is one.
+fn touch(change_date: &mut Option<ChangeDate>, stamp: Option<&ChangeDate>) {
+    if let Some(stamp) = stamp {
+        *change_date = Some(stamp.clone());
+    }
+}
+
+/// Adds a link of the given type to `family` unless the individual has one,
+/// and returns whether it was added.
+fn add_link(individual: &mut Individual, family: &str, link_type: FamilyLinkType) -> bool {
+    let linked = individual
+        .families
+        .iter()
+        .any(|link| link.xref == family && link.family_link_type == link_type);
+    if !linked {
+        individual.families.push(FamilyLink {
+            xref: family.to_string(),
+            family_link_type: link_type,
+            pedigree_linkage_type: None,
+            pedigree_phrase: None,
+            child_linkage_status: None,
+            status_phrase: None,
+            adopted_by: None,
+            adopted_by_phrase: None,
+            notes: Vec::new(),
+            custom_data: Vec::new(),
+        });
+    }
+    !linked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{construct::IndividualBuilder, GedcomBuilder};
+
+    const SAMPLE: &str = "\
+        0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+        0 @I1@ INDI\n1 NAME John /Doe/\n1 FAMS @F1@\n\
+        0 @I2@ INDI\n1 NAME Jane /Roe/\n1 FAMS @F1@\n\
+        0 @I3@ INDI\n1 NAME Jim /Doe/\n1 FAMC @F1@\n1 ASSO @I1@\n2 RELA Godfather\n\
+        0 @I4@ INDI\n1 NAME Ann /Poe/\n\
+        0 @I5@ INDI\n1 NAME Bob /Poe/\n\
+        0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n1 CHIL @I3@\n\
+        0 @S1@ SOUR\n1 TITL Register\n\
+        0 TRLR";
+
+    #[test]
+    fn test_add_child_and_set_spouse() {
+        let mut data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        data.add_child_to_family("@F1@", "@I4@").unwrap();
+        data.add_child_to_family("@F1@", "@I4@").unwrap();
+        assert_eq!(data.families[0].children, vec!["@I3@", "@I4@"]);
+        assert_eq!(data.individuals[3].families.len(), 1);
+
+        let replaced = data.set_spouse("@F1@", "@I5@", Spouse::Spouse2).unwrap();
+        assert_eq!(replaced.as_deref(), Some("@I2@"));
+        assert!(data.individuals[1].families.is_empty());
+        let bob = &data.individuals[4];
+        assert_eq!(bob.families[0].family_link_type, FamilyLinkType::Spouse);
+
+        // Moving John from husband to wife empties his old place.
+        assert_eq!(
+            data.set_spouse("@F1@", "@I1@", Spouse::Spouse2).unwrap(),
+            Some("@I5@".to_string())
+        );
+        assert_eq!(data.families[0].individual1, None);
+        assert_eq!(data.families[0].individual2.as_deref(), Some("@I1@"));
+
+        let result = data.add_child_to_family("@F9@", "@I4@");
+        assert!(matches!(result, Err(GedcomError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_remove_individual() {
+        let mut data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let removed = data
+            .remove_individual("@I1@", &RemoveOptions::default())
+            .unwrap();
+        assert_eq!(removed, vec!["@I1@"]);
+        assert_eq!(data.families[0].individual1, None);
+        assert!(data.individuals[1].associations.is_empty());
+
+        let options = RemoveOptions {
+            remove_empty_families: true,
+        };
+        assert_eq!(data.remove_individual("@I4@", &options).unwrap().len(), 1);
+        // Only Jim is left in the family.
+        assert_eq!(
+            data.remove_individual("@I2@", &options).unwrap(),
+            vec!["@I2@", "@F1@"]
+        );
+        assert!(data.families.is_empty());
+        assert!(data.individuals[0].families.is_empty());
+    }
+
+    #[test]
+    fn test_attach_citation() {
+        let mut data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        data.attach_citation("@F1@", "@S1@", None).unwrap();
+        data.attach_citation("@I1@", "@S1@", Some("p. 3")).unwrap();
+        assert_eq!(data.families[0].sources[0].xref, "@S1@");
+        assert_eq!(data.individuals[0].source[0].page.as_deref(), Some("p. 3"));
+
+        assert!(data.attach_citation("@I1@", "@S9@", None).is_err());
+        assert!(data.attach_citation("@S1@", "@S1@", None).is_err());
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(Timestamp::from_unix_seconds(0), Timestamp::new(1970, 1, 1));
+        let leap_day = Timestamp::from_unix_seconds(951_868_799);
+        assert_eq!((leap_day.month, leap_day.day, leap_day.second), (2, 29, 59));
+
+        let timestamp = Timestamp::from_unix_seconds(1_709_647_387);
+        let change_date = timestamp.to_change_date();
+        let date = change_date.date.as_ref().unwrap();
+        assert_eq!(date.value.as_deref(), Some("5 MAR 2024"));
+        assert_eq!(date.time.as_deref(), Some("14:03:07"));
+        assert_eq!(Timestamp::from_change_date(&change_date), Some(timestamp));
+
+        let mut date = date.clone();
+        date.value = Some("ABT 2024".to_string());
+        let change_date = ChangeDate {
+            date: Some(date),
+            notes: Vec::new(),
+        };
+        assert_eq!(Timestamp::from_change_date(&change_date), None);
+    }
+
+    #[test]
+    fn test_change_dates() {
+        let mut data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let now = Timestamp::from_unix_seconds(1_709_647_387);
+        data.change_clock = ChangeClock::Fixed(now);
+        assert!(data.touched_since(Timestamp::new(1900, 1, 1)).is_empty());
+
+        data.add_child_to_family("@F1@", "@I4@").unwrap();
+        data.attach_citation("@I5@", "@S1@", None).unwrap();
+        let touched = data.touched_since(Timestamp::new(2024, 3, 5));
+        assert_eq!(
+            touched,
+            vec![
+                (RecordType::Individual, "@I4@"),
+                (RecordType::Individual, "@I5@"),
+                (RecordType::Family, "@F1@"),
+            ]
+        );
+        assert!(data.touched_since(Timestamp::new(2024, 3, 6)).is_empty());
+
+        let new_one = IndividualBuilder::new("@I9@").given("New").build();
+        assert_eq!(data.insert_individual(new_one).unwrap(), "@I9@");
+        let family = data.insert_family(Family::default()).unwrap();
+        assert_eq!(family, "@F2@");
+        assert!(data.families[1].creation_date.is_some());
+        let duplicate = IndividualBuilder::new("@I1@").build();
+        assert!(data.insert_individual(duplicate).is_err());
+
+        data.change_clock = ChangeClock::Off;
+        let options = RemoveOptions::default();
+        data.remove_individual("@I3@", &options).unwrap();
+        assert_eq!(data.touched_since(Timestamp::new(2024, 3, 5)).len(), 5);
+
+        data.change_clock = ChangeClock::Fixed(Timestamp::new(2025, 1, 1));
+        data.remove_individual("@I1@", &options).unwrap();
+        // Only the family still pointed at John.
+        let touched = data.touched_since(Timestamp::new(2025, 1, 1));
+        assert_eq!(touched, vec![(RecordType::Family, "@F1@")]);
+    }
+}
@@ -7,6 +7,9 @@
 //! - **UTF-16**: Sometimes used, especially with Windows applications (with BOM)
 //! - **ISO-8859-1** (Latin-1): Common in older European GEDCOM files
 //! - **ISO-8859-15** (Latin-9): Similar to Latin-1 but includes the Euro sign
+//! - **Windows-1252** (`ANSI`): Latin-1 with smart quotes and dashes, often mislabeled as Latin-1
+//! - **MacRoman**: The character set of 1990s Macintosh software (`MACINTOSH`)
+//! - **Code pages 437 and 850**: The character sets of MS-DOS software (`IBMPC`, `CP850`)
 //! - **ANSEL**: A legacy encoding used in older GEDCOM 5.x files (Z39.47)
 //! - **ASCII**: 7-bit ASCII, a subset of UTF-8
 //!
@@ -22,11 +25,27 @@
 //! assert!(content.contains("HEAD"));
 //! ```
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::GedcomError;
-use encoding_rs::{Encoding, ISO_8859_15, UTF_16BE, UTF_16LE, WINDOWS_1252};
+use encoding_rs::{
+    Decoder, DecoderResult, Encoding, ISO_8859_15, MACINTOSH, UTF_16BE, UTF_16LE, UTF_8,
+    WINDOWS_1252,
+};
+use std::borrow::Cow;
+use std::io::{BufRead, Read};
+
+#[cfg(feature = "unicode-normalization")]
+pub mod normalize;
+pub mod repair;
+mod transcode;
+
+pub use transcode::{transcode, Transcoder, TranscodingReader, TranscodingWriter};
 
 /// Represents the detected or declared encoding of a GEDCOM file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GedcomEncoding {
     /// UTF-8 encoding (default for GEDCOM 7.0)
     Utf8,
@@ -38,6 +57,15 @@ pub enum GedcomEncoding {
     Iso8859_1,
     /// ISO-8859-15 (Latin-9) encoding, includes Euro sign
     Iso8859_15,
+    /// Windows-1252 encoding, ISO-8859-1 with typographic quotes, dashes and
+    /// the Euro sign in place of the control characters at 0x80-0x9F
+    Windows1252,
+    /// Mac OS Roman, the character set of Macintosh software before Mac OS X
+    MacRoman,
+    /// Code page 437, the character set of the IBM PC and US MS-DOS
+    Cp437,
+    /// Code page 850, the multilingual MS-DOS character set of Western Europe
+    Cp850,
     /// ASCII encoding (7-bit, subset of UTF-8)
     Ascii,
     /// ANSEL encoding (Z39.47, used in older GEDCOM 5.x files)
@@ -54,6 +82,10 @@ impl std::fmt::Display for GedcomEncoding {
             GedcomEncoding::Utf16Be => write!(f, "UTF-16BE"),
             GedcomEncoding::Iso8859_1 => write!(f, "ISO-8859-1"),
             GedcomEncoding::Iso8859_15 => write!(f, "ISO-8859-15"),
+            GedcomEncoding::Windows1252 => write!(f, "WINDOWS-1252"),
+            GedcomEncoding::MacRoman => write!(f, "MACINTOSH"),
+            GedcomEncoding::Cp437 => write!(f, "CP437"),
+            GedcomEncoding::Cp850 => write!(f, "CP850"),
             GedcomEncoding::Ascii => write!(f, "ASCII"),
             GedcomEncoding::Ansel => write!(f, "ANSEL"),
             GedcomEncoding::Unknown => write!(f, "Unknown"),
@@ -61,11 +93,38 @@ impl std::fmt::Display for GedcomEncoding {
     }
 }
 
+impl GedcomEncoding {
+    /// Returns the value of the header `CHAR` line that declares this
+    /// encoding, as [`encode_to_bytes`] produces it: `UNICODE` for both
+    /// UTF-16 byte orders, `ANSI`, as Windows programs write it, for
+    /// Windows-1252, `IBMPC`, as PAF for DOS wrote it, for code page 437,
+    /// and `UTF-8` for `Unknown`.
+    #[must_use]
+    pub fn char_value(self) -> &'static str {
+        match self {
+            GedcomEncoding::Utf8 | GedcomEncoding::Unknown => "UTF-8",
+            GedcomEncoding::Utf16Le | GedcomEncoding::Utf16Be => "UNICODE",
+            GedcomEncoding::Iso8859_1 => "ISO-8859-1",
+            GedcomEncoding::Iso8859_15 => "ISO-8859-15",
+            GedcomEncoding::Windows1252 => "ANSI",
+            GedcomEncoding::MacRoman => "MACINTOSH",
+            GedcomEncoding::Cp437 => "IBMPC",
+            GedcomEncoding::Cp850 => "CP850",
+            GedcomEncoding::Ascii => "ASCII",
+            GedcomEncoding::Ansel => "ANSEL",
+        }
+    }
+}
+
 /// Detects the encoding of GEDCOM bytes by examining:
 /// 1. Byte Order Mark (BOM) for UTF-16/UTF-8
 /// 2. The CHAR tag value in the header
 /// 3. Heuristics based on byte patterns
 ///
+/// A file that claims or looks like ISO-8859-1 but has bytes in 0x80-0x9F,
+/// such as the smart quotes 0x91-0x94, is detected as Windows-1252: those
+/// are control characters in ISO-8859-1 that text never contains.
+///
 /// # Arguments
 ///
 /// * `bytes` - The raw bytes of the GEDCOM file
@@ -88,13 +147,14 @@ pub fn detect_encoding(bytes: &[u8]) -> GedcomEncoding {
         }
     }
 
-    // Try to find CHAR tag in the header to determine declared encoding
-    if let Some(encoding) = detect_encoding_from_char_tag(bytes) {
-        return encoding;
+    // Try to find CHAR tag in the header to determine declared encoding,
+    // or if there is none, detect it by content
+    let encoding =
+        detect_encoding_from_char_tag(bytes).unwrap_or_else(|| detect_encoding_by_content(bytes));
+    if encoding == GedcomEncoding::Iso8859_1 && bytes.iter().any(|b| (0x80..=0x9F).contains(b)) {
+        return GedcomEncoding::Windows1252;
     }
-
-    // If no BOM and no CHAR tag, try to detect by content
-    detect_encoding_by_content(bytes)
+    encoding
 }
 
 /// Detects encoding by looking for the CHAR tag in the GEDCOM header.
@@ -133,7 +193,15 @@ fn parse_encoding_value(value: &str) -> Option<GedcomEncoding> {
         "UTF-16BE" | "UTF16BE" => Some(GedcomEncoding::Utf16Be),
         "ISO-8859-1" | "ISO8859-1" | "LATIN1" | "ISO_8859-1" => Some(GedcomEncoding::Iso8859_1),
         "ISO-8859-15" | "ISO8859-15" | "LATIN9" | "ISO_8859-15" => Some(GedcomEncoding::Iso8859_15),
-        "ASCII" | "ANSI" => Some(GedcomEncoding::Ascii),
+        // Windows programs call the code page of the system ANSI
+        "WINDOWS-1252" | "WINDOWS1252" | "CP1252" | "ANSI" => Some(GedcomEncoding::Windows1252),
+        "MACINTOSH" | "MACROMAN" | "MAC" | "X-MAC-ROMAN" => Some(GedcomEncoding::MacRoman),
+        // DOS programs such as PAF declared the code page of the IBM PC
+        "IBMPC" | "IBM-PC" | "IBM_PC" | "CP437" | "IBM437" | "DOS" | "MSDOS" => {
+            Some(GedcomEncoding::Cp437)
+        }
+        "CP850" | "IBM850" | "IBM-850" | "DOS850" => Some(GedcomEncoding::Cp850),
+        "ASCII" => Some(GedcomEncoding::Ascii),
         "ANSEL" => Some(GedcomEncoding::Ansel),
         _ => None,
     }
@@ -204,6 +272,41 @@ pub fn decode_gedcom_bytes(bytes: &[u8]) -> Result<(String, GedcomEncoding), Ged
     decode_with_encoding(bytes, encoding)
 }
 
+/// Decodes GEDCOM bytes like [`decode_gedcom_bytes`], borrowing UTF-8 and
+/// ASCII input instead of copying it.
+///
+/// A UTF-8 byte order mark is left out of the returned text.
+///
+/// # Errors
+///
+/// Returns `GedcomError::EncodingError` if the bytes cannot be decoded
+///
+/// # Example
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use ged_io::encoding::{decode_borrowed, GedcomEncoding};
+///
+/// let bytes = b"\xEF\xBB\xBF0 HEAD\n1 CHAR UTF-8\n0 TRLR\n";
+/// let (content, encoding) = decode_borrowed(bytes).unwrap();
+/// assert_eq!(encoding, GedcomEncoding::Utf8);
+/// assert!(matches!(content, Cow::Borrowed("0 HEAD\n1 CHAR UTF-8\n0 TRLR\n")));
+/// ```
+pub fn decode_borrowed(bytes: &[u8]) -> Result<(Cow<'_, str>, GedcomEncoding), GedcomError> {
+    match detect_encoding(bytes) {
+        encoding @ (GedcomEncoding::Utf8 | GedcomEncoding::Ascii) => {
+            let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+            let content = std::str::from_utf8(bytes)
+                .map_err(|e| GedcomError::EncodingError(format!("Invalid UTF-8: {e}")))?;
+            Ok((Cow::Borrowed(content), encoding))
+        }
+        encoding => {
+            let (content, encoding) = decode_with_encoding(bytes, encoding)?;
+            Ok((Cow::Owned(content), encoding))
+        }
+    }
+}
+
 /// Decodes GEDCOM bytes using a specific encoding.
 ///
 /// # Arguments
@@ -236,13 +339,13 @@ pub fn decode_with_encoding(
         }
         GedcomEncoding::Utf16Le => decode_utf16(bytes, UTF_16LE)?,
         GedcomEncoding::Utf16Be => decode_utf16(bytes, UTF_16BE)?,
-        GedcomEncoding::Iso8859_1 => {
+        GedcomEncoding::Iso8859_1 | GedcomEncoding::Windows1252 => {
             // Use Windows-1252 which is a superset of ISO-8859-1
             let (decoded, _, had_errors) = WINDOWS_1252.decode(bytes);
             if had_errors {
-                return Err(GedcomError::EncodingError(
-                    "Invalid ISO-8859-1 sequence".to_string(),
-                ));
+                return Err(GedcomError::EncodingError(format!(
+                    "Invalid {encoding} sequence"
+                )));
             }
             decoded.into_owned()
         }
@@ -255,7 +358,18 @@ pub fn decode_with_encoding(
             }
             decoded.into_owned()
         }
-        GedcomEncoding::Ansel => decode_ansel(bytes),
+        GedcomEncoding::MacRoman => {
+            let (decoded, _, had_errors) = MACINTOSH.decode(bytes);
+            if had_errors {
+                return Err(GedcomError::EncodingError(
+                    "Invalid MacRoman sequence".to_string(),
+                ));
+            }
+            decoded.into_owned()
+        }
+        GedcomEncoding::Cp437 => decode_code_page(bytes, &CP437_HIGH),
+        GedcomEncoding::Cp850 => decode_code_page(bytes, &CP850_HIGH),
+        GedcomEncoding::Ansel => decode_ansel(bytes, AnselOptions::default())?,
         GedcomEncoding::Unknown => {
             // Try UTF-8 first, then fall back to ISO-8859-1
             if let Ok(s) = String::from_utf8(bytes.to_vec()) {
@@ -330,153 +444,189 @@ fn ansel_combining_mark(byte: u8) -> Option<char> {
     }
 }
 
-/// ANSEL special characters (0xA1-0xDF, 0xFC-0xFD).
+/// ANSEL special characters (0x88-0x8E, 0xA1-0xDF, 0xFC-0xFD).
 /// Maps ANSEL byte -> Unicode character.
 fn ansel_special_char(byte: u8) -> Option<char> {
     match byte {
-        0xA1 => Some('\u{0141}'),        // Ł - Latin capital L with stroke
-        0xA2 => Some('\u{00D8}'),        // Ø - Latin capital O with stroke
-        0xA3 => Some('\u{0110}'),        // Đ - Latin capital D with stroke
-        0xA4 => Some('\u{00DE}'),        // Þ - Latin capital Thorn
-        0xA5 => Some('\u{00C6}'),        // Æ - Latin capital AE
-        0xA6 => Some('\u{0152}'),        // Œ - Latin capital OE
-        0xA7 => Some('\u{02B9}'),        // ʹ - modifier letter prime (soft sign)
-        0xA8 => Some('\u{00B7}'),        // · - middle dot
-        0xA9 => Some('\u{266D}'),        // ♭ - music flat sign
-        0xAA => Some('\u{00AE}'),        // ® - registered sign
-        0xAB => Some('\u{00B1}'),        // ± - plus-minus sign
-        0xAC => Some('\u{01A0}'),        // Ơ - Latin capital O with horn
-        0xAD => Some('\u{01AF}'),        // Ư - Latin capital U with horn
-        0xAE => Some('\u{02BC}'),        // ʼ - modifier letter apostrophe (alif)
-        0xB0 => Some('\u{02BB}'),        // ʻ - modifier letter turned comma (ayn)
-        0xB1 => Some('\u{0142}'),        // ł - Latin small l with stroke
-        0xB2 => Some('\u{00F8}'),        // ø - Latin small o with stroke
-        0xB3 => Some('\u{0111}'),        // đ - Latin small d with stroke
-        0xB4 => Some('\u{00FE}'),        // þ - Latin small thorn
-        0xB5 => Some('\u{00E6}'),        // æ - Latin small ae
-        0xB6 => Some('\u{0153}'),        // œ - Latin small oe
-        0xB7 => Some('\u{02BA}'),        // ʺ - modifier letter double prime (hard sign)
-        0xB8 => Some('\u{0131}'),        // ı - Latin small dotless i
-        0xB9 => Some('\u{00A3}'),        // £ - pound sign
-        0xBA => Some('\u{00F0}'),        // ð - Latin small eth
-        0xBC => Some('\u{01A1}'),        // ơ - Latin small o with horn
-        0xBD => Some('\u{01B0}'),        // ư - Latin small u with horn
-        0xC0 => Some('\u{00B0}'),        // ° - degree sign
-        0xC1 => Some('\u{2113}'),        // ℓ - script small l
-        0xC2 => Some('\u{2117}'),        // ℗ - sound recording copyright
-        0xC3 => Some('\u{00A9}'),        // © - copyright sign
-        0xC4 => Some('\u{266F}'),        // ♯ - music sharp sign
-        0xC5 => Some('\u{00BF}'),        // ¿ - inverted question mark
-        0xC6 => Some('\u{00A1}'),        // ¡ - inverted exclamation mark
+        0x88 => Some('\u{0098}'), // non-sorting character sequence begins (MARC-8)
+        0x89 => Some('\u{009C}'), // non-sorting character sequence ends (MARC-8)
+        0x8D => Some('\u{200D}'), // zero-width joiner (MARC-8)
+        0x8E => Some('\u{200C}'), // zero-width non-joiner (MARC-8)
+        0xA1 => Some('\u{0141}'), // Ł - Latin capital L with stroke
+        0xA2 => Some('\u{00D8}'), // Ø - Latin capital O with stroke
+        0xA3 => Some('\u{0110}'), // Đ - Latin capital D with stroke
+        0xA4 => Some('\u{00DE}'), // Þ - Latin capital Thorn
+        0xA5 => Some('\u{00C6}'), // Æ - Latin capital AE
+        0xA6 => Some('\u{0152}'), // Œ - Latin capital OE
+        0xA7 => Some('\u{02B9}'), // ʹ - modifier letter prime (soft sign)
+        0xA8 => Some('\u{00B7}'), // · - middle dot
+        0xA9 => Some('\u{266D}'), // ♭ - music flat sign
+        0xAA => Some('\u{00AE}'), // ® - registered sign
+        0xAB => Some('\u{00B1}'), // ± - plus-minus sign
+        0xAC => Some('\u{01A0}'), // Ơ - Latin capital O with horn
+        0xAD => Some('\u{01AF}'), // Ư - Latin capital U with horn
+        0xAE => Some('\u{02BC}'), // ʼ - modifier letter apostrophe (alif)
+        0xB0 => Some('\u{02BB}'), // ʻ - modifier letter turned comma (ayn)
+        0xB1 => Some('\u{0142}'), // ł - Latin small l with stroke
+        0xB2 => Some('\u{00F8}'), // ø - Latin small o with stroke
+        0xB3 => Some('\u{0111}'), // đ - Latin small d with stroke
+        0xB4 => Some('\u{00FE}'), // þ - Latin small thorn
+        0xB5 => Some('\u{00E6}'), // æ - Latin small ae
+        0xB6 => Some('\u{0153}'), // œ - Latin small oe
+        0xB7 => Some('\u{02BA}'), // ʺ - modifier letter double prime (hard sign)
+        0xB8 => Some('\u{0131}'), // ı - Latin small dotless i
+        0xB9 => Some('\u{00A3}'), // £ - pound sign
+        0xBA => Some('\u{00F0}'), // ð - Latin small eth
+        0xBC => Some('\u{01A1}'), // ơ - Latin small o with horn
+        0xBD => Some('\u{01B0}'), // ư - Latin small u with horn
+        0xBE => Some('\u{25A1}'), // □ - empty box (GEDCOM)
+        0xBF => Some('\u{25A0}'), // ■ - black box (GEDCOM)
+        0xC0 => Some('\u{00B0}'), // ° - degree sign
+        0xC1 => Some('\u{2113}'), // ℓ - script small l
+        0xC2 => Some('\u{2117}'), // ℗ - sound recording copyright
+        0xC3 => Some('\u{00A9}'), // © - copyright sign
+        0xC4 => Some('\u{266F}'), // ♯ - music sharp sign
+        0xC5 => Some('\u{00BF}'), // ¿ - inverted question mark
+        0xC6 => Some('\u{00A1}'), // ¡ - inverted exclamation mark
         0xC7 | 0xCF => Some('\u{00DF}'), // ß - eszett (both positions)
-        0xC8 => Some('\u{20AC}'),        // € - euro sign
-        // Extended characters that some GEDCOM files use
-        0xFC => Some('\u{200D}'), // zero-width joiner (used in some implementations)
-        0xFD => Some('\u{200C}'), // zero-width non-joiner
+        0xC8 => Some('\u{20AC}'), // € - euro sign
         _ => None,
     }
 }
 
+/// Options for [`decode_ansel`] and [`encode_ansel`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct AnselOptions {
+    /// Whether bytes and characters ANSEL has no mapping for are an error
+    /// (default: false).
+    ///
+    /// Otherwise unknown bytes decode as their Latin-1 character and
+    /// characters without an ANSEL form encode as `?`.
+    pub strict: bool,
+}
+
 /// Decodes ANSEL-encoded bytes to a UTF-8 string.
 ///
 /// ANSEL (ANSI/NISO Z39.47) is a character encoding used in older GEDCOM files.
 /// It uses:
 /// - ASCII for bytes 0x00-0x7F
-/// - Special characters in 0xA1-0xDF range
+/// - Special characters in 0x88-0xDF, including the MARC-8 additions
 /// - Combining diacritical marks in 0xE0-0xFE range (these precede the base character)
-fn decode_ansel(bytes: &[u8]) -> String {
+///
+/// Several diacritics before one character are written after it in the
+/// same order, as MARC-8 converters do, so the first is the one closest to
+/// the base character. The result is in decomposed form.
+///
+/// # Errors
+///
+/// Returns `GedcomError::EncodingError` for a byte ANSEL has no mapping for
+/// when [`AnselOptions::strict`] is set.
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::encoding::{decode_ansel, AnselOptions};
+///
+/// let name = decode_ansel(&[b'J', b'o', b's', 0xE2, b'e'], AnselOptions::default()).unwrap();
+/// assert_eq!(name, "Jose\u{301}");
+///
+/// let strict = AnselOptions { strict: true };
+/// assert!(decode_ansel(&[0x9F], strict).is_err());
+/// ```
+pub fn decode_ansel(bytes: &[u8], options: AnselOptions) -> Result<String, GedcomError> {
     let mut result = String::with_capacity(bytes.len());
     let mut pending_diacritics: Vec<char> = Vec::new();
-    let mut i = 0;
-
-    while i < bytes.len() {
-        let byte = bytes[i];
 
-        // Check if this is a combining diacritical mark
+    for (offset, &byte) in bytes.iter().enumerate() {
+        // In ANSEL, diacritics precede the base character
+        // Collect them and apply after the base character
         if let Some(combining) = ansel_combining_mark(byte) {
-            // In ANSEL, diacritics precede the base character
-            // Collect them and apply after the base character
             pending_diacritics.push(combining);
-            i += 1;
             continue;
         }
 
-        // Get the character for this byte
         let ch = if byte < 0x80 {
-            // ASCII range
             byte as char
         } else if let Some(special) = ansel_special_char(byte) {
             special
+        } else if options.strict {
+            return Err(GedcomError::EncodingError(format!(
+                "Invalid ANSEL byte 0x{byte:02X} at offset {offset}"
+            )));
         } else {
-            // Unknown byte - use replacement character or pass through
-            // For compatibility, map high bytes to Latin-1 equivalent
-            char::from_u32(u32::from(byte)).unwrap_or('\u{FFFD}')
+            // For compatibility, map unknown high bytes to their Latin-1 equivalent
+            char::from(byte)
         };
 
-        // Output the base character
         result.push(ch);
-
-        // Apply any pending diacritics (in reverse order for proper stacking)
-        for diacritic in pending_diacritics.drain(..) {
-            result.push(diacritic);
-        }
-
-        i += 1;
+        result.extend(pending_diacritics.drain(..));
     }
 
     // If there are leftover diacritics with no base character, append them anyway
-    for diacritic in pending_diacritics {
-        result.push(diacritic);
-    }
-
-    result
+    result.extend(pending_diacritics);
+    Ok(result)
 }
 
 /// Encodes a UTF-8 string to ANSEL bytes.
 ///
-/// This performs a best-effort conversion. Characters that cannot be represented
-/// in ANSEL will be replaced with '?' or their closest ASCII equivalent.
-fn encode_ansel(content: &str) -> Vec<u8> {
+/// Precomposed characters, such as `é` or the Vietnamese `ệ`, are written
+/// as their base character preceded by its diacritics, and combining marks
+/// following a character are added to them in the same order.
+///
+/// # Errors
+///
+/// Returns `GedcomError::EncodingError` for a character ANSEL cannot
+/// represent when [`AnselOptions::strict`] is set; otherwise it is written
+/// as `?`.
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::encoding::{encode_ansel, AnselOptions};
+///
+/// let bytes = encode_ansel("Łódź", AnselOptions::default()).unwrap();
+/// assert_eq!(bytes, [0xA1, 0xE2, b'o', b'd', 0xE2, b'z']);
+///
+/// let strict = AnselOptions { strict: true };
+/// assert!(encode_ansel("北京", strict).is_err());
+/// ```
+pub fn encode_ansel(content: &str, options: AnselOptions) -> Result<Vec<u8>, GedcomError> {
     let mut result = Vec::with_capacity(content.len());
     let mut chars = content.chars().peekable();
+    let mut diacritics = Vec::new();
 
     while let Some(ch) = chars.next() {
-        // Check if this is a base character followed by combining marks
-        let base_byte = unicode_to_ansel_base(ch);
+        // Split a precomposed character into its base and diacritics,
+        // innermost last
+        let mut base = ch;
+        while let Some((inner, diacritic)) = decompose_for_ansel(base) {
+            diacritics.push(diacritic);
+            base = inner;
+        }
+        diacritics.reverse();
 
         // Collect any following combining marks
-        let mut combining_marks = Vec::new();
-        while let Some(&next_ch) = chars.peek() {
-            if let Some(ansel_diacritic) = unicode_combining_to_ansel(next_ch) {
-                combining_marks.push(ansel_diacritic);
-                chars.next();
-            } else {
-                break;
-            }
+        while let Some(diacritic) = chars.peek().copied().and_then(unicode_combining_to_ansel) {
+            diacritics.push(diacritic);
+            chars.next();
         }
 
         // In ANSEL, diacritics precede the base character
-        for diacritic in combining_marks {
-            result.push(diacritic);
-        }
-
-        // Output the base character
-        if let Some(byte) = base_byte {
-            result.push(byte);
-        } else {
-            // Try to find precomposed character mapping
-            if let Some(bytes) = unicode_precomposed_to_ansel(ch) {
-                result.extend_from_slice(&bytes);
-            } else if ch.is_ascii() {
-                result.push(ch as u8);
-            } else {
-                // Cannot encode - use '?'
-                result.push(b'?');
+        result.append(&mut diacritics);
+        // A combining mark with no character before it is kept on its own
+        match unicode_to_ansel_base(base).or_else(|| unicode_combining_to_ansel(base)) {
+            Some(byte) => result.push(byte),
+            None if options.strict => {
+                return Err(GedcomError::EncodingError(format!(
+                    "Cannot encode to ANSEL: unsupported character {ch:?}"
+                )));
             }
+            None => result.push(b'?'),
         }
     }
 
-    result
+    Ok(result)
 }
 
 /// Maps a Unicode base character to its ANSEL byte (if it's a special ANSEL character).
@@ -518,6 +668,12 @@ fn unicode_to_ansel_base(ch: char) -> Option<u8> {
         '\u{00A1}' => Some(0xC6), // ¡
         '\u{00DF}' => Some(0xC7), // ß
         '\u{20AC}' => Some(0xC8), // €
+        '\u{0098}' => Some(0x88), // non-sorting character sequence begins
+        '\u{009C}' => Some(0x89), // non-sorting character sequence ends
+        '\u{200D}' => Some(0x8D), // zero-width joiner
+        '\u{200C}' => Some(0x8E), // zero-width non-joiner
+        '\u{25A1}' => Some(0xBE), // □ - empty box
+        '\u{25A0}' => Some(0xBF), // ■ - black box
         _ if ch.is_ascii() => Some(ch as u8),
         _ => None,
     }
@@ -559,199 +715,868 @@ fn unicode_combining_to_ansel(ch: char) -> Option<u8> {
     }
 }
 
-/// Maps precomposed Unicode characters to ANSEL byte sequences.
-/// This handles common accented characters that are stored as single Unicode codepoints.
-fn unicode_precomposed_to_ansel(ch: char) -> Option<Vec<u8>> {
-    acute_to_ansel(ch)
-        .or_else(|| grave_to_ansel(ch))
-        .or_else(|| circumflex_to_ansel(ch))
-        .or_else(|| tilde_to_ansel(ch))
-        .or_else(|| umlaut_to_ansel(ch))
-        .or_else(|| caron_to_ansel(ch))
-        .or_else(|| ring_to_ansel(ch))
-        .or_else(|| cedilla_to_ansel(ch))
-        .or_else(|| ogonek_to_ansel(ch))
-        .or_else(|| macron_to_ansel(ch))
-        .or_else(|| breve_to_ansel(ch))
-        .or_else(|| dot_above_to_ansel(ch))
-        .or_else(|| double_acute_to_ansel(ch))
+/// Splits a precomposed character into the character it is built on and
+/// the ANSEL byte of its outermost diacritic, or returns `None`.
+///
+/// The character returned may itself be precomposed, as `ê` is for `ệ`.
+#[allow(clippy::too_many_lines)] // One arm per character.
+fn decompose_for_ansel(ch: char) -> Option<(char, u8)> {
+    let (base, diacritic) = match ch {
+        // hook above
+        'Ả' => ('A', 0xE0),
+        'ả' => ('a', 0xE0),
+        'Ẩ' => ('Â', 0xE0),
+        'ẩ' => ('â', 0xE0),
+        'Ẳ' => ('Ă', 0xE0),
+        'ẳ' => ('ă', 0xE0),
+        'Ẻ' => ('E', 0xE0),
+        'ẻ' => ('e', 0xE0),
+        'Ể' => ('Ê', 0xE0),
+        'ể' => ('ê', 0xE0),
+        'Ỉ' => ('I', 0xE0),
+        'ỉ' => ('i', 0xE0),
+        'Ỏ' => ('O', 0xE0),
+        'ỏ' => ('o', 0xE0),
+        'Ổ' => ('Ô', 0xE0),
+        'ổ' => ('ô', 0xE0),
+        'Ở' => ('Ơ', 0xE0),
+        'ở' => ('ơ', 0xE0),
+        'Ủ' => ('U', 0xE0),
+        'ủ' => ('u', 0xE0),
+        'Ử' => ('Ư', 0xE0),
+        'ử' => ('ư', 0xE0),
+        'Ỷ' => ('Y', 0xE0),
+        'ỷ' => ('y', 0xE0),
+        // grave accent
+        'À' => ('A', 0xE1),
+        'È' => ('E', 0xE1),
+        'Ì' => ('I', 0xE1),
+        'Ò' => ('O', 0xE1),
+        'Ù' => ('U', 0xE1),
+        'à' => ('a', 0xE1),
+        'è' => ('e', 0xE1),
+        'ì' => ('i', 0xE1),
+        'ò' => ('o', 0xE1),
+        'ù' => ('u', 0xE1),
+        'Ǜ' => ('Ü', 0xE1),
+        'ǜ' => ('ü', 0xE1),
+        'Ǹ' => ('N', 0xE1),
+        'ǹ' => ('n', 0xE1),
+        'Ḕ' => ('Ē', 0xE1),
+        'ḕ' => ('ē', 0xE1),
+        'Ṑ' => ('Ō', 0xE1),
+        'ṑ' => ('ō', 0xE1),
+        'Ẁ' => ('W', 0xE1),
+        'ẁ' => ('w', 0xE1),
+        'Ầ' => ('Â', 0xE1),
+        'ầ' => ('â', 0xE1),
+        'Ằ' => ('Ă', 0xE1),
+        'ằ' => ('ă', 0xE1),
+        'Ề' => ('Ê', 0xE1),
+        'ề' => ('ê', 0xE1),
+        'Ồ' => ('Ô', 0xE1),
+        'ồ' => ('ô', 0xE1),
+        'Ờ' => ('Ơ', 0xE1),
+        'ờ' => ('ơ', 0xE1),
+        'Ừ' => ('Ư', 0xE1),
+        'ừ' => ('ư', 0xE1),
+        'Ỳ' => ('Y', 0xE1),
+        'ỳ' => ('y', 0xE1),
+        // acute accent
+        'Á' => ('A', 0xE2),
+        'É' => ('E', 0xE2),
+        'Í' => ('I', 0xE2),
+        'Ó' => ('O', 0xE2),
+        'Ú' => ('U', 0xE2),
+        'Ý' => ('Y', 0xE2),
+        'á' => ('a', 0xE2),
+        'é' => ('e', 0xE2),
+        'í' => ('i', 0xE2),
+        'ó' => ('o', 0xE2),
+        'ú' => ('u', 0xE2),
+        'ý' => ('y', 0xE2),
+        'Ć' => ('C', 0xE2),
+        'ć' => ('c', 0xE2),
+        'Ĺ' => ('L', 0xE2),
+        'ĺ' => ('l', 0xE2),
+        'Ń' => ('N', 0xE2),
+        'ń' => ('n', 0xE2),
+        'Ŕ' => ('R', 0xE2),
+        'ŕ' => ('r', 0xE2),
+        'Ś' => ('S', 0xE2),
+        'ś' => ('s', 0xE2),
+        'Ź' => ('Z', 0xE2),
+        'ź' => ('z', 0xE2),
+        'Ǘ' => ('Ü', 0xE2),
+        'ǘ' => ('ü', 0xE2),
+        'Ǵ' => ('G', 0xE2),
+        'ǵ' => ('g', 0xE2),
+        'Ǻ' => ('Å', 0xE2),
+        'ǻ' => ('å', 0xE2),
+        'Ǽ' => ('Æ', 0xE2),
+        'ǽ' => ('æ', 0xE2),
+        'Ǿ' => ('Ø', 0xE2),
+        'ǿ' => ('ø', 0xE2),
+        'Ḉ' => ('Ç', 0xE2),
+        'ḉ' => ('ç', 0xE2),
+        'Ḗ' => ('Ē', 0xE2),
+        'ḗ' => ('ē', 0xE2),
+        'Ḯ' => ('Ï', 0xE2),
+        'ḯ' => ('ï', 0xE2),
+        'Ḱ' => ('K', 0xE2),
+        'ḱ' => ('k', 0xE2),
+        'Ḿ' => ('M', 0xE2),
+        'ḿ' => ('m', 0xE2),
+        'Ṍ' => ('Õ', 0xE2),
+        'ṍ' => ('õ', 0xE2),
+        'Ṓ' => ('Ō', 0xE2),
+        'ṓ' => ('ō', 0xE2),
+        'Ṕ' => ('P', 0xE2),
+        'ṕ' => ('p', 0xE2),
+        'Ṹ' => ('Ũ', 0xE2),
+        'ṹ' => ('ũ', 0xE2),
+        'Ẃ' => ('W', 0xE2),
+        'ẃ' => ('w', 0xE2),
+        'Ấ' => ('Â', 0xE2),
+        'ấ' => ('â', 0xE2),
+        'Ắ' => ('Ă', 0xE2),
+        'ắ' => ('ă', 0xE2),
+        'Ế' => ('Ê', 0xE2),
+        'ế' => ('ê', 0xE2),
+        'Ố' => ('Ô', 0xE2),
+        'ố' => ('ô', 0xE2),
+        'Ớ' => ('Ơ', 0xE2),
+        'ớ' => ('ơ', 0xE2),
+        'Ứ' => ('Ư', 0xE2),
+        'ứ' => ('ư', 0xE2),
+        // circumflex
+        'Â' => ('A', 0xE3),
+        'Ê' => ('E', 0xE3),
+        'Î' => ('I', 0xE3),
+        'Ô' => ('O', 0xE3),
+        'Û' => ('U', 0xE3),
+        'â' => ('a', 0xE3),
+        'ê' => ('e', 0xE3),
+        'î' => ('i', 0xE3),
+        'ô' => ('o', 0xE3),
+        'û' => ('u', 0xE3),
+        'Ĉ' => ('C', 0xE3),
+        'ĉ' => ('c', 0xE3),
+        'Ĝ' => ('G', 0xE3),
+        'ĝ' => ('g', 0xE3),
+        'Ĥ' => ('H', 0xE3),
+        'ĥ' => ('h', 0xE3),
+        'Ĵ' => ('J', 0xE3),
+        'ĵ' => ('j', 0xE3),
+        'Ŝ' => ('S', 0xE3),
+        'ŝ' => ('s', 0xE3),
+        'Ŵ' => ('W', 0xE3),
+        'ŵ' => ('w', 0xE3),
+        'Ŷ' => ('Y', 0xE3),
+        'ŷ' => ('y', 0xE3),
+        'Ẑ' => ('Z', 0xE3),
+        'ẑ' => ('z', 0xE3),
+        'Ậ' => ('Ạ', 0xE3),
+        'ậ' => ('ạ', 0xE3),
+        'Ệ' => ('Ẹ', 0xE3),
+        'ệ' => ('ẹ', 0xE3),
+        'Ộ' => ('Ọ', 0xE3),
+        'ộ' => ('ọ', 0xE3),
+        // tilde
+        'Ã' => ('A', 0xE4),
+        'Ñ' => ('N', 0xE4),
+        'Õ' => ('O', 0xE4),
+        'ã' => ('a', 0xE4),
+        'ñ' => ('n', 0xE4),
+        'õ' => ('o', 0xE4),
+        'Ĩ' => ('I', 0xE4),
+        'ĩ' => ('i', 0xE4),
+        'Ũ' => ('U', 0xE4),
+        'ũ' => ('u', 0xE4),
+        'Ṽ' => ('V', 0xE4),
+        'ṽ' => ('v', 0xE4),
+        'Ẫ' => ('Â', 0xE4),
+        'ẫ' => ('â', 0xE4),
+        'Ẵ' => ('Ă', 0xE4),
+        'ẵ' => ('ă', 0xE4),
+        'Ẽ' => ('E', 0xE4),
+        'ẽ' => ('e', 0xE4),
+        'Ễ' => ('Ê', 0xE4),
+        'ễ' => ('ê', 0xE4),
+        'Ỗ' => ('Ô', 0xE4),
+        'ỗ' => ('ô', 0xE4),
+        'Ỡ' => ('Ơ', 0xE4),
+        'ỡ' => ('ơ', 0xE4),
+        'Ữ' => ('Ư', 0xE4),
+        'ữ' => ('ư', 0xE4),
+        'Ỹ' => ('Y', 0xE4),
+        'ỹ' => ('y', 0xE4),
+        // macron
+        'Ā' => ('A', 0xE5),
+        'ā' => ('a', 0xE5),
+        'Ē' => ('E', 0xE5),
+        'ē' => ('e', 0xE5),
+        'Ī' => ('I', 0xE5),
+        'ī' => ('i', 0xE5),
+        'Ō' => ('O', 0xE5),
+        'ō' => ('o', 0xE5),
+        'Ū' => ('U', 0xE5),
+        'ū' => ('u', 0xE5),
+        'Ǖ' => ('Ü', 0xE5),
+        'ǖ' => ('ü', 0xE5),
+        'Ǟ' => ('Ä', 0xE5),
+        'ǟ' => ('ä', 0xE5),
+        'Ǡ' => ('Ȧ', 0xE5),
+        'ǡ' => ('ȧ', 0xE5),
+        'Ǣ' => ('Æ', 0xE5),
+        'ǣ' => ('æ', 0xE5),
+        'Ǭ' => ('Ǫ', 0xE5),
+        'ǭ' => ('ǫ', 0xE5),
+        'Ȫ' => ('Ö', 0xE5),
+        'ȫ' => ('ö', 0xE5),
+        'Ȭ' => ('Õ', 0xE5),
+        'ȭ' => ('õ', 0xE5),
+        'Ȱ' => ('Ȯ', 0xE5),
+        'ȱ' => ('ȯ', 0xE5),
+        'Ȳ' => ('Y', 0xE5),
+        'ȳ' => ('y', 0xE5),
+        'Ḡ' => ('G', 0xE5),
+        'ḡ' => ('g', 0xE5),
+        'Ḹ' => ('Ḷ', 0xE5),
+        'ḹ' => ('ḷ', 0xE5),
+        'Ṝ' => ('Ṛ', 0xE5),
+        'ṝ' => ('ṛ', 0xE5),
+        // breve
+        'Ă' => ('A', 0xE6),
+        'ă' => ('a', 0xE6),
+        'Ĕ' => ('E', 0xE6),
+        'ĕ' => ('e', 0xE6),
+        'Ğ' => ('G', 0xE6),
+        'ğ' => ('g', 0xE6),
+        'Ĭ' => ('I', 0xE6),
+        'ĭ' => ('i', 0xE6),
+        'Ŏ' => ('O', 0xE6),
+        'ŏ' => ('o', 0xE6),
+        'Ŭ' => ('U', 0xE6),
+        'ŭ' => ('u', 0xE6),
+        'Ḝ' => ('Ȩ', 0xE6),
+        'ḝ' => ('ȩ', 0xE6),
+        'Ặ' => ('Ạ', 0xE6),
+        'ặ' => ('ạ', 0xE6),
+        // dot above
+        'Ċ' => ('C', 0xE7),
+        'ċ' => ('c', 0xE7),
+        'Ė' => ('E', 0xE7),
+        'ė' => ('e', 0xE7),
+        'Ġ' => ('G', 0xE7),
+        'ġ' => ('g', 0xE7),
+        'İ' => ('I', 0xE7),
+        'Ż' => ('Z', 0xE7),
+        'ż' => ('z', 0xE7),
+        'Ȧ' => ('A', 0xE7),
+        'ȧ' => ('a', 0xE7),
+        'Ȯ' => ('O', 0xE7),
+        'ȯ' => ('o', 0xE7),
+        'Ḃ' => ('B', 0xE7),
+        'ḃ' => ('b', 0xE7),
+        'Ḋ' => ('D', 0xE7),
+        'ḋ' => ('d', 0xE7),
+        'Ḟ' => ('F', 0xE7),
+        'ḟ' => ('f', 0xE7),
+        'Ḣ' => ('H', 0xE7),
+        'ḣ' => ('h', 0xE7),
+        'Ṁ' => ('M', 0xE7),
+        'ṁ' => ('m', 0xE7),
+        'Ṅ' => ('N', 0xE7),
+        'ṅ' => ('n', 0xE7),
+        'Ṗ' => ('P', 0xE7),
+        'ṗ' => ('p', 0xE7),
+        'Ṙ' => ('R', 0xE7),
+        'ṙ' => ('r', 0xE7),
+        'Ṡ' => ('S', 0xE7),
+        'ṡ' => ('s', 0xE7),
+        'Ṥ' => ('Ś', 0xE7),
+        'ṥ' => ('ś', 0xE7),
+        'Ṧ' => ('Š', 0xE7),
+        'ṧ' => ('š', 0xE7),
+        'Ṩ' => ('Ṣ', 0xE7),
+        'ṩ' => ('ṣ', 0xE7),
+        'Ṫ' => ('T', 0xE7),
+        'ṫ' => ('t', 0xE7),
+        'Ẇ' => ('W', 0xE7),
+        'ẇ' => ('w', 0xE7),
+        'Ẋ' => ('X', 0xE7),
+        'ẋ' => ('x', 0xE7),
+        'Ẏ' => ('Y', 0xE7),
+        'ẏ' => ('y', 0xE7),
+        // umlaut/diaeresis
+        'Ä' => ('A', 0xE8),
+        'Ë' => ('E', 0xE8),
+        'Ï' => ('I', 0xE8),
+        'Ö' => ('O', 0xE8),
+        'Ü' => ('U', 0xE8),
+        'ä' => ('a', 0xE8),
+        'ë' => ('e', 0xE8),
+        'ï' => ('i', 0xE8),
+        'ö' => ('o', 0xE8),
+        'ü' => ('u', 0xE8),
+        'ÿ' => ('y', 0xE8),
+        'Ÿ' => ('Y', 0xE8),
+        'Ḧ' => ('H', 0xE8),
+        'ḧ' => ('h', 0xE8),
+        'Ṏ' => ('Õ', 0xE8),
+        'ṏ' => ('õ', 0xE8),
+        'Ṻ' => ('Ū', 0xE8),
+        'ṻ' => ('ū', 0xE8),
+        'Ẅ' => ('W', 0xE8),
+        'ẅ' => ('w', 0xE8),
+        'Ẍ' => ('X', 0xE8),
+        'ẍ' => ('x', 0xE8),
+        'ẗ' => ('t', 0xE8),
+        // caron/hacek
+        'Č' => ('C', 0xE9),
+        'č' => ('c', 0xE9),
+        'Ď' => ('D', 0xE9),
+        'ď' => ('d', 0xE9),
+        'Ě' => ('E', 0xE9),
+        'ě' => ('e', 0xE9),
+        'Ľ' => ('L', 0xE9),
+        'ľ' => ('l', 0xE9),
+        'Ň' => ('N', 0xE9),
+        'ň' => ('n', 0xE9),
+        'Ř' => ('R', 0xE9),
+        'ř' => ('r', 0xE9),
+        'Š' => ('S', 0xE9),
+        'š' => ('s', 0xE9),
+        'Ť' => ('T', 0xE9),
+        'ť' => ('t', 0xE9),
+        'Ž' => ('Z', 0xE9),
+        'ž' => ('z', 0xE9),
+        'Ǎ' => ('A', 0xE9),
+        'ǎ' => ('a', 0xE9),
+        'Ǐ' => ('I', 0xE9),
+        'ǐ' => ('i', 0xE9),
+        'Ǒ' => ('O', 0xE9),
+        'ǒ' => ('o', 0xE9),
+        'Ǔ' => ('U', 0xE9),
+        'ǔ' => ('u', 0xE9),
+        'Ǚ' => ('Ü', 0xE9),
+        'ǚ' => ('ü', 0xE9),
+        'Ǧ' => ('G', 0xE9),
+        'ǧ' => ('g', 0xE9),
+        'Ǩ' => ('K', 0xE9),
+        'ǩ' => ('k', 0xE9),
+        'ǰ' => ('j', 0xE9),
+        'Ȟ' => ('H', 0xE9),
+        'ȟ' => ('h', 0xE9),
+        // ring above
+        'Å' => ('A', 0xEA),
+        'å' => ('a', 0xEA),
+        'Ů' => ('U', 0xEA),
+        'ů' => ('u', 0xEA),
+        'ẘ' => ('w', 0xEA),
+        'ẙ' => ('y', 0xEA),
+        // double acute
+        'Ő' => ('O', 0xEE),
+        'ő' => ('o', 0xEE),
+        'Ű' => ('U', 0xEE),
+        'ű' => ('u', 0xEE),
+        // cedilla
+        'Ç' => ('C', 0xF0),
+        'ç' => ('c', 0xF0),
+        'Ģ' => ('G', 0xF0),
+        'ģ' => ('g', 0xF0),
+        'Ķ' => ('K', 0xF0),
+        'ķ' => ('k', 0xF0),
+        'Ļ' => ('L', 0xF0),
+        'ļ' => ('l', 0xF0),
+        'Ņ' => ('N', 0xF0),
+        'ņ' => ('n', 0xF0),
+        'Ŗ' => ('R', 0xF0),
+        'ŗ' => ('r', 0xF0),
+        'Ş' => ('S', 0xF0),
+        'ş' => ('s', 0xF0),
+        'Ţ' => ('T', 0xF0),
+        'ţ' => ('t', 0xF0),
+        'Ȩ' => ('E', 0xF0),
+        'ȩ' => ('e', 0xF0),
+        'Ḑ' => ('D', 0xF0),
+        'ḑ' => ('d', 0xF0),
+        'Ḩ' => ('H', 0xF0),
+        'ḩ' => ('h', 0xF0),
+        // ogonek
+        'Ą' => ('A', 0xF1),
+        'ą' => ('a', 0xF1),
+        'Ę' => ('E', 0xF1),
+        'ę' => ('e', 0xF1),
+        'Į' => ('I', 0xF1),
+        'į' => ('i', 0xF1),
+        'Ų' => ('U', 0xF1),
+        'ų' => ('u', 0xF1),
+        'Ǫ' => ('O', 0xF1),
+        'ǫ' => ('o', 0xF1),
+        // dot below
+        'Ḅ' => ('B', 0xF2),
+        'ḅ' => ('b', 0xF2),
+        'Ḍ' => ('D', 0xF2),
+        'ḍ' => ('d', 0xF2),
+        'Ḥ' => ('H', 0xF2),
+        'ḥ' => ('h', 0xF2),
+        'Ḳ' => ('K', 0xF2),
+        'ḳ' => ('k', 0xF2),
+        'Ḷ' => ('L', 0xF2),
+        'ḷ' => ('l', 0xF2),
+        'Ṃ' => ('M', 0xF2),
+        'ṃ' => ('m', 0xF2),
+        'Ṇ' => ('N', 0xF2),
+        'ṇ' => ('n', 0xF2),
+        'Ṛ' => ('R', 0xF2),
+        'ṛ' => ('r', 0xF2),
+        'Ṣ' => ('S', 0xF2),
+        'ṣ' => ('s', 0xF2),
+        'Ṭ' => ('T', 0xF2),
+        'ṭ' => ('t', 0xF2),
+        'Ṿ' => ('V', 0xF2),
+        'ṿ' => ('v', 0xF2),
+        'Ẉ' => ('W', 0xF2),
+        'ẉ' => ('w', 0xF2),
+        'Ẓ' => ('Z', 0xF2),
+        'ẓ' => ('z', 0xF2),
+        'Ạ' => ('A', 0xF2),
+        'ạ' => ('a', 0xF2),
+        'Ẹ' => ('E', 0xF2),
+        'ẹ' => ('e', 0xF2),
+        'Ị' => ('I', 0xF2),
+        'ị' => ('i', 0xF2),
+        'Ọ' => ('O', 0xF2),
+        'ọ' => ('o', 0xF2),
+        'Ợ' => ('Ơ', 0xF2),
+        'ợ' => ('ơ', 0xF2),
+        'Ụ' => ('U', 0xF2),
+        'ụ' => ('u', 0xF2),
+        'Ự' => ('Ư', 0xF2),
+        'ự' => ('ư', 0xF2),
+        'Ỵ' => ('Y', 0xF2),
+        'ỵ' => ('y', 0xF2),
+        // double dot below
+        'Ṳ' => ('U', 0xF3),
+        'ṳ' => ('u', 0xF3),
+        // ring below
+        'Ḁ' => ('A', 0xF4),
+        'ḁ' => ('a', 0xF4),
+        // comma below
+        'Ș' => ('S', 0xF7),
+        'ș' => ('s', 0xF7),
+        'Ț' => ('T', 0xF7),
+        'ț' => ('t', 0xF7),
+        // half ring below
+        'Ḫ' => ('H', 0xF9),
+        'ḫ' => ('h', 0xF9),
+        _ => return None,
+    };
+    Some((base, diacritic))
 }
 
-fn acute_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Á' => Some(vec![0xE2, b'A']),
-        'á' => Some(vec![0xE2, b'a']),
-        'É' => Some(vec![0xE2, b'E']),
-        'é' => Some(vec![0xE2, b'e']),
-        'Í' => Some(vec![0xE2, b'I']),
-        'í' => Some(vec![0xE2, b'i']),
-        'Ó' => Some(vec![0xE2, b'O']),
-        'ó' => Some(vec![0xE2, b'o']),
-        'Ú' => Some(vec![0xE2, b'U']),
-        'ú' => Some(vec![0xE2, b'u']),
-        'Ý' => Some(vec![0xE2, b'Y']),
-        'ý' => Some(vec![0xE2, b'y']),
-        'Ć' => Some(vec![0xE2, b'C']),
-        'ć' => Some(vec![0xE2, b'c']),
-        'Ń' => Some(vec![0xE2, b'N']),
-        'ń' => Some(vec![0xE2, b'n']),
-        'Ś' => Some(vec![0xE2, b'S']),
-        'ś' => Some(vec![0xE2, b's']),
-        'Ź' => Some(vec![0xE2, b'Z']),
-        'ź' => Some(vec![0xE2, b'z']),
-        _ => None,
-    }
+/// The characters of bytes 0x80-0xFF in code page 437, the character set of
+/// the original IBM PC and of MS-DOS in the United States.
+const CP437_HIGH: [char; 128] = [
+    '\u{00C7}', '\u{00FC}', '\u{00E9}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E5}', '\u{00E7}',
+    '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00EF}', '\u{00EE}', '\u{00EC}', '\u{00C4}', '\u{00C5}',
+    '\u{00C9}', '\u{00E6}', '\u{00C6}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00FB}', '\u{00F9}',
+    '\u{00FF}', '\u{00D6}', '\u{00DC}', '\u{00A2}', '\u{00A3}', '\u{00A5}', '\u{20A7}', '\u{0192}',
+    '\u{00E1}', '\u{00ED}', '\u{00F3}', '\u{00FA}', '\u{00F1}', '\u{00D1}', '\u{00AA}', '\u{00BA}',
+    '\u{00BF}', '\u{2310}', '\u{00AC}', '\u{00BD}', '\u{00BC}', '\u{00A1}', '\u{00AB}', '\u{00BB}',
+    '\u{2591}', '\u{2592}', '\u{2593}', '\u{2502}', '\u{2524}', '\u{2561}', '\u{2562}', '\u{2556}',
+    '\u{2555}', '\u{2563}', '\u{2551}', '\u{2557}', '\u{255D}', '\u{255C}', '\u{255B}', '\u{2510}',
+    '\u{2514}', '\u{2534}', '\u{252C}', '\u{251C}', '\u{2500}', '\u{253C}', '\u{255E}', '\u{255F}',
+    '\u{255A}', '\u{2554}', '\u{2569}', '\u{2566}', '\u{2560}', '\u{2550}', '\u{256C}', '\u{2567}',
+    '\u{2568}', '\u{2564}', '\u{2565}', '\u{2559}', '\u{2558}', '\u{2552}', '\u{2553}', '\u{256B}',
+    '\u{256A}', '\u{2518}', '\u{250C}', '\u{2588}', '\u{2584}', '\u{258C}', '\u{2590}', '\u{2580}',
+    '\u{03B1}', '\u{00DF}', '\u{0393}', '\u{03C0}', '\u{03A3}', '\u{03C3}', '\u{00B5}', '\u{03C4}',
+    '\u{03A6}', '\u{0398}', '\u{03A9}', '\u{03B4}', '\u{221E}', '\u{03C6}', '\u{03B5}', '\u{2229}',
+    '\u{2261}', '\u{00B1}', '\u{2265}', '\u{2264}', '\u{2320}', '\u{2321}', '\u{00F7}', '\u{2248}',
+    '\u{00B0}', '\u{2219}', '\u{00B7}', '\u{221A}', '\u{207F}', '\u{00B2}', '\u{25A0}', '\u{00A0}',
+];
+
+/// The characters of bytes 0x80-0xFF in code page 850, the multilingual
+/// MS-DOS character set of Western Europe, which trades most of the
+/// box-drawing and Greek characters of code page 437 for accented capitals.
+const CP850_HIGH: [char; 128] = [
+    '\u{00C7}', '\u{00FC}', '\u{00E9}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E5}', '\u{00E7}',
+    '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00EF}', '\u{00EE}', '\u{00EC}', '\u{00C4}', '\u{00C5}',
+    '\u{00C9}', '\u{00E6}', '\u{00C6}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00FB}', '\u{00F9}',
+    '\u{00FF}', '\u{00D6}', '\u{00DC}', '\u{00F8}', '\u{00A3}', '\u{00D8}', '\u{00D7}', '\u{0192}',
+    '\u{00E1}', '\u{00ED}', '\u{00F3}', '\u{00FA}', '\u{00F1}', '\u{00D1}', '\u{00AA}', '\u{00BA}',
+    '\u{00BF}', '\u{00AE}', '\u{00AC}', '\u{00BD}', '\u{00BC}', '\u{00A1}', '\u{00AB}', '\u{00BB}',
+    '\u{2591}', '\u{2592}', '\u{2593}', '\u{2502}', '\u{2524}', '\u{00C1}', '\u{00C2}', '\u{00C0}',
+    '\u{00A9}', '\u{2563}', '\u{2551}', '\u{2557}', '\u{255D}', '\u{00A2}', '\u{00A5}', '\u{2510}',
+    '\u{2514}', '\u{2534}', '\u{252C}', '\u{251C}', '\u{2500}', '\u{253C}', '\u{00E3}', '\u{00C3}',
+    '\u{255A}', '\u{2554}', '\u{2569}', '\u{2566}', '\u{2560}', '\u{2550}', '\u{256C}', '\u{00A4}',
+    '\u{00F0}', '\u{00D0}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{0131}', '\u{00CD}', '\u{00CE}',
+    '\u{00CF}', '\u{2518}', '\u{250C}', '\u{2588}', '\u{2584}', '\u{00A6}', '\u{00CC}', '\u{2580}',
+    '\u{00D3}', '\u{00DF}', '\u{00D4}', '\u{00D2}', '\u{00F5}', '\u{00D5}', '\u{00B5}', '\u{00FE}',
+    '\u{00DE}', '\u{00DA}', '\u{00DB}', '\u{00D9}', '\u{00FD}', '\u{00DD}', '\u{00AF}', '\u{00B4}',
+    '\u{00AD}', '\u{00B1}', '\u{2017}', '\u{00BE}', '\u{00B6}', '\u{00A7}', '\u{00F7}', '\u{00B8}',
+    '\u{00B0}', '\u{00A8}', '\u{00B7}', '\u{00B9}', '\u{00B3}', '\u{00B2}', '\u{25A0}', '\u{00A0}',
+];
+
+/// Decodes bytes in a DOS code page. Every byte is a character, so this
+/// cannot fail; bytes below 0x80 are ASCII, control characters included.
+fn decode_code_page(bytes: &[u8], table: &[char; 128]) -> String {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x00..=0x7F => char::from(b),
+            _ => table[usize::from(b - 0x80)],
+        })
+        .collect()
 }
 
-fn grave_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'À' => Some(vec![0xE1, b'A']),
-        'à' => Some(vec![0xE1, b'a']),
-        'È' => Some(vec![0xE1, b'E']),
-        'è' => Some(vec![0xE1, b'e']),
-        'Ì' => Some(vec![0xE1, b'I']),
-        'ì' => Some(vec![0xE1, b'i']),
-        'Ò' => Some(vec![0xE1, b'O']),
-        'ò' => Some(vec![0xE1, b'o']),
-        'Ù' => Some(vec![0xE1, b'U']),
-        'ù' => Some(vec![0xE1, b'u']),
-        _ => None,
-    }
+/// Encodes text in a DOS code page, or returns `None` if a character is
+/// not in it.
+fn encode_code_page(content: &str, table: &[char; 128]) -> Option<Vec<u8>> {
+    content
+        .chars()
+        .map(|c| {
+            if c.is_ascii() {
+                u8::try_from(c).ok()
+            } else {
+                let index = table.iter().position(|&t| t == c)?;
+                u8::try_from(index + 0x80).ok()
+            }
+        })
+        .collect()
 }
 
-fn circumflex_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Â' => Some(vec![0xE3, b'A']),
-        'â' => Some(vec![0xE3, b'a']),
-        'Ê' => Some(vec![0xE3, b'E']),
-        'ê' => Some(vec![0xE3, b'e']),
-        'Î' => Some(vec![0xE3, b'I']),
-        'î' => Some(vec![0xE3, b'i']),
-        'Ô' => Some(vec![0xE3, b'O']),
-        'ô' => Some(vec![0xE3, b'o']),
-        'Û' => Some(vec![0xE3, b'U']),
-        'û' => Some(vec![0xE3, b'u']),
-        _ => None,
+/// Number of leading bytes buffered to detect the encoding of a reader.
+const DETECTION_PREFIX_LEN: usize = 4096;
+
+/// Size of the chunks read after the detection prefix.
+const READ_CHUNK_LEN: usize = 64 * 1024;
+
+/// Reads GEDCOM data from a reader and decodes it to a UTF-8 string.
+///
+/// Only the first few kilobytes are buffered to detect the encoding (BOM,
+/// then the `CHAR` tag, then byte heuristics). The rest is transcoded chunk
+/// by chunk as it is read, so the raw bytes are never held in memory
+/// alongside the decoded text.
+///
+/// Detection only sees the start of the input. A file without BOM or `CHAR`
+/// tag whose first non-ASCII byte comes late is therefore decoded as UTF-8;
+/// use [`decode_gedcom_bytes`] for such files.
+///
+/// # Arguments
+///
+/// * `reader` - The source of the GEDCOM bytes
+/// * `max_size` - Optional limit on the number of bytes read
+///
+/// # Returns
+///
+/// A tuple of (decoded string, detected encoding) or an error
+///
+/// # Errors
+///
+/// Returns `GedcomError::EncodingError` if the input is not valid in the
+/// detected encoding, `GedcomError::FileSizeLimitExceeded` if more than
+/// `max_size` bytes are available (checked as reading goes, so the limit
+/// holds even when the input has no known length), and `GedcomError::IoError` if reading fails.
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::encoding::{decode_reader, GedcomEncoding};
+///
+/// let bytes: &[u8] = b"0 HEAD\n1 CHAR UTF-8\n0 TRLR\n";
+/// let (content, encoding) = decode_reader(bytes, None).unwrap();
+/// assert_eq!(encoding, GedcomEncoding::Utf8);
+/// assert!(content.contains("HEAD"));
+/// ```
+pub fn decode_reader<R: Read>(
+    mut reader: R,
+    max_size: Option<usize>,
+) -> Result<(String, GedcomEncoding), GedcomError> {
+    let mut total = 0;
+    let mut read_next = |chunk: &mut [u8]| -> Result<usize, GedcomError> {
+        let len = read_chunk(&mut reader, chunk)?;
+        total += len;
+        match max_size {
+            Some(max_size) if total > max_size => Err(GedcomError::FileSizeLimitExceeded {
+                size: total,
+                max_size,
+            }),
+            _ => Ok(len),
+        }
+    };
+
+    let mut chunk = vec![0; DETECTION_PREFIX_LEN];
+    let mut len = read_next(&mut chunk)?;
+    let mut last = len < chunk.len();
+
+    let encoding = detect_prefix_encoding(&chunk[..len], last);
+
+    let mut decoder = StreamDecoder::new(encoding);
+    let mut content = String::new();
+    loop {
+        decoder.decode(&chunk[..len], &mut content, last)?;
+        if last {
+            break;
+        }
+
+        chunk.resize(READ_CHUNK_LEN, 0);
+        len = read_next(&mut chunk)?;
+        last = len < chunk.len();
     }
-}
 
-fn tilde_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ã' => Some(vec![0xE4, b'A']),
-        'ã' => Some(vec![0xE4, b'a']),
-        'Ñ' => Some(vec![0xE4, b'N']),
-        'ñ' => Some(vec![0xE4, b'n']),
-        'Õ' => Some(vec![0xE4, b'O']),
-        'õ' => Some(vec![0xE4, b'o']),
-        _ => None,
+    // ASCII was only seen in the prefix; later bytes were decoded as UTF-8.
+    if encoding == GedcomEncoding::Ascii && !content.is_ascii() {
+        return Ok((content, GedcomEncoding::Utf8));
     }
+    Ok((content, encoding))
 }
 
-fn umlaut_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ä' => Some(vec![0xE8, b'A']),
-        'ä' => Some(vec![0xE8, b'a']),
-        'Ë' => Some(vec![0xE8, b'E']),
-        'ë' => Some(vec![0xE8, b'e']),
-        'Ï' => Some(vec![0xE8, b'I']),
-        'ï' => Some(vec![0xE8, b'i']),
-        'Ö' => Some(vec![0xE8, b'O']),
-        'ö' => Some(vec![0xE8, b'o']),
-        'Ü' => Some(vec![0xE8, b'U']),
-        'ü' => Some(vec![0xE8, b'u']),
-        'Ÿ' => Some(vec![0xE8, b'Y']),
-        'ÿ' => Some(vec![0xE8, b'y']),
-        _ => None,
+/// Reads all the bytes of a reader, failing with
+/// `GedcomError::FileSizeLimitExceeded` as soon as more than `max_size` are
+/// available.
+pub(crate) fn read_limited<R: Read>(
+    reader: R,
+    max_size: Option<usize>,
+) -> Result<Vec<u8>, GedcomError> {
+    let mut bytes = Vec::new();
+    let Some(max_size) = max_size else {
+        let mut reader = reader;
+        reader.read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    };
+    let limit = u64::try_from(max_size)
+        .unwrap_or(u64::MAX)
+        .saturating_add(1);
+    reader.take(limit).read_to_end(&mut bytes)?;
+    if bytes.len() > max_size {
+        return Err(GedcomError::FileSizeLimitExceeded {
+            size: bytes.len(),
+            max_size,
+        });
     }
+    Ok(bytes)
 }
 
-fn caron_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Č' => Some(vec![0xE9, b'C']),
-        'č' => Some(vec![0xE9, b'c']),
-        'Ě' => Some(vec![0xE9, b'E']),
-        'ě' => Some(vec![0xE9, b'e']),
-        'Ř' => Some(vec![0xE9, b'R']),
-        'ř' => Some(vec![0xE9, b'r']),
-        'Š' => Some(vec![0xE9, b'S']),
-        'š' => Some(vec![0xE9, b's']),
-        'Ž' => Some(vec![0xE9, b'Z']),
-        'ž' => Some(vec![0xE9, b'z']),
-        _ => None,
-    }
+/// Detects the encoding of the first bytes of an input.
+///
+/// `complete` tells whether `prefix` holds the whole input.
+fn detect_prefix_encoding(prefix: &[u8], complete: bool) -> GedcomEncoding {
+    // A multi-byte character cut at the end of the prefix must not make
+    // valid UTF-8 look like a legacy encoding.
+    let prefix = match std::str::from_utf8(prefix) {
+        Err(e) if !complete && e.error_len().is_none() => &prefix[..e.valid_up_to()],
+        _ => prefix,
+    };
+    detect_encoding(prefix)
 }
 
-fn ring_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Å' => Some(vec![0xEA, b'A']),
-        'å' => Some(vec![0xEA, b'a']),
-        'Ů' => Some(vec![0xEA, b'U']),
-        'ů' => Some(vec![0xEA, b'u']),
-        _ => None,
-    }
+/// A [`BufRead`] adapter that transcodes a GEDCOM byte stream to UTF-8.
+///
+/// The encoding is detected from the first few kilobytes when the reader is
+/// created, and every later chunk is converted as it is read, so the
+/// streaming APIs ([`GedcomStreamParser`](crate::GedcomStreamParser),
+/// [`StreamTokenizer`](crate::tokenizer::StreamTokenizer)) can process
+/// UTF-16, ISO-8859-x and ANSEL files of any size. Input that is invalid in
+/// the detected encoding makes reads fail with `io::ErrorKind::InvalidData`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::BufRead;
+/// use ged_io::encoding::{DecodingReader, GedcomEncoding};
+///
+/// let bytes: &[u8] = b"0 HEAD\n1 CHAR ISO-8859-1\n1 NOTE caf\xE9\n0 TRLR\n";
+/// let reader = DecodingReader::new(bytes).unwrap();
+/// assert_eq!(reader.encoding(), GedcomEncoding::Iso8859_1);
+///
+/// let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+/// assert_eq!(lines[2], "1 NOTE café");
+/// ```
+pub struct DecodingReader<R: Read> {
+    reader: R,
+    encoding: GedcomEncoding,
+    decoder: StreamDecoder,
+    /// Raw bytes read but not decoded yet.
+    input: Vec<u8>,
+    input_len: usize,
+    /// Whether `input` ends the stream.
+    input_last: bool,
+    /// Decoded text not yet consumed, from `pos` on.
+    output: String,
+    pos: usize,
+    done: bool,
 }
 
-fn cedilla_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ç' => Some(vec![0xF0, b'C']),
-        'ç' => Some(vec![0xF0, b'c']),
-        'Ş' => Some(vec![0xF0, b'S']),
-        'ş' => Some(vec![0xF0, b's']),
-        _ => None,
+impl<R: Read> DecodingReader<R> {
+    /// Wraps `reader`, detecting its encoding from the first bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::IoError` if reading the first bytes fails.
+    pub fn new(mut reader: R) -> Result<Self, GedcomError> {
+        let mut input = vec![0; DETECTION_PREFIX_LEN];
+        let input_len = read_chunk(&mut reader, &mut input)?;
+        let input_last = input_len < input.len();
+        let encoding = detect_prefix_encoding(&input[..input_len], input_last);
+        Ok(Self {
+            reader,
+            encoding,
+            decoder: StreamDecoder::new(encoding),
+            input,
+            input_len,
+            input_last,
+            output: String::new(),
+            pos: 0,
+            done: false,
+        })
     }
-}
 
-fn ogonek_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ą' => Some(vec![0xF1, b'A']),
-        'ą' => Some(vec![0xF1, b'a']),
-        'Ę' => Some(vec![0xF1, b'E']),
-        'ę' => Some(vec![0xF1, b'e']),
-        _ => None,
+    /// Wraps `reader`, decoding it with a known encoding.
+    pub fn with_encoding(reader: R, encoding: GedcomEncoding) -> Self {
+        Self {
+            reader,
+            encoding,
+            decoder: StreamDecoder::new(encoding),
+            input: Vec::new(),
+            input_len: 0,
+            input_last: false,
+            output: String::new(),
+            pos: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the encoding the input is decoded from.
+    pub fn encoding(&self) -> GedcomEncoding {
+        self.encoding
     }
 }
 
-fn macron_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ā' => Some(vec![0xE5, b'A']),
-        'ā' => Some(vec![0xE5, b'a']),
-        'Ē' => Some(vec![0xE5, b'E']),
-        'ē' => Some(vec![0xE5, b'e']),
-        'Ī' => Some(vec![0xE5, b'I']),
-        'ī' => Some(vec![0xE5, b'i']),
-        'Ō' => Some(vec![0xE5, b'O']),
-        'ō' => Some(vec![0xE5, b'o']),
-        'Ū' => Some(vec![0xE5, b'U']),
-        'ū' => Some(vec![0xE5, b'u']),
-        _ => None,
+impl<R: Read> BufRead for DecodingReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        while self.pos == self.output.len() && !self.done {
+            self.output.clear();
+            self.pos = 0;
+            if self.input_len == 0 && !self.input_last {
+                self.input.resize(READ_CHUNK_LEN, 0);
+                self.input_len = read_chunk(&mut self.reader, &mut self.input)?;
+                self.input_last = self.input_len < self.input.len();
+            }
+            self.decoder
+                .decode(
+                    &self.input[..self.input_len],
+                    &mut self.output,
+                    self.input_last,
+                )
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+            self.input_len = 0;
+            self.done = self.input_last;
+        }
+        Ok(&self.output.as_bytes()[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.output.len());
     }
 }
 
-fn breve_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ă' => Some(vec![0xE6, b'A']),
-        'ă' => Some(vec![0xE6, b'a']),
-        _ => None,
+impl<R: Read> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
     }
 }
 
-fn dot_above_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ż' => Some(vec![0xE7, b'Z']),
-        'ż' => Some(vec![0xE7, b'z']),
-        'Ġ' => Some(vec![0xE7, b'G']),
-        'ġ' => Some(vec![0xE7, b'g']),
-        _ => None,
+/// Fills `buf` from `reader`, stopping early only at end of input.
+fn read_chunk<R: Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
     }
+    Ok(filled)
 }
 
-fn double_acute_to_ansel(ch: char) -> Option<Vec<u8>> {
-    match ch {
-        'Ő' => Some(vec![0xEE, b'O']),
-        'ő' => Some(vec![0xEE, b'o']),
-        'Ű' => Some(vec![0xEE, b'U']),
-        'ű' => Some(vec![0xEE, b'u']),
-        _ => None,
+/// Incremental decoder used by [`decode_reader`] and the transcoding adapters.
+enum StreamDecoder {
+    /// An encoding handled by `encoding_rs`, which keeps partial characters between chunks.
+    Standard(Decoder),
+    /// ANSEL, holding back trailing diacritics until their base character arrives.
+    Ansel(Vec<u8>),
+    /// A DOS code page, one character per byte.
+    CodePage(&'static [char; 128]),
+}
+
+impl StreamDecoder {
+    fn new(encoding: GedcomEncoding) -> Self {
+        let standard = match encoding {
+            GedcomEncoding::Ansel => return StreamDecoder::Ansel(Vec::new()),
+            GedcomEncoding::Cp437 => return StreamDecoder::CodePage(&CP437_HIGH),
+            GedcomEncoding::Cp850 => return StreamDecoder::CodePage(&CP850_HIGH),
+            GedcomEncoding::MacRoman => MACINTOSH,
+            GedcomEncoding::Utf16Le => UTF_16LE,
+            GedcomEncoding::Utf16Be => UTF_16BE,
+            GedcomEncoding::Iso8859_1 | GedcomEncoding::Windows1252 => WINDOWS_1252,
+            GedcomEncoding::Iso8859_15 => ISO_8859_15,
+            GedcomEncoding::Utf8 | GedcomEncoding::Ascii | GedcomEncoding::Unknown => UTF_8,
+        };
+        StreamDecoder::Standard(standard.new_decoder_with_bom_removal())
+    }
+
+    fn decode(
+        &mut self,
+        mut bytes: &[u8],
+        out: &mut String,
+        last: bool,
+    ) -> Result<(), GedcomError> {
+        match self {
+            StreamDecoder::Standard(decoder) => loop {
+                let needed = decoder
+                    .max_utf8_buffer_length_without_replacement(bytes.len())
+                    .unwrap_or(bytes.len());
+                out.reserve(needed);
+                let (result, read) = decoder.decode_to_string_without_replacement(bytes, out, last);
+                bytes = &bytes[read..];
+                match result {
+                    DecoderResult::InputEmpty => return Ok(()),
+                    DecoderResult::OutputFull => {}
+                    DecoderResult::Malformed(_, _) => {
+                        return Err(GedcomError::EncodingError(format!(
+                            "Invalid {} sequence",
+                            decoder.encoding().name()
+                        )));
+                    }
+                }
+            },
+            StreamDecoder::Ansel(pending) => {
+                pending.extend_from_slice(bytes);
+                let split = if last {
+                    pending.len()
+                } else {
+                    pending
+                        .iter()
+                        .rposition(|&b| ansel_combining_mark(b).is_none())
+                        .map_or(0, |i| i + 1)
+                };
+                out.push_str(&decode_ansel(&pending[..split], AnselOptions::default())?);
+                pending.drain(..split);
+                Ok(())
+            }
+            StreamDecoder::CodePage(table) => {
+                out.push_str(&decode_code_page(bytes, table));
+                Ok(())
+            }
+        }
     }
 }
 
@@ -771,7 +1596,13 @@ fn double_acute_to_ansel(ch: char) -> Option<Vec<u8>> {
 /// Returns `GedcomError::EncodingError` if the string cannot be encoded
 pub fn encode_to_bytes(content: &str, encoding: GedcomEncoding) -> Result<Vec<u8>, GedcomError> {
     match encoding {
-        GedcomEncoding::Utf8 | GedcomEncoding::Ascii | GedcomEncoding::Unknown => {
+        GedcomEncoding::Utf8 | GedcomEncoding::Unknown => Ok(content.as_bytes().to_vec()),
+        GedcomEncoding::Ascii => {
+            if !content.is_ascii() {
+                return Err(GedcomError::EncodingError(
+                    "Cannot encode to ASCII: contains non-ASCII characters".to_string(),
+                ));
+            }
             Ok(content.as_bytes().to_vec())
         }
         GedcomEncoding::Utf16Le => {
@@ -788,11 +1619,20 @@ pub fn encode_to_bytes(content: &str, encoding: GedcomEncoding) -> Result<Vec<u8
             }
             Ok(bytes)
         }
-        GedcomEncoding::Iso8859_1 => {
+        GedcomEncoding::Iso8859_1 => content
+            .chars()
+            .map(|c| u8::try_from(u32::from(c)).ok())
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| {
+                GedcomError::EncodingError(
+                    "Cannot encode to ISO-8859-1: contains unsupported characters".to_string(),
+                )
+            }),
+        GedcomEncoding::Windows1252 => {
             let (encoded, _, had_errors) = WINDOWS_1252.encode(content);
             if had_errors {
                 return Err(GedcomError::EncodingError(
-                    "Cannot encode to ISO-8859-1: contains unsupported characters".to_string(),
+                    "Cannot encode to Windows-1252: contains unsupported characters".to_string(),
                 ));
             }
             Ok(encoded.into_owned())
@@ -806,7 +1646,194 @@ pub fn encode_to_bytes(content: &str, encoding: GedcomEncoding) -> Result<Vec<u8
             }
             Ok(encoded.into_owned())
         }
-        GedcomEncoding::Ansel => Ok(encode_ansel(content)),
+        GedcomEncoding::MacRoman => {
+            let (encoded, _, had_errors) = MACINTOSH.encode(content);
+            if had_errors {
+                return Err(GedcomError::EncodingError(
+                    "Cannot encode to MacRoman: contains unsupported characters".to_string(),
+                ));
+            }
+            Ok(encoded.into_owned())
+        }
+        GedcomEncoding::Cp437 | GedcomEncoding::Cp850 => {
+            let table = if encoding == GedcomEncoding::Cp437 {
+                &CP437_HIGH
+            } else {
+                &CP850_HIGH
+            };
+            encode_code_page(content, table).ok_or_else(|| {
+                GedcomError::EncodingError(format!(
+                    "Cannot encode to {encoding}: contains unsupported characters"
+                ))
+            })
+        }
+        GedcomEncoding::Ansel => encode_ansel(content, AnselOptions::default()),
+    }
+}
+
+/// The kind of conformance problem found by [`validate_utf8_strict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Utf8IssueKind {
+    /// Bytes that do not form a valid UTF-8 sequence.
+    InvalidSequence,
+    /// A character banned by GEDCOM 7.0: C0 and C1 controls other than tab,
+    /// line feed and carriage return, DEL, and the noncharacters U+FFFE/U+FFFF.
+    DisallowedCharacter(char),
+    /// The last line is not terminated by a line break.
+    MissingFinalNewline,
+}
+
+/// A single problem reported by [`validate_utf8_strict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Utf8Issue {
+    /// Byte offset in the input at which the problem starts.
+    pub offset: usize,
+    /// What is wrong at that offset.
+    pub kind: Utf8IssueKind,
+}
+
+impl std::fmt::Display for Utf8Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            Utf8IssueKind::InvalidSequence => {
+                write!(f, "invalid UTF-8 sequence at byte {}", self.offset)
+            }
+            Utf8IssueKind::DisallowedCharacter(c) => write!(
+                f,
+                "disallowed character U+{:04X} at byte {}",
+                u32::from(c),
+                self.offset
+            ),
+            Utf8IssueKind::MissingFinalNewline => {
+                write!(f, "missing final newline at byte {}", self.offset)
+            }
+        }
+    }
+}
+
+/// The result of a strict UTF-8 conformance scan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Utf8ValidationReport {
+    /// Problems found, in the order they appear in the input.
+    pub issues: Vec<Utf8Issue>,
+}
+
+impl Utf8ValidationReport {
+    /// Returns true if no problems were found.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl std::fmt::Display for Utf8ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if GEDCOM 7.0 bans `c` from appearing in a file.
+fn is_disallowed_gedcom7_char(c: char) -> bool {
+    match c {
+        '\t' | '\n' | '\r' => false,
+        '\u{0}'..='\u{1F}' | '\u{7F}'..='\u{9F}' | '\u{FFFE}' | '\u{FFFF}' => true,
+        _ => false,
+    }
+}
+
+/// Scans bytes for strict UTF-8 conformance as required by GEDCOM 7.0.
+///
+/// Unlike [`decode_gedcom_bytes`], nothing is repaired or decoded through a
+/// fallback encoding: every invalid sequence, banned control character and a
+/// missing final line break is reported with its byte offset. A leading UTF-8
+/// BOM is permitted.
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::encoding::{validate_utf8_strict, Utf8IssueKind};
+///
+/// let report = validate_utf8_strict(b"0 HEAD\n1 NOTE a\xFFb\n0 TRLR");
+/// assert_eq!(report.issues.len(), 2);
+/// assert_eq!(report.issues[0].offset, 15);
+/// assert_eq!(report.issues[0].kind, Utf8IssueKind::InvalidSequence);
+/// assert_eq!(report.issues[1].kind, Utf8IssueKind::MissingFinalNewline);
+/// ```
+#[must_use]
+pub fn validate_utf8_strict(bytes: &[u8]) -> Utf8ValidationReport {
+    let mut issues = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let (valid, skip) = match std::str::from_utf8(&bytes[pos..]) {
+            Ok(s) => (s, None),
+            Err(e) => {
+                let valid =
+                    std::str::from_utf8(&bytes[pos..pos + e.valid_up_to()]).unwrap_or_default();
+                (
+                    valid,
+                    Some(e.error_len().unwrap_or(bytes.len() - pos - e.valid_up_to())),
+                )
+            }
+        };
+
+        for (i, c) in valid.char_indices() {
+            if is_disallowed_gedcom7_char(c) {
+                issues.push(Utf8Issue {
+                    offset: pos + i,
+                    kind: Utf8IssueKind::DisallowedCharacter(c),
+                });
+            }
+        }
+        pos += valid.len();
+
+        match skip {
+            Some(len) => {
+                issues.push(Utf8Issue {
+                    offset: pos,
+                    kind: Utf8IssueKind::InvalidSequence,
+                });
+                pos += len;
+            }
+            None => break,
+        }
+    }
+
+    if !bytes.is_empty() && !matches!(bytes.last(), Some(b'\n' | b'\r')) {
+        issues.push(Utf8Issue {
+            offset: bytes.len(),
+            kind: Utf8IssueKind::MissingFinalNewline,
+        });
+    }
+
+    Utf8ValidationReport { issues }
+}
+
+/// Runs [`validate_utf8_strict`] if the bytes declare GEDCOM 7.0.
+///
+/// Returns `None` for files that declare another version (or none), since
+/// GEDCOM 5.x permits legacy encodings.
+#[must_use]
+pub fn validate_gedcom7_utf8(bytes: &[u8]) -> Option<Utf8ValidationReport> {
+    let head = &bytes[..bytes.len().min(1000)];
+    let head = match std::str::from_utf8(head) {
+        Ok(s) => s,
+        Err(e) => std::str::from_utf8(&head[..e.valid_up_to()]).unwrap_or_default(),
+    };
+
+    if crate::version::detect_version(head).is_v7() {
+        Some(validate_utf8_strict(bytes))
+    } else {
+        None
     }
 }
 
@@ -814,6 +1841,81 @@ pub fn encode_to_bytes(content: &str, encoding: GedcomEncoding) -> Result<Vec<u8
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_decode_reader_across_chunk_boundaries() {
+        // Pad so that multi-byte or combining sequences straddle the prefix.
+        let header = "0 HEAD\n1 CHAR ANSEL\n1 NOTE ";
+        let mut ansel = header.as_bytes().to_vec();
+        ansel.resize(DETECTION_PREFIX_LEN - 1, b'x');
+        ansel.extend_from_slice(&[0xE2, b'e', b'\n']);
+        let (content, encoding) = decode_reader(ansel.as_slice(), None).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Ansel);
+        assert!(content.ends_with("xe\u{301}\n"));
+
+        let text = format!("0 HEAD\n1 NOTE {}é\n", "x".repeat(DETECTION_PREFIX_LEN));
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        let (content, encoding) = decode_reader(utf16.as_slice(), None).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Utf16Le);
+        assert_eq!(content, text);
+
+        let mut utf8 = "0 HEAD\n1 NOTE ".as_bytes().to_vec();
+        utf8.resize(DETECTION_PREFIX_LEN - 1, b'x');
+        utf8.extend_from_slice("é\n".as_bytes());
+        let (content, encoding) = decode_reader(utf8.as_slice(), None).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Utf8);
+        assert!(content.ends_with("xé\n"));
+    }
+
+    #[test]
+    fn test_decoding_reader_lines() {
+        let text = format!(
+            "0 HEAD\n1 CHAR UNICODE\n1 NOTE {}\n0 TRLR\n",
+            "x".repeat(READ_CHUNK_LEN)
+        );
+        let mut utf16 = vec![0xFE, 0xFF];
+        utf16.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+        let reader = DecodingReader::new(utf16.as_slice()).unwrap();
+        assert_eq!(reader.encoding(), GedcomEncoding::Utf16Be);
+        let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[3], "0 TRLR");
+
+        let ansel: &[u8] = b"0 HEAD\n1 NOTE \xE2e\n";
+        let mut decoded = String::new();
+        DecodingReader::with_encoding(ansel, GedcomEncoding::Ansel)
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "0 HEAD\n1 NOTE e\u{301}\n");
+
+        let invalid: &[u8] = b"0 HEAD\n1 CHAR UTF-8\n1 NOTE \xFF\n";
+        let mut reader = DecodingReader::new(invalid).unwrap();
+        let err = reader.read_to_string(&mut String::new()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_decode_reader_latin1_and_size_limit() {
+        let mut latin1 = b"0 HEAD\n1 CHAR ISO-8859-1\n1 NOTE caf\xE9\n".to_vec();
+        latin1.extend(std::iter::repeat_n(b'x', READ_CHUNK_LEN));
+        let (content, encoding) = decode_reader(latin1.as_slice(), None).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Iso8859_1);
+        assert!(content.contains("café"));
+
+        match decode_reader(latin1.as_slice(), Some(DETECTION_PREFIX_LEN * 2)) {
+            Err(GedcomError::FileSizeLimitExceeded { max_size, .. }) => {
+                assert_eq!(max_size, DETECTION_PREFIX_LEN * 2);
+            }
+            other => panic!("Expected FileSizeLimitExceeded, got {other:?}"),
+        }
+
+        let invalid: &[u8] = b"0 HEAD\n1 CHAR UTF-8\n1 NOTE \xFF\n";
+        assert!(matches!(
+            decode_reader(invalid, None),
+            Err(GedcomError::EncodingError(_))
+        ));
+    }
+
     #[test]
     fn test_detect_utf8_bom() {
         let bytes = [0xEF, 0xBB, 0xBF, b'0', b' ', b'H', b'E', b'A', b'D'];
@@ -882,6 +1984,78 @@ mod tests {
         assert!(content.contains("José"));
     }
 
+    #[test]
+    fn test_detect_windows_1252() {
+        // Smart quotes around "Bill" in a file that claims ISO-8859-1
+        let bytes = b"0 HEAD\n1 CHAR ISO-8859-1\n1 NOTE \x93Bill\x94 caf\xE9\n0 TRLR\n";
+        let (content, encoding) = decode_gedcom_bytes(bytes).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Windows1252);
+        assert!(content.contains("\u{201C}Bill\u{201D} café"));
+
+        let bytes = b"0 HEAD\n1 NOTE it\x92s caf\xE9\n0 TRLR\n";
+        assert_eq!(detect_encoding(bytes), GedcomEncoding::Windows1252);
+        let bytes = b"0 HEAD\n1 NOTE caf\xE9\n0 TRLR\n";
+        assert_eq!(detect_encoding(bytes), GedcomEncoding::Iso8859_1);
+        let bytes = b"0 HEAD\n1 CHAR ANSI\n1 NOTE \x80 5\n0 TRLR\n";
+        let (content, encoding) = decode_gedcom_bytes(bytes).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Windows1252);
+        assert!(content.contains("€ 5"));
+    }
+
+    #[test]
+    fn test_encode_windows_1252_and_iso8859_1() {
+        let content = "\u{201C}café\u{201D}";
+        assert_eq!(
+            encode_to_bytes(content, GedcomEncoding::Windows1252).unwrap(),
+            b"\x93caf\xE9\x94"
+        );
+        assert!(encode_to_bytes(content, GedcomEncoding::Iso8859_1).is_err());
+        assert_eq!(
+            encode_to_bytes("café", GedcomEncoding::Iso8859_1).unwrap(),
+            b"caf\xE9"
+        );
+    }
+
+    #[test]
+    fn test_decode_mac_roman() {
+        let bytes = b"0 HEAD\n1 CHAR MACINTOSH\n1 NOTE Ren\x8E M\x9Fller Stra\xA7e\n0 TRLR\n";
+        let (content, encoding) = decode_gedcom_bytes(bytes).unwrap();
+        assert_eq!(encoding, GedcomEncoding::MacRoman);
+        assert!(content.contains("René Müller Straße"));
+        assert_eq!(
+            encode_to_bytes("René", GedcomEncoding::MacRoman).unwrap(),
+            b"Ren\x8E"
+        );
+        assert!(encode_to_bytes("\u{20A7}", GedcomEncoding::MacRoman).is_err());
+    }
+
+    #[test]
+    fn test_decode_dos_code_pages() {
+        let bytes = b"0 HEAD\n1 CHAR IBMPC\n1 NOTE Jos\x82 Mu\xA4oz \xE1\n0 TRLR\n";
+        let (content, encoding) = decode_gedcom_bytes(bytes).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Cp437);
+        assert!(content.contains("José Muñoz ß"));
+
+        let bytes = b"0 HEAD\n1 CHAR CP850\n1 NOTE \xC7\xE0 Jo\xC6o\n0 TRLR\n";
+        let (content, encoding) = decode_gedcom_bytes(bytes).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Cp850);
+        assert!(content.contains("ÃÓ João"));
+
+        let (content, _) = decode_reader(&bytes[..], None).unwrap();
+        assert!(content.contains("ÃÓ João"));
+
+        assert_eq!(
+            encode_to_bytes("José ß", GedcomEncoding::Cp437).unwrap(),
+            b"Jos\x82 \xE1"
+        );
+        assert_eq!(
+            encode_to_bytes("João", GedcomEncoding::Cp850).unwrap(),
+            b"Jo\xC6o"
+        );
+        // Code page 437 has no accented capitals but É
+        assert!(encode_to_bytes("Ã", GedcomEncoding::Cp437).is_err());
+    }
+
     #[test]
     fn test_decode_iso8859_15() {
         // "10€" in ISO-8859-15: €=0xA4
@@ -969,6 +2143,10 @@ mod tests {
         assert_eq!(format!("{}", GedcomEncoding::Utf16Be), "UTF-16BE");
         assert_eq!(format!("{}", GedcomEncoding::Iso8859_1), "ISO-8859-1");
         assert_eq!(format!("{}", GedcomEncoding::Iso8859_15), "ISO-8859-15");
+        assert_eq!(format!("{}", GedcomEncoding::Windows1252), "WINDOWS-1252");
+        assert_eq!(format!("{}", GedcomEncoding::MacRoman), "MACINTOSH");
+        assert_eq!(format!("{}", GedcomEncoding::Cp437), "CP437");
+        assert_eq!(format!("{}", GedcomEncoding::Cp850), "CP850");
         assert_eq!(format!("{}", GedcomEncoding::Ascii), "ASCII");
         assert_eq!(format!("{}", GedcomEncoding::Ansel), "ANSEL");
         assert_eq!(format!("{}", GedcomEncoding::Unknown), "Unknown");
@@ -978,7 +2156,7 @@ mod tests {
     fn test_ansel_decode_basic() {
         // Simple ASCII text should pass through unchanged
         let bytes = b"0 HEAD\n1 NAME John Smith\n0 TRLR\n";
-        let result = decode_ansel(bytes);
+        let result = decode_ansel(bytes, AnselOptions::default()).unwrap();
         assert_eq!(result, "0 HEAD\n1 NAME John Smith\n0 TRLR\n");
     }
 
@@ -987,7 +2165,7 @@ mod tests {
         // Test ANSEL special characters
         // Ł (0xA1), Ø (0xA2), æ (0xB5), ø (0xB2)
         let bytes = &[0xA1, 0xA2, 0xB5, 0xB2];
-        let result = decode_ansel(bytes);
+        let result = decode_ansel(bytes, AnselOptions::default()).unwrap();
         assert_eq!(result, "ŁØæø");
     }
 
@@ -996,7 +2174,7 @@ mod tests {
         // Test combining diacritics: é is acute (0xE2) + e
         // In ANSEL, the diacritic precedes the base character
         let bytes = &[0xE2, b'e']; // acute + e = é
-        let result = decode_ansel(bytes);
+        let result = decode_ansel(bytes, AnselOptions::default()).unwrap();
         // Result should be 'e' followed by combining acute (U+0301)
         assert_eq!(result, "e\u{0301}");
     }
@@ -1005,7 +2183,7 @@ mod tests {
     fn test_ansel_decode_jose() {
         // "José" in ANSEL: J, o, s, acute(0xE2), e
         let bytes = &[b'J', b'o', b's', 0xE2, b'e'];
-        let result = decode_ansel(bytes);
+        let result = decode_ansel(bytes, AnselOptions::default()).unwrap();
         assert_eq!(result, "Jose\u{0301}"); // José with combining acute
     }
 
@@ -1014,14 +2192,14 @@ mod tests {
         // Multiple diacritics on same character
         // circumflex + umlaut + a
         let bytes = &[0xE3, 0xE8, b'a'];
-        let result = decode_ansel(bytes);
+        let result = decode_ansel(bytes, AnselOptions::default()).unwrap();
         assert_eq!(result, "a\u{0302}\u{0308}"); // a with circumflex and umlaut
     }
 
     #[test]
     fn test_ansel_encode_basic() {
         let content = "John Smith";
-        let bytes = encode_ansel(content);
+        let bytes = encode_ansel(content, AnselOptions::default()).unwrap();
         assert_eq!(bytes, b"John Smith");
     }
 
@@ -1029,7 +2207,7 @@ mod tests {
     fn test_ansel_encode_special_chars() {
         // Test encoding special characters
         let content = "Łódź"; // Polish city name
-        let bytes = encode_ansel(content);
+        let bytes = encode_ansel(content, AnselOptions::default()).unwrap();
         // Ł = 0xA1, ó = acute + o, d = d, ź = acute + z
         assert_eq!(bytes, &[0xA1, 0xE2, b'o', b'd', 0xE2, b'z']);
     }
@@ -1038,7 +2216,7 @@ mod tests {
     fn test_ansel_encode_precomposed() {
         // Test encoding precomposed characters
         let content = "José García";
-        let bytes = encode_ansel(content);
+        let bytes = encode_ansel(content, AnselOptions::default()).unwrap();
         // J, o, s, acute+e, space, G, a, r, c, acute+i, a
         assert_eq!(
             bytes,
@@ -1050,12 +2228,59 @@ mod tests {
     fn test_ansel_roundtrip_special() {
         // Test roundtrip of special characters
         let original_bytes = &[0xA1, 0xB1, 0xA5, 0xB5]; // Ł, ł, Æ, æ
-        let decoded = decode_ansel(original_bytes);
+        let decoded = decode_ansel(original_bytes, AnselOptions::default()).unwrap();
         assert_eq!(decoded, "ŁłÆæ");
-        let encoded = encode_ansel(&decoded);
+        let encoded = encode_ansel(&decoded, AnselOptions::default()).unwrap();
         assert_eq!(encoded, original_bytes);
     }
 
+    #[test]
+    fn test_ansel_stacked_diacritics() {
+        // Vietnamese ệ is e with dot below, then circumflex
+        let strict = AnselOptions { strict: true };
+        let bytes = encode_ansel("Nguyễn Việt", strict).unwrap();
+        assert_eq!(
+            bytes,
+            [
+                b'N', b'g', b'u', b'y', 0xE3, 0xE4, b'e', b'n', b' ', b'V', b'i', 0xF2, 0xE3, b'e',
+                b't'
+            ]
+        );
+        let decoded = decode_ansel(&bytes, strict).unwrap();
+        assert_eq!(decoded, "Nguye\u{302}\u{303}n Vie\u{323}\u{302}t");
+        assert_eq!(encode_ansel(&decoded, strict).unwrap(), bytes);
+
+        // A combining mark after a precomposed character goes outermost
+        let bytes = encode_ansel("é\u{323}", strict).unwrap();
+        assert_eq!(bytes, [0xE2, 0xF2, b'e']);
+        assert_eq!(encode_ansel("\u{301}", strict).unwrap(), [0xE2]);
+        let bytes = encode_ansel("\u{1EDB}", strict).unwrap();
+        assert_eq!(bytes, [0xE2, 0xBC]);
+    }
+
+    #[test]
+    fn test_ansel_strict() {
+        let strict = AnselOptions { strict: true };
+        assert_eq!(
+            decode_ansel(&[0x8D, 0xBE, 0xC8], strict).unwrap(),
+            "\u{200D}□€"
+        );
+        assert!(matches!(
+            decode_ansel(&[b'a', 0xAF], strict),
+            Err(GedcomError::EncodingError(message)) if message.contains("0xAF at offset 1")
+        ));
+        assert_eq!(
+            decode_ansel(&[b'a', 0xAF], AnselOptions::default()).unwrap(),
+            "a\u{AF}"
+        );
+
+        assert!(encode_ansel("Łódź 北京", strict).is_err());
+        assert_eq!(
+            encode_ansel("ł 北", AnselOptions::default()).unwrap(),
+            [0xB1, b' ', b'?']
+        );
+    }
+
     #[test]
     fn test_ansel_with_char_tag() {
         // Test detection via CHAR tag
@@ -1102,7 +2327,67 @@ mod tests {
             Some(GedcomEncoding::Utf16Le)
         );
         assert_eq!(parse_encoding_value("ASCII"), Some(GedcomEncoding::Ascii));
+        assert_eq!(
+            parse_encoding_value("ANSI"),
+            Some(GedcomEncoding::Windows1252)
+        );
         assert_eq!(parse_encoding_value("ANSEL"), Some(GedcomEncoding::Ansel));
+        assert_eq!(
+            parse_encoding_value("MACINTOSH"),
+            Some(GedcomEncoding::MacRoman)
+        );
+        assert_eq!(parse_encoding_value("IBMPC"), Some(GedcomEncoding::Cp437));
+        assert_eq!(parse_encoding_value("IBM850"), Some(GedcomEncoding::Cp850));
         assert_eq!(parse_encoding_value("UNKNOWN"), None);
     }
+
+    #[test]
+    fn test_validate_utf8_strict_clean_input() {
+        let bytes = "\u{FEFF}0 HEAD\r\n1 NOTE caf\u{e9}\t\u{1F600}\r\n0 TRLR\r\n".as_bytes();
+        assert!(validate_utf8_strict(bytes).is_valid());
+        assert!(validate_utf8_strict(b"").is_valid());
+    }
+
+    #[test]
+    fn test_validate_utf8_strict_reports_offsets() {
+        let bytes = b"0 HEAD\n1 NOTE \x7F\xE2\x82\n1 NOTE \xC2\x85\xEF\xBF\xBE\n0 TRLR";
+        let report = validate_utf8_strict(bytes);
+        assert_eq!(
+            report.issues,
+            vec![
+                Utf8Issue {
+                    offset: 14,
+                    kind: Utf8IssueKind::DisallowedCharacter('\u{7F}'),
+                },
+                Utf8Issue {
+                    offset: 15,
+                    kind: Utf8IssueKind::InvalidSequence,
+                },
+                Utf8Issue {
+                    offset: 25,
+                    kind: Utf8IssueKind::DisallowedCharacter('\u{85}'),
+                },
+                Utf8Issue {
+                    offset: 27,
+                    kind: Utf8IssueKind::DisallowedCharacter('\u{FFFE}'),
+                },
+                Utf8Issue {
+                    offset: 37,
+                    kind: Utf8IssueKind::MissingFinalNewline,
+                },
+            ]
+        );
+        assert_eq!(
+            report.issues[0].to_string(),
+            "disallowed character U+007F at byte 14"
+        );
+    }
+
+    #[test]
+    fn test_validate_gedcom7_utf8_only_for_v7() {
+        let v7 = b"0 HEAD\n1 GEDC\n2 VERS 7.0\n0 TRLR\n";
+        let v5 = b"0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR ANSEL\n0 TRLR\xE2";
+        assert!(validate_gedcom7_utf8(v7).unwrap().is_valid());
+        assert!(validate_gedcom7_utf8(v5).is_none());
+    }
 }
@@ -0,0 +1,230 @@
+//! Unicode normalization of decoded text.
+//!
+//! The same accented letter can be written two ways in Unicode: precomposed,
+//! as the single character `é`, or decomposed, as `e` followed by the
+//! combining acute accent. ANSEL files decode to the decomposed form, since
+//! ANSEL stores accents as separate bytes, while most software writes and
+//! searches for the precomposed one, so names from an ANSEL file may fail to
+//! match the same names typed in. [`to_nfc`] converts text to Normalization
+//! Form C, precomposed wherever possible, and [`to_nfd`] to Normalization
+//! Form D, fully decomposed. The builder normalizes what it parses with
+//! [`normalize_unicode`](crate::GedcomBuilder::normalize_unicode).
+//!
+//! Requires the `unicode-normalization` feature to be enabled.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::encoding::normalize::{to_nfc, to_nfd};
+//!
+//! assert_eq!(to_nfc("Zoe\u{308} Dupre\u{301}"), "Zo\u{eb} Dupr\u{e9}");
+//! assert_eq!(to_nfd("Zo\u{eb}"), "Zoe\u{308}");
+//! ```
+
+use std::borrow::Cow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod tables;
+
+use tables::{COMBINING_CLASS, COMPOSITION, DECOMPOSITION};
+
+/// A Unicode normalization form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UnicodeForm {
+    /// Normalization Form C: canonical decomposition, then canonical
+    /// composition, so accented letters are precomposed where Unicode has a
+    /// character for them.
+    Nfc,
+    /// Normalization Form D: canonical decomposition, so accented letters
+    /// are a base letter followed by combining marks.
+    Nfd,
+}
+
+impl UnicodeForm {
+    /// Returns `text` in this normalization form.
+    #[must_use]
+    pub fn normalize(self, text: &str) -> Cow<'_, str> {
+        match self {
+            UnicodeForm::Nfc => to_nfc(text),
+            UnicodeForm::Nfd => to_nfd(text),
+        }
+    }
+}
+
+// The Hangul syllables, which decompose and compose algorithmically.
+const HANGUL_S_BASE: u32 = 0xAC00;
+const HANGUL_L_BASE: u32 = 0x1100;
+const HANGUL_V_BASE: u32 = 0x1161;
+const HANGUL_T_BASE: u32 = 0x11A7;
+const HANGUL_L_COUNT: u32 = 19;
+const HANGUL_V_COUNT: u32 = 21;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = HANGUL_V_COUNT * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = HANGUL_L_COUNT * HANGUL_N_COUNT;
+
+/// Returns `text` in Normalization Form C, borrowed if it already is.
+#[must_use]
+pub fn to_nfc(text: &str) -> Cow<'_, str> {
+    // Nothing below the combining diacritical marks changes under NFC
+    if text.chars().all(|c| c < '\u{300}') {
+        return Cow::Borrowed(text);
+    }
+    let normalized = compose(&decompose(text));
+    if normalized == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Returns `text` in Normalization Form D, borrowed if it already is.
+#[must_use]
+pub fn to_nfd(text: &str) -> Cow<'_, str> {
+    // Nothing below the first precomposed letter changes under NFD
+    if text.chars().all(|c| c < '\u{C0}') {
+        return Cow::Borrowed(text);
+    }
+    let normalized: String = decompose(text).into_iter().collect();
+    if normalized == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Returns the canonical combining class of `c`.
+fn combining_class(c: char) -> u8 {
+    COMBINING_CLASS
+        .binary_search_by(|&(first, last, _)| {
+            if last < c {
+                std::cmp::Ordering::Less
+            } else if first > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .map_or(0, |i| COMBINING_CLASS[i].2)
+}
+
+/// Returns the full canonical decomposition of `text`, in canonical order.
+fn decompose(text: &str) -> Vec<char> {
+    let mut chars = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let s = u32::from(c).wrapping_sub(HANGUL_S_BASE);
+        if s < HANGUL_S_COUNT {
+            let l = HANGUL_L_BASE + s / HANGUL_N_COUNT;
+            let v = HANGUL_V_BASE + (s % HANGUL_N_COUNT) / HANGUL_T_COUNT;
+            let t = HANGUL_T_BASE + s % HANGUL_T_COUNT;
+            chars.extend([l, v].into_iter().filter_map(char::from_u32));
+            if t != HANGUL_T_BASE {
+                chars.extend(char::from_u32(t));
+            }
+            continue;
+        }
+        match DECOMPOSITION.binary_search_by_key(&c, |&(composed, _)| composed) {
+            Ok(i) => chars.extend(DECOMPOSITION[i].1.chars()),
+            Err(_) => chars.push(c),
+        }
+    }
+
+    // Sort each run of combining marks by class, keeping the order of marks
+    // of the same class
+    let mut start = 0;
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let end = chars[start..]
+            .iter()
+            .position(|&c| combining_class(c) == 0)
+            .map_or(chars.len(), |n| start + n);
+        chars[start..end].sort_by_key(|&c| combining_class(c));
+        start = end;
+    }
+    chars
+}
+
+/// Returns the primary composite of `first` and `second`, if any.
+fn compose_pair(first: char, second: char) -> Option<char> {
+    let (first_code, second_code) = (u32::from(first), u32::from(second));
+    let l = first_code.wrapping_sub(HANGUL_L_BASE);
+    let v = second_code.wrapping_sub(HANGUL_V_BASE);
+    if l < HANGUL_L_COUNT && v < HANGUL_V_COUNT {
+        return char::from_u32(HANGUL_S_BASE + (l * HANGUL_V_COUNT + v) * HANGUL_T_COUNT);
+    }
+    let s = first_code.wrapping_sub(HANGUL_S_BASE);
+    let t = second_code.wrapping_sub(HANGUL_T_BASE);
+    if s < HANGUL_S_COUNT && s % HANGUL_T_COUNT == 0 && t > 0 && t < HANGUL_T_COUNT {
+        return char::from_u32(first_code + t);
+    }
+    COMPOSITION
+        .binary_search_by(|&(a, b, _)| (a, b).cmp(&(first, second)))
+        .ok()
+        .map(|i| COMPOSITION[i].2)
+}
+
+/// Composes canonically decomposed, canonically ordered characters.
+fn compose(chars: &[char]) -> String {
+    let mut composed: Vec<char> = Vec::with_capacity(chars.len());
+    // The last starter, and the class of the last character kept after it
+    let mut starter: Option<usize> = None;
+    let mut last_class: Option<u8> = None;
+    for &c in chars {
+        let class = combining_class(c);
+        if let Some(index) = starter {
+            // A character between the starter and `c` with class 0, or a
+            // class at least that of `c`, blocks the composition
+            let blocked = last_class.is_some_and(|last| last == 0 || last >= class);
+            if !blocked {
+                if let Some(primary) = compose_pair(composed[index], c) {
+                    composed[index] = primary;
+                    continue;
+                }
+            }
+        }
+        if class == 0 {
+            starter = Some(composed.len());
+            last_class = None;
+        } else {
+            last_class = Some(class);
+        }
+        composed.push(c);
+    }
+    composed.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_nfc() {
+        assert!(matches!(to_nfc("John /Doe/"), Cow::Borrowed(_)));
+        assert!(matches!(to_nfc("Zo\u{eb}"), Cow::Borrowed(_)));
+        assert_eq!(to_nfc("Zoe\u{308}"), "Zo\u{eb}");
+        // Marks are reordered by class before composing: the dot below
+        // composes first, then the circumflex
+        assert_eq!(to_nfc("a\u{302}\u{323}"), "\u{1EAD}");
+        assert_eq!(to_nfc("\u{1EA1}\u{302}"), "\u{1EAD}");
+        // Singletons and excluded composites are not composed back
+        assert_eq!(to_nfc("\u{212B}"), "\u{C5}");
+        assert_eq!(to_nfc("\u{958}"), "\u{915}\u{93C}");
+        // A mark blocked by another of the same class stays apart
+        assert_eq!(to_nfc("e\u{301}\u{301}"), "\u{E9}\u{301}");
+        assert_eq!(to_nfc("\u{1112}\u{1161}\u{11AB}"), "\u{D55C}");
+    }
+
+    #[test]
+    fn test_to_nfd() {
+        assert!(matches!(to_nfd("Zoe\u{308}"), Cow::Borrowed(_)));
+        assert_eq!(to_nfd("Dupr\u{e9}"), "Dupre\u{301}");
+        assert_eq!(to_nfd("\u{1EAD}"), "a\u{323}\u{302}");
+        assert_eq!(to_nfd("\u{D55C}"), "\u{1112}\u{1161}\u{11AB}");
+        assert_eq!(UnicodeForm::Nfd.normalize("\u{C5}"), "A\u{30A}");
+    }
+}
@@ -0,0 +1,335 @@
+//! Repairing files whose bytes do not match their declared encoding.
+//!
+//! Old exports often went through several programs, each with its own idea
+//! of the character set. [`repair_encoding`] decodes such a file line by
+//! line and fixes three common problems:
+//!
+//! - the `CHAR` line declares one encoding while the bytes are in another,
+//!   such as UTF-8 text labeled `ANSEL`;
+//! - a few lines are in a different encoding from the rest of the file,
+//!   as happens when records from two files are pasted together;
+//! - UTF-8 text was decoded as Windows-1252 and encoded as UTF-8 again, so
+//!   that `José` reads `JosÃ©`.
+//!
+//! Each repair is reported as an [`EncodingIssue`], which converts into a
+//! [`Diagnostic`](crate::diagnostics::Diagnostic). The builder runs the pass
+//! on byte input with [`repair_encoding`](crate::GedcomBuilder::repair_encoding).
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::encoding::repair::{repair_encoding, EncodingIssueKind};
+//! use ged_io::GedcomEncoding;
+//!
+//! let bytes = "0 HEAD\n1 CHAR ANSEL\n0 @I1@ INDI\n1 NAME JosÃ© /Garc\u{ed}a/\n0 TRLR\n".as_bytes();
+//! let repair = repair_encoding(bytes).unwrap();
+//!
+//! assert!(repair.content.contains("1 NAME José /García/"));
+//! assert_eq!(repair.encoding, GedcomEncoding::Utf8);
+//! assert_eq!(
+//!     repair.issues[0].kind,
+//!     EncodingIssueKind::Mislabeled {
+//!         declared: GedcomEncoding::Ansel,
+//!         actual: GedcomEncoding::Utf8,
+//!     }
+//! );
+//! assert_eq!(repair.issues[1].kind, EncodingIssueKind::DoubleEncoded);
+//! assert_eq!(repair.issues[1].line, 4);
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use encoding_rs::WINDOWS_1252;
+
+use super::{decode_with_encoding, detect_encoding, parse_encoding_value, GedcomEncoding};
+use crate::GedcomError;
+
+/// How many times text may have been encoded twice over.
+const MAX_DOUBLE_ENCODING: usize = 3;
+
+/// The text of a file after [`repair_encoding`], with what was fixed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingRepair {
+    /// The repaired text.
+    pub content: String,
+    /// The encoding most of the file is in.
+    pub encoding: GedcomEncoding,
+    /// The problems found and fixed, in line order, the mislabeled encoding
+    /// first.
+    pub issues: Vec<EncodingIssue>,
+}
+
+/// A problem [`repair_encoding`] found and fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EncodingIssue {
+    /// The line the problem is on: the `CHAR` line for a mislabeled
+    /// encoding, counting from 1.
+    pub line: u32,
+    /// What was wrong.
+    pub kind: EncodingIssueKind,
+}
+
+/// The kind of problem found by [`repair_encoding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EncodingIssueKind {
+    /// The header declares one encoding, but the file is in another.
+    Mislabeled {
+        /// The encoding the `CHAR` line declares.
+        declared: GedcomEncoding,
+        /// The encoding the file was decoded as.
+        actual: GedcomEncoding,
+    },
+    /// The line is in a different encoding from the rest of the file.
+    MixedEncoding {
+        /// The encoding the line was decoded as.
+        encoding: GedcomEncoding,
+    },
+    /// The line held UTF-8 text that had been read as Windows-1252 and
+    /// written as UTF-8 again, such as `JosÃ©` for `José`.
+    DoubleEncoded,
+}
+
+impl fmt::Display for EncodingIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            EncodingIssueKind::Mislabeled { declared, actual } => write!(
+                f,
+                "declared encoding {declared} at line {} but the file is {actual}",
+                self.line
+            ),
+            EncodingIssueKind::MixedEncoding { encoding } => {
+                write!(f, "{encoding} text at line {}", self.line)
+            }
+            EncodingIssueKind::DoubleEncoded => {
+                write!(f, "double-encoded UTF-8 at line {}", self.line)
+            }
+        }
+    }
+}
+
+/// Decodes GEDCOM bytes, fixing a mislabeled encoding, lines in another
+/// encoding and double-encoded UTF-8, and reports what it fixed.
+///
+/// Files with a byte order mark are decoded as it says. Otherwise each line
+/// that is valid UTF-8 is read as UTF-8 and each other line in the
+/// single-byte encoding the file declares, Windows-1252 if it declares
+/// none; the encoding most lines with non-ASCII characters are in is the
+/// encoding of the file. Words that read as UTF-8 once encoded to
+/// Windows-1252 are decoded again.
+///
+/// # Errors
+///
+/// Returns `GedcomError::EncodingError` if a file with a byte order mark
+/// is not valid in its encoding.
+pub fn repair_encoding(bytes: &[u8]) -> Result<EncodingRepair, GedcomError> {
+    let mut issues = Vec::new();
+    let (content, encoding) = match detect_encoding(bytes) {
+        encoding @ (GedcomEncoding::Utf16Le | GedcomEncoding::Utf16Be) => {
+            decode_with_encoding(bytes, encoding)?
+        }
+        _ if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) => {
+            decode_with_encoding(bytes, GedcomEncoding::Utf8)?
+        }
+        _ => decode_lines(bytes, &mut issues)?,
+    };
+
+    let mut repaired = String::with_capacity(content.len());
+    for (line, text) in (1..).zip(content.split_inclusive('\n')) {
+        let mut fixed = false;
+        for word in text.split_inclusive(' ') {
+            match fix_double_encoding(word) {
+                Some(word) => {
+                    repaired.push_str(&word);
+                    fixed = true;
+                }
+                None => repaired.push_str(word),
+            }
+        }
+        if fixed {
+            issues.push(EncodingIssue {
+                line,
+                kind: EncodingIssueKind::DoubleEncoded,
+            });
+        }
+    }
+
+    Ok(EncodingRepair {
+        content: repaired,
+        encoding,
+        issues,
+    })
+}
+
+/// Decodes a file without a byte order mark line by line, reporting a
+/// mislabeled encoding and the lines in another encoding than the file.
+fn decode_lines(
+    bytes: &[u8],
+    issues: &mut Vec<EncodingIssue>,
+) -> Result<(String, GedcomEncoding), GedcomError> {
+    let lines: Vec<&[u8]> = bytes.split_inclusive(|&b| b == b'\n').collect();
+    let declared = (1..).zip(&lines).take(50).find_map(|(line, text)| {
+        let text = String::from_utf8_lossy(text);
+        let mut parts = text.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("1"), Some(tag), Some(value)) if tag.eq_ignore_ascii_case("CHAR") => {
+                Some((line, parse_encoding_value(value)?))
+            }
+            _ => None,
+        }
+    });
+
+    // Lines with non-ASCII bytes are UTF-8 if they are valid UTF-8, and in
+    // the declared single-byte encoding otherwise
+    let is_utf8: Vec<Option<bool>> = lines
+        .iter()
+        .map(|text| (!text.is_ascii()).then(|| std::str::from_utf8(text).is_ok()))
+        .collect();
+    let utf8_lines = is_utf8.iter().filter(|kind| **kind == Some(true)).count();
+    let legacy_lines = is_utf8.iter().filter(|kind| **kind == Some(false)).count();
+    let legacy = match declared.map(|(_, encoding)| encoding) {
+        Some(GedcomEncoding::Iso8859_1) => {
+            let c1 = bytes.iter().any(|b| (0x80..=0x9F).contains(b));
+            if c1 {
+                GedcomEncoding::Windows1252
+            } else {
+                GedcomEncoding::Iso8859_1
+            }
+        }
+        Some(
+            encoding @ (GedcomEncoding::Iso8859_15
+            | GedcomEncoding::Windows1252
+            | GedcomEncoding::MacRoman
+            | GedcomEncoding::Cp437
+            | GedcomEncoding::Cp850
+            | GedcomEncoding::Ansel),
+        ) => encoding,
+        _ => GedcomEncoding::Windows1252,
+    };
+    let declares_utf8 = matches!(
+        declared,
+        None | Some((_, GedcomEncoding::Utf8 | GedcomEncoding::Ascii))
+    );
+    let file_is_utf8 = match utf8_lines.cmp(&legacy_lines) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Less => false,
+        std::cmp::Ordering::Equal => declares_utf8,
+    };
+    let actual = if file_is_utf8 {
+        GedcomEncoding::Utf8
+    } else {
+        legacy
+    };
+
+    if let Some((line, declared)) = declared {
+        if utf8_lines + legacy_lines > 0 && declares_utf8 != file_is_utf8 {
+            issues.push(EncodingIssue {
+                line,
+                kind: EncodingIssueKind::Mislabeled { declared, actual },
+            });
+        }
+    }
+
+    let mut content = String::with_capacity(bytes.len());
+    for ((line, text), kind) in (1..).zip(&lines).zip(is_utf8) {
+        match kind {
+            Some(true) => content.push_str(std::str::from_utf8(text).unwrap_or_default()),
+            Some(false) => content.push_str(&decode_with_encoding(text, legacy)?.0),
+            None => content.push_str(&String::from_utf8_lossy(text)),
+        }
+        if kind.is_some_and(|utf8| utf8 != file_is_utf8) {
+            let encoding = if file_is_utf8 {
+                legacy
+            } else {
+                GedcomEncoding::Utf8
+            };
+            issues.push(EncodingIssue {
+                line,
+                kind: EncodingIssueKind::MixedEncoding { encoding },
+            });
+        }
+    }
+    Ok((content, actual))
+}
+
+/// Returns `word` decoded once more if it is UTF-8 that was read as
+/// Windows-1252 and encoded as UTF-8 again, up to a few times over.
+fn fix_double_encoding(word: &str) -> Option<String> {
+    let mut fixed: Option<String> = None;
+    for _ in 0..MAX_DOUBLE_ENCODING {
+        let text = fixed.as_deref().unwrap_or(word);
+        if text.is_ascii() {
+            break;
+        }
+        let (bytes, _, had_errors) = WINDOWS_1252.encode(text);
+        if had_errors {
+            break;
+        }
+        match String::from_utf8(bytes.into_owned()) {
+            Ok(decoded) if decoded != text => fixed = Some(decoded),
+            _ => break,
+        }
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fix_double_encoding() {
+        assert_eq!(fix_double_encoding("JosÃ©").as_deref(), Some("José"));
+        assert_eq!(fix_double_encoding("Ã¢â‚¬Å“").as_deref(), Some("\u{201C}"));
+        assert_eq!(fix_double_encoding("José"), None);
+        assert_eq!(fix_double_encoding("Smith"), None);
+        assert_eq!(fix_double_encoding("北京"), None);
+    }
+
+    #[test]
+    fn test_repair_mixed_lines() {
+        let mut bytes = b"0 HEAD\n1 CHAR ISO-8859-1\n".to_vec();
+        bytes.extend_from_slice(b"0 @I1@ INDI\n1 NAME Jos\xE9 /Garc\xEDa/\n");
+        bytes.extend_from_slice(b"0 @I2@ INDI\n1 NAME Ren\xE9 /Dupr\xE9/\n");
+        bytes.extend_from_slice("0 @I3@ INDI\n1 NAME Zoë /Doe/\n0 TRLR\n".as_bytes());
+        let repair = repair_encoding(&bytes).unwrap();
+
+        assert_eq!(repair.encoding, GedcomEncoding::Iso8859_1);
+        assert!(repair.content.contains("José /García/"));
+        assert!(repair.content.contains("René /Dupré/"));
+        assert!(repair.content.contains("Zoë /Doe/"));
+        assert_eq!(
+            repair.issues,
+            [EncodingIssue {
+                line: 8,
+                kind: EncodingIssueKind::MixedEncoding {
+                    encoding: GedcomEncoding::Utf8
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_repair_mislabeled_utf8() {
+        let bytes = b"0 HEAD\n1 CHAR UTF-8\n0 @I1@ INDI\n1 NAME \x93Bill\x94 /Caf\xE9/\n0 TRLR\n";
+        let repair = repair_encoding(bytes).unwrap();
+
+        assert_eq!(repair.encoding, GedcomEncoding::Windows1252);
+        assert!(repair.content.contains("\u{201C}Bill\u{201D} /Café/"));
+        assert_eq!(repair.issues.len(), 1);
+        assert_eq!(repair.issues[0].line, 2);
+        assert_eq!(
+            repair.issues[0].to_string(),
+            "declared encoding UTF-8 at line 2 but the file is WINDOWS-1252"
+        );
+
+        let clean = "0 HEAD\n1 CHAR UTF-8\n1 NOTE café\n0 TRLR\n";
+        let repair = repair_encoding(clean.as_bytes()).unwrap();
+        assert_eq!(repair.content, clean);
+        assert!(repair.issues.is_empty());
+    }
+}
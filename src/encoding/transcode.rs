@@ -0,0 +1,488 @@
+//! Converting GEDCOM bytes from one encoding to another.
+//!
+//! [`transcode`] converts a whole file in memory. A [`Transcoder`] does the
+//! same, and also wraps a reader or a writer so that large files convert
+//! chunk by chunk as they are read or written, in the way `iconv` is used
+//! on the command line.
+//!
+//! The `CHAR` line of the header is rewritten to name the target encoding,
+//! and added after the `0 HEAD` line of a GEDCOM 5 header that has none, so
+//! the converted file reads back as it was written. GEDCOM 7.0 headers have
+//! no `CHAR` line and get none.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::io::Write;
+//! use ged_io::encoding::{transcode, GedcomEncoding, Transcoder};
+//!
+//! let latin1 = b"0 HEAD\n1 NOTE caf\xE9\n0 TRLR\n";
+//! let utf8 = transcode(latin1, GedcomEncoding::Iso8859_1, GedcomEncoding::Utf8).unwrap();
+//! assert_eq!(utf8, "0 HEAD\n1 CHAR UTF-8\n1 NOTE café\n0 TRLR\n".as_bytes());
+//!
+//! let mut writer = Transcoder::detecting(GedcomEncoding::Utf8).writer(Vec::new());
+//! writer.write_all(b"0 HEAD\n1 CHAR ISO-8859-1\n1 NOTE caf\xE9\n0 TRLR\n").unwrap();
+//! let utf8 = writer.finish().unwrap();
+//! let utf8 = String::from_utf8(utf8).unwrap();
+//! assert!(utf8.contains("1 CHAR UTF-8\n") && utf8.contains("café"));
+//! ```
+
+use std::io::{self, BufRead, Read, Write};
+
+use super::{
+    decode_gedcom_bytes, decode_with_encoding, detect_prefix_encoding, encode_to_bytes,
+    unicode_combining_to_ansel, DecodingReader, GedcomEncoding, StreamDecoder,
+    DETECTION_PREFIX_LEN,
+};
+use crate::GedcomError;
+
+/// Converts `bytes` from the encoding `from` to the encoding `to`.
+///
+/// A UTF-8 byte order mark is dropped; UTF-16 output starts with one. The
+/// header `CHAR` line is made to declare `to`.
+///
+/// # Errors
+///
+/// Returns `GedcomError::EncodingError` if `bytes` are not valid in `from`,
+/// or the text has characters `to` cannot represent.
+pub fn transcode(
+    bytes: &[u8],
+    from: GedcomEncoding,
+    to: GedcomEncoding,
+) -> Result<Vec<u8>, GedcomError> {
+    Transcoder::new(from, to).transcode(bytes)
+}
+
+/// Converts GEDCOM bytes from one encoding to another, in memory or as a
+/// stream.
+///
+/// The source encoding is either given, or detected from the input the way
+/// [`decode_gedcom_bytes`] does it: from the byte order mark, the `CHAR`
+/// line, then the bytes themselves. The streaming adapters detect it from
+/// the first few kilobytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transcoder {
+    from: Option<GedcomEncoding>,
+    to: GedcomEncoding,
+}
+
+impl Transcoder {
+    /// Creates a transcoder from the encoding `from` to the encoding `to`.
+    #[must_use]
+    pub fn new(from: GedcomEncoding, to: GedcomEncoding) -> Self {
+        Self {
+            from: Some(from),
+            to,
+        }
+    }
+
+    /// Creates a transcoder to the encoding `to` that detects the encoding
+    /// of its input.
+    #[must_use]
+    pub fn detecting(to: GedcomEncoding) -> Self {
+        Self { from: None, to }
+    }
+
+    /// Converts `bytes` in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::EncodingError` if `bytes` are not valid in the
+    /// source encoding, or the text has characters the target encoding
+    /// cannot represent.
+    pub fn transcode(&self, bytes: &[u8]) -> Result<Vec<u8>, GedcomError> {
+        let (text, _) = match self.from {
+            Some(from) => decode_with_encoding(bytes, from)?,
+            None => decode_gedcom_bytes(bytes)?,
+        };
+        let header = header_len(&text).unwrap_or(text.len());
+        let mut converted = declare_encoding(&text[..header], self.to);
+        converted.push_str(&text[header..]);
+        encode_to_bytes(&converted, self.to)
+    }
+
+    /// Wraps `reader`, so that reading yields its bytes in the target
+    /// encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::IoError` if the source encoding is detected and
+    /// reading the first bytes fails.
+    pub fn reader<R: Read>(self, reader: R) -> Result<TranscodingReader<R>, GedcomError> {
+        let decoded = match self.from {
+            Some(from) => DecodingReader::with_encoding(reader, from),
+            None => DecodingReader::new(reader)?,
+        };
+        Ok(TranscodingReader {
+            decoded,
+            encoder: StreamEncoder::new(self.to),
+            output: Vec::new(),
+            pos: 0,
+            done: false,
+        })
+    }
+
+    /// Wraps `writer`, so that bytes written in the source encoding reach it
+    /// in the target encoding. Call [`TranscodingWriter::finish`] once
+    /// everything is written.
+    pub fn writer<W: Write>(self, writer: W) -> TranscodingWriter<W> {
+        TranscodingWriter {
+            writer,
+            decoder: self.from.map(StreamDecoder::new),
+            prefix: Vec::new(),
+            encoder: StreamEncoder::new(self.to),
+            text: String::new(),
+            output: Vec::new(),
+        }
+    }
+}
+
+/// Returns the offsets at which the lines of `text` start.
+fn line_starts(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let bytes = text.as_bytes();
+    std::iter::once(0).chain(bytes.iter().enumerate().filter_map(move |(i, &b)| {
+        let ends_line = b == b'\n' || (b == b'\r' && bytes.get(i + 1) != Some(&b'\n'));
+        ends_line.then_some(i + 1)
+    }))
+}
+
+/// Returns the length of the header record at the start of `text`, or
+/// `None` if the line starting the next record has not been seen yet.
+fn header_len(text: &str) -> Option<usize> {
+    line_starts(text)
+        .skip(1)
+        .find(|&start| text[start..].trim_start().starts_with("0 "))
+}
+
+/// Returns `header` with its `CHAR` line declaring `encoding`, adding one
+/// after the `0 HEAD` line of a GEDCOM 5 header that has none.
+fn declare_encoding(header: &str, encoding: GedcomEncoding) -> String {
+    let value = encoding.char_value();
+    let is_head = header
+        .trim_start_matches(['\u{FEFF}', ' ', '\t'])
+        .starts_with("0 HEAD");
+    if !is_head {
+        return header.to_string();
+    }
+
+    let line_end = |start: usize| {
+        header[start..]
+            .find(['\r', '\n'])
+            .map_or(header.len(), |i| start + i)
+    };
+    for start in line_starts(header) {
+        let line = &header[start..line_end(start)];
+        let indent = line.len() - line.trim_start().len();
+        let Some(rest) = line[indent..].strip_prefix("1 CHAR") else {
+            continue;
+        };
+        if rest.is_empty() || rest.starts_with(' ') {
+            let value_start = start + indent + "1 CHAR".len();
+            return format!(
+                "{} {value}{}",
+                &header[..value_start],
+                &header[start + line.len()..]
+            );
+        }
+    }
+
+    if crate::version::detect_version(header).is_v7() {
+        return header.to_string();
+    }
+    let first_end = line_end(0);
+    let terminator = match &header[first_end..] {
+        "" => return format!("{header}\n1 CHAR {value}"),
+        rest if rest.starts_with("\r\n") => "\r\n",
+        rest if rest.starts_with('\r') => "\r",
+        _ => "\n",
+    };
+    let (first, rest) = header.split_at(first_end + terminator.len());
+    format!("{first}1 CHAR {value}{terminator}{rest}")
+}
+
+/// Incremental encoder used by the transcoding adapters.
+struct StreamEncoder {
+    encoding: GedcomEncoding,
+    /// The start of the input, held back until the whole header has arrived
+    /// so that its `CHAR` line can be rewritten; `None` once it is done.
+    header: Option<String>,
+    /// Text held back until the characters that follow it arrive.
+    pending: String,
+    started: bool,
+}
+
+impl StreamEncoder {
+    fn new(encoding: GedcomEncoding) -> Self {
+        Self {
+            encoding,
+            header: Some(String::new()),
+            pending: String::new(),
+            started: false,
+        }
+    }
+
+    /// Encodes `text` to `out`, holding back what the next chunk may still
+    /// change unless `last` is set.
+    fn encode(&mut self, text: &str, out: &mut Vec<u8>, last: bool) -> Result<(), GedcomError> {
+        let Some(header) = &mut self.header else {
+            return self.encode_text(text, out, last);
+        };
+        header.push_str(text);
+        let len = match header_len(header) {
+            Some(len) => len,
+            None if last => header.len(),
+            None => return Ok(()),
+        };
+        let mut converted = declare_encoding(&header[..len], self.encoding);
+        converted.push_str(&header[len..]);
+        self.header = None;
+        self.encode_text(&converted, out, last)
+    }
+
+    /// Encodes text whose header is already converted.
+    fn encode_text(
+        &mut self,
+        text: &str,
+        out: &mut Vec<u8>,
+        last: bool,
+    ) -> Result<(), GedcomError> {
+        self.pending.push_str(text);
+        // ANSEL writes combining marks before their base character, so the
+        // last character waits for the marks that may follow it
+        let split = if last || self.encoding != GedcomEncoding::Ansel {
+            self.pending.len()
+        } else {
+            self.pending
+                .char_indices()
+                .rev()
+                .find(|(_, c)| unicode_combining_to_ansel(*c).is_none())
+                .map_or(0, |(i, _)| i)
+        };
+        if split == 0 && !last {
+            return Ok(());
+        }
+
+        let encoded = encode_to_bytes(&self.pending[..split], self.encoding)?;
+        // Only the start of the output has a byte order mark
+        let skip = match self.encoding {
+            GedcomEncoding::Utf16Le | GedcomEncoding::Utf16Be if self.started => 2,
+            _ => 0,
+        };
+        out.extend_from_slice(&encoded[skip..]);
+        self.pending.drain(..split);
+        self.started = true;
+        Ok(())
+    }
+}
+
+fn invalid_data(error: &GedcomError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+/// A [`Read`] adapter that converts a byte stream to another encoding as it
+/// is read. Created by [`Transcoder::reader`].
+///
+/// Input that is invalid in the source encoding, or text the target
+/// encoding cannot represent, makes reads fail with
+/// `io::ErrorKind::InvalidData`.
+pub struct TranscodingReader<R: Read> {
+    decoded: DecodingReader<R>,
+    encoder: StreamEncoder,
+    /// Encoded bytes not yet read, from `pos` on.
+    output: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl<R: Read> TranscodingReader<R> {
+    /// Returns the encoding the input is converted from.
+    pub fn source_encoding(&self) -> GedcomEncoding {
+        self.decoded.encoding()
+    }
+}
+
+impl<R: Read> Read for TranscodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.output.len() && !self.done {
+            self.output.clear();
+            self.pos = 0;
+            let text = self.decoded.fill_buf()?;
+            let len = text.len();
+            self.done = len == 0;
+            // The decoder hands out whole characters
+            let text = std::str::from_utf8(text).unwrap_or_default();
+            self.encoder
+                .encode(text, &mut self.output, self.done)
+                .map_err(|e| invalid_data(&e))?;
+            self.decoded.consume(len);
+        }
+        let n = (self.output.len() - self.pos).min(buf.len());
+        buf[..n].copy_from_slice(&self.output[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// A [`Write`] adapter that converts the bytes written to it to another
+/// encoding before passing them on. Created by [`Transcoder::writer`].
+///
+/// A few bytes may be held back until the characters they belong to are
+/// complete, so [`finish`](Self::finish) must be called once everything is
+/// written; dropping the writer without it loses the end of the output.
+pub struct TranscodingWriter<W: Write> {
+    writer: W,
+    /// `None` until the source encoding is detected from `prefix`.
+    decoder: Option<StreamDecoder>,
+    prefix: Vec<u8>,
+    encoder: StreamEncoder,
+    text: String,
+    output: Vec<u8>,
+}
+
+impl<W: Write> TranscodingWriter<W> {
+    /// Converts and writes `bytes`, ending the input if `last` is set.
+    fn convert(&mut self, bytes: &[u8], last: bool) -> io::Result<()> {
+        let Some(decoder) = &mut self.decoder else {
+            self.prefix.extend_from_slice(bytes);
+            if self.prefix.len() < DETECTION_PREFIX_LEN && !last {
+                return Ok(());
+            }
+            let encoding = detect_prefix_encoding(&self.prefix, last);
+            let prefix = std::mem::take(&mut self.prefix);
+            self.decoder = Some(StreamDecoder::new(encoding));
+            return self.convert(&prefix, last);
+        };
+        self.text.clear();
+        decoder
+            .decode(bytes, &mut self.text, last)
+            .map_err(|e| invalid_data(&e))?;
+        self.output.clear();
+        self.encoder
+            .encode(&self.text, &mut self.output, last)
+            .map_err(|e| invalid_data(&e))?;
+        self.writer.write_all(&self.output)
+    }
+
+    /// Writes out the bytes held back, flushes the inner writer and returns
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `io::ErrorKind::InvalidData` if the input
+    /// ends in the middle of a character, and any error of the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.convert(&[], true)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for TranscodingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.convert(buf, false)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcode() {
+        let utf8 = "0 HEAD\n1 NOTE Zoë Dupré\n0 TRLR\n";
+        let ansel =
+            transcode(utf8.as_bytes(), GedcomEncoding::Utf8, GedcomEncoding::Ansel).unwrap();
+        assert_eq!(
+            ansel,
+            b"0 HEAD\n1 CHAR ANSEL\n1 NOTE Zo\xE8e Dupr\xE2e\n0 TRLR\n"
+        );
+        // ANSEL decodes to decomposed characters
+        assert_eq!(
+            transcode(&ansel, GedcomEncoding::Ansel, GedcomEncoding::Utf8).unwrap(),
+            "0 HEAD\n1 CHAR UTF-8\n1 NOTE Zoe\u{308} Dupre\u{301}\n0 TRLR\n".as_bytes()
+        );
+        assert!(transcode(utf8.as_bytes(), GedcomEncoding::Utf8, GedcomEncoding::Ascii).is_err());
+
+        let bytes = b"0 HEAD\n1 CHAR ISO-8859-1\n1 NOTE caf\xE9\n0 TRLR\n";
+        let converted = Transcoder::detecting(GedcomEncoding::Utf8)
+            .transcode(bytes)
+            .unwrap();
+        assert_eq!(
+            converted,
+            "0 HEAD\n1 CHAR UTF-8\n1 NOTE café\n0 TRLR\n".as_bytes()
+        );
+        let data = crate::GedcomBuilder::new()
+            .build_from_bytes(&converted)
+            .unwrap();
+        assert_eq!(
+            data.header.unwrap().note.unwrap().value.as_deref(),
+            Some("café")
+        );
+    }
+
+    #[test]
+    fn test_declare_encoding() {
+        let utf16 = GedcomEncoding::Utf16Le;
+        assert_eq!(
+            declare_encoding("0 HEAD\r\n 1 CHAR  ANSEL\r\n2 VERS 1\r\n", utf16),
+            "0 HEAD\r\n 1 CHAR UNICODE\r\n2 VERS 1\r\n"
+        );
+        assert_eq!(
+            declare_encoding("0 HEAD\r1 GEDC\r2 VERS 5.5.1\r", utf16),
+            "0 HEAD\r1 CHAR UNICODE\r1 GEDC\r2 VERS 5.5.1\r"
+        );
+        assert_eq!(declare_encoding("0 HEAD", utf16), "0 HEAD\n1 CHAR UNICODE");
+        // GEDCOM 7.0 has no CHAR line, and other records are left alone.
+        let gedcom_7 = "0 HEAD\n1 GEDC\n2 VERS 7.0\n";
+        assert_eq!(declare_encoding(gedcom_7, utf16), gedcom_7);
+        assert_eq!(declare_encoding("0 @I1@ INDI\n", utf16), "0 @I1@ INDI\n");
+        assert_eq!(header_len("0 HEAD\n1 CHAR ANSEL\n0 TRLR\n"), Some(20));
+        assert_eq!(header_len("0 HEAD\n1 CHAR ANSEL\n0"), None);
+    }
+
+    #[test]
+    fn test_transcoding_reader() {
+        let mut source = b"0 HEAD\n1 CHAR ANSEL\n".to_vec();
+        for _ in 0..20_000 {
+            source.extend_from_slice(b"1 NOTE Zo\xE8e Dupr\xE2e\n");
+        }
+        source.extend_from_slice(b"0 TRLR\n");
+
+        let mut reader = Transcoder::detecting(GedcomEncoding::Utf16Le)
+            .reader(&source[..])
+            .unwrap();
+        assert_eq!(reader.source_encoding(), GedcomEncoding::Ansel);
+        let mut utf16 = Vec::new();
+        reader.read_to_end(&mut utf16).unwrap();
+        assert_eq!(
+            utf16,
+            transcode(&source, GedcomEncoding::Ansel, GedcomEncoding::Utf16Le).unwrap()
+        );
+        assert_eq!(&utf16[..2], [0xFF, 0xFE]);
+    }
+
+    #[test]
+    fn test_transcoding_writer() {
+        let utf8 = "0 HEAD\n1 CHAR UTF-8\n1 NOTE Zoe\u{308} Dupré\n0 TRLR\n";
+        let mut writer =
+            Transcoder::new(GedcomEncoding::Utf8, GedcomEncoding::Ansel).writer(Vec::new());
+        // One byte at a time, so characters and their marks arrive apart
+        for byte in utf8.as_bytes() {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        assert_eq!(
+            writer.finish().unwrap(),
+            transcode(utf8.as_bytes(), GedcomEncoding::Utf8, GedcomEncoding::Ansel).unwrap()
+        );
+
+        let mut writer =
+            Transcoder::new(GedcomEncoding::Utf8, GedcomEncoding::Iso8859_1).writer(Vec::new());
+        writer.write_all(b"caf\xC3").unwrap();
+        assert!(writer.finish().is_err());
+    }
+}
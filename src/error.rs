@@ -1,10 +1,19 @@
 use std::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    encoding::repair::{EncodingIssue, EncodingIssueKind},
+    types::DuplicateXref,
+};
+
 /// Represents errors that can occur during GEDCOM parsing.
 ///
 /// This enum provides detailed error information including line numbers
 /// and context to help users identify and fix issues in their GEDCOM files.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GedcomError {
     /// A parsing error, with the line number and a message.
     ParseError {
@@ -66,6 +75,23 @@ pub enum GedcomError {
 
     /// An I/O error occurred.
     IoError(String),
+
+    /// An error that occurred while parsing a file, tagged with the file's path.
+    InFile {
+        /// The path of the file being parsed.
+        path: String,
+        /// The underlying error.
+        source: Box<GedcomError>,
+    },
+
+    /// A record tree built outside the parser, such as one deserialized from
+    /// JSON, is malformed or references records that do not exist.
+    InvalidStructure {
+        /// Where the problem is, such as `$.families[0].children[1]`.
+        path: String,
+        /// A description of the problem.
+        message: String,
+    },
 }
 
 impl fmt::Display for GedcomError {
@@ -109,11 +135,22 @@ impl fmt::Display for GedcomError {
                 )
             }
             GedcomError::IoError(msg) => write!(f, "I/O error: {msg}"),
+            GedcomError::InFile { path, source } => write!(f, "{path}: {source}"),
+            GedcomError::InvalidStructure { path, message } => {
+                write!(f, "Invalid structure at {path}: {message}")
+            }
         }
     }
 }
 
-impl std::error::Error for GedcomError {}
+impl std::error::Error for GedcomError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GedcomError::InFile { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl From<std::io::Error> for GedcomError {
     fn from(err: std::io::Error) -> Self {
@@ -121,6 +158,92 @@ impl From<std::io::Error> for GedcomError {
     }
 }
 
+/// A recoverable problem found while parsing.
+///
+/// In the default lenient mode these are collected and parsing carries on;
+/// with strict mode enabled the first one is turned into an error (see
+/// [`ParseWarning::to_error`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParseWarning {
+    /// The input ended without a `0 TRLR` trailer record.
+    MissingTrailer,
+    /// Non-blank lines follow the `0 TRLR` trailer record. They are ignored.
+    ContentAfterTrailer {
+        /// The line number of the first ignored line.
+        line: u32,
+    },
+    /// A record could not be parsed and was left out. Only reported when
+    /// parsing with [`recover`](crate::GedcomBuilder::recover) enabled.
+    SkippedRecord {
+        /// The line number of the first line of the record.
+        line: u32,
+        /// The line number of the last line of the record.
+        end_line: u32,
+        /// The text of the record, as read.
+        text: String,
+        /// Why the record could not be parsed.
+        error: String,
+    },
+    /// Bytes that did not match the declared encoding were repaired. Only
+    /// reported when parsing with
+    /// [`repair_encoding`](crate::GedcomBuilder::repair_encoding) enabled.
+    Encoding(EncodingIssue),
+    /// Records of the same type shared an xref and were resolved by the
+    /// [`duplicate_xrefs`](crate::GedcomBuilder::duplicate_xrefs) policy.
+    DuplicateXref(DuplicateXref),
+}
+
+impl ParseWarning {
+    /// Returns the error reported for this warning in strict mode.
+    #[must_use]
+    pub fn to_error(&self) -> GedcomError {
+        match self {
+            ParseWarning::MissingTrailer => {
+                GedcomError::InvalidFormat("Missing 0 TRLR trailer record".to_string())
+            }
+            ParseWarning::ContentAfterTrailer { line } => GedcomError::ParseError {
+                line: *line,
+                message: "Content after 0 TRLR trailer record".to_string(),
+            },
+            ParseWarning::SkippedRecord { line, error, .. } => GedcomError::ParseError {
+                line: *line,
+                message: format!("Skipped record: {error}"),
+            },
+            ParseWarning::Encoding(issue) => match issue.kind {
+                EncodingIssueKind::DoubleEncoded => GedcomError::ParseError {
+                    line: issue.line,
+                    message: "Double-encoded UTF-8".to_string(),
+                },
+                _ => GedcomError::EncodingError(issue.to_string()),
+            },
+            ParseWarning::DuplicateXref(duplicate) => GedcomError::InvalidFormat(format!(
+                "Duplicate xref {} on {:?} records",
+                duplicate.xref, duplicate.record_type
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseWarning::MissingTrailer => write!(f, "Missing 0 TRLR trailer record"),
+            ParseWarning::ContentAfterTrailer { line } => {
+                write!(f, "Content after 0 TRLR trailer record at line {line}")
+            }
+            ParseWarning::SkippedRecord {
+                line,
+                end_line,
+                error,
+                ..
+            } => write!(f, "Skipped the record at lines {line}-{end_line}: {error}"),
+            ParseWarning::Encoding(issue) => write!(f, "Repaired {issue}"),
+            ParseWarning::DuplicateXref(duplicate) => write!(f, "{duplicate}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,6 +334,31 @@ mod tests {
         assert_eq!(format!("{err}"), "I/O error: File not found");
     }
 
+    #[test]
+    fn test_in_file_display() {
+        let err = GedcomError::InFile {
+            path: "family.ged".to_string(),
+            source: Box::new(GedcomError::InvalidFormat("Missing header".to_string())),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "family.ged: Invalid GEDCOM format: Missing header"
+        );
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_invalid_structure_display() {
+        let err = GedcomError::InvalidStructure {
+            path: "$.families[0].children[1]".to_string(),
+            message: "no individual @I9@".to_string(),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "Invalid structure at $.families[0].children[1]: no individual @I9@"
+        );
+    }
+
     #[test]
     fn test_error_trait_implementation() {
         let err: Box<dyn std::error::Error> = Box::new(GedcomError::ParseError {
@@ -0,0 +1,41 @@
+//! Exporters that render GEDCOM data in other formats.
+//!
+//! Each submodule produces a self-contained document from a [`GedcomData`](crate::GedcomData).
+
+pub mod csv;
+pub mod dot;
+pub mod graph;
+pub mod sosa;
+pub mod sql;
+pub mod svg;
+
+/// Escapes text for use in XML content and attribute values, such as those
+/// of the [`graph`] and [`svg`] exports.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"Tom & "Jerry" <O'Neil>"#),
+            "Tom &amp; &quot;Jerry&quot; &lt;O&apos;Neil&gt;"
+        );
+        assert_eq!(escape("plain"), "plain");
+    }
+}
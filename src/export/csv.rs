@@ -0,0 +1,689 @@
+//! CSV tables for spreadsheets and BI tools.
+//!
+//! [`export`] flattens GEDCOM data into four tables: one row per individual,
+//! per family, per event or attribute, and per source citation. Each table's
+//! columns can be chosen and reordered through [`CsvConfig`]. Fields are
+//! quoted as described in RFC 4180, and fields holding several values, such
+//! as a family's children, list them separated by `;`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{export::csv, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1 JAN 1900\n2 PLAC Boston, MA\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let config = csv::CsvConfig {
+//!     individual_columns: vec![csv::IndividualColumn::Xref, csv::IndividualColumn::BirthPlace],
+//!     ..csv::CsvConfig::default()
+//! };
+//! let tables = csv::export(&data, &config);
+//! assert_eq!(tables.individuals, "xref,birth_place\r\n@I1@,\"Boston, MA\"\r\n");
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::{collections::HashMap, io, path::Path};
+
+use crate::{
+    types::{
+        date::Date,
+        event::{detail::Detail, Event},
+        individual::Individual,
+        place::Place,
+        source::{citation::Citation, quay::CertaintyAssessment},
+        GedcomData,
+    },
+    writer::{attribute_to_tag, event_to_tag},
+};
+
+/// A column of the individuals table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IndividualColumn {
+    /// The individual's xref.
+    Xref,
+    /// The full name, without slashes.
+    Name,
+    /// The given name, from `GIVN` or else the name before the slashes.
+    GivenName,
+    /// The surname, from `SURN` or else the name between the slashes.
+    Surname,
+    /// The sex, such as `Male`.
+    Sex,
+    /// The date of the first birth event.
+    BirthDate,
+    /// The place of the first birth event.
+    BirthPlace,
+    /// The date of the first death event.
+    DeathDate,
+    /// The place of the first death event.
+    DeathPlace,
+    /// The families the individual is a child in.
+    FamiliesAsChild,
+    /// The families the individual is a partner in.
+    FamiliesAsPartner,
+}
+
+impl IndividualColumn {
+    /// Every column, in the default order.
+    pub const ALL: [IndividualColumn; 11] = [
+        Self::Xref,
+        Self::Name,
+        Self::GivenName,
+        Self::Surname,
+        Self::Sex,
+        Self::BirthDate,
+        Self::BirthPlace,
+        Self::DeathDate,
+        Self::DeathPlace,
+        Self::FamiliesAsChild,
+        Self::FamiliesAsPartner,
+    ];
+
+    /// Returns the column's header.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Xref => "xref",
+            Self::Name => "name",
+            Self::GivenName => "given_name",
+            Self::Surname => "surname",
+            Self::Sex => "sex",
+            Self::BirthDate => "birth_date",
+            Self::BirthPlace => "birth_place",
+            Self::DeathDate => "death_date",
+            Self::DeathPlace => "death_place",
+            Self::FamiliesAsChild => "families_as_child",
+            Self::FamiliesAsPartner => "families_as_partner",
+        }
+    }
+}
+
+/// A column of the families table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FamilyColumn {
+    /// The family's xref.
+    Xref,
+    /// The xref of the first partner (`HUSB`).
+    Partner1,
+    /// The xref of the second partner (`WIFE`).
+    Partner2,
+    /// The full name of the first partner.
+    Partner1Name,
+    /// The full name of the second partner.
+    Partner2Name,
+    /// The date of the first marriage event.
+    MarriageDate,
+    /// The place of the first marriage event.
+    MarriagePlace,
+    /// The xrefs of the children, in family order.
+    Children,
+    /// The number of children listed.
+    ChildCount,
+}
+
+impl FamilyColumn {
+    /// Every column, in the default order.
+    pub const ALL: [FamilyColumn; 9] = [
+        Self::Xref,
+        Self::Partner1,
+        Self::Partner2,
+        Self::Partner1Name,
+        Self::Partner2Name,
+        Self::MarriageDate,
+        Self::MarriagePlace,
+        Self::Children,
+        Self::ChildCount,
+    ];
+
+    /// Returns the column's header.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Xref => "xref",
+            Self::Partner1 => "partner1",
+            Self::Partner2 => "partner2",
+            Self::Partner1Name => "partner1_name",
+            Self::Partner2Name => "partner2_name",
+            Self::MarriageDate => "marriage_date",
+            Self::MarriagePlace => "marriage_place",
+            Self::Children => "children",
+            Self::ChildCount => "child_count",
+        }
+    }
+}
+
+/// A column of the events table, which also lists individual attributes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EventColumn {
+    /// The xref of the individual or family.
+    Record,
+    /// `INDI` or `FAM`.
+    RecordType,
+    /// The event or attribute tag, such as `BIRT` or `OCCU`.
+    Tag,
+    /// The `TYPE` classification.
+    Type,
+    /// The line value, such as an occupation.
+    Value,
+    /// The date.
+    Date,
+    /// The place.
+    Place,
+}
+
+impl EventColumn {
+    /// Every column, in the default order.
+    pub const ALL: [EventColumn; 7] = [
+        Self::Record,
+        Self::RecordType,
+        Self::Tag,
+        Self::Type,
+        Self::Value,
+        Self::Date,
+        Self::Place,
+    ];
+
+    /// Returns the column's header.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Record => "record",
+            Self::RecordType => "record_type",
+            Self::Tag => "tag",
+            Self::Type => "type",
+            Self::Value => "value",
+            Self::Date => "date",
+            Self::Place => "place",
+        }
+    }
+}
+
+/// A column of the citations table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CitationColumn {
+    /// The xref of the individual or family.
+    Record,
+    /// The tag of the cited event or attribute, empty for the record itself.
+    Tag,
+    /// The xref of the source.
+    Source,
+    /// Where in the source the information is (`PAGE`).
+    Page,
+    /// The certainty assessment from 0 to 3 (`QUAY`).
+    Quality,
+}
+
+impl CitationColumn {
+    /// Every column, in the default order.
+    pub const ALL: [CitationColumn; 5] = [
+        Self::Record,
+        Self::Tag,
+        Self::Source,
+        Self::Page,
+        Self::Quality,
+    ];
+
+    /// Returns the column's header.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Record => "record",
+            Self::Tag => "tag",
+            Self::Source => "source",
+            Self::Page => "page",
+            Self::Quality => "quality",
+        }
+    }
+}
+
+/// Options for the CSV tables.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct CsvConfig {
+    /// The field delimiter (default: `,`).
+    pub delimiter: char,
+    /// Whether to start each table with a header row (default: true).
+    pub header: bool,
+    /// The columns of the individuals table (default: all).
+    pub individual_columns: Vec<IndividualColumn>,
+    /// The columns of the families table (default: all).
+    pub family_columns: Vec<FamilyColumn>,
+    /// The columns of the events table (default: all).
+    pub event_columns: Vec<EventColumn>,
+    /// The columns of the citations table (default: all).
+    pub citation_columns: Vec<CitationColumn>,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            header: true,
+            individual_columns: IndividualColumn::ALL.to_vec(),
+            family_columns: FamilyColumn::ALL.to_vec(),
+            event_columns: EventColumn::ALL.to_vec(),
+            citation_columns: CitationColumn::ALL.to_vec(),
+        }
+    }
+}
+
+/// The four CSV tables of a file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CsvTables {
+    /// One row per individual.
+    pub individuals: String,
+    /// One row per family.
+    pub families: String,
+    /// One row per individual or family event, and per individual attribute.
+    pub events: String,
+    /// One row per source citation of a record, event or attribute.
+    pub citations: String,
+}
+
+impl CsvTables {
+    /// Writes the tables as `individuals.csv`, `families.csv`, `events.csv`
+    /// and `citations.csv` in a directory, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written.
+    pub fn write_to_dir(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        for (name, table) in [
+            ("individuals.csv", &self.individuals),
+            ("families.csv", &self.families),
+            ("events.csv", &self.events),
+            ("citations.csv", &self.citations),
+        ] {
+            std::fs::write(dir.join(name), table)?;
+        }
+        Ok(())
+    }
+}
+
+/// Flattens GEDCOM data into CSV tables.
+#[must_use]
+pub fn export(data: &GedcomData, config: &CsvConfig) -> CsvTables {
+    CsvTables {
+        individuals: individuals(data, config),
+        families: families(data, config),
+        events: events(data, config),
+        citations: citations(data, config),
+    }
+}
+
+/// Accumulates the rows of one table.
+struct Table {
+    delimiter: char,
+    out: String,
+}
+
+impl Table {
+    fn new<C: Copy>(config: &CsvConfig, columns: &[C], name: impl Fn(C) -> &'static str) -> Self {
+        let mut table = Table {
+            delimiter: config.delimiter,
+            out: String::new(),
+        };
+        if config.header {
+            table.row(columns.iter().map(|&c| name(c).to_string()));
+        }
+        table
+    }
+
+    fn row(&mut self, fields: impl Iterator<Item = String>) {
+        for (i, field) in fields.enumerate() {
+            if i > 0 {
+                self.out.push(self.delimiter);
+            }
+            self.push_field(&field);
+        }
+        self.out.push_str("\r\n");
+    }
+
+    fn push_field(&mut self, field: &str) {
+        let needs_quotes = field
+            .chars()
+            .any(|c| c == self.delimiter || matches!(c, '"' | '\n' | '\r'))
+            || field.starts_with(' ')
+            || field.ends_with(' ');
+        if needs_quotes {
+            self.out.push('"');
+            self.out.push_str(&field.replace('"', "\"\""));
+            self.out.push('"');
+        } else {
+            self.out.push_str(field);
+        }
+    }
+}
+
+fn text(value: Option<&str>) -> String {
+    value.unwrap_or_default().to_string()
+}
+
+fn date(date: Option<&Date>) -> String {
+    text(date.and_then(|d| d.value.as_deref()))
+}
+
+fn place(place: Option<&Place>) -> String {
+    text(place.and_then(|p| p.value.as_deref()))
+}
+
+fn list<'a>(values: impl IntoIterator<Item = &'a String>) -> String {
+    values
+        .into_iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn individuals(data: &GedcomData, config: &CsvConfig) -> String {
+    let mut as_child: HashMap<&str, Vec<&String>> = HashMap::new();
+    let mut as_partner: HashMap<&str, Vec<&String>> = HashMap::new();
+    for family in &data.families {
+        let Some(ref xref) = family.xref else {
+            continue;
+        };
+        for child in &family.children {
+            as_child.entry(child).or_default().push(xref);
+        }
+        for partner in [&family.individual1, &family.individual2]
+            .into_iter()
+            .flatten()
+        {
+            as_partner.entry(partner).or_default().push(xref);
+        }
+    }
+
+    let columns = &config.individual_columns;
+    let mut table = Table::new(config, columns, IndividualColumn::name);
+    for individual in &data.individuals {
+        let xref = individual.xref.as_deref().unwrap_or_default();
+        let families = |index: &HashMap<&str, Vec<&String>>| {
+            list(index.get(xref).into_iter().flatten().copied())
+        };
+        table.row(columns.iter().map(|column| {
+            match column {
+                IndividualColumn::Xref => xref.to_string(),
+                IndividualColumn::Name => individual.full_name().unwrap_or_default(),
+                IndividualColumn::GivenName => text(individual.given_name_or_parsed()),
+                IndividualColumn::Surname => text(individual.surname_or_parsed()),
+                IndividualColumn::Sex => individual
+                    .sex
+                    .as_ref()
+                    .map(|s| s.value.to_string())
+                    .unwrap_or_default(),
+                IndividualColumn::BirthDate => {
+                    date(individual.birth().and_then(|e| e.date.as_ref()))
+                }
+                IndividualColumn::BirthPlace => {
+                    place(individual.birth().and_then(|e| e.place.as_ref()))
+                }
+                IndividualColumn::DeathDate => {
+                    date(individual.death().and_then(|e| e.date.as_ref()))
+                }
+                IndividualColumn::DeathPlace => {
+                    place(individual.death().and_then(|e| e.place.as_ref()))
+                }
+                IndividualColumn::FamiliesAsChild => families(&as_child),
+                IndividualColumn::FamiliesAsPartner => families(&as_partner),
+            }
+        }));
+    }
+    table.out
+}
+
+fn families(data: &GedcomData, config: &CsvConfig) -> String {
+    let columns = &config.family_columns;
+    let mut table = Table::new(config, columns, FamilyColumn::name);
+    for family in &data.families {
+        let name = |partner: &Option<String>| {
+            partner
+                .as_deref()
+                .and_then(|x| data.find_individual(x))
+                .and_then(Individual::full_name)
+                .unwrap_or_default()
+        };
+        let marriage = family
+            .events
+            .iter()
+            .chain(&family.family_event)
+            .find(|e| e.event == Event::Marriage);
+        table.row(columns.iter().map(|column| match column {
+            FamilyColumn::Xref => text(family.xref.as_deref()),
+            FamilyColumn::Partner1 => text(family.individual1.as_deref()),
+            FamilyColumn::Partner2 => text(family.individual2.as_deref()),
+            FamilyColumn::Partner1Name => name(&family.individual1),
+            FamilyColumn::Partner2Name => name(&family.individual2),
+            FamilyColumn::MarriageDate => date(marriage.and_then(|e| e.date.as_ref())),
+            FamilyColumn::MarriagePlace => place(marriage.and_then(|e| e.place.as_ref())),
+            FamilyColumn::Children => list(&family.children),
+            FamilyColumn::ChildCount => family.children.len().to_string(),
+        }));
+    }
+    table.out
+}
+
+/// The fields of one events table row.
+struct EventRow<'a> {
+    record: &'a str,
+    record_type: &'static str,
+    tag: &'static str,
+    event_type: Option<&'a str>,
+    value: Option<&'a str>,
+    date: Option<&'a Date>,
+    place: Option<&'a Place>,
+}
+
+impl<'a> EventRow<'a> {
+    fn from_detail(record: &'a str, record_type: &'static str, detail: &'a Detail) -> Self {
+        EventRow {
+            record,
+            record_type,
+            tag: event_to_tag(&detail.event),
+            event_type: detail.event_type.as_deref(),
+            value: detail.value.as_payload(),
+            date: detail.date.as_ref(),
+            place: detail.place.as_ref(),
+        }
+    }
+}
+
+fn events(data: &GedcomData, config: &CsvConfig) -> String {
+    let mut rows = Vec::new();
+    for individual in &data.individuals {
+        let xref = individual.xref.as_deref().unwrap_or_default();
+        rows.extend(
+            individual
+                .events
+                .iter()
+                .map(|e| EventRow::from_detail(xref, "INDI", e)),
+        );
+        rows.extend(individual.attributes.iter().map(|a| EventRow {
+            record: xref,
+            record_type: "INDI",
+            tag: attribute_to_tag(&a.attribute),
+            event_type: a.attribute_type.as_deref(),
+            value: a.value.as_deref(),
+            date: a.date.as_ref(),
+            place: a.place.as_ref(),
+        }));
+    }
+    for family in &data.families {
+        let xref = family.xref.as_deref().unwrap_or_default();
+        rows.extend(
+            family
+                .events
+                .iter()
+                .chain(&family.family_event)
+                .map(|e| EventRow::from_detail(xref, "FAM", e)),
+        );
+    }
+
+    let columns = &config.event_columns;
+    let mut table = Table::new(config, columns, EventColumn::name);
+    for row in rows {
+        table.row(columns.iter().map(|column| match column {
+            EventColumn::Record => row.record.to_string(),
+            EventColumn::RecordType => row.record_type.to_string(),
+            EventColumn::Tag => row.tag.to_string(),
+            EventColumn::Type => text(row.event_type),
+            EventColumn::Value => text(row.value),
+            EventColumn::Date => date(row.date),
+            EventColumn::Place => place(row.place),
+        }));
+    }
+    table.out
+}
+
+fn event_citations<'a>(
+    rows: &mut Vec<(&'a str, &'static str, &'a Citation)>,
+    xref: &'a str,
+    events: &'a [Detail],
+) {
+    for event in events {
+        let tag = event_to_tag(&event.event);
+        rows.extend(event.citations.iter().map(|c| (xref, tag, c)));
+    }
+}
+
+fn citations(data: &GedcomData, config: &CsvConfig) -> String {
+    let mut rows: Vec<(&str, &str, &Citation)> = Vec::new();
+    for individual in &data.individuals {
+        let xref = individual.xref.as_deref().unwrap_or_default();
+        rows.extend(individual.source.iter().map(|c| (xref, "", c)));
+        event_citations(&mut rows, xref, &individual.events);
+        for attribute in &individual.attributes {
+            let tag = attribute_to_tag(&attribute.attribute);
+            rows.extend(attribute.sources.iter().map(|c| (xref, tag, c)));
+        }
+    }
+    for family in &data.families {
+        let xref = family.xref.as_deref().unwrap_or_default();
+        rows.extend(family.sources.iter().map(|c| (xref, "", c)));
+        event_citations(&mut rows, xref, &family.events);
+        event_citations(&mut rows, xref, &family.family_event);
+    }
+
+    let columns = &config.citation_columns;
+    let mut table = Table::new(config, columns, CitationColumn::name);
+    for (record, tag, citation) in rows {
+        table.row(columns.iter().map(|column| {
+            match column {
+                CitationColumn::Record => record.to_string(),
+                CitationColumn::Tag => tag.to_string(),
+                CitationColumn::Source => citation.xref.clone(),
+                CitationColumn::Page => text(citation.page.as_deref()),
+                CitationColumn::Quality => citation
+                    .certainty_assessment
+                    .as_ref()
+                    .and_then(CertaintyAssessment::get_int)
+                    .map(|q| q.to_string())
+                    .unwrap_or_default(),
+            }
+        }));
+    }
+    table.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    fn sample() -> GedcomData {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 NAME John /Doe/\n\
+            1 SEX M\n\
+            1 BIRT\n\
+            2 DATE 1 JAN 1900\n\
+            2 PLAC Boston, MA\n\
+            2 SOUR @S1@\n\
+            3 PAGE p. 12\n\
+            3 QUAY 3\n\
+            1 OCCU Farmer \"Jack\"\n\
+            0 @I2@ INDI\n\
+            1 NAME Jane /Roe/\n\
+            1 SOUR @S1@\n\
+            0 @I3@ INDI\n\
+            1 NAME Jim /Doe/\n\
+            0 @F1@ FAM\n\
+            1 HUSB @I1@\n\
+            1 WIFE @I2@\n\
+            1 CHIL @I3@\n\
+            1 MARR\n\
+            2 DATE 1925\n\
+            0 @S1@ SOUR\n\
+            1 TITL Records\n\
+            0 TRLR";
+        GedcomBuilder::new().build_from_str(source).unwrap()
+    }
+
+    #[test]
+    fn test_csv_tables() {
+        let tables = export(&sample(), &CsvConfig::default());
+
+        let individuals: Vec<&str> = tables.individuals.lines().collect();
+        assert_eq!(
+            individuals[0],
+            "xref,name,given_name,surname,sex,birth_date,birth_place,death_date,death_place,families_as_child,families_as_partner"
+        );
+        assert_eq!(
+            individuals[1],
+            "@I1@,John Doe,John,Doe,Male,1 JAN 1900,\"Boston, MA\",,,,@F1@"
+        );
+        assert!(individuals[3].ends_with(",@F1@,"));
+
+        assert_eq!(
+            tables.families.lines().nth(1),
+            Some("@F1@,@I1@,@I2@,John Doe,Jane Roe,1925,,@I3@,1")
+        );
+
+        let events: Vec<&str> = tables.events.lines().skip(1).collect();
+        assert_eq!(
+            events,
+            vec![
+                "@I1@,INDI,BIRT,,,1 JAN 1900,\"Boston, MA\"",
+                "@I1@,INDI,OCCU,,\"Farmer \"\"Jack\"\"\",,",
+                "@F1@,FAM,MARR,,,1925,",
+            ]
+        );
+
+        let citations: Vec<&str> = tables.citations.lines().skip(1).collect();
+        assert_eq!(citations, vec!["@I1@,BIRT,@S1@,p. 12,3", "@I2@,,@S1@,,"]);
+    }
+
+    #[test]
+    fn test_csv_columns_and_delimiter() {
+        let config = CsvConfig {
+            delimiter: ';',
+            header: false,
+            family_columns: vec![FamilyColumn::ChildCount, FamilyColumn::Xref],
+            ..CsvConfig::default()
+        };
+        let tables = export(&sample(), &config);
+        assert_eq!(tables.families, "1;@F1@\r\n");
+        assert!(tables.individuals.contains(";Boston, MA;"));
+        assert!(tables.individuals.starts_with("@I1@;John Doe;"));
+
+        let dir = std::env::temp_dir().join("ged_io_csv_export");
+        tables.write_to_dir(&dir).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("families.csv")).unwrap(),
+            tables.families
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,368 @@
+//! Family-tree graphs in the Graphviz DOT language.
+//!
+//! [`export`] turns GEDCOM data into a directed graph that `dot` can render:
+//! each individual is a box, each family a small point joining the spouses
+//! to their children. [`DotConfig`] narrows the graph to the ancestors or
+//! descendants of one person, limits the number of generations, chooses the
+//! labels and can draw each couple inside a cluster.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{export::dot, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 FAMC @F1@\n0 @I2@ INDI\n1 NAME Jim /Doe/\n0 @F1@ FAM\n1 HUSB @I2@\n1 CHIL @I1@\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let config = dot::DotConfig {
+//!     scope: dot::Scope::Ancestors("@I1@".to_string()),
+//!     ..dot::DotConfig::default()
+//! };
+//! let graph = dot::export(&data, &config).unwrap();
+//! assert!(graph.starts_with("digraph family_tree {"));
+//! assert!(graph.contains("\"I2\" -> \"F1\" [dir=none];"));
+//! // Pipe the output to `dot -Tsvg` to render it.
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+
+use crate::types::{individual::Individual, GedcomData};
+
+use super::svg::lifespan;
+
+/// Which people a graph shows.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Scope {
+    /// Every individual and family of the file.
+    #[default]
+    Everyone,
+    /// An individual, identified by xref, and their ancestors through every
+    /// family they are a child in.
+    Ancestors(String),
+    /// An individual, identified by xref, and their descendants, along with
+    /// the spouses of each of them.
+    Descendants(String),
+}
+
+/// Options for [`export`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct DotConfig {
+    /// The people to include (default: everyone).
+    pub scope: Scope,
+    /// Number of generations to include, the root's included, when the scope
+    /// has a root (default: no limit).
+    pub max_generations: Option<u32>,
+    /// Whether labels show names rather than xrefs (default: true).
+    pub show_names: bool,
+    /// Whether to add birth and death dates under names (default: true).
+    pub show_lifespan: bool,
+    /// Whether to draw each couple and their family point in a cluster
+    /// (default: false).
+    ///
+    /// Someone with several families stays in the cluster of the first one.
+    pub cluster_families: bool,
+}
+
+impl Default for DotConfig {
+    fn default() -> Self {
+        Self {
+            scope: Scope::Everyone,
+            max_generations: None,
+            show_names: true,
+            show_lifespan: true,
+            cluster_families: false,
+        }
+    }
+}
+
+/// Renders the individuals and families selected by `config` as a DOT graph.
+///
+/// Individuals and families keep the order of the file. Returns `None` if
+/// the scope names an individual that does not exist.
+#[must_use]
+pub fn export(data: &GedcomData, config: &DotConfig) -> Option<String> {
+    let selection = Selection::new(data, config)?;
+
+    let mut dot = String::from("digraph family_tree {\n");
+    dot.push_str("  node [shape=box];\n");
+
+    let mut drawn = HashSet::new();
+    for individual in &data.individuals {
+        let Some(xref) = individual.xref.as_deref() else {
+            continue;
+        };
+        if selection.people.contains(xref) {
+            drawn.insert(xref);
+            let _ = writeln!(
+                dot,
+                "  {} [label={}];",
+                node_id(xref),
+                quote(&label(individual, config))
+            );
+        }
+    }
+
+    let mut clustered = HashSet::new();
+    for family in &data.families {
+        let Some(xref) = family.xref.as_deref() else {
+            continue;
+        };
+        if !selection.families.contains(xref) {
+            continue;
+        }
+        let spouses: Vec<&str> = [&family.individual1, &family.individual2]
+            .into_iter()
+            .filter_map(|s| s.as_deref())
+            .filter(|s| drawn.contains(s))
+            .collect();
+
+        let id = node_id(xref);
+        if config.cluster_families {
+            let _ = writeln!(
+                dot,
+                "  subgraph {} {{",
+                quote(&format!("cluster_{}", bare(xref)))
+            );
+            let _ = writeln!(dot, "    {id} [shape=point];");
+            for spouse in spouses.iter().filter(|s| clustered.insert(**s)) {
+                let _ = writeln!(dot, "    {};", node_id(spouse));
+            }
+            dot.push_str("  }\n");
+        } else {
+            let _ = writeln!(dot, "  {id} [shape=point];");
+        }
+
+        for spouse in &spouses {
+            let _ = writeln!(dot, "  {} -> {id} [dir=none];", node_id(spouse));
+        }
+        for child in family
+            .children
+            .iter()
+            .filter(|c| drawn.contains(c.as_str()))
+        {
+            let _ = writeln!(dot, "  {id} -> {};", node_id(child));
+        }
+    }
+
+    dot.push_str("}\n");
+    Some(dot)
+}
+
+/// The xrefs of the people and families a graph includes.
+struct Selection<'a> {
+    people: HashSet<&'a str>,
+    families: HashSet<&'a str>,
+}
+
+impl<'a> Selection<'a> {
+    fn new(data: &'a GedcomData, config: &DotConfig) -> Option<Self> {
+        let (root, ancestors) = match &config.scope {
+            Scope::Everyone => {
+                return Some(Self {
+                    people: data
+                        .individuals
+                        .iter()
+                        .filter_map(|i| i.xref.as_deref())
+                        .collect(),
+                    families: data
+                        .families
+                        .iter()
+                        .filter_map(|f| f.xref.as_deref())
+                        .collect(),
+                });
+            }
+            Scope::Ancestors(root) => (root, true),
+            Scope::Descendants(root) => (root, false),
+        };
+        let root = data.find_individual(root)?.xref.as_deref()?;
+
+        let mut selection = Self {
+            people: HashSet::from([root]),
+            families: HashSet::new(),
+        };
+        // Breadth-first, so that someone reachable along several lines is
+        // counted at the generation nearest the root.
+        let mut queue = VecDeque::from([(root, 1)]);
+        while let Some((xref, generation)) = queue.pop_front() {
+            if config.max_generations.is_some_and(|max| generation >= max) {
+                continue;
+            }
+            let families = if ancestors {
+                data.get_families_as_child(xref)
+            } else {
+                data.get_families_as_spouse(xref)
+            };
+            for family in families {
+                let Some(family_xref) = family.xref.as_deref() else {
+                    continue;
+                };
+                selection.families.insert(family_xref);
+                let spouses = [&family.individual1, &family.individual2]
+                    .into_iter()
+                    .filter_map(|s| s.as_deref());
+                let next: Vec<&str> = if ancestors {
+                    spouses.collect()
+                } else {
+                    selection.people.extend(spouses);
+                    family.children.iter().map(String::as_str).collect()
+                };
+                for person in next {
+                    if selection.people.insert(person) {
+                        queue.push_back((person, generation + 1));
+                    }
+                }
+            }
+        }
+        Some(selection)
+    }
+}
+
+/// Returns the name, or xref, of an individual and optionally their dates.
+fn label(individual: &Individual, config: &DotConfig) -> String {
+    let xref = individual.xref.clone().unwrap_or_default();
+    let mut label = if config.show_names {
+        individual.full_name().unwrap_or(xref)
+    } else {
+        xref
+    };
+    if let Some(dates) = lifespan(individual).filter(|_| config.show_lifespan) {
+        label.push('\n');
+        label.push_str(&dates);
+    }
+    label
+}
+
+/// Returns the DOT node identifier of an xref, which is the quoted xref
+/// without its `@` delimiters.
+fn node_id(xref: &str) -> String {
+    quote(bare(xref))
+}
+
+fn bare(xref: &str) -> &str {
+    xref.trim_matches('@')
+}
+
+/// Quotes text as a DOT string.
+fn quote(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const FAMILY: &str = "\
+        0 HEAD\n\
+        1 GEDC\n\
+        2 VERS 5.5\n\
+        0 @I1@ INDI\n\
+        1 NAME Grandfather /Doe/\n\
+        0 @I2@ INDI\n\
+        1 NAME Father /Doe/\n\
+        1 BIRT\n\
+        2 DATE 1920\n\
+        0 @I3@ INDI\n\
+        1 NAME Mother /\"Roe\"/\n\
+        0 @I4@ INDI\n\
+        1 NAME Son /Doe/\n\
+        0 @I5@ INDI\n\
+        1 NAME Daughter-in-law /Poe/\n\
+        0 @I6@ INDI\n\
+        1 NAME Grandson /Doe/\n\
+        0 @F1@ FAM\n\
+        1 HUSB @I1@\n\
+        1 CHIL @I2@\n\
+        0 @F2@ FAM\n\
+        1 HUSB @I2@\n\
+        1 WIFE @I3@\n\
+        1 CHIL @I4@\n\
+        0 @F3@ FAM\n\
+        1 HUSB @I4@\n\
+        1 WIFE @I5@\n\
+        1 CHIL @I6@\n\
+        0 TRLR";
+
+    #[test]
+    fn test_export_everyone() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let dot = export(&data, &DotConfig::default()).unwrap();
+
+        assert!(dot.starts_with("digraph family_tree {\n  node [shape=box];\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("  \"I2\" [label=\"Father Doe\\nb. 1920\"];\n"));
+        assert!(dot.contains("  \"I3\" [label=\"Mother \\\"Roe\\\"\"];\n"));
+        assert!(dot.contains("  \"F2\" [shape=point];\n"));
+        assert!(dot.contains(
+            "  \"I2\" -> \"F2\" [dir=none];\n  \"I3\" -> \"F2\" [dir=none];\n  \"F2\" -> \"I4\";\n"
+        ));
+        assert_eq!(dot.matches(" -> ").count(), 8);
+    }
+
+    #[test]
+    fn test_export_ancestors_and_descendants() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+
+        let config = DotConfig {
+            scope: Scope::Ancestors("@I4@".to_string()),
+            max_generations: Some(2),
+            show_names: false,
+            ..DotConfig::default()
+        };
+        let dot = export(&data, &config).unwrap();
+        assert!(dot.contains("  \"I2\" [label=\"@I2@\\nb. 1920\"];\n"));
+        assert!(dot.contains("\"F2\" -> \"I4\";"));
+        assert!(!dot.contains("\"I1\""));
+        assert!(!dot.contains("\"I6\""));
+
+        let config = DotConfig {
+            scope: Scope::Descendants("@I2@".to_string()),
+            show_lifespan: false,
+            ..DotConfig::default()
+        };
+        let dot = export(&data, &config).unwrap();
+        assert!(dot.contains("  \"I2\" [label=\"Father Doe\"];\n"));
+        assert!(dot.contains("\"I5\" -> \"F3\" [dir=none];"));
+        assert!(dot.contains("\"F3\" -> \"I6\";"));
+        assert!(!dot.contains("\"I1\""));
+        assert!(!dot.contains("\"F1\""));
+
+        let config = DotConfig {
+            scope: Scope::Ancestors("@MISSING@".to_string()),
+            ..DotConfig::default()
+        };
+        assert!(export(&data, &config).is_none());
+    }
+
+    #[test]
+    fn test_export_clusters() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let config = DotConfig {
+            cluster_families: true,
+            ..DotConfig::default()
+        };
+        let dot = export(&data, &config).unwrap();
+
+        assert!(dot.contains(
+            "  subgraph \"cluster_F2\" {\n    \"F2\" [shape=point];\n    \"I2\";\n    \"I3\";\n  }\n"
+        ));
+        assert_eq!(dot.matches("subgraph").count(), 3);
+    }
+}
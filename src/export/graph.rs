@@ -0,0 +1,343 @@
+//! The individual/family network as `GraphML` or a nodes-and-edges structure.
+//!
+//! [`export`] builds a [`Graph`] with one node per individual and per family.
+//! Spouses point to their families, and families to their children, with
+//! adopted children told apart from birth ones. [`Graph::to_graphml`] writes
+//! it for tools such as Gephi, Neo4j or networkx, and with the `json` feature
+//! enabled the graph serializes as `{"nodes": [...], "edges": [...]}`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{export::graph, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 @I2@ INDI\n1 NAME Jim /Doe/\n0 @F1@ FAM\n1 HUSB @I2@\n1 CHIL @I1@\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let network = graph::export(&data);
+//! assert_eq!(network.nodes.len(), 3);
+//! assert_eq!(network.edges[0].kind, graph::EdgeKind::Spouse);
+//! assert!(network.to_graphml().contains(r#"<edge source="@F1@" target="@I1@">"#));
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::escape;
+use crate::types::{
+    event::Event,
+    individual::{
+        family_link::{pedigree::Pedigree, FamilyLinkType},
+        Individual,
+    },
+    GedcomData,
+};
+
+/// What a node stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NodeKind {
+    /// An `INDI` record.
+    Individual,
+    /// A `FAM` record.
+    Family,
+}
+
+/// What an edge stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EdgeKind {
+    /// From a spouse or partner to their family.
+    Spouse,
+    /// From a family to a child born into it.
+    ParentChild,
+    /// From a family to a child adopted into it, through a `PEDI adopted`
+    /// link or an `ADOP` event pointing at the family.
+    Adoption,
+}
+
+impl EdgeKind {
+    /// Returns the name used for the edge kind in `GraphML` output.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            EdgeKind::Spouse => "spouse",
+            EdgeKind::ParentChild => "parent_child",
+            EdgeKind::Adoption => "adoption",
+        }
+    }
+}
+
+/// An individual or family.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GraphNode {
+    /// The record's xref.
+    pub id: String,
+    /// Whether this is an individual or a family.
+    pub kind: NodeKind,
+    /// The individual's name, or the xref for families and unnamed people.
+    pub label: String,
+    /// The individual's sex, such as `Male`.
+    pub sex: Option<String>,
+    /// The date of the individual's first birth event.
+    pub birth_date: Option<String>,
+    /// The date of the individual's first death event.
+    pub death_date: Option<String>,
+}
+
+/// A typed link between two nodes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GraphEdge {
+    /// The id of the spouse or family the edge starts from.
+    pub source: String,
+    /// The id of the family or child the edge leads to.
+    pub target: String,
+    /// What the edge stands for.
+    pub kind: EdgeKind,
+}
+
+/// The individuals and families of a file, and the links between them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Graph {
+    /// Individuals, then families, in file order.
+    pub nodes: Vec<GraphNode>,
+    /// For each family, its spouse edges and then its child edges.
+    pub edges: Vec<GraphEdge>,
+}
+
+impl Graph {
+    /// Returns the graph as a directed `GraphML` document.
+    ///
+    /// Node attributes are declared as `kind`, `label`, `sex`, `birth_date`
+    /// and `death_date` keys, and edges carry their kind in a `kind` key.
+    #[must_use]
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        ));
+        for key in ["kind", "label", "sex", "birth_date", "death_date"] {
+            let _ = writeln!(
+                xml,
+                r#"  <key id="{key}" for="node" attr.name="{key}" attr.type="string"/>"#
+            );
+        }
+        xml.push_str(
+            "  <key id=\"edge_kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+        );
+        xml.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+
+        for node in &self.nodes {
+            let _ = writeln!(xml, r#"    <node id="{}">"#, escape(&node.id));
+            let kind = match node.kind {
+                NodeKind::Individual => "individual",
+                NodeKind::Family => "family",
+            };
+            let values = [
+                ("kind", Some(kind)),
+                ("label", Some(node.label.as_str())),
+                ("sex", node.sex.as_deref()),
+                ("birth_date", node.birth_date.as_deref()),
+                ("death_date", node.death_date.as_deref()),
+            ];
+            for (key, value) in values {
+                if let Some(value) = value {
+                    let _ = writeln!(xml, r#"      <data key="{key}">{}</data>"#, escape(value));
+                }
+            }
+            xml.push_str("    </node>\n");
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                xml,
+                r#"    <edge source="{}" target="{}">"#,
+                escape(&edge.source),
+                escape(&edge.target)
+            );
+            let _ = writeln!(
+                xml,
+                r#"      <data key="edge_kind">{}</data>"#,
+                edge.kind.name()
+            );
+            xml.push_str("    </edge>\n");
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+}
+
+/// Builds the individual/family graph of `data`.
+///
+/// Records without an xref have no id and are left out, as are edges to
+/// records that do not exist.
+#[must_use]
+pub fn export(data: &GedcomData) -> Graph {
+    let mut graph = Graph::default();
+    for individual in &data.individuals {
+        if let Some(xref) = individual.xref.clone() {
+            graph.nodes.push(GraphNode {
+                label: individual.full_name().unwrap_or_else(|| xref.clone()),
+                id: xref,
+                kind: NodeKind::Individual,
+                sex: individual.sex.as_ref().map(|s| s.value.to_string()),
+                birth_date: individual.birth_date().map(str::to_string),
+                death_date: individual.death_date().map(str::to_string),
+            });
+        }
+    }
+    let people: HashSet<&str> = data
+        .individuals
+        .iter()
+        .filter_map(|i| i.xref.as_deref())
+        .collect();
+
+    for family in &data.families {
+        let Some(xref) = family.xref.as_deref() else {
+            continue;
+        };
+        let spouses = [&family.individual1, &family.individual2]
+            .into_iter()
+            .filter_map(|s| s.as_deref())
+            .filter(|s| people.contains(s));
+        for spouse in spouses {
+            graph.edges.push(GraphEdge {
+                source: spouse.to_string(),
+                target: xref.to_string(),
+                kind: EdgeKind::Spouse,
+            });
+        }
+        for child in family
+            .children
+            .iter()
+            .filter_map(|c| data.find_individual(c))
+        {
+            let Some(child_xref) = child.xref.clone() else {
+                continue;
+            };
+            graph.edges.push(GraphEdge {
+                source: xref.to_string(),
+                target: child_xref,
+                kind: if is_adopted(child, xref) {
+                    EdgeKind::Adoption
+                } else {
+                    EdgeKind::ParentChild
+                },
+            });
+        }
+
+        graph.nodes.push(GraphNode {
+            id: xref.to_string(),
+            kind: NodeKind::Family,
+            label: xref.to_string(),
+            sex: None,
+            birth_date: None,
+            death_date: None,
+        });
+    }
+    graph
+}
+
+/// Returns true if an individual was adopted into a family.
+fn is_adopted(individual: &Individual, family: &str) -> bool {
+    let by_pedigree = individual.families.iter().any(|link| {
+        link.family_link_type == FamilyLinkType::Child
+            && link.xref == family
+            && link.pedigree_linkage_type == Some(Pedigree::Adopted)
+    });
+    let by_event = individual.events.iter().any(|event| {
+        event.event == Event::Adoption
+            && event
+                .family_link
+                .as_ref()
+                .is_some_and(|link| link.xref == family)
+    });
+    by_pedigree || by_event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const FAMILY: &str = "\
+        0 HEAD\n\
+        1 GEDC\n\
+        2 VERS 5.5.1\n\
+        0 @I1@ INDI\n\
+        1 NAME Jim /Doe & Co/\n\
+        1 SEX M\n\
+        1 BIRT\n\
+        2 DATE 1900\n\
+        0 @I2@ INDI\n\
+        1 NAME Jane /Roe/\n\
+        0 @I3@ INDI\n\
+        1 NAME Born /Doe/\n\
+        1 FAMC @F1@\n\
+        0 @I4@ INDI\n\
+        1 NAME Adopted /Doe/\n\
+        1 FAMC @F1@\n\
+        2 PEDI adopted\n\
+        0 @I5@ INDI\n\
+        1 ADOP\n\
+        2 FAMC @F1@\n\
+        0 @F1@ FAM\n\
+        1 HUSB @I1@\n\
+        1 WIFE @I2@\n\
+        1 CHIL @I3@\n\
+        1 CHIL @I4@\n\
+        1 CHIL @I5@\n\
+        1 CHIL @MISSING@\n\
+        0 TRLR";
+
+    #[test]
+    fn test_export_graph() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let graph = export(&data);
+
+        assert_eq!(graph.nodes.len(), 6);
+        assert_eq!(graph.nodes[0].label, "Jim Doe & Co");
+        assert_eq!(graph.nodes[0].sex.as_deref(), Some("Male"));
+        assert_eq!(graph.nodes[0].birth_date.as_deref(), Some("1900"));
+        assert_eq!(graph.nodes[5].kind, NodeKind::Family);
+
+        let kinds: Vec<(&str, &str, EdgeKind)> = graph
+            .edges
+            .iter()
+            .map(|e| (e.source.as_str(), e.target.as_str(), e.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("@I1@", "@F1@", EdgeKind::Spouse),
+                ("@I2@", "@F1@", EdgeKind::Spouse),
+                ("@F1@", "@I3@", EdgeKind::ParentChild),
+                ("@F1@", "@I4@", EdgeKind::Adoption),
+                ("@F1@", "@I5@", EdgeKind::Adoption),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let xml = export(&data).to_graphml();
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml"));
+        assert!(xml.ends_with("</graph>\n</graphml>\n"));
+        assert!(xml.contains(
+            "    <node id=\"@I1@\">\n      <data key=\"kind\">individual</data>\n      <data key=\"label\">Jim Doe &amp; Co</data>\n"
+        ));
+        assert!(xml.contains(
+            "    <edge source=\"@F1@\" target=\"@I4@\">\n      <data key=\"edge_kind\">adoption</data>\n    </edge>\n"
+        ));
+        assert_eq!(xml.matches("<node ").count(), 6);
+        assert_eq!(xml.matches("<edge ").count(), 5);
+    }
+}
@@ -0,0 +1,241 @@
+//! Ancestor-only GEDCOM files numbered by the Sosa-Stradonitz system.
+//!
+//! [`ancestors`] extracts the ancestors of an individual into a dataset of
+//! its own, as DNA-matching services ask for: the root is number 1, the
+//! father of number `n` is `2n` and the mother `2n + 1`. When the same
+//! person is an ancestor through several lines (pedigree collapse), the
+//! record appears once, with a `FAMS` link to each family it is a parent
+//! in, and [`SosaExport::collapses`] lists every number it holds.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{export::sosa, GedcomBuilder, GedcomWriter};
+//!
+//! // The parents of @I1@ are first cousins: @I6@ and @I7@ are the common
+//! // grandparents of both.
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 FAMC @F1@\n0 @I2@ INDI\n1 FAMC @F2@\n1 FAMS @F1@\n\
+//!     0 @I3@ INDI\n1 FAMC @F3@\n1 FAMS @F1@\n0 @I4@ INDI\n1 FAMC @F4@\n1 FAMS @F2@\n\
+//!     0 @I5@ INDI\n1 FAMC @F4@\n1 FAMS @F3@\n0 @I6@ INDI\n1 FAMS @F4@\n0 @I7@ INDI\n1 FAMS @F4@\n\
+//!     0 @F1@ FAM\n1 HUSB @I2@\n1 WIFE @I3@\n1 CHIL @I1@\n\
+//!     0 @F2@ FAM\n1 HUSB @I4@\n1 CHIL @I2@\n0 @F3@ FAM\n1 WIFE @I5@\n1 CHIL @I3@\n\
+//!     0 @F4@ FAM\n1 HUSB @I6@\n1 WIFE @I7@\n1 CHIL @I4@\n1 CHIL @I5@\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let export = sosa::ancestors(&data, "@I1@", &sosa::SosaConfig::default()).unwrap();
+//! assert_eq!(export.data.individuals.len(), 7);
+//! assert_eq!(export.collapses[0].xref, "@I6@");
+//! assert_eq!(export.collapses[0].numbers, vec![8, 14]);
+//!
+//! let gedcom = GedcomWriter::new().write_to_string(&export.data).unwrap();
+//! assert!(gedcom.contains("0 @I6@ INDI"));
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    individual::{family_link::pedigree::Pedigree, family_link::FamilyLinkType, Individual},
+    GedcomData,
+};
+
+/// The most generations a Sosa number in a `u64` can reach.
+pub const MAX_GENERATIONS: u32 = 64;
+
+/// Options for [`ancestors`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct SosaConfig {
+    /// Generations to include, counting the root as the first (default and
+    /// maximum: [`MAX_GENERATIONS`]).
+    pub generations: u32,
+    /// Also include the sources, repositories, multimedia and shared note
+    /// records that the ancestors and their families cite (default: true).
+    pub include_dependencies: bool,
+}
+
+impl Default for SosaConfig {
+    fn default() -> Self {
+        Self {
+            generations: MAX_GENERATIONS,
+            include_dependencies: true,
+        }
+    }
+}
+
+/// An ancestor found on more than one line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Collapse {
+    /// The ancestor's xref.
+    pub xref: String,
+    /// The Sosa numbers of the ancestor, lowest first. The ancestor's own
+    /// ancestors are numbered from the lowest one only.
+    pub numbers: Vec<u64>,
+}
+
+/// The result of [`ancestors`].
+#[derive(Clone, Debug)]
+pub struct SosaExport {
+    /// The root, its ancestors, and the families linking them, with the
+    /// header of the original dataset. Pointers to other records are
+    /// removed.
+    pub data: GedcomData,
+    /// The lowest Sosa number of each included individual, by xref.
+    pub numbers: HashMap<String, u64>,
+    /// The ancestors found on more than one line, by lowest number.
+    pub collapses: Vec<Collapse>,
+}
+
+impl SosaExport {
+    /// Returns the xrefs of the included individuals in Sosa order.
+    #[must_use]
+    pub fn by_number(&self) -> Vec<(u64, &str)> {
+        let mut numbered: Vec<(u64, &str)> = self
+            .numbers
+            .iter()
+            .map(|(xref, &n)| (n, xref.as_str()))
+            .collect();
+        numbered.sort_unstable();
+        numbered
+    }
+}
+
+/// Extracts the ancestors of the individual `root`.
+///
+/// Each individual's parents are taken from its birth family: the first
+/// `FAMC` link with a birth pedigree or none, or else the first one.
+///
+/// Returns `None` if there is no individual `root`.
+#[must_use]
+pub fn ancestors(data: &GedcomData, root: &str, config: &SosaConfig) -> Option<SosaExport> {
+    data.find_individual(root)?;
+    let generations = config.generations.clamp(1, MAX_GENERATIONS);
+
+    let mut numbers: HashMap<String, u64> = HashMap::new();
+    let mut repeats: HashMap<String, Vec<u64>> = HashMap::new();
+    let mut families: Vec<&str> = Vec::new();
+    let mut queue = VecDeque::from([(root, 1_u64, 1_u32)]);
+    // Breadth-first, so that each individual is first reached by its lowest number.
+    while let Some((xref, number, generation)) = queue.pop_front() {
+        if numbers.contains_key(xref) {
+            repeats.entry(xref.to_string()).or_default().push(number);
+            continue;
+        }
+        numbers.insert(xref.to_string(), number);
+        if generation >= generations {
+            continue;
+        }
+        let Some(family) = data
+            .find_individual(xref)
+            .and_then(birth_family)
+            .and_then(|f| data.find_family(f))
+        else {
+            continue;
+        };
+        let parents = [&family.individual1, &family.individual2];
+        for (offset, parent) in (0..).zip(parents) {
+            if let Some(parent) = parent.as_deref() {
+                if data.find_individual(parent).is_some() {
+                    queue.push_back((parent, number * 2 + offset, generation + 1));
+                }
+            }
+        }
+        if let Some(family) = family.xref.as_deref() {
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        }
+    }
+
+    let mut collapses: Vec<Collapse> = repeats
+        .into_iter()
+        .map(|(xref, repeated)| {
+            let mut numbers = vec![numbers[&xref]];
+            numbers.extend(repeated);
+            Collapse { xref, numbers }
+        })
+        .collect();
+    collapses.sort_by_key(|c| c.numbers[0]);
+
+    let mut xrefs: Vec<(u64, &str)> = numbers.iter().map(|(x, &n)| (n, x.as_str())).collect();
+    xrefs.sort_unstable();
+    let mut selected: Vec<&str> = xrefs.into_iter().map(|(_, xref)| xref).collect();
+    selected.extend(families);
+    // Every xref was found above, so this cannot fail.
+    let extracted = data
+        .extract_records(&selected, config.include_dependencies)
+        .ok()?;
+
+    Some(SosaExport {
+        data: extracted,
+        numbers,
+        collapses,
+    })
+}
+
+/// Returns the xref of the family the individual was born into.
+fn birth_family(individual: &Individual) -> Option<&str> {
+    let mut as_child = individual
+        .families
+        .iter()
+        .filter(|link| link.family_link_type == FamilyLinkType::Child);
+    let first = as_child.clone().next();
+    as_child
+        .find(|link| matches!(link.pedigree_linkage_type, None | Some(Pedigree::Birth)))
+        .or(first)
+        .map(|link| link.xref.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const SAMPLE: &str = "\
+        0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+        0 @I1@ INDI\n1 NAME Root\n1 FAMC @F2@\n2 PEDI adopted\n1 FAMC @F1@\n\
+        0 @I2@ INDI\n1 NAME Father\n1 FAMS @F1@\n1 SOUR @S1@\n\
+        0 @I3@ INDI\n1 NAME Mother\n1 FAMS @F1@\n1 FAMC @F3@\n\
+        0 @I4@ INDI\n1 NAME Adoptive\n1 FAMS @F2@\n\
+        0 @I5@ INDI\n1 NAME Sibling\n1 FAMC @F1@\n\
+        0 @I6@ INDI\n1 NAME Grandmother\n1 FAMS @F3@\n\
+        0 @F1@ FAM\n1 HUSB @I2@\n1 WIFE @I3@\n1 CHIL @I1@\n1 CHIL @I5@\n\
+        0 @F2@ FAM\n1 HUSB @I4@\n1 CHIL @I1@\n\
+        0 @F3@ FAM\n1 WIFE @I6@\n1 CHIL @I3@\n\
+        0 @S1@ SOUR\n1 TITL Register\n\
+        0 TRLR";
+
+    #[test]
+    fn test_sosa_ancestors() {
+        let data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let export = ancestors(&data, "@I1@", &SosaConfig::default()).unwrap();
+        assert_eq!(
+            export.by_number(),
+            vec![(1, "@I1@"), (2, "@I2@"), (3, "@I3@"), (7, "@I6@")]
+        );
+        assert!(export.collapses.is_empty());
+
+        let extracted = &export.data;
+        assert_eq!(extracted.families.len(), 2);
+        assert_eq!(extracted.sources.len(), 1);
+        let family = extracted.find_family("@F1@").unwrap();
+        assert_eq!(family.children, vec!["@I1@".to_string()]);
+        let root = extracted.find_individual("@I1@").unwrap();
+        assert_eq!(root.families.len(), 1);
+        assert_eq!(root.families[0].xref, "@F1@");
+
+        let config = SosaConfig {
+            generations: 2,
+            include_dependencies: false,
+        };
+        let export = ancestors(&data, "@I1@", &config).unwrap();
+        assert_eq!(export.numbers.len(), 3);
+        assert!(export.data.sources.is_empty());
+        assert!(export.data.find_individual("@I3@").unwrap().families.len() == 1);
+        assert!(ancestors(&data, "@I9@", &config).is_none());
+    }
+}
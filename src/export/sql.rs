@@ -0,0 +1,517 @@
+//! SQL dumps for querying family data with plain SQL.
+//!
+//! [`export`] writes a script that creates one table per GEDCOM record type,
+//! plus link tables for children, events and citations, and fills them with
+//! `INSERT` statements. Records reference each other through foreign keys on
+//! their xrefs. The script sticks to `TEXT` and `INTEGER` columns, so it loads
+//! into `SQLite` with `sqlite3 family.db < family.sql` as well as into most
+//! other databases.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{export::sql, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /O'Neil/\n0 @F1@ FAM\n1 HUSB @I1@\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let script = sql::export(&data);
+//! assert!(script.starts_with(sql::SCHEMA));
+//! assert!(script.contains("INSERT INTO families VALUES ('@F1@', '@I1@', NULL);"));
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::{
+    types::{
+        date::Date,
+        event::detail::Detail,
+        place::Place,
+        source::{citation::Citation, quay::CertaintyAssessment},
+        GedcomData,
+    },
+    writer::{attribute_to_tag, event_to_tag},
+};
+
+/// The `CREATE TABLE` statements of the dump, in an order that satisfies
+/// every foreign key.
+pub const SCHEMA: &str = "\
+CREATE TABLE submitters (
+  xref TEXT PRIMARY KEY,
+  name TEXT
+);
+CREATE TABLE repositories (
+  xref TEXT PRIMARY KEY,
+  name TEXT
+);
+CREATE TABLE sources (
+  xref TEXT PRIMARY KEY,
+  title TEXT,
+  author TEXT,
+  abbreviation TEXT,
+  publication TEXT
+);
+CREATE TABLE source_repositories (
+  source TEXT NOT NULL REFERENCES sources (xref),
+  repository TEXT REFERENCES repositories (xref),
+  call_number TEXT
+);
+CREATE TABLE multimedia (
+  xref TEXT PRIMARY KEY,
+  file TEXT,
+  form TEXT,
+  title TEXT
+);
+CREATE TABLE shared_notes (
+  xref TEXT PRIMARY KEY,
+  text TEXT NOT NULL
+);
+CREATE TABLE individuals (
+  xref TEXT PRIMARY KEY,
+  name TEXT,
+  given_name TEXT,
+  surname TEXT,
+  sex TEXT,
+  birth_date TEXT,
+  birth_place TEXT,
+  death_date TEXT,
+  death_place TEXT
+);
+CREATE TABLE families (
+  xref TEXT PRIMARY KEY,
+  partner1 TEXT REFERENCES individuals (xref),
+  partner2 TEXT REFERENCES individuals (xref)
+);
+CREATE TABLE family_children (
+  family TEXT NOT NULL REFERENCES families (xref),
+  child TEXT NOT NULL REFERENCES individuals (xref),
+  position INTEGER NOT NULL
+);
+CREATE TABLE events (
+  id INTEGER PRIMARY KEY,
+  individual TEXT REFERENCES individuals (xref),
+  family TEXT REFERENCES families (xref),
+  tag TEXT NOT NULL,
+  type TEXT,
+  value TEXT,
+  date TEXT,
+  place TEXT
+);
+CREATE TABLE citations (
+  id INTEGER PRIMARY KEY,
+  individual TEXT REFERENCES individuals (xref),
+  family TEXT REFERENCES families (xref),
+  event INTEGER REFERENCES events (id),
+  source TEXT REFERENCES sources (xref),
+  page TEXT,
+  quality INTEGER
+);
+";
+
+/// Renders GEDCOM data as an SQL script: [`SCHEMA`], then one transaction
+/// inserting every record.
+///
+/// Records without an xref have no primary key and are left out, along with
+/// their events and citations. A pointer to a record that does not exist is
+/// written as `NULL`, and a child that does not exist gets no
+/// `family_children` row, so the dump always satisfies its foreign keys.
+/// Events and attributes share the `events` table, numbered in file order;
+/// a citation belongs either to a record or to one of its events.
+#[must_use]
+pub fn export(data: &GedcomData) -> String {
+    let mut dump = Dump::new(data);
+    dump.out.push_str(SCHEMA);
+    dump.out.push_str("BEGIN TRANSACTION;\n");
+    dump.records(data);
+    dump.people(data);
+    dump.events(data);
+    dump.out.push_str("COMMIT;\n");
+    dump.out
+}
+
+const NULL: &str = "NULL";
+
+/// The record an event or citation row belongs to.
+#[derive(Clone, Copy)]
+enum Owner<'a> {
+    Individual(&'a str),
+    Family(&'a str),
+}
+
+impl Owner<'_> {
+    /// Returns the `individual` and `family` columns of a row.
+    fn columns(self) -> [String; 2] {
+        match self {
+            Owner::Individual(xref) => [text(Some(xref)), NULL.to_string()],
+            Owner::Family(xref) => [NULL.to_string(), text(Some(xref))],
+        }
+    }
+}
+
+/// Accumulates the statements of a dump.
+struct Dump<'a> {
+    out: String,
+    individuals: HashSet<&'a str>,
+    repositories: HashSet<&'a str>,
+    sources: HashSet<&'a str>,
+    last_event: u64,
+    last_citation: u64,
+}
+
+impl<'a> Dump<'a> {
+    fn new(data: &'a GedcomData) -> Self {
+        Self {
+            out: String::new(),
+            individuals: data
+                .individuals
+                .iter()
+                .filter_map(|i| i.xref.as_deref())
+                .collect(),
+            repositories: data
+                .repositories
+                .iter()
+                .filter_map(|r| r.xref.as_deref())
+                .collect(),
+            sources: data
+                .sources
+                .iter()
+                .filter_map(|s| s.xref.as_deref())
+                .collect(),
+            last_event: 0,
+            last_citation: 0,
+        }
+    }
+
+    /// Inserts the submitters, repositories, sources, media and notes.
+    fn records(&mut self, data: &GedcomData) {
+        for submitter in &data.submitters {
+            if let Some(xref) = submitter.xref.as_deref() {
+                self.insert(
+                    "submitters",
+                    &[text(Some(xref)), text(submitter.name.as_deref())],
+                );
+            }
+        }
+        for repository in &data.repositories {
+            if let Some(xref) = repository.xref.as_deref() {
+                self.insert(
+                    "repositories",
+                    &[text(Some(xref)), text(repository.name.as_deref())],
+                );
+            }
+        }
+        for source in &data.sources {
+            let Some(xref) = source.xref.as_deref() else {
+                continue;
+            };
+            self.insert(
+                "sources",
+                &[
+                    text(Some(xref)),
+                    text(source.title.as_deref()),
+                    text(source.author.as_deref()),
+                    text(source.abbreviation.as_deref()),
+                    text(source.publication_facts.as_deref()),
+                ],
+            );
+            for citation in &source.repo_citations {
+                let repository = pointer(&self.repositories, &citation.xref);
+                self.insert(
+                    "source_repositories",
+                    &[
+                        text(Some(xref)),
+                        repository,
+                        text(citation.call_number.as_deref()),
+                    ],
+                );
+            }
+        }
+        for multimedia in &data.multimedia {
+            if let Some(xref) = multimedia.xref.as_deref() {
+                let file = multimedia.file();
+                let form = multimedia
+                    .form
+                    .as_ref()
+                    .or_else(|| file.and_then(|f| f.form.as_ref()));
+                self.insert(
+                    "multimedia",
+                    &[
+                        text(Some(xref)),
+                        text(file.and_then(|f| f.value.as_deref())),
+                        text(form.and_then(|f| f.value.as_deref())),
+                        text(
+                            multimedia
+                                .title
+                                .as_deref()
+                                .or_else(|| file.and_then(|f| f.title.as_deref())),
+                        ),
+                    ],
+                );
+            }
+        }
+        for note in &data.shared_notes {
+            if let Some(xref) = note.xref.as_deref() {
+                self.insert("shared_notes", &[text(Some(xref)), text(Some(&note.text))]);
+            }
+        }
+    }
+
+    /// Inserts the individuals, families and children.
+    fn people(&mut self, data: &GedcomData) {
+        for individual in &data.individuals {
+            let Some(xref) = individual.xref.as_deref() else {
+                continue;
+            };
+            let birth = individual.birth();
+            let death = individual.death();
+            self.insert(
+                "individuals",
+                &[
+                    text(Some(xref)),
+                    text(individual.full_name().as_deref()),
+                    text(individual.given_name_or_parsed()),
+                    text(individual.surname_or_parsed()),
+                    text(
+                        individual
+                            .sex
+                            .as_ref()
+                            .map(|s| s.value.to_string())
+                            .as_deref(),
+                    ),
+                    date(birth.and_then(|e| e.date.as_ref())),
+                    place(birth.and_then(|e| e.place.as_ref())),
+                    date(death.and_then(|e| e.date.as_ref())),
+                    place(death.and_then(|e| e.place.as_ref())),
+                ],
+            );
+        }
+        for family in &data.families {
+            let Some(xref) = family.xref.as_deref() else {
+                continue;
+            };
+            let partner = |p: &Option<String>| {
+                p.as_ref()
+                    .map_or_else(|| NULL.to_string(), |p| pointer(&self.individuals, p))
+            };
+            let row = [
+                text(Some(xref)),
+                partner(&family.individual1),
+                partner(&family.individual2),
+            ];
+            self.insert("families", &row);
+            let children = family
+                .children
+                .iter()
+                .filter(|c| self.individuals.contains(c.as_str()))
+                .collect::<Vec<_>>();
+            for (position, child) in (1..).zip(children) {
+                self.insert(
+                    "family_children",
+                    &[text(Some(xref)), text(Some(child)), position.to_string()],
+                );
+            }
+        }
+    }
+
+    /// Inserts the events and citations of individuals and families.
+    fn events(&mut self, data: &GedcomData) {
+        for individual in &data.individuals {
+            let Some(xref) = individual.xref.as_deref() else {
+                continue;
+            };
+            let owner = Owner::Individual(xref);
+            self.citations(owner, None, &individual.source);
+            for event in &individual.events {
+                self.event(owner, event);
+            }
+            for attribute in &individual.attributes {
+                let id = self.insert_event(
+                    owner,
+                    attribute_to_tag(&attribute.attribute),
+                    [
+                        text(attribute.attribute_type.as_deref()),
+                        text(attribute.value.as_deref()),
+                        date(attribute.date.as_ref()),
+                        place(attribute.place.as_ref()),
+                    ],
+                );
+                self.citations(owner, Some(id), &attribute.sources);
+            }
+        }
+        for family in &data.families {
+            let Some(xref) = family.xref.as_deref() else {
+                continue;
+            };
+            let owner = Owner::Family(xref);
+            self.citations(owner, None, &family.sources);
+            for event in family.events.iter().chain(&family.family_event) {
+                self.event(owner, event);
+            }
+        }
+    }
+
+    fn insert(&mut self, table: &str, values: &[String]) {
+        let _ = writeln!(
+            self.out,
+            "INSERT INTO {table} VALUES ({});",
+            values.join(", ")
+        );
+    }
+
+    /// Inserts an event row from its type, value, date and place, and
+    /// returns its id.
+    fn insert_event(&mut self, owner: Owner, tag: &str, fields: [String; 4]) -> u64 {
+        self.last_event += 1;
+        let id = self.last_event;
+        let [individual, family] = owner.columns();
+        let [event_type, value, date, place] = fields;
+        self.insert(
+            "events",
+            &[
+                id.to_string(),
+                individual,
+                family,
+                text(Some(tag)),
+                event_type,
+                value,
+                date,
+                place,
+            ],
+        );
+        id
+    }
+
+    fn event(&mut self, owner: Owner, event: &Detail) {
+        let id = self.insert_event(
+            owner,
+            event_to_tag(&event.event),
+            [
+                text(event.event_type.as_deref()),
+                text(event.value.as_payload()),
+                date(event.date.as_ref()),
+                place(event.place.as_ref()),
+            ],
+        );
+        self.citations(owner, Some(id), &event.citations);
+    }
+
+    fn citations(&mut self, owner: Owner, event: Option<u64>, citations: &[Citation]) {
+        for citation in citations {
+            self.last_citation += 1;
+            let [individual, family] = owner.columns();
+            let row = [
+                self.last_citation.to_string(),
+                individual,
+                family,
+                event.map_or_else(|| NULL.to_string(), |id| id.to_string()),
+                pointer(&self.sources, &citation.xref),
+                text(citation.page.as_deref()),
+                citation
+                    .certainty_assessment
+                    .as_ref()
+                    .and_then(CertaintyAssessment::get_int)
+                    .map_or_else(|| NULL.to_string(), |q| q.to_string()),
+            ];
+            self.insert("citations", &row);
+        }
+    }
+}
+
+/// Returns a pointer as an SQL value, or `NULL` if it points nowhere.
+fn pointer(records: &HashSet<&str>, xref: &str) -> String {
+    if records.contains(xref) {
+        text(Some(xref))
+    } else {
+        NULL.to_string()
+    }
+}
+
+/// Quotes text as an SQL string literal, or returns `NULL`.
+fn text(value: Option<&str>) -> String {
+    value.map_or_else(
+        || NULL.to_string(),
+        |value| format!("'{}'", value.replace('\'', "''")),
+    )
+}
+
+fn date(date: Option<&Date>) -> String {
+    text(date.and_then(|d| d.value.as_deref()))
+}
+
+fn place(place: Option<&Place>) -> String {
+    text(place.and_then(|p| p.value.as_deref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const FAMILY: &str = "\
+        0 HEAD\n\
+        1 GEDC\n\
+        2 VERS 5.5.1\n\
+        0 @I1@ INDI\n\
+        1 NAME Patrick /O'Neil/\n\
+        1 SEX M\n\
+        1 BIRT\n\
+        2 DATE 1 JAN 1900\n\
+        2 PLAC Cork\n\
+        2 SOUR @S1@\n\
+        3 PAGE p. 12\n\
+        3 QUAY 3\n\
+        1 OCCU Farmer\n\
+        0 @I2@ INDI\n\
+        1 NAME Mary /Roe/\n\
+        0 @F1@ FAM\n\
+        1 HUSB @I1@\n\
+        1 WIFE @MISSING@\n\
+        1 CHIL @I2@\n\
+        1 CHIL @GONE@\n\
+        1 SOUR @S2@\n\
+        0 @S1@ SOUR\n\
+        1 TITL Parish register\n\
+        1 REPO @R1@\n\
+        2 CALN 42\n\
+        0 @R1@ REPO\n\
+        1 NAME Archive\n\
+        0 TRLR";
+
+    #[test]
+    fn test_export_records() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let script = export(&data);
+
+        assert!(script.starts_with(SCHEMA));
+        assert!(script.ends_with("COMMIT;\n"));
+        assert!(script.contains("INSERT INTO repositories VALUES ('@R1@', 'Archive');\n"));
+        assert!(script.contains(
+            "INSERT INTO sources VALUES ('@S1@', 'Parish register', NULL, NULL, NULL);\n"
+        ));
+        assert!(script.contains("INSERT INTO source_repositories VALUES ('@S1@', '@R1@', '42');\n"));
+        assert!(script.contains(
+            "INSERT INTO individuals VALUES ('@I1@', 'Patrick O''Neil', 'Patrick', 'O''Neil', 'Male', '1 JAN 1900', 'Cork', NULL, NULL);\n"
+        ));
+        assert!(script.contains("INSERT INTO families VALUES ('@F1@', '@I1@', NULL);\n"));
+        assert!(script.contains("INSERT INTO family_children VALUES ('@F1@', '@I2@', 1);\n"));
+        assert!(!script.contains("@GONE@"));
+    }
+
+    #[test]
+    fn test_export_events_and_citations() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let script = export(&data);
+
+        assert!(script.contains(
+            "INSERT INTO events VALUES (1, '@I1@', NULL, 'BIRT', NULL, NULL, '1 JAN 1900', 'Cork');\n"
+        ));
+        assert!(script.contains(
+            "INSERT INTO events VALUES (2, '@I1@', NULL, 'OCCU', NULL, 'Farmer', NULL, NULL);\n"
+        ));
+        assert!(script
+            .contains("INSERT INTO citations VALUES (1, '@I1@', NULL, 1, '@S1@', 'p. 12', 3);\n"));
+        assert!(script
+            .contains("INSERT INTO citations VALUES (2, NULL, '@F1@', NULL, NULL, NULL, NULL);\n"));
+    }
+}
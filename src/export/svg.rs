@@ -0,0 +1,375 @@
+//! SVG pedigree and fan charts.
+//!
+//! [`pedigree`] renders the ancestors of an individual as a standalone SVG
+//! document, either as boxes laid out by [`crate::layout`] or as a half-circle
+//! fan. The output only needs a browser or an SVG viewer to display.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{export::svg, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1 JAN 1900\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let chart = svg::pedigree(&data, "@I1@", 4, &svg::Style::default()).unwrap();
+//! assert!(chart.starts_with("<svg"));
+//! assert!(chart.contains("John Doe"));
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use super::escape;
+use crate::{
+    layout::{LayoutConfig, TreeLayout},
+    types::{individual::Individual, GedcomData},
+};
+
+/// The overall shape of a chart.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ChartShape {
+    /// A box per person, ancestors on the rows below the root.
+    #[default]
+    Boxes,
+    /// A half-circle fan, with the root at the center and one ring per generation.
+    Fan,
+}
+
+/// Sizes and colors of a chart.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct Style {
+    /// The chart shape (default: boxes).
+    pub shape: ChartShape,
+    /// Width of a person's box (default: 160).
+    pub node_width: f64,
+    /// Height of a person's box (default: 48).
+    pub node_height: f64,
+    /// Space between neighbouring boxes (default: 16).
+    pub horizontal_gap: f64,
+    /// Space between generations of boxes (default: 32).
+    pub vertical_gap: f64,
+    /// Depth of each ring of a fan chart (default: 90).
+    pub ring_width: f64,
+    /// Blank space around the chart (default: 10).
+    pub margin: f64,
+    /// Font family for labels (default: "sans-serif").
+    pub font_family: String,
+    /// Font size for names; dates use 80% of it (default: 12).
+    pub font_size: f64,
+    /// Fill color of boxes and fan sectors (default: "#f4f1ea").
+    pub fill: String,
+    /// Color of outlines and connecting lines (default: "#5b4a3a").
+    pub stroke: String,
+    /// Color of labels (default: "#222222").
+    pub text_color: String,
+    /// Whether to add birth and death dates under names (default: true).
+    pub show_dates: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            shape: ChartShape::Boxes,
+            node_width: 160.0,
+            node_height: 48.0,
+            horizontal_gap: 16.0,
+            vertical_gap: 32.0,
+            ring_width: 90.0,
+            margin: 10.0,
+            font_family: "sans-serif".to_string(),
+            font_size: 12.0,
+            fill: "#f4f1ea".to_string(),
+            stroke: "#5b4a3a".to_string(),
+            text_color: "#222222".to_string(),
+            show_dates: true,
+        }
+    }
+}
+
+/// Renders the ancestors of `root` over `generations` generations as an SVG document.
+///
+/// The root counts as the first generation. Returns `None` if no individual
+/// has the given xref.
+#[must_use]
+pub fn pedigree(data: &GedcomData, root: &str, generations: u32, style: &Style) -> Option<String> {
+    let generations = generations.max(1);
+    Some(match style.shape {
+        ChartShape::Boxes => {
+            let config = LayoutConfig {
+                node_width: style.node_width,
+                node_height: style.node_height,
+                horizontal_gap: style.horizontal_gap,
+                vertical_gap: style.vertical_gap,
+                max_generations: generations,
+            };
+            boxes(data, &data.pedigree_layout(root, &config)?, style)
+        }
+        ChartShape::Fan => fan(data, data.find_individual(root)?, generations, style),
+    })
+}
+
+fn boxes(data: &GedcomData, layout: &TreeLayout, style: &Style) -> String {
+    let (w, h) = (style.node_width, style.node_height);
+
+    let mut svg = open_svg(layout.width, layout.height, style);
+    for edge in &layout.edges {
+        if let (Some(from), Some(to)) = (layout.node(&edge.from), layout.node(&edge.to)) {
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}"/>"#,
+                from.x + w / 2.0,
+                from.y + h,
+                to.x + w / 2.0,
+                to.y,
+                escape(&style.stroke)
+            );
+        }
+    }
+    for node in &layout.nodes {
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{w}" height="{h}" rx="4" fill="{}" stroke="{}"/>"#,
+            node.x,
+            node.y,
+            escape(&style.fill),
+            escape(&style.stroke)
+        );
+        if let Some(individual) = data.find_individual(&node.xref) {
+            label(
+                &mut svg,
+                individual,
+                node.x + w / 2.0,
+                node.y + h / 2.0,
+                style,
+            );
+        }
+    }
+    close_svg(svg)
+}
+
+fn fan(data: &GedcomData, root: &Individual, generations: u32, style: &Style) -> String {
+    // Sosa numbers must fit in a u32.
+    let generations = generations.min(31);
+    let ring = style.ring_width;
+    let radius = ring * f64::from(generations);
+    let (cx, cy) = (radius, radius);
+
+    let mut ancestors = Vec::new();
+    collect_ancestors(data, root, 1, 0, generations, &mut ancestors);
+
+    let mut svg = open_svg(2.0 * radius, radius + ring, style);
+    for &(individual, sosa, generation) in &ancestors {
+        let (x, y) = if generation == 0 {
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{cx}" cy="{cy}" r="{ring}" fill="{}" stroke="{}"/>"#,
+                escape(&style.fill),
+                escape(&style.stroke)
+            );
+            (cx, cy)
+        } else {
+            let slots = f64::from(1_u32 << generation);
+            let slot = f64::from(sosa - (1_u32 << generation));
+            let (start, end) = (PI + slot * PI / slots, PI + (slot + 1.0) * PI / slots);
+            let inner = ring * f64::from(generation);
+            let outer = inner + ring;
+            let point = |r: f64, a: f64| (cx + r * a.cos(), cy + r * a.sin());
+            let (p1, p2, p3, p4) = (
+                point(outer, start),
+                point(outer, end),
+                point(inner, end),
+                point(inner, start),
+            );
+            let _ = writeln!(
+                svg,
+                r#"<path d="M {:.2} {:.2} A {outer} {outer} 0 0 1 {:.2} {:.2} L {:.2} {:.2} A {inner} {inner} 0 0 0 {:.2} {:.2} Z" fill="{}" stroke="{}"/>"#,
+                p1.0,
+                p1.1,
+                p2.0,
+                p2.1,
+                p3.0,
+                p3.1,
+                p4.0,
+                p4.1,
+                escape(&style.fill),
+                escape(&style.stroke)
+            );
+            point(inner + ring / 2.0, f64::midpoint(start, end))
+        };
+        label(&mut svg, individual, x, y, style);
+    }
+    close_svg(svg)
+}
+
+/// Gathers `(individual, sosa number, generation)` for the root and its ancestors.
+fn collect_ancestors<'a>(
+    data: &'a GedcomData,
+    individual: &'a Individual,
+    sosa: u32,
+    generation: u32,
+    generations: u32,
+    out: &mut Vec<(&'a Individual, u32, u32)>,
+) {
+    out.push((individual, sosa, generation));
+    if generation + 1 >= generations {
+        return;
+    }
+    let Some(xref) = individual.xref.as_deref() else {
+        return;
+    };
+    if let Some(family) = data.get_families_as_child(xref).first() {
+        let parents = [&family.individual1, &family.individual2];
+        for (offset, parent) in (0..).zip(parents) {
+            if let Some(parent) = parent.as_deref().and_then(|x| data.find_individual(x)) {
+                collect_ancestors(
+                    data,
+                    parent,
+                    sosa * 2 + offset,
+                    generation + 1,
+                    generations,
+                    out,
+                );
+            }
+        }
+    }
+}
+
+fn open_svg(width: f64, height: f64, style: &Style) -> String {
+    let m = style.margin;
+    let (width, height) = (width + 2.0 * m, height + 2.0 * m);
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.2} {height:.2}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<g transform="translate({m} {m})" font-family="{}" font-size="{}" fill="{}" text-anchor="middle">"#,
+        escape(&style.font_family),
+        style.font_size,
+        escape(&style.text_color)
+    );
+    svg
+}
+
+fn close_svg(mut svg: String) -> String {
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// Writes a name, and optionally dates, centered on `(x, y)`.
+fn label(svg: &mut String, individual: &Individual, x: f64, y: f64, style: &Style) {
+    let name = individual
+        .full_name()
+        .or_else(|| individual.xref.clone())
+        .unwrap_or_default();
+    let dates = if style.show_dates {
+        lifespan(individual)
+    } else {
+        None
+    };
+    let name_y = if dates.is_some() {
+        y - 2.0
+    } else {
+        y + style.font_size / 3.0
+    };
+    let _ = writeln!(
+        svg,
+        r#"<text x="{x:.2}" y="{name_y:.2}" font-weight="bold">{}</text>"#,
+        escape(&name)
+    );
+    if let Some(dates) = dates {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x:.2}" y="{:.2}" font-size="{}">{}</text>"#,
+            y + style.font_size,
+            style.font_size * 0.8,
+            escape(&dates)
+        );
+    }
+}
+
+/// Formats birth and death dates as e.g. `b. 1 JAN 1900 – d. 1980`.
+pub(super) fn lifespan(individual: &Individual) -> Option<String> {
+    match (individual.birth_date(), individual.death_date()) {
+        (Some(b), Some(d)) => Some(format!("b. {b} – d. {d}")),
+        (Some(b), None) => Some(format!("b. {b}")),
+        (None, Some(d)) => Some(format!("d. {d}")),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const FAMILY: &str = "\
+        0 HEAD\n\
+        1 GEDC\n\
+        2 VERS 5.5\n\
+        0 @I1@ INDI\n\
+        1 NAME Root /Doe & Sons/\n\
+        1 BIRT\n\
+        2 DATE 1 JAN 1950\n\
+        0 @I2@ INDI\n\
+        1 NAME Father /Doe/\n\
+        1 DEAT\n\
+        2 DATE 1990\n\
+        0 @I3@ INDI\n\
+        1 NAME Mother /Roe/\n\
+        0 @I4@ INDI\n\
+        1 NAME Grandfather /Doe/\n\
+        0 @F1@ FAM\n\
+        1 HUSB @I2@\n\
+        1 WIFE @I3@\n\
+        1 CHIL @I1@\n\
+        0 @F2@ FAM\n\
+        1 HUSB @I4@\n\
+        1 CHIL @I2@\n\
+        0 TRLR";
+
+    #[test]
+    fn test_pedigree_boxes() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let svg = pedigree(&data, "@I1@", 3, &Style::default()).unwrap();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert_eq!(svg.matches("<line").count(), 3);
+        assert!(svg.contains(">Root Doe &amp; Sons</text>"));
+        assert!(svg.contains(">b. 1 JAN 1950</text>"));
+        assert!(svg.contains(">d. 1990</text>"));
+
+        let svg = pedigree(&data, "@I1@", 2, &Style::default()).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(!svg.contains("Grandfather"));
+
+        assert!(pedigree(&data, "@MISSING@", 3, &Style::default()).is_none());
+    }
+
+    #[test]
+    fn test_pedigree_fan() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let style = Style {
+            shape: ChartShape::Fan,
+            show_dates: false,
+            ..Style::default()
+        };
+        let svg = pedigree(&data, "@I1@", 3, &style).unwrap();
+
+        assert_eq!(svg.matches("<circle").count(), 1);
+        assert_eq!(svg.matches("<path").count(), 3);
+        assert!(svg.contains(">Grandfather Doe</text>"));
+        assert!(!svg.contains("b. "));
+        // The father's sector starts at the far left of the half circle.
+        assert!(svg.contains(r#"<path d="M 90.00 270.00 A 180 180"#));
+    }
+}
@@ -0,0 +1,264 @@
+//! Resolving extension tags through the header schema.
+//!
+//! A GEDCOM 7.0 file documents its extension tags in the `SCHMA` structure of
+//! the header, mapping each tag to a URI that names its meaning. The tag is
+//! only an abbreviation: two files may use different tags for the same URI,
+//! so code that understands an extension should key it by URI.
+//! [`GedcomData::resolve_extension`] looks a tag up in the schema, and an
+//! [`ExtensionRegistry`](crate::extension::ExtensionRegistry) maps the URIs
+//! it knows to typed fields of the records. Extension structures nobody
+//! handles stay in the records' `custom_data`, children included.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{extension::ExtensionRegistry, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+//!     1 SCHMA\n2 TAG _FSFTID https://example.com/familysearch/tree-id\n\
+//!     0 @I1@ INDI\n1 _FSFTID KWCB-9XN\n1 _COLOR blue\n0 TRLR";
+//! let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+//! assert_eq!(
+//!     data.resolve_extension("_FSFTID"),
+//!     Some("https://example.com/familysearch/tree-id")
+//! );
+//!
+//! let registry = ExtensionRegistry::new().external_id(
+//!     "https://example.com/familysearch/tree-id",
+//!     "https://www.familysearch.org/tree/person/details/",
+//! );
+//! assert_eq!(data.apply_extensions(&registry), 1);
+//!
+//! let individual = &data.individuals[0];
+//! assert_eq!(
+//!     individual.external_ids[0].full_url().as_deref(),
+//!     Some("https://www.familysearch.org/tree/person/details/KWCB-9XN")
+//! );
+//! assert_eq!(&*individual.custom_data[0].tag, "_COLOR");
+//! ```
+
+use std::fmt;
+
+use crate::types::{
+    custom::UserDefinedTag,
+    header::schema::{Schema, TagDefinition},
+    shared_note::ExternalId,
+    GedcomData,
+};
+
+/// Turns an extension structure into an external identifier, or returns
+/// `None` to leave it in the custom data.
+type Handler = Box<dyn Fn(&UserDefinedTag) -> Option<ExternalId> + Send + Sync>;
+
+/// The extension URIs an application understands and how to map each into
+/// the typed fields of a record.
+///
+/// Handlers are keyed by URI; the tag a file uses for a URI comes from its
+/// schema, or from the fallback definitions of the registry for files that
+/// have none, such as GEDCOM 5.5.1 exports.
+#[derive(Default)]
+pub struct ExtensionRegistry {
+    definitions: Schema,
+    handlers: Vec<(String, Handler)>,
+}
+
+impl ExtensionRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `tag` to `uri` in files whose schema does not define `tag`.
+    #[must_use]
+    pub fn define(mut self, tag: &str, uri: &str) -> Self {
+        self.definitions
+            .add_definition(TagDefinition::new(tag, uri));
+        self
+    }
+
+    /// Registers a handler for the extension structures whose tag resolves
+    /// to `uri`. The first handler registered for a URI wins.
+    #[must_use]
+    pub fn register<F>(mut self, uri: &str, handler: F) -> Self
+    where
+        F: Fn(&UserDefinedTag) -> Option<ExternalId> + Send + Sync + 'static,
+    {
+        self.handlers.push((uri.to_string(), Box::new(handler)));
+        self
+    }
+
+    /// Registers a handler that turns the payload of the extension
+    /// structures with `uri` into an external identifier issued by
+    /// `type_uri`, as the `EXID` structure of GEDCOM 7.0 records it.
+    #[must_use]
+    pub fn external_id(self, uri: &str, type_uri: &str) -> Self {
+        let type_uri = type_uri.to_string();
+        self.register(uri, move |tag| {
+            let id = tag.value.as_deref()?.trim();
+            (!id.is_empty()).then(|| ExternalId::new(id, Some(&type_uri)))
+        })
+    }
+
+    /// Returns the handler for the URI `tag` resolves to in `schema` or in
+    /// the fallback definitions.
+    fn handler(&self, schema: Option<&Schema>, tag: &str) -> Option<&Handler> {
+        let uri = schema
+            .and_then(|schema| schema.find_uri(tag))
+            .or_else(|| self.definitions.find_uri(tag))?;
+        self.handlers
+            .iter()
+            .find(|(known, _)| known == uri)
+            .map(|(_, handler)| handler)
+    }
+
+    /// Moves the extension structures the registry handles from
+    /// `custom_data` into `external_ids`, and returns how many moved.
+    #[allow(clippy::vec_box)] // The type of the `custom_data` fields.
+    fn apply(
+        &self,
+        schema: Option<&Schema>,
+        custom_data: &mut Vec<Box<UserDefinedTag>>,
+        external_ids: &mut Vec<ExternalId>,
+    ) -> usize {
+        let before = custom_data.len();
+        custom_data.retain(|tag| {
+            match self
+                .handler(schema, &tag.tag)
+                .and_then(|handler| handler(tag))
+            {
+                Some(external_id) => {
+                    external_ids.push(external_id);
+                    false
+                }
+                None => true,
+            }
+        });
+        before - custom_data.len()
+    }
+}
+
+impl fmt::Debug for ExtensionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtensionRegistry")
+            .field("definitions", &self.definitions.tag_definitions)
+            .field(
+                "handlers",
+                &self.handlers.iter().map(|(uri, _)| uri).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl GedcomData {
+    /// Returns the URI the header schema documents for the extension `tag`.
+    ///
+    /// Returns `None` if there is no schema or it does not define the tag.
+    #[must_use]
+    pub fn resolve_extension(&self, tag: &str) -> Option<&str> {
+        self.header.as_ref()?.find_extension_uri(tag)
+    }
+
+    /// Moves the extension structures `registry` has a handler for from the
+    /// custom data of the individual, family, source, repository and shared
+    /// note records into their external identifiers, and returns how many
+    /// moved.
+    ///
+    /// Only the structures directly under a record are considered. Those
+    /// whose tag resolves to no registered URI, and those a handler
+    /// declines, are left where they are.
+    pub fn apply_extensions(&mut self, registry: &ExtensionRegistry) -> usize {
+        let schema = self.header.as_ref().and_then(|h| h.schema.as_ref());
+        let mut moved = 0;
+        for individual in &mut self.individuals {
+            moved += registry.apply(
+                schema,
+                &mut individual.custom_data,
+                &mut individual.external_ids,
+            );
+        }
+        for family in &mut self.families {
+            moved += registry.apply(schema, &mut family.custom_data, &mut family.external_ids);
+        }
+        for source in &mut self.sources {
+            moved += registry.apply(schema, &mut source.custom_data, &mut source.external_ids);
+        }
+        for repository in &mut self.repositories {
+            moved += registry.apply(
+                schema,
+                &mut repository.custom_data,
+                &mut repository.external_ids,
+            );
+        }
+        for note in &mut self.shared_notes {
+            moved += registry.apply(schema, &mut note.custom_data, &mut note.external_ids);
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const TREE_ID: &str = "https://example.com/familysearch/tree-id";
+    const PERSON: &str = "https://www.familysearch.org/tree/person/details/";
+
+    #[test]
+    fn test_resolve_extension() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            1 SCHMA\n2 TAG _FSFTID https://example.com/familysearch/tree-id\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert_eq!(data.resolve_extension("_FSFTID"), Some(TREE_ID));
+        assert_eq!(data.resolve_extension("_COLOR"), None);
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert_eq!(data.resolve_extension("_FSFTID"), None);
+    }
+
+    #[test]
+    fn test_apply_extensions() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            1 SCHMA\n2 TAG _TREEID https://example.com/familysearch/tree-id\n\
+            0 @I1@ INDI\n1 _TREEID KWCB-9XN\n1 _TREEID\n1 _FSFTID L1MN-2OP\n\
+            0 @F1@ FAM\n1 _TREEID KW1F-XYZ\n1 _MEMO\n2 TEXT kept\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let registry = ExtensionRegistry::new()
+            .define("_FSFTID", "https://example.com/other")
+            .external_id(TREE_ID, PERSON);
+        assert_eq!(data.apply_extensions(&registry), 2);
+
+        let individual = &data.individuals[0];
+        assert_eq!(
+            individual.external_ids,
+            vec![ExternalId::new("KWCB-9XN", Some(PERSON))]
+        );
+        // The empty one is declined, and _FSFTID resolves to another URI.
+        let tags: Vec<&str> = individual.custom_data.iter().map(|t| &*t.tag).collect();
+        assert_eq!(tags, vec!["_TREEID", "_FSFTID"]);
+
+        let family = &data.families[0];
+        assert_eq!(family.external_ids[0].id, "KW1F-XYZ");
+        assert_eq!(&*family.custom_data[0].tag, "_MEMO");
+        assert_eq!(
+            family.custom_data[0].children[0].value.as_deref(),
+            Some("kept")
+        );
+    }
+
+    #[test]
+    fn test_apply_extensions_fallback_definitions() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 _FSFTID KWCB-9XN\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let registry = ExtensionRegistry::new().external_id(TREE_ID, PERSON);
+        assert_eq!(data.apply_extensions(&registry), 0);
+
+        let registry = registry.define("_FSFTID", TREE_ID);
+        assert_eq!(data.apply_extensions(&registry), 1);
+        assert!(data.individuals[0].custom_data.is_empty());
+        assert_eq!(data.individuals[0].external_ids[0].id, "KWCB-9XN");
+    }
+}
@@ -0,0 +1,243 @@
+//! Test corpora for tests and benchmarks, kept out of the repository.
+//!
+//! Large real-world files make useful fixtures but bloat a repository, and
+//! many cannot be redistributed. [`Fixtures`](crate::fixtures::Fixtures)
+//! loads them from a local directory instead, named by the
+//! `GED_IO_CORPUS_DIR` environment variable or given explicitly, and
+//! [`synthetic`](crate::fixtures::synthetic) generates a deterministic file of
+//! any size for when a corpus is not available. Downstream crates can use
+//! both to benchmark against the same datasets as this one.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{fixtures::Fixtures, GedcomBuilder};
+//!
+//! # fn main() -> Result<(), ged_io::GedcomError> {
+//! let fixtures = Fixtures::from_dir("tests/fixtures");
+//! let simple = fixtures.load("simple.ged")?;
+//! assert!(simple.starts_with("0 HEAD"));
+//!
+//! // A corpus that is not on disk falls back to a generated one.
+//! let large = fixtures.load_or_generate("royal92.ged", 1_000)?;
+//! let data = GedcomBuilder::new().build_from_str(&large)?;
+//! assert_eq!(data.individuals.len(), 1_000);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{encoding::decode_gedcom_bytes, GedcomError};
+
+/// The environment variable naming the corpus directory of
+/// [`Fixtures::new`].
+pub const CORPUS_DIR_ENV: &str = "GED_IO_CORPUS_DIR";
+
+/// A directory of GEDCOM corpora, which may not be configured.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fixtures {
+    dir: Option<PathBuf>,
+}
+
+impl Fixtures {
+    /// Uses the directory named by the `GED_IO_CORPUS_DIR` environment
+    /// variable, or none if it is unset or empty.
+    #[must_use]
+    pub fn new() -> Self {
+        let dir = std::env::var_os(CORPUS_DIR_ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from);
+        Self { dir }
+    }
+
+    /// Uses the given directory.
+    #[must_use]
+    pub fn from_dir<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    /// Returns the corpus directory, if one is configured.
+    #[must_use]
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Returns the path of a corpus file, if it exists.
+    #[must_use]
+    pub fn path(&self, name: &str) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(name))
+            .filter(|path| path.is_file())
+    }
+
+    /// Returns the names of the `.ged` files in the corpus directory,
+    /// sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomError::IoError`] if the directory cannot be read. No
+    /// configured directory is not an error and yields no names.
+    pub fn available(&self) -> Result<Vec<String>, GedcomError> {
+        let Some(dir) = &self.dir else {
+            return Ok(Vec::new());
+        };
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_gedcom = path
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("ged"));
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                if is_gedcom && path.is_file() {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Reads a corpus file and decodes it to text, detecting its encoding
+    /// as [`GedcomBuilder::build_from_bytes`](crate::GedcomBuilder::build_from_bytes)
+    /// does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomError::IoError`] if no directory is configured, and
+    /// [`GedcomError::InFile`] if the file cannot be read or decoded.
+    pub fn load(&self, name: &str) -> Result<String, GedcomError> {
+        let Some(dir) = &self.dir else {
+            return Err(GedcomError::IoError(format!(
+                "no corpus directory for {name}; set {CORPUS_DIR_ENV}"
+            )));
+        };
+        let path = dir.join(name);
+        std::fs::read(&path)
+            .map_err(GedcomError::from)
+            .and_then(|bytes| decode_gedcom_bytes(&bytes))
+            .map(|(text, _)| text)
+            .map_err(|e| GedcomError::InFile {
+                path: path.display().to_string(),
+                source: Box::new(e),
+            })
+    }
+
+    /// Reads a corpus file, or generates a [`synthetic`] file with the given
+    /// number of individuals if the file is not available.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomError::InFile`] if the file exists but cannot be read
+    /// or decoded.
+    pub fn load_or_generate(&self, name: &str, individuals: usize) -> Result<String, GedcomError> {
+        match self.path(name) {
+            Some(_) => self.load(name),
+            None => Ok(synthetic(individuals)),
+        }
+    }
+}
+
+/// Generates a GEDCOM 5.5.1 file with the given number of individuals.
+///
+/// The output depends only on `individuals`, so the same call yields the
+/// same dataset on every machine. Individuals have a name, sex, birth date
+/// and place, drawn from 100 surnames and 50 places. One family is made for
+/// every four individuals, pairing consecutive individuals as partners and
+/// taking a child from the second half of the file.
+#[must_use]
+pub fn synthetic(individuals: usize) -> String {
+    let mut gedcom = String::with_capacity(individuals * 200);
+
+    gedcom.push_str("0 HEAD\n");
+    gedcom.push_str("1 GEDC\n");
+    gedcom.push_str("2 VERS 5.5.1\n");
+    gedcom.push_str("1 CHAR UTF-8\n");
+
+    for i in 1..=individuals {
+        let _ = writeln!(gedcom, "0 @I{i}@ INDI");
+        let _ = writeln!(gedcom, "1 NAME Person{i} /Family{}/", i % 100);
+        gedcom.push_str(if i % 2 == 0 { "1 SEX F\n" } else { "1 SEX M\n" });
+        gedcom.push_str("1 BIRT\n");
+        let _ = writeln!(gedcom, "2 DATE {} JAN {}", (i % 28) + 1, 1900 + (i % 100));
+        let _ = writeln!(
+            gedcom,
+            "2 PLAC City{}, State{}, Country{}",
+            i % 50,
+            i % 10,
+            i % 5
+        );
+    }
+
+    for i in 1..=individuals / 4 {
+        let (husband, wife) = (i * 2 - 1, i * 2);
+        let _ = writeln!(gedcom, "0 @F{i}@ FAM");
+        let _ = writeln!(gedcom, "1 HUSB @I{husband}@");
+        let _ = writeln!(gedcom, "1 WIFE @I{wife}@");
+        let child = individuals / 2 + i;
+        if child <= individuals {
+            let _ = writeln!(gedcom, "1 CHIL @I{child}@");
+        }
+    }
+
+    gedcom.push_str("0 TRLR");
+    gedcom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    fn repo_fixtures() -> Fixtures {
+        Fixtures::from_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+    }
+
+    #[test]
+    fn test_load_from_dir() {
+        let fixtures = repo_fixtures();
+        let names = fixtures.available().unwrap();
+        assert!(names.contains(&"simple.ged".to_string()));
+        assert!(names.windows(2).all(|w| w[0] <= w[1]));
+
+        let text = fixtures.load("simple.ged").unwrap();
+        let data = GedcomBuilder::new().build_from_str(&text).unwrap();
+        assert_eq!(data.individuals.len(), 3);
+
+        let err = fixtures.load("missing.ged").unwrap_err();
+        assert!(matches!(err, GedcomError::InFile { .. }));
+    }
+
+    #[test]
+    fn test_unconfigured_fixtures() {
+        let fixtures = Fixtures::default();
+        assert_eq!(fixtures.dir(), None);
+        assert_eq!(fixtures.path("simple.ged"), None);
+        assert!(fixtures.available().unwrap().is_empty());
+        assert!(matches!(
+            fixtures.load("simple.ged"),
+            Err(GedcomError::IoError(_))
+        ));
+        assert_eq!(
+            fixtures.load_or_generate("simple.ged", 8).unwrap(),
+            synthetic(8)
+        );
+    }
+
+    #[test]
+    fn test_synthetic_is_deterministic() {
+        assert_eq!(synthetic(40), synthetic(40));
+
+        let data = GedcomBuilder::new().build_from_str(&synthetic(40)).unwrap();
+        assert_eq!(data.individuals.len(), 40);
+        assert_eq!(data.families.len(), 10);
+        assert_eq!(data.families[0].children, vec!["@I21@".to_string()]);
+        assert!(data.warnings.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! Media galleries for individuals.
+//!
+//! An individual's `OBJE` links either point to a multimedia record or embed
+//! the file reference directly. This module resolves both kinds into flat
+//! [`MediaItem`](crate::gallery::MediaItem)s carrying what a viewer needs:
+//! the file, a title, the format and the crop region, so that a UI or an HTML
+//! export can list a person's pictures without walking the record structure.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 OBJE @M1@\n0 @M1@ OBJE\n1 FILE john.jpg\n2 FORM jpg\n1 TITL Portrait\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let gallery = data.individuals[0].media(&data);
+//! assert_eq!(gallery[0].file.as_deref(), Some("john.jpg"));
+//! assert_eq!(gallery[0].title.as_deref(), Some("Portrait"));
+//! assert_eq!(gallery[0].format.as_deref(), Some("jpg"));
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{gedcom7::Crop, individual::Individual, multimedia::Multimedia, GedcomData};
+
+/// One picture or other media file linked to an individual.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MediaItem {
+    /// The multimedia record the link points to, or `None` for an embedded link.
+    pub xref: Option<String>,
+    /// The file path or URL.
+    pub file: Option<String>,
+    /// The title, taken from the link, then the file, then the record.
+    pub title: Option<String>,
+    /// The file format (e.g. `jpg` or `image/jpeg`).
+    pub format: Option<String>,
+    /// The kind of source the file shows (e.g. `PHOTO`), from `FORM.TYPE`/`MEDI`.
+    pub media_type: Option<String>,
+    /// The region of the image to show, from the link or else the file.
+    pub crop: Option<Crop>,
+}
+
+/// The media of one individual.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PersonGallery {
+    /// The individual's xref.
+    pub xref: String,
+    /// The individual's name, if any.
+    pub name: Option<String>,
+    /// The individual's media, in link order.
+    pub items: Vec<MediaItem>,
+}
+
+impl Individual {
+    /// Resolves the individual's multimedia links into gallery items.
+    ///
+    /// Items keep the order of the links. Links to multimedia records that
+    /// do not exist in `data` are skipped.
+    #[must_use]
+    pub fn media(&self, data: &GedcomData) -> Vec<MediaItem> {
+        self.multimedia
+            .iter()
+            .filter_map(|link| media_item(link, data))
+            .collect()
+    }
+}
+
+impl GedcomData {
+    /// Builds the gallery of every individual that has media.
+    ///
+    /// Galleries are returned in record order; individuals without an xref
+    /// or without any resolvable media are left out.
+    #[must_use]
+    pub fn media_by_person(&self) -> Vec<PersonGallery> {
+        self.individuals
+            .iter()
+            .filter_map(|individual| {
+                let xref = individual.xref.clone()?;
+                let items = individual.media(self);
+                (!items.is_empty()).then(|| PersonGallery {
+                    xref,
+                    name: individual.full_name(),
+                    items,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Resolves one link, combining it with the record it points to.
+fn media_item(link: &Multimedia, data: &GedcomData) -> Option<MediaItem> {
+    let record = match link.xref {
+        Some(ref xref) => data.find_multimedia(xref)?,
+        None => link,
+    };
+    let file = record.file();
+    let form = file.and_then(|f| f.form.as_ref()).or(record.form.as_ref());
+
+    Some(MediaItem {
+        xref: link.xref.clone(),
+        file: file.and_then(|f| f.value.clone()),
+        title: link
+            .title
+            .clone()
+            .or_else(|| file.and_then(|f| f.title.clone()))
+            .or_else(|| record.title.clone()),
+        format: form.and_then(|f| f.value.clone()),
+        media_type: form.and_then(|f| f.source_media_type.clone()),
+        crop: link
+            .crop
+            .clone()
+            .or_else(|| file.and_then(|f| f.crop.clone())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_media_resolves_pointer_and_embedded_links() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 NAME John /Doe/\n\
+            1 OBJE @M1@\n\
+            2 CROP\n\
+            3 TOP 10\n\
+            3 HEIGHT 50\n\
+            2 TITL Face\n\
+            1 OBJE\n\
+            2 FILE scans/census.png\n\
+            3 FORM image/png\n\
+            4 MEDI PHOTO\n\
+            3 TITL Census\n\
+            1 OBJE @MISSING@\n\
+            0 @I2@ INDI\n\
+            1 NAME Jane /Doe/\n\
+            0 @M1@ OBJE\n\
+            1 FILE family.jpg\n\
+            2 FORM image/jpeg\n\
+            2 TITL Family photo\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let items = data.individuals[0].media(&data);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].xref.as_deref(), Some("@M1@"));
+        assert_eq!(items[0].file.as_deref(), Some("family.jpg"));
+        assert_eq!(items[0].title.as_deref(), Some("Face"));
+        assert_eq!(items[0].format.as_deref(), Some("image/jpeg"));
+        let crop = items[0].crop.as_ref().unwrap();
+        assert_eq!((crop.top, crop.height), (Some(10.0), Some(50.0)));
+
+        assert_eq!(items[1].xref, None);
+        assert_eq!(items[1].file.as_deref(), Some("scans/census.png"));
+        assert_eq!(items[1].title.as_deref(), Some("Census"));
+        assert_eq!(items[1].media_type.as_deref(), Some("PHOTO"));
+        assert_eq!(items[1].crop, None);
+
+        let galleries = data.media_by_person();
+        assert_eq!(galleries.len(), 1);
+        assert_eq!(galleries[0].xref, "@I1@");
+        assert_eq!(galleries[0].name.as_deref(), Some("John Doe"));
+    }
+}
@@ -47,13 +47,22 @@
 
 use std::collections::HashMap;
 use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use zip::read::ZipArchive;
 use zip::write::ZipWriter;
 use zip::CompressionMethod;
 
 use crate::encoding::decode_gedcom_bytes;
-use crate::types::GedcomData;
+use crate::types::{
+    event::detail::Detail,
+    multimedia::{for_each_multimedia, Multimedia},
+    source::citation::Citation,
+    GedcomData,
+};
+use crate::version::GedcomVersion;
 use crate::writer::GedcomWriter;
 use crate::GedcomError;
 
@@ -71,8 +80,12 @@ pub enum GedzipError {
     GedcomError(GedcomError),
     /// An I/O error occurred.
     IoError(std::io::Error),
-    /// A media file referenced in the GEDCOM was not found in the archive.
+    /// A media file referenced in the GEDCOM was not found in the archive,
+    /// or on disk when packing it.
     MissingMediaFile(String),
+    /// A file in the archive has a name that would be written outside the
+    /// directory it is extracted to, such as `../evil.sh` or `/etc/passwd`.
+    UnsafePath(String),
 }
 
 impl std::fmt::Display for GedzipError {
@@ -85,6 +98,7 @@ impl std::fmt::Display for GedzipError {
             Self::MissingMediaFile(name) => {
                 write!(f, "Media file not found in archive: {name}")
             }
+            Self::UnsafePath(name) => write!(f, "Unsafe path in archive: {name}"),
         }
     }
 }
@@ -95,7 +109,7 @@ impl std::error::Error for GedzipError {
             Self::ZipError(e) => Some(e),
             Self::GedcomError(e) => Some(e),
             Self::IoError(e) => Some(e),
-            Self::MissingGedcomFile | Self::MissingMediaFile(_) => None,
+            Self::MissingGedcomFile | Self::MissingMediaFile(_) | Self::UnsafePath(_) => None,
         }
     }
 }
@@ -118,6 +132,38 @@ impl From<std::io::Error> for GedzipError {
     }
 }
 
+/// The result of checking a GEDZIP archive with [`GedzipReader::validate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GedzipReport {
+    /// The GEDCOM version `gedcom.ged` declares, if any.
+    pub version: Option<String>,
+    /// The `FILE` paths of the data that no file of the archive has, in the
+    /// order they first appear. URLs are not included.
+    pub missing_files: Vec<String>,
+    /// The files of the archive, besides `gedcom.ged`, that no `FILE`
+    /// structure refers to, in archive order.
+    pub orphan_files: Vec<String>,
+}
+
+impl GedzipReport {
+    /// Returns true if `gedcom.ged` declares GEDCOM 7.0, which GEDZIP
+    /// requires.
+    #[must_use]
+    pub fn is_gedcom_7(&self) -> bool {
+        self.version
+            .as_deref()
+            .is_some_and(|version| GedcomVersion::from_version_str(version).is_v7())
+    }
+
+    /// Returns true if the archive is GEDCOM 7.0 and every referenced file
+    /// is in it. Orphan files are allowed by the format and do not count.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.is_gedcom_7() && self.missing_files.is_empty()
+    }
+}
+
 /// A reader for GEDZIP files.
 ///
 /// `GedzipReader` wraps a ZIP archive and provides methods to:
@@ -242,6 +288,105 @@ impl<R: Read + Seek> GedzipReader<R> {
         Ok(bytes)
     }
 
+    /// Writes every media file of the archive under `dir`, keeping the
+    /// folders of their names, and returns the paths written.
+    ///
+    /// Names that could land outside `dir`, because they are absolute, climb
+    /// out with `..`, or hold a `\` or a `:`, which Windows reads as a
+    /// separator or a drive, are rejected before anything is written.
+    /// Existing files are overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to extract into; it is created if missing
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedzipError::UnsafePath`] for a name that would be written
+    /// outside `dir`, or an error if a file cannot be read or written.
+    pub fn extract_media_to(&mut self, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, GedzipError> {
+        let dir = dir.as_ref();
+        let mut targets = Vec::new();
+        for name in self.media_files() {
+            // Folder entries are created along with the files inside them.
+            if name.ends_with('/') {
+                continue;
+            }
+            let path =
+                sanitize_path(name).ok_or_else(|| GedzipError::UnsafePath(name.to_string()))?;
+            targets.push((name.to_string(), dir.join(path)));
+        }
+
+        let mut written = Vec::with_capacity(targets.len());
+        for (name, path) in targets {
+            let bytes = self.read_media_file(&name)?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, bytes)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Checks the archive against the data in `gedcom.ged`: that it is
+    /// GEDCOM 7.0, that every file its `FILE` structures refer to is in the
+    /// archive, and which files of the archive nothing refers to.
+    ///
+    /// `FILE` structures are found as in
+    /// [`GedzipWriter::add_media_from_fs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `gedcom.ged` cannot be read or parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "gedzip")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::collections::HashMap;
+    /// use ged_io::gedzip::{write_gedzip_with_media, GedzipReader};
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @M1@ OBJE\n1 FILE john.jpg\n2 FORM image/jpeg\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source)?;
+    /// let media = HashMap::from([("jane.jpg".to_string(), vec![0xFF, 0xD8])]);
+    /// let bytes = write_gedzip_with_media(&data, &media)?;
+    ///
+    /// let report = GedzipReader::new(std::io::Cursor::new(bytes))?.validate()?;
+    /// assert!(!report.is_valid());
+    /// assert_eq!(report.missing_files, ["john.jpg"]);
+    /// assert_eq!(report.orphan_files, ["jane.jpg"]);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "gedzip"))]
+    /// # fn main() {}
+    /// ```
+    pub fn validate(&mut self) -> Result<GedzipReport, GedzipError> {
+        let data = self.parse_gedcom()?;
+        let referenced = referenced_files(&data);
+
+        let mut missing_files: Vec<String> = Vec::new();
+        for name in &referenced {
+            if !self.contains_file(name) && !missing_files.iter().any(|known| known == name) {
+                missing_files.push((*name).to_string());
+            }
+        }
+        let orphan_files = self
+            .media_files()
+            .into_iter()
+            .filter(|name| !name.ends_with('/') && !referenced.contains(name))
+            .map(String::from)
+            .collect();
+
+        Ok(GedzipReport {
+            version: data.gedcom_version().map(String::from),
+            missing_files,
+            orphan_files,
+        })
+    }
+
     /// Checks if a file exists in the archive.
     #[must_use]
     pub fn contains_file(&self, name: &str) -> bool {
@@ -261,6 +406,28 @@ impl<R: Read + Seek> GedzipReader<R> {
     }
 }
 
+/// Turns the name of a file in an archive into a relative path, or returns
+/// `None` if it would leave the directory it is extracted to.
+///
+/// ZIP names separate folders with `/`. A `\` is a separator on Windows
+/// and a `:` names a drive or an alternate data stream there, so segments
+/// holding either are rejected wherever they are.
+fn sanitize_path(name: &str) -> Option<PathBuf> {
+    if name.starts_with('/') {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for part in name.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => return None,
+            part if part.contains([':', '\\']) => return None,
+            part => path.push(part),
+        }
+    }
+    (path.components().next().is_some()).then_some(path)
+}
+
 /// A writer for GEDZIP files.
 ///
 /// `GedzipWriter` creates a ZIP archive containing a GEDCOM dataset and
@@ -398,6 +565,143 @@ impl<W: Write + Seek> GedzipWriter<W> {
         Ok(())
     }
 
+    /// Adds the media files the `FILE` structures of `data` refer to,
+    /// reading each from its path relative to `base_dir`, and returns their
+    /// names.
+    ///
+    /// The files of multimedia records and of the multimedia links embedded
+    /// in individuals, families, sources, their events and their citations
+    /// are packed, each once, under the name the `FILE` structure gives, so
+    /// the data keeps pointing at them. URLs, such as `https://…`, are left
+    /// out. Every file is checked before any is added.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The GEDCOM data whose media files to add
+    /// * `base_dir` - The directory the `FILE` paths are relative to
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedzipError::MissingMediaFile`] for a file that is not on
+    /// disk, [`GedzipError::UnsafePath`] for a path that is absolute, climbs
+    /// out of `base_dir` or holds a `\` or a `:`, or an error if a file
+    /// cannot be read or written.
+    pub fn add_media_from_fs(
+        &mut self,
+        data: &GedcomData,
+        base_dir: impl AsRef<Path>,
+    ) -> Result<Vec<String>, GedzipError> {
+        let base_dir = base_dir.as_ref();
+        let mut files: Vec<(&str, PathBuf)> = Vec::new();
+        for name in referenced_files(data) {
+            if files.iter().any(|(known, _)| *known == name) {
+                continue;
+            }
+            let path =
+                sanitize_path(name).ok_or_else(|| GedzipError::UnsafePath(name.to_string()))?;
+            let path = base_dir.join(path);
+            if !path.is_file() {
+                return Err(GedzipError::MissingMediaFile(name.to_string()));
+            }
+            files.push((name, path));
+        }
+
+        let mut added = Vec::with_capacity(files.len());
+        for (name, path) in files {
+            self.add_media_file(name, &std::fs::read(path)?)?;
+            added.push(name.to_string());
+        }
+        Ok(added)
+    }
+
+    /// Downloads the remote media files the `FILE` structures of `data`
+    /// refer to with `fetch`, adds them under `media/`, points the `FILE`
+    /// structures at the packed copies, and returns their names.
+    ///
+    /// `fetch` is given each distinct `http://` or `https://` URL once and
+    /// returns its content; the crate has no HTTP client of its own, so any
+    /// can be used. A file is named after the last segment of its URL path,
+    /// prefixed with a number when that name is already taken. The files
+    /// covered are those [`add_media_from_fs`](Self::add_media_from_fs)
+    /// packs. Write `data` with [`write_gedcom`](Self::write_gedcom) after
+    /// this call, so the archive refers to its own copies.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The GEDCOM data whose remote media files to add
+    /// * `fetch` - Downloads the content of a URL
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "gedzip")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ged_io::gedzip::GedzipWriter;
+    /// use ged_io::GedcomBuilder;
+    /// use std::fs::File;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @M1@ OBJE\n1 FILE https://example.com/john.jpg\n0 TRLR";
+    /// let mut data = GedcomBuilder::new().build_from_str(source)?;
+    ///
+    /// let mut writer = GedzipWriter::new(File::create("family.gdz")?)?;
+    /// writer.add_remote_media(&mut data, |url| {
+    ///     // Download `url` with the HTTP client of your choice.
+    ///     Ok(Vec::new())
+    /// })?;
+    /// writer.write_gedcom(&data)?;
+    /// writer.finish()?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "gedzip"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error `fetch` returns, or an error if a file
+    /// cannot be written. `data` is only changed once every file is added.
+    pub fn add_remote_media<F>(
+        &mut self,
+        data: &mut GedcomData,
+        mut fetch: F,
+    ) -> Result<Vec<String>, GedzipError>
+    where
+        F: FnMut(&str) -> std::io::Result<Vec<u8>>,
+    {
+        let mut taken: Vec<String> = referenced_files(data)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let mut renamed: Vec<(String, String)> = Vec::new();
+        for_each_multimedia(data, &mut |media| {
+            for file in media.files.iter().filter(|file| file.is_remote()) {
+                let url = file.value.as_deref().unwrap_or_default();
+                if !renamed.iter().any(|(known, _)| known == url) {
+                    let name = remote_name(url, &taken);
+                    taken.push(name.clone());
+                    renamed.push((url.to_string(), name));
+                }
+            }
+        });
+
+        for (url, name) in &renamed {
+            let bytes = fetch(url)?;
+            self.add_media_file(name, &bytes)?;
+        }
+        for_each_multimedia(data, &mut |media| {
+            for value in media
+                .files
+                .iter_mut()
+                .filter_map(|file| file.value.as_mut())
+            {
+                if let Some((_, name)) = renamed.iter().find(|(url, _)| url == value) {
+                    value.clone_from(name);
+                }
+            }
+        });
+        Ok(renamed.into_iter().map(|(_, name)| name).collect())
+    }
+
     /// Finalizes the archive and returns the underlying writer.
     ///
     /// This must be called to ensure the ZIP archive is properly closed.
@@ -416,6 +720,112 @@ impl<W: Write + Seek> GedzipWriter<W> {
     }
 }
 
+/// Returns the `FILE` values of the multimedia records and embedded
+/// multimedia links of `data` that are not URLs, in file order.
+fn referenced_files(data: &GedcomData) -> Vec<&str> {
+    fn media<'a>(multimedia: &'a [Multimedia], files: &mut Vec<&'a str>) {
+        for file in multimedia.iter().flat_map(|media| &media.files) {
+            match file.value.as_deref() {
+                Some(value) if !value.contains("://") => files.push(value),
+                _ => {}
+            }
+        }
+    }
+    fn citations<'a>(citations: &'a [Citation], files: &mut Vec<&'a str>) {
+        for citation in citations {
+            media(&citation.multimedia, files);
+        }
+    }
+    fn events<'a>(events: &'a [Detail], files: &mut Vec<&'a str>) {
+        for event in events {
+            media(&event.multimedia, files);
+            citations(&event.citations, files);
+        }
+    }
+
+    let mut files = Vec::new();
+    media(&data.multimedia, &mut files);
+    for individual in &data.individuals {
+        media(&individual.multimedia, &mut files);
+        events(&individual.events, &mut files);
+        citations(&individual.source, &mut files);
+    }
+    for family in &data.families {
+        media(&family.multimedia, &mut files);
+        events(&family.events, &mut files);
+        citations(&family.sources, &mut files);
+    }
+    for source in &data.sources {
+        media(&source.multimedia, &mut files);
+    }
+    files
+}
+
+/// Returns the archive name for the remote file at `url`: the last segment
+/// of its path under `media/`, numbered when the name is in `taken`.
+fn remote_name(url: &str, taken: &[String]) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let segment = path
+        .split_once("://")
+        .map_or(path, |(_, rest)| {
+            rest.split_once('/').map_or("", |(_, p)| p)
+        })
+        .rsplit('/')
+        .next()
+        .filter(|segment| sanitize_path(segment).is_some())
+        .unwrap_or("file");
+    let mut name = format!("media/{segment}");
+    let mut number = 1;
+    while taken.contains(&name) {
+        number += 1;
+        name = format!("media/{number}-{segment}");
+    }
+    name
+}
+
+/// Writes GEDCOM data to a GEDZIP file along with the media files it refers
+/// to, read from their paths relative to `base_dir`, and returns the bytes.
+///
+/// See [`GedzipWriter::add_media_from_fs`] for which files are packed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "gedzip")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use ged_io::gedzip::write_package;
+/// use ged_io::GedcomBuilder;
+///
+/// let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @M1@ OBJE\n1 FILE photos/john.jpg\n2 FORM image/jpeg\n0 TRLR";
+/// let data = GedcomBuilder::new().build_from_str(source)?;
+///
+/// // Packs family/photos/john.jpg as photos/john.jpg.
+/// let bytes = write_package(&data, "family")?;
+/// std::fs::write("family.gdz", bytes)?;
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "gedzip"))]
+/// # fn main() {}
+/// ```
+///
+/// # Errors
+///
+/// Returns a `GedzipError` if:
+/// - The GEDCOM data cannot be serialized
+/// - A media file is missing, outside `base_dir`, or cannot be read
+/// - The ZIP archive cannot be created
+pub fn write_package(
+    data: &GedcomData,
+    base_dir: impl AsRef<Path>,
+) -> Result<Vec<u8>, GedzipError> {
+    let cursor = std::io::Cursor::new(Vec::new());
+    let mut writer = GedzipWriter::new(cursor)?;
+    writer.write_gedcom(data)?;
+    writer.add_media_from_fs(data, base_dir)?;
+    let cursor = writer.finish()?;
+    Ok(cursor.into_inner())
+}
+
 /// Reads a GEDZIP file from bytes and returns the parsed GEDCOM data.
 ///
 /// This is a convenience function for simple use cases.
@@ -663,4 +1073,171 @@ mod tests {
         let result = reader.read_media_file("nonexistent.jpg");
         assert!(matches!(result, Err(GedzipError::MissingMediaFile(_))));
     }
+
+    #[test]
+    fn test_extract_media_to() {
+        let data = create_minimal_gedcom();
+        let mut media = HashMap::new();
+        media.insert("test.txt".to_string(), b"Hello, World!".to_vec());
+        media.insert("photos/2020/image.jpg".to_string(), vec![0xFF, 0xD8]);
+        let bytes = write_gedzip_with_media(&data, &media).unwrap();
+        let mut reader = GedzipReader::new(std::io::Cursor::new(bytes)).unwrap();
+
+        let dir =
+            std::env::temp_dir().join(format!("ged_io_gedzip_extract_{}", std::process::id()));
+        let mut written = reader.extract_media_to(&dir).unwrap();
+        written.sort();
+        assert_eq!(
+            written,
+            vec![dir.join("photos/2020/image.jpg"), dir.join("test.txt")]
+        );
+        assert_eq!(
+            std::fs::read(dir.join("test.txt")).unwrap(),
+            b"Hello, World!"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 OBJE\n2 FILE photos/john.jpg\n1 OBJE @M1@\n\
+            0 @M1@ OBJE\n1 FILE photos/john.jpg\n1 FILE https://example.com/a.jpg\n0 TRLR";
+        let data = crate::GedcomBuilder::new().build_from_str(source).unwrap();
+        let mut media = HashMap::new();
+        media.insert("photos/john.jpg".to_string(), vec![0xFF]);
+        let bytes = write_gedzip_with_media(&data, &media).unwrap();
+        let report = GedzipReader::new(std::io::Cursor::new(bytes))
+            .unwrap()
+            .validate()
+            .unwrap();
+        assert_eq!(report.version.as_deref(), Some("7.0"));
+        assert!(report.is_valid());
+        assert!(report.orphan_files.is_empty());
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @M1@ OBJE\n1 FILE a.jpg\n0 TRLR\n";
+        let mut writer = GedzipWriter::new(std::io::Cursor::new(Vec::new())).unwrap();
+        writer.write_gedcom_bytes(source.as_bytes()).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        let report = GedzipReader::new(std::io::Cursor::new(bytes))
+            .unwrap()
+            .validate()
+            .unwrap();
+        assert!(!report.is_gedcom_7());
+        assert_eq!(report.missing_files, ["a.jpg"]);
+    }
+
+    #[test]
+    fn test_write_package() {
+        let base = std::env::temp_dir().join("ged_io_gedzip_package");
+        std::fs::create_dir_all(base.join("photos")).unwrap();
+        std::fs::write(base.join("photos/john.jpg"), [0xFF, 0xD8]).unwrap();
+        std::fs::write(base.join("birth.pdf"), b"%PDF").unwrap();
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 BIRT\n2 OBJE\n3 FILE birth.pdf\n1 OBJE @M1@\n\
+            0 @M1@ OBJE\n1 FILE photos/john.jpg\n2 FORM image/jpeg\n\
+            0 @M2@ OBJE\n1 FILE https://example.com/jane.jpg\n2 FORM image/jpeg\n\
+            0 @M3@ OBJE\n1 FILE photos/john.jpg\n2 FORM image/jpeg\n0 TRLR";
+        let data = crate::GedcomBuilder::new().build_from_str(source).unwrap();
+        let bytes = write_package(&data, &base).unwrap();
+
+        let mut reader = GedzipReader::new(std::io::Cursor::new(bytes)).unwrap();
+        let mut media = reader.media_files();
+        media.sort_unstable();
+        assert_eq!(media, ["birth.pdf", "photos/john.jpg"]);
+        assert_eq!(reader.read_media_file("birth.pdf").unwrap(), b"%PDF");
+
+        std::fs::remove_file(base.join("birth.pdf")).unwrap();
+        assert!(matches!(
+            write_package(&data, &base),
+            Err(GedzipError::MissingMediaFile(name)) if name == "birth.pdf"
+        ));
+        std::fs::remove_dir_all(&base).unwrap();
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @M1@ OBJE\n1 FILE ../secret.txt\n0 TRLR";
+        let data = crate::GedcomBuilder::new().build_from_str(source).unwrap();
+        assert!(matches!(
+            write_package(&data, &base),
+            Err(GedzipError::UnsafePath(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_remote_media() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 OBJE\n2 FILE https://example.com/a/john.jpg\n\
+            0 @M1@ OBJE\n1 FILE https://example.com/b/john.jpg?size=large\n\
+            0 @M2@ OBJE\n1 FILE https://example.com/a/john.jpg\n\
+            0 @M3@ OBJE\n1 FILE media/scan.tif\n\
+            0 @M4@ OBJE\n1 FILE https://example.com/\n0 TRLR";
+        let mut data = crate::GedcomBuilder::new().build_from_str(source).unwrap();
+        let mut writer = GedzipWriter::new(std::io::Cursor::new(Vec::new())).unwrap();
+        let mut fetched = Vec::new();
+        let added = writer
+            .add_remote_media(&mut data, |url| {
+                fetched.push(url.to_string());
+                Ok(url.as_bytes().to_vec())
+            })
+            .unwrap();
+        assert_eq!(added, ["media/john.jpg", "media/2-john.jpg", "media/file"]);
+        assert_eq!(fetched.len(), 3);
+
+        let file = |media: &Multimedia| media.files[0].value.clone().unwrap();
+        assert_eq!(file(&data.multimedia[0]), "media/john.jpg");
+        assert_eq!(file(&data.multimedia[1]), "media/2-john.jpg");
+        assert_eq!(file(&data.individuals[0].multimedia[0]), "media/2-john.jpg");
+        assert_eq!(file(&data.multimedia[2]), "media/scan.tif");
+        assert!(!data.multimedia.iter().any(Multimedia::is_remote));
+
+        writer.write_gedcom(&data).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        let mut reader = GedzipReader::new(std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(
+            reader.read_media_file("media/john.jpg").unwrap(),
+            b"https://example.com/b/john.jpg?size=large"
+        );
+
+        let mut data = crate::GedcomBuilder::new().build_from_str(source).unwrap();
+        let mut writer = GedzipWriter::new(std::io::Cursor::new(Vec::new())).unwrap();
+        let failed = writer.add_remote_media(&mut data, |_| {
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "404"))
+        });
+        assert!(matches!(failed, Err(GedzipError::IoError(_))));
+        assert!(data.multimedia[0].is_remote());
+    }
+
+    #[test]
+    fn test_extract_media_to_rejects_unsafe_paths() {
+        let dir = std::env::temp_dir().join(format!("ged_io_gedzip_unsafe_{}", std::process::id()));
+        for name in [
+            "../evil.sh",
+            "photos/../../evil.sh",
+            "..\\evil.sh",
+            "/etc/passwd",
+            "C:/evil",
+            "photos/C:/evil",
+            "photos/a:b.jpg",
+            "photos\\evil.sh",
+        ] {
+            let mut media = HashMap::new();
+            media.insert("safe.txt".to_string(), b"safe".to_vec());
+            media.insert(name.to_string(), b"evil".to_vec());
+            let bytes = write_gedzip_with_media(&create_minimal_gedcom(), &media).unwrap();
+            let mut reader = GedzipReader::new(std::io::Cursor::new(bytes)).unwrap();
+
+            let result = reader.extract_media_to(&dir);
+            assert!(
+                matches!(&result, Err(GedzipError::UnsafePath(path)) if path == name),
+                "{name}"
+            );
+            // Nothing is written when any name is unsafe.
+            let written = dir.exists();
+            let _ = std::fs::remove_dir_all(&dir);
+            assert!(!written, "{name}");
+        }
+
+        assert_eq!(sanitize_path("./a//b.jpg"), Some(PathBuf::from("a/b.jpg")));
+        assert_eq!(sanitize_path("./"), None);
+    }
 }
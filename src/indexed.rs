@@ -18,11 +18,20 @@
 //! assert!(indexed.find_family("@F1@").is_some());
 //! ```
 
-use std::collections::HashMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::types::{
-    family::Family, individual::Individual, multimedia::Multimedia, repository::Repository,
-    source::Source, submitter::Submitter, GedcomData,
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+use crate::{
+    search::{SearchIndex, SearchQuery},
+    types::{
+        family::Family, individual::Individual, multimedia::Multimedia, repository::Repository,
+        source::Source, submitter::Submitter, GedcomData,
+    },
 };
 
 /// A wrapper around `GedcomData` that provides O(1) lookups by cross-reference ID.
@@ -45,6 +54,24 @@ pub struct IndexedGedcomData {
     multimedia_index: HashMap<Box<str>, usize>,
     /// Index mapping submitter xrefs to their position in the submitters vector
     submitter_index: HashMap<Box<str>, usize>,
+    /// Lazily computed per-person figures, cleared by [`update`](Self::update)
+    figures: Mutex<FigureCache>,
+    /// Index for [`search`](Self::search), built on first use and cleared by
+    /// [`update`](Self::update)
+    search_index: OnceLock<SearchIndex>,
+}
+
+/// Parent and child links, and the figures computed from them.
+#[derive(Debug, Default)]
+struct FigureCache {
+    /// Whether the links below have been loaded
+    loaded: bool,
+    /// Parents of each individual, from the first family listing them as a child
+    parents: HashMap<Box<str>, Vec<Box<str>>>,
+    /// Children of each individual, from every family they are a partner in
+    children: HashMap<Box<str>, Vec<Box<str>>>,
+    descendant_counts: HashMap<Box<str>, usize>,
+    completeness: HashMap<(Box<str>, u32), f64>,
 }
 
 impl IndexedGedcomData {
@@ -60,6 +87,8 @@ impl IndexedGedcomData {
             repository_index: HashMap::with_capacity(data.repositories.len()),
             multimedia_index: HashMap::with_capacity(data.multimedia.len()),
             submitter_index: HashMap::with_capacity(data.submitters.len()),
+            figures: Mutex::default(),
+            search_index: OnceLock::new(),
             data,
         };
         indexed.build_indexes();
@@ -124,6 +153,91 @@ impl IndexedGedcomData {
         self.data
     }
 
+    /// Modifies the underlying `GedcomData`, then rebuilds the indexes.
+    ///
+    /// Cached figures such as [`descendant_count`](Self::descendant_count),
+    /// and the index of [`search`](Self::search), are discarded, so they are
+    /// recomputed on next use.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomBuilder, indexed::IndexedGedcomData};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n0 @I2@ INDI\n0 @F1@ FAM\n1 HUSB @I1@\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+    /// let mut indexed = IndexedGedcomData::from(data);
+    /// assert_eq!(indexed.descendant_count("@I1@"), Some(0));
+    ///
+    /// indexed.update(|data| data.families[0].children.push("@I2@".to_string()));
+    /// assert_eq!(indexed.descendant_count("@I1@"), Some(1));
+    /// ```
+    pub fn update<R>(&mut self, modify: impl FnOnce(&mut GedcomData) -> R) -> R {
+        let result = modify(&mut self.data);
+        for index in [
+            &mut self.individual_index,
+            &mut self.family_index,
+            &mut self.source_index,
+            &mut self.repository_index,
+            &mut self.multimedia_index,
+            &mut self.submitter_index,
+        ] {
+            index.clear();
+        }
+        self.build_indexes();
+        *self
+            .figures
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = FigureCache::default();
+        self.search_index = OnceLock::new();
+        result
+    }
+
+    /// Compacts the underlying `GedcomData` and rebuilds the indexes to fit
+    /// the records they hold, returning the number of bytes released. See
+    /// [`GedcomData::compact`].
+    ///
+    /// Cached figures are discarded as by [`update`](Self::update); the
+    /// memory they held is not counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomBuilder, indexed::IndexedGedcomData};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n0 @I2@ INDI\n0 @I3@ INDI\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+    /// let mut indexed = IndexedGedcomData::from(data);
+    ///
+    /// indexed.update(|data| data.individuals.truncate(1));
+    /// assert!(indexed.compact() > 0);
+    /// assert!(indexed.find_individual("@I1@").is_some());
+    /// ```
+    pub fn compact(&mut self) -> usize {
+        let entry = std::mem::size_of::<(Box<str>, usize)>();
+        let capacity = |indexed: &Self| {
+            indexed.individual_index.capacity()
+                + indexed.family_index.capacity()
+                + indexed.source_index.capacity()
+                + indexed.repository_index.capacity()
+                + indexed.multimedia_index.capacity()
+                + indexed.submitter_index.capacity()
+        };
+        let before = capacity(self);
+        let reclaimed = self.update(GedcomData::compact);
+        for index in [
+            &mut self.individual_index,
+            &mut self.family_index,
+            &mut self.source_index,
+            &mut self.repository_index,
+            &mut self.multimedia_index,
+            &mut self.submitter_index,
+        ] {
+            index.shrink_to_fit();
+        }
+        reclaimed + before.saturating_sub(capacity(self)) * entry
+    }
+
     /// Finds an individual by cross-reference ID in O(1) time.
     ///
     /// # Example
@@ -268,6 +382,32 @@ impl IndexedGedcomData {
         self.data.search_individuals_by_name(query)
     }
 
+    /// Returns the individuals matching `query`, in file order, as
+    /// [`GedcomData::search`] does.
+    ///
+    /// The index is built on the first search and kept until the next
+    /// [`update`](Self::update).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomBuilder, indexed::IndexedGedcomData, search::SearchQuery};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+    /// let mut indexed = IndexedGedcomData::from(data);
+    /// assert_eq!(indexed.search(&SearchQuery::default().surname("Roe")).len(), 0);
+    ///
+    /// indexed.update(|data| data.individuals[0].names[0].value = Some("John /Roe/".into()));
+    /// assert_eq!(indexed.search(&SearchQuery::default().surname("Roe")).len(), 1);
+    /// ```
+    #[must_use]
+    pub fn search(&self, query: &SearchQuery) -> Vec<&Individual> {
+        self.search_index
+            .get_or_init(|| SearchIndex::new(&self.data))
+            .search(&self.data, query)
+    }
+
     /// Returns the total count of all records.
     #[must_use]
     pub fn total_records(&self) -> usize {
@@ -292,6 +432,111 @@ impl IndexedGedcomData {
         self.data.families.len()
     }
 
+    /// Counts the distinct descendants of an individual.
+    ///
+    /// Someone reached through several lines of descent is counted once.
+    /// The result is cached until the next [`update`](Self::update).
+    /// Returns `None` if no individual has the xref.
+    #[must_use]
+    pub fn descendant_count(&self, xref: &str) -> Option<usize> {
+        self.find_individual(xref)?;
+        let mut cache = self.figures();
+        if let Some(&count) = cache.descendant_counts.get(xref) {
+            return Some(count);
+        }
+
+        let mut seen: HashSet<&str> = HashSet::from([xref]);
+        let mut pending = vec![xref];
+        while let Some(person) = pending.pop() {
+            for child in cache.children.get(person).into_iter().flatten() {
+                if seen.insert(child) {
+                    pending.push(child);
+                }
+            }
+        }
+        let count = seen.len() - 1;
+        cache.descendant_counts.insert(xref.into(), count);
+        Some(count)
+    }
+
+    /// Returns the fraction of ancestor slots filled over a number of generations.
+    ///
+    /// Generation 1 has the two parent slots, generation 2 the four
+    /// grandparent slots, and so on; a slot is filled when the ancestor has
+    /// an individual record. An ancestor in several slots, as with cousin
+    /// marriages, fills each of them. Zero generations are complete. The
+    /// result is cached until the next [`update`](Self::update). Returns
+    /// `None` if no individual has the xref.
+    #[must_use]
+    pub fn ancestor_completeness(&self, xref: &str, generations: u32) -> Option<f64> {
+        self.find_individual(xref)?;
+        let mut cache = self.figures();
+        let key = (Box::from(xref), generations);
+        if let Some(&fraction) = cache.completeness.get(&key) {
+            return Some(fraction);
+        }
+
+        let (mut filled, mut slots) = (0.0, 0.0);
+        let mut generation = vec![xref];
+        for depth in 1..=generations {
+            generation = generation
+                .iter()
+                .flat_map(|person| cache.parents.get(*person).into_iter().flatten())
+                .map(|parent| &**parent)
+                .collect();
+            filled += f64::from(u32::try_from(generation.len()).unwrap_or(u32::MAX));
+            slots += 2f64.powi(i32::try_from(depth).unwrap_or(i32::MAX));
+            if generation.is_empty() {
+                // Every further generation adds empty slots only.
+                slots += (depth + 1..=generations)
+                    .map(|d| 2f64.powi(i32::try_from(d).unwrap_or(i32::MAX)))
+                    .sum::<f64>();
+                break;
+            }
+        }
+        let fraction = if generations == 0 {
+            1.0
+        } else {
+            filled / slots
+        };
+        cache.completeness.insert(key, fraction);
+        Some(fraction)
+    }
+
+    /// Locks the figure cache, loading the parent and child links on first use.
+    fn figures(&self) -> std::sync::MutexGuard<'_, FigureCache> {
+        let mut cache = self.figures.lock().unwrap_or_else(PoisonError::into_inner);
+        if !cache.loaded {
+            let mut parents: HashMap<Box<str>, Vec<Box<str>>> = HashMap::new();
+            let mut children: HashMap<Box<str>, Vec<Box<str>>> = HashMap::new();
+            for family in &self.data.families {
+                let partners: Vec<&String> = [&family.individual1, &family.individual2]
+                    .into_iter()
+                    .flatten()
+                    .filter(|x| self.individual_index.contains_key(x.as_str()))
+                    .collect();
+                for child in &family.children {
+                    if !self.individual_index.contains_key(child.as_str()) {
+                        continue;
+                    }
+                    parents
+                        .entry(child.as_str().into())
+                        .or_insert_with(|| partners.iter().map(|p| p.as_str().into()).collect());
+                    for partner in &partners {
+                        children
+                            .entry(partner.as_str().into())
+                            .or_default()
+                            .push(child.as_str().into());
+                    }
+                }
+            }
+            cache.loaded = true;
+            cache.parents = parents;
+            cache.children = children;
+        }
+        cache
+    }
+
     /// Returns statistics about the indexes.
     #[must_use]
     pub fn index_stats(&self) -> IndexStats {
@@ -314,6 +559,7 @@ impl From<GedcomData> for IndexedGedcomData {
 
 /// Statistics about the indexes in `IndexedGedcomData`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IndexStats {
     /// Number of entries in the individual index
     pub individual_index_size: usize,
@@ -469,6 +715,53 @@ mod tests {
         assert_eq!(stats.total(), 6);
     }
 
+    #[test]
+    fn test_descendant_count_and_ancestor_completeness() {
+        // I4 and I5 are both children of I3 and are the parents of I6, so I6
+        // has I3 in both grandparent slots, and I1 and I2 twice among the
+        // great-grandparents.
+        let source = "0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5\n\
+            0 @I1@ INDI\n\
+            0 @I2@ INDI\n\
+            0 @I3@ INDI\n\
+            0 @I4@ INDI\n\
+            0 @I5@ INDI\n\
+            0 @I6@ INDI\n\
+            0 @F1@ FAM\n\
+            1 HUSB @I1@\n\
+            1 WIFE @I2@\n\
+            1 CHIL @I3@\n\
+            0 @F2@ FAM\n\
+            1 WIFE @I3@\n\
+            1 CHIL @I4@\n\
+            1 CHIL @I5@\n\
+            0 @F3@ FAM\n\
+            1 HUSB @I5@\n\
+            1 WIFE @I4@\n\
+            1 CHIL @I6@\n\
+            0 TRLR";
+        let mut indexed =
+            IndexedGedcomData::from(GedcomBuilder::new().build_from_str(source).unwrap());
+
+        assert_eq!(indexed.descendant_count("@I1@"), Some(4));
+        assert_eq!(indexed.descendant_count("@I5@"), Some(1));
+        assert_eq!(indexed.descendant_count("@I6@"), Some(0));
+        assert_eq!(indexed.descendant_count("@I9@"), None);
+
+        // 2 of 2 parents, 2 of 4 grandparents (both I3), 4 of 8 great-grandparents.
+        assert_eq!(indexed.ancestor_completeness("@I6@", 1), Some(1.0));
+        assert_eq!(indexed.ancestor_completeness("@I6@", 2), Some(4.0 / 6.0));
+        assert_eq!(indexed.ancestor_completeness("@I6@", 3), Some(8.0 / 14.0));
+        assert_eq!(indexed.ancestor_completeness("@I1@", 3), Some(0.0));
+        assert_eq!(indexed.ancestor_completeness("@I1@", 0), Some(1.0));
+
+        indexed.update(|data| data.families[0].children.clear());
+        assert_eq!(indexed.descendant_count("@I1@"), Some(0));
+        assert_eq!(indexed.ancestor_completeness("@I6@", 3), Some(4.0 / 14.0));
+    }
+
     #[test]
     fn test_into_inner() {
         let data = create_test_data();
@@ -0,0 +1,386 @@
+//! String interning for repeated values.
+//!
+//! Large files repeat the same few thousand place names, surnames, temple
+//! codes and repository names across hundreds of thousands of records.
+//! [`Interner`](crate::intern::Interner) stores each distinct string once and
+//! hands out small [`Symbol`](crate::intern::Symbol) handles, and
+//! [`GedcomData::intern_values`] builds an index of those values over a
+//! parsed file. The index can outlive the `GedcomData` it was built from,
+//! which keeps one copy of each value instead of one per occurrence.
+//!
+//! To share the values while the records are built instead, parse with
+//! [`GedcomBuilder::intern_strings`](crate::GedcomBuilder::intern_strings):
+//! equal place names, surnames and custom tags then point to one string,
+//! stored in a [`util::StringInterner`](crate::util::StringInterner), the
+//! thread-safe form of the same interner.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::intern::ValueKind;
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 PLAC Boston\n0 @I2@ INDI\n1 NAME Jane /Doe/\n1 BIRT\n2 PLAC Boston\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let values = data.intern_values();
+//! assert_eq!(values.distinct(ValueKind::Place), vec!["Boston"]);
+//! assert_eq!(values.count(ValueKind::Surname, "Doe"), 2);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::{collections::HashMap, mem::size_of, sync::Arc};
+
+use crate::types::{lds::LdsOrdinance, place::Place, GedcomData};
+
+/// A handle to a string stored in an [`Interner`].
+///
+/// Symbols are only meaningful for the interner that created them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the position of the string in interning order.
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A set of distinct strings, each stored once.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "Vec<String>", into = "Vec<String>")
+)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the symbol for a string, storing it if it is new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` distinct strings are interned.
+    pub fn intern(&mut self, value: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(value) {
+            return symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.strings.len()).expect("too many interned strings"));
+        let shared: Arc<str> = Arc::from(value);
+        self.strings.push(Arc::clone(&shared));
+        self.symbols.insert(shared, symbol);
+        symbol
+    }
+
+    /// Returns the symbol for a string that has already been interned.
+    #[must_use]
+    pub fn get(&self, value: &str) -> Option<Symbol> {
+        self.symbols.get(value).copied()
+    }
+
+    /// Returns the string behind a symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symbol comes from another interner.
+    #[must_use]
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.index()]
+    }
+
+    /// Returns a shared handle to the string behind a symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symbol comes from another interner.
+    #[must_use]
+    pub fn shared(&self, symbol: Symbol) -> Arc<str> {
+        Arc::clone(&self.strings[symbol.index()])
+    }
+
+    /// Returns the number of distinct strings.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if no string has been interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Iterates over the strings in interning order.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        (0..).map(Symbol).zip(self.strings.iter().map(|s| &**s))
+    }
+}
+
+/// Interns strings in order, so that a string's symbol is its position
+/// unless it repeats an earlier one.
+impl From<Vec<String>> for Interner {
+    fn from(strings: Vec<String>) -> Self {
+        let mut interner = Self::new();
+        for string in &strings {
+            interner.intern(string);
+        }
+        interner
+    }
+}
+
+/// Returns the strings in interning order.
+impl From<Interner> for Vec<String> {
+    fn from(interner: Interner) -> Self {
+        interner.strings.iter().map(ToString::to_string).collect()
+    }
+}
+
+/// The kind of a repeated value indexed by [`GedcomData::intern_values`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ValueKind {
+    /// A place name (`PLAC`) of an event or attribute.
+    Place,
+    /// An individual's surname.
+    Surname,
+    /// An LDS temple code (`TEMP`).
+    Temple,
+    /// A repository name.
+    Repository,
+}
+
+/// One occurrence of an interned value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ValueUse {
+    /// What the value is.
+    pub kind: ValueKind,
+    /// The xref of the record it occurs in.
+    pub record: Option<Symbol>,
+    /// The value itself.
+    pub value: Symbol,
+}
+
+/// Repeated values of a file, each distinct string stored once.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InternedValues {
+    /// The distinct values and record xrefs.
+    pub strings: Interner,
+    /// Every occurrence, in record order.
+    pub uses: Vec<ValueUse>,
+}
+
+impl InternedValues {
+    /// Returns the distinct values of a kind, in order of first occurrence.
+    #[must_use]
+    pub fn distinct(&self, kind: ValueKind) -> Vec<&str> {
+        let mut seen = std::collections::HashSet::new();
+        self.uses
+            .iter()
+            .filter(|u| u.kind == kind && seen.insert(u.value))
+            .map(|u| self.strings.resolve(u.value))
+            .collect()
+    }
+
+    /// Counts the occurrences of a value.
+    #[must_use]
+    pub fn count(&self, kind: ValueKind, value: &str) -> usize {
+        self.strings.get(value).map_or(0, |symbol| {
+            self.uses
+                .iter()
+                .filter(|u| u.kind == kind && u.value == symbol)
+                .count()
+        })
+    }
+
+    /// Drops the strings that no occurrence in [`uses`](Self::uses) refers
+    /// to, such as those of occurrences removed since the index was built,
+    /// and returns the number of bytes released.
+    ///
+    /// The remaining strings are renumbered, so symbols taken from this
+    /// index before compacting are no longer valid.
+    pub fn compact(&mut self) -> usize {
+        let size = |values: &Self| {
+            values.strings.iter().map(|(_, s)| s.len()).sum::<usize>()
+                + values.strings.strings.capacity() * size_of::<Arc<str>>()
+                + values.strings.symbols.capacity() * size_of::<(Arc<str>, Symbol)>()
+                + values.uses.capacity() * size_of::<ValueUse>()
+        };
+        let before = size(self);
+
+        let old = std::mem::take(&mut self.strings);
+        let mut renumbered = HashMap::new();
+        let mut move_symbol = |symbol: Symbol, strings: &mut Interner| {
+            *renumbered
+                .entry(symbol)
+                .or_insert_with(|| strings.intern(old.resolve(symbol)))
+        };
+        for value_use in &mut self.uses {
+            value_use.record = value_use
+                .record
+                .map(|record| move_symbol(record, &mut self.strings));
+            value_use.value = move_symbol(value_use.value, &mut self.strings);
+        }
+        self.strings.strings.shrink_to_fit();
+        self.strings.symbols.shrink_to_fit();
+        self.uses.shrink_to_fit();
+
+        before.saturating_sub(size(self))
+    }
+
+    fn add(&mut self, kind: ValueKind, record: Option<Symbol>, value: Option<&str>) {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            let value = self.strings.intern(value);
+            self.uses.push(ValueUse {
+                kind,
+                record,
+                value,
+            });
+        }
+    }
+
+    fn add_record<'a>(
+        &mut self,
+        xref: Option<&str>,
+        places: impl Iterator<Item = &'a Place>,
+        ordinances: &[LdsOrdinance],
+    ) -> Option<Symbol> {
+        let record = xref.map(|x| self.strings.intern(x));
+        for place in places {
+            self.add(ValueKind::Place, record, place.value.as_deref());
+        }
+        for ordinance in ordinances {
+            self.add(ValueKind::Temple, record, ordinance.temple.as_deref());
+        }
+        record
+    }
+}
+
+impl GedcomData {
+    /// Interns the place names, surnames, temple codes and repository names
+    /// of the file.
+    #[must_use]
+    pub fn intern_values(&self) -> InternedValues {
+        let mut values = InternedValues::default();
+
+        for individual in &self.individuals {
+            let places = individual
+                .events
+                .iter()
+                .filter_map(|e| e.place.as_ref())
+                .chain(
+                    individual
+                        .attributes
+                        .iter()
+                        .filter_map(|a| a.place.as_ref()),
+                );
+            let record = values.add_record(
+                individual.xref.as_deref(),
+                places,
+                &individual.lds_ordinances,
+            );
+            values.add(ValueKind::Surname, record, individual.surname_or_parsed());
+        }
+        for family in &self.families {
+            let places = family
+                .events
+                .iter()
+                .chain(&family.family_event)
+                .filter_map(|e| e.place.as_ref());
+            values.add_record(family.xref.as_deref(), places, &family.lds_ordinances);
+        }
+        for repository in &self.repositories {
+            let record = repository.xref.as_deref().map(|x| values.strings.intern(x));
+            values.add(ValueKind::Repository, record, repository.name.as_deref());
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_interner_deduplicates() {
+        let mut interner = Interner::new();
+        let a = interner.intern("Boston");
+        let b = interner.intern("Salem");
+        assert_eq!(interner.intern("Boston"), a);
+        assert_ne!(a, b);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.resolve(b), "Salem");
+        assert_eq!(interner.get("Salem"), Some(b));
+        assert_eq!(interner.get("Lynn"), None);
+        assert!(Arc::ptr_eq(&interner.shared(a), &interner.shared(a)));
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            vec![(a, "Boston"), (b, "Salem")]
+        );
+    }
+
+    #[test]
+    fn test_intern_values() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 NAME John /Doe/\n\
+            1 BIRT\n\
+            2 PLAC Boston\n\
+            1 BAPL\n\
+            2 TEMP SLAKE\n\
+            0 @I2@ INDI\n\
+            1 NAME Jane /Doe/\n\
+            1 RESI\n\
+            2 PLAC Salem\n\
+            0 @F1@ FAM\n\
+            1 MARR\n\
+            2 PLAC Boston\n\
+            0 @R1@ REPO\n\
+            1 NAME Town Archive\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let mut values = data.intern_values();
+
+        assert_eq!(values.distinct(ValueKind::Place), vec!["Boston", "Salem"]);
+        assert_eq!(values.count(ValueKind::Place, "Boston"), 2);
+        assert_eq!(values.count(ValueKind::Surname, "Doe"), 2);
+        assert_eq!(values.distinct(ValueKind::Temple), vec!["SLAKE"]);
+        assert_eq!(values.distinct(ValueKind::Repository), vec!["Town Archive"]);
+
+        let birth = values
+            .uses
+            .iter()
+            .find(|u| u.kind == ValueKind::Place)
+            .unwrap();
+        assert_eq!(values.strings.resolve(birth.record.unwrap()), "@I1@");
+        // Each distinct string is stored once: 4 xrefs and 5 values.
+        assert_eq!(values.strings.len(), 9);
+
+        values.uses.retain(|u| u.kind == ValueKind::Place);
+        assert!(values.compact() > 0);
+        assert_eq!(values.distinct(ValueKind::Place), vec!["Boston", "Salem"]);
+        assert_eq!(values.count(ValueKind::Place, "Boston"), 2);
+        assert_eq!(values.strings.get("SLAKE"), None);
+        let birth = values.uses[0];
+        assert_eq!(values.strings.resolve(birth.record.unwrap()), "@I1@");
+    }
+}
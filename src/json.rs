@@ -0,0 +1,262 @@
+//! Building a [`GedcomData`] from JSON, checked the way a parsed file would be.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{error::Category, Value};
+
+use crate::{
+    types::{
+        event::detail::Detail,
+        family::Family,
+        header::Header,
+        individual::Individual,
+        multimedia::Multimedia,
+        repository::Repository,
+        shared_note::SharedNote,
+        source::{citation::Citation, Source},
+        submission::Submission,
+        submitter::Submitter,
+        GedcomData,
+    },
+    GedcomError,
+};
+
+impl GedcomData {
+    /// Deserializes JSON produced by serializing a `GedcomData`, then checks
+    /// that the result is a tree the parser could have produced.
+    ///
+    /// The checks are that the header is present, every record has an xref,
+    /// no xref is used twice, and every pointer leads to a record of the
+    /// right type: partners and children to individuals, `FAMC` and `FAMS`
+    /// links to families, citations to sources, repository citations to
+    /// repositories and the header's `SUBM` to a submitter. Citations of
+    /// inline sources, whose "xref" is text rather than a pointer, are not
+    /// checked.
+    ///
+    /// Requires the `json` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomError::ParseError`] with the line of the problem if
+    /// the text is not valid JSON, and [`GedcomError::InvalidStructure`]
+    /// with a path such as `$.families[0].children[1]` if a value has the
+    /// wrong shape or a check fails. Only the first problem is reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{types::GedcomData, GedcomBuilder, GedcomError};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n0 @F1@ FAM\n1 CHIL @I1@\n0 TRLR";
+    /// let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+    ///
+    /// let json = serde_json::to_string(&data).unwrap();
+    /// assert_eq!(GedcomData::from_json_validated(&json).unwrap(), data);
+    ///
+    /// data.individuals.clear();
+    /// let json = serde_json::to_string(&data).unwrap();
+    /// let Err(GedcomError::InvalidStructure { path, .. }) = GedcomData::from_json_validated(&json) else {
+    ///     panic!("expected a dangling child");
+    /// };
+    /// assert_eq!(path, "$.families[0].children[0]");
+    /// ```
+    pub fn from_json_validated(json: &str) -> Result<GedcomData, GedcomError> {
+        let value: Value = serde_json::from_str(json).map_err(|e| syntax_error(&e))?;
+        let data = GedcomData::deserialize_located(&value)?;
+        data.check_structure()?;
+        Ok(data)
+    }
+
+    /// Deserializes a JSON value, locating the record behind a shape error.
+    fn deserialize_located(value: &Value) -> Result<GedcomData, GedcomError> {
+        let error = match GedcomData::deserialize(value) {
+            Ok(data) => return Ok(data),
+            Err(error) => error,
+        };
+        let located = locate::<Option<Header>>(value, "header")
+            .or_else(|| locate_each::<Submitter>(value, "submitters"))
+            .or_else(|| locate_each::<Submission>(value, "submissions"))
+            .or_else(|| locate_each::<Individual>(value, "individuals"))
+            .or_else(|| locate_each::<Family>(value, "families"))
+            .or_else(|| locate_each::<Repository>(value, "repositories"))
+            .or_else(|| locate_each::<Source>(value, "sources"))
+            .or_else(|| locate_each::<Multimedia>(value, "multimedia"))
+            .or_else(|| locate_each::<SharedNote>(value, "shared_notes"));
+        Err(located.unwrap_or_else(|| structure_error("$", &error)))
+    }
+
+    /// Runs the reference and cardinality checks of
+    /// [`from_json_validated`](Self::from_json_validated).
+    fn check_structure(&self) -> Result<(), GedcomError> {
+        if self.header.is_none() {
+            return Err(invalid("$.header", "missing header"));
+        }
+
+        let mut records = Records::default();
+        records.add("individuals", self.individuals.iter().map(|r| &r.xref))?;
+        records.add("families", self.families.iter().map(|r| &r.xref))?;
+        records.add("sources", self.sources.iter().map(|r| &r.xref))?;
+        records.add("repositories", self.repositories.iter().map(|r| &r.xref))?;
+        records.add("submitters", self.submitters.iter().map(|r| &r.xref))?;
+        records.add("submissions", self.submissions.iter().map(|r| &r.xref))?;
+        records.add("multimedia", self.multimedia.iter().map(|r| &r.xref))?;
+        records.add("shared_notes", self.shared_notes.iter().map(|r| &r.xref))?;
+
+        if let Some(submitter) = self.header.as_ref().and_then(|h| h.submitter_tag.as_ref()) {
+            records.expect("submitters", "$.header.submitter_tag", submitter)?;
+        }
+        for (i, individual) in self.individuals.iter().enumerate() {
+            let path = format!("$.individuals[{i}]");
+            for (j, link) in individual.families.iter().enumerate() {
+                records.expect(
+                    "families",
+                    &format!("{path}.families[{j}].xref"),
+                    &link.xref,
+                )?;
+            }
+            records.citations(&format!("{path}.source"), &individual.source)?;
+            records.events(&format!("{path}.events"), &individual.events)?;
+            for (j, attribute) in individual.attributes.iter().enumerate() {
+                records.citations(
+                    &format!("{path}.attributes[{j}].sources"),
+                    &attribute.sources,
+                )?;
+            }
+        }
+        for (i, family) in self.families.iter().enumerate() {
+            let path = format!("$.families[{i}]");
+            for (field, partner) in [
+                ("individual1", &family.individual1),
+                ("individual2", &family.individual2),
+            ] {
+                if let Some(partner) = partner {
+                    records.expect("individuals", &format!("{path}.{field}"), partner)?;
+                }
+            }
+            for (j, child) in family.children.iter().enumerate() {
+                records.expect("individuals", &format!("{path}.children[{j}]"), child)?;
+            }
+            records.citations(&format!("{path}.sources"), &family.sources)?;
+            records.events(&format!("{path}.events"), &family.events)?;
+            records.events(&format!("{path}.family_event"), &family.family_event)?;
+        }
+        for (i, source) in self.sources.iter().enumerate() {
+            for (j, citation) in source.repo_citations.iter().enumerate() {
+                records.expect(
+                    "repositories",
+                    &format!("$.sources[{i}].repo_citations[{j}].xref"),
+                    &citation.xref,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The xrefs of each record collection, for the checks of
+/// [`GedcomData::from_json_validated`].
+#[derive(Default)]
+struct Records<'a> {
+    by_collection: HashMap<&'static str, HashSet<&'a str>>,
+    paths: HashMap<&'a str, String>,
+}
+
+impl<'a> Records<'a> {
+    fn add(
+        &mut self,
+        collection: &'static str,
+        xrefs: impl Iterator<Item = &'a Option<String>>,
+    ) -> Result<(), GedcomError> {
+        let set = self.by_collection.entry(collection).or_default();
+        for (i, xref) in xrefs.enumerate() {
+            let path = format!("$.{collection}[{i}].xref");
+            let Some(xref) = xref.as_deref() else {
+                return Err(invalid(&path, "missing xref"));
+            };
+            if let Some(first) = self.paths.get(xref) {
+                return Err(invalid(
+                    &path,
+                    &format!("{xref} is already used at {first}"),
+                ));
+            }
+            self.paths.insert(xref, path);
+            set.insert(xref);
+        }
+        Ok(())
+    }
+
+    /// Checks that a pointer leads to a record of a collection.
+    fn expect(&self, collection: &str, path: &str, xref: &str) -> Result<(), GedcomError> {
+        if self
+            .by_collection
+            .get(collection)
+            .is_some_and(|set| set.contains(xref))
+        {
+            return Ok(());
+        }
+        Err(invalid(path, &format!("{xref} is not in {collection}")))
+    }
+
+    fn citations(&self, path: &str, citations: &[Citation]) -> Result<(), GedcomError> {
+        for (i, citation) in citations.iter().enumerate() {
+            if citation.xref.starts_with('@') {
+                self.expect("sources", &format!("{path}[{i}].xref"), &citation.xref)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn events(&self, path: &str, events: &[Detail]) -> Result<(), GedcomError> {
+        for (i, event) in events.iter().enumerate() {
+            self.citations(&format!("{path}[{i}].citations"), &event.citations)?;
+            if let Some(link) = &event.family_link {
+                self.expect(
+                    "families",
+                    &format!("{path}[{i}].family_link.xref"),
+                    &link.xref,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the error of the first item of a collection that does not
+/// deserialize.
+fn locate_each<T: DeserializeOwned>(value: &Value, key: &str) -> Option<GedcomError> {
+    let items = value.get(key)?.as_array()?;
+    items.iter().enumerate().find_map(|(i, item)| {
+        T::deserialize(item)
+            .err()
+            .map(|e| structure_error(&format!("$.{key}[{i}]"), &e))
+    })
+}
+
+/// Returns the error of a field that does not deserialize.
+fn locate<T: DeserializeOwned>(value: &Value, key: &str) -> Option<GedcomError> {
+    T::deserialize(value.get(key)?)
+        .err()
+        .map(|e| structure_error(&format!("$.{key}"), &e))
+}
+
+fn syntax_error(error: &serde_json::Error) -> GedcomError {
+    match error.classify() {
+        Category::Syntax | Category::Eof => GedcomError::ParseError {
+            line: u32::try_from(error.line()).unwrap_or(u32::MAX),
+            message: error.to_string(),
+        },
+        Category::Data | Category::Io => structure_error("$", error),
+    }
+}
+
+fn structure_error(path: &str, error: &serde_json::Error) -> GedcomError {
+    invalid(path, &error.to_string())
+}
+
+fn invalid(path: &str, message: &str) -> GedcomError {
+    GedcomError::InvalidStructure {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
@@ -0,0 +1,325 @@
+//! Chart layout for pedigree and descendant trees.
+//!
+//! This module computes box coordinates for a tree chart rooted at one
+//! individual, so applications can draw charts without their own layout
+//! engine. Each generation is placed on its own row. Leaves get consecutive
+//! columns and every other person is centered over the people it leads to,
+//! so wide branches get the space they need without overlapping their
+//! neighbours.
+//!
+//! Coordinates are in SVG user units, with the origin at the top left and `y`
+//! growing downwards. With the `json` feature enabled, a
+//! [`TreeLayout`](crate::layout::TreeLayout) can be serialized as is.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{layout::LayoutConfig, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 FAMC @F1@\n0 @I2@ INDI\n1 NAME Jim /Doe/\n0 @I3@ INDI\n1 NAME Jane /Roe/\n0 @F1@ FAM\n1 HUSB @I2@\n1 WIFE @I3@\n1 CHIL @I1@\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let layout = data.pedigree_layout("@I1@", &LayoutConfig::default()).unwrap();
+//! assert_eq!(layout.nodes.len(), 3);
+//! assert_eq!(layout.edges.len(), 2);
+//! println!("<svg viewBox=\"{}\">", layout.view_box());
+//! ```
+
+use std::collections::HashSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{individual::Individual, GedcomData};
+
+/// The direction a chart grows from its root.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ChartKind {
+    /// Ancestors: the root's parents, their parents, and so on.
+    Pedigree,
+    /// Descendants: the root's children from every family, their children, and so on.
+    Descendants,
+}
+
+/// Box sizes and spacing used to compute a layout.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayoutConfig {
+    /// Width of a person's box (default: 160).
+    pub node_width: f64,
+    /// Height of a person's box (default: 60).
+    pub node_height: f64,
+    /// Space between neighbouring boxes in a row (default: 20).
+    pub horizontal_gap: f64,
+    /// Space between generations (default: 40).
+    pub vertical_gap: f64,
+    /// Number of generations to lay out, the root included (default: 10).
+    pub max_generations: u32,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            node_width: 160.0,
+            node_height: 60.0,
+            horizontal_gap: 20.0,
+            vertical_gap: 40.0,
+            max_generations: 10,
+        }
+    }
+}
+
+/// A person's box in a chart.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayoutNode {
+    /// The individual's xref.
+    pub xref: String,
+    /// The individual's name, or their xref if unnamed.
+    pub label: String,
+    /// Distance from the root, which is generation 0.
+    pub generation: u32,
+    /// Left edge of the box.
+    pub x: f64,
+    /// Top edge of the box.
+    pub y: f64,
+}
+
+/// A line between two boxes, from the person nearer the root to the next one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LayoutEdge {
+    /// Xref of the person nearer the root.
+    pub from: String,
+    /// Xref of the parent (pedigree) or child (descendants).
+    pub to: String,
+}
+
+/// The computed layout of a chart.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TreeLayout {
+    /// Which way the chart grows.
+    pub kind: ChartKind,
+    /// Boxes ordered by generation, then from left to right.
+    pub nodes: Vec<LayoutNode>,
+    /// Lines between boxes.
+    pub edges: Vec<LayoutEdge>,
+    /// Width and height of each box.
+    pub node_size: (f64, f64),
+    /// Total width of the chart.
+    pub width: f64,
+    /// Total height of the chart.
+    pub height: f64,
+}
+
+impl TreeLayout {
+    /// Returns the node for an xref, if it is part of the chart.
+    #[must_use]
+    pub fn node(&self, xref: &str) -> Option<&LayoutNode> {
+        self.nodes.iter().find(|n| n.xref == xref)
+    }
+
+    /// Returns an SVG `viewBox` attribute value covering the whole chart.
+    #[must_use]
+    pub fn view_box(&self) -> String {
+        format!("0 0 {} {}", self.width, self.height)
+    }
+}
+
+impl GedcomData {
+    /// Lays out the ancestors of an individual, with the root on the top row.
+    ///
+    /// Father's lines are placed left of mother's. Returns `None` if no
+    /// individual has the given xref.
+    #[must_use]
+    pub fn pedigree_layout(&self, xref: &str, config: &LayoutConfig) -> Option<TreeLayout> {
+        LayoutBuilder::new(self, ChartKind::Pedigree, config).run(xref)
+    }
+
+    /// Lays out the descendants of an individual, with the root on the top row.
+    ///
+    /// Children keep the order of their family records, and families the order
+    /// they appear in the file. Returns `None` if no individual has the given xref.
+    #[must_use]
+    pub fn descendant_layout(&self, xref: &str, config: &LayoutConfig) -> Option<TreeLayout> {
+        LayoutBuilder::new(self, ChartKind::Descendants, config).run(xref)
+    }
+}
+
+struct LayoutBuilder<'a> {
+    data: &'a GedcomData,
+    kind: ChartKind,
+    config: &'a LayoutConfig,
+    /// People already placed. Someone reachable twice (e.g. through cousin
+    /// marriages) is shown once, which also guards against cyclic data.
+    placed: HashSet<&'a str>,
+    /// Column given to the next leaf.
+    next_column: f64,
+    nodes: Vec<LayoutNode>,
+    edges: Vec<LayoutEdge>,
+}
+
+impl<'a> LayoutBuilder<'a> {
+    fn new(data: &'a GedcomData, kind: ChartKind, config: &'a LayoutConfig) -> Self {
+        Self {
+            data,
+            kind,
+            config,
+            placed: HashSet::new(),
+            next_column: 0.0,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    fn run(mut self, xref: &str) -> Option<TreeLayout> {
+        let root = self.data.find_individual(xref)?;
+        self.place(root, 0)?;
+
+        let max_generation = self.nodes.iter().map(|n| n.generation).max().unwrap_or(0);
+        let (w, h) = (self.config.node_width, self.config.node_height);
+        self.nodes
+            .sort_by(|a, b| a.generation.cmp(&b.generation).then(a.x.total_cmp(&b.x)));
+
+        Some(TreeLayout {
+            kind: self.kind,
+            nodes: self.nodes,
+            edges: self.edges,
+            node_size: (w, h),
+            width: self.next_column * (w + self.config.horizontal_gap) - self.config.horizontal_gap,
+            height: f64::from(max_generation + 1) * (h + self.config.vertical_gap)
+                - self.config.vertical_gap,
+        })
+    }
+
+    /// Places an individual and everyone after them, returning the column they got.
+    fn place(&mut self, individual: &'a Individual, generation: u32) -> Option<f64> {
+        let xref = individual.xref.as_deref()?;
+        if !self.placed.insert(xref) {
+            return None;
+        }
+
+        let mut columns = Vec::new();
+        if generation + 1 < self.config.max_generations {
+            for next in self.next_generation(xref) {
+                if let Some(column) = self.place(next, generation + 1) {
+                    columns.push(column);
+                    self.edges.push(LayoutEdge {
+                        from: xref.to_string(),
+                        to: next.xref.clone().unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        let column = if let (Some(first), Some(last)) = (columns.first(), columns.last()) {
+            (first + last) / 2.0
+        } else {
+            self.next_column += 1.0;
+            self.next_column - 1.0
+        };
+
+        self.nodes.push(LayoutNode {
+            xref: xref.to_string(),
+            label: individual.full_name().unwrap_or_else(|| xref.to_string()),
+            generation,
+            x: column * (self.config.node_width + self.config.horizontal_gap),
+            y: f64::from(generation) * (self.config.node_height + self.config.vertical_gap),
+        });
+        Some(column)
+    }
+
+    /// Returns the people on the next row after `xref`, in display order.
+    fn next_generation(&self, xref: &str) -> Vec<&'a Individual> {
+        match self.kind {
+            ChartKind::Pedigree => self
+                .data
+                .get_families_as_child(xref)
+                .first()
+                .map(|family| self.data.get_parents(family))
+                .unwrap_or_default(),
+            ChartKind::Descendants => self
+                .data
+                .get_families_as_spouse(xref)
+                .into_iter()
+                .flat_map(|family| self.data.get_children(family))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const FAMILY: &str = "\
+        0 HEAD\n\
+        1 GEDC\n\
+        2 VERS 5.5\n\
+        0 @I1@ INDI\n\
+        1 NAME Root /Doe/\n\
+        0 @I2@ INDI\n\
+        1 NAME Father /Doe/\n\
+        0 @I3@ INDI\n\
+        1 NAME Mother /Roe/\n\
+        0 @I4@ INDI\n\
+        1 NAME Grandfather /Doe/\n\
+        0 @I5@ INDI\n\
+        1 NAME Grandmother /Poe/\n\
+        0 @F1@ FAM\n\
+        1 HUSB @I2@\n\
+        1 WIFE @I3@\n\
+        1 CHIL @I1@\n\
+        0 @F2@ FAM\n\
+        1 HUSB @I4@\n\
+        1 WIFE @I5@\n\
+        1 CHIL @I2@\n\
+        0 TRLR";
+
+    #[test]
+    fn test_pedigree_layout() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let config = LayoutConfig::default();
+        let layout = data.pedigree_layout("@I1@", &config).unwrap();
+
+        assert_eq!(layout.nodes.len(), 5);
+        assert_eq!(layout.edges.len(), 4);
+        assert_eq!(layout.view_box(), "0 0 520 260");
+
+        let pos = |xref| {
+            let node = layout.node(xref).unwrap();
+            (node.x, node.y)
+        };
+        assert_eq!(pos("@I4@"), (0.0, 200.0));
+        assert_eq!(pos("@I5@"), (180.0, 200.0));
+        assert_eq!(pos("@I2@"), (90.0, 100.0));
+        assert_eq!(pos("@I3@"), (360.0, 100.0));
+        assert_eq!(pos("@I1@"), (225.0, 0.0));
+        assert_eq!(layout.node("@I1@").unwrap().label, "Root Doe");
+    }
+
+    #[test]
+    fn test_descendant_layout_and_generation_limit() {
+        let data = GedcomBuilder::new().build_from_str(FAMILY).unwrap();
+        let config = LayoutConfig {
+            max_generations: 2,
+            ..LayoutConfig::default()
+        };
+        let layout = data.descendant_layout("@I4@", &config).unwrap();
+
+        let xrefs: Vec<&str> = layout.nodes.iter().map(|n| n.xref.as_str()).collect();
+        assert_eq!(xrefs, vec!["@I4@", "@I2@"]);
+        assert_eq!(
+            layout.edges,
+            vec![LayoutEdge {
+                from: "@I4@".to_string(),
+                to: "@I2@".to_string()
+            }]
+        );
+
+        assert!(data.descendant_layout("@MISSING@", &config).is_none());
+    }
+}
@@ -31,7 +31,10 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 ```
 
-This crate contains an optional `"json"` feature that implements serialization and deserialization to JSON with [`serde`](https://serde.rs).
+This crate contains an optional `"serde"` feature that implements [`serde`](https://serde.rs)'s
+`Serialize` and `Deserialize` for the parsed records as well as parser and writer configurations,
+diagnostics and export options, so any of them can be stored in JSON, YAML, TOML or another
+format. The `"json"` feature enables it along with `serde_json`.
 
 To enable JSON support, add the feature to your `Cargo.toml`:
 
@@ -121,6 +124,12 @@ fn main() -> Result<(), Box<dyn Error>> {
                 GedcomError::IoError(msg) => {
                     eprintln!("I/O error: {}", msg);
                 }
+                GedcomError::InFile { path, source } => {
+                    eprintln!("Error in {}: {}", path, source);
+                }
+                GedcomError::InvalidStructure { path, message } => {
+                    eprintln!("Invalid structure at {}: {}", path, message);
+                }
             }
         }
     }
@@ -146,15 +155,51 @@ pub mod encoding;
 /// - Known GEDCOM tag handling
 #[macro_use]
 pub mod util;
+/// Asynchronous streaming parser for GEDCOM files.
+///
+/// Requires the `async` feature to be enabled.
+#[cfg(feature = "async")]
+pub mod async_stream;
+/// Zero-copy parsing into a borrowed record tree.
+pub mod borrowed;
 /// Builder pattern for configuring GEDCOM parsing.
 pub mod builder;
+/// Releasing memory left over after editing.
+pub mod compact;
+/// Fluent builders for creating records from scratch.
+pub mod construct;
+/// Copying records between datasets under collision-free xrefs.
+pub mod copy;
 /// Improved Debug trait implementations for GEDCOM data structures.
 pub mod debug;
+/// Machine-readable diagnostics and SARIF output.
+pub mod diagnostics;
+/// Reading the non-standard GEDCOM of particular applications.
+pub mod dialect;
+/// Record- and field-level differences between two datasets.
+pub mod diff;
+/// Content digests of whole datasets, for verifying files after transfer.
+pub mod digest;
 /// Display trait implementations for GEDCOM data structures.
 pub mod display;
+/// Editing records while keeping the links between them consistent.
+pub mod edit;
 /// Error types for the `ged_io` crate.
 pub mod error;
 
+/// Resolving extension tags through the header schema and mapping known ones
+/// into typed fields.
+pub mod extension;
+
+/// Exporters that render GEDCOM data in other formats, such as SVG charts.
+pub mod export;
+
+/// Test corpora loaded from a local directory or generated on demand.
+pub mod fixtures;
+
+/// Per-individual media galleries resolved from multimedia links.
+pub mod gallery;
+
 /// GEDZIP file format support for GEDCOM 7.0.
 ///
 /// This module provides functionality to read and write GEDZIP files, which are
@@ -187,7 +232,42 @@ pub mod gedzip;
 
 /// Indexed GEDCOM data structure for O(1) lookups.
 pub mod indexed;
+/// Interning of repeated strings such as place names and surnames.
+pub mod intern;
+#[cfg(feature = "json")]
+mod json;
+/// Pedigree and descendant chart layout.
+pub mod layout;
+/// Low-level iteration over GEDCOM lines.
+pub mod lines;
+/// Merging duplicate records with conflict reporting.
+pub mod merge;
+/// Comparing personal names, such as by how they sound.
+pub mod names;
+#[cfg(feature = "parallel")]
+mod parallel;
 pub mod parser;
+/// Where each record and structure sits in the source file.
+pub mod position;
+/// Common imports and one-call parsing of strings, bytes and paths.
+pub mod prelude;
+/// Hiding the details of living people before sharing a tree.
+pub mod privacy;
+/// Per-fact provenance and change history for individuals.
+pub mod provenance;
+/// Push-based parsing of input that arrives in chunks.
+pub mod push;
+/// The source text of each record, kept for debugging and audits.
+pub mod raw;
+mod recover;
+/// Renumbering xrefs to a regular scheme and allocating unused ones.
+pub mod renumber;
+/// Tag frequencies and other statistics gathered while parsing.
+pub mod report;
+/// Finding individuals by name, birth year and place.
+pub mod search;
+/// Aggregate statistics, such as event counts per decade.
+pub mod statistics;
 /// Streaming parser for large GEDCOM files.
 ///
 /// This module provides an iterator-based streaming parser that reads GEDCOM files
@@ -215,6 +295,8 @@ pub mod parser;
 /// }
 /// ```
 pub mod stream;
+/// Extracting the branch of a tree around one individual.
+pub mod subtree;
 pub mod tokenizer;
 pub mod types;
 /// GEDCOM version detection and handling.
@@ -255,22 +337,27 @@ pub mod writer;
 pub use builder::{GedcomBuilder, ParserConfig};
 pub use debug::ImprovedDebug;
 pub use encoding::{decode_gedcom_bytes, detect_encoding, GedcomEncoding};
-pub use error::GedcomError;
+pub use error::{GedcomError, ParseWarning};
 pub use stream::{GedcomRecord, GedcomStreamParser};
-pub use types::SourceCitationStats;
+pub use types::{DuplicateXref, DuplicateXrefPolicy, RecordType, SourceCitationStats};
 pub use version::{detect_version, GedcomVersion, VersionFeatures};
-pub use writer::{GedcomWriter, WriterConfig};
+pub use writer::{write_file, GedcomWriter, LineLengthMeasure, WriterConfig, WriterProfile};
 
 use crate::{
+    encoding::{decode_borrowed, read_limited},
     tokenizer::{Token, Tokenizer},
     types::GedcomData,
 };
-use std::str::Chars;
+use std::{borrow::Cow, str::Chars};
 
 /// The main interface for parsing GEDCOM files into structured Rust data types.
 ///
-/// This struct wraps a tokenizer and provides methods to parse GEDCOM content
-/// into a [`GedcomData`] structure.
+/// This struct holds GEDCOM text, borrowed or decoded, and provides methods to
+/// parse it into a [`GedcomData`] structure. Text can come from a character
+/// iterator ([`new`](Self::new)), a byte slice ([`from_bytes`](Self::from_bytes))
+/// or a reader ([`from_reader`](Self::from_reader)); the last two detect and
+/// decode the encoding the same way as
+/// [`GedcomBuilder::build_from_bytes`] and [`GedcomBuilder::build_from_reader`].
 ///
 /// # Version Support
 ///
@@ -292,7 +379,7 @@ use std::str::Chars;
 /// assert_eq!(data.individuals.len(), 1);
 /// ```
 pub struct Gedcom<'a> {
-    tokenizer: Tokenizer<'a>,
+    source: Cow<'a, str>,
 }
 
 impl<'a> Gedcom<'a> {
@@ -301,10 +388,40 @@ impl<'a> Gedcom<'a> {
     /// # Errors
     ///
     /// Returns an error if the GEDCOM data is malformed.
+    // Taking `Chars` by value keeps the original signature.
+    #[allow(clippy::needless_pass_by_value)]
     pub fn new(chars: Chars<'a>) -> Result<Gedcom<'a>, GedcomError> {
-        let mut tokenizer = Tokenizer::new(chars);
-        tokenizer.next_token()?;
-        Ok(Gedcom { tokenizer })
+        Self::from_text(Cow::Borrowed(chars.as_str()))
+    }
+
+    /// Creates a new `Gedcom` parser from raw bytes in any supported encoding.
+    ///
+    /// UTF-8 and ASCII input is parsed in place; other encodings are decoded
+    /// to a string first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes cannot be decoded or the GEDCOM data is
+    /// malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::Gedcom;
+    ///
+    /// let bytes = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n1 CHAR ISO-8859-1\n0 @I1@ INDI\n1 NAME Ren\xE9 /Roy/\n0 TRLR";
+    /// let data = Gedcom::from_bytes(bytes).unwrap().parse_data().unwrap();
+    /// assert_eq!(data.individuals[0].full_name().as_deref(), Some("René Roy"));
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Gedcom<'a>, GedcomError> {
+        let (source, _) = decode_borrowed(bytes)?;
+        Self::from_text(source)
+    }
+
+    fn from_text(source: Cow<'a, str>) -> Result<Gedcom<'a>, GedcomError> {
+        // Report a malformed first line here rather than on parse.
+        Tokenizer::new(source.chars()).next_token()?;
+        Ok(Gedcom { source })
     }
 
     /// Processes the character data to produce a [`GedcomData`] object containing the parsed
@@ -314,11 +431,43 @@ impl<'a> Gedcom<'a> {
     ///
     /// Returns an error if the GEDCOM data is malformed.
     pub fn parse_data(&mut self) -> Result<GedcomData, GedcomError> {
+        let mut tokenizer = Tokenizer::new(self.source.chars());
+        tokenizer.next_token()?;
         // Accept EOF-terminated files (missing TRLR).
-        if self.tokenizer.current_token == Token::EOF {
+        if tokenizer.current_token == Token::EOF {
             return Ok(GedcomData::default());
         }
-        GedcomData::new(&mut self.tokenizer, 0)
+        GedcomData::new(&mut tokenizer, 0)
+    }
+}
+
+impl Gedcom<'static> {
+    /// Creates a new `Gedcom` parser from a reader, detecting and decoding
+    /// its encoding.
+    ///
+    /// The whole input is read before this returns, and then handled as in
+    /// [`from_bytes`](Gedcom::from_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails, the input cannot be decoded or the
+    /// GEDCOM data is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::Gedcom;
+    ///
+    /// let bytes: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n0 TRLR";
+    /// let data = Gedcom::from_reader(bytes).unwrap().parse_data().unwrap();
+    /// assert_eq!(data.individuals.len(), 1);
+    /// ```
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<Self, GedcomError> {
+        let bytes = read_limited(reader, None)?;
+        let source = Gedcom::from_bytes(&bytes)?.source.into_owned();
+        Ok(Gedcom {
+            source: Cow::Owned(source),
+        })
     }
 }
 
@@ -415,4 +564,47 @@ mod tests {
             Some("http://example.com/custom")
         );
     }
+
+    #[test]
+    fn test_parse_from_bytes_and_reader() {
+        let sample = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME Zoë /Roy/\n0 TRLR";
+        let from_str = Gedcom::new(sample.chars()).unwrap().parse_data().unwrap();
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend(sample.encode_utf16().flat_map(u16::to_le_bytes));
+        let from_bytes = Gedcom::from_bytes(&utf16).unwrap().parse_data().unwrap();
+        assert_eq!(from_bytes, from_str);
+
+        let from_reader = Gedcom::from_reader(utf16.as_slice())
+            .unwrap()
+            .parse_data()
+            .unwrap();
+        assert_eq!(from_reader, from_str);
+
+        let mut bom = b"\xEF\xBB\xBF".to_vec();
+        bom.extend_from_slice(sample.as_bytes());
+        let from_bytes = Gedcom::from_bytes(&bom).unwrap().parse_data().unwrap();
+        assert_eq!(
+            from_bytes.individuals[0].full_name().as_deref(),
+            Some("Zoë Roy")
+        );
+
+        // The first non-ASCII byte comes after the first few kilobytes.
+        let mut late = format!(
+            "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 NOTE {}\n",
+            "x".repeat(5000)
+        )
+        .into_bytes();
+        late.extend_from_slice(b"0 @I1@ INDI\n1 NAME Ren\xE9 /Roy/\n0 TRLR\n");
+        let from_bytes = Gedcom::from_bytes(&late).unwrap().parse_data().unwrap();
+        let from_reader = Gedcom::from_reader(late.as_slice())
+            .unwrap()
+            .parse_data()
+            .unwrap();
+        assert_eq!(
+            from_bytes.individuals[0].full_name().as_deref(),
+            Some("René Roy")
+        );
+        assert_eq!(from_reader, from_bytes);
+    }
 }
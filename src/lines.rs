@@ -0,0 +1,313 @@
+//! Line-level access to GEDCOM files.
+//!
+//! [`GedcomLineIter`](crate::lines::GedcomLineIter) splits input into
+//! [`GedcomLine`](crate::lines::GedcomLine)s, each holding the level,
+//! optional xref, tag and optional value of one physical line. This is
+//! the layer below the tokenizer: nothing is nested, `CONC`/`CONT` lines are
+//! not joined and values are returned exactly as written, `@@` escapes
+//! included. Each line carries its line number and byte offset in the input,
+//! so it suits tools that analyze files on their own terms, such as tag
+//! counters, linters or grep-style filters, without building the typed model.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::lines::GedcomLineIter;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+//! let lines: Vec<_> = GedcomLineIter::from_str(source).collect::<Result<_, _>>().unwrap();
+//!
+//! assert_eq!(lines[3].xref.as_deref(), Some("@I1@"));
+//! assert_eq!(lines[4].tag, "NAME");
+//! assert_eq!(lines[4].value.as_deref(), Some("John /Doe/"));
+//! assert_eq!((lines[4].line_number, lines[4].offset), (5, 37));
+//! ```
+
+use std::{fmt, io::BufRead};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::GedcomError;
+
+/// One line of a GEDCOM file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GedcomLine {
+    /// The nesting level, 0 for records.
+    pub level: u8,
+    /// The cross-reference identifier, with its `@` delimiters (e.g. `@I1@`).
+    pub xref: Option<String>,
+    /// The tag, standard or user-defined.
+    pub tag: String,
+    /// The raw line value, if the line has one.
+    pub value: Option<String>,
+    /// The 1-based line number in the input.
+    pub line_number: u32,
+    /// The offset in bytes of the start of the line in the input.
+    pub offset: usize,
+}
+
+impl GedcomLine {
+    /// Parses a single line, without its terminator. Its offset is left at
+    /// 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError::ParseError` if the line has no level or no tag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::lines::GedcomLine;
+    ///
+    /// let line = GedcomLine::parse("0 @F1@ FAM", 1).unwrap();
+    /// assert_eq!(line.level, 0);
+    /// assert_eq!(line.xref.as_deref(), Some("@F1@"));
+    /// assert_eq!(line.tag, "FAM");
+    /// assert_eq!(line.value, None);
+    /// ```
+    pub fn parse(text: &str, line_number: u32) -> Result<GedcomLine, GedcomError> {
+        let line = RawLine::parse(text, line_number)?;
+        Ok(GedcomLine {
+            level: line.level,
+            xref: line.xref.map(str::to_string),
+            tag: line.tag.to_string(),
+            value: line.value.map(str::to_string),
+            line_number,
+            offset: 0,
+        })
+    }
+}
+
+/// A line split into its parts, borrowing from the input.
+pub(crate) struct RawLine<'a> {
+    pub(crate) level: u8,
+    pub(crate) xref: Option<&'a str>,
+    pub(crate) tag: &'a str,
+    pub(crate) value: Option<&'a str>,
+}
+
+impl<'a> RawLine<'a> {
+    pub(crate) fn parse(text: &'a str, line_number: u32) -> Result<RawLine<'a>, GedcomError> {
+        let error = |message: &str| GedcomError::ParseError {
+            line: line_number,
+            message: message.to_string(),
+        };
+
+        let text = text.trim_start();
+        let (level, rest) = split_word(text);
+        let level = level
+            .parse()
+            .map_err(|_| error(&format!("Expected a level, found {level:?}")))?;
+
+        let (mut tag, mut rest) = split_word(rest);
+        let mut xref = None;
+        if tag.starts_with('@') {
+            xref = Some(tag);
+            (tag, rest) = split_word(rest);
+        }
+        if tag.is_empty() {
+            return Err(error("Missing tag"));
+        }
+
+        Ok(RawLine {
+            level,
+            xref,
+            tag,
+            value: (!rest.is_empty()).then_some(rest),
+        })
+    }
+}
+
+/// Writes the line back in GEDCOM form, without a terminator.
+impl fmt::Display for GedcomLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.level)?;
+        if let Some(ref xref) = self.xref {
+            write!(f, " {xref}")?;
+        }
+        write!(f, " {}", self.tag)?;
+        if let Some(ref value) = self.value {
+            write!(f, " {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits off the first space-delimited word, dropping that one delimiter.
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start_matches(' ');
+    match text.split_once(' ') {
+        Some((word, rest)) => (word, rest),
+        None => (text, ""),
+    }
+}
+
+/// An iterator over the lines of a GEDCOM file.
+///
+/// Blank lines are skipped, and a UTF-8 byte order mark at the start is
+/// ignored. LF, CRLF and CR-only line endings are accepted. After an error
+/// the iterator ends. Like the [streaming parser](crate::stream), it requires
+/// UTF-8 input; wrap other input in a
+/// [`DecodingReader`](crate::encoding::DecodingReader).
+pub struct GedcomLineIter<R: BufRead> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// Lines read so far, blank ones included
+    line_number: u32,
+    /// Bytes read so far
+    offset: usize,
+    finished: bool,
+}
+
+impl<'a> GedcomLineIter<&'a [u8]> {
+    /// Creates an iterator over the lines of a string.
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(content: &'a str) -> Self {
+        Self::new(content.as_bytes())
+    }
+}
+
+impl<R: BufRead> GedcomLineIter<R> {
+    /// Creates an iterator over the lines of a buffered reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::with_capacity(256),
+            line_number: 0,
+            offset: 0,
+            finished: false,
+        }
+    }
+
+    /// Reads the next physical line into the buffer, without its terminator.
+    ///
+    /// Returns false at the end of the input.
+    fn read_line(&mut self) -> Result<bool, GedcomError> {
+        self.buffer.clear();
+        loop {
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                return Ok(!self.buffer.is_empty());
+            }
+
+            if let Some(end) = available.iter().position(|&b| b == b'\n' || b == b'\r') {
+                let terminator = available[end];
+                self.buffer.extend_from_slice(&available[..end]);
+                self.reader.consume(end + 1);
+                self.offset += end + 1;
+                if terminator == b'\r' {
+                    // Take the LF of a CRLF pair along with the CR.
+                    if self.reader.fill_buf()?.first() == Some(&b'\n') {
+                        self.reader.consume(1);
+                        self.offset += 1;
+                    }
+                }
+                return Ok(true);
+            }
+
+            let len = available.len();
+            self.buffer.extend_from_slice(available);
+            self.reader.consume(len);
+            self.offset += len;
+        }
+    }
+
+    fn next_line(&mut self) -> Result<Option<GedcomLine>, GedcomError> {
+        loop {
+            let mut offset = self.offset;
+            if !self.read_line()? {
+                return Ok(None);
+            }
+            self.line_number += 1;
+
+            let text = std::str::from_utf8(&self.buffer).map_err(|_| {
+                GedcomError::EncodingError(format!(
+                    "Line iterator requires UTF-8 input; invalid UTF-8 at line {}",
+                    self.line_number
+                ))
+            })?;
+            let text = if self.line_number == 1 {
+                let stripped = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+                offset += text.len() - stripped.len();
+                stripped
+            } else {
+                text
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            let mut line = GedcomLine::parse(text, self.line_number)?;
+            line.offset = offset;
+            return Ok(Some(line));
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for GedcomLineIter<R> {
+    type Item = Result<GedcomLine, GedcomError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.next_line().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_iter_fields_and_line_endings() {
+        let source = "\u{FEFF}0 HEAD\r\n1 GEDC\r2 VERS 5.5\n\n0 @I1@ INDI\n1 NOTE  two spaces @@here\n1 _CUSTOM\n";
+        let lines: Vec<GedcomLine> = GedcomLineIter::from_str(source)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let numbered: Vec<(u32, String)> = lines
+            .iter()
+            .map(|l| (l.line_number, l.to_string()))
+            .collect();
+        assert_eq!(
+            numbered,
+            vec![
+                (1, "0 HEAD".to_string()),
+                (2, "1 GEDC".to_string()),
+                (3, "2 VERS 5.5".to_string()),
+                (5, "0 @I1@ INDI".to_string()),
+                (6, "1 NOTE  two spaces @@here".to_string()),
+                (7, "1 _CUSTOM".to_string()),
+            ]
+        );
+        assert_eq!(lines[3].xref.as_deref(), Some("@I1@"));
+        assert_eq!(lines[4].value.as_deref(), Some(" two spaces @@here"));
+        assert_eq!(lines[5].value, None);
+
+        let offsets: Vec<usize> = lines.iter().map(|l| l.offset).collect();
+        assert_eq!(offsets, vec![3, 11, 18, 30, 42, 68]);
+    }
+
+    #[test]
+    fn test_line_iter_stops_after_error() {
+        let source = "0 HEAD\nX BAD\n0 TRLR\n";
+        let mut lines =
+            GedcomLineIter::new(std::io::BufReader::with_capacity(2, source.as_bytes()));
+
+        assert!(lines.next().unwrap().is_ok());
+        assert!(matches!(
+            lines.next(),
+            Some(Err(GedcomError::ParseError { line: 2, .. }))
+        ));
+        assert!(lines.next().is_none());
+
+        assert!(GedcomLine::parse("1 @X1@", 1).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+//! Merging records that describe the same person.
+//!
+//! [`GedcomData::merge_individuals`] folds one individual record into
+//! another: facts, citations and family links of both are combined, every
+//! pointer to the merged-away record is redirected to the survivor, and the
+//! fields that disagree are listed in a
+//! [`MergeReport`](crate::merge::MergeReport) for a person to review.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{merge::MergePolicy, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Smith/\n1 BIRT\n2 DATE 1850\n\
+//!     0 @I2@ INDI\n1 NAME Johann /Schmidt/\n1 DEAT\n2 DATE 1910\n1 FAMS @F1@\n\
+//!     0 @F1@ FAM\n1 HUSB @I2@\n0 TRLR";
+//! let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let report = data.merge_individuals("@I1@", "@I2@", MergePolicy::PreferFirst).unwrap();
+//! assert_eq!(data.individuals.len(), 1);
+//! assert_eq!(data.individuals[0].events.len(), 2);
+//! assert_eq!(data.families[0].individual1.as_deref(), Some("@I1@"));
+//!
+//! assert_eq!(report.conflicts[0].field, "NAME");
+//! assert_eq!(report.conflicts[0].discarded, "Johann /Schmidt/");
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::{
+        event::{detail::Detail, Event},
+        individual::{association::Association, Individual},
+        GedcomData, RecordType,
+    },
+    writer::event_to_tag,
+    GedcomError,
+};
+
+/// Which record's value is kept when both records have a different one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MergePolicy {
+    /// Keep the value of the surviving record (the default).
+    #[default]
+    PreferFirst,
+    /// Keep the value of the record merged into it.
+    PreferSecond,
+}
+
+/// A field on which the two merged records disagreed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MergeConflict {
+    /// The GEDCOM tag of the field, with a path for nested ones (e.g. `BIRT.DATE`).
+    pub field: String,
+    /// The value now on the merged record.
+    pub kept: String,
+    /// The other value, no longer on the merged record unless the field
+    /// can occur several times.
+    pub discarded: String,
+}
+
+/// The outcome of [`GedcomData::merge_individuals`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MergeReport {
+    /// The xref of the merged record.
+    pub survivor: String,
+    /// The xref of the record merged into it, which no longer exists.
+    pub removed: String,
+    /// Fields that need a person to decide between the two values.
+    pub conflicts: Vec<MergeConflict>,
+    /// The number of pointers changed from `removed` to `survivor`.
+    pub rewritten_references: usize,
+}
+
+impl MergeReport {
+    /// Returns true if the records did not disagree on any field.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl GedcomData {
+    /// Merges the individual `second` into the individual `first`.
+    ///
+    /// The record of `first` survives, keeping its xref, and the record of
+    /// `second` is removed. Events, attributes, citations, media, family
+    /// links and other repeatable facts of both are combined, skipping exact
+    /// duplicates. Single-valued fields such as the name or sex are taken
+    /// from whichever record has one; when both do and they differ, `policy`
+    /// picks the value and the disagreement is reported. Births and deaths
+    /// with different dates are kept side by side and reported too, with the
+    /// preferred record's event first.
+    ///
+    /// Every pointer to `second`, such as a family's partners and children
+    /// with their relationships, associations and aliases, is changed to
+    /// point to `first`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if either xref names no
+    /// individual, or both name the same one.
+    pub fn merge_individuals(
+        &mut self,
+        first: &str,
+        second: &str,
+        policy: MergePolicy,
+    ) -> Result<MergeReport, GedcomError> {
+        let position = |xref: &str| {
+            self.individuals
+                .iter()
+                .position(|i| i.xref.as_deref() == Some(xref))
+                .ok_or_else(|| {
+                    GedcomError::InvalidFormat(format!("No individual with xref {xref}"))
+                })
+        };
+        let (first_index, second_index) = (position(first)?, position(second)?);
+        if first_index == second_index {
+            return Err(GedcomError::InvalidFormat(format!(
+                "Cannot merge individual {first} into itself"
+            )));
+        }
+
+        let removed = self.individuals.remove(second_index);
+        let survivor_index = if second_index < first_index {
+            first_index - 1
+        } else {
+            first_index
+        };
+        let mut report = MergeReport {
+            survivor: first.to_string(),
+            removed: second.to_string(),
+            ..MergeReport::default()
+        };
+        merge_into(
+            &mut self.individuals[survivor_index],
+            removed,
+            policy,
+            &mut report.conflicts,
+        );
+        report.rewritten_references = self.redirect_individual(second, first);
+        Ok(report)
+    }
+
+    /// Changes every pointer to the individual `from` into one to `to`, and
+    /// returns how many were changed.
+    fn redirect_individual(&mut self, from: &str, to: &str) -> usize {
+        let mut count = 0;
+        self.visit_all_links(&mut |record_type, xref| {
+            if record_type == RecordType::Individual && xref == from {
+                *xref = to.to_string();
+                count += 1;
+            }
+            true
+        });
+
+        for family in &mut self.families {
+            let mut seen = Vec::new();
+            family.children.retain(|child| {
+                let first = !seen.contains(child);
+                seen.push(child.clone());
+                first
+            });
+            let mut seen = Vec::new();
+            family.child_details.retain(|child| {
+                let first = !seen.contains(&child.xref);
+                seen.push(child.xref.clone());
+                first
+            });
+        }
+        for individual in &mut self.individuals {
+            // A record may have pointed to the other half of the merge.
+            let own = individual.xref.clone();
+            individual
+                .aliases
+                .retain(|alias| Some(alias) != own.as_ref());
+            individual
+                .associations
+                .retain(|association: &Association| Some(&association.xref) != own.as_ref());
+        }
+        count
+    }
+}
+
+/// Folds `other` into `survivor`, recording disagreements in `conflicts`.
+fn merge_into(
+    survivor: &mut Individual,
+    other: Individual,
+    policy: MergePolicy,
+    conflicts: &mut Vec<MergeConflict>,
+) {
+    let mut merger = Merger { policy, conflicts };
+    // The first names are the primary ones; any others are kept alongside.
+    let mut kept_names = std::mem::take(&mut survivor.names);
+    let mut other_names = other.names;
+    let mut primary = (!kept_names.is_empty()).then(|| kept_names.remove(0));
+    let other_primary = (!other_names.is_empty()).then(|| other_names.remove(0));
+    merger.single("NAME", &mut primary, other_primary, |n| {
+        n.value.clone().unwrap_or_default()
+    });
+    survivor.names.extend(primary);
+    append_new(&mut survivor.names, kept_names);
+    append_new(&mut survivor.names, other_names);
+    merger.single("SEX", &mut survivor.sex, other.sex, |g| g.value.to_string());
+    for (field, kept, other) in [
+        ("UID", &mut survivor.uid, other.uid),
+        ("RESN", &mut survivor.restriction, other.restriction),
+        (
+            "RIN",
+            &mut survivor.automated_record_id,
+            other.automated_record_id,
+        ),
+        (
+            "AFN",
+            &mut survivor.ancestral_file_number,
+            other.ancestral_file_number,
+        ),
+        (
+            "ANCI",
+            &mut survivor.ancestor_interest,
+            other.ancestor_interest,
+        ),
+        (
+            "DESI",
+            &mut survivor.descendant_interest,
+            other.descendant_interest,
+        ),
+    ] {
+        merger.single(field, kept, other, String::clone);
+    }
+
+    // Change dates are bookkeeping rather than facts to review.
+    let (preferred, fallback) = merger.order(survivor.change_date.take(), other.change_date);
+    survivor.change_date = preferred.or(fallback);
+    let (preferred, fallback) = merger.order(survivor.last_updated.take(), other.last_updated);
+    survivor.last_updated = preferred.or(fallback);
+
+    merger.events(&mut survivor.events, other.events);
+    append_new(&mut survivor.attributes, other.attributes);
+    append_new(&mut survivor.source, other.source);
+    append_new(&mut survivor.notes, other.notes);
+    append_new(&mut survivor.multimedia, other.multimedia);
+    append_new(&mut survivor.non_events, other.non_events);
+    append_new(&mut survivor.lds_ordinances, other.lds_ordinances);
+    append_new(&mut survivor.associations, other.associations);
+    append_new(&mut survivor.aliases, other.aliases);
+    append_new(
+        &mut survivor.user_reference_numbers,
+        other.user_reference_numbers,
+    );
+    append_new(&mut survivor.vendor_uids, other.vendor_uids);
+    append_new(&mut survivor.external_ids, other.external_ids);
+    append_new(&mut survivor.custom_data, other.custom_data);
+    for link in other.families {
+        let known = survivor
+            .families
+            .iter()
+            .any(|l| l.xref == link.xref && l.family_link_type == link.family_link_type);
+        if !known {
+            survivor.families.push(link);
+        }
+    }
+}
+
+/// Applies a [`MergePolicy`] field by field.
+struct Merger<'a> {
+    policy: MergePolicy,
+    conflicts: &'a mut Vec<MergeConflict>,
+}
+
+impl Merger<'_> {
+    /// Returns the two values with the preferred one first.
+    fn order<T>(&self, survivor: T, other: T) -> (T, T) {
+        match self.policy {
+            MergePolicy::PreferFirst => (survivor, other),
+            MergePolicy::PreferSecond => (other, survivor),
+        }
+    }
+
+    /// Merges a field that holds at most one value.
+    fn single<T: PartialEq>(
+        &mut self,
+        field: &str,
+        kept: &mut Option<T>,
+        other: Option<T>,
+        show: fn(&T) -> String,
+    ) {
+        let (current, other) = match (kept.take(), other) {
+            (Some(current), Some(other)) if current != other => (current, other),
+            (current, other) => {
+                *kept = current.or(other);
+                return;
+            }
+        };
+        let (preferred, discarded) = self.order(current, other);
+        self.conflicts.push(MergeConflict {
+            field: field.to_string(),
+            kept: show(&preferred),
+            discarded: show(&discarded),
+        });
+        *kept = Some(preferred);
+    }
+
+    /// Combines two event lists, reporting births and deaths whose dates
+    /// disagree.
+    fn events(&mut self, kept: &mut Vec<Detail>, other: Vec<Detail>) {
+        let (preferred, fallback) = self.order(std::mem::take(kept), other);
+        *kept = preferred;
+        for event in &fallback {
+            if !matches!(event.event, Event::Birth | Event::Death) {
+                continue;
+            }
+            let date = |e: &Detail| e.date.as_ref().and_then(|d| d.value.clone());
+            let Some(discarded) = date(event) else {
+                continue;
+            };
+            let existing = kept
+                .iter()
+                .filter(|e| e.event == event.event)
+                .find_map(date);
+            if let Some(existing) = existing.filter(|existing| *existing != discarded) {
+                self.conflicts.push(MergeConflict {
+                    field: format!("{}.DATE", event_to_tag(&event.event)),
+                    kept: existing,
+                    discarded,
+                });
+            }
+        }
+        append_new(kept, fallback);
+    }
+}
+
+/// Appends the items of `other` that `kept` does not already hold.
+fn append_new<T: PartialEq>(kept: &mut Vec<T>, other: Vec<T>) {
+    for item in other {
+        if !kept.contains(&item) {
+            kept.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const SOURCE: &str = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+        0 @I1@ INDI\n1 NAME Mary /Jones/\n1 SEX F\n1 BIRT\n2 DATE 1 MAY 1850\n1 FAMC @F1@\n\
+        0 @I2@ INDI\n1 NAME Mary /Jones/\n1 BIRT\n2 DATE 1851\n1 OCCU Weaver\n1 REFN 42\n1 FAMC @F1@\n1 FAMS @F2@\n\
+        0 @I3@ INDI\n1 ASSO @I2@\n2 RELA Godparent\n1 ALIA @I2@\n\
+        0 @F1@ FAM\n1 CHIL @I1@\n1 CHIL @I2@\n\
+        0 @F2@ FAM\n1 WIFE @I2@\n\
+        0 TRLR";
+
+    #[test]
+    fn test_merge_combines_and_redirects() {
+        let mut data = GedcomBuilder::new().build_from_str(SOURCE).unwrap();
+        let report = data
+            .merge_individuals("@I1@", "@I2@", MergePolicy::PreferFirst)
+            .unwrap();
+
+        assert_eq!(report.survivor, "@I1@");
+        assert_eq!(report.removed, "@I2@");
+        assert_eq!(report.rewritten_references, 4);
+        assert_eq!(
+            report.conflicts,
+            vec![MergeConflict {
+                field: "BIRT.DATE".to_string(),
+                kept: "1 MAY 1850".to_string(),
+                discarded: "1851".to_string(),
+            }]
+        );
+
+        assert!(data.find_individual("@I2@").is_none());
+        let mary = data.find_individual("@I1@").unwrap();
+        assert_eq!(mary.events.len(), 2);
+        assert_eq!(mary.attributes.len(), 1);
+        assert_eq!(mary.user_reference_numbers[0].value.as_deref(), Some("42"));
+        assert_eq!(mary.families.len(), 2);
+
+        assert_eq!(data.families[0].children, vec!["@I1@".to_string()]);
+        assert_eq!(data.families[1].individual2.as_deref(), Some("@I1@"));
+        let other = data.find_individual("@I3@").unwrap();
+        assert_eq!(other.associations[0].xref, "@I1@");
+        assert_eq!(other.aliases, vec!["@I1@".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_redirects_child_details() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME Ann /Roe/\n\
+            0 @I2@ INDI\n1 NAME Anne /Roe/\n1 FAMC @F1@\n\
+            0 @F1@ FAM\n1 CHIL @I2@\n2 _FREL Adopted\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        data.merge_individuals("@I1@", "@I2@", MergePolicy::PreferFirst)
+            .unwrap();
+
+        let family = &data.families[0];
+        assert_eq!(family.children, vec!["@I1@".to_string()]);
+        assert_eq!(family.child_details[0].xref, "@I1@");
+        let output = crate::GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(
+            output.contains("1 CHIL @I1@\n2 _FREL Adopted\n"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_merge_prefer_second() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME Jon /Smith/\n1 ALIA @I2@\n\
+            0 @I2@ INDI\n1 NAME John /Smith/\n1 SEX M\n\
+            0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let report = data
+            .merge_individuals("@I1@", "@I2@", MergePolicy::PreferSecond)
+            .unwrap();
+
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].kept, "John /Smith/");
+        assert_eq!(report.conflicts[0].discarded, "Jon /Smith/");
+        assert!(!report.is_clean());
+
+        let john = &data.individuals[0];
+        assert_eq!(john.xref.as_deref(), Some("@I1@"));
+        assert_eq!(john.names[0].value.as_deref(), Some("John /Smith/"));
+        assert!(john.sex.is_some());
+        assert!(john.aliases.is_empty());
+    }
+
+    #[test]
+    fn test_merge_errors() {
+        let mut data = GedcomBuilder::new().build_from_str(SOURCE).unwrap();
+        let before = data.clone();
+        assert!(data
+            .merge_individuals("@I1@", "@I9@", MergePolicy::default())
+            .is_err());
+        assert!(data
+            .merge_individuals("@I1@", "@I1@", MergePolicy::default())
+            .is_err());
+        assert_eq!(data, before);
+    }
+}
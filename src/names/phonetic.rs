@@ -0,0 +1,1162 @@
+//! Phonetic codes of names.
+//!
+//! A phonetic algorithm reduces a name to codes for how it sounds, and two
+//! names that share a code are taken to sound alike. Three are provided:
+//!
+//! - [`soundex`], American Soundex, used by the US census indexes: a letter
+//!   and three digits, such as `R163` for `Robert` and `Rupert`;
+//! - [`double_metaphone`], which knows the spelling rules of English and of
+//!   many names of European and Asian origin, and gives a second code where
+//!   a name has two likely pronunciations;
+//! - [`daitch_mokotoff`], designed for the Slavic and Yiddish names of
+//!   Central and Eastern European Jewish genealogy, which gives six digits
+//!   and several codes for ambiguous spellings.
+//!
+//! Accented Latin letters are read as their base letters, and characters
+//! other than letters are ignored. [`Individual::name_matches`] compares
+//! the names of two individuals with one of the algorithms.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::names::phonetic::{daitch_mokotoff, double_metaphone, soundex, Algorithm};
+//!
+//! assert_eq!(soundex("Robert"), "R163");
+//! assert_eq!(soundex("Rupert"), "R163");
+//! assert_eq!(double_metaphone("Schmidt"), ("XMT".to_string(), "SMT".to_string()));
+//! assert_eq!(daitch_mokotoff("Peters"), ["739400", "734000"]);
+//!
+//! assert!(Algorithm::DoubleMetaphone.matches("Schmidt", "Smith"));
+//! assert!(Algorithm::DaitchMokotoff.matches("Moskowitz", "Moskovitz"));
+//! assert!(!Algorithm::Soundex.matches("Smith", "Jones"));
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::latin_base;
+use crate::types::individual::{name::Name, Individual};
+
+/// A phonetic algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Algorithm {
+    /// American Soundex; see [`soundex`].
+    Soundex,
+    /// Double Metaphone; see [`double_metaphone`].
+    DoubleMetaphone,
+    /// Daitch–Mokotoff Soundex; see [`daitch_mokotoff`].
+    DaitchMokotoff,
+}
+
+impl Algorithm {
+    /// Returns the distinct codes of `name`, none if it has no letters.
+    #[must_use]
+    pub fn codes(self, name: &str) -> Vec<String> {
+        let mut codes = match self {
+            Algorithm::Soundex => vec![soundex(name)],
+            Algorithm::DoubleMetaphone => {
+                let (primary, alternate) = double_metaphone(name);
+                vec![primary, alternate]
+            }
+            Algorithm::DaitchMokotoff => daitch_mokotoff(name),
+        };
+        codes.retain(|code| !code.is_empty());
+        codes.dedup();
+        codes
+    }
+
+    /// Returns true if `a` and `b` share a code.
+    #[must_use]
+    pub fn matches(self, a: &str, b: &str) -> bool {
+        let codes = self.codes(a);
+        self.codes(b).iter().any(|code| codes.contains(code))
+    }
+}
+
+/// Appends the ASCII letters `c` is read as to `out`, in upper case. Other
+/// characters are dropped, except those in `keep`.
+fn fold(c: char, keep: &[char], out: &mut Vec<char>) {
+    if keep.contains(&c) {
+        out.push(c);
+        return;
+    }
+    let c = c.to_lowercase().next().unwrap_or(c);
+    if c.is_ascii_lowercase() {
+        out.push(c.to_ascii_uppercase());
+    } else if let Some(base) = latin_base(c) {
+        out.extend(base.chars().map(|c| c.to_ascii_uppercase()));
+    }
+}
+
+/// Returns the letters of `name` folded to upper-case ASCII, keeping the
+/// characters in `keep` and, if `spaces` is set, single spaces between
+/// words.
+fn letters(name: &str, keep: &[char], spaces: bool) -> Vec<char> {
+    let mut out = Vec::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_whitespace() || c == '-' {
+            if spaces && out.last().is_some_and(|&last| last != ' ') {
+                out.push(' ');
+            }
+        } else {
+            fold(c, keep, &mut out);
+        }
+    }
+    if out.last() == Some(&' ') {
+        out.pop();
+    }
+    out
+}
+
+/// Returns the American Soundex code of `name`: its first letter and the
+/// digits of the next three consonant sounds, or an empty string if it has
+/// no letters.
+///
+/// Letters with the same digit count once when they are adjacent or
+/// separated by `H` or `W` only, and vowels separate them.
+#[must_use]
+pub fn soundex(name: &str) -> String {
+    fn digit(c: char) -> Option<char> {
+        match c {
+            'B' | 'F' | 'P' | 'V' => Some('1'),
+            'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+            'D' | 'T' => Some('3'),
+            'L' => Some('4'),
+            'M' | 'N' => Some('5'),
+            'R' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let letters = letters(name, &[], false);
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+    let mut code = String::from(first);
+    let mut last = digit(first);
+    for &c in &letters[1..] {
+        if code.len() == 4 {
+            break;
+        }
+        match (c, digit(c)) {
+            ('H' | 'W', _) => {}
+            (_, Some(d)) if Some(d) != last => {
+                code.push(d);
+                last = Some(d);
+            }
+            (_, Some(_)) => {}
+            (_, None) => last = None,
+        }
+    }
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// The length of Double Metaphone codes.
+const METAPHONE_LEN: usize = 4;
+
+/// The primary and alternate codes of a Double Metaphone encoding.
+struct MetaphoneCodes {
+    primary: String,
+    alternate: String,
+}
+
+impl MetaphoneCodes {
+    fn push(&mut self, code: &str) {
+        self.push_both(code, code);
+    }
+
+    fn push_both(&mut self, primary: &str, alternate: &str) {
+        self.push_primary(primary);
+        self.push_alternate(alternate);
+    }
+
+    fn push_primary(&mut self, code: &str) {
+        let room = METAPHONE_LEN.saturating_sub(self.primary.len());
+        self.primary.extend(code.chars().take(room));
+    }
+
+    fn push_alternate(&mut self, code: &str) {
+        let room = METAPHONE_LEN.saturating_sub(self.alternate.len());
+        self.alternate.extend(code.chars().take(room));
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= METAPHONE_LEN && self.alternate.len() >= METAPHONE_LEN
+    }
+}
+
+/// A name being encoded by [`double_metaphone`], with the lookups of the
+/// reference implementation by Lawrence Philips.
+struct Metaphone {
+    value: Vec<char>,
+    slavo_germanic: bool,
+    codes: MetaphoneCodes,
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+}
+
+impl Metaphone {
+    /// Returns the character at `index`, or NUL outside the name.
+    fn at(&self, index: isize) -> char {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.value.get(index))
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    /// Returns true if the name has one of `options` at `start`.
+    fn has(&self, start: isize, options: &[&str]) -> bool {
+        let Ok(start) = usize::try_from(start) else {
+            return false;
+        };
+        options.iter().any(|option| {
+            let len = option.chars().count();
+            start + len <= self.value.len()
+                && self.value[start..start + len]
+                    .iter()
+                    .copied()
+                    .eq(option.chars())
+        })
+    }
+
+    fn last(&self) -> isize {
+        self.len() - 1
+    }
+
+    fn len(&self) -> isize {
+        isize::try_from(self.value.len()).unwrap_or(isize::MAX)
+    }
+
+    /// Returns the index after `index`, skipping a doubled `c`.
+    fn skip_double(&self, index: isize, c: char) -> isize {
+        if self.at(index + 1) == c {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode(mut self) -> (String, String) {
+        let mut index = isize::from(self.has(0, &["GN", "KN", "PN", "WR", "PS"]));
+        while !self.codes.is_complete() && index < self.len() {
+            index = match self.at(index) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if index == 0 {
+                        self.codes.push("A");
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.codes.push("P");
+                    self.skip_double(index, 'B')
+                }
+                'Ç' => {
+                    self.codes.push("S");
+                    index + 1
+                }
+                'C' => self.c(index),
+                'D' => self.d(index),
+                'F' => {
+                    self.codes.push("F");
+                    self.skip_double(index, 'F')
+                }
+                'G' => self.g(index),
+                'H' => self.h(index),
+                'J' => self.j(index),
+                'K' => {
+                    self.codes.push("K");
+                    self.skip_double(index, 'K')
+                }
+                'L' => self.l(index),
+                'M' => {
+                    self.codes.push("M");
+                    if self.m_is_double(index) {
+                        index + 2
+                    } else {
+                        index + 1
+                    }
+                }
+                'N' => {
+                    self.codes.push("N");
+                    self.skip_double(index, 'N')
+                }
+                'Ñ' => {
+                    self.codes.push("N");
+                    index + 1
+                }
+                'P' => self.p(index),
+                'Q' => {
+                    self.codes.push("K");
+                    self.skip_double(index, 'Q')
+                }
+                'R' => self.r(index),
+                'S' => self.s(index),
+                'T' => self.t(index),
+                'V' => {
+                    self.codes.push("F");
+                    self.skip_double(index, 'V')
+                }
+                'W' => self.w(index),
+                'X' => self.x(index),
+                'Z' => self.z(index),
+                _ => index + 1,
+            };
+        }
+        (self.codes.primary, self.codes.alternate)
+    }
+
+    fn c(&mut self, index: isize) -> isize {
+        if self.c_is_k(index) {
+            self.codes.push("K");
+            index + 2
+        } else if index == 0 && self.has(index, &["CAESAR"]) {
+            self.codes.push("S");
+            index + 2
+        } else if self.has(index, &["CH"]) {
+            self.ch(index)
+        } else if self.has(index, &["CZ"]) && !self.has(index - 2, &["WICZ"]) {
+            // Czerny
+            self.codes.push_both("S", "X");
+            index + 2
+        } else if self.has(index + 1, &["CIA"]) {
+            // Focaccia
+            self.codes.push("X");
+            index + 3
+        } else if self.has(index, &["CC"]) && !(index == 1 && self.at(0) == 'M') {
+            // A double C, but not McClelland
+            self.cc(index)
+        } else if self.has(index, &["CK", "CG", "CQ"]) {
+            self.codes.push("K");
+            index + 2
+        } else if self.has(index, &["CI", "CE", "CY"]) {
+            if self.has(index, &["CIO", "CIE", "CIA"]) {
+                // Italian
+                self.codes.push_both("S", "X");
+            } else {
+                self.codes.push("S");
+            }
+            index + 2
+        } else {
+            self.codes.push("K");
+            if self.has(index + 1, &[" C", " Q", " G"]) {
+                // Mac Caffrey, Mac Gregor
+                index + 3
+            } else if self.has(index + 1, &["C", "K", "Q"]) && !self.has(index + 1, &["CE", "CI"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    /// Whether a C before H is a K, as in the Germanic "Bacher".
+    fn c_is_k(&self, index: isize) -> bool {
+        if self.has(index, &["CHIA"]) {
+            return true;
+        }
+        if index <= 1 || is_vowel(self.at(index - 2)) || !self.has(index - 1, &["ACH"]) {
+            return false;
+        }
+        let next = self.at(index + 2);
+        (next != 'I' && next != 'E') || self.has(index - 2, &["BACHER", "MACHER"])
+    }
+
+    fn cc(&mut self, index: isize) -> isize {
+        if self.has(index + 2, &["I", "E", "H"]) && !self.has(index + 2, &["HU"]) {
+            if (index == 1 && self.at(index - 1) == 'A') || self.has(index - 1, &["UCCEE", "UCCES"])
+            {
+                // Accident, accede, succeed
+                self.codes.push("KS");
+            } else {
+                // Bacci, Bertucci
+                self.codes.push("X");
+            }
+            index + 3
+        } else {
+            self.codes.push("K");
+            index + 2
+        }
+    }
+
+    fn ch(&mut self, index: isize) -> isize {
+        if index > 0 && self.has(index, &["CHAE"]) {
+            // Michael
+            self.codes.push_both("K", "X");
+        } else if self.ch_is_greek(index) || self.ch_is_kh(index) {
+            self.codes.push("K");
+        } else if index == 0 {
+            self.codes.push("X");
+        } else if self.has(0, &["MC"]) {
+            self.codes.push("K");
+        } else {
+            self.codes.push_both("X", "K");
+        }
+        index + 2
+    }
+
+    /// Whether an initial CH has a Greek root, as in "Christ" or "chorus".
+    fn ch_is_greek(&self, index: isize) -> bool {
+        index == 0
+            && (self.has(index + 1, &["HARAC", "HARIS"])
+                || self.has(index + 1, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.has(0, &["CHORE"])
+    }
+
+    /// Whether CH sounds like KH, in Germanic and Greek words.
+    fn ch_is_kh(&self, index: isize) -> bool {
+        self.has(0, &["VAN ", "VON ", "SCH"])
+            || self.has(index - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.has(index + 2, &["T", "S"])
+            || ((self.has(index - 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.has(
+                    index + 2,
+                    &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                ) || index + 1 == self.last()))
+    }
+
+    fn d(&mut self, index: isize) -> isize {
+        if self.has(index, &["DG"]) {
+            if self.has(index + 2, &["I", "E", "Y"]) {
+                // Edge
+                self.codes.push("J");
+                index + 3
+            } else {
+                // Edgar
+                self.codes.push("TK");
+                index + 2
+            }
+        } else if self.has(index, &["DT", "DD"]) {
+            self.codes.push("T");
+            index + 2
+        } else {
+            self.codes.push("T");
+            index + 1
+        }
+    }
+
+    fn g(&mut self, index: isize) -> isize {
+        let next = self.at(index + 1);
+        if next == 'H' {
+            self.gh(index)
+        } else if next == 'N' {
+            if index == 1 && is_vowel(self.at(0)) && !self.slavo_germanic {
+                self.codes.push_both("KN", "N");
+            } else if !self.has(index + 2, &["EY"]) && !self.slavo_germanic {
+                self.codes.push_both("N", "KN");
+            } else {
+                self.codes.push("KN");
+            }
+            index + 2
+        } else if self.has(index + 1, &["LI"]) && !self.slavo_germanic {
+            // Tagliaro
+            self.codes.push_both("KL", "L");
+            index + 2
+        } else if self.g_is_kj(index) {
+            self.codes.push_both("K", "J");
+            index + 2
+        } else if self.has(index + 1, &["E", "I", "Y"]) || self.has(index - 1, &["AGGI", "OGGI"]) {
+            if self.has(0, &["VAN ", "VON ", "SCH"]) || self.has(index + 1, &["ET"]) {
+                // Germanic
+                self.codes.push("K");
+            } else if self.has(index + 1, &["IER"]) {
+                self.codes.push("J");
+            } else {
+                self.codes.push_both("J", "K");
+            }
+            index + 2
+        } else {
+            self.codes.push("K");
+            self.skip_double(index, 'G')
+        }
+    }
+
+    /// Whether G sounds like K or J, as in "Gerber", "Geyer" or "Gilbert".
+    fn g_is_kj(&self, index: isize) -> bool {
+        let next = self.at(index + 1);
+        if index == 0 {
+            return next == 'Y'
+                || self.has(
+                    index + 1,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                );
+        }
+        (self.has(index + 1, &["ER"]) || next == 'Y')
+            && !self.has(0, &["DANGER", "RANGER", "MANGER"])
+            && !self.has(index - 1, &["E", "I"])
+            && !self.has(index - 1, &["RGY", "OGY"])
+    }
+
+    fn gh(&mut self, index: isize) -> isize {
+        if index > 0 && !is_vowel(self.at(index - 1)) {
+            self.codes.push("K");
+        } else if index == 0 {
+            if self.at(index + 2) == 'I' {
+                self.codes.push("J");
+            } else {
+                self.codes.push("K");
+            }
+        } else if (index > 1 && self.has(index - 2, &["B", "H", "D"]))
+            || (index > 2 && self.has(index - 3, &["B", "H", "D"]))
+            || (index > 3 && self.has(index - 4, &["B", "H"]))
+        {
+            // Silent, as in Hugh
+        } else if index > 2
+            && self.at(index - 1) == 'U'
+            && self.has(index - 3, &["C", "G", "L", "R", "T"])
+        {
+            // Laugh, McLaughlin, cough, rough
+            self.codes.push("F");
+        } else if self.at(index - 1) != 'I' {
+            self.codes.push("K");
+        }
+        index + 2
+    }
+
+    fn h(&mut self, index: isize) -> isize {
+        // Kept only when first or after a vowel, and before a vowel
+        if (index == 0 || is_vowel(self.at(index - 1))) && is_vowel(self.at(index + 1)) {
+            self.codes.push("H");
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn j(&mut self, index: isize) -> isize {
+        if self.has(index, &["JOSE"]) || self.has(0, &["SAN "]) {
+            // Spanish, as in Jose or San Jacinto
+            if (index == 0 && self.at(index + 4) == ' ')
+                || self.len() == 4
+                || self.has(0, &["SAN "])
+            {
+                self.codes.push("H");
+            } else {
+                self.codes.push_both("J", "H");
+            }
+            return index + 1;
+        }
+        if index == 0 {
+            self.codes.push_both("J", "A");
+        } else if is_vowel(self.at(index - 1))
+            && !self.slavo_germanic
+            && matches!(self.at(index + 1), 'A' | 'O')
+        {
+            self.codes.push_both("J", "H");
+        } else if index == self.last() {
+            self.codes.push_both("J", "");
+        } else if !self.has(index + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.has(index - 1, &["S", "K", "L"])
+        {
+            self.codes.push("J");
+        }
+        self.skip_double(index, 'J')
+    }
+
+    fn l(&mut self, index: isize) -> isize {
+        if self.at(index + 1) != 'L' {
+            self.codes.push("L");
+            return index + 1;
+        }
+        // Spanish, as in Cabrillo or Gallegos
+        let spanish = (index == self.len() - 3 && self.has(index - 1, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.has(self.len() - 2, &["AS", "OS"]) || self.has(self.last(), &["A", "O"]))
+                && self.has(index - 1, &["ALLE"]));
+        if spanish {
+            self.codes.push_primary("L");
+        } else {
+            self.codes.push("L");
+        }
+        index + 2
+    }
+
+    /// Whether an M is doubled, or followed by a silent B as in "dumb".
+    fn m_is_double(&self, index: isize) -> bool {
+        self.at(index + 1) == 'M'
+            || (self.has(index - 1, &["UMB"])
+                && (index + 1 == self.last() || self.has(index + 2, &["ER"])))
+    }
+
+    fn p(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'H' {
+            self.codes.push("F");
+            index + 2
+        } else {
+            self.codes.push("P");
+            if self.has(index + 1, &["P", "B"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn r(&mut self, index: isize) -> isize {
+        if index == self.last()
+            && !self.slavo_germanic
+            && self.has(index - 2, &["IE"])
+            && !self.has(index - 4, &["ME", "MA"])
+        {
+            // French, as in Rogier
+            self.codes.push_alternate("R");
+        } else {
+            self.codes.push("R");
+        }
+        self.skip_double(index, 'R')
+    }
+
+    fn s(&mut self, index: isize) -> isize {
+        if self.has(index - 1, &["ISL", "YSL"]) {
+            // Island, Carlisle
+            index + 1
+        } else if index == 0 && self.has(index, &["SUGAR"]) {
+            self.codes.push_both("X", "S");
+            index + 1
+        } else if self.has(index, &["SH"]) {
+            if self.has(index + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                // Germanic
+                self.codes.push("S");
+            } else {
+                self.codes.push("X");
+            }
+            index + 2
+        } else if self.has(index, &["SIO", "SIA"]) {
+            // Italian and Armenian
+            if self.slavo_germanic {
+                self.codes.push("S");
+            } else {
+                self.codes.push_both("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.has(index + 1, &["M", "N", "L", "W"]))
+            || self.has(index + 1, &["Z"])
+        {
+            // Smith matching Schmidt, Snider matching Schneider, and the
+            // Slavic SZ
+            self.codes.push_both("S", "X");
+            if self.has(index + 1, &["Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        } else if self.has(index, &["SC"]) {
+            self.sc(index)
+        } else {
+            if index == self.last() && self.has(index - 2, &["AI", "OI"]) {
+                // French, as in Artois
+                self.codes.push_alternate("S");
+            } else {
+                self.codes.push("S");
+            }
+            if self.has(index + 1, &["S", "Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn sc(&mut self, index: isize) -> isize {
+        if self.at(index + 2) == 'H' {
+            if self.has(index + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // Dutch, as in Schooner and Schermerhorn
+                if self.has(index + 3, &["ER", "EN"]) {
+                    self.codes.push_both("X", "SK");
+                } else {
+                    self.codes.push("SK");
+                }
+            } else if index == 0 && !is_vowel(self.at(3)) && self.at(3) != 'W' {
+                self.codes.push_both("X", "S");
+            } else {
+                self.codes.push("X");
+            }
+        } else if self.has(index + 2, &["I", "E", "Y"]) {
+            self.codes.push("S");
+        } else {
+            self.codes.push("SK");
+        }
+        index + 3
+    }
+
+    fn t(&mut self, index: isize) -> isize {
+        if self.has(index, &["TION", "TIA", "TCH"]) {
+            self.codes.push("X");
+            index + 3
+        } else if self.has(index, &["TH", "TTH"]) {
+            if self.has(index + 2, &["OM", "AM"]) || self.has(0, &["VAN ", "VON ", "SCH"]) {
+                // Thomas, Thames, and Germanic
+                self.codes.push("T");
+            } else {
+                self.codes.push_both("0", "T");
+            }
+            index + 2
+        } else {
+            self.codes.push("T");
+            if self.has(index + 1, &["T", "D"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn w(&mut self, index: isize) -> isize {
+        if self.has(index, &["WR"]) {
+            self.codes.push("R");
+            return index + 2;
+        }
+        if index == 0 && (is_vowel(self.at(index + 1)) || self.has(index, &["WH"])) {
+            if is_vowel(self.at(index + 1)) {
+                // Wasserman matching Vasserman
+                self.codes.push_both("A", "F");
+            } else {
+                self.codes.push("A");
+            }
+            index + 1
+        } else if (index == self.last() && is_vowel(self.at(index - 1)))
+            || self.has(index - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.has(0, &["SCH"])
+        {
+            // Arnow matching Arnoff
+            self.codes.push_alternate("F");
+            index + 1
+        } else if self.has(index, &["WICZ", "WITZ"]) {
+            // Polish, as in Filipowicz
+            self.codes.push_both("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn x(&mut self, index: isize) -> isize {
+        if index == 0 {
+            self.codes.push("S");
+            return index + 1;
+        }
+        let silent = index == self.last()
+            && (self.has(index - 3, &["IAU", "EAU"]) || self.has(index - 2, &["AU", "OU"]));
+        if !silent {
+            // Not French, as in Breaux
+            self.codes.push("KS");
+        }
+        if self.has(index + 1, &["C", "X"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn z(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'H' {
+            // Chinese pinyin, as in Zhao
+            self.codes.push("J");
+            return index + 2;
+        }
+        if self.has(index + 1, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && index > 0 && self.at(index - 1) != 'T')
+        {
+            self.codes.push_both("S", "TS");
+        } else {
+            self.codes.push("S");
+        }
+        self.skip_double(index, 'Z')
+    }
+}
+
+/// Returns the primary and alternate Double Metaphone codes of `name`, of
+/// at most four characters each; `0` stands for the sound of "th". The two
+/// are the same when the name has one likely pronunciation, and empty if it
+/// has no letters.
+#[must_use]
+pub fn double_metaphone(name: &str) -> (String, String) {
+    let value = letters(name, &['Ç', 'ç', 'Ñ', 'ñ'], true)
+        .into_iter()
+        .map(|c| match c {
+            'ç' => 'Ç',
+            'ñ' => 'Ñ',
+            c => c,
+        })
+        .collect::<Vec<_>>();
+    let text: String = value.iter().collect();
+    let slavo_germanic = text.contains(['W', 'K']) || text.contains("CZ");
+    Metaphone {
+        value,
+        slavo_germanic,
+        codes: MetaphoneCodes {
+            primary: String::new(),
+            alternate: String::new(),
+        },
+    }
+    .encode()
+}
+
+/// The rules of Daitch–Mokotoff Soundex: a spelling and its codes at the
+/// start of a name, before a vowel and elsewhere. Alternatives are
+/// separated by `|`; an empty code is not coded. Longer spellings come
+/// before the shorter ones they start with.
+const DAITCH_MOKOTOFF_RULES: &[(&str, &str, &str, &str)] = &[
+    ("AI", "0", "1", ""),
+    ("AJ", "0", "1", ""),
+    ("AY", "0", "1", ""),
+    ("AU", "0", "7", ""),
+    ("A", "0", "", ""),
+    ("Ą", "", "", "6|"),
+    ("B", "7", "7", "7"),
+    ("CHS", "5", "54", "54"),
+    ("CH", "5|4", "5|4", "5|4"),
+    ("CK", "5|45", "5|45", "5|45"),
+    ("CSZ", "4", "4", "4"),
+    ("CZS", "4", "4", "4"),
+    ("CZ", "4", "4", "4"),
+    ("CS", "4", "4", "4"),
+    ("C", "5|4", "5|4", "5|4"),
+    ("DRZ", "4", "4", "4"),
+    ("DRS", "4", "4", "4"),
+    ("DSH", "4", "4", "4"),
+    ("DSZ", "4", "4", "4"),
+    ("DS", "4", "4", "4"),
+    ("DZH", "4", "4", "4"),
+    ("DZS", "4", "4", "4"),
+    ("DZ", "4", "4", "4"),
+    ("DT", "3", "3", "3"),
+    ("D", "3", "3", "3"),
+    ("EI", "0", "1", ""),
+    ("EJ", "0", "1", ""),
+    ("EY", "0", "1", ""),
+    ("EU", "1", "1", ""),
+    ("E", "0", "", ""),
+    ("Ę", "", "", "6|"),
+    ("FB", "7", "7", "7"),
+    ("F", "7", "7", "7"),
+    ("G", "5", "5", "5"),
+    ("H", "5", "5", ""),
+    ("IA", "1", "", ""),
+    ("IE", "1", "", ""),
+    ("IO", "1", "", ""),
+    ("IU", "1", "", ""),
+    ("I", "0", "", ""),
+    ("J", "1|4", "1|4", "1|4"),
+    ("KS", "5", "54", "54"),
+    ("KH", "5", "5", "5"),
+    ("K", "5", "5", "5"),
+    ("L", "8", "8", "8"),
+    ("MN", "66", "66", "66"),
+    ("M", "6", "6", "6"),
+    ("NM", "66", "66", "66"),
+    ("N", "6", "6", "6"),
+    ("OI", "0", "1", ""),
+    ("OJ", "0", "1", ""),
+    ("OY", "0", "1", ""),
+    ("O", "0", "", ""),
+    ("PF", "7", "7", "7"),
+    ("PH", "7", "7", "7"),
+    ("P", "7", "7", "7"),
+    ("Q", "5", "5", "5"),
+    ("RZ", "94|4", "94|4", "94|4"),
+    ("RS", "94|4", "94|4", "94|4"),
+    ("R", "9", "9", "9"),
+    ("SCHTSCH", "2", "4", "4"),
+    ("SCHTSH", "2", "4", "4"),
+    ("SCHTCH", "2", "4", "4"),
+    ("SCHT", "2", "43", "43"),
+    ("SCHD", "2", "43", "43"),
+    ("SCH", "4", "4", "4"),
+    ("SHTCH", "2", "4", "4"),
+    ("SHTSH", "2", "4", "4"),
+    ("SHCH", "2", "4", "4"),
+    ("SHT", "2", "43", "43"),
+    ("SHD", "2", "43", "43"),
+    ("SH", "4", "4", "4"),
+    ("STSCH", "2", "4", "4"),
+    ("STCH", "2", "4", "4"),
+    ("STRZ", "2", "4", "4"),
+    ("STRS", "2", "4", "4"),
+    ("STSH", "2", "4", "4"),
+    ("ST", "2", "43", "43"),
+    ("SC", "2", "4", "4"),
+    ("SZCZ", "2", "4", "4"),
+    ("SZCS", "2", "4", "4"),
+    ("SZT", "2", "43", "43"),
+    ("SZD", "2", "43", "43"),
+    ("SZ", "4", "4", "4"),
+    ("SD", "2", "43", "43"),
+    ("S", "4", "4", "4"),
+    ("TTSCH", "4", "4", "4"),
+    ("TTCH", "4", "4", "4"),
+    ("TTSZ", "4", "4", "4"),
+    ("TTS", "4", "4", "4"),
+    ("TTZ", "4", "4", "4"),
+    ("TCH", "4", "4", "4"),
+    ("TH", "3", "3", "3"),
+    ("TRZ", "4", "4", "4"),
+    ("TRS", "4", "4", "4"),
+    ("TSCH", "4", "4", "4"),
+    ("TSH", "4", "4", "4"),
+    ("TSZ", "4", "4", "4"),
+    ("TS", "4", "4", "4"),
+    ("TC", "4", "4", "4"),
+    ("TZS", "4", "4", "4"),
+    ("TZ", "4", "4", "4"),
+    ("T", "3", "3", "3"),
+    ("Ţ", "3|4", "3|4", "3|4"),
+    ("UI", "0", "1", ""),
+    ("UJ", "0", "1", ""),
+    ("UY", "0", "1", ""),
+    ("UE", "0", "", ""),
+    ("U", "0", "", ""),
+    ("V", "7", "7", "7"),
+    ("W", "7", "7", "7"),
+    ("X", "5", "54", "54"),
+    ("Y", "1", "", ""),
+    ("ZHDZH", "2", "4", "4"),
+    ("ZDZH", "2", "4", "4"),
+    ("ZDZ", "2", "4", "4"),
+    ("ZHD", "2", "43", "43"),
+    ("ZD", "2", "43", "43"),
+    ("ZSCH", "4", "4", "4"),
+    ("ZSH", "4", "4", "4"),
+    ("ZH", "4", "4", "4"),
+    ("ZS", "4", "4", "4"),
+    ("Z", "4", "4", "4"),
+];
+
+/// The length of Daitch–Mokotoff codes.
+const DAITCH_MOKOTOFF_LEN: usize = 6;
+
+/// Returns the Daitch–Mokotoff Soundex codes of `name`, six digits each,
+/// or none if it has no letters.
+///
+/// Spellings with two possible sounds, such as `CH` or `RZ`, give a code
+/// for each, so a name may have several codes; a name matches another when
+/// they share one.
+#[must_use]
+pub fn daitch_mokotoff(name: &str) -> Vec<String> {
+    let input = letters(name, &['Ą', 'ą', 'Ę', 'ę', 'Ţ', 'ţ'], false)
+        .into_iter()
+        .flat_map(char::to_uppercase)
+        .collect::<Vec<_>>();
+    if input.is_empty() {
+        return Vec::new();
+    }
+
+    // Each branch is a code so far and the last code appended to it
+    let mut branches: Vec<(String, &str)> = vec![(String::new(), "")];
+    let mut index = 0;
+    let mut last_char = '\0';
+    while index < input.len() {
+        let c = input[index];
+        let rule = DAITCH_MOKOTOFF_RULES.iter().find(|(spelling, ..)| {
+            let len = spelling.chars().count();
+            index + len <= input.len()
+                && input[index..index + len]
+                    .iter()
+                    .copied()
+                    .eq(spelling.chars())
+        });
+        let Some(&(spelling, start, before_vowel, other)) = rule else {
+            index += 1;
+            continue;
+        };
+        let len = spelling.chars().count();
+        let codes = if index == 0 {
+            start
+        } else if input
+            .get(index + len)
+            .is_some_and(|c| matches!(c, 'A' | 'E' | 'I' | 'O' | 'U'))
+        {
+            before_vowel
+        } else {
+            other
+        };
+        // M and N next to each other are both coded
+        let force = matches!((last_char, c), ('M', 'N') | ('N', 'M'));
+
+        let mut next: Vec<(String, &str)> = Vec::new();
+        for (code, last) in &branches {
+            for replacement in codes.split('|') {
+                let mut code = code.clone();
+                if (!last.ends_with(replacement) || force) && code.len() < DAITCH_MOKOTOFF_LEN {
+                    code.push_str(replacement);
+                    code.truncate(DAITCH_MOKOTOFF_LEN);
+                }
+                if !next.iter().any(|(known, _)| *known == code) {
+                    next.push((code, replacement));
+                }
+            }
+        }
+        branches = next;
+        last_char = c;
+        index += len;
+    }
+
+    branches
+        .into_iter()
+        .map(|(mut code, _)| {
+            while code.len() < DAITCH_MOKOTOFF_LEN {
+                code.push('0');
+            }
+            code
+        })
+        .fold(Vec::new(), |mut codes, code| {
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
+            codes
+        })
+}
+
+/// Returns true if the surnames of `a` and `b` sound alike, and so do their
+/// first given names if both have one.
+fn names_match(a: &Name, b: &Name, algorithm: Algorithm) -> bool {
+    let (a, b) = (a.parse_components(), b.parse_components());
+    let (Some(a_surname), Some(b_surname)) = (&a.surname, &b.surname) else {
+        return false;
+    };
+    if !algorithm.matches(a_surname, b_surname) {
+        return false;
+    }
+    let first = |given: &Option<String>| {
+        given
+            .as_deref()
+            .and_then(|given| given.split_whitespace().next())
+            .map(str::to_string)
+    };
+    match (first(&a.given), first(&b.given)) {
+        (Some(a_given), Some(b_given)) => algorithm.matches(&a_given, &b_given),
+        _ => true,
+    }
+}
+
+impl Individual {
+    /// Returns true if a name of this individual sounds like a name of
+    /// `other` under `algorithm`.
+    ///
+    /// Two names match when their surnames share a code and, if both have
+    /// given names, so do their first given names. The pieces of each name
+    /// come from [`Name::parse_components`]; names without a surname match
+    /// nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{names::phonetic::Algorithm, GedcomBuilder};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+    ///     0 @I1@ INDI\n1 NAME John /Schmidt/\n\
+    ///     0 @I2@ INDI\n1 NAME Jon /Smith/\n\
+    ///     0 @I3@ INDI\n1 NAME John /Jones/\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+    /// let [schmidt, smith, jones] = &data.individuals[..] else { panic!() };
+    ///
+    /// assert!(schmidt.name_matches(smith, Algorithm::DoubleMetaphone));
+    /// assert!(!smith.name_matches(jones, Algorithm::DoubleMetaphone));
+    /// ```
+    #[must_use]
+    pub fn name_matches(&self, other: &Individual, algorithm: Algorithm) -> bool {
+        self.names
+            .iter()
+            .any(|a| other.names.iter().any(|b| names_match(a, b, algorithm)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Rubin"), "R150");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Ashcroft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(soundex("Pfister"), "P236");
+        assert_eq!(soundex("Honeyman"), "H555");
+        assert_eq!(soundex("Lee"), "L000");
+        assert_eq!(soundex("Müller"), soundex("Muller"));
+        assert_eq!(soundex("O'Brien"), "O165");
+        assert_eq!(soundex(""), "");
+        assert_eq!(soundex("?"), "");
+    }
+
+    #[test]
+    fn test_double_metaphone() {
+        let pair = |primary: &str, alternate: &str| (primary.to_string(), alternate.to_string());
+        assert_eq!(double_metaphone("Smith"), pair("SM0", "XMT"));
+        assert_eq!(double_metaphone("Schmidt"), pair("XMT", "SMT"));
+        assert_eq!(double_metaphone("Thomas"), pair("TMS", "TMS"));
+        assert_eq!(double_metaphone("Jose"), pair("HS", "HS"));
+        assert_eq!(double_metaphone("Caesar"), pair("SSR", "SSR"));
+        assert_eq!(double_metaphone("Michael"), pair("MKL", "MXL"));
+        assert_eq!(double_metaphone("Bacchus"), pair("PKS", "PKS"));
+        assert_eq!(double_metaphone("Gallegos"), pair("KLKS", "KKS"));
+        assert_eq!(double_metaphone("Filipowicz"), pair("FLPT", "FLPF"));
+        assert_eq!(double_metaphone("Knight"), pair("NT", "NT"));
+        assert_eq!(double_metaphone("Laugh"), pair("LF", "LF"));
+        assert_eq!(double_metaphone("Breaux"), pair("PR", "PR"));
+        assert_eq!(double_metaphone("Zhao"), pair("J", "J"));
+        assert_eq!(double_metaphone("Czerny"), pair("SRN", "XRN"));
+        assert_eq!(double_metaphone("Wasserman"), pair("ASRM", "FSRM"));
+        assert_eq!(double_metaphone("François"), pair("FRNS", "FRNS"));
+        assert_eq!(double_metaphone(""), pair("", ""));
+    }
+
+    #[test]
+    fn test_daitch_mokotoff() {
+        assert_eq!(daitch_mokotoff("Moskowitz"), ["645740"]);
+        assert_eq!(daitch_mokotoff("Moskovitz"), ["645740"]);
+        assert_eq!(daitch_mokotoff("Auerbach"), ["097500", "097400"]);
+        assert_eq!(daitch_mokotoff("Ohrbach"), ["097500", "097400"]);
+        assert_eq!(daitch_mokotoff("Lipshitz"), ["874400"]);
+        assert_eq!(daitch_mokotoff("Lippszyc"), ["874500", "874400"]);
+        assert_eq!(daitch_mokotoff("Peters"), ["739400", "734000"]);
+        assert_eq!(
+            daitch_mokotoff("Jackson"),
+            ["154600", "145460", "454600", "445460"]
+        );
+        assert_eq!(daitch_mokotoff("Schwarzenegger"), ["479465", "474659"]);
+        assert!(daitch_mokotoff("").is_empty());
+    }
+
+    #[test]
+    fn test_algorithm_matches() {
+        assert!(Algorithm::Soundex.matches("Meyer", "Maier"));
+        assert!(Algorithm::DoubleMetaphone.matches("Snider", "Schneider"));
+        assert!(Algorithm::DaitchMokotoff.matches("Lipshitz", "Lippszyc"));
+        assert!(!Algorithm::DaitchMokotoff.matches("Lipshitz", "Peters"));
+        assert!(!Algorithm::Soundex.matches("", ""));
+        assert_eq!(Algorithm::DoubleMetaphone.codes("Thomas"), ["TMS"]);
+    }
+
+    #[test]
+    fn test_name_matches() {
+        use crate::GedcomBuilder;
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME Anna /Mayer/\n1 NAME Anna /Schmidt/\n2 TYPE MARRIED\n\
+            0 @I2@ INDI\n1 NAME Ana /Meier/\n\
+            0 @I3@ INDI\n1 NAME /Smith/\n\
+            0 @I4@ INDI\n1 NAME Otto /Meier/\n\
+            0 @I5@ INDI\n1 NAME Anna\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let [mayer, meier, smith, otto, no_surname] = &data.individuals[..] else {
+            panic!("expected five individuals");
+        };
+
+        assert!(mayer.name_matches(meier, Algorithm::Soundex));
+        // The married name matches, and a missing given name does not count
+        assert!(mayer.name_matches(smith, Algorithm::DoubleMetaphone));
+        assert!(!mayer.name_matches(otto, Algorithm::Soundex));
+        assert!(!mayer.name_matches(no_surname, Algorithm::Soundex));
+    }
+}
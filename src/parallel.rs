@@ -0,0 +1,135 @@
+//! Parallel parsing of large GEDCOM files.
+//!
+//! The input is split into level 0 records on one thread, the same way the
+//! [streaming parser](crate::stream) splits it, and runs of consecutive
+//! records are then parsed concurrently on the rayon thread pool, each
+//! record straight from its slice of the input. Records keep their original
+//! order, so the result is the same as a sequential parse.
+
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
+use crate::{
+    parser::TagHandlers,
+    report::{InvisibleCharacterPolicy, ReportCollector},
+    stream::{
+        parse_record_text_with_report, split_records, GedcomRecord, RecordSettings, RecordText,
+    },
+    types::GedcomData,
+    util::StringInterner,
+    GedcomError,
+};
+
+/// How many runs of records each thread gets, so that a thread that is done
+/// early can take on another.
+const RUNS_PER_THREAD: usize = 4;
+
+/// Parses GEDCOM text, spreading the records over the rayon thread pool.
+///
+/// Trailer problems are recorded in [`GedcomData::warnings`], as the
+/// sequential parser does. The threads share `interner`, if any.
+pub(crate) fn parse(
+    content: &str,
+    invisible_characters: InvisibleCharacterPolicy,
+    interner: Option<&Arc<StringInterner>>,
+) -> Result<GedcomData, GedcomError> {
+    let (records, warning) = split_records(content);
+
+    let Some((head, rest)) = records.split_first() else {
+        return Ok(GedcomData {
+            warnings: warning.into_iter().collect(),
+            ..GedcomData::default()
+        });
+    };
+
+    let settings = RecordSettings {
+        invisible_characters,
+        tag_handlers: &TagHandlers::default(),
+        interner,
+    };
+    // Each run of records gathers one report, merged in file order.
+    let parse_run = |run: &[RecordText], gedcom_7| {
+        let mut report = ReportCollector::default();
+        let records: Result<Vec<GedcomRecord>, GedcomError> = run
+            .iter()
+            .map(|record| {
+                parse_record_text_with_report(
+                    record.text,
+                    gedcom_7,
+                    &settings,
+                    record.line,
+                    record.tag_line,
+                    record.offset,
+                    &mut report,
+                )
+            })
+            .collect();
+        records.map(|records| (records, report))
+    };
+    let (mut records, mut report) = parse_run(std::slice::from_ref(head), false)?;
+    // The header decides how `@` is unescaped in every other record.
+    let gedcom_7 = matches!(&records[0], GedcomRecord::Header(header) if header.is_gedcom_7());
+
+    let run_len = rest
+        .len()
+        .div_ceil(rayon::current_num_threads() * RUNS_PER_THREAD)
+        .max(1);
+    let parsed: Vec<Result<(Vec<GedcomRecord>, ReportCollector), GedcomError>> = rest
+        .par_chunks(run_len)
+        .map(|run| parse_run(run, gedcom_7))
+        .collect();
+
+    // Report the first error in file order, not whichever thread failed first.
+    for result in parsed {
+        let (run, run_report) = result?;
+        records.extend(run);
+        report.merge(run_report);
+    }
+    let mut data: GedcomData = records.into_iter().collect();
+    data.warnings.extend(warning);
+    data.report = report.into_report();
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_parallel_parse_matches_sequential() {
+        for path in [
+            "tests/fixtures/simple.ged",
+            "tests/fixtures/sample.ged",
+            "tests/fixtures/washington.ged",
+            "tests/fixtures/allged.ged",
+        ] {
+            let content = std::fs::read_to_string(path).unwrap();
+            let sequential = GedcomBuilder::new().build_from_str(&content).unwrap();
+            let parallel = parse(&content, InvisibleCharacterPolicy::Report, None).unwrap();
+            assert_eq!(parallel, sequential, "{path}");
+        }
+
+        // The last record ends the input, without a trailer or a newline.
+        let content = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/";
+        let sequential = GedcomBuilder::new().build_from_str(content).unwrap();
+        let parallel = parse(content, InvisibleCharacterPolicy::Report, None).unwrap();
+        assert_eq!(parallel, sequential);
+        assert_eq!(
+            parallel.individuals[0].full_name().as_deref(),
+            Some("John Doe")
+        );
+    }
+
+    #[test]
+    fn test_parallel_parse_reports_first_error() {
+        let content = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n0 BOGUS\n0 ALSO\n0 TRLR\n";
+        let Err(GedcomError::ParseError { line, .. }) =
+            parse(content, InvisibleCharacterPolicy::Report, None)
+        else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(line, 5);
+    }
+}
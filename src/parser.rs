@@ -3,6 +3,8 @@
 //! This module provides the core parsing infrastructure for GEDCOM data.
 //! Functions are optimized with inline hints for performance-critical paths.
 
+use std::{fmt, sync::Arc};
+
 use crate::{
     tokenizer::{Token, Tokenizer, TokenizerTrait},
     types::custom::UserDefinedTag,
@@ -33,6 +35,59 @@ pub trait StreamParser {
     ) -> Result<(), GedcomError>;
 }
 
+/// Intercepts custom tags while parsing, so that they can be read into the
+/// application's own types instead of landing in the `custom_data` of the
+/// enclosing structure.
+///
+/// Handlers are registered with
+/// [`GedcomBuilder::tag_handler`](crate::GedcomBuilder::tag_handler) and see
+/// the custom tags of every structure, at any level, in file order. They take
+/// `&self`, so a handler that collects what it reads keeps it behind a lock
+/// and the application reads it back through its own `Arc` once the parse is
+/// done.
+pub trait TagHandler: Send + Sync {
+    /// Returns whether the handler takes custom structures with the tag `tag`,
+    /// such as `_MILT`.
+    fn handles(&self, tag: &str) -> bool;
+
+    /// Receives a custom structure the handler takes, with its
+    /// substructures, found at `level`.
+    ///
+    /// Returns the structure to keep it in the custom data as well, or
+    /// `None` to drop it.
+    fn handle(&self, tag: UserDefinedTag, level: u8) -> Option<UserDefinedTag>;
+}
+
+/// The tag handlers registered for a parse.
+#[derive(Clone, Default)]
+pub(crate) struct TagHandlers(Vec<Arc<dyn TagHandler>>);
+
+impl TagHandlers {
+    pub(crate) fn push(&mut self, handler: Arc<dyn TagHandler>) {
+        self.0.push(handler);
+    }
+
+    #[cfg(feature = "parallel")]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Offers `tag` to the first handler that takes it, and returns what is
+    /// left for the custom data.
+    pub(crate) fn intercept(&self, tag: UserDefinedTag, level: u8) -> Option<UserDefinedTag> {
+        match self.0.iter().find(|handler| handler.handles(&tag.tag)) {
+            Some(handler) => handler.handle(tag, level),
+            None => Some(tag),
+        }
+    }
+}
+
+impl fmt::Debug for TagHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TagHandlers({})", self.0.len())
+    }
+}
+
 /// Parses GEDCOM tokens at a specific hierarchical level, handling both standard and custom tags.
 ///
 /// This function processes tokens from the tokenizer until it encounters a token at or below
@@ -67,11 +122,10 @@ where
             }
             Token::CustomTag(tag) => {
                 let tag_clone = tag.clone();
-                non_standard_dataset.push(Box::new(UserDefinedTag::new(
-                    tokenizer,
-                    level + 1,
-                    &tag_clone,
-                )?));
+                let custom = UserDefinedTag::new(tokenizer, level + 1, &tag_clone)?;
+                if let Some(custom) = tokenizer.intercept(custom, level + 1) {
+                    non_standard_dataset.push(Box::new(custom));
+                }
             }
             Token::Level(_) => tokenizer.next_token()?,
             Token::LineValue(_) => {
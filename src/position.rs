@@ -0,0 +1,184 @@
+//! Where each record and structure sits in the source file.
+//!
+//! With
+//! [`GedcomBuilder::track_positions`](crate::GedcomBuilder::track_positions)
+//! enabled, the parser keeps a [`SourceSpan`](crate::position::SourceSpan)
+//! for every level 0 record in [`GedcomData::positions`], in file order. A
+//! span holds the first and last line of its structure, `CONC` and `CONT`
+//! lines included, and the spans of the substructures beneath it, so that a
+//! validator or an editor can point at the exact lines of a date or a name.
+//! Line numbers count every physical line of the input from 1, blank ones
+//! included.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1 JAN 1900\n2 NOTE Born at\n3 CONT home\n0 TRLR";
+//! let data = GedcomBuilder::new().track_positions(true).build_from_str(source).unwrap();
+//!
+//! let individual = data.position("@I1@").unwrap();
+//! assert_eq!((individual.line, individual.end_line), (4, 9));
+//! assert_eq!(individual.find("BIRT/DATE").unwrap().line, 7);
+//! assert_eq!(individual.find("BIRT/NOTE").unwrap().end_line, 9);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lines::RawLine,
+    report::is_invisible,
+    stream::{split_records, RecordText},
+    types::GedcomData,
+};
+
+/// The lines of one structure of the source file and of its substructures.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SourceSpan {
+    /// The tag of the structure, such as `BIRT`.
+    pub tag: String,
+    /// The xref of the structure as written in the file, if it has one.
+    pub xref: Option<String>,
+    /// The line number of the first line of the structure.
+    pub line: u32,
+    /// The line number of the last line of the structure, substructures
+    /// included.
+    pub end_line: u32,
+    /// The spans of the substructures, in file order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub children: Vec<SourceSpan>,
+}
+
+impl SourceSpan {
+    /// Returns the span of the substructure at `path`, a list of tags
+    /// separated by `/`, such as `BIRT/DATE`.
+    ///
+    /// A tag picks its first occurrence; `TAG[n]` picks the one at index
+    /// `n` among the substructures with that tag, so `NAME[1]/GIVN` is the
+    /// given name of the second name.
+    #[must_use]
+    pub fn find(&self, path: &str) -> Option<&SourceSpan> {
+        path.split('/').try_fold(self, |span, step| {
+            let (tag, index) = match step.strip_suffix(']').and_then(|s| s.split_once('[')) {
+                Some((tag, index)) => (tag, index.parse().ok()?),
+                None => (step, 0),
+            };
+            span.children
+                .iter()
+                .filter(|child| child.tag == tag)
+                .nth(index)
+        })
+    }
+
+    /// Returns whether `line` is one of the lines of the structure.
+    #[must_use]
+    pub fn contains(&self, line: u32) -> bool {
+        (self.line..=self.end_line).contains(&line)
+    }
+}
+
+/// Returns the spans of the records of GEDCOM text, stopping at the trailer.
+///
+/// Lines that are not well-formed GEDCOM lines are left out.
+pub(crate) fn spans(content: &str) -> Vec<SourceSpan> {
+    let (records, _) = split_records(content);
+    records.iter().filter_map(record_span).collect()
+}
+
+/// Returns the span of one record and its substructures.
+fn record_span(record: &RecordText) -> Option<SourceSpan> {
+    // The open structures, outermost first.
+    let mut open: Vec<SourceSpan> = Vec::new();
+    for (text, number) in record.text.lines().zip(record.line..) {
+        let text = text.trim_start_matches(|c: char| c.is_whitespace() || is_invisible(c));
+        let Ok(line) = RawLine::parse(text, number) else {
+            continue;
+        };
+        if matches!(line.tag, "CONC" | "CONT") {
+            if let Some(last) = open.last_mut() {
+                last.end_line = number;
+            }
+            continue;
+        }
+        close(&mut open, usize::from(line.level).max(1));
+        open.push(SourceSpan {
+            tag: line.tag.to_string(),
+            xref: line.xref.map(str::to_string),
+            line: number,
+            end_line: number,
+            children: Vec::new(),
+        });
+    }
+    close(&mut open, 1);
+    open.pop()
+}
+
+/// Closes the open structures deeper than `depth`, adding each to the one
+/// that holds it.
+fn close(open: &mut Vec<SourceSpan>, depth: usize) {
+    while open.len() > depth {
+        let Some(span) = open.pop() else {
+            return;
+        };
+        if let Some(parent) = open.last_mut() {
+            parent.end_line = parent.end_line.max(span.end_line);
+            parent.children.push(span);
+        }
+    }
+}
+
+impl GedcomData {
+    /// Returns the span of the record with `xref` in the source file.
+    ///
+    /// Returns `None` if the data was parsed without
+    /// [`track_positions`](crate::GedcomBuilder::track_positions) or no
+    /// record has the xref.
+    #[must_use]
+    pub fn position(&self, xref: &str) -> Option<&SourceSpan> {
+        self.positions
+            .iter()
+            .find(|span| span.xref.as_deref() == Some(xref))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spans() {
+        let content = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\n\
+            0 @I1@ INDI\n1 NAME A /B/\n2 GIVN A\n1 NAME C /D/\n2 GIVN C\n\
+            3 _X deep\n1 NOTE one\n2 CONC two\n0 TRLR\n";
+        let spans = spans(content);
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[0].line, spans[0].end_line), (1, 3));
+
+        let individual = &spans[1];
+        assert_eq!(individual.xref.as_deref(), Some("@I1@"));
+        assert_eq!((individual.line, individual.end_line), (5, 12));
+        let given = individual.find("NAME[1]/GIVN").unwrap();
+        assert_eq!((given.line, given.end_line), (9, 10));
+        assert_eq!(given.children[0].tag, "_X");
+        let note = individual.find("NOTE").unwrap();
+        assert_eq!((note.line, note.end_line), (11, 12));
+        assert!(note.children.is_empty());
+        assert!(individual.find("NAME[2]").is_none());
+        assert!(individual.find("NAME[x]").is_none());
+        assert!(individual.contains(8) && !individual.contains(13));
+    }
+
+    #[test]
+    fn test_spans_with_level_jumps() {
+        let spans = spans("0 @F1@ FAM\n2 HUSB @I1@\n1 CHIL @I2@\n0 TRLR");
+        let tags: Vec<&str> = spans[0].children.iter().map(|c| c.tag.as_str()).collect();
+        assert_eq!(tags, ["HUSB", "CHIL"]);
+    }
+}
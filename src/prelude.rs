@@ -0,0 +1,85 @@
+//! The types most programs need, and one-call parsing.
+//!
+//! `use ged_io::prelude::*;` brings in the builder, writer, data and error
+//! types, and the [`ParseGedcom`](crate::prelude::ParseGedcom) trait, which
+//! parses text, bytes or a file with the default settings of
+//! [`GedcomBuilder`]. Use the builder directly to change them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::prelude::*;
+//!
+//! # fn main() -> Result<(), GedcomError> {
+//! let data = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR".parse_gedcom()?;
+//! assert_eq!(data.individuals.len(), 1);
+//!
+//! let data = std::path::Path::new("tests/fixtures/simple.ged").parse_gedcom()?;
+//! assert!(!data.individuals.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+
+pub use crate::{
+    types::{family::Family, individual::Individual, GedcomData},
+    GedcomBuilder, GedcomError, GedcomWriter,
+};
+
+/// Parses GEDCOM data with the default settings of [`GedcomBuilder`].
+pub trait ParseGedcom {
+    /// Parses `self` as GEDCOM data.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error the builder returns for the same input.
+    fn parse_gedcom(&self) -> Result<GedcomData, GedcomError>;
+}
+
+impl ParseGedcom for str {
+    /// Parses GEDCOM text, as [`GedcomBuilder::build_from_str`] does.
+    fn parse_gedcom(&self) -> Result<GedcomData, GedcomError> {
+        GedcomBuilder::new().build_from_str(self)
+    }
+}
+
+impl ParseGedcom for [u8] {
+    /// Parses GEDCOM bytes in any supported encoding, as
+    /// [`GedcomBuilder::build_from_bytes`] does.
+    fn parse_gedcom(&self) -> Result<GedcomData, GedcomError> {
+        GedcomBuilder::new().build_from_bytes(self)
+    }
+}
+
+impl ParseGedcom for Path {
+    /// Reads and parses a GEDCOM file, as [`GedcomBuilder::build_from_path`]
+    /// does.
+    fn parse_gedcom(&self) -> Result<GedcomData, GedcomError> {
+        GedcomBuilder::new().build_from_path(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_gedcom_sources() {
+        let text = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Ann /Roe/\n0 TRLR";
+        let from_str = text.parse_gedcom().unwrap();
+        let from_string = text.to_string().parse_gedcom().unwrap();
+        let from_bytes = text.as_bytes().parse_gedcom().unwrap();
+        assert_eq!(from_str.individuals, from_string.individuals);
+        assert_eq!(from_str.individuals, from_bytes.individuals);
+
+        let path = PathBuf::from("tests/fixtures/simple.ged");
+        let from_path = path.parse_gedcom().unwrap();
+        let built = GedcomBuilder::new().build_from_path(&path).unwrap();
+        assert_eq!(from_path.individuals, built.individuals);
+
+        let missing = Path::new("tests/fixtures/missing.ged").parse_gedcom();
+        assert!(matches!(missing, Err(GedcomError::InFile { .. })));
+    }
+}
@@ -0,0 +1,318 @@
+//! Hiding the details of living people.
+//!
+//! Family trees usually include people who are still alive, whose details
+//! should not be published. [`GedcomData::privatize`] finds the individuals
+//! who are probably living and removes their events, facts, notes, media
+//! and citations, and those of their marriages, while keeping the family
+//! links that hold the tree together. Names are reduced as the
+//! [`PrivacyPolicy`](crate::privacy::PrivacyPolicy) asks.
+//!
+//! Someone is considered living unless the file records their death,
+//! burial, cremation or probate, or their earliest dated event is more than
+//! [`living_years`](crate::privacy::PrivacyPolicy::living_years) years ago.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{privacy::PrivacyPolicy, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1850\n1 FAMC @F1@\n\
+//!     0 @I2@ INDI\n1 NAME Jane /Doe/\n1 BIRT\n2 DATE 1990\n2 PLAC Leeds\n1 FAMC @F1@\n\
+//!     0 @F1@ FAM\n1 CHIL @I1@\n1 CHIL @I2@\n0 TRLR";
+//! let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let privatized = data.privatize(PrivacyPolicy::default());
+//! assert_eq!(privatized, vec!["@I2@".to_string()]);
+//!
+//! let jane = &data.individuals[1];
+//! assert_eq!(jane.full_name().as_deref(), Some("Living Doe"));
+//! assert!(jane.events.is_empty());
+//! assert_eq!(jane.families[0].xref, "@F1@");
+//! assert_eq!(data.individuals[0].full_name().as_deref(), Some("John Doe"));
+//! ```
+
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    statistics::summary::year_of,
+    types::{
+        event::Event,
+        family::Family,
+        individual::{name::Name, Individual},
+        GedcomData,
+    },
+};
+
+/// The given name that replaces the names of living individuals.
+pub const LIVING_NAME: &str = "Living";
+
+/// How much of a living individual's name is kept.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NamePrivacy {
+    /// Keep the name unchanged.
+    Keep,
+    /// Keep the surname and replace the rest with [`LIVING_NAME`], as in
+    /// `Living /Doe/` (the default).
+    #[default]
+    KeepSurname,
+    /// Replace the whole name with [`LIVING_NAME`].
+    Replace,
+}
+
+/// Which individuals [`GedcomData::privatize`] treats as living, and how
+/// their names are redacted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PrivacyPolicy {
+    /// How many years after their earliest dated event, usually the birth,
+    /// someone may still be alive (default: 100).
+    pub living_years: i32,
+    /// The year to count from (default: `None`, the current year).
+    pub reference_year: Option<i32>,
+    /// Whether someone with no dated event and no record of death is
+    /// considered living (default: true).
+    pub assume_living_without_dates: bool,
+    /// How the names of living individuals are redacted.
+    pub names: NamePrivacy,
+}
+
+impl Default for PrivacyPolicy {
+    fn default() -> Self {
+        Self {
+            living_years: 100,
+            reference_year: None,
+            assume_living_without_dates: true,
+            names: NamePrivacy::default(),
+        }
+    }
+}
+
+impl PrivacyPolicy {
+    /// Returns true if the individual is probably living under this policy.
+    #[must_use]
+    pub fn is_living(&self, individual: &Individual) -> bool {
+        let dead = individual.events.iter().any(|e| {
+            matches!(
+                e.event,
+                Event::Death | Event::Burial | Event::Cremation | Event::Probate
+            )
+        });
+        if dead {
+            return false;
+        }
+        let earliest = individual
+            .birth_date()
+            .and_then(year_of)
+            .or_else(|| earliest_year(individual));
+        match earliest {
+            Some(year) => {
+                year > self.reference_year.unwrap_or_else(current_year) - self.living_years
+            }
+            None => self.assume_living_without_dates,
+        }
+    }
+}
+
+impl GedcomData {
+    /// Redacts the individuals that `policy` considers living, and the
+    /// families they are a spouse in, and returns the xrefs of those
+    /// individuals.
+    ///
+    /// Their events, attributes, citations, media, notes, LDS ordinances,
+    /// extensions, identifiers and record dates are removed, their name is
+    /// redacted per [`PrivacyPolicy::names`], and their restriction notice is
+    /// set to `privacy`. Their sex and their family and association links are
+    /// kept.
+    /// The families lose their events, citations, media and notes.
+    pub fn privatize(&mut self, policy: PrivacyPolicy) -> Vec<String> {
+        let mut living = Vec::new();
+        for individual in &mut self.individuals {
+            if policy.is_living(individual) {
+                redact(individual, policy.names);
+                living.extend(individual.xref.clone());
+            }
+        }
+        for family in &mut self.families {
+            let spouse_living = [&family.individual1, &family.individual2]
+                .into_iter()
+                .flatten()
+                .any(|xref| living.contains(xref));
+            if spouse_living {
+                redact_family(family);
+            }
+        }
+        living
+    }
+}
+
+/// Returns the year of the individual's earliest dated event or attribute.
+fn earliest_year(individual: &Individual) -> Option<i32> {
+    let events = individual.events.iter().map(|e| e.date.as_ref());
+    let attributes = individual.attributes.iter().map(|a| a.date.as_ref());
+    events
+        .chain(attributes)
+        .filter_map(|date| year_of(date?.value.as_deref()?))
+        .min()
+}
+
+fn current_year() -> i32 {
+    // Seconds in an average Gregorian year.
+    let years = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() / 31_556_952);
+    i32::try_from(years).map_or(i32::MAX, |years| 1970 + years)
+}
+
+fn redact(individual: &mut Individual, names: NamePrivacy) {
+    let surname = individual.surname_or_parsed().map(Arc::<str>::from);
+    match names {
+        NamePrivacy::Keep => {}
+        NamePrivacy::KeepSurname if surname.is_some() => {
+            individual.names = vec![Name {
+                value: surname.as_ref().map(|s| format!("{LIVING_NAME} /{s}/")),
+                given: Some(LIVING_NAME.to_string()),
+                surname,
+                ..Name::default()
+            }];
+        }
+        NamePrivacy::KeepSurname | NamePrivacy::Replace => {
+            individual.names = vec![Name {
+                value: Some(LIVING_NAME.to_string()),
+                given: Some(LIVING_NAME.to_string()),
+                ..Name::default()
+            }];
+        }
+    }
+
+    individual.events.clear();
+    individual.attributes.clear();
+    individual.source.clear();
+    individual.multimedia.clear();
+    individual.notes.clear();
+    individual.lds_ordinances.clear();
+    individual.non_events.clear();
+    individual.custom_data.clear();
+    individual.external_ids.clear();
+    individual.uid = None;
+    individual.vendor_uids.clear();
+    individual.user_reference_numbers.clear();
+    individual.automated_record_id = None;
+    individual.ancestral_file_number = None;
+    individual.change_date = None;
+    individual.creation_date = None;
+    individual.last_updated = None;
+    if let Some(ref mut sex) = individual.sex {
+        sex.sources.clear();
+    }
+    for link in &mut individual.families {
+        link.notes.clear();
+    }
+    for association in &mut individual.associations {
+        association.notes.clear();
+        association.citations.clear();
+    }
+    individual.restriction = Some("privacy".to_string());
+}
+
+fn redact_family(family: &mut Family) {
+    family.events.clear();
+    family.family_event.clear();
+    family.sources.clear();
+    family.multimedia.clear();
+    family.notes.clear();
+    family.lds_ordinances.clear();
+    family.non_events.clear();
+    family.associations.clear();
+    family.custom_data.clear();
+    family.change_date = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GedcomBuilder, GedcomWriter};
+
+    const SAMPLE: &str = "\
+        0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+        0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1950\n1 DEAT\n1 FAMS @F1@\n\
+        0 @I2@ INDI\n1 NAME Ann /Roe/\n1 OCCU Weaver\n2 DATE 1975\n1 NOTE Private\n1 FAMS @F1@\n\
+        1 REFN 42\n1 RIN 7\n1 AFN 9ABC-DE\n1 _UID 0123456789ABCDEF\n1 CREA\n2 DATE 1 JAN 2020\n\
+        0 @I3@ INDI\n1 NAME Jim\n1 FAMC @F1@\n\
+        0 @I4@ INDI\n1 NAME Old /Doe/\n1 CHR\n2 DATE ABT 1890\n\
+        0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n1 CHIL @I3@\n1 MARR\n2 DATE 1974\n\
+        0 TRLR";
+
+    #[test]
+    fn test_is_living() {
+        let data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let policy = PrivacyPolicy {
+            reference_year: Some(2024),
+            ..PrivacyPolicy::default()
+        };
+        let living: Vec<bool> = data
+            .individuals
+            .iter()
+            .map(|i| policy.is_living(i))
+            .collect();
+        assert_eq!(living, vec![false, true, true, false]);
+
+        let policy = PrivacyPolicy {
+            reference_year: Some(2024),
+            living_years: 40,
+            assume_living_without_dates: false,
+            ..PrivacyPolicy::default()
+        };
+        let living: Vec<bool> = data
+            .individuals
+            .iter()
+            .map(|i| policy.is_living(i))
+            .collect();
+        assert_eq!(living, vec![false, false, false, false]);
+    }
+
+    #[test]
+    fn test_privatize() {
+        let mut data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let policy = PrivacyPolicy {
+            reference_year: Some(2024),
+            names: NamePrivacy::Replace,
+            ..PrivacyPolicy::default()
+        };
+        assert_eq!(data.privatize(policy), vec!["@I2@", "@I3@"]);
+
+        let ann = &data.individuals[1];
+        assert_eq!(ann.full_name().as_deref(), Some(LIVING_NAME));
+        assert!(ann.attributes.is_empty());
+        assert!(ann.notes.is_empty());
+        assert_eq!(ann.restriction.as_deref(), Some("privacy"));
+        assert_eq!(ann.families.len(), 1);
+        assert!(ann.user_reference_numbers.is_empty());
+        assert!(ann.automated_record_id.is_none());
+        assert!(ann.ancestral_file_number.is_none());
+        assert!(ann.vendor_uids.is_empty());
+        assert!(ann.creation_date.is_none());
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("0 @I2@ INDI\n1 RESN privacy\n"));
+        for tag in ["REFN", "RIN", "AFN", "_UID", "CREA"] {
+            assert!(!output.contains(tag), "{tag} in {output}");
+        }
+
+        let family = &data.families[0];
+        assert!(family.events.is_empty());
+        assert_eq!(family.individual2.as_deref(), Some("@I2@"));
+        assert_eq!(family.children, vec!["@I3@".to_string()]);
+
+        assert_eq!(data.individuals[0].events.len(), 2);
+        assert_eq!(data.individuals[0].full_name().as_deref(), Some("John Doe"));
+    }
+}
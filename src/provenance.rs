@@ -0,0 +1,246 @@
+//! Tracing where each fact about an individual came from.
+//!
+//! GEDCOM keeps provenance in a few places: the header names the file and the
+//! producing system, each record may carry a `CHAN` date, and each fact may
+//! cite sources. This module gathers those into one
+//! [`FactProvenance`](crate::provenance::FactProvenance) entry per fact, so
+//! that users can audit where a piece of information came from.
+//!
+//! When the same individual appears in several files (e.g. before merging or
+//! while diffing), [`change_history`](crate::provenance::change_history)
+//! lists the facts contributed by every file, ordered by change date.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n1 FILE family.ged\n0 @I1@ INDI\n1 BIRT\n2 DATE 1 JAN 1900\n2 SOUR @S1@\n1 CHAN\n2 DATE 3 MAR 2020\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let facts = data.fact_provenance("@I1@");
+//! assert_eq!(facts[0].tag, "BIRT");
+//! assert_eq!(facts[0].origin.as_deref(), Some("family.ged"));
+//! assert_eq!(facts[0].changed.as_deref(), Some("3 MAR 2020"));
+//! assert_eq!(facts[0].sources, vec!["@S1@".to_string()]);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::{
+        date::change_date::ChangeDate, individual::Individual, source::citation::Citation,
+        GedcomData,
+    },
+    writer::{attribute_to_tag, event_to_tag},
+};
+
+/// Where a single fact about an individual came from.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FactProvenance {
+    /// The GEDCOM tag of the fact (e.g. `NAME`, `BIRT`, `OCCU`).
+    pub tag: String,
+    /// The fact's value, or its date for events without a value.
+    pub value: Option<String>,
+    /// The file that contributed the fact: `HEAD.FILE`, else the producing system from `HEAD.SOUR`.
+    pub origin: Option<String>,
+    /// Position of the contributing dataset in the slice given to [`change_history`].
+    ///
+    /// Always 0 for [`GedcomData::fact_provenance`].
+    pub dataset: usize,
+    /// The last change date (`CHAN`) of the record holding the fact, with its time if present.
+    pub changed: Option<String>,
+    /// Sources cited for the fact, either pointers or inline source descriptions.
+    pub sources: Vec<String>,
+}
+
+impl GedcomData {
+    /// Lists every fact recorded on an individual together with its provenance.
+    ///
+    /// Facts are returned in record order: name, sex, events, then attributes.
+    /// Returns an empty list if no individual has the given xref.
+    #[must_use]
+    pub fn fact_provenance(&self, xref: &str) -> Vec<FactProvenance> {
+        self.find_individual(xref)
+            .map(|individual| individual_facts(individual, self.origin(), 0))
+            .unwrap_or_default()
+    }
+
+    /// Returns the name of the file this data came from, as declared in the header.
+    fn origin(&self) -> Option<&str> {
+        let header = self.header.as_ref()?;
+        header.filename.as_deref().or_else(|| {
+            header
+                .source
+                .as_ref()
+                .and_then(|s| s.name.as_deref().or(s.value.as_deref()))
+        })
+    }
+}
+
+/// Builds the change history of one individual across several datasets.
+///
+/// Facts from every dataset that contains `xref` are listed, oldest change
+/// first. Facts whose record has no change date come first, in dataset order.
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::{provenance::change_history, GedcomBuilder};
+///
+/// let old = "0 HEAD\n1 GEDC\n2 VERS 5.5\n1 FILE old.ged\n0 @I1@ INDI\n1 OCCU Farmer\n1 CHAN\n2 DATE 1 JAN 2001\n0 TRLR";
+/// let new = "0 HEAD\n1 GEDC\n2 VERS 5.5\n1 FILE new.ged\n0 @I1@ INDI\n1 OCCU Miller\n1 CHAN\n2 DATE 5 FEB 1999\n0 TRLR";
+/// let datasets = [
+///     GedcomBuilder::new().build_from_str(old).unwrap(),
+///     GedcomBuilder::new().build_from_str(new).unwrap(),
+/// ];
+///
+/// let history = change_history(&datasets, "@I1@");
+/// assert_eq!(history[0].origin.as_deref(), Some("new.ged"));
+/// assert_eq!(history[1].value.as_deref(), Some("Farmer"));
+/// ```
+#[must_use]
+pub fn change_history(datasets: &[GedcomData], xref: &str) -> Vec<FactProvenance> {
+    let mut history: Vec<FactProvenance> = datasets
+        .iter()
+        .enumerate()
+        .filter_map(|(i, data)| {
+            data.find_individual(xref)
+                .map(|individual| individual_facts(individual, data.origin(), i))
+        })
+        .flatten()
+        .collect();
+
+    history.sort_by_key(|fact| fact.changed.as_deref().map(change_sort_key));
+    history
+}
+
+fn individual_facts(
+    individual: &Individual,
+    origin: Option<&str>,
+    dataset: usize,
+) -> Vec<FactProvenance> {
+    let changed = individual.change_date.as_ref().and_then(change_date_text);
+    let fact = |tag: &str, value: Option<&str>, citations: &[Citation]| FactProvenance {
+        tag: tag.to_string(),
+        value: value.map(str::to_string),
+        origin: origin.map(str::to_string),
+        dataset,
+        changed: changed.clone(),
+        sources: citations.iter().map(|c| c.xref.clone()).collect(),
+    };
+
+    let mut facts = Vec::new();
+
+    for name in &individual.names {
+        facts.push(fact("NAME", name.value.as_deref(), &name.source));
+    }
+
+    if let Some(ref sex) = individual.sex {
+        let value = sex.value.to_string();
+        facts.push(fact("SEX", Some(&value), &sex.sources));
+    }
+
+    for event in &individual.events {
+        let value = event
+            .value
+            .as_payload()
+            .or_else(|| event.date.as_ref().and_then(|d| d.value.as_deref()));
+        facts.push(fact(event_to_tag(&event.event), value, &event.citations));
+    }
+
+    for attr in &individual.attributes {
+        facts.push(fact(
+            attribute_to_tag(&attr.attribute),
+            attr.value.as_deref(),
+            &attr.sources,
+        ));
+    }
+
+    facts
+}
+
+/// Formats a `CHAN` date and its optional time as a single string.
+fn change_date_text(change_date: &ChangeDate) -> Option<String> {
+    let date = change_date.date.as_ref()?;
+    let value = date.value.as_deref()?;
+    Some(match date.time {
+        Some(ref time) => format!("{value} {time}"),
+        None => value.to_string(),
+    })
+}
+
+/// Turns a `CHAN` date such as `3 MAR 2020 10:15:00` into a sortable key.
+///
+/// Unrecognized parts sort as zero, so malformed dates keep their relative order.
+fn change_sort_key(text: &str) -> (i32, u8, u8, String) {
+    let mut parts = text.split_whitespace();
+    let day = parts.next().and_then(|d| d.parse().ok()).unwrap_or(0);
+    let month = parts.next().map_or(0, month_number);
+    let year = parts.next().and_then(|y| y.parse().ok()).unwrap_or(0);
+    let time = parts.next().unwrap_or_default().to_string();
+    (year, month, day, time)
+}
+
+fn month_number(month: &str) -> u8 {
+    const MONTHS: [&str; 12] = [
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    MONTHS
+        .iter()
+        .zip(1..)
+        .find(|(m, _)| m.eq_ignore_ascii_case(month))
+        .map_or(0, |(_, n)| n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_fact_provenance_lists_all_facts() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5\n\
+            1 SOUR MyTree\n\
+            2 NAME My Tree App\n\
+            0 @I1@ INDI\n\
+            1 NAME John /Doe/\n\
+            2 SOUR @S2@\n\
+            1 SEX M\n\
+            1 DEAT Y\n\
+            1 OCCU Blacksmith\n\
+            2 SOUR Census 1900\n\
+            1 CHAN\n\
+            2 DATE 3 MAR 2020\n\
+            3 TIME 10:15:00\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let facts = data.fact_provenance("@I1@");
+        let tags: Vec<&str> = facts.iter().map(|f| f.tag.as_str()).collect();
+        assert_eq!(tags, vec!["NAME", "SEX", "DEAT", "OCCU"]);
+        assert_eq!(facts[0].sources, vec!["@S2@".to_string()]);
+        assert_eq!(facts[2].value.as_deref(), Some("Y"));
+        assert_eq!(facts[3].sources, vec!["Census 1900".to_string()]);
+        assert!(facts
+            .iter()
+            .all(|f| f.origin.as_deref() == Some("My Tree App")));
+        assert!(facts
+            .iter()
+            .all(|f| f.changed.as_deref() == Some("3 MAR 2020 10:15:00")));
+
+        assert!(data.fact_provenance("@MISSING@").is_empty());
+    }
+
+    #[test]
+    fn test_change_sort_key() {
+        assert!(change_sort_key("2 JAN 2001") > change_sort_key("31 DEC 2000"));
+        assert!(change_sort_key("1 FEB 2001") > change_sort_key("28 JAN 2001"));
+        assert!(change_sort_key("1 FEB 2001 10:00:00") > change_sort_key("1 FEB 2001 09:59"));
+    }
+}
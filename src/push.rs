@@ -0,0 +1,287 @@
+//! Push-based parsing of GEDCOM text that arrives in chunks.
+//!
+//! [`GedcomPushParser`](crate::push::GedcomPushParser) is for input the
+//! caller receives rather than reads, such as the body of an HTTP request
+//! or the messages of a socket. Each [`feed`](crate::push::GedcomPushParser::feed)
+//! takes the next chunk of bytes, whatever its size, and returns the records
+//! it completed; partial lines and the record being read are buffered until
+//! the rest arrives. [`finish`](crate::push::GedcomPushParser::finish) ends
+//! the input and returns the last record. Like the
+//! [streaming parser](crate::GedcomStreamParser), it requires UTF-8 input.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{push::GedcomPushParser, stream::GedcomRecord};
+//!
+//! # fn main() -> Result<(), ged_io::GedcomError> {
+//! let mut parser = GedcomPushParser::new();
+//! let mut records = Vec::new();
+//! for chunk in ["0 HEAD\n1 GEDC\n2 VE", "RS 5.5\n0 @I1@ INDI\n1 NA", "ME John /Doe/\n0 TRLR\n"] {
+//!     records.extend(parser.feed(chunk.as_bytes())?);
+//! }
+//! records.extend(parser.finish()?);
+//!
+//! assert_eq!(records.len(), 2);
+//! assert!(matches!(&records[1], GedcomRecord::Individual(i) if i.xref.as_deref() == Some("@I1@")));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    stream::{end_of_input, parse_record_text, GedcomRecord},
+    tokenizer::{is_trailer_line, is_trailer_padding},
+    GedcomError, ParseWarning,
+};
+
+/// A GEDCOM parser that is fed the input a chunk at a time.
+///
+/// Records are returned in file order, the header first. After an error the
+/// parser stops: later calls return no records.
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct GedcomPushParser {
+    /// Bytes of the line not yet complete
+    pending: Vec<u8>,
+    /// Text of the record being accumulated
+    record_buffer: String,
+    /// Lines read so far
+    line_number: u32,
+    /// Whether the input ended or an error stopped the parser
+    finished: bool,
+    /// Whether the header declared GEDCOM 7.0, which changes `@` unescaping
+    gedcom_7: bool,
+    /// Whether the `0 TRLR` line has been read
+    trailer_seen: bool,
+    /// Whether trailer problems are errors rather than warnings
+    strict: bool,
+    /// Recoverable problems found so far
+    warnings: Vec<ParseWarning>,
+}
+
+impl GedcomPushParser {
+    /// Creates a parser waiting for its first chunk.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables strict mode.
+    ///
+    /// Trailer problems are handled as in
+    /// [`GedcomStreamParser::strict_mode`](crate::GedcomStreamParser::strict_mode).
+    #[must_use]
+    pub fn strict_mode(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Returns the recoverable problems found so far.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Takes the next chunk of input and returns the records it completed.
+    ///
+    /// A record is complete once the line starting the next one, or the
+    /// trailer, has arrived.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if a line is not valid UTF-8 or a record
+    /// fails to parse, and in strict mode for content after the trailer.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<GedcomRecord>, GedcomError> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        // The pending bytes hold no line break, so only the new ones need
+        // searching; rescanning a long line on every chunk is quadratic.
+        let scanned = self.pending.len();
+        self.pending.extend_from_slice(chunk);
+
+        let mut records = Vec::new();
+        let Some(last) = self.pending[scanned..]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map(|i| scanned + i)
+        else {
+            return Ok(records);
+        };
+        let lines: Vec<u8> = self.pending.drain(..=last).collect();
+        for line in lines.split_inclusive(|&b| b == b'\n') {
+            self.push_line(line, &mut records)
+                .inspect_err(|_| self.finished = true)?;
+            if self.finished {
+                break;
+            }
+        }
+        Ok(records)
+    }
+
+    /// Ends the input and returns the records it completed: the last
+    /// record, unless the trailer already closed it.
+    ///
+    /// A missing trailer is recorded in the [`warnings`](Self::warnings).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if the last line is not valid UTF-8 or the
+    /// last record fails to parse, and in strict mode for a missing trailer
+    /// or content after it.
+    pub fn finish(&mut self) -> Result<Vec<GedcomRecord>, GedcomError> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        let mut records = Vec::new();
+        let mut line = std::mem::take(&mut self.pending);
+        if !line.is_empty() {
+            line.push(b'\n');
+        }
+        let result = self.push_line(&line, &mut records).and_then(|()| {
+            if self.finished {
+                return Ok(());
+            }
+            if !self.record_buffer.is_empty() {
+                let record = std::mem::take(&mut self.record_buffer);
+                records.push(self.parse(&record)?);
+            }
+            end_of_input(
+                Some(ParseWarning::MissingTrailer),
+                self.strict,
+                &mut self.warnings,
+            )
+        });
+        self.finished = true;
+        result.map(|()| records)
+    }
+
+    /// Adds one line, with its terminator, to the record being read.
+    fn push_line(
+        &mut self,
+        bytes: &[u8],
+        records: &mut Vec<GedcomRecord>,
+    ) -> Result<(), GedcomError> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        self.line_number += 1;
+        let line = match std::str::from_utf8(bytes) {
+            Ok(line) => line,
+            // Binary junk after the trailer is content like any other.
+            Err(_) if self.trailer_seen => "\u{FFFD}",
+            Err(_) => {
+                return Err(GedcomError::EncodingError(format!(
+                    "Push parser requires UTF-8 input; invalid UTF-8 at line {}",
+                    self.line_number
+                )))
+            }
+        };
+
+        if self.trailer_seen {
+            if !line.chars().all(is_trailer_padding) {
+                self.finished = true;
+                let warning = ParseWarning::ContentAfterTrailer {
+                    line: self.line_number,
+                };
+                end_of_input(Some(warning), self.strict, &mut self.warnings)?;
+            }
+            return Ok(());
+        }
+
+        let line = if self.line_number == 1 {
+            line.strip_prefix('\u{FEFF}').unwrap_or(line)
+        } else {
+            line
+        };
+        let trimmed = line.trim_start();
+        let starts_record =
+            trimmed.starts_with('0') && trimmed[1..].starts_with(char::is_whitespace);
+        if starts_record && !self.record_buffer.is_empty() {
+            let record = std::mem::take(&mut self.record_buffer);
+            records.push(self.parse(&record)?);
+        }
+        if starts_record && is_trailer_line(line) {
+            self.trailer_seen = true;
+        } else {
+            self.record_buffer.push_str(line);
+        }
+        Ok(())
+    }
+
+    /// Parses the text of one record.
+    fn parse(&mut self, text: &str) -> Result<GedcomRecord, GedcomError> {
+        let record = parse_record_text(text, self.gedcom_7, self.line_number)?;
+        if let GedcomRecord::Header(header) = &record {
+            self.gedcom_7 = header.is_gedcom_7();
+        }
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::GedcomData, GedcomBuilder};
+
+    fn push(content: &[u8], chunk_size: usize) -> Result<GedcomData, GedcomError> {
+        let mut parser = GedcomPushParser::new();
+        let mut records = Vec::new();
+        for chunk in content.chunks(chunk_size) {
+            records.extend(parser.feed(chunk)?);
+        }
+        records.extend(parser.finish()?);
+        let mut data: GedcomData = records.into_iter().collect();
+        data.warnings = parser.warnings().to_vec();
+        Ok(data)
+    }
+
+    #[test]
+    fn test_push_parser_matches_builder() {
+        let content = std::fs::read_to_string("tests/fixtures/sample.ged").unwrap();
+        let expected = GedcomBuilder::new().build_from_str(&content).unwrap();
+        for chunk_size in [1, 7, 4096] {
+            let data = push(content.as_bytes(), chunk_size).unwrap();
+            assert_eq!(data.individuals, expected.individuals, "{chunk_size}");
+            assert_eq!(data.families, expected.families, "{chunk_size}");
+            assert_eq!(data.header, expected.header, "{chunk_size}");
+        }
+    }
+
+    #[test]
+    fn test_push_parser_split_characters_and_trailer() {
+        let content = "\u{FEFF}0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME Zoë /Brontë/\n0 TRLR\n\nstray";
+        let data = push(content.as_bytes(), 1).unwrap();
+        assert_eq!(
+            data.individuals[0].full_name().as_deref(),
+            Some("Zoë Brontë")
+        );
+        assert_eq!(
+            data.warnings,
+            vec![ParseWarning::ContentAfterTrailer { line: 8 }]
+        );
+
+        let data = push(b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI", 5).unwrap();
+        assert_eq!(data.individuals.len(), 1);
+        assert_eq!(data.warnings, vec![ParseWarning::MissingTrailer]);
+    }
+
+    #[test]
+    fn test_push_parser_stops_after_error() {
+        let mut parser = GedcomPushParser::new().strict_mode(true);
+        assert!(parser
+            .feed(b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 BOGUS\n")
+            .is_ok());
+        assert!(matches!(
+            parser.feed(b"0 @I1@ INDI\n"),
+            Err(GedcomError::ParseError { .. })
+        ));
+        assert!(parser.feed(b"0 @I2@ INDI\n").unwrap().is_empty());
+        assert!(parser.finish().unwrap().is_empty());
+
+        let mut parser = GedcomPushParser::new().strict_mode(true);
+        parser.feed(b"0 HEAD\n1 GEDC\n2 VERS 5.5\n").unwrap();
+        assert!(parser.finish().is_err());
+        assert!(parser.feed(&[0xFF, b'\n']).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+//! The source text of each record, kept for debugging and audits.
+//!
+//! With [`GedcomBuilder::keep_raw`](crate::GedcomBuilder::keep_raw) enabled,
+//! the parser keeps the text of every level 0 record in
+//! [`GedcomData::raw_records`], in file order, with the lines it spans. The
+//! text is exactly what the file said, before any
+//! [dialect](crate::dialect) fixes, so it can be shown next to the parsed
+//! record or written out again unchanged.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Doe/\n1 _COLOR blue\n0 TRLR\n";
+//! let data = GedcomBuilder::new().keep_raw(true).build_from_str(source).unwrap();
+//!
+//! let raw = data.raw_record("@I1@").unwrap();
+//! assert_eq!(raw.tag, "INDI");
+//! assert_eq!((raw.line, raw.end_line), (4, 6));
+//! assert_eq!(raw.text, "0 @I1@ INDI\n1 NAME John /Doe/\n1 _COLOR blue\n");
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{report::is_invisible, stream::split_records, types::GedcomData};
+
+/// The source text of one level 0 record.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RawRecord {
+    /// The tag of the record, such as `INDI`.
+    pub tag: String,
+    /// The xref of the record as written in the file, if it has one.
+    pub xref: Option<String>,
+    /// The line number of the first line of the record.
+    pub line: u32,
+    /// The line number of the last line of the record.
+    pub end_line: u32,
+    /// The offset in bytes of the record in the decoded text.
+    pub offset: usize,
+    /// The text of the record, line endings included.
+    pub text: String,
+}
+
+impl RawRecord {
+    /// Returns the lines of the record, without their line endings.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+}
+
+/// Splits GEDCOM text into the raw text of its records, stopping at the
+/// trailer.
+pub(crate) fn records(content: &str) -> Vec<RawRecord> {
+    let (texts, _) = split_records(content);
+    texts
+        .into_iter()
+        .map(|record| {
+            let mut fields = record
+                .text
+                .trim_start_matches(|c: char| c.is_whitespace() || is_invisible(c))
+                .split_whitespace()
+                .skip(1);
+            let mut tag = fields.next().unwrap_or_default();
+            let mut xref = None;
+            if tag.starts_with('@') {
+                xref = Some(tag.to_string());
+                tag = fields.next().unwrap_or_default();
+            }
+            let lines = u32::try_from(record.text.lines().count()).unwrap_or(1);
+            RawRecord {
+                tag: tag.to_string(),
+                xref,
+                line: record.line,
+                end_line: record.line + lines - 1,
+                offset: record.offset,
+                text: record.text.to_string(),
+            }
+        })
+        .collect()
+}
+
+impl GedcomData {
+    /// Returns the source text of the record with `xref`, as written in the
+    /// file.
+    ///
+    /// Returns `None` if the data was parsed without
+    /// [`keep_raw`](crate::GedcomBuilder::keep_raw) or no record has the
+    /// xref.
+    #[must_use]
+    pub fn raw_record(&self, xref: &str) -> Option<&RawRecord> {
+        self.raw_records
+            .iter()
+            .find(|record| record.xref.as_deref() == Some(xref))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_records() {
+        let content = "\u{FEFF}0 HEAD\r\n1 GEDC\r\n\r\n0 @N1@ NOTE A note\r\n\
+            0 _PLAC Paris\r\n0 TRLR\r\n";
+        let records = records(content);
+
+        let tags: Vec<(&str, Option<&str>)> = records
+            .iter()
+            .map(|r| (r.tag.as_str(), r.xref.as_deref()))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![("HEAD", None), ("NOTE", Some("@N1@")), ("_PLAC", None)]
+        );
+        assert_eq!((records[0].line, records[0].end_line), (1, 3));
+        assert_eq!(records[1].offset, 21);
+        assert_eq!(records[1].text, "0 @N1@ NOTE A note\r\n");
+        assert_eq!(
+            records[1].lines().collect::<Vec<_>>(),
+            ["0 @N1@ NOTE A note"]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Recovering from malformed records.
+//!
+//! The input is split into level 0 records, as the
+//! [streaming parser](crate::stream) splits it, and each is parsed on its
+//! own. A record that fails to parse is left out and recorded in
+//! [`GedcomData::warnings`] as a [`ParseWarning::SkippedRecord`] with its
+//! text, its lines and the error, and parsing goes on with the next one.
+
+use crate::{
+    report::ReportCollector,
+    stream::{parse_record_text_with_report, split_records, GedcomRecord, RecordSettings},
+    types::GedcomData,
+    ParseWarning,
+};
+
+/// Parses GEDCOM text, skipping the records that fail to parse.
+pub(crate) fn parse(content: &str, settings: &RecordSettings) -> GedcomData {
+    let (texts, trailer) = split_records(content);
+
+    let mut records = Vec::with_capacity(texts.len());
+    let mut skipped = Vec::new();
+    let mut report = ReportCollector::default();
+    let mut gedcom_7 = false;
+    for record in &texts {
+        let end_line = record.line + u32::try_from(record.text.lines().count()).unwrap_or(1) - 1;
+        // Gathered apart, so that skipped records are left out of the report.
+        let mut record_report = ReportCollector::default();
+        match parse_record_text_with_report(
+            record.text,
+            gedcom_7,
+            settings,
+            end_line,
+            record.tag_line,
+            record.offset,
+            &mut record_report,
+        ) {
+            Ok(parsed) => {
+                // The header decides how `@` is unescaped in every other record.
+                if let GedcomRecord::Header(header) = &parsed {
+                    gedcom_7 = header.is_gedcom_7();
+                }
+                records.push(parsed);
+                report.merge(record_report);
+            }
+            Err(error) => skipped.push(ParseWarning::SkippedRecord {
+                line: record.line,
+                end_line,
+                text: record.text.to_string(),
+                error: error.to_string(),
+            }),
+        }
+    }
+
+    let mut data: GedcomData = records.into_iter().collect();
+    data.warnings = skipped;
+    data.warnings.extend(trailer);
+    data.report = report.into_report();
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::TagHandlers, report::InvisibleCharacterPolicy};
+
+    #[test]
+    fn test_recover_skips_bad_records() {
+        let content = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n\
+            0 @X1@ BOGUS\n1 NOTE lost\n\
+            0 @I2@ INDI\n1 NAME Jane /Doe/\n0 TRLR\n";
+        let data = parse(
+            content,
+            &RecordSettings {
+                invisible_characters: InvisibleCharacterPolicy::Report,
+                tag_handlers: &TagHandlers::default(),
+                interner: None,
+            },
+        );
+
+        assert_eq!(data.individuals.len(), 2);
+        assert_eq!(data.individuals[1].xref.as_deref(), Some("@I2@"));
+        let [ParseWarning::SkippedRecord {
+            line,
+            end_line,
+            text,
+            error,
+        }] = &data.warnings[..]
+        else {
+            panic!("expected one skipped record, got {:?}", data.warnings);
+        };
+        assert_eq!((*line, *end_line), (6, 7));
+        assert_eq!(text, "0 @X1@ BOGUS\n1 NOTE lost\n");
+        assert!(error.contains("Unhandled tag BOGUS"), "{error}");
+    }
+}
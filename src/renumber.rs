@@ -0,0 +1,381 @@
+//! Renumbering and allocating xrefs.
+//!
+//! Files that went through several programs, or were merged from several
+//! sources, end up with xrefs such as `@I1@`, `@P102@` and `@I1_2@` side by
+//! side. [`GedcomData::renumber_xrefs`] gives every record a new xref from
+//! an [`XrefScheme`](crate::renumber::XrefScheme), numbered in file order
+//! within each record type, and rewrites every pointer to match.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{renumber::XrefScheme, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @P7@ INDI\n1 NAME John /Doe/\n1 FAMS @FAM3@\n\
+//!     0 @P2@ INDI\n1 NAME Jane /Roe/\n1 FAMS @FAM3@\n\
+//!     0 @FAM3@ FAM\n1 HUSB @P7@\n1 WIFE @P2@\n0 TRLR";
+//! let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let renamed = data.renumber_xrefs(&XrefScheme::default());
+//! assert_eq!(renamed["@P7@"], "@I1@");
+//! assert_eq!(renamed["@FAM3@"], "@F1@");
+//!
+//! let family = &data.families[0];
+//! assert_eq!(family.individual1.as_deref(), Some("@I1@"));
+//! assert_eq!(family.individual2.as_deref(), Some("@I2@"));
+//! ```
+//!
+//! Code that adds records asks for xrefs no record uses yet, following the
+//! same scheme: [`GedcomData::new_individual_xref`] and its siblings for one
+//! record, or an [`XrefAllocator`](crate::renumber::XrefAllocator) for many.
+//!
+//! ```rust
+//! use ged_io::{renumber::{XrefAllocator, XrefScheme}, types::RecordType, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n0 @I3@ INDI\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//! assert_eq!(data.new_individual_xref(), "@I2@");
+//!
+//! let mut allocator = XrefAllocator::new(&data, XrefScheme::default());
+//! assert_eq!(allocator.next(RecordType::Individual), "@I2@");
+//! assert_eq!(allocator.next(RecordType::Individual), "@I4@");
+//! assert_eq!(allocator.next(RecordType::Family), "@F1@");
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    copy::Record,
+    types::{GedcomData, RecordType},
+};
+
+/// The xrefs [`GedcomData::renumber_xrefs`] assigns: a prefix per record
+/// type followed by a number, as in `@I1@`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct XrefScheme {
+    /// Prefix of individual xrefs (default: `I`).
+    pub individual: String,
+    /// Prefix of family xrefs (default: `F`).
+    pub family: String,
+    /// Prefix of source xrefs (default: `S`).
+    pub source: String,
+    /// Prefix of repository xrefs (default: `R`).
+    pub repository: String,
+    /// Prefix of multimedia xrefs (default: `M`).
+    pub multimedia: String,
+    /// Prefix of shared note xrefs (default: `N`).
+    pub shared_note: String,
+    /// Prefix of submitter xrefs (default: `U`).
+    pub submitter: String,
+    /// Prefix of submission xrefs (default: `SUBN`).
+    pub submission: String,
+    /// The number of the first record of each type (default: 1).
+    pub start: u32,
+}
+
+impl Default for XrefScheme {
+    fn default() -> Self {
+        Self {
+            individual: "I".to_string(),
+            family: "F".to_string(),
+            source: "S".to_string(),
+            repository: "R".to_string(),
+            multimedia: "M".to_string(),
+            shared_note: "N".to_string(),
+            submitter: "U".to_string(),
+            submission: "SUBN".to_string(),
+            start: 1,
+        }
+    }
+}
+
+impl XrefScheme {
+    /// Returns the prefix for records of the given type, or `None` for
+    /// custom records, which keep their xrefs.
+    #[must_use]
+    pub fn prefix(&self, record_type: RecordType) -> Option<&str> {
+        let prefix = match record_type {
+            RecordType::Individual => &self.individual,
+            RecordType::Family => &self.family,
+            RecordType::Source => &self.source,
+            RecordType::Repository => &self.repository,
+            RecordType::Multimedia => &self.multimedia,
+            RecordType::SharedNote => &self.shared_note,
+            RecordType::Submitter => &self.submitter,
+            RecordType::Submission => &self.submission,
+            RecordType::Custom => return None,
+        };
+        Some(prefix)
+    }
+}
+
+/// Hands out xrefs that no record of a dataset uses, following an
+/// [`XrefScheme`].
+///
+/// Each type counts up from [`XrefScheme::start`], skipping the xrefs in
+/// use when the allocator was made and the ones it handed out since.
+#[derive(Clone, Debug)]
+pub struct XrefAllocator {
+    scheme: XrefScheme,
+    taken: HashSet<String>,
+    next: HashMap<RecordType, u32>,
+}
+
+impl XrefAllocator {
+    /// Creates an allocator that avoids the xrefs of the records of `data`.
+    #[must_use]
+    pub fn new(data: &GedcomData, scheme: XrefScheme) -> Self {
+        Self {
+            scheme,
+            taken: data.xrefs_in_use(),
+            next: HashMap::new(),
+        }
+    }
+
+    /// Returns a new xref for a record of the given type. Custom records
+    /// get the prefix `X`.
+    pub fn next(&mut self, record_type: RecordType) -> String {
+        let prefix = self.scheme.prefix(record_type).unwrap_or("X");
+        let n = self.next.entry(record_type).or_insert(self.scheme.start);
+        loop {
+            let candidate = format!("@{prefix}{n}@");
+            *n += 1;
+            if self.taken.insert(candidate.clone()) {
+                return candidate;
+            }
+        }
+    }
+}
+
+impl GedcomData {
+    /// Returns the first xref of `scheme` for the given record type that no
+    /// record uses.
+    ///
+    /// Add the record before asking for the next xref, or the same one is
+    /// returned again; an [`XrefAllocator`] hands out several at once.
+    #[must_use]
+    pub fn new_xref(&self, record_type: RecordType, scheme: &XrefScheme) -> String {
+        XrefAllocator::new(self, scheme.clone()).next(record_type)
+    }
+
+    /// Returns an unused individual xref such as `@I12@`; see
+    /// [`new_xref`](Self::new_xref).
+    #[must_use]
+    pub fn new_individual_xref(&self) -> String {
+        self.new_xref(RecordType::Individual, &XrefScheme::default())
+    }
+
+    /// Returns an unused family xref such as `@F12@`.
+    #[must_use]
+    pub fn new_family_xref(&self) -> String {
+        self.new_xref(RecordType::Family, &XrefScheme::default())
+    }
+
+    /// Returns an unused source xref such as `@S12@`.
+    #[must_use]
+    pub fn new_source_xref(&self) -> String {
+        self.new_xref(RecordType::Source, &XrefScheme::default())
+    }
+
+    /// Returns an unused repository xref such as `@R12@`.
+    #[must_use]
+    pub fn new_repository_xref(&self) -> String {
+        self.new_xref(RecordType::Repository, &XrefScheme::default())
+    }
+
+    /// Returns an unused multimedia xref such as `@M12@`.
+    #[must_use]
+    pub fn new_multimedia_xref(&self) -> String {
+        self.new_xref(RecordType::Multimedia, &XrefScheme::default())
+    }
+
+    /// Returns an unused shared note xref such as `@N12@`.
+    #[must_use]
+    pub fn new_shared_note_xref(&self) -> String {
+        self.new_xref(RecordType::SharedNote, &XrefScheme::default())
+    }
+
+    /// Returns an unused submitter xref such as `@U12@`.
+    #[must_use]
+    pub fn new_submitter_xref(&self) -> String {
+        self.new_xref(RecordType::Submitter, &XrefScheme::default())
+    }
+
+    /// Gives every record a new xref following `scheme`, rewrites the
+    /// pointers to them, and returns the new xref of each record keyed by
+    /// its old one.
+    ///
+    /// Records are numbered in file order within each type. No two records
+    /// end up with the same xref: a number whose xref another type already
+    /// took, because two prefixes overlap, is skipped. Records that shared
+    /// an xref get one each, and pointers go to the first of them, which is
+    /// the one the map lists. Pointers to records that do not exist are
+    /// removed, so that they cannot point at a renumbered record by chance.
+    pub fn renumber_xrefs(&mut self, scheme: &XrefScheme) -> BTreeMap<String, String> {
+        let mut numbering = Numbering {
+            scheme,
+            taken: HashSet::new(),
+            renamed: HashMap::new(),
+        };
+        numbering.number(&mut self.submitters, RecordType::Submitter);
+        numbering.number(&mut self.submissions, RecordType::Submission);
+        numbering.number(&mut self.individuals, RecordType::Individual);
+        numbering.number(&mut self.families, RecordType::Family);
+        numbering.number(&mut self.repositories, RecordType::Repository);
+        numbering.number(&mut self.sources, RecordType::Source);
+        numbering.number(&mut self.multimedia, RecordType::Multimedia);
+        numbering.number(&mut self.shared_notes, RecordType::SharedNote);
+
+        let renamed = numbering.renamed;
+        let mut rewrite = |record_type: RecordType, link: &mut String| match renamed
+            .get(&(record_type, link.clone()))
+        {
+            Some(new) => {
+                link.clone_from(new);
+                true
+            }
+            None => false,
+        };
+        self.visit_all_links(&mut rewrite);
+
+        if let Some(ref mut header) = self.header {
+            for (pointer, record_type) in [
+                (&mut header.submitter_tag, RecordType::Submitter),
+                (&mut header.submission_tag, RecordType::Submission),
+            ] {
+                if let Some(ref mut xref) = pointer {
+                    if !rewrite(record_type, xref) {
+                        *pointer = None;
+                    }
+                }
+            }
+        }
+
+        let mut map = BTreeMap::new();
+        for ((_, old), new) in renamed {
+            map.entry(old).or_insert(new);
+        }
+        map
+    }
+}
+
+/// The xrefs handed out so far by [`GedcomData::renumber_xrefs`].
+struct Numbering<'a> {
+    scheme: &'a XrefScheme,
+    taken: HashSet<String>,
+    /// The new xref of the first record of each type with each old xref.
+    renamed: HashMap<(RecordType, String), String>,
+}
+
+impl Numbering<'_> {
+    fn number<T: Record>(&mut self, records: &mut [T], record_type: RecordType) {
+        let prefix = self.scheme.prefix(record_type).unwrap_or_default();
+        let mut n = self.scheme.start;
+        for record in records {
+            let Some(old) = record.xref().map(str::to_string) else {
+                continue;
+            };
+            let new = loop {
+                let candidate = format!("@{prefix}{n}@");
+                n += 1;
+                if self.taken.insert(candidate.clone()) {
+                    break candidate;
+                }
+            };
+            self.renamed
+                .entry((record_type, old))
+                .or_insert_with(|| new.clone());
+            *record.xref_mut() = Some(new);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const SAMPLE: &str = "\
+        0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 SUBM @SUB9@\n\
+        0 @SUB9@ SUBM\n1 NAME Submitter\n\
+        0 @X5@ INDI\n1 NAME John /Doe/\n1 FAMS @X1@\n1 SOUR @X2@\n1 ASSO @X9@\n2 RELA Friend\n\
+        0 @I1@ INDI\n1 NAME Jim /Doe/\n1 FAMC @X1@\n\
+        0 @X1@ FAM\n1 HUSB @X5@\n1 CHIL @I1@\n\
+        0 @X2@ SOUR\n1 TITL Register\n1 REPO @R7@\n\
+        0 @R7@ REPO\n1 NAME Archive\n\
+        0 TRLR";
+
+    #[test]
+    fn test_renumber_rewrites_every_pointer() {
+        let mut data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let renamed = data.renumber_xrefs(&XrefScheme::default());
+        assert_eq!(
+            renamed.into_iter().collect::<Vec<_>>(),
+            [
+                ("@I1@", "@I2@"),
+                ("@R7@", "@R1@"),
+                ("@SUB9@", "@U1@"),
+                ("@X1@", "@F1@"),
+                ("@X2@", "@S1@"),
+                ("@X5@", "@I1@"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+
+        let john = &data.individuals[0];
+        assert_eq!(john.families[0].xref, "@F1@");
+        assert_eq!(john.source[0].xref, "@S1@");
+        // The association pointed at no record.
+        assert!(john.associations.is_empty());
+        let family = &data.families[0];
+        assert_eq!(family.individual1.as_deref(), Some("@I1@"));
+        assert_eq!(family.children, vec!["@I2@".to_string()]);
+        assert_eq!(data.sources[0].repo_citations[0].xref, "@R1@");
+        let header = data.header.as_ref().unwrap();
+        assert_eq!(header.submitter_tag.as_deref(), Some("@U1@"));
+    }
+
+    #[test]
+    fn test_renumber_avoids_collisions() {
+        let mut data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let scheme = XrefScheme {
+            family: "I".to_string(),
+            start: 0,
+            ..XrefScheme::default()
+        };
+        data.renumber_xrefs(&scheme);
+        let xrefs: Vec<&str> = data
+            .individuals
+            .iter()
+            .filter_map(|i| i.xref.as_deref())
+            .chain(data.families.iter().filter_map(|f| f.xref.as_deref()))
+            .collect();
+        assert_eq!(xrefs, vec!["@I0@", "@I1@", "@I2@"]);
+        assert_eq!(data.families[0].children, vec!["@I1@".to_string()]);
+    }
+
+    #[test]
+    fn test_allocate_xrefs() {
+        let data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        // @I1@ is taken by an individual, @X1@ and @X2@ by other types.
+        assert_eq!(data.new_individual_xref(), "@I2@");
+        assert_eq!(data.new_family_xref(), "@F1@");
+
+        let scheme = XrefScheme {
+            individual: "X".to_string(),
+            ..XrefScheme::default()
+        };
+        assert_eq!(data.new_xref(RecordType::Individual, &scheme), "@X3@");
+        let mut allocator = XrefAllocator::new(&data, scheme);
+        let xrefs: Vec<String> = (0..3)
+            .map(|_| allocator.next(RecordType::Individual))
+            .collect();
+        assert_eq!(xrefs, vec!["@X3@", "@X4@", "@X6@"]);
+        assert_eq!(allocator.next(RecordType::Custom), "@X7@");
+    }
+}
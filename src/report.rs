@@ -0,0 +1,353 @@
+//! Statistics gathered while parsing.
+//!
+//! The tokenizer counts every tag it reads, so a parsed file carries a
+//! [`ParseReport`](crate::report::ParseReport) in
+//! [`GedcomData::report`](crate::types::GedcomData::report) at no extra pass
+//! over the input. [`tag_frequencies`](crate::report::ParseReport::tag_frequencies)
+//! lists the tags most frequent first, with the first lines each one appears
+//! on, which shows at a glance which vendor extensions and unknown tags a
+//! new file relies on.
+//!
+//! The trailer is not counted.
+//!
+//! Files stitched together from several exports often carry stray byte order
+//! marks and zero-width characters, which make `NAME\u{200B}` a different tag
+//! from `NAME` and two spellings of a surname compare unequal. The tokenizer
+//! lists each one in
+//! [`invisible_characters`](crate::report::ParseReport::invisible_characters)
+//! with its line and byte offset, and with
+//! [`InvisibleCharacterPolicy::Strip`](crate::report::InvisibleCharacterPolicy::Strip)
+//! also removes them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{report::TagKind, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME John /Doe/\n1 _UID 1234\n\
+//!     0 @I2@ INDI\n1 NAME Jane /Doe/\n1 _UID 5678\n\
+//!     1 _MILT\n2 UNIT Navy\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let unknown = data.report.unknown_tags();
+//! assert_eq!(unknown.len(), 3);
+//! assert_eq!(unknown[0].tag, "_UID");
+//! assert_eq!(unknown[0].kind, TagKind::Extension);
+//! assert_eq!(unknown[0].count, 2);
+//! assert_eq!(unknown[0].lines, vec![6, 9]);
+//! assert_eq!(unknown[2].tag, "UNIT");
+//! assert_eq!(unknown[2].kind, TagKind::Unknown);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::cmp::Reverse;
+
+use crate::intern::Interner;
+
+/// How many lines [`TagFrequency::lines`] keeps as examples.
+pub const MAX_EXAMPLE_LINES: usize = 3;
+
+/// The tags defined by GEDCOM 5.5, 5.5.1 or 7.0, sorted.
+const STANDARD_TAGS: &[&str] = &[
+    "ABBR", "ADDR", "ADOP", "ADR1", "ADR2", "ADR3", "AFN", "AGE", "AGNC", "ALIA", "ANCE", "ANCI",
+    "ANUL", "ASSO", "AUTH", "BAPL", "BAPM", "BARM", "BASM", "BIRT", "BLES", "BLOB", "BURI", "CALN",
+    "CAST", "CAUS", "CENS", "CHAN", "CHAR", "CHIL", "CHR", "CHRA", "CITY", "CONC", "CONF", "CONL",
+    "CONT", "COPR", "CORP", "CREA", "CREM", "CROP", "CTRY", "DATA", "DATE", "DEAT", "DESC", "DESI",
+    "DEST", "DIV", "DIVF", "DSCR", "EDUC", "EMAIL", "EMIG", "ENDL", "ENGA", "EVEN", "EXID", "FACT",
+    "FAM", "FAMC", "FAMF", "FAMS", "FAX", "FCOM", "FILE", "FONE", "FORM", "GEDC", "GIVN", "GRAD",
+    "HEAD", "HEIGHT", "HUSB", "IDNO", "IMMI", "INDI", "INIL", "LANG", "LATI", "LEFT", "LONG",
+    "MAP", "MARB", "MARC", "MARL", "MARR", "MARS", "MEDI", "MIME", "NAME", "NATI", "NATU", "NCHI",
+    "NICK", "NMR", "NO", "NOTE", "NPFX", "NSFX", "OBJE", "OCCU", "ORDI", "ORDN", "PAGE", "PEDI",
+    "PHON", "PHRASE", "PLAC", "POST", "PROB", "PROP", "PUBL", "QUAY", "REFN", "RELA", "RELI",
+    "REPO", "RESI", "RESN", "RETI", "RFN", "RIN", "ROLE", "ROMN", "SCHMA", "SDATE", "SEX", "SLGC",
+    "SLGS", "SNOTE", "SOUR", "SPFX", "SSN", "STAE", "STAT", "SUBM", "SUBN", "SURN", "TAG", "TEMP",
+    "TEXT", "TIME", "TITL", "TOP", "TRAN", "TRLR", "TYPE", "UID", "VERS", "WIDTH", "WIFE", "WILL",
+    "WWW",
+];
+
+/// Whether a tag is defined by the GEDCOM specifications.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TagKind {
+    /// A tag defined by GEDCOM 5.5, 5.5.1 or 7.0.
+    Standard,
+    /// A user-defined tag, starting with an underscore.
+    Extension,
+    /// Any other tag, such as a misspelling or an undocumented vendor tag.
+    Unknown,
+}
+
+impl TagKind {
+    /// Classifies a tag.
+    #[must_use]
+    pub fn of(tag: &str) -> Self {
+        if tag.starts_with('_') {
+            TagKind::Extension
+        } else if STANDARD_TAGS.binary_search(&tag).is_ok() {
+            TagKind::Standard
+        } else {
+            TagKind::Unknown
+        }
+    }
+}
+
+/// How often one tag appears in a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TagFrequency {
+    /// The tag, as written.
+    pub tag: String,
+    /// Whether the tag is standard.
+    pub kind: TagKind,
+    /// The number of lines with the tag.
+    pub count: usize,
+    /// The first lines with the tag, at most [`MAX_EXAMPLE_LINES`] of them.
+    pub lines: Vec<u32>,
+}
+
+/// What the tokenizer does with the invisible characters that
+/// [`is_invisible`] matches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InvisibleCharacterPolicy {
+    /// Keep them in tags, xrefs and values, and list them in
+    /// [`ParseReport::invisible_characters`] (the default).
+    #[default]
+    Report,
+    /// Remove them while reading, and list them in
+    /// [`ParseReport::invisible_characters`].
+    Strip,
+}
+
+/// An invisible character found in the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InvisibleCharacter {
+    /// The character.
+    pub character: char,
+    /// The line it is on, not counting blank lines.
+    pub line: u32,
+    /// Its offset in bytes from the start of the input.
+    pub offset: usize,
+}
+
+/// Returns true for the characters that have no place in GEDCOM text and
+/// are left behind by concatenating files or copying from web pages: a byte
+/// order mark after the start of the input (U+FEFF), a zero-width space
+/// (U+200B) or a word joiner (U+2060).
+///
+/// The zero-width joiner and non-joiner are not matched, as they shape
+/// names written in Arabic, Persian and Indic scripts.
+#[must_use]
+pub fn is_invisible(c: char) -> bool {
+    matches!(c, '\u{FEFF}' | '\u{200B}' | '\u{2060}')
+}
+
+/// What the parser saw in a file besides its records.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct ParseReport {
+    /// Every tag read, in order of first appearance.
+    pub tags: Vec<TagFrequency>,
+    /// The invisible characters found, in file order. A byte order mark at
+    /// the very start of the input is expected and not listed.
+    pub invisible_characters: Vec<InvisibleCharacter>,
+}
+
+impl ParseReport {
+    /// Returns true if nothing was recorded, as for data not built by the
+    /// parser.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.invisible_characters.is_empty()
+    }
+
+    /// Returns every tag read, most frequent first. Tags as frequent as each
+    /// other keep the order they first appear in.
+    #[must_use]
+    pub fn tag_frequencies(&self) -> Vec<&TagFrequency> {
+        let mut tags: Vec<&TagFrequency> = self.tags.iter().collect();
+        tags.sort_by_key(|t| Reverse(t.count));
+        tags
+    }
+
+    /// Returns the extension and unknown tags, most frequent first.
+    #[must_use]
+    pub fn unknown_tags(&self) -> Vec<&TagFrequency> {
+        let mut tags = self.tag_frequencies();
+        tags.retain(|t| t.kind != TagKind::Standard);
+        tags
+    }
+
+    /// Returns how many lines have the tag.
+    #[must_use]
+    pub fn count(&self, tag: &str) -> usize {
+        self.tags
+            .iter()
+            .find(|t| t.tag == tag)
+            .map_or(0, |t| t.count)
+    }
+}
+
+/// Gathers a [`ParseReport`] as the tokenizer reads.
+#[derive(Debug, Default)]
+pub(crate) struct ReportCollector {
+    tags: Interner,
+    /// The count and example lines of each tag, by symbol.
+    counts: Vec<(usize, Vec<u32>)>,
+    invisible_characters: Vec<InvisibleCharacter>,
+}
+
+impl ReportCollector {
+    pub(crate) fn record_tag(&mut self, tag: &str, line: u32) {
+        let index = self.tags.intern(tag).index();
+        if index == self.counts.len() {
+            self.counts.push((0, Vec::new()));
+        }
+        let (count, lines) = &mut self.counts[index];
+        *count += 1;
+        if lines.len() < MAX_EXAMPLE_LINES {
+            lines.push(line);
+        }
+    }
+
+    pub(crate) fn record_invisible(&mut self, character: char, line: u32, offset: usize) {
+        self.invisible_characters.push(InvisibleCharacter {
+            character,
+            line,
+            offset,
+        });
+    }
+
+    /// Adds what was gathered from a later part of the same file.
+    pub(crate) fn merge(&mut self, other: ReportCollector) {
+        self.invisible_characters.extend(other.invisible_characters);
+        for ((_, tag), (count, lines)) in other.tags.iter().zip(other.counts) {
+            let index = self.tags.intern(tag).index();
+            if index == self.counts.len() {
+                self.counts.push((0, Vec::new()));
+            }
+            let (total, examples) = &mut self.counts[index];
+            *total += count;
+            let room = MAX_EXAMPLE_LINES - examples.len();
+            examples.extend(lines.into_iter().take(room));
+        }
+    }
+
+    pub(crate) fn into_report(self) -> ParseReport {
+        let tags = self
+            .tags
+            .iter()
+            .zip(self.counts)
+            .filter(|((_, tag), _)| *tag != "TRLR")
+            .map(|((_, tag), (count, lines))| TagFrequency {
+                tag: tag.to_string(),
+                kind: TagKind::of(tag),
+                count,
+                lines,
+            })
+            .collect();
+        ParseReport {
+            tags,
+            invisible_characters: self.invisible_characters,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_standard_tags_sorted() {
+        assert!(STANDARD_TAGS.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(TagKind::of("BIRT"), TagKind::Standard);
+        assert_eq!(TagKind::of("_MARNM"), TagKind::Extension);
+        assert_eq!(TagKind::of("BIRTH"), TagKind::Unknown);
+    }
+
+    #[test]
+    fn test_tag_frequencies() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME A\n1 _UID 1\n1 _UID 2\n\
+            0 @I2@ INDI\n1 NAME B\n1 _UID 3\n1 _UID 4\n1 _MILT\n2 UNIT Navy\n0 TRLR\n";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let report = &data.report;
+
+        let top = report.tag_frequencies();
+        assert_eq!(top[0].tag, "_UID");
+        assert_eq!(top[0].count, 4);
+        assert_eq!(top[0].lines, vec![6, 7, 10]);
+        assert_eq!(report.count("INDI"), 2);
+        assert_eq!(report.count("TRLR"), 0);
+
+        let unknown: Vec<&str> = report.unknown_tags().iter().map(|t| &*t.tag).collect();
+        assert_eq!(unknown, vec!["_UID", "_MILT", "UNIT"]);
+        assert_eq!(report.unknown_tags()[2].kind, TagKind::Unknown);
+    }
+
+    #[test]
+    fn test_invisible_characters() {
+        // Two exports joined together, with a zero-width space after a tag and
+        // a word joiner in a surname.
+        let source = "\u{FEFF}0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME A /Doe\u{2060}/\n\
+            \u{FEFF}0 @I2@ INDI\n1 NAME B /Doe/\n1 _UID\u{200B} 1\n0 TRLR\n";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let found: Vec<(char, u32, usize)> = data
+            .report
+            .invisible_characters
+            .iter()
+            .map(|c| (c.character, c.line, c.offset))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ('\u{2060}', 5, 55),
+                ('\u{FEFF}', 6, 60),
+                ('\u{200B}', 8, 96)
+            ]
+        );
+        assert_eq!(&source[55..58], "\u{2060}");
+        assert_eq!(data.individuals[0].full_name().unwrap(), "A Doe\u{2060}");
+        assert_eq!(data.report.count("_UID\u{200B}"), 1);
+
+        let data = GedcomBuilder::new()
+            .invisible_characters(InvisibleCharacterPolicy::Strip)
+            .build_from_str(source)
+            .unwrap();
+        assert_eq!(data.report.invisible_characters.len(), 3);
+        assert_eq!(data.individuals[0].full_name().unwrap(), "A Doe");
+        assert_eq!(data.report.count("_UID"), 1);
+
+        #[cfg(feature = "parallel")]
+        for policy in [
+            InvisibleCharacterPolicy::Report,
+            InvisibleCharacterPolicy::Strip,
+        ] {
+            let builder = GedcomBuilder::new().invisible_characters(policy);
+            let sequential = builder.clone().build_from_str(source).unwrap();
+            let parallel = builder.parallel(true).build_from_str(source).unwrap();
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[test]
+    fn test_merge_counts() {
+        let mut first = ReportCollector::default();
+        first.record_tag("NAME", 1);
+        first.record_tag("_X", 2);
+        let mut second = ReportCollector::default();
+        second.record_tag("_X", 5);
+        second.record_tag("_X", 6);
+        second.record_tag("_X", 7);
+        first.merge(second);
+
+        let report = first.into_report();
+        assert_eq!(report.tags[1].count, 4);
+        assert_eq!(report.tags[1].lines, vec![2, 5, 6]);
+    }
+}
@@ -0,0 +1,465 @@
+//! Finding individuals by name, birth year and place.
+//!
+//! [`GedcomData::search`] returns the individuals matching a
+//! [`SearchQuery`](crate::search::SearchQuery): a surname, given names, a
+//! range of birth years and part of a place name, any of which may be left
+//! out. Names and places are compared without regard to case or accents, so
+//! `muller` finds `Müller`, and a query can allow a few typing differences
+//! with [`fuzzy`](crate::search::SearchQuery::fuzzy).
+//!
+//! The search runs against a [`SearchIndex`](crate::search::SearchIndex) of
+//! the individuals' names, birth years and places. [`GedcomData::search`]
+//! builds one for each call, which suits a single search. For repeated
+//! searches of the same tree, build a `SearchIndex` once and search it, or
+//! use
+//! [`IndexedGedcomData::search`](crate::indexed::IndexedGedcomData::search),
+//! which builds its index on the first search and drops it whenever the data
+//! is updated.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{search::SearchQuery, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME Johann /Müller/\n1 BIRT\n2 DATE 12 MAR 1852\n2 PLAC Köln, Preußen\n\
+//!     0 @I2@ INDI\n1 NAME Anna /Mueller/\n1 BIRT\n2 DATE 1880\n\
+//!     0 @I3@ INDI\n1 NAME John /Miller/\n1 BIRT\n2 DATE 1855\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let found = data.search(&SearchQuery::default().surname("muller"));
+//! assert_eq!(found.len(), 1);
+//! assert_eq!(found[0].xref.as_deref(), Some("@I1@"));
+//!
+//! let query = SearchQuery::default().surname("Muller").fuzzy(1).born_between(1850, 1860);
+//! let found: Vec<_> = data.search(&query).iter().map(|i| i.xref.clone().unwrap()).collect();
+//! assert_eq!(found, ["@I1@", "@I3@"]);
+//!
+//! let found = data.search(&SearchQuery::default().place("koln"));
+//! assert_eq!(found[0].xref.as_deref(), Some("@I1@"));
+//! ```
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    names::latin_base,
+    statistics::summary::year_of,
+    types::{individual::Individual, GedcomData},
+};
+
+/// What [`GedcomData::search`] looks for. Every criterion that is set must
+/// match; an empty query matches every individual.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SearchQuery {
+    /// The surname, matched as a whole, with or without its prefix: `Berg`
+    /// and `van der Berg` both find `Pieter /van der Berg/`.
+    pub surname: Option<String>,
+    /// Given names, each of which must be one of the given names of the same
+    /// name as the surname, in any order.
+    pub given: Option<String>,
+    /// How many single-letter insertions, deletions or substitutions a name
+    /// may differ by and still match (default: 0, exact matches only).
+    pub max_distance: usize,
+    /// The earliest year of birth, inclusive.
+    pub born_from: Option<i32>,
+    /// The latest year of birth, inclusive.
+    pub born_to: Option<i32>,
+    /// Part of the place of any event or attribute of the individual.
+    pub place: Option<String>,
+}
+
+impl SearchQuery {
+    /// Sets the surname to look for.
+    #[must_use]
+    pub fn surname(mut self, surname: &str) -> Self {
+        self.surname = Some(surname.to_string());
+        self
+    }
+
+    /// Sets the given names to look for.
+    #[must_use]
+    pub fn given(mut self, given: &str) -> Self {
+        self.given = Some(given.to_string());
+        self
+    }
+
+    /// Lets names differ by up to `max_distance` letters.
+    #[must_use]
+    pub fn fuzzy(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Keeps individuals born from `from` to `to`, both inclusive.
+    /// Individuals without a year of birth no longer match.
+    #[must_use]
+    pub fn born_between(mut self, from: i32, to: i32) -> Self {
+        self.born_from = Some(from);
+        self.born_to = Some(to);
+        self
+    }
+
+    /// Keeps individuals with an event or attribute at a place whose name
+    /// contains `place`.
+    #[must_use]
+    pub fn place(mut self, place: &str) -> Self {
+        self.place = Some(place.to_string());
+        self
+    }
+
+    /// Returns true if `a` and `b` are within the allowed distance.
+    fn close(&self, a: &str, b: &str) -> bool {
+        if self.max_distance == 0 {
+            a == b
+        } else {
+            a.chars().count().abs_diff(b.chars().count()) <= self.max_distance
+                && distance(a, b) <= self.max_distance
+        }
+    }
+}
+
+/// One name of an indexed individual.
+#[derive(Clone, Debug)]
+struct IndexedName {
+    /// The surname alone and, if it has a prefix, with the prefix.
+    surnames: Vec<String>,
+    given: Vec<String>,
+}
+
+/// The searchable details of one individual.
+#[derive(Clone, Debug)]
+struct Entry {
+    names: Vec<IndexedName>,
+    birth_year: Option<i32>,
+    places: Vec<String>,
+}
+
+/// An index of the individuals of a [`GedcomData`] for
+/// [`SearchQuery`]s: the individuals keyed by their folded surnames, and the
+/// details the other criteria are checked against.
+///
+/// The index is a snapshot of the data it was built from, and refers to the
+/// individuals by position. Once individuals are added, removed, reordered
+/// or edited, it no longer matches the data and must be built again;
+/// [`IndexedGedcomData`](crate::indexed::IndexedGedcomData) does this for
+/// its own index in `update` and `compact`.
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::{search::{SearchIndex, SearchQuery}, GedcomBuilder};
+///
+/// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+///     0 @I1@ INDI\n1 NAME John /Doe/\n0 @I2@ INDI\n1 NAME Jane /Roe/\n0 TRLR";
+/// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+///
+/// let index = SearchIndex::new(&data);
+/// for (surname, xref) in [("doe", "@I1@"), ("roe", "@I2@")] {
+///     let found = index.search(&data, &SearchQuery::default().surname(surname));
+///     assert_eq!(found[0].xref.as_deref(), Some(xref));
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SearchIndex {
+    /// Positions in `individuals` of those with each surname
+    surnames: BTreeMap<String, Vec<usize>>,
+    /// Details of each individual, by position
+    entries: Vec<Entry>,
+}
+
+impl SearchIndex {
+    /// Indexes the individuals of `data`.
+    #[must_use]
+    pub fn new(data: &GedcomData) -> Self {
+        let mut index = SearchIndex::default();
+        for (position, individual) in data.individuals.iter().enumerate() {
+            let entry = Entry::new(individual);
+            for surname in entry.names.iter().flat_map(|name| &name.surnames) {
+                let positions = index.surnames.entry(surname.clone()).or_default();
+                if positions.last() != Some(&position) {
+                    positions.push(position);
+                }
+            }
+            index.entries.push(entry);
+        }
+        index
+    }
+
+    /// Returns the individuals of `data` matching `query`, in file order.
+    ///
+    /// `data` must be the data the index was built from, unchanged since.
+    #[must_use]
+    pub fn search<'d>(&self, data: &'d GedcomData, query: &SearchQuery) -> Vec<&'d Individual> {
+        self.positions(query)
+            .into_iter()
+            .filter_map(|position| data.individuals.get(position))
+            .collect()
+    }
+
+    /// Returns the positions of the individuals matching `query`, in order.
+    fn positions(&self, query: &SearchQuery) -> Vec<usize> {
+        let surname = query.surname.as_deref().map(fold);
+        let given: Vec<String> = query
+            .given
+            .as_deref()
+            .map(|given| fold(given).split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+        let place = query.place.as_deref().map(fold);
+
+        let mut candidates: Vec<usize> = match &surname {
+            Some(surname) => self
+                .surnames
+                .iter()
+                .filter(|(known, _)| query.close(known, surname))
+                .flat_map(|(_, positions)| positions.iter().copied())
+                .collect(),
+            None => (0..self.entries.len()).collect(),
+        };
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        candidates.retain(|&position| {
+            let entry = &self.entries[position];
+            let name_matches = (surname.is_none() && given.is_empty())
+                || entry.names.iter().any(|name| {
+                    let surname_matches = surname.as_ref().is_none_or(|surname| {
+                        name.surnames
+                            .iter()
+                            .any(|known| query.close(known, surname))
+                    });
+                    surname_matches
+                        && given
+                            .iter()
+                            .all(|wanted| name.given.iter().any(|known| query.close(known, wanted)))
+                });
+            let year_matches = (query.born_from.is_none() && query.born_to.is_none())
+                || entry.birth_year.is_some_and(|year| {
+                    query.born_from.is_none_or(|from| year >= from)
+                        && query.born_to.is_none_or(|to| year <= to)
+                });
+            let place_matches = place.as_ref().is_none_or(|place| {
+                entry
+                    .places
+                    .iter()
+                    .any(|known| known.contains(place.as_str()))
+            });
+            name_matches && year_matches && place_matches
+        });
+        candidates
+    }
+}
+
+impl Entry {
+    fn new(individual: &Individual) -> Self {
+        let names = individual
+            .names
+            .iter()
+            .map(|name| {
+                let components = name.parse_components();
+                let surnames = components
+                    .surname
+                    .as_deref()
+                    .map(|surname| surnames(components.surname_prefix.as_deref(), surname))
+                    .unwrap_or_default();
+                let given = components
+                    .given
+                    .as_deref()
+                    .map(|given| fold(given).split_whitespace().map(str::to_string).collect())
+                    .unwrap_or_default();
+                IndexedName { surnames, given }
+            })
+            .collect();
+        let events = individual.events.iter().map(|e| e.place.as_ref());
+        let attributes = individual.attributes.iter().map(|a| a.place.as_ref());
+        let places = events
+            .chain(attributes)
+            .filter_map(|place| place?.value.as_deref())
+            .map(fold)
+            .collect();
+        Entry {
+            names,
+            birth_year: individual.birth_date().and_then(year_of),
+            places,
+        }
+    }
+}
+
+/// Returns the folded forms a surname is found by: with and without its
+/// prefix. Without an `SPFX`, leading words in lower case, such as `van der`
+/// in `van der Berg`, are taken as the prefix.
+fn surnames(prefix: Option<&str>, surname: &str) -> Vec<String> {
+    let full = match prefix {
+        Some(prefix) => format!("{prefix} {surname}"),
+        None => surname.to_string(),
+    };
+    let words: Vec<&str> = full.split_whitespace().collect();
+    let particles = match prefix {
+        Some(prefix) => prefix.split_whitespace().count(),
+        None => words
+            .iter()
+            .take_while(|word| word.starts_with(char::is_lowercase))
+            .count(),
+    };
+    let mut surnames = vec![fold(&full)];
+    if particles > 0 && particles < words.len() {
+        surnames.push(fold(&words[particles..].join(" ")));
+    }
+    surnames
+}
+
+/// Returns `text` in lower case, with accented Latin letters replaced by
+/// their base letters and runs of whitespace by single spaces.
+fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !folded.is_empty() {
+            folded.push(' ');
+        }
+        for c in word.chars().flat_map(char::to_lowercase) {
+            match latin_base(c) {
+                Some(base) => folded.push_str(base),
+                // Combining accents, as decoded from ANSEL
+                None if ('\u{300}'..='\u{36F}').contains(&c) => {}
+                None => folded.push(c),
+            }
+        }
+    }
+    folded
+}
+
+/// Returns the Levenshtein distance between `a` and `b`, in characters.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+impl GedcomData {
+    /// Returns the individuals matching `query`, in file order.
+    ///
+    /// This builds a new [`SearchIndex`] on each call, so that the result
+    /// always reflects the current data. To search the same data
+    /// repeatedly, build a `SearchIndex` once, or wrap the data in an
+    /// [`IndexedGedcomData`](crate::indexed::IndexedGedcomData), which keeps
+    /// the index until the data is updated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{search::SearchQuery, GedcomBuilder};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+    ///     0 @I1@ INDI\n1 NAME John Henry /Doe/\n\
+    ///     0 @I2@ INDI\n1 NAME Jane /Doe/\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+    ///
+    /// assert_eq!(data.search(&SearchQuery::default().surname("doe")).len(), 2);
+    /// let found = data.search(&SearchQuery::default().surname("Doe").given("henry"));
+    /// assert_eq!(found[0].xref.as_deref(), Some("@I1@"));
+    /// ```
+    #[must_use]
+    pub fn search(&self, query: &SearchQuery) -> Vec<&Individual> {
+        SearchIndex::new(self).search(self, query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    fn xrefs<'a>(individuals: &[&'a Individual]) -> Vec<&'a str> {
+        individuals
+            .iter()
+            .filter_map(|i| i.xref.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_fold_and_distance() {
+        assert_eq!(
+            fold("  Jean-Fran\u{e7}ois   M\u{fc}LLER "),
+            "jean-francois muller"
+        );
+        assert_eq!(fold("Zoe\u{308}"), "zoe");
+        assert_eq!(
+            fold("\u{41f}\u{435}\u{442}\u{440}\u{43e}\u{432}"),
+            "\u{43f}\u{435}\u{442}\u{440}\u{43e}\u{432}"
+        );
+        assert_eq!(distance("meyer", "maier"), 2);
+        assert_eq!(distance("smith", "smyth"), 1);
+        assert_eq!(distance("", "doe"), 3);
+        assert_eq!(distance("doe", "doe"), 0);
+    }
+
+    #[test]
+    fn test_search() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME Johann /Müller/\n1 BIRT\n2 DATE 12 MAR 1852\n2 PLAC Köln\n\
+            0 @I2@ INDI\n1 NAME Anna Maria /Meyer/\n1 NAME Anna /Müller/\n2 TYPE MARRIED\n\
+            1 BIRT\n2 DATE ABT 1860\n1 RESI\n2 PLAC Bonn\n\
+            0 @I3@ INDI\n1 NAME Pieter /van der Berg/\n\
+            0 @I4@ INDI\n1 NAME /Maier/\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let search = |query: SearchQuery| xrefs(&data.search(&query));
+
+        assert_eq!(search(SearchQuery::default()).len(), 4);
+        assert_eq!(
+            search(SearchQuery::default().surname("MULLER")),
+            ["@I1@", "@I2@"]
+        );
+        // The surname and given names must come from the same name
+        assert_eq!(
+            search(SearchQuery::default().surname("Muller").given("maria")),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            search(SearchQuery::default().surname("Meyer").given("maria anna")),
+            ["@I2@"]
+        );
+        assert_eq!(search(SearchQuery::default().given("Johann")), ["@I1@"]);
+        assert_eq!(search(SearchQuery::default().surname("Berg")), ["@I3@"]);
+        assert_eq!(
+            search(SearchQuery::default().surname("van der berg")),
+            ["@I3@"]
+        );
+        assert_eq!(
+            search(SearchQuery::default().surname("Mayer")),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            search(SearchQuery::default().surname("Mayer").fuzzy(1)),
+            ["@I2@", "@I4@"]
+        );
+        assert_eq!(
+            search(SearchQuery::default().born_between(1855, 1870)),
+            ["@I2@"]
+        );
+        assert_eq!(
+            search(SearchQuery {
+                born_to: Some(1855),
+                ..SearchQuery::default()
+            }),
+            ["@I1@"]
+        );
+        assert_eq!(search(SearchQuery::default().place("bonn")), ["@I2@"]);
+        assert_eq!(
+            search(SearchQuery::default().surname("Muller").place("KOLN")),
+            ["@I1@"]
+        );
+    }
+}
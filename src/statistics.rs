@@ -0,0 +1,28 @@
+//! Aggregate statistics over GEDCOM data.
+//!
+//! These functions summarize a whole file into the numbers behind charts and
+//! reports, such as how many births fall in each decade, or a whole-file
+//! [`Statistics`](crate::statistics::Statistics) summary.
+
+#[cfg(feature = "calendar")]
+pub mod histogram;
+
+#[cfg(feature = "calendar")]
+pub use histogram::{Bucket, EventHistogram};
+
+pub mod archives;
+pub mod summary;
+
+pub use archives::{ArchiveReport, Holding, RepositoryHoldings};
+pub use summary::{DateCoverage, OrphanedRecords, RecordCounts, Statistics};
+
+/// Returns the first date of a `FROM ... TO` period or `BET ... AND` range.
+fn range_start(value: &str) -> &str {
+    let value = value.trim();
+    for (keyword, separator) in [("FROM ", " TO "), ("BET ", " AND ")] {
+        if let Some(rest) = value.strip_prefix(keyword) {
+            return rest.split(separator).next().unwrap_or(rest);
+        }
+    }
+    value.strip_prefix("TO ").unwrap_or(value)
+}
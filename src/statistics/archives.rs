@@ -0,0 +1,172 @@
+//! Which archive holds which source.
+//!
+//! [`GedcomData::archive_report`] lists the sources of a file by the
+//! repository that holds them, with the call number to ask for, as a list
+//! to take on a research trip. It also lists the sources no repository is
+//! known for, and the repository links that lead nowhere, which is worth
+//! checking after merging files.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @S1@ SOUR\n1 TITL Parish register\n1 REPO @R1@\n2 CALN PR-1850\n\
+//!     0 @S2@ SOUR\n1 TITL Family Bible\n\
+//!     0 @R1@ REPO\n1 NAME County Archive\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let report = data.archive_report();
+//! let archive = &report.repositories[0];
+//! assert_eq!(archive.name.as_deref(), Some("County Archive"));
+//! assert_eq!(archive.holdings[0].call_number.as_deref(), Some("PR-1850"));
+//! assert_eq!(report.without_repository, vec!["@S2@".to_string()]);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::GedcomData;
+
+/// The sources of a file grouped by repository.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArchiveReport {
+    /// Every repository record, in file order, with the sources it holds.
+    pub repositories: Vec<RepositoryHoldings>,
+    /// The xrefs of the sources that cite no repository.
+    pub without_repository: Vec<String>,
+    /// Repository citations naming no repository record, as the xref of
+    /// the citing source and the xref it names.
+    pub unresolved: Vec<(String, String)>,
+}
+
+/// A repository and the sources it holds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RepositoryHoldings {
+    /// The repository's xref.
+    pub repository: String,
+    /// The repository's name.
+    pub name: Option<String>,
+    /// The sources it holds, in file order.
+    pub holdings: Vec<Holding>,
+}
+
+/// A source held by a repository.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Holding {
+    /// The source's xref.
+    pub source: String,
+    /// The source's title.
+    pub title: Option<String>,
+    /// The call number to find it by (tag: CALN).
+    pub call_number: Option<String>,
+    /// The medium it is kept on (tag: MEDI), such as `film` or `book`.
+    pub media_type: Option<String>,
+}
+
+impl GedcomData {
+    /// Groups the sources by the repositories that hold them.
+    #[must_use]
+    pub fn archive_report(&self) -> ArchiveReport {
+        let mut report = ArchiveReport {
+            repositories: self
+                .repositories
+                .iter()
+                .filter_map(|repository| {
+                    Some(RepositoryHoldings {
+                        repository: repository.xref.clone()?,
+                        name: repository.name.clone(),
+                        holdings: Vec::new(),
+                    })
+                })
+                .collect(),
+            ..ArchiveReport::default()
+        };
+
+        for source in &self.sources {
+            let xref = source.xref.clone().unwrap_or_default();
+            if source.repo_citations.is_empty() {
+                report.without_repository.push(xref.clone());
+            }
+            for citation in &source.repo_citations {
+                let holder = report
+                    .repositories
+                    .iter_mut()
+                    .find(|r| r.repository == citation.xref);
+                let Some(holder) = holder else {
+                    report
+                        .unresolved
+                        .push((xref.clone(), citation.xref.clone()));
+                    continue;
+                };
+                holder.holdings.push(Holding {
+                    source: xref.clone(),
+                    title: source.title.clone(),
+                    call_number: citation.call_number.clone(),
+                    media_type: citation.media_type.clone(),
+                });
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_archive_report() {
+        let source = "\
+            0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @S1@ SOUR\n1 TITL Census 1851\n1 REPO @R1@\n2 CALN HO107/1\n3 MEDI film\n1 REPO @R2@\n\
+            0 @S2@ SOUR\n1 TITL Will of John Doe\n1 REPO @R1@\n2 CALN PROB 11\n1 REPO @R9@\n\
+            0 @R1@ REPO\n1 NAME National Archives\n\
+            0 @R2@ REPO\n1 NAME Library\n\
+            0 @R3@ REPO\n1 NAME Unused\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let census = &data.sources[0];
+        let repositories: Vec<_> = census
+            .repositories(&data)
+            .into_iter()
+            .map(|(citation, repository)| {
+                (citation.call_number.as_deref(), repository.name.as_deref())
+            })
+            .collect();
+        assert_eq!(
+            repositories,
+            vec![
+                (Some("HO107/1"), Some("National Archives")),
+                (None, Some("Library")),
+            ]
+        );
+        assert_eq!(data.sources[1].repositories(&data).len(), 1);
+
+        let report = data.archive_report();
+        assert_eq!(report.repositories.len(), 3);
+        let national = &report.repositories[0];
+        let holdings: Vec<&str> = national
+            .holdings
+            .iter()
+            .map(|h| h.source.as_str())
+            .collect();
+        assert_eq!(holdings, vec!["@S1@", "@S2@"]);
+        assert_eq!(national.holdings[0].media_type.as_deref(), Some("film"));
+        assert_eq!(
+            national.holdings[1].title.as_deref(),
+            Some("Will of John Doe")
+        );
+        assert!(report.repositories[2].holdings.is_empty());
+        assert!(report.without_repository.is_empty());
+        assert_eq!(
+            report.unresolved,
+            vec![("@S2@".to_string(), "@R9@".to_string())]
+        );
+    }
+}
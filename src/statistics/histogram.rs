@@ -0,0 +1,200 @@
+//! Event counts per period, for "births per decade" style charts.
+//!
+//! Years come from the typed date layer ([`ParsedDateTime`]), so qualifiers
+//! such as `ABT` are ignored and Hebrew or French Republican dates are
+//! converted to Gregorian when they are complete. Periods (`FROM ... TO`) and
+//! ranges (`BET ... AND`) count towards the year they start in.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::statistics::Bucket;
+//! use ged_io::types::event::Event;
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 BIRT\n2 DATE 1887\n0 @I2@ INDI\n1 BIRT\n2 DATE ABT 1889\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let histogram = data.event_histogram(&Event::Birth, Bucket::Decade);
+//! assert_eq!(histogram.counts.get(&1880), Some(&2));
+//! ```
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    statistics::range_start,
+    types::{
+        date::{Calendar, Date, ParsedDateTime},
+        event::{detail::Detail, Event},
+        GedcomData,
+    },
+};
+
+/// The width of the periods events are counted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Bucket {
+    /// One period per year.
+    Year,
+    /// Periods of ten years starting at a multiple of ten (1880-1889).
+    Decade,
+    /// Periods of a hundred years starting at a multiple of a hundred (1800-1899).
+    Century,
+}
+
+impl Bucket {
+    /// Returns the first year of the period a year falls in.
+    #[must_use]
+    pub fn start(self, year: i32) -> i32 {
+        let width = match self {
+            Bucket::Year => 1,
+            Bucket::Decade => 10,
+            Bucket::Century => 100,
+        };
+        year.div_euclid(width) * width
+    }
+}
+
+/// Event counts per period, overall and per place.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EventHistogram {
+    /// The width of the periods.
+    pub bucket: Bucket,
+    /// Event counts keyed by the first year of each period.
+    pub counts: BTreeMap<i32, usize>,
+    /// Event counts per place, then per period; undated events are left out.
+    pub by_place: BTreeMap<String, BTreeMap<i32, usize>>,
+    /// Events with no date, or a date without a usable year.
+    pub undated: usize,
+}
+
+impl EventHistogram {
+    fn new(bucket: Bucket) -> Self {
+        Self {
+            bucket,
+            counts: BTreeMap::new(),
+            by_place: BTreeMap::new(),
+            undated: 0,
+        }
+    }
+
+    fn add(&mut self, detail: &Detail) {
+        let Some(year) = detail.date.as_ref().and_then(gregorian_year) else {
+            self.undated += 1;
+            return;
+        };
+        let start = self.bucket.start(year);
+        *self.counts.entry(start).or_default() += 1;
+        if let Some(place) = detail.place.as_ref().and_then(|p| p.value.as_deref()) {
+            *self
+                .by_place
+                .entry(place.to_string())
+                .or_default()
+                .entry(start)
+                .or_default() += 1;
+        }
+    }
+
+    /// Returns the total number of events, dated or not.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.values().sum::<usize>() + self.undated
+    }
+}
+
+impl GedcomData {
+    /// Counts the events of a type per period.
+    ///
+    /// Both individual and family events are counted, so this works for
+    /// marriages as well as births.
+    #[must_use]
+    pub fn event_histogram(&self, event: &Event, bucket: Bucket) -> EventHistogram {
+        let mut histogram = EventHistogram::new(bucket);
+        let individual_events = self.individuals.iter().flat_map(|i| &i.events);
+        let family_events = self
+            .families
+            .iter()
+            .flat_map(|f| f.events.iter().chain(&f.family_event));
+        for detail in individual_events.chain(family_events) {
+            if detail.event == *event {
+                histogram.add(detail);
+            }
+        }
+        histogram
+    }
+}
+
+/// Returns the Gregorian year a date falls in, or starts in for a range.
+fn gregorian_year(date: &Date) -> Option<i32> {
+    let value = date.value.as_deref()?;
+    let parsed = ParsedDateTime::from_gedcom_date(range_start(value)).ok()?;
+    let parsed = match parsed.calendar {
+        Calendar::Gregorian | Calendar::Julian => parsed,
+        _ => parsed.convert_to(Calendar::Gregorian).ok()?,
+    };
+    parsed.year
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_bucket_start() {
+        assert_eq!(Bucket::Decade.start(1889), 1880);
+        assert_eq!(Bucket::Century.start(1900), 1900);
+        assert_eq!(Bucket::Year.start(1776), 1776);
+        assert_eq!(Bucket::Decade.start(-5), -10);
+    }
+
+    #[test]
+    fn test_event_histogram() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 BIRT\n\
+            2 DATE 3 APR 1887\n\
+            2 PLAC Boston\n\
+            1 DEAT\n\
+            2 DATE 1950\n\
+            0 @I2@ INDI\n\
+            1 BIRT\n\
+            2 DATE BET 1889 AND 1891\n\
+            2 PLAC Salem\n\
+            0 @I3@ INDI\n\
+            1 BIRT\n\
+            2 DATE ABT 1902\n\
+            2 PLAC Boston\n\
+            0 @I4@ INDI\n\
+            1 BIRT\n\
+            2 PLAC Boston\n\
+            0 @F1@ FAM\n\
+            1 MARR\n\
+            2 DATE FROM 1910 TO 1911\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let births = data.event_histogram(&Event::Birth, Bucket::Decade);
+        assert_eq!(births.counts, BTreeMap::from([(1880, 2), (1900, 1)]));
+        assert_eq!(births.undated, 1);
+        assert_eq!(births.total(), 4);
+        assert_eq!(
+            births.by_place["Boston"],
+            BTreeMap::from([(1880, 1), (1900, 1)])
+        );
+        assert_eq!(births.by_place["Salem"], BTreeMap::from([(1880, 1)]));
+
+        let births = data.event_histogram(&Event::Birth, Bucket::Century);
+        assert_eq!(births.counts, BTreeMap::from([(1800, 2), (1900, 1)]));
+
+        let marriages = data.event_histogram(&Event::Marriage, Bucket::Year);
+        assert_eq!(marriages.counts, BTreeMap::from([(1910, 1)]));
+    }
+}
@@ -0,0 +1,457 @@
+//! A whole-file summary returned as data.
+//!
+//! [`GedcomData::statistics`] gathers the numbers a "tree statistics" page
+//! shows: record counts, the most common surnames and birthplaces, average
+//! lifespan, the longest line of generations, records that nothing links to
+//! and the span of years the events cover. Unlike
+//! [`GedcomData::stats`](crate::types::GedcomData::stats), which prints, it
+//! returns a [`Statistics`] value, and serializes with the `serde` feature.
+//!
+//! Years are read from the date text, so `ABT 1850` counts as 1850 and a
+//! `BET ... AND ...` range as its first year; dates in other calendars are
+//! not converted.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::GedcomBuilder;
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n1 BIRT\n2 DATE 1850\n1 DEAT\n2 DATE 1910\n0 @I2@ INDI\n1 NAME Jim /Doe/\n1 BIRT\n2 DATE 1880\n0 @F1@ FAM\n1 HUSB @I1@\n1 CHIL @I2@\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let statistics = data.statistics();
+//! assert_eq!(statistics.records.individuals, 2);
+//! assert_eq!(statistics.surnames, vec![("Doe".to_string(), 2)]);
+//! assert_eq!(statistics.average_lifespan, Some(60.0));
+//! assert_eq!(statistics.generation_depth, 2);
+//! assert_eq!(statistics.date_coverage.earliest, Some(1850));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    statistics::range_start,
+    types::{source::citation::Citation, GedcomData, SourceCitationStats},
+};
+
+/// A summary of a whole file.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Statistics {
+    /// The number of records of each type.
+    pub records: RecordCounts,
+    /// Where the file's source citations are.
+    pub source_citations: SourceCitationStats,
+    /// Surnames and how many individuals carry each, most common first.
+    pub surnames: Vec<(String, usize)>,
+    /// Birthplaces and how many individuals were born in each, most common
+    /// first.
+    pub birthplaces: Vec<(String, usize)>,
+    /// The average age at death in years, over the individuals with a birth
+    /// and a death year.
+    pub average_lifespan: Option<f64>,
+    /// The number of individuals the average lifespan is taken over.
+    pub lifespans_counted: usize,
+    /// The number of generations in the longest line from an ancestor to a
+    /// descendant; 1 when no one has parents in the file.
+    pub generation_depth: usize,
+    /// Records that nothing links to.
+    pub orphans: OrphanedRecords,
+    /// The years the file's events fall in.
+    pub date_coverage: DateCoverage,
+}
+
+/// The number of records of each type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordCounts {
+    /// `INDI` records.
+    pub individuals: usize,
+    /// `FAM` records.
+    pub families: usize,
+    /// `SOUR` records.
+    pub sources: usize,
+    /// `REPO` records.
+    pub repositories: usize,
+    /// `SUBM` records.
+    pub submitters: usize,
+    /// `SUBN` records.
+    pub submissions: usize,
+    /// `OBJE` records.
+    pub multimedia: usize,
+    /// `SNOTE` records.
+    pub shared_notes: usize,
+}
+
+/// The xrefs of records that nothing links to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OrphanedRecords {
+    /// Individuals who are neither a partner nor a child in any family.
+    pub individuals: Vec<String>,
+    /// Families with no partners and no children.
+    pub families: Vec<String>,
+    /// Sources that no citation points to.
+    pub sources: Vec<String>,
+    /// Repositories that no source points to.
+    pub repositories: Vec<String>,
+}
+
+/// The years covered by the events of a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DateCoverage {
+    /// The earliest year of an event.
+    pub earliest: Option<i32>,
+    /// The latest year of an event.
+    pub latest: Option<i32>,
+    /// Individual and family events with a year.
+    pub dated_events: usize,
+    /// Individual and family events without a date, or without a year in it.
+    pub undated_events: usize,
+}
+
+impl DateCoverage {
+    fn add(&mut self, year: Option<i32>) {
+        let Some(year) = year else {
+            self.undated_events += 1;
+            return;
+        };
+        self.dated_events += 1;
+        self.earliest = Some(self.earliest.map_or(year, |y| y.min(year)));
+        self.latest = Some(self.latest.map_or(year, |y| y.max(year)));
+    }
+}
+
+impl GedcomData {
+    /// Summarizes the file; see the [module documentation](crate::statistics::summary).
+    #[must_use]
+    pub fn statistics(&self) -> Statistics {
+        let lifespans: Vec<i32> = self
+            .individuals
+            .iter()
+            .filter_map(|i| Some(year_of(i.death_date()?)? - year_of(i.birth_date()?)?))
+            .filter(|years| *years >= 0)
+            .collect();
+        let average_lifespan = u32::try_from(lifespans.len())
+            .ok()
+            .filter(|&count| count > 0)
+            .map(|count| f64::from(lifespans.iter().sum::<i32>()) / f64::from(count));
+
+        Statistics {
+            records: RecordCounts {
+                individuals: self.individuals.len(),
+                families: self.families.len(),
+                sources: self.sources.len(),
+                repositories: self.repositories.len(),
+                submitters: self.submitters.len(),
+                submissions: self.submissions.len(),
+                multimedia: self.multimedia.len(),
+                shared_notes: self.shared_notes.len(),
+            },
+            source_citations: self.count_source_citations(),
+            surnames: ranked(self.individuals.iter().map(|i| i.surname_or_parsed())),
+            birthplaces: ranked(self.individuals.iter().map(|i| i.birth_place())),
+            average_lifespan,
+            lifespans_counted: lifespans.len(),
+            generation_depth: self.generation_depth(),
+            orphans: self.orphaned_records(),
+            date_coverage: self.date_coverage(),
+        }
+    }
+
+    /// Returns the number of generations in the longest ancestor line.
+    fn generation_depth(&self) -> usize {
+        let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for family in &self.families {
+            let partners = [&family.individual1, &family.individual2];
+            for child in &family.children {
+                parents
+                    .entry(child.as_str())
+                    .or_default()
+                    .extend(partners.iter().filter_map(|p| p.as_deref()));
+            }
+        }
+
+        let mut depths = HashMap::new();
+        self.individuals
+            .iter()
+            .filter_map(|i| i.xref.as_deref())
+            .map(|xref| depth(xref, &parents, &mut depths))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn orphaned_records(&self) -> OrphanedRecords {
+        let mut linked: HashSet<&str> = HashSet::new();
+        let mut orphans = OrphanedRecords::default();
+        for family in &self.families {
+            let members: Vec<&str> = [&family.individual1, &family.individual2]
+                .into_iter()
+                .filter_map(|p| p.as_deref())
+                .chain(family.children.iter().map(String::as_str))
+                .collect();
+            if members.is_empty() {
+                orphans.families.extend(family.xref.clone());
+            }
+            linked.extend(members);
+        }
+        orphans.individuals = self
+            .individuals
+            .iter()
+            .filter(|i| i.families.is_empty())
+            .filter_map(|i| i.xref.as_deref())
+            .filter(|xref| !linked.contains(xref))
+            .map(str::to_string)
+            .collect();
+
+        let cited = self.cited_sources();
+        orphans.sources = self
+            .sources
+            .iter()
+            .filter_map(|s| s.xref.as_deref())
+            .filter(|xref| !cited.contains(xref))
+            .map(str::to_string)
+            .collect();
+
+        let held: HashSet<&str> = self
+            .sources
+            .iter()
+            .flat_map(|s| &s.repo_citations)
+            .map(|c| c.xref.as_str())
+            .collect();
+        orphans.repositories = self
+            .repositories
+            .iter()
+            .filter_map(|r| r.xref.as_deref())
+            .filter(|xref| !held.contains(xref))
+            .map(str::to_string)
+            .collect();
+        orphans
+    }
+
+    /// Returns the xrefs of the sources cited anywhere in the file.
+    fn cited_sources(&self) -> HashSet<&str> {
+        let mut citations: Vec<&Citation> = Vec::new();
+        for individual in &self.individuals {
+            citations.extend(&individual.source);
+            citations.extend(individual.names.iter().flat_map(|n| &n.source));
+            citations.extend(individual.sex.iter().flat_map(|s| &s.sources));
+            citations.extend(individual.events.iter().flat_map(|e| &e.citations));
+            citations.extend(individual.attributes.iter().flat_map(|a| &a.sources));
+            citations.extend(
+                individual
+                    .lds_ordinances
+                    .iter()
+                    .flat_map(|o| &o.source_citations),
+            );
+            citations.extend(
+                individual
+                    .non_events
+                    .iter()
+                    .flat_map(|n| &n.source_citations),
+            );
+        }
+        for family in &self.families {
+            citations.extend(&family.sources);
+            citations.extend(
+                family
+                    .events
+                    .iter()
+                    .chain(&family.family_event)
+                    .flat_map(|e| &e.citations),
+            );
+            citations.extend(
+                family
+                    .lds_ordinances
+                    .iter()
+                    .flat_map(|o| &o.source_citations),
+            );
+            citations.extend(family.non_events.iter().flat_map(|n| &n.source_citations));
+        }
+        citations.extend(self.shared_notes.iter().flat_map(|n| &n.source_citations));
+        citations.into_iter().map(|c| c.xref.as_str()).collect()
+    }
+
+    fn date_coverage(&self) -> DateCoverage {
+        let mut coverage = DateCoverage::default();
+        let individual_events = self.individuals.iter().flat_map(|i| &i.events);
+        let family_events = self
+            .families
+            .iter()
+            .flat_map(|f| f.events.iter().chain(&f.family_event));
+        for event in individual_events.chain(family_events) {
+            let year = event
+                .date
+                .as_ref()
+                .and_then(|d| d.value.as_deref())
+                .and_then(year_of);
+            coverage.add(year);
+        }
+        coverage
+    }
+}
+
+/// Returns the number of generations up to and including `xref`, treating
+/// a line that loops back on itself as ending there.
+fn depth<'a>(
+    xref: &'a str,
+    parents: &HashMap<&'a str, Vec<&'a str>>,
+    depths: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if let Some(&known) = depths.get(xref) {
+        return known;
+    }
+    // Placeholder while the ancestors are walked, which stops cycles.
+    depths.insert(xref, 0);
+    let above = parents
+        .get(xref)
+        .into_iter()
+        .flatten()
+        .map(|parent| depth(parent, parents, depths))
+        .max()
+        .unwrap_or(0);
+    depths.insert(xref, above + 1);
+    above + 1
+}
+
+/// Counts values, most common first and alphabetically among equals.
+fn ranked<'a>(values: impl Iterator<Item = Option<&'a str>>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values.flatten().filter(|v| !v.is_empty()) {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut ranked: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(value, count)| (value.to_string(), count))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+/// Returns the year a date names, or starts in for a range.
+pub(crate) fn year_of(value: &str) -> Option<i32> {
+    range_start(value)
+        .split_whitespace()
+        .rev()
+        .find_map(|word| word.split('/').next()?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    #[test]
+    fn test_year_of() {
+        assert_eq!(year_of("3 APR 1887"), Some(1887));
+        assert_eq!(year_of("ABT 1902"), Some(1902));
+        assert_eq!(year_of("BET 1889 AND 1891"), Some(1889));
+        assert_eq!(year_of("11 FEB 1731/32"), Some(1731));
+        assert_eq!(year_of("INT 1900 (about then)"), Some(1900));
+        assert_eq!(year_of("(unknown)"), None);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 NAME John /Doe/\n\
+            1 BIRT\n\
+            2 DATE 1820\n\
+            2 PLAC Boston\n\
+            1 DEAT\n\
+            2 DATE 1890\n\
+            0 @I2@ INDI\n\
+            1 NAME Jim /Doe/\n\
+            1 BIRT\n\
+            2 DATE ABT 1850\n\
+            2 PLAC Salem\n\
+            1 DEAT\n\
+            2 DATE 1900\n\
+            2 SOUR @S1@\n\
+            0 @I3@ INDI\n\
+            1 NAME Ann /Roe/\n\
+            1 BIRT\n\
+            2 DATE 1880\n\
+            2 PLAC Boston\n\
+            0 @I4@ INDI\n\
+            1 NAME Lone /Roe/\n\
+            1 BURI\n\
+            0 @F1@ FAM\n\
+            1 HUSB @I1@\n\
+            1 CHIL @I2@\n\
+            0 @F2@ FAM\n\
+            1 HUSB @I2@\n\
+            1 CHIL @I3@\n\
+            0 @F3@ FAM\n\
+            0 @S1@ SOUR\n\
+            1 REPO @R1@\n\
+            0 @S2@ SOUR\n\
+            0 @R1@ REPO\n\
+            1 NAME Archive\n\
+            0 @R2@ REPO\n\
+            1 NAME Library\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let statistics = data.statistics();
+
+        assert_eq!(statistics.records.individuals, 4);
+        assert_eq!(statistics.records.families, 3);
+        assert_eq!(statistics.source_citations.total, 1);
+        assert_eq!(
+            statistics.surnames,
+            vec![("Doe".to_string(), 2), ("Roe".to_string(), 2)]
+        );
+        assert_eq!(
+            statistics.birthplaces,
+            vec![("Boston".to_string(), 2), ("Salem".to_string(), 1)]
+        );
+        assert_eq!(statistics.average_lifespan, Some(60.0));
+        assert_eq!(statistics.lifespans_counted, 2);
+        assert_eq!(statistics.generation_depth, 3);
+        assert_eq!(
+            statistics.orphans,
+            OrphanedRecords {
+                individuals: vec!["@I4@".to_string()],
+                families: vec!["@F3@".to_string()],
+                sources: vec!["@S2@".to_string()],
+                repositories: vec!["@R2@".to_string()],
+            }
+        );
+        assert_eq!(
+            statistics.date_coverage,
+            DateCoverage {
+                earliest: Some(1820),
+                latest: Some(1900),
+                dated_events: 5,
+                undated_events: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_generation_depth_survives_cycles() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            0 @I2@ INDI\n\
+            0 @F1@ FAM\n\
+            1 HUSB @I1@\n\
+            1 CHIL @I2@\n\
+            0 @F2@ FAM\n\
+            1 HUSB @I2@\n\
+            1 CHIL @I1@\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert_eq!(data.statistics().generation_depth, 2);
+    }
+}
@@ -31,24 +31,41 @@
 //! `GedcomStreamParser` only buffers one record at a time. For files with many small
 //! records, memory usage stays constant regardless of file size.
 //!
+//! # Trailer Handling
+//!
+//! Files missing their `0 TRLR` trailer, or with records after it, are both
+//! seen in the wild. The streaming parser treats them like
+//! [`GedcomBuilder`](crate::GedcomBuilder): content after the trailer is
+//! skipped and the problem is recorded in [`GedcomStreamParser::warnings`],
+//! or reported as an error once [`GedcomStreamParser::strict_mode`] is set.
+//!
 //! # UTF-8 Requirement
 //!
 //! The streaming parser requires UTF-8 encoded input. For files with other encodings,
-//! either convert them to UTF-8 first, or use the in-memory parser with encoding detection.
+//! use [`GedcomStreamParser::with_decoding`], which transcodes the input as it is read
+//! through a [`DecodingReader`](crate::encoding::DecodingReader).
 
-use std::io::BufRead;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Read, Seek, SeekFrom},
+    sync::Arc,
+};
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    tokenizer::Tokenizer,
+    encoding::DecodingReader,
+    parser::TagHandlers,
+    report::{is_invisible, InvisibleCharacterPolicy, ReportCollector},
+    tokenizer::{is_trailer_line, is_trailer_padding, Tokenizer},
     types::{
         custom::UserDefinedTag, family::Family, header::Header, individual::Individual,
         multimedia::Multimedia, repository::Repository, shared_note::SharedNote, source::Source,
-        submission::Submission, submitter::Submitter, GedcomData,
+        submission::Submission, submitter::Submitter, GedcomData, RecordType,
     },
-    GedcomError,
+    util::StringInterner,
+    GedcomError, ParseWarning,
 };
 
 /// A single top-level GEDCOM record.
@@ -75,7 +92,7 @@ use crate::{
 /// }
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(clippy::large_enum_variant)]
 pub enum GedcomRecord {
     /// File header containing metadata.
@@ -94,7 +111,7 @@ pub enum GedcomRecord {
     Submission(Submission),
     /// A multimedia object record.
     Multimedia(Multimedia),
-    /// A shared note record (GEDCOM 7.0 only).
+    /// A shared note record: `SNOTE`, or `NOTE` before GEDCOM 7.0.
     SharedNote(SharedNote),
     /// A custom/user-defined record.
     CustomData(Box<UserDefinedTag>),
@@ -295,6 +312,92 @@ pub struct GedcomStreamParser<R: BufRead> {
     line_number: u32,
     /// Whether we've finished parsing
     finished: bool,
+    /// Whether the header declared GEDCOM 7.0, which changes `@` unescaping
+    gedcom_7: bool,
+    /// Whether trailer problems are errors rather than warnings
+    strict: bool,
+    /// Recoverable problems found so far
+    warnings: Vec<ParseWarning>,
+    /// The types of the records to yield, or `None` for all of them
+    record_types: Option<Vec<RecordType>>,
+    /// Bytes read so far
+    offset: u64,
+    /// Where the peeked line starts
+    peeked_position: StreamPosition,
+    /// Where the record last read starts
+    record_position: StreamPosition,
+}
+
+/// Where a record starts in the input of a [`GedcomStreamParser`].
+///
+/// Returned by [`GedcomStreamParser::index`], and given back to
+/// [`GedcomStreamParser::seek`] to read the record again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StreamPosition {
+    /// The offset in bytes of the first line of the record.
+    pub offset: u64,
+    /// The line number of the first line of the record.
+    pub line: u32,
+}
+
+impl<R: Read> GedcomStreamParser<DecodingReader<R>> {
+    /// Creates a streaming parser for input in any supported encoding.
+    ///
+    /// The encoding is detected from the first bytes (BOM, `CHAR` tag, then
+    /// byte patterns) and the input is converted to UTF-8 as records are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if an I/O error occurs while reading, or if
+    /// the first line is not valid in the detected encoding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::stream::{GedcomRecord, GedcomStreamParser};
+    ///
+    /// let bytes: &[u8] = b"0 HEAD\n1 CHAR ISO-8859-1\n0 @I1@ INDI\n1 NAME Ren\xE9 /Roy/\n0 TRLR\n";
+    /// let mut parser = GedcomStreamParser::with_decoding(bytes).unwrap();
+    /// parser.next();
+    /// let Some(Ok(GedcomRecord::Individual(indi))) = parser.next() else {
+    ///     panic!("expected an individual");
+    /// };
+    /// assert_eq!(indi.full_name().as_deref(), Some("René Roy"));
+    /// ```
+    pub fn with_decoding(reader: R) -> Result<Self, GedcomError> {
+        Self::new(DecodingReader::new(reader)?)
+    }
+}
+
+impl GedcomStreamParser<io::StdinLock<'static>> {
+    /// Creates a streaming parser reading UTF-8 records from standard input.
+    ///
+    /// Standard input stays locked, and is read through its own buffer, for
+    /// as long as the parser lives. For input in other encodings, use
+    /// `GedcomStreamParser::with_decoding(io::stdin().lock())`. See
+    /// [`GedcomWriter::write_to_stdout`](crate::GedcomWriter::write_to_stdout)
+    /// for the other end of a pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` under the same conditions as [`new`](Self::new).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ged_io::stream::{GedcomRecord, GedcomStreamParser};
+    ///
+    /// // cat family.ged | count-individuals
+    /// let parser = GedcomStreamParser::from_stdin().unwrap();
+    /// let individuals = parser
+    ///     .filter(|record| matches!(record, Ok(GedcomRecord::Individual(_))))
+    ///     .count();
+    /// println!("{individuals}");
+    /// ```
+    pub fn from_stdin() -> Result<Self, GedcomError> {
+        Self::new(io::stdin().lock())
+    }
 }
 
 impl<R: BufRead> GedcomStreamParser<R> {
@@ -324,14 +427,21 @@ impl<R: BufRead> GedcomStreamParser<R> {
         let mut first_line = String::new();
         match reader.read_line(&mut first_line) {
             Ok(0) => {
-                // Empty file
+                // Empty file, reported as missing its trailer on the first read
                 return Ok(Self {
                     reader,
                     record_buffer: String::with_capacity(4096),
                     line_buffer: String::with_capacity(256),
                     peeked_line: None,
                     line_number: 0,
-                    finished: true,
+                    finished: false,
+                    gedcom_7: false,
+                    strict: false,
+                    warnings: Vec::new(),
+                    record_types: None,
+                    offset: 0,
+                    peeked_position: StreamPosition::default(),
+                    record_position: StreamPosition::default(),
                 });
             }
             Ok(_) => {}
@@ -340,7 +450,7 @@ impl<R: BufRead> GedcomStreamParser<R> {
                 // (e.g., UTF-16). Provide a helpful error message.
                 if e.kind() == std::io::ErrorKind::InvalidData {
                     return Err(GedcomError::EncodingError(
-                        "Streaming parser requires UTF-8 input; file appears to use a different encoding (possibly UTF-16)".to_string(),
+                        "Streaming parser requires UTF-8 input; file appears to use a different encoding (use GedcomStreamParser::with_decoding)".to_string(),
                     ));
                 }
                 return Err(GedcomError::IoError(e.to_string()));
@@ -353,10 +463,11 @@ impl<R: BufRead> GedcomStreamParser<R> {
             && ((bytes[0] == 0xFF && bytes[1] == 0xFE) || (bytes[0] == 0xFE && bytes[1] == 0xFF))
         {
             return Err(GedcomError::EncodingError(
-                "Streaming parser requires UTF-8 input; UTF-16 BOM detected".to_string(),
+                "Streaming parser requires UTF-8 input; UTF-16 BOM detected (use GedcomStreamParser::with_decoding)".to_string(),
             ));
         }
 
+        let offset = first_line.len() as u64;
         // Skip UTF-8 BOM if present
         let first_line = if first_line.starts_with('\u{FEFF}') {
             first_line['\u{FEFF}'.len_utf8()..].to_string()
@@ -371,9 +482,184 @@ impl<R: BufRead> GedcomStreamParser<R> {
             peeked_line: Some(first_line),
             line_number: 1,
             finished: false,
+            gedcom_7: false,
+            strict: false,
+            warnings: Vec::new(),
+            record_types: None,
+            offset,
+            peeked_position: StreamPosition { offset: 0, line: 1 },
+            record_position: StreamPosition::default(),
         })
     }
 
+    /// Enables or disables strict mode.
+    ///
+    /// By default a missing `0 TRLR` trailer, or content after it, is
+    /// recorded in [`warnings`](Self::warnings) and the stream simply ends.
+    /// In strict mode the stream yields an error instead, as
+    /// [`GedcomBuilder::strict_mode`](crate::GedcomBuilder::strict_mode) does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::stream::GedcomStreamParser;
+    ///
+    /// let gedcom: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n";
+    /// let parser = GedcomStreamParser::new(gedcom).unwrap().strict_mode(true);
+    /// let results: Vec<_> = parser.collect();
+    /// assert!(results.last().unwrap().is_err());
+    /// ```
+    #[must_use]
+    pub fn strict_mode(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Yields only the records of the listed types, besides the header.
+    ///
+    /// The records of other types are read past without being parsed, which
+    /// saves most of the time and allocations a pass over a large file
+    /// spends on records it does not look at. The header is always yielded,
+    /// as it decides how the rest of the file is read. Records with a tag
+    /// that names no record type are still parsed, so that they are
+    /// reported as errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{stream::GedcomStreamParser, RecordType};
+    ///
+    /// let gedcom: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @S1@ SOUR\n1 TITL Census\n\
+    ///     0 @I1@ INDI\n1 NAME John /Doe/\n0 @F1@ FAM\n0 TRLR\n";
+    /// let records: Vec<_> = GedcomStreamParser::new(gedcom)
+    ///     .unwrap()
+    ///     .filter_records(&[RecordType::Individual, RecordType::Family])
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(records.len(), 3);
+    /// assert!(records[1].is_individual() && records[2].is_family());
+    /// ```
+    #[must_use]
+    pub fn filter_records(mut self, keep: &[RecordType]) -> Self {
+        self.record_types = Some(keep.to_vec());
+        self
+    }
+
+    /// Reads on to the record with `xref` and returns it, or `None` if no
+    /// record after the current position has it.
+    ///
+    /// Only the header and the record found are parsed; the records before
+    /// it are read past. The stream goes on after the record found, so
+    /// several records can be found in the order of the file. To reach
+    /// records in any order, build an [`index`](Self::index) first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if reading fails, or if the header or the
+    /// record found fails to parse.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::stream::GedcomStreamParser;
+    ///
+    /// let gedcom: &[u8] = b"0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n\
+    ///     0 @I2@ INDI\n1 NAME Jane /Doe/\n0 TRLR\n";
+    /// let mut parser = GedcomStreamParser::new(gedcom).unwrap();
+    /// let record = parser.find_record("@I2@").unwrap().unwrap();
+    /// let individual = record.as_individual().unwrap();
+    /// assert_eq!(individual.full_name().as_deref(), Some("Jane Doe"));
+    /// assert!(parser.find_record("@I1@").unwrap().is_none());
+    /// ```
+    pub fn find_record(&mut self, xref: &str) -> Result<Option<GedcomRecord>, GedcomError> {
+        if self.finished {
+            return Ok(None);
+        }
+        while let Some(text) = self.read_next_record()? {
+            let (found, tag) = record_head(&text);
+            if found == Some(xref) || tag == "HEAD" {
+                let record = self.parse(&text)?;
+                if found == Some(xref) {
+                    return Ok(Some(record));
+                }
+            }
+        }
+        self.finished = true;
+        Ok(None)
+    }
+
+    /// Reads the rest of the input and returns where each record with an
+    /// xref starts, keyed by xref.
+    ///
+    /// Only the header is parsed. Afterwards the stream is at its end;
+    /// when the reader can seek, [`seek`](Self::seek) goes back to any
+    /// record in the index. Offsets count the bytes the parser read, so they
+    /// are offsets into the file for UTF-8 input, but not for input read
+    /// through [`with_decoding`](GedcomStreamParser::with_decoding).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if reading fails or the header fails to parse.
+    pub fn index(&mut self) -> Result<HashMap<String, StreamPosition>, GedcomError> {
+        let mut index = HashMap::new();
+        if self.finished {
+            return Ok(index);
+        }
+        while let Some(text) = self.read_next_record()? {
+            match record_head(&text) {
+                (Some(xref), _) => {
+                    index
+                        .entry(xref.to_string())
+                        .or_insert(self.record_position);
+                }
+                (None, "HEAD") => {
+                    self.parse(&text)?;
+                }
+                (None, _) => {}
+            }
+        }
+        self.finished = true;
+        Ok(index)
+    }
+
+    /// Parses the text of one record, noting the GEDCOM version of a header.
+    fn parse(&mut self, text: &str) -> Result<GedcomRecord, GedcomError> {
+        let record = parse_record_text(text, self.gedcom_7, self.line_number)?;
+        if let GedcomRecord::Header(header) = &record {
+            self.gedcom_7 = header.is_gedcom_7();
+        }
+        Ok(record)
+    }
+
+    /// Returns whether the record with `text` is to be parsed and yielded.
+    fn is_wanted(&self, text: &str) -> bool {
+        let Some(keep) = &self.record_types else {
+            return true;
+        };
+        let (_, tag) = record_head(text);
+        let record_type = match tag {
+            "INDI" => RecordType::Individual,
+            "FAM" => RecordType::Family,
+            "SOUR" => RecordType::Source,
+            "REPO" => RecordType::Repository,
+            "OBJE" => RecordType::Multimedia,
+            "SUBM" => RecordType::Submitter,
+            "SUBN" => RecordType::Submission,
+            "SNOTE" | "NOTE" => RecordType::SharedNote,
+            _ if tag.starts_with('_') => RecordType::Custom,
+            _ => return true,
+        };
+        keep.contains(&record_type)
+    }
+
+    /// Returns the recoverable problems found so far.
+    ///
+    /// Trailer problems are only known once the stream has ended.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
     /// Reads the next complete record from the stream.
     ///
     /// Returns the record text and whether we hit TRLR or EOF.
@@ -382,23 +668,30 @@ impl<R: BufRead> GedcomStreamParser<R> {
 
         // Start with peeked line or read a new one
         let first_line = if let Some(line) = self.peeked_line.take() {
+            self.record_position = self.peeked_position;
             line
         } else {
             self.line_buffer.clear();
+            self.record_position = StreamPosition {
+                offset: self.offset,
+                line: self.line_number + 1,
+            };
             match self.reader.read_line(&mut self.line_buffer) {
-                Ok(0) => return Ok(None),
-                Ok(_) => {
+                Ok(0) => return self.end_of_input(Some(ParseWarning::MissingTrailer)),
+                Ok(read) => {
                     self.line_number += 1;
+                    self.offset += read as u64;
                     std::mem::take(&mut self.line_buffer)
                 }
                 Err(e) => return Err(GedcomError::IoError(e.to_string())),
             }
         };
 
-        // Check if this is TRLR
-        let trimmed = first_line.trim();
-        if trimmed == "0 TRLR" || trimmed.starts_with("0 TRLR ") {
-            return Ok(None); // End of file
+        if is_trailer_line(&first_line) {
+            let warning = self
+                .content_after_trailer()?
+                .map(|line| ParseWarning::ContentAfterTrailer { line });
+            return self.end_of_input(warning);
         }
 
         // Start accumulating the record
@@ -407,10 +700,15 @@ impl<R: BufRead> GedcomStreamParser<R> {
         // Read until we hit another level 0 line or EOF
         loop {
             self.line_buffer.clear();
+            let position = StreamPosition {
+                offset: self.offset,
+                line: self.line_number + 1,
+            };
             match self.reader.read_line(&mut self.line_buffer) {
                 Ok(0) => break, // EOF
-                Ok(_) => {
+                Ok(read) => {
                     self.line_number += 1;
+                    self.offset += read as u64;
 
                     // Check if this line starts a new level 0 record
                     let trimmed = self.line_buffer.trim_start();
@@ -419,6 +717,7 @@ impl<R: BufRead> GedcomStreamParser<R> {
                         if second_char.is_whitespace() {
                             // This is a new level 0 record - save it for next iteration
                             self.peeked_line = Some(std::mem::take(&mut self.line_buffer));
+                            self.peeked_position = position;
                             break;
                         }
                     }
@@ -433,90 +732,304 @@ impl<R: BufRead> GedcomStreamParser<R> {
         Ok(Some(std::mem::take(&mut self.record_buffer)))
     }
 
-    /// Parses a record text into a `GedcomRecord`.
-    fn parse_record_text(&self, text: &str) -> Result<GedcomRecord, GedcomError> {
-        use crate::tokenizer::Token;
+    /// Reads past the trailer, returning the line number of the first non-blank line.
+    fn content_after_trailer(&mut self) -> Result<Option<u32>, GedcomError> {
+        loop {
+            self.line_buffer.clear();
+            match self.reader.read_line(&mut self.line_buffer) {
+                Ok(0) => return Ok(None),
+                Ok(read) => {
+                    self.line_number += 1;
+                    self.offset += read as u64;
+                    if !self.line_buffer.chars().all(is_trailer_padding) {
+                        return Ok(Some(self.line_number));
+                    }
+                }
+                // Binary junk after the trailer is content like any other.
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                    return Ok(Some(self.line_number + 1));
+                }
+                Err(e) => return Err(GedcomError::IoError(e.to_string())),
+            }
+        }
+    }
 
-        let doc_text = format!("{text}0 TRLR\n");
+    fn end_of_input(
+        &mut self,
+        warning: Option<ParseWarning>,
+    ) -> Result<Option<String>, GedcomError> {
+        end_of_input(warning, self.strict, &mut self.warnings)?;
+        Ok(None)
+    }
+}
 
-        let mut tokenizer = Tokenizer::new(doc_text.chars());
-        tokenizer.next_token()?;
+impl<R: BufRead + Seek> GedcomStreamParser<R> {
+    /// Moves the stream to a record found by [`index`](Self::index), which
+    /// the next call to [`next`](Iterator::next) yields.
+    ///
+    /// The stream goes on from there to the end of the input, and can be
+    /// moved again at any time. Records keep being parsed by the rules of
+    /// the header read before, so the header must have been read, as
+    /// `index` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if the reader fails to seek.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use ged_io::stream::GedcomStreamParser;
+    ///
+    /// let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n\
+    ///     0 @I2@ INDI\n1 NAME Jane /Doe/\n0 TRLR\n";
+    /// let mut parser = GedcomStreamParser::new(Cursor::new(gedcom)).unwrap();
+    /// let index = parser.index().unwrap();
+    ///
+    /// for xref in ["@I2@", "@I1@"] {
+    ///     parser.seek(index[xref]).unwrap();
+    ///     let record = parser.next().unwrap().unwrap();
+    ///     assert_eq!(record.as_individual().unwrap().xref.as_deref(), Some(xref));
+    /// }
+    /// ```
+    pub fn seek(&mut self, position: StreamPosition) -> Result<(), GedcomError> {
+        self.reader
+            .seek(SeekFrom::Start(position.offset))
+            .map_err(|e| GedcomError::IoError(e.to_string()))?;
+        self.offset = position.offset;
+        self.line_number = position.line - 1;
+        self.peeked_line = None;
+        self.finished = false;
+        Ok(())
+    }
+}
 
-        let Token::Level(level) = tokenizer.current_token else {
-            if tokenizer.current_token == Token::EOF {
-                return Err(GedcomError::ParseError {
-                    line: self.line_number,
-                    message: "Empty record".to_string(),
+/// Returns the xref and the tag of the first line of a record.
+fn record_head(text: &str) -> (Option<&str>, &str) {
+    let mut fields = text.split_whitespace().skip(1);
+    let tag = fields.next().unwrap_or_default();
+    if tag.starts_with('@') {
+        (Some(tag), fields.next().unwrap_or_default())
+    } else {
+        (None, tag)
+    }
+}
+
+/// Applies the strict mode policy to a trailer problem found at the end of the input.
+///
+/// Shared by the streaming parsers so that they agree with each other and with
+/// [`GedcomBuilder`](crate::GedcomBuilder).
+pub(crate) fn end_of_input(
+    warning: Option<ParseWarning>,
+    strict: bool,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(), GedcomError> {
+    match warning {
+        Some(warning) if strict => Err(warning.to_error()),
+        Some(warning) => {
+            warnings.push(warning);
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Parses the text of one level 0 record into a `GedcomRecord`.
+///
+/// `line_number` is the last line of the record, used in error messages.
+pub(crate) fn parse_record_text(
+    text: &str,
+    gedcom_7: bool,
+    line_number: u32,
+) -> Result<GedcomRecord, GedcomError> {
+    let mut tokenizer = Tokenizer::with_version(text.chars(), gedcom_7);
+    parse_record(&mut tokenizer, line_number)
+}
+
+/// How the records split from a file are tokenized.
+pub(crate) struct RecordSettings<'a> {
+    /// What to do with invisible characters.
+    pub(crate) invisible_characters: InvisibleCharacterPolicy,
+    /// The handlers offered each custom structure.
+    pub(crate) tag_handlers: &'a TagHandlers,
+    /// Where repeated values are shared, if they are.
+    pub(crate) interner: Option<&'a Arc<StringInterner>>,
+}
+
+/// Parses the text of one level 0 record, adding what was gathered for the
+/// parse report to `report` and numbering the record's first line
+/// `first_line` as the tokenizer numbers lines and its first byte
+/// `first_offset`.
+///
+/// On error `report` may hold part of the record.
+pub(crate) fn parse_record_text_with_report(
+    text: &str,
+    gedcom_7: bool,
+    settings: &RecordSettings,
+    line_number: u32,
+    first_line: u32,
+    first_offset: usize,
+    report: &mut ReportCollector,
+) -> Result<GedcomRecord, GedcomError> {
+    use crate::tokenizer::Token;
+
+    let mut tokenizer = Tokenizer::with_version(text.chars(), gedcom_7);
+    tokenizer.set_invisible_characters(settings.invisible_characters);
+    tokenizer.set_tag_handlers(settings.tag_handlers.clone());
+    tokenizer.set_interner(settings.interner.cloned());
+    tokenizer.set_position(first_line - 1, first_offset);
+    tokenizer.set_report(std::mem::take(report));
+    let result = parse_record(&mut tokenizer, line_number).and_then(|record| {
+        // The sequential parser skips, and so counts, what the record parser
+        // leaves unread, such as the rest of a user-defined record.
+        while !matches!(tokenizer.current_token, Token::Level(0) | Token::EOF) {
+            tokenizer.next_token()?;
+        }
+        Ok(record)
+    });
+    *report = tokenizer.take_report();
+    result
+}
+
+/// The text of one level 0 record and the line it starts on.
+pub(crate) struct RecordText<'a> {
+    pub(crate) line: u32,
+    /// The line it starts on, not counting blank lines, as the tokenizer
+    /// numbers them.
+    pub(crate) tag_line: u32,
+    /// Its offset in bytes from the start of the input.
+    pub(crate) offset: usize,
+    pub(crate) text: &'a str,
+}
+
+/// Splits GEDCOM text into level 0 records, stopping at the trailer.
+pub(crate) fn split_records(content: &str) -> (Vec<RecordText<'_>>, Option<ParseWarning>) {
+    let stripped = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    let bom = content.len() - stripped.len();
+    let content = stripped;
+
+    let mut records = Vec::new();
+    let mut start: Option<(usize, u32, u32)> = None;
+    let mut offset = 0;
+    let mut tag_line = 0;
+    let mut lines = content.split_inclusive('\n').zip(1..);
+
+    for (line, number) in lines.by_ref() {
+        // Files joined together can open a record with a stray byte order mark.
+        let trimmed = line.trim_start_matches(|c: char| c.is_whitespace() || is_invisible(c));
+        let starts_record =
+            trimmed.starts_with('0') && trimmed[1..].starts_with(char::is_whitespace);
+        if starts_record {
+            if let Some((begin, first_line, first_tag_line)) = start.take() {
+                records.push(RecordText {
+                    line: first_line,
+                    tag_line: first_tag_line,
+                    offset: bom + begin,
+                    text: &content[begin..offset],
                 });
             }
-            return Err(GedcomError::ParseError {
-                line: self.line_number,
-                message: format!("Expected Level, found {:?}", tokenizer.current_token),
-            });
-        };
+            if is_trailer_line(line) {
+                let after = lines
+                    .find(|(line, _)| !line.chars().all(is_trailer_padding))
+                    .map(|(_, line)| ParseWarning::ContentAfterTrailer { line });
+                return (records, after);
+            }
+        }
+        if !trimmed.is_empty() {
+            tag_line += 1;
+        }
+        if start.is_none() {
+            start = Some((offset, number, tag_line));
+        }
+        offset += line.len();
+    }
+
+    if let Some((begin, first_line, first_tag_line)) = start {
+        records.push(RecordText {
+            line: first_line,
+            tag_line: first_tag_line,
+            offset: bom + begin,
+            text: &content[begin..],
+        });
+    }
+    (records, Some(ParseWarning::MissingTrailer))
+}
 
-        if level != 0 {
+/// Parses one level 0 record from a tokenizer at the start of its input.
+fn parse_record(tokenizer: &mut Tokenizer, line_number: u32) -> Result<GedcomRecord, GedcomError> {
+    use crate::tokenizer::Token;
+
+    tokenizer.next_token()?;
+
+    let Token::Level(level) = tokenizer.current_token else {
+        if tokenizer.current_token == Token::EOF {
             return Err(GedcomError::ParseError {
-                line: self.line_number,
-                message: format!("Expected level 0, found level {level}"),
+                line: line_number,
+                message: "Empty record".to_string(),
             });
         }
+        return Err(GedcomError::ParseError {
+            line: line_number,
+            message: format!("Expected Level, found {:?}", tokenizer.current_token),
+        });
+    };
+
+    if level != 0 {
+        return Err(GedcomError::ParseError {
+            line: line_number,
+            message: format!("Expected level 0, found level {level}"),
+        });
+    }
 
-        tokenizer.next_token()?;
+    tokenizer.next_token()?;
 
-        let mut pointer: Option<String> = None;
-        if let Token::Pointer(xref) = &tokenizer.current_token {
-            pointer = Some(xref.to_string());
-            tokenizer.next_token()?;
-        }
+    let mut pointer: Option<String> = None;
+    if let Token::Pointer(xref) = &tokenizer.current_token {
+        pointer = Some(xref.to_string());
+        tokenizer.next_token()?;
+    }
 
-        if let Token::Tag(tag) = &tokenizer.current_token {
-            let record = match tag.as_ref() {
-                "HEAD" => GedcomRecord::Header(Header::new(&mut tokenizer, 0)?),
-                "FAM" => GedcomRecord::Family(Family::new(&mut tokenizer, 0, pointer)?),
-                "INDI" => {
-                    GedcomRecord::Individual(Individual::new(&mut tokenizer, level, pointer)?)
-                }
-                "REPO" => {
-                    GedcomRecord::Repository(Repository::new(&mut tokenizer, level, pointer)?)
-                }
-                "SOUR" => GedcomRecord::Source(Source::new(&mut tokenizer, level, pointer)?),
-                "SUBN" => GedcomRecord::Submission(Submission::new(&mut tokenizer, 0, pointer)?),
-                "SUBM" => GedcomRecord::Submitter(Submitter::new(&mut tokenizer, 0, pointer)?),
-                "OBJE" => GedcomRecord::Multimedia(Multimedia::new(&mut tokenizer, 0, pointer)?),
-                "SNOTE" => GedcomRecord::SharedNote(SharedNote::new(&mut tokenizer, 0, pointer)?),
-                "TRLR" => {
-                    return Err(GedcomError::ParseError {
-                        line: self.line_number,
-                        message: "Unexpected TRLR".to_string(),
-                    });
-                }
-                _ => {
-                    return Err(GedcomError::ParseError {
-                        line: self.line_number,
-                        message: format!("Unhandled tag {tag}"),
-                    });
-                }
-            };
-            Ok(record)
-        } else if let Token::CustomTag(tag) = &tokenizer.current_token {
-            let tag_clone = tag.clone();
-            Ok(GedcomRecord::CustomData(Box::new(UserDefinedTag::new(
-                &mut tokenizer,
-                1,
-                &tag_clone,
-            )?)))
-        } else if tokenizer.current_token == Token::EOF {
-            Err(GedcomError::ParseError {
-                line: self.line_number,
-                message: "Unexpected EOF".to_string(),
-            })
-        } else {
-            Err(GedcomError::ParseError {
-                line: self.line_number,
-                message: format!("Unhandled token {:?}", tokenizer.current_token),
-            })
-        }
+    if let Token::Tag(tag) = &tokenizer.current_token {
+        let record = match tag.as_ref() {
+            "HEAD" => GedcomRecord::Header(Header::new(tokenizer, 0)?),
+            "FAM" => GedcomRecord::Family(Family::new(tokenizer, 0, pointer)?),
+            "INDI" => GedcomRecord::Individual(Individual::new(tokenizer, level, pointer)?),
+            "REPO" => GedcomRecord::Repository(Repository::new(tokenizer, level, pointer)?),
+            "SOUR" => GedcomRecord::Source(Source::new(tokenizer, level, pointer)?),
+            "SUBN" => GedcomRecord::Submission(Submission::new(tokenizer, 0, pointer)?),
+            "SUBM" => GedcomRecord::Submitter(Submitter::new(tokenizer, 0, pointer)?),
+            "OBJE" => GedcomRecord::Multimedia(Multimedia::new(tokenizer, 0, pointer)?),
+            "SNOTE" | "NOTE" => GedcomRecord::SharedNote(SharedNote::new(tokenizer, 0, pointer)?),
+            "TRLR" => {
+                return Err(GedcomError::ParseError {
+                    line: line_number,
+                    message: "Unexpected TRLR".to_string(),
+                });
+            }
+            _ => {
+                return Err(GedcomError::ParseError {
+                    line: line_number,
+                    message: format!("Unhandled tag {tag}"),
+                });
+            }
+        };
+        Ok(record)
+    } else if let Token::CustomTag(tag) = &tokenizer.current_token {
+        let tag_clone = tag.clone();
+        Ok(GedcomRecord::CustomData(Box::new(UserDefinedTag::new(
+            tokenizer, 1, &tag_clone,
+        )?)))
+    } else if tokenizer.current_token == Token::EOF {
+        Err(GedcomError::ParseError {
+            line: line_number,
+            message: "Unexpected EOF".to_string(),
+        })
+    } else {
+        Err(GedcomError::ParseError {
+            line: line_number,
+            message: format!("Unhandled token {:?}", tokenizer.current_token),
+        })
     }
 }
 
@@ -528,8 +1041,12 @@ impl<R: BufRead> Iterator for GedcomStreamParser<R> {
             return None;
         }
 
-        match self.read_next_record() {
-            Ok(Some(text)) => match self.parse_record_text(&text) {
+        let mut next = self.read_next_record();
+        while matches!(&next, Ok(Some(text)) if !self.is_wanted(text)) {
+            next = self.read_next_record();
+        }
+        match next {
+            Ok(Some(text)) => match self.parse(&text) {
                 Ok(record) => Some(Ok(record)),
                 Err(e) => {
                     self.finished = true;
@@ -566,6 +1083,7 @@ impl FromIterator<GedcomRecord> for GedcomData {
                 GedcomRecord::CustomData(c) => data.add_custom_data(*c),
             }
         }
+        data.apply_child_relationships();
         data
     }
 }
@@ -575,6 +1093,88 @@ mod tests {
     use super::*;
     use std::io::BufReader;
 
+    #[test]
+    fn test_split_records() {
+        let content = "\u{FEFF}0 HEAD\n1 GEDC\n0 @I1@ INDI\n1 NAME A /B/\n0 TRLR\n\n0 @I2@ INDI\n";
+        let (records, warning) = split_records(content);
+
+        let texts: Vec<(u32, &str)> = records.iter().map(|r| (r.line, r.text)).collect();
+        assert_eq!(
+            texts,
+            vec![(1, "0 HEAD\n1 GEDC\n"), (3, "0 @I1@ INDI\n1 NAME A /B/\n")]
+        );
+        assert_eq!(warning, Some(ParseWarning::ContentAfterTrailer { line: 7 }));
+
+        let (records, warning) = split_records("0 HEAD\n1 GEDC");
+        assert_eq!(records.len(), 1);
+        assert_eq!(warning, Some(ParseWarning::MissingTrailer));
+    }
+
+    #[test]
+    fn test_stream_parser_filter_records() {
+        let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NAME John /Doe/\n\
+            0 @S1@ SOUR\n1 TITL Census\n0 @N1@ NOTE Shared\n0 _PLAC Paris\n\
+            0 @F1@ FAM\n1 HUSB @I1@\n0 TRLR\n";
+        let parser = GedcomStreamParser::new(gedcom.as_bytes())
+            .unwrap()
+            .filter_records(&[RecordType::Family, RecordType::Custom]);
+        let records: Vec<GedcomRecord> = parser.collect::<Result<_, _>>().unwrap();
+        assert!(records[0].is_header());
+        assert!(matches!(records[1], GedcomRecord::CustomData(_)));
+        assert!(records[2].is_family());
+        assert_eq!(records.len(), 3);
+
+        // Unknown record tags still fail.
+        let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @X1@ BOGUS\n0 TRLR\n";
+        let results: Vec<_> = GedcomStreamParser::new(gedcom.as_bytes())
+            .unwrap()
+            .filter_records(&[RecordType::Individual])
+            .collect();
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn test_stream_parser_find_and_seek() {
+        let gedcom = "\u{FEFF}0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NAME John@@ /Doe/\n\
+            0 @S1@ SOUR\n1 TITL Census\n0 @I2@ INDI\n1 NAME Jane /Doe/\n0 TRLR\n";
+        let mut parser = GedcomStreamParser::new(gedcom.as_bytes()).unwrap();
+        let source = parser.find_record("@S1@").unwrap().unwrap();
+        assert!(matches!(source, GedcomRecord::Source(_)));
+        assert!(parser.next().unwrap().unwrap().is_individual());
+        assert!(parser.find_record("@I1@").unwrap().is_none());
+
+        let mut parser = GedcomStreamParser::new(io::Cursor::new(gedcom)).unwrap();
+        let index = parser.index().unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(
+            index["@I1@"],
+            StreamPosition {
+                offset: 28,
+                line: 4
+            }
+        );
+        assert_eq!(
+            index["@I2@"],
+            StreamPosition {
+                offset: 86,
+                line: 8
+            }
+        );
+        assert!(parser.next().is_none());
+
+        parser.seek(index["@I1@"]).unwrap();
+        let Some(Ok(GedcomRecord::Individual(individual))) = parser.next() else {
+            panic!("expected an individual");
+        };
+        // The header read by the index decides how `@@` is unescaped.
+        assert_eq!(individual.full_name().as_deref(), Some("John@@ Doe"));
+        assert!(matches!(parser.next(), Some(Ok(GedcomRecord::Source(_)))));
+
+        parser.seek(index["@S1@"]).unwrap();
+        let records: Vec<_> = parser.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+    }
+
     #[test]
     fn test_stream_parser_basic() {
         let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
@@ -587,6 +1187,19 @@ mod tests {
         assert!(records[1].is_individual());
     }
 
+    #[test]
+    fn test_stream_parser_with_decoding_utf16() {
+        let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME Zoë /Doe/\n0 TRLR\n";
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(gedcom.encode_utf16().flat_map(u16::to_le_bytes));
+
+        assert!(GedcomStreamParser::new(BufReader::new(bytes.as_slice())).is_err());
+
+        let parser = GedcomStreamParser::with_decoding(bytes.as_slice()).unwrap();
+        let data: GedcomData = parser.collect::<Result<GedcomData, _>>().unwrap();
+        assert_eq!(data.individuals[0].full_name().as_deref(), Some("Zoë Doe"));
+    }
+
     #[test]
     fn test_stream_parser_multiple_records() {
         let gedcom = "\
@@ -731,7 +1344,7 @@ mod tests {
 
         assert_eq!(records.len(), 2);
         let indi = records[1].as_individual().unwrap();
-        assert!(indi.note.is_some());
+        assert!(!indi.notes.is_empty());
     }
 
     #[test]
@@ -769,10 +1382,31 @@ mod tests {
 
         assert_eq!(records.len(), 2); // Header + Custom
         if let GedcomRecord::CustomData(c) = &records[1] {
-            assert_eq!(c.tag, "_CUSTOM");
+            assert_eq!(&*c.tag, "_CUSTOM");
             assert_eq!(c.value.as_deref(), Some("MyValue"));
         } else {
             panic!("Expected CustomData");
         }
     }
+
+    #[test]
+    fn test_stream_parser_unescapes_by_header_version() {
+        let gedcom = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NOTE a@@b\n0 TRLR\n";
+        let reader = BufReader::new(gedcom.as_bytes());
+        let data: GedcomData = GedcomStreamParser::new(reader)
+            .unwrap()
+            .collect::<Result<GedcomData, _>>()
+            .unwrap();
+        let note = &data.individuals[0].notes[0];
+        assert_eq!(note.inline().unwrap().value.as_deref(), Some("a@@b"));
+
+        let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NOTE a@@b\n0 TRLR\n";
+        let reader = BufReader::new(gedcom.as_bytes());
+        let data: GedcomData = GedcomStreamParser::new(reader)
+            .unwrap()
+            .collect::<Result<GedcomData, _>>()
+            .unwrap();
+        let note = &data.individuals[0].notes[0];
+        assert_eq!(note.inline().unwrap().value.as_deref(), Some("a@b"));
+    }
 }
@@ -0,0 +1,239 @@
+//! Extracting one branch of a tree.
+//!
+//! [`GedcomData::extract_subtree`] returns a new dataset holding the
+//! relatives of one individual: their descendants, their ancestors, or
+//! both, a chosen number of generations deep, optionally with the spouses
+//! of the descendants and the sources and media the records cite. Pointers
+//! to records left out are removed, so the result can be written and shared
+//! as it is.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::{subtree::SubtreeOptions, GedcomBuilder};
+//!
+//! let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+//!     0 @I1@ INDI\n1 NAME Grandma /Doe/\n1 FAMS @F1@\n\
+//!     0 @I2@ INDI\n1 NAME Mum /Doe/\n1 FAMC @F1@\n1 FAMS @F2@\n\
+//!     0 @I3@ INDI\n1 NAME Dad /Roe/\n1 FAMS @F2@\n\
+//!     0 @I4@ INDI\n1 NAME Kid /Roe/\n1 FAMC @F2@\n\
+//!     0 @I5@ INDI\n1 NAME Stranger\n\
+//!     0 @F1@ FAM\n1 WIFE @I1@\n1 CHIL @I2@\n\
+//!     0 @F2@ FAM\n1 HUSB @I3@\n1 WIFE @I2@\n1 CHIL @I4@\n0 TRLR";
+//! let data = GedcomBuilder::new().build_from_str(source).unwrap();
+//!
+//! let branch = data.extract_subtree("@I1@", &SubtreeOptions::default()).unwrap();
+//! assert_eq!(branch.individuals.len(), 4);
+//! assert!(branch.find_individual("@I5@").is_none());
+//!
+//! let options = SubtreeOptions {
+//!     include_spouses: false,
+//!     ..SubtreeOptions::default()
+//! };
+//! let branch = data.extract_subtree("@I1@", &options).unwrap();
+//! assert_eq!(branch.individuals.len(), 3);
+//! assert_eq!(branch.find_family("@F2@").unwrap().individual1, None);
+//! ```
+
+use std::collections::VecDeque;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{types::GedcomData, GedcomError};
+
+/// What [`GedcomData::extract_subtree`] includes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct SubtreeOptions {
+    /// Generations of ancestors to include, or `None` for all of them
+    /// (default: 0).
+    pub ancestor_generations: Option<u32>,
+    /// Generations of descendants to include, or `None` for all of them
+    /// (default: `None`).
+    pub descendant_generations: Option<u32>,
+    /// Whether to include the spouses of the root and its descendants
+    /// (default: true). The families are included either way.
+    pub include_spouses: bool,
+    /// Whether to include the sources, repositories, multimedia and shared
+    /// note records cited by the included records (default: true).
+    pub include_dependencies: bool,
+}
+
+impl Default for SubtreeOptions {
+    fn default() -> Self {
+        Self {
+            ancestor_generations: Some(0),
+            descendant_generations: None,
+            include_spouses: true,
+            include_dependencies: true,
+        }
+    }
+}
+
+impl GedcomData {
+    /// Returns a new dataset with the individual `root` and the relatives
+    /// selected by `options`, the families that link them, and the header of
+    /// this one.
+    ///
+    /// Ancestors are followed through every family the individuals are a
+    /// child in, including adoptive ones. Records keep their xrefs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` if there is no individual `root`.
+    pub fn extract_subtree(
+        &self,
+        root: &str,
+        options: &SubtreeOptions,
+    ) -> Result<GedcomData, GedcomError> {
+        if self.find_individual(root).is_none() {
+            return Err(GedcomError::InvalidFormat(format!(
+                "No individual with xref {root}"
+            )));
+        }
+        let within = |limit: Option<u32>, generation: u32| limit.is_none_or(|n| generation < n);
+
+        let mut individuals: Vec<&str> = vec![root];
+        let mut families: Vec<&str> = Vec::new();
+
+        let mut queue = VecDeque::from([(root, 0)]);
+        while let Some((xref, generation)) = queue.pop_front() {
+            if !within(options.ancestor_generations, generation) {
+                continue;
+            }
+            for family in self.get_families_as_child(xref) {
+                let Some(family_xref) = family.xref.as_deref() else {
+                    continue;
+                };
+                add(&mut families, family_xref);
+                for parent in [&family.individual1, &family.individual2] {
+                    if let Some(parent) = parent.as_deref() {
+                        if self.find_individual(parent).is_some() && add(&mut individuals, parent) {
+                            queue.push_back((parent, generation + 1));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut queue = VecDeque::from([(root, 0)]);
+        while let Some((xref, generation)) = queue.pop_front() {
+            let more = within(options.descendant_generations, generation);
+            if !more && !options.include_spouses {
+                continue;
+            }
+            for family in self.get_families_as_spouse(xref) {
+                let Some(family_xref) = family.xref.as_deref() else {
+                    continue;
+                };
+                add(&mut families, family_xref);
+                if options.include_spouses {
+                    for spouse in [&family.individual1, &family.individual2] {
+                        if let Some(spouse) = spouse.as_deref() {
+                            if self.find_individual(spouse).is_some() {
+                                add(&mut individuals, spouse);
+                            }
+                        }
+                    }
+                }
+                if !more {
+                    continue;
+                }
+                for child in &family.children {
+                    if self.find_individual(child).is_some() && add(&mut individuals, child) {
+                        queue.push_back((child, generation + 1));
+                    }
+                }
+            }
+        }
+
+        individuals.extend(families);
+        self.extract_records(&individuals, options.include_dependencies)
+    }
+}
+
+/// Adds `xref` to `list` unless it is there already, and returns whether it
+/// was added.
+fn add<'a>(list: &mut Vec<&'a str>, xref: &'a str) -> bool {
+    let new = !list.contains(&xref);
+    if new {
+        list.push(xref);
+    }
+    new
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    const SAMPLE: &str = "\
+        0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 SUBM @U1@\n\
+        0 @U1@ SUBM\n1 NAME Submitter\n\
+        0 @I1@ INDI\n1 NAME Great /Doe/\n1 FAMS @F1@\n\
+        0 @I2@ INDI\n1 NAME Grandpa /Doe/\n1 FAMC @F1@\n1 FAMS @F2@\n\
+        0 @I3@ INDI\n1 NAME Grandma /Roe/\n1 FAMS @F2@\n\
+        0 @I4@ INDI\n1 NAME Dad /Doe/\n1 FAMC @F2@\n1 FAMS @F3@\n1 SOUR @S1@\n\
+        0 @I5@ INDI\n1 NAME Mum /Poe/\n1 FAMS @F3@\n\
+        0 @I6@ INDI\n1 NAME Kid /Doe/\n1 FAMC @F3@\n\
+        0 @F1@ FAM\n1 HUSB @I1@\n1 CHIL @I2@\n\
+        0 @F2@ FAM\n1 HUSB @I2@\n1 WIFE @I3@\n1 CHIL @I4@\n\
+        0 @F3@ FAM\n1 HUSB @I4@\n1 WIFE @I5@\n1 CHIL @I6@\n\
+        0 @S1@ SOUR\n1 TITL Register\n\
+        0 TRLR";
+
+    fn xrefs(data: &GedcomData) -> Vec<&str> {
+        let mut xrefs: Vec<&str> = data
+            .individuals
+            .iter()
+            .filter_map(|i| i.xref.as_deref())
+            .collect();
+        xrefs.sort_unstable();
+        xrefs
+    }
+
+    #[test]
+    fn test_extract_descendants() {
+        let data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let options = SubtreeOptions {
+            descendant_generations: Some(1),
+            include_dependencies: false,
+            ..SubtreeOptions::default()
+        };
+        let branch = data.extract_subtree("@I2@", &options).unwrap();
+        // Dad's wife is included as a spouse, but not their child.
+        assert_eq!(xrefs(&branch), vec!["@I2@", "@I3@", "@I4@", "@I5@"]);
+        assert_eq!(branch.families.len(), 2);
+        assert!(branch.find_family("@F3@").unwrap().children.is_empty());
+        assert!(branch.sources.is_empty());
+        assert!(branch.find_individual("@I4@").unwrap().source.is_empty());
+        assert_eq!(branch.submitters.len(), 1);
+
+        let branch = data
+            .extract_subtree("@I2@", &SubtreeOptions::default())
+            .unwrap();
+        assert_eq!(branch.individuals.len(), 5);
+        assert_eq!(branch.sources.len(), 1);
+        // The link to the family Grandpa was born in is gone.
+        let grandpa = branch.find_individual("@I2@").unwrap();
+        assert_eq!(grandpa.families.len(), 1);
+    }
+
+    #[test]
+    fn test_extract_ancestors() {
+        let data = GedcomBuilder::new().build_from_str(SAMPLE).unwrap();
+        let options = SubtreeOptions {
+            ancestor_generations: Some(2),
+            descendant_generations: Some(0),
+            include_spouses: false,
+            include_dependencies: false,
+        };
+        let branch = data.extract_subtree("@I6@", &options).unwrap();
+        assert_eq!(xrefs(&branch), vec!["@I2@", "@I3@", "@I4@", "@I5@", "@I6@"]);
+        assert_eq!(branch.families.len(), 2);
+
+        let result = data.extract_subtree("@I9@", &options);
+        assert!(matches!(result, Err(GedcomError::InvalidFormat(_))));
+    }
+}
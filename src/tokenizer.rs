@@ -6,9 +6,17 @@
 //! Both tokenizers implement the [`TokenizerTrait`] trait, allowing parsers to
 //! work with either implementation.
 
-use crate::GedcomError;
+use crate::{
+    parser::TagHandlers,
+    report::{is_invisible, InvisibleCharacterPolicy, ReportCollector},
+    types::custom::UserDefinedTag,
+    util::{unescape_at_signs, StringInterner},
+    version::detect_version,
+    GedcomError,
+};
 use std::io::BufRead;
 use std::str::Chars;
+use std::sync::Arc;
 
 /// The base enum of Token types making use of [GEDCOM Standard Release
 /// 5.5.1](https://gedcom.io/specifications/ged551.pdf), p.11 `gedcom_line: level + delim +
@@ -157,20 +165,108 @@ pub struct Tokenizer<'a> {
     chars: Chars<'a>,
     /// The current line number of the file we are parsing
     pub line: u32,
+    /// Whether line values follow GEDCOM 7.0 `@` escaping rules
+    gedcom_7: bool,
+    /// The tags and invisible characters read so far
+    report: ReportCollector,
+    /// What to do with invisible characters
+    invisible_characters: InvisibleCharacterPolicy,
+    /// The handlers offered each custom structure
+    tag_handlers: TagHandlers,
+    /// The length in bytes of the input
+    input_len: usize,
+    /// The offset in bytes of the input within the whole file
+    offset_base: usize,
+    /// Whether the characters being read belong to the next line
+    at_line_start: bool,
+    /// Where repeated values are shared, if they are
+    interner: Option<Arc<StringInterner>>,
 }
 
+/// Number of characters scanned for the header version when creating a `Tokenizer`
+const VERSION_SCAN_LEN: usize = 1000;
+
 impl<'a> Tokenizer<'a> {
     /// Creates a new tokenizer for a char interator of GEDCOM file contents
+    ///
+    /// The declared GEDCOM version is read from the start of the input so that
+    /// doubled `@` signs in line values are unescaped by the matching rules.
     #[must_use]
     pub fn new(chars: Chars<'a>) -> Tokenizer<'a> {
+        let head: String = chars.clone().take(VERSION_SCAN_LEN).collect();
+        Tokenizer::with_version(chars, detect_version(&head).is_v7())
+    }
+
+    /// Creates a tokenizer for input whose header is not part of the text,
+    /// such as a single record, unescaping `@` by the GEDCOM 7.0 rules if
+    /// `gedcom_7` is set.
+    pub(crate) fn with_version(chars: Chars<'a>, gedcom_7: bool) -> Tokenizer<'a> {
         Tokenizer {
             current_char: '\n',
             current_token: Token::None,
-            chars,
             line: 0,
+            gedcom_7,
+            report: ReportCollector::default(),
+            invisible_characters: InvisibleCharacterPolicy::default(),
+            tag_handlers: TagHandlers::default(),
+            input_len: chars.as_str().len(),
+            offset_base: 0,
+            at_line_start: true,
+            interner: None,
+            chars,
+        }
+    }
+
+    /// Sets what to do with invisible characters.
+    pub(crate) fn set_invisible_characters(&mut self, policy: InvisibleCharacterPolicy) {
+        self.invisible_characters = policy;
+    }
+
+    /// Sets the handlers offered each custom structure.
+    pub(crate) fn set_tag_handlers(&mut self, handlers: TagHandlers) {
+        self.tag_handlers = handlers;
+    }
+
+    /// Shares repeated place names, surnames and custom tags through
+    /// `interner`.
+    pub(crate) fn set_interner(&mut self, interner: Option<Arc<StringInterner>>) {
+        self.interner = interner;
+    }
+
+    /// Returns `value` as a shared string, the same one as for every equal
+    /// value when values are interned.
+    pub(crate) fn share(&self, value: &str) -> Arc<str> {
+        match &self.interner {
+            Some(interner) => interner.intern(value),
+            None => Arc::from(value),
         }
     }
 
+    /// Offers a custom structure found at `level` to the tag handlers, and
+    /// returns what is left for the custom data.
+    pub(crate) fn intercept(&self, tag: UserDefinedTag, level: u8) -> Option<UserDefinedTag> {
+        self.tag_handlers.intercept(tag, level)
+    }
+
+    /// Numbers the input as a part of a file starting `offset` bytes in, on
+    /// the line after `line`.
+    pub(crate) fn set_position(&mut self, line: u32, offset: usize) {
+        self.line = line;
+        self.offset_base = offset;
+    }
+
+    /// Gathers the parse report into `report`, which may hold what was
+    /// gathered from earlier parts of the same file.
+    pub(crate) fn set_report(&mut self, report: ReportCollector) {
+        self.report = report;
+    }
+
+    /// Returns what was gathered for the parse report so far and starts
+    /// afresh.
+    pub(crate) fn take_report(&mut self) -> ReportCollector {
+        std::mem::take(&mut self.report)
+    }
+
     /// Ends the tokenization
     #[inline]
     #[must_use]
@@ -192,6 +288,7 @@ impl<'a> Tokenizer<'a> {
         // Level number is at the start of each line.
         // Also allow a file that starts without a leading newline.
         if matches!(self.current_token, Token::None) || self.current_char == '\n' {
+            self.at_line_start = true;
             // Tolerate UTF-8 BOM at the start of the file.
             while matches!(self.current_token, Token::None)
                 && (self.current_char as u32) == 65279_u32
@@ -221,6 +318,7 @@ impl<'a> Tokenizer<'a> {
 
             self.current_token = Token::Level(self.extract_number()?);
             self.line += 1;
+            self.at_line_start = false;
             return Ok(());
         }
 
@@ -263,7 +361,8 @@ impl<'a> Tokenizer<'a> {
                 {
                     Token::LineValue("".into())
                 } else {
-                    Token::LineValue(self.extract_value_with_capacity(VALUE_CAPACITY))
+                    let value = self.extract_value_with_capacity(VALUE_CAPACITY);
+                    Token::LineValue(self.unescape_value(value))
                 }
             }
             _ => {
@@ -273,9 +372,22 @@ impl<'a> Tokenizer<'a> {
                 })
             }
         };
+        if let Token::Tag(tag) | Token::CustomTag(tag) = &self.current_token {
+            self.report.record_tag(tag, self.line);
+        }
         Ok(())
     }
 
+    /// Collapses escaped `@@` sequences in a line value.
+    #[inline]
+    fn unescape_value(&self, value: Box<str>) -> Box<str> {
+        if value.contains("@@") {
+            unescape_at_signs(&value, self.gedcom_7).into_boxed_str()
+        } else {
+            value
+        }
+    }
+
     /// Like `next_token`, but returns a clone of the token you are popping.
     ///
     /// # Errors
@@ -290,6 +402,30 @@ impl<'a> Tokenizer<'a> {
     #[inline]
     fn next_char(&mut self) {
         self.current_char = self.chars.next().unwrap_or('\0');
+        if is_invisible(self.current_char) {
+            self.sanitize_char();
+        }
+    }
+
+    /// Reports the invisible current character, and with
+    /// [`InvisibleCharacterPolicy::Strip`] reads on past it and any that follow.
+    #[cold]
+    fn sanitize_char(&mut self) {
+        while is_invisible(self.current_char) {
+            let offset = self.input_len - self.chars.as_str().len() - self.current_char.len_utf8();
+            let offset = self.offset_base + offset;
+            // A byte order mark opening the file is expected.
+            if offset == 0 && self.current_char == '\u{FEFF}' {
+                return;
+            }
+            let line = self.line + u32::from(self.at_line_start);
+            self.report
+                .record_invisible(self.current_char, line, offset);
+            if self.invisible_characters != InvisibleCharacterPolicy::Strip {
+                return;
+            }
+            self.current_char = self.chars.next().unwrap_or('\0');
+        }
     }
 
     #[inline]
@@ -385,6 +521,17 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
+    /// Grabs and returns to the end of the current line as a shared string,
+    /// see [`Tokenizer::share`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GedcomError` if an unexpected line value is encountered.
+    pub(crate) fn take_shared_value(&mut self) -> Result<Arc<str>, GedcomError> {
+        let value = self.take_line_value()?;
+        Ok(self.share(&value))
+    }
+
     /// Takes the value of the current line including handling
     /// multi-line values from CONT & CONC tags.
     ///
@@ -432,6 +579,45 @@ impl<'a> Tokenizer<'a> {
         }
         Ok(value)
     }
+
+    /// Consumes the input after a `0 TRLR` tag.
+    ///
+    /// Returns the line number of the first line after the trailer that is
+    /// not blank, if there is one.
+    pub(crate) fn content_after_trailer(&mut self) -> Option<u32> {
+        // The rest of the trailer line belongs to it.
+        while !matches!(self.current_char, '\n' | '\0') {
+            self.next_char();
+        }
+
+        let mut line = self.line;
+        while self.current_char != '\0' {
+            if self.current_char == '\n' {
+                line += 1;
+            } else if !is_trailer_padding(self.current_char) {
+                self.current_char = '\0';
+                self.current_token = Token::EOF;
+                return Some(line);
+            }
+            self.next_char();
+        }
+        self.current_token = Token::EOF;
+        None
+    }
+}
+
+/// Returns true for a `0 TRLR` line, ignoring surrounding whitespace.
+pub(crate) fn is_trailer_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed == "0 TRLR" || trimmed.starts_with("0 TRLR ")
+}
+
+/// Returns true for characters that may pad the end of a file after the trailer.
+///
+/// Besides whitespace this accepts the DOS end-of-file marker (Ctrl-Z), which
+/// older tools still append.
+pub(crate) fn is_trailer_padding(c: char) -> bool {
+    c.is_whitespace() || c == '\u{1A}'
 }
 
 impl TokenizerTrait for Tokenizer<'_> {
@@ -491,9 +677,9 @@ const LINE_BUFFER_CAPACITY: usize = 256;
 ///
 /// # UTF-8 Requirement
 ///
-/// The streaming tokenizer requires UTF-8 encoded input. If you have a file with
-/// a different encoding (UTF-16, ISO-8859-1, etc.), you must convert it to UTF-8
-/// first or use the in-memory [`Tokenizer`] with encoding detection.
+/// The streaming tokenizer requires UTF-8 encoded input. For a file with a
+/// different encoding (UTF-16, ISO-8859-1, ANSEL, etc.), wrap the reader in a
+/// [`DecodingReader`](crate::encoding::DecodingReader), which converts it on the fly.
 ///
 /// # Example
 ///
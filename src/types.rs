@@ -2,7 +2,7 @@
 
 #![allow(missing_docs)]
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 type Xref = String;
@@ -27,15 +27,21 @@ pub mod submission;
 pub mod submitter;
 pub mod translation;
 
+use std::collections::{HashMap, HashSet};
+
 use crate::{
+    edit::ChangeClock,
     parser::Parser,
+    position::SourceSpan,
+    raw::RawRecord,
+    report::ParseReport,
     tokenizer::{Token, Tokenizer},
     types::{
         custom::UserDefinedTag, family::Family, header::Header, individual::Individual,
-        multimedia::Multimedia, repository::Repository, shared_note::SharedNote, source::Source,
-        submission::Submission, submitter::Submitter,
+        multimedia::Multimedia, note::Note, repository::Repository, shared_note::SharedNote,
+        source::Source, submission::Submission, submitter::Submitter,
     },
-    GedcomError,
+    GedcomError, ParseWarning,
 };
 
 /// Represents a complete parsed GEDCOM genealogy file.
@@ -47,7 +53,7 @@ use crate::{
 /// # GEDCOM Version Support
 ///
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SourceCitationStats {
     /// Total number of source citations across all records.
     pub total: usize,
@@ -65,6 +71,77 @@ pub struct SourceCitationStats {
     pub on_other: usize,
 }
 
+/// The kinds of top-level record collections held by [`GedcomData`].
+///
+/// Used with [`GedcomData::retain_record_types`] to select which collections to keep.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RecordType {
+    /// Submitter records (`SUBM`).
+    Submitter,
+    /// Submission records (`SUBN`, GEDCOM 5.5.1 only).
+    Submission,
+    /// Individual records (`INDI`).
+    Individual,
+    /// Family records (`FAM`).
+    Family,
+    /// Repository records (`REPO`).
+    Repository,
+    /// Source records (`SOUR`).
+    Source,
+    /// Multimedia records (`OBJE`).
+    Multimedia,
+    /// Shared note records (`SNOTE`, GEDCOM 7.0 only).
+    SharedNote,
+    /// Top-level user-defined records (tags starting with `_`).
+    Custom,
+}
+
+/// What to do when several records of the same type share an xref.
+///
+/// See [`GedcomData::resolve_duplicate_xrefs`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DuplicateXrefPolicy {
+    /// Keep every record, as read (the default).
+    #[default]
+    KeepAll,
+    /// Fail with an error naming the first duplicate.
+    Error,
+    /// Keep the first record with a given xref and drop the later ones.
+    KeepFirst,
+    /// Keep the last record with a given xref and drop the earlier ones.
+    KeepLast,
+    /// Keep every record, giving each later duplicate a fresh xref.
+    Rename,
+}
+
+/// A duplicate xref found by [`GedcomData::resolve_duplicate_xrefs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DuplicateXref {
+    /// The type of the records sharing the xref.
+    pub record_type: RecordType,
+    /// The shared xref.
+    pub xref: String,
+    /// The xref given to the duplicate, under [`DuplicateXrefPolicy::Rename`].
+    pub renamed_to: Option<String>,
+}
+
+impl std::fmt::Display for DuplicateXref {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} records share the xref {}",
+            self.record_type, self.xref
+        )?;
+        match &self.renamed_to {
+            Some(renamed) => write!(f, "; the duplicate was renamed {renamed}"),
+            None => Ok(()),
+        }
+    }
+}
+
 /// The main data structure for parsed GEDCOM data.
 ///
 /// This contains all the parsed records from a GEDCOM file: individuals and
@@ -75,9 +152,10 @@ pub struct SourceCitationStats {
 ///
 /// This structure supports both GEDCOM 5.5.1 and GEDCOM 7.0 files:
 /// - `submissions` are only present in GEDCOM 5.5.1 files
-/// - `shared_notes` are only present in GEDCOM 7.0 files
+/// - `shared_notes` are `SNOTE` records in GEDCOM 7.0 files and `NOTE` records
+///   in earlier ones
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GedcomData {
     /// Header containing file metadata
     pub header: Option<Header>,
@@ -95,7 +173,7 @@ pub struct GedcomData {
     pub sources: Vec<Source>,
     /// A multimedia asset linked to a fact
     pub multimedia: Vec<Multimedia>,
-    /// Shared notes that can be referenced by multiple structures (GEDCOM 7.0 only)
+    /// Shared notes that can be referenced by multiple structures
     ///
     /// A shared note record may be pointed to by multiple other structures.
     /// Shared notes should only be used if editing the note in one place
@@ -106,6 +184,53 @@ pub struct GedcomData {
     /// user-defined tags must consider that they have meaning only with respect to a system
     /// contained in the HEAD.SOUR context.
     pub custom_data: Vec<Box<UserDefinedTag>>,
+    /// Recoverable problems found while parsing, such as a missing trailer.
+    ///
+    /// Strict mode turns these into errors instead; see
+    /// [`GedcomBuilder::strict_mode`](crate::GedcomBuilder::strict_mode).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub warnings: Vec<ParseWarning>,
+    /// What the parser saw besides the records, such as how often each tag
+    /// appears. Empty unless the data was parsed from text.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "ParseReport::is_empty")
+    )]
+    pub report: ParseReport,
+    /// The source text of each record, in file order. Empty unless parsed
+    /// with [`GedcomBuilder::keep_raw`](crate::GedcomBuilder::keep_raw).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub raw_records: Vec<RawRecord>,
+    /// Where each record and its substructures are in the source file, in
+    /// file order. Empty unless parsed with
+    /// [`GedcomBuilder::track_positions`](crate::GedcomBuilder::track_positions).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub positions: Vec<SourceSpan>,
+    /// Languages to show notes in, most preferred first, as BCP 47 tags.
+    ///
+    /// This is a reader's setting, not part of the file: the parser leaves
+    /// it empty. [`GedcomData::note_text`] and the `Display` output pick the
+    /// first of these languages a note has a translation into.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub preferred_languages: Vec<String>,
+    /// Where the change dates that the [`edit`](crate::edit) methods give
+    /// the records they change come from (default: the system clock).
+    ///
+    /// Like `preferred_languages`, this is a setting, not part of the file.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub change_clock: ChangeClock,
 }
 
 impl GedcomData {
@@ -118,6 +243,8 @@ impl GedcomData {
     pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<GedcomData, GedcomError> {
         let mut data = GedcomData::default();
         data.parse(tokenizer, level)?;
+        data.apply_child_relationships();
+        data.report = tokenizer.take_report().into_report();
         Ok(data)
     }
 
@@ -156,7 +283,7 @@ impl GedcomData {
         self.multimedia.push(multimedia);
     }
 
-    /// Adds a [`SharedNote`] record to the genealogy data (GEDCOM 7.0 only).
+    /// Adds a [`SharedNote`] record to the genealogy data.
     pub fn add_shared_note(&mut self, shared_note: SharedNote) {
         self.shared_notes.push(shared_note);
     }
@@ -167,6 +294,8 @@ impl GedcomData {
     }
 
     /// Prints a summary of record counts to stdout.
+    ///
+    /// [`statistics`](Self::statistics) returns these counts, and more, as data.
     pub fn stats(&self) {
         let citation_stats = self.count_source_citations();
         println!("----------------------");
@@ -231,8 +360,8 @@ impl GedcomData {
             // Direct citations on the individual
             stats.on_individuals += individual.source.len();
 
-            // Citations on name
-            if let Some(ref name) = individual.name {
+            // Citations on names
+            for name in &individual.names {
                 stats.on_names += name.source.len();
             }
 
@@ -379,7 +508,8 @@ impl GedcomData {
 
     /// Finds a shared note by their cross-reference ID (xref).
     ///
-    /// This is only relevant for GEDCOM 7.0 files.
+    /// These are the `SNOTE` records of GEDCOM 7.0 and the `NOTE` records of
+    /// earlier versions.
     #[must_use]
     pub fn find_shared_note(&self, xref: &str) -> Option<&SharedNote> {
         self.shared_notes
@@ -387,6 +517,22 @@ impl GedcomData {
             .find(|n| n.xref.as_ref().is_some_and(|x| x == xref))
     }
 
+    /// Returns a note's text in the first of the
+    /// [`preferred_languages`](Self::preferred_languages) it is available in,
+    /// or its own text.
+    #[must_use]
+    pub fn note_text<'a>(&self, note: &'a Note) -> Option<&'a str> {
+        note.preferred_text(&self.preferred_languages)
+    }
+
+    /// Returns a shared note's text in the first of the
+    /// [`preferred_languages`](Self::preferred_languages) it is available in,
+    /// or its own text.
+    #[must_use]
+    pub fn shared_note_text<'a>(&self, note: &'a SharedNote) -> &'a str {
+        note.preferred_text(&self.preferred_languages)
+    }
+
     /// Gets the families where an individual is a spouse/partner.
     ///
     /// # Example
@@ -431,6 +577,56 @@ impl GedcomData {
             .collect()
     }
 
+    /// Copies the `_FREL`/`_MREL` relationships of family children onto the
+    /// children's `FAMC` links, where those have no conflicting `PEDI`.
+    ///
+    /// The parser calls this, so that the pedigree of a link reads the same
+    /// whether a file uses `PEDI` or the Family Tree Maker extensions; see
+    /// [`FamilyChild::pedigree`](family::child::FamilyChild::pedigree).
+    pub fn apply_child_relationships(&mut self) {
+        let mut pedigrees: HashMap<(&str, &str), _> = HashMap::new();
+        for family in &self.families {
+            let Some(ref family_xref) = family.xref else {
+                continue;
+            };
+            for child in &family.child_details {
+                if let Some(pedigree) = child.pedigree() {
+                    pedigrees.insert((child.xref.as_str(), family_xref.as_str()), pedigree);
+                }
+            }
+        }
+        if pedigrees.is_empty() {
+            return;
+        }
+
+        for individual in &mut self.individuals {
+            let Some(ref xref) = individual.xref else {
+                continue;
+            };
+            for link in &mut individual.families {
+                if link.family_link_type != individual::family_link::FamilyLinkType::Child {
+                    continue;
+                }
+                let Some((pedigree, adopted_by)) =
+                    pedigrees.get(&(xref.as_str(), link.xref.as_str()))
+                else {
+                    continue;
+                };
+                if link
+                    .pedigree_linkage_type
+                    .as_ref()
+                    .is_some_and(|p| p != pedigree)
+                {
+                    continue;
+                }
+                link.pedigree_linkage_type = Some(pedigree.clone());
+                if link.adopted_by.is_none() {
+                    link.adopted_by.clone_from(adopted_by);
+                }
+            }
+        }
+    }
+
     /// Gets the parents/partners of a family as Individual references.
     #[must_use]
     pub fn get_parents(&self, family: &Family) -> Vec<&Individual> {
@@ -494,7 +690,7 @@ impl GedcomData {
         self.individuals
             .iter()
             .filter(|i| {
-                i.name.as_ref().is_some_and(|name| {
+                i.names.iter().any(|name| {
                     name.value
                         .as_ref()
                         .is_some_and(|v| v.to_lowercase().contains(&query_lower))
@@ -555,7 +751,7 @@ impl GedcomData {
     /// Checks for:
     /// - Version string starting with "7."
     /// - Presence of SCHMA structure
-    /// - Presence of SNOTE records
+    /// - Presence of shared notes, if the header gives no version
     #[must_use]
     pub fn is_gedcom_7(&self) -> bool {
         // Check header indicators
@@ -565,8 +761,9 @@ impl GedcomData {
             }
         }
 
-        // Check for shared notes (GEDCOM 7.0 only)
-        if !self.shared_notes.is_empty() {
+        // Without a version, shared notes hint at GEDCOM 7.0; earlier
+        // versions have `NOTE` records instead, read as shared notes too.
+        if self.gedcom_version().is_none() && !self.shared_notes.is_empty() {
             return true;
         }
 
@@ -582,6 +779,266 @@ impl GedcomData {
         // Default to 5.5.1 if no version specified
         !self.is_gedcom_7()
     }
+
+    // ========================================================================
+    // Record Type Retention
+    // ========================================================================
+
+    /// Keeps only the listed record collections, dropping all others.
+    ///
+    /// Pointers into the dropped collections are pruned in the same pass, so
+    /// the result has no dangling links: source citations are removed when
+    /// sources are dropped, multimedia links when multimedia records are
+    /// dropped, and so on. Citations and media given inline (without a
+    /// pointer) are kept. The header is always kept.
+    ///
+    /// This is a cheap way to slim a dataset down to its structure, e.g. just
+    /// individuals and families.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{Gedcom, RecordType};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 SOUR @S1@\n0 @S1@ SOUR\n0 TRLR";
+    /// let mut gedcom = Gedcom::new(source.chars()).unwrap();
+    /// let mut data = gedcom.parse_data().unwrap();
+    ///
+    /// data.retain_record_types(&[RecordType::Individual, RecordType::Family]);
+    /// assert!(data.sources.is_empty());
+    /// assert!(data.individuals[0].source.is_empty());
+    /// ```
+    pub fn retain_record_types(&mut self, keep: &[RecordType]) {
+        let kept = |record_type| keep.contains(&record_type);
+
+        if !kept(RecordType::Submitter) {
+            self.submitters.clear();
+            if let Some(ref mut header) = self.header {
+                header.submitter_tag = None;
+            }
+        }
+        if !kept(RecordType::Submission) {
+            self.submissions.clear();
+        }
+        if !kept(RecordType::Individual) {
+            self.individuals.clear();
+        }
+        if !kept(RecordType::Family) {
+            self.families.clear();
+        }
+        if !kept(RecordType::Repository) {
+            self.repositories.clear();
+        }
+        if !kept(RecordType::Source) {
+            self.sources.clear();
+        }
+        if !kept(RecordType::Multimedia) {
+            self.multimedia.clear();
+        }
+        if !kept(RecordType::SharedNote) {
+            self.shared_notes.clear();
+        }
+        if !kept(RecordType::Custom) {
+            self.custom_data.clear();
+        }
+
+        self.visit_all_links(&mut |record_type, _| kept(record_type));
+    }
+
+    /// Removes every LDS ordinance (`BAPL`, `CONL`, `ENDL`, `INIL`, `SLGC`
+    /// and `SLGS`) from the individuals and families, and returns how many
+    /// were removed.
+    ///
+    /// [`GedcomBuilder::skip_lds_ordinances`](crate::GedcomBuilder::skip_lds_ordinances)
+    /// does this while parsing, and
+    /// [`GedcomWriter::exclude_lds_ordinances`](crate::GedcomWriter::exclude_lds_ordinances)
+    /// leaves them out of the output without changing the data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::GedcomBuilder;
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 BAPL\n2 TEMP SLAKE\n1 ENDL\n0 TRLR";
+    /// let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+    ///
+    /// assert_eq!(data.remove_lds_ordinances(), 2);
+    /// assert!(data.individuals[0].lds_ordinances.is_empty());
+    /// ```
+    pub fn remove_lds_ordinances(&mut self) -> usize {
+        let individuals = self.individuals.iter_mut().map(|i| &mut i.lds_ordinances);
+        let families = self.families.iter_mut().map(|f| &mut f.lds_ordinances);
+        individuals
+            .chain(families)
+            .map(|ordinances| std::mem::take(ordinances).len())
+            .sum()
+    }
+
+    // ========================================================================
+    // Duplicate Xref Resolution
+    // ========================================================================
+
+    /// Finds records of the same type that share an xref and applies `policy` to them.
+    ///
+    /// Records are compared within each collection, in file order. Every
+    /// duplicate after the first occurrence is reported once, whatever the
+    /// policy. Pointers are left untouched: under [`DuplicateXrefPolicy::Rename`]
+    /// they keep pointing at the first record.
+    ///
+    /// [`GedcomBuilder::duplicate_xrefs`](crate::GedcomBuilder::duplicate_xrefs)
+    /// applies a policy while building; call this directly to get the report.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomError::InvalidFormat` under [`DuplicateXrefPolicy::Error`]
+    /// if any xref is duplicated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{DuplicateXrefPolicy, Gedcom};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME First\n0 @I1@ INDI\n1 NAME Second\n0 TRLR";
+    /// let mut gedcom = Gedcom::new(source.chars()).unwrap();
+    /// let mut data = gedcom.parse_data().unwrap();
+    ///
+    /// let report = data.resolve_duplicate_xrefs(DuplicateXrefPolicy::Rename).unwrap();
+    /// assert_eq!(report[0].renamed_to.as_deref(), Some("@I1_2@"));
+    /// assert_eq!(data.individuals[1].xref.as_deref(), Some("@I1_2@"));
+    /// ```
+    pub fn resolve_duplicate_xrefs(
+        &mut self,
+        policy: DuplicateXrefPolicy,
+    ) -> Result<Vec<DuplicateXref>, GedcomError> {
+        let taken = if policy == DuplicateXrefPolicy::Rename {
+            self.xrefs_in_use()
+        } else {
+            HashSet::new()
+        };
+
+        let mut resolver = DuplicateResolver {
+            policy,
+            taken,
+            report: Vec::new(),
+        };
+        resolver.resolve(&mut self.submitters, RecordType::Submitter, |r| &mut r.xref)?;
+        resolver.resolve(&mut self.submissions, RecordType::Submission, |r| {
+            &mut r.xref
+        })?;
+        resolver.resolve(&mut self.individuals, RecordType::Individual, |r| {
+            &mut r.xref
+        })?;
+        resolver.resolve(&mut self.families, RecordType::Family, |r| &mut r.xref)?;
+        resolver.resolve(&mut self.repositories, RecordType::Repository, |r| {
+            &mut r.xref
+        })?;
+        resolver.resolve(&mut self.sources, RecordType::Source, |r| &mut r.xref)?;
+        resolver.resolve(&mut self.multimedia, RecordType::Multimedia, |r| {
+            &mut r.xref
+        })?;
+        resolver.resolve(&mut self.shared_notes, RecordType::SharedNote, |r| {
+            &mut r.xref
+        })?;
+        Ok(resolver.report)
+    }
+
+    /// Returns the xrefs of all records.
+    pub(crate) fn xrefs_in_use(&self) -> HashSet<String> {
+        let mut taken = HashSet::new();
+        taken.extend(self.submitters.iter().filter_map(|r| r.xref.clone()));
+        taken.extend(self.submissions.iter().filter_map(|r| r.xref.clone()));
+        taken.extend(self.individuals.iter().filter_map(|r| r.xref.clone()));
+        taken.extend(self.families.iter().filter_map(|r| r.xref.clone()));
+        taken.extend(self.repositories.iter().filter_map(|r| r.xref.clone()));
+        taken.extend(self.sources.iter().filter_map(|r| r.xref.clone()));
+        taken.extend(self.multimedia.iter().filter_map(|r| r.xref.clone()));
+        taken.extend(self.shared_notes.iter().filter_map(|r| r.xref.clone()));
+        taken
+    }
+}
+
+/// Derives an xref from `xref` that is not in `taken`, e.g. `@I1_2@` from
+/// `@I1@`, and adds it to `taken`.
+pub(crate) fn fresh_xref(taken: &mut HashSet<String>, xref: &str) -> String {
+    let stem = xref.trim_matches('@');
+    let mut n = 2;
+    loop {
+        let candidate = format!("@{stem}_{n}@");
+        if taken.insert(candidate.clone()) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// State shared across collections by [`GedcomData::resolve_duplicate_xrefs`].
+struct DuplicateResolver {
+    policy: DuplicateXrefPolicy,
+    /// Every xref in use, so that renamed records never collide.
+    taken: HashSet<String>,
+    report: Vec<DuplicateXref>,
+}
+
+impl DuplicateResolver {
+    fn resolve<T>(
+        &mut self,
+        records: &mut Vec<T>,
+        record_type: RecordType,
+        xref_of: impl Fn(&mut T) -> &mut Option<String>,
+    ) -> Result<(), GedcomError> {
+        let mut last_index: HashMap<String, usize> = HashMap::new();
+        let mut duplicated = vec![false; records.len()];
+        for (i, record) in records.iter_mut().enumerate() {
+            if let Some(xref) = xref_of(record).clone() {
+                duplicated[i] = last_index.insert(xref, i).is_some();
+            }
+        }
+        if !duplicated.contains(&true) {
+            return Ok(());
+        }
+
+        for (i, record) in records.iter_mut().enumerate() {
+            if !duplicated[i] {
+                continue;
+            }
+            let xref = xref_of(record);
+            let original = xref.clone().unwrap_or_default();
+            let renamed_to = match self.policy {
+                DuplicateXrefPolicy::Error => {
+                    return Err(GedcomError::InvalidFormat(format!(
+                        "Duplicate xref {original} on {record_type:?} records"
+                    )));
+                }
+                DuplicateXrefPolicy::Rename => {
+                    let renamed = fresh_xref(&mut self.taken, &original);
+                    *xref = Some(renamed.clone());
+                    Some(renamed)
+                }
+                _ => None,
+            };
+            self.report.push(DuplicateXref {
+                record_type,
+                xref: original,
+                renamed_to,
+            });
+        }
+
+        let mut i = 0;
+        match self.policy {
+            DuplicateXrefPolicy::KeepFirst => records.retain(|_| {
+                i += 1;
+                !duplicated[i - 1]
+            }),
+            DuplicateXrefPolicy::KeepLast => records.retain_mut(|record| {
+                i += 1;
+                xref_of(record)
+                    .as_ref()
+                    .is_none_or(|xref| last_index[xref] == i - 1)
+            }),
+            _ => {}
+        }
+        Ok(())
+    }
 }
 
 impl Parser for GedcomData {
@@ -624,10 +1081,18 @@ impl Parser for GedcomData {
                     "SUBN" => self.add_submission(Submission::new(tokenizer, level, pointer)?),
                     "SUBM" => self.add_submitter(Submitter::new(tokenizer, level, pointer)?),
                     "OBJE" => self.add_multimedia(Multimedia::new(tokenizer, level, pointer)?),
-                    // GEDCOM 7.0: Shared note record
-                    "SNOTE" => self.add_shared_note(SharedNote::new(tokenizer, level, pointer)?),
-                    // Trailer is optional in the wild; allow EOF-terminated files.
-                    "TRLR" => break,
+                    // Shared note records: `SNOTE` in GEDCOM 7.0, `NOTE` before.
+                    "SNOTE" | "NOTE" => {
+                        self.add_shared_note(SharedNote::new(tokenizer, level, pointer)?);
+                    }
+                    "TRLR" => {
+                        // Some tools keep writing after the trailer; that content is dropped.
+                        if let Some(line) = tokenizer.content_after_trailer() {
+                            self.warnings
+                                .push(ParseWarning::ContentAfterTrailer { line });
+                        }
+                        return Ok(());
+                    }
                     _ => {
                         return Err(GedcomError::ParseError {
                             line: tokenizer.line,
@@ -642,7 +1107,10 @@ impl Parser for GedcomData {
                 }
             } else if let Token::CustomTag(tag) = &tokenizer.current_token {
                 let tag_clone = tag.clone();
-                self.add_custom_data(UserDefinedTag::new(tokenizer, level + 1, &tag_clone)?);
+                let custom = UserDefinedTag::new(tokenizer, level + 1, &tag_clone)?;
+                if let Some(custom) = tokenizer.intercept(custom, current_level) {
+                    self.add_custom_data(custom);
+                }
                 // self.add_custom_data(parse_custom_tag(tokenizer, tag_clone));
                 while tokenizer.current_token != Token::Level(level) {
                     tokenizer.next_token()?;
@@ -658,6 +1126,8 @@ impl Parser for GedcomData {
             }
         }
 
+        // The trailer is optional in the wild; EOF-terminated files are accepted.
+        self.warnings.push(ParseWarning::MissingTrailer);
         Ok(())
     }
 }
@@ -753,4 +1223,138 @@ mod tests {
 
         assert_eq!(data.total_records(), 2); // 1 individual + 1 shared note
     }
+
+    #[test]
+    fn test_resolve_duplicate_xrefs() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5\n\
+            0 @I1@ INDI\n\
+            1 NAME First /Doe/\n\
+            0 @I1_2@ INDI\n\
+            0 @I1@ INDI\n\
+            1 NAME Second /Doe/\n\
+            0 @I1@ INDI\n\
+            1 NAME Third /Doe/\n\
+            0 @F1@ FAM\n\
+            0 TRLR";
+        let parse = || {
+            let mut tokenizer = Tokenizer::new(sample.chars());
+            tokenizer.next_token().unwrap();
+            GedcomData::new(&mut tokenizer, 0).unwrap()
+        };
+        let names = |data: &GedcomData| -> Vec<Option<String>> {
+            data.individuals.iter().map(Individual::full_name).collect()
+        };
+
+        let mut data = parse();
+        let report = data
+            .resolve_duplicate_xrefs(DuplicateXrefPolicy::KeepAll)
+            .unwrap();
+        assert_eq!(report.len(), 2);
+        assert_eq!(data.individuals.len(), 4);
+
+        assert!(parse()
+            .resolve_duplicate_xrefs(DuplicateXrefPolicy::Error)
+            .is_err());
+
+        let mut data = parse();
+        data.resolve_duplicate_xrefs(DuplicateXrefPolicy::KeepFirst)
+            .unwrap();
+        assert_eq!(names(&data), vec![Some("First Doe".to_string()), None]);
+
+        let mut data = parse();
+        data.resolve_duplicate_xrefs(DuplicateXrefPolicy::KeepLast)
+            .unwrap();
+        assert_eq!(names(&data), vec![None, Some("Third Doe".to_string())]);
+
+        let mut data = parse();
+        let report = data
+            .resolve_duplicate_xrefs(DuplicateXrefPolicy::Rename)
+            .unwrap();
+        let xrefs: Vec<&str> = data
+            .individuals
+            .iter()
+            .filter_map(|i| i.xref.as_deref())
+            .collect();
+        assert_eq!(xrefs, vec!["@I1@", "@I1_2@", "@I1_3@", "@I1_4@"]);
+        assert_eq!(
+            report[0],
+            DuplicateXref {
+                record_type: RecordType::Individual,
+                xref: "@I1@".to_string(),
+                renamed_to: Some("@I1_3@".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_retain_record_types() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            1 SUBM @U1@\n\
+            0 @U1@ SUBM\n\
+            1 NAME Submitter\n\
+            0 @I1@ INDI\n\
+            1 NAME John /Doe/\n\
+            2 SOUR @S1@\n\
+            1 BIRT\n\
+            2 SOUR @S1@\n\
+            2 SOUR A parish register\n\
+            1 OBJE @M1@\n\
+            1 FAMS @F1@\n\
+            0 @F1@ FAM\n\
+            1 HUSB @I1@\n\
+            1 MARR\n\
+            2 OBJE @M1@\n\
+            1 SOUR @S1@\n\
+            0 @S1@ SOUR\n\
+            1 TITL Records\n\
+            1 REPO @R1@\n\
+            0 @R1@ REPO\n\
+            1 NAME Archive\n\
+            0 @M1@ OBJE\n\
+            1 FILE photo.jpg\n\
+            0 TRLR";
+
+        let mut tokenizer = Tokenizer::new(sample.chars());
+        tokenizer.next_token().unwrap();
+        let mut data = GedcomData::new(&mut tokenizer, 0).unwrap();
+
+        let mut with_sources = data.clone();
+        with_sources.retain_record_types(&[RecordType::Individual, RecordType::Source]);
+        assert!(with_sources.families.is_empty());
+        assert!(with_sources.repositories.is_empty());
+        assert!(with_sources.sources[0].repo_citations.is_empty());
+        assert!(with_sources.individuals[0].families.is_empty());
+        assert_eq!(with_sources.individuals[0].events[0].citations.len(), 2);
+
+        data.retain_record_types(&[RecordType::Individual, RecordType::Family]);
+        assert_eq!(data.total_records(), 2);
+        assert!(data.header.as_ref().unwrap().submitter_tag.is_none());
+
+        let indi = &data.individuals[0];
+        assert!(indi.names[0].source.is_empty());
+        assert!(indi.multimedia.is_empty());
+        assert_eq!(indi.events[0].citations.len(), 1);
+        assert_eq!(indi.events[0].citations[0].xref, "A parish register");
+        assert_eq!(indi.families[0].xref, "@F1@");
+
+        let fam = &data.families[0];
+        assert_eq!(fam.individual1.as_deref(), Some("@I1@"));
+        assert!(fam.sources.is_empty());
+        assert!(fam.events[0].multimedia.is_empty());
+
+        let sample = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n0 @F1@ FAM\n1 CHIL @I1@\n2 _FREL Adopted\n0 TRLR";
+        let mut tokenizer = Tokenizer::new(sample.chars());
+        tokenizer.next_token().unwrap();
+        let mut data = GedcomData::new(&mut tokenizer, 0).unwrap();
+        data.retain_record_types(&[RecordType::Family]);
+        assert!(data.families[0].children.is_empty());
+        assert!(data.families[0].child_details.is_empty());
+    }
 }
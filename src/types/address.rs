@@ -1,4 +1,4 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -11,7 +11,7 @@ use crate::{
 
 /// Physical address at which a fact occurs
 #[derive(Clone, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Address {
     pub value: Option<String>,
     pub adr1: Option<String>,
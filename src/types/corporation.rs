@@ -4,26 +4,26 @@ use crate::{
     types::address::Address,
     GedcomError,
 };
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Corporation (tag: CORP) is the name of the business, corporation, or person that produced or
 /// commissioned the product. See
 /// <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#CORP>.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Corporation {
     pub value: Option<String>,
     /// tag: ADDR
     pub address: Option<Address>,
     /// tag: PHON
-    pub phone: Option<String>,
+    pub phone: Vec<String>,
     /// tag: EMAIL
-    pub email: Option<String>,
+    pub email: Vec<String>,
     /// tag: FAX
-    pub fax: Option<String>,
+    pub fax: Vec<String>,
     /// tag: WWW
-    pub website: Option<String>,
+    pub website: Vec<String>,
 }
 
 impl Corporation {
@@ -47,10 +47,10 @@ impl Parser for Corporation {
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "ADDR" => self.address = Some(Address::new(tokenizer, level + 1)?),
-                "PHON" => self.phone = Some(tokenizer.take_line_value()?),
-                "EMAIL" => self.email = Some(tokenizer.take_line_value()?),
-                "FAX" => self.fax = Some(tokenizer.take_line_value()?),
-                "WWW" => self.website = Some(tokenizer.take_line_value()?),
+                "PHON" => self.phone.push(tokenizer.take_line_value()?),
+                "EMAIL" => self.email.push(tokenizer.take_line_value()?),
+                "FAX" => self.fax.push(tokenizer.take_line_value()?),
+                "WWW" => self.website.push(tokenizer.take_line_value()?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
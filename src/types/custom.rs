@@ -1,9 +1,11 @@
+use std::sync::Arc;
+
 use crate::{
     parser::Parser,
     tokenizer::{Token, Tokenizer, TokenizerTrait},
     GedcomError,
 };
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Handles a user-defined tag that is contained in the GEDCOM current transmission. This tag must
@@ -12,9 +14,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/ged55.pdf> (page 49).
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UserDefinedTag {
-    pub tag: String,
+    pub tag: Arc<str>,
     pub value: Option<String>,
     pub children: Vec<Box<UserDefinedTag>>,
 }
@@ -31,7 +33,7 @@ impl UserDefinedTag {
         tag: &str,
     ) -> Result<UserDefinedTag, GedcomError> {
         let mut udd = UserDefinedTag {
-            tag: tag.to_string(),
+            tag: tokenizer.share(tag),
             value: None,
             children: Vec::new(),
         };
@@ -50,7 +52,7 @@ impl UserDefinedTag {
         tag: &str,
     ) -> Result<UserDefinedTag, GedcomError> {
         let mut udd = UserDefinedTag {
-            tag: tag.to_string(),
+            tag: Arc::from(tag),
             value: None,
             children: Vec::new(),
         };
@@ -118,6 +120,21 @@ impl UserDefinedTag {
     }
 }
 
+/// Moves the values of the `_UID` extensions in `custom_data` to `uids`.
+///
+/// Several desktop applications record a GUID for each record under this
+/// tag. Tags with substructures are left in place.
+#[allow(clippy::vec_box)] // The type of the `custom_data` fields.
+pub(crate) fn take_vendor_uids(custom_data: &mut Vec<Box<UserDefinedTag>>, uids: &mut Vec<String>) {
+    custom_data.retain(|tag| match (&*tag.tag, &tag.value) {
+        ("_UID", Some(value)) if tag.children.is_empty() => {
+            uids.push(value.clone());
+            false
+        }
+        _ => true,
+    });
+}
+
 impl Parser for UserDefinedTag {
     fn parse(&mut self, tokenizer: &mut Tokenizer, level: u8) -> Result<(), GedcomError> {
         // skip ahead of initial tag
@@ -191,22 +208,22 @@ mod tests {
 
         let custom = &data.individuals[0].custom_data;
         assert_eq!(custom.len(), 1);
-        assert_eq!(custom[0].as_ref().tag, "_MILT");
+        assert_eq!(&*custom[0].as_ref().tag, "_MILT");
 
         let cs_date = custom[0].as_ref().children[0].as_ref();
-        assert_eq!(cs_date.tag, "DATE");
+        assert_eq!(&*cs_date.tag, "DATE");
         assert_eq!(cs_date.value.as_ref().unwrap(), "3 Nov 1947");
 
         let cs_plac = custom[0].as_ref().children[1].as_ref();
-        assert_eq!(cs_plac.tag, "PLAC");
+        assert_eq!(&*cs_plac.tag, "PLAC");
         assert_eq!(cs_plac.value.as_ref().unwrap(), "Rochester, New York, USA");
 
         let cs_sour = custom[0].as_ref().children[2].as_ref();
-        assert_eq!(cs_sour.tag, "SOUR");
+        assert_eq!(&*cs_sour.tag, "SOUR");
         assert_eq!(cs_sour.value.as_ref().unwrap(), "@S1207169483@");
 
         let cs_sour_page = cs_sour.children[0].as_ref();
-        assert_eq!(cs_sour_page.tag, "PAGE");
+        assert_eq!(&*cs_sour_page.tag, "PAGE");
         assert_eq!(cs_sour_page.value.as_ref().unwrap(), "New York State Archives; Albany, New York; Collection: New York, New York National Guard Service Cards, 1917-1954; Series: Xxxxx; Film Number: Xx");
     }
 }
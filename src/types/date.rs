@@ -1,4 +1,5 @@
 pub mod change_date;
+pub mod reinterpret;
 
 #[cfg(feature = "calendar")]
 pub mod calendar;
@@ -9,7 +10,7 @@ use crate::{
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "calendar")]
@@ -24,7 +25,7 @@ pub use calendar::{Calendar, CalendarConversionError, DateQualifier, ParsedDateT
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#DATE>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Date {
     pub value: Option<String>,
     pub time: Option<String>,
@@ -323,8 +324,11 @@ mod tests {
         assert_eq!(date.value.as_ref().unwrap(), "1 APR 1998");
         assert_eq!(date.time.as_ref().unwrap(), "12:34:56.789");
 
-        let chan_note = chan.note.as_ref().unwrap();
-        assert_eq!(chan_note.value.as_ref().unwrap(), "A note");
+        let chan_note = &chan.notes[0];
+        assert_eq!(
+            chan_note.inline().unwrap().value.as_ref().unwrap(),
+            "A note"
+        );
     }
 
     #[test]
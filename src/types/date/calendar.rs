@@ -23,12 +23,12 @@
 
 use crate::GedcomError;
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The four calendar systems supported by GEDCOM.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Calendar {
     /// Gregorian calendar (default, most common).
     /// GEDCOM escape: `@#DGREGORIAN@`
@@ -85,6 +85,7 @@ impl std::fmt::Display for Calendar {
 
 /// Error type for calendar conversion operations.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CalendarConversionError {
     /// The date has a qualifier (BEF, AFT, ABT, etc.) that prevents exact conversion.
     QualifiedDate { qualifier: String },
@@ -148,7 +149,7 @@ impl From<CalendarConversionError> for GedcomError {
 
 /// A date qualifier that indicates approximate or uncertain dates.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DateQualifier {
     /// Exact date (no qualifier).
     Exact,
@@ -197,7 +198,7 @@ impl DateQualifier {
 /// This struct represents a fully parsed GEDCOM date with all components
 /// separated out for easy manipulation and conversion.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParsedDateTime {
     /// The calendar system for this date.
     pub calendar: Calendar,
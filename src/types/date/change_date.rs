@@ -1,10 +1,10 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{date::Date, note::Note},
+    types::{date::Date, note::NoteRef},
     GedcomError,
 };
 
@@ -24,10 +24,10 @@ use crate::{
 /// [GEDCOM 5.5.1 specification, page 31](https://gedcom.io/specifications/ged551.pdf)
 /// [GEDCOM 7.0 Specification, page 44](gedcom.io/specifications/FamilySearchGEDCOMv7.html)
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChangeDate {
     pub date: Option<Date>,
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
 }
 
 impl ChangeDate {
@@ -43,6 +43,26 @@ impl ChangeDate {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl ChangeDate {
+    /// Gets the moment of the change, or of the creation for a `CREA`, from
+    /// its `DATE` and `TIME`, read as
+    /// [`Timestamp::from_change_date`](crate::edit::Timestamp::from_change_date)
+    /// does.
+    ///
+    /// Returns `None` unless the date is an exact Gregorian date.
+    #[must_use]
+    pub fn changed_at(&self) -> Option<chrono::NaiveDateTime> {
+        let moment = crate::edit::Timestamp::from_change_date(self)?;
+        chrono::NaiveDate::from_ymd_opt(moment.year, moment.month.into(), moment.day.into())?
+            .and_hms_opt(
+                moment.hour.into(),
+                moment.minute.into(),
+                moment.second.into(),
+            )
+    }
+}
+
 impl Parser for ChangeDate {
     fn parse(&mut self, tokenizer: &mut Tokenizer, level: u8) -> Result<(), GedcomError> {
         tokenizer.next_token()?;
@@ -50,7 +70,7 @@ impl Parser for ChangeDate {
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "DATE" => self.date = Some(Date::new(tokenizer, level + 1)?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -66,3 +86,53 @@ impl Parser for ChangeDate {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Gedcom;
+
+    #[test]
+    fn test_parse_record_dates() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @S1@ SOUR\n\
+            1 CHAN\n\
+            2 DATE 5 MAR 2024\n\
+            3 TIME 14:03:07\n\
+            2 NOTE Merged duplicates\n\
+            1 CREA\n\
+            2 DATE 1 JAN 2020\n\
+            0 @R1@ REPO\n\
+            1 NAME Archives\n\
+            1 CREA\n\
+            2 DATE 2 JAN 2020\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let source = &data.sources[0];
+        let change = source.change_date.as_ref().unwrap();
+        let time = change.date.as_ref().unwrap().time.as_deref();
+        assert_eq!(time, Some("14:03:07"));
+        assert_eq!(change.notes.len(), 1);
+        let creation = source.creation_date.as_ref().unwrap();
+        assert_eq!(
+            creation.date.as_ref().unwrap().value.as_deref(),
+            Some("1 JAN 2020")
+        );
+        assert!(data.repositories[0].creation_date.is_some());
+
+        #[cfg(feature = "chrono")]
+        {
+            let changed = change.changed_at().unwrap();
+            assert_eq!(changed.to_string(), "2024-03-05 14:03:07");
+            assert_eq!(
+                creation.changed_at().unwrap().to_string(),
+                "2020-01-01 00:00:00"
+            );
+        }
+    }
+}
@@ -0,0 +1,401 @@
+//! Detection and repair of non-standard date formats.
+//!
+//! Many files written by spreadsheets or older tools store dates such as
+//! `03/04/1887` or `1887-04-03` instead of GEDCOM dates like `3 APR 1887`.
+//! [`reinterpret_date`] recognizes these numeric forms and converts them,
+//! leaving any surrounding qualifiers (`ABT`, `BET ... AND ...`) in place.
+//!
+//! ISO forms (`1887-04-03`, `1887-04`) are unambiguous. In `a/b/yyyy` forms
+//! (also with `.` or `-`) the day and month can only be told apart when one
+//! of them is over 12 or both are equal; otherwise the value is ambiguous
+//! and is only converted when a [`DateOrder`] is given.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ged_io::types::date::reinterpret::{reinterpret_date, DateOrder, Reinterpretation};
+//!
+//! assert_eq!(
+//!     reinterpret_date("ABT 1887-04-03", None),
+//!     Some(Reinterpretation::Converted("ABT 3 APR 1887".to_string()))
+//! );
+//! assert_eq!(
+//!     reinterpret_date("03/04/1887", Some(DateOrder::MonthDayYear)),
+//!     Some(Reinterpretation::Converted("4 MAR 1887".to_string()))
+//! );
+//! assert_eq!(reinterpret_date("3 APR 1887", None), None);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{date::Date, GedcomData};
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// How to read the first two numbers of an `a/b/yyyy` date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DateOrder {
+    /// `03/04/1887` is 3 April 1887.
+    DayMonthYear,
+    /// `03/04/1887` is 4 March 1887.
+    MonthDayYear,
+}
+
+/// The outcome of reinterpreting a date value with numeric dates in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Reinterpretation {
+    /// The value converted to a GEDCOM date.
+    Converted(String),
+    /// The value reads differently depending on the date order, and no order
+    /// was given.
+    Ambiguous {
+        /// The value read as day, month, year.
+        day_first: String,
+        /// The value read as month, day, year.
+        month_first: String,
+    },
+    /// The value has a numeric date that is not a valid calendar date.
+    Invalid,
+}
+
+/// Reinterprets a date value containing numeric dates.
+///
+/// Returns `None` if the value has no numeric date in it, which includes
+/// values that already are GEDCOM dates. Two-digit years are not recognized.
+#[must_use]
+pub fn reinterpret_date(value: &str, order: Option<DateOrder>) -> Option<Reinterpretation> {
+    let mut found = false;
+    let mut ambiguous = false;
+    let mut day_first = Vec::new();
+    let mut month_first = Vec::new();
+
+    for word in value.split_whitespace() {
+        match numeric_date(word) {
+            None => {
+                day_first.push(word.to_string());
+                month_first.push(word.to_string());
+            }
+            Some(Numeric::Invalid) => return Some(Reinterpretation::Invalid),
+            Some(Numeric::Definite(date)) => {
+                found = true;
+                day_first.push(date.clone());
+                month_first.push(date);
+            }
+            Some(Numeric::Ambiguous { dmy, mdy }) => {
+                found = true;
+                ambiguous = true;
+                day_first.push(dmy);
+                month_first.push(mdy);
+            }
+        }
+    }
+
+    if !found {
+        return None;
+    }
+    let (day_first, month_first) = (day_first.join(" "), month_first.join(" "));
+    Some(match order {
+        _ if !ambiguous => Reinterpretation::Converted(day_first),
+        Some(DateOrder::DayMonthYear) => Reinterpretation::Converted(day_first),
+        Some(DateOrder::MonthDayYear) => Reinterpretation::Converted(month_first),
+        None => Reinterpretation::Ambiguous {
+            day_first,
+            month_first,
+        },
+    })
+}
+
+/// A date value that was converted to a GEDCOM date.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConvertedDate {
+    /// The record holding the date.
+    pub xref: Option<String>,
+    /// The value as found in the file.
+    pub original: String,
+    /// The GEDCOM date it was replaced with.
+    pub converted: String,
+}
+
+/// A date value that was left untouched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UntouchedDate {
+    /// The record holding the date.
+    pub xref: Option<String>,
+    /// The value as found in the file.
+    pub value: String,
+    /// The readings of an ambiguous value, or `None` if it is not a valid date.
+    pub readings: Option<(String, String)>,
+}
+
+/// What [`GedcomData::reinterpret_dates`] changed and what it left alone.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DateReport {
+    /// Values converted to GEDCOM dates.
+    pub converted: Vec<ConvertedDate>,
+    /// Ambiguous or invalid numeric values left as they were.
+    pub untouched: Vec<UntouchedDate>,
+}
+
+impl DateReport {
+    /// Returns the ambiguous values, excluding invalid ones.
+    pub fn ambiguous(&self) -> impl Iterator<Item = &UntouchedDate> {
+        self.untouched.iter().filter(|d| d.readings.is_some())
+    }
+
+    fn apply(&mut self, xref: Option<&String>, date: &mut Date, order: Option<DateOrder>) {
+        let Some(value) = date.value.as_ref() else {
+            return;
+        };
+        let (readings, converted) = match reinterpret_date(value, order) {
+            None => return,
+            Some(Reinterpretation::Converted(converted)) => (None, Some(converted)),
+            Some(Reinterpretation::Ambiguous {
+                day_first,
+                month_first,
+            }) => (Some((day_first, month_first)), None),
+            Some(Reinterpretation::Invalid) => (None, None),
+        };
+        match converted {
+            Some(converted) => {
+                self.converted.push(ConvertedDate {
+                    xref: xref.cloned(),
+                    original: value.clone(),
+                    converted: converted.clone(),
+                });
+                date.value = Some(converted);
+            }
+            None => self.untouched.push(UntouchedDate {
+                xref: xref.cloned(),
+                value: value.clone(),
+                readings,
+            }),
+        }
+    }
+}
+
+impl GedcomData {
+    /// Converts numeric dates such as `03/04/1887` into GEDCOM dates.
+    ///
+    /// This covers the dates of individual and family events, attributes,
+    /// non-events and LDS ordinances. Ambiguous values are converted using
+    /// `order`, or left untouched and reported if it is `None`; pass `None`
+    /// to a clone of the data to only detect non-standard dates.
+    pub fn reinterpret_dates(&mut self, order: Option<DateOrder>) -> DateReport {
+        let mut report = DateReport::default();
+
+        for individual in &mut self.individuals {
+            let xref = individual.xref.as_ref();
+            let dates = individual
+                .events
+                .iter_mut()
+                .filter_map(|e| e.date.as_mut())
+                .chain(
+                    individual
+                        .attributes
+                        .iter_mut()
+                        .filter_map(|a| a.date.as_mut()),
+                )
+                .chain(
+                    individual
+                        .non_events
+                        .iter_mut()
+                        .filter_map(|n| n.date.as_mut()),
+                )
+                .chain(
+                    individual
+                        .lds_ordinances
+                        .iter_mut()
+                        .flat_map(|o| o.date.as_mut().into_iter().chain(o.status_date.as_mut())),
+                );
+            for date in dates {
+                report.apply(xref, date, order);
+            }
+        }
+
+        for family in &mut self.families {
+            let xref = family.xref.as_ref();
+            let dates = family
+                .events
+                .iter_mut()
+                .chain(family.family_event.iter_mut())
+                .filter_map(|e| e.date.as_mut())
+                .chain(family.non_events.iter_mut().filter_map(|n| n.date.as_mut()))
+                .chain(
+                    family
+                        .lds_ordinances
+                        .iter_mut()
+                        .flat_map(|o| o.date.as_mut().into_iter().chain(o.status_date.as_mut())),
+                );
+            for date in dates {
+                report.apply(xref, date, order);
+            }
+        }
+
+        report
+    }
+}
+
+/// A numeric date word, as read by [`numeric_date`].
+enum Numeric {
+    Definite(String),
+    Ambiguous { dmy: String, mdy: String },
+    Invalid,
+}
+
+/// Reads one word as a numeric date, or returns `None` if it is not one.
+fn numeric_date(word: &str) -> Option<Numeric> {
+    let separator = word.chars().find(|c| matches!(c, '/' | '.' | '-'))?;
+    let parts: Vec<&str> = word.split(separator).collect();
+    let numeric = |p: &&str| !p.is_empty() && p.len() <= 4 && p.bytes().all(|b| b.is_ascii_digit());
+    if !parts.iter().all(numeric) {
+        return None;
+    }
+    let short = |p: &str| p.len() <= 2;
+    let definite = |date: Option<String>| Some(date.map_or(Numeric::Invalid, Numeric::Definite));
+
+    match parts[..] {
+        [year, month, day] if year.len() == 4 && short(month) && short(day) => {
+            definite(gedcom_date(Some(day), month, year))
+        }
+        [year, month] | [month, year] if year.len() == 4 && short(month) => {
+            definite(gedcom_date(None, month, year))
+        }
+        [a, b, year] if year.len() == 4 && short(a) && short(b) => {
+            match (gedcom_date(Some(a), b, year), gedcom_date(Some(b), a, year)) {
+                (Some(dmy), Some(mdy)) if dmy != mdy => Some(Numeric::Ambiguous { dmy, mdy }),
+                (Some(date), _) | (None, Some(date)) => Some(Numeric::Definite(date)),
+                (None, None) => Some(Numeric::Invalid),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Formats a day, month and year as a GEDCOM date, if they form a valid date.
+fn gedcom_date(day: Option<&str>, month: &str, year: &str) -> Option<String> {
+    let year: u32 = year.parse().ok()?;
+    let month: u32 = month.parse().ok()?;
+    let name = MONTHS.get(usize::try_from(month).ok()?.checked_sub(1)?)?;
+    match day {
+        None => Some(format!("{name} {year}")),
+        Some(day) => {
+            let day: u32 = day.parse().ok()?;
+            (1..=days_in_month(month, year))
+                .contains(&day)
+                .then(|| format!("{day} {name} {year}"))
+        }
+    }
+}
+
+fn days_in_month(month: u32, year: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GedcomBuilder;
+
+    fn convert(value: &str, order: Option<DateOrder>) -> Option<Reinterpretation> {
+        reinterpret_date(value, order)
+    }
+
+    #[test]
+    fn test_reinterpret_date_formats() {
+        let converted = |s: &str| Some(Reinterpretation::Converted(s.to_string()));
+
+        assert_eq!(convert("1887-04-03", None), converted("3 APR 1887"));
+        assert_eq!(convert("1887/4/3", None), converted("3 APR 1887"));
+        assert_eq!(convert("1887-04", None), converted("APR 1887"));
+        assert_eq!(convert("04/1887", None), converted("APR 1887"));
+        assert_eq!(convert("25/12/1887", None), converted("25 DEC 1887"));
+        assert_eq!(convert("12.25.1887", None), converted("25 DEC 1887"));
+        assert_eq!(convert("5/5/1887", None), converted("5 MAY 1887"));
+        assert_eq!(
+            convert("BET 1/13/1900 AND 1900-02", None),
+            converted("BET 13 JAN 1900 AND FEB 1900")
+        );
+
+        assert_eq!(
+            convert("abt 03/04/1887", None),
+            Some(Reinterpretation::Ambiguous {
+                day_first: "abt 3 APR 1887".to_string(),
+                month_first: "abt 4 MAR 1887".to_string(),
+            })
+        );
+        assert_eq!(
+            convert("03/04/1887", Some(DateOrder::DayMonthYear)),
+            converted("3 APR 1887")
+        );
+
+        assert_eq!(convert("31/31/1887", None), Some(Reinterpretation::Invalid));
+        assert_eq!(convert("1900-02-29", None), Some(Reinterpretation::Invalid));
+        assert_eq!(convert("2000-02-29", None), converted("29 FEB 2000"));
+
+        assert_eq!(convert("3 APR 1887", None), None);
+        assert_eq!(convert("1900-1910", None), None);
+        assert_eq!(convert("03/04/87", None), None);
+        assert_eq!(convert("@#DJULIAN@ 15 MAR 1582", None), None);
+    }
+
+    #[test]
+    fn test_reinterpret_dates_report() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 BIRT\n\
+            2 DATE 1887-04-03\n\
+            1 DEAT\n\
+            2 DATE 03/04/1950\n\
+            1 OCCU Farmer\n\
+            2 DATE 31/31/1900\n\
+            0 @F1@ FAM\n\
+            1 MARR\n\
+            2 DATE 12 JUN 1910\n\
+            0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let report = data.clone().reinterpret_dates(None);
+        assert_eq!(report.converted.len(), 1);
+        assert_eq!(report.converted[0].xref.as_deref(), Some("@I1@"));
+        assert_eq!(report.converted[0].converted, "3 APR 1887");
+        assert_eq!(report.untouched.len(), 2);
+        let ambiguous: Vec<_> = report.ambiguous().collect();
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].value, "03/04/1950");
+
+        let report = data.reinterpret_dates(Some(DateOrder::MonthDayYear));
+        assert_eq!(report.converted.len(), 2);
+        assert_eq!(report.untouched.len(), 1);
+        let john = &data.individuals[0];
+        let event_date = |i: usize| john.events[i].date.as_ref().unwrap().value.as_deref();
+        assert_eq!(event_date(0), Some("3 APR 1887"));
+        assert_eq!(event_date(1), Some("4 MAR 1950"));
+        assert_eq!(
+            data.families[0].events[0]
+                .date
+                .as_ref()
+                .unwrap()
+                .value
+                .as_deref(),
+            Some("12 JUN 1910")
+        );
+    }
+}
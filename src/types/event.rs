@@ -3,12 +3,12 @@ pub mod family;
 pub mod spouse;
 pub mod util;
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Event {
     Adoption,
     AdultChristening,
@@ -162,7 +162,7 @@ mod tests {
             "15 MAR 2020"
         );
         assert_eq!(
-            sep.place.as_ref().unwrap().value.as_ref().unwrap(),
+            sep.place.as_ref().unwrap().value.as_deref().unwrap(),
             "Los Angeles, CA"
         );
     }
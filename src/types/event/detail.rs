@@ -1,4 +1,4 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -6,18 +6,77 @@ use crate::{
     parser::{parse_subset, Parser},
     tokenizer::{Token, Tokenizer},
     types::{
+        address::Address,
         date::Date,
         event::{family::FamilyEventDetail, Event},
         gedcom7::SortDate,
-        individual::{association::Association, family_link::FamilyLink},
+        individual::{
+            association::Association,
+            family_link::{adopted::AdoptedByWhichParent, FamilyLink},
+        },
         multimedia::Multimedia,
-        note::Note,
+        note::NoteRef,
         place::Place,
         source::citation::Citation,
     },
     GedcomError,
 };
 
+/// The payload on the line of an event tag.
+///
+/// GEDCOM uses `1 DEAT Y` to say that an event happened when nothing else is
+/// known about it, while `EVEN` and, in GEDCOM 7.0, `RESI` carry text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EventValue {
+    /// No payload, as in `1 BIRT`.
+    #[default]
+    None,
+    /// The `Y` flag: the event is known to have happened.
+    Asserted,
+    /// Descriptive text, as in `1 EVEN Coronation`.
+    Description(String),
+}
+
+impl EventValue {
+    /// Interprets the payload of an event line: `Y` asserts the event, an
+    /// empty payload is none, and anything else is a description.
+    #[must_use]
+    pub fn from_line(value: &str) -> EventValue {
+        match value.trim() {
+            "" => EventValue::None,
+            "Y" => EventValue::Asserted,
+            _ => EventValue::Description(value.to_string()),
+        }
+    }
+
+    /// Returns true for the `Y` flag.
+    #[must_use]
+    pub fn is_asserted(&self) -> bool {
+        matches!(self, EventValue::Asserted)
+    }
+
+    /// Returns the payload as it appears on the event line: `Y`, the text,
+    /// or `None`.
+    #[must_use]
+    pub fn as_payload(&self) -> Option<&str> {
+        match self {
+            EventValue::None => None,
+            EventValue::Asserted => Some("Y"),
+            EventValue::Description(text) => Some(text),
+        }
+    }
+
+    /// Returns the descriptive text, if any.
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            EventValue::Description(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
 /// `EventDetail` is a thing that happens on a specific date. Use the date form 'BET date AND date'
 /// to indicate that an event took place at some time between two dates. Resist the temptation to
 /// use a 'FROM date TO date' form in an event structure. If the subject of your recording occurred
@@ -33,10 +92,12 @@ use crate::{
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#INDIVIDUAL_EVENT_STRUCTURE>
 #[derive(Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Detail {
     pub event: Event,
-    pub value: Option<String>,
+    /// The payload on the event line: the `Y` flag, descriptive text, or
+    /// nothing.
+    pub value: EventValue,
     pub date: Option<Date>,
     /// The place where the event occurred (tag: PLAC).
     ///
@@ -46,7 +107,9 @@ pub struct Detail {
     /// - Romanized variations (ROMN)
     /// - Place form
     pub place: Option<Place>,
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
+    /// The family the individual was born, christened or adopted into
+    /// (tag: FAMC), with the adoptive parents under `ADOP` for an adoption.
     pub family_link: Option<FamilyLink>,
     pub family_event_details: Vec<FamilyEventDetail>,
     /// `event_type` handles the TYPE tag, a descriptive word or phrase used to further classify
@@ -99,6 +162,16 @@ pub struct Detail {
     /// A religious denomination to which a person is affiliated or for which
     /// a record applies.
     pub religion: Option<String>,
+    /// Address where the event took place (tag: ADDR).
+    pub address: Option<Address>,
+    /// Phone number(s) for the event (tag: PHON).
+    pub phone: Vec<String>,
+    /// Email address(es) for the event (tag: EMAIL).
+    pub email: Vec<String>,
+    /// Fax number(s) for the event (tag: FAX).
+    pub fax: Vec<String>,
+    /// Website URL(s) for the event (tag: WWW).
+    pub website: Vec<String>,
 }
 
 impl Detail {
@@ -110,10 +183,10 @@ impl Detail {
     pub fn new(tokenizer: &mut Tokenizer, level: u8, tag: &str) -> Result<Detail, GedcomError> {
         let mut event = Detail {
             event: Self::from_tag(tag),
-            value: None,
+            value: EventValue::None,
             date: None,
             place: None,
-            note: None,
+            notes: Vec::new(),
             family_link: None,
             family_event_details: Vec::new(),
             event_type: None,
@@ -126,6 +199,11 @@ impl Detail {
             age: None,
             agency: None,
             religion: None,
+            address: None,
+            phone: Vec::new(),
+            email: Vec::new(),
+            fax: Vec::new(),
+            website: Vec::new(),
         };
         event.parse(tokenizer, level)?;
         Ok(event)
@@ -197,6 +275,20 @@ impl Detail {
     pub fn get_citations(&self) -> Vec<Citation> {
         self.citations.clone()
     }
+
+    /// Gets the xref of the family the individual was born, christened or
+    /// adopted into.
+    #[must_use]
+    pub fn family(&self) -> Option<&str> {
+        self.family_link.as_ref().map(|link| link.xref.as_str())
+    }
+
+    /// Gets which parents of the family adopted the individual, for an
+    /// adoption.
+    #[must_use]
+    pub fn adopted_by(&self) -> Option<&AdoptedByWhichParent> {
+        self.family_link.as_ref()?.adopted_by.as_ref()
+    }
 }
 
 impl std::fmt::Debug for Detail {
@@ -243,7 +335,7 @@ impl Parser for Detail {
                         tag,
                     )?);
                 }
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "TYPE" => self.event_type = Some(tokenizer.take_line_value()?),
                 "OBJE" => {
                     self.add_multimedia_record(Multimedia::new(tokenizer, level + 1, pointer)?);
@@ -257,6 +349,11 @@ impl Parser for Detail {
                 "AGE" => self.age = Some(tokenizer.take_line_value()?),
                 "AGNC" => self.agency = Some(tokenizer.take_line_value()?),
                 "RELI" => self.religion = Some(tokenizer.take_line_value()?),
+                "ADDR" => self.address = Some(Address::new(tokenizer, level + 1)?),
+                "PHON" => self.phone.push(tokenizer.take_line_value()?),
+                "EMAIL" => self.email.push(tokenizer.take_line_value()?),
+                "FAX" => self.fax.push(tokenizer.take_line_value()?),
+                "WWW" => self.website.push(tokenizer.take_line_value()?),
                 _ => {
                     // Gracefully skip unknown tags instead of failing
                     // This handles non-standard extensions from various GEDCOM generators
@@ -268,9 +365,7 @@ impl Parser for Detail {
 
         parse_subset(tokenizer, level, handle_subset)?;
 
-        if !value.is_empty() {
-            self.value = Some(value);
-        }
+        self.value = EventValue::from_line(&value);
 
         Ok(())
     }
@@ -278,7 +373,7 @@ impl Parser for Detail {
 
 #[cfg(test)]
 mod tests {
-    use crate::Gedcom;
+    use crate::{types::individual::family_link::adopted::AdoptedByWhichParent, Gedcom};
 
     #[test]
     fn test_parse_event_with_cause() {
@@ -410,4 +505,67 @@ mod tests {
         );
         assert_eq!(death.restriction.as_ref().unwrap(), "privacy");
     }
+
+    #[test]
+    fn test_parse_event_with_address() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 GRAD\n\
+            2 DATE 1972\n\
+            2 ADDR Harvard University\n\
+            3 CITY Cambridge\n\
+            2 PHON +1-617-495-1000\n\
+            2 PHON +1-617-495-1551\n\
+            2 EMAIL registrar@harvard.edu\n\
+            2 WWW https://www.harvard.edu\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let graduation = &data.individuals[0].events[0];
+        let address = graduation.address.as_ref().unwrap();
+        assert_eq!(address.value.as_deref(), Some("Harvard University"));
+        assert_eq!(address.city.as_deref(), Some("Cambridge"));
+        assert_eq!(graduation.phone, ["+1-617-495-1000", "+1-617-495-1551"]);
+        assert_eq!(graduation.email, ["registrar@harvard.edu"]);
+        assert!(graduation.fax.is_empty());
+        assert_eq!(graduation.website, ["https://www.harvard.edu"]);
+    }
+
+    #[test]
+    fn test_parse_adoption_family() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 ADOP\n\
+            2 DATE 1952\n\
+            2 FAMC @F2@\n\
+            3 ADOP WIFE\n\
+            4 PHRASE Adopted by her stepmother\n\
+            1 BIRT\n\
+            2 FAMC @F1@\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let adoption = &data.individuals[0].events[0];
+        assert_eq!(adoption.family(), Some("@F2@"));
+        assert_eq!(adoption.adopted_by(), Some(&AdoptedByWhichParent::Wife));
+        let link = adoption.family_link.as_ref().unwrap();
+        assert_eq!(
+            link.adopted_by_phrase.as_deref(),
+            Some("Adopted by her stepmother")
+        );
+
+        let birth = &data.individuals[0].events[1];
+        assert_eq!(birth.family(), Some("@F1@"));
+        assert_eq!(birth.adopted_by(), None);
+    }
 }
@@ -1,4 +1,4 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -9,11 +9,18 @@ use crate::{
 };
 
 /// `FamilyEventDetail` defines an additional dataset found in certain events.
-#[derive(Clone, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+///
+/// It records the age of one spouse at the time of a family event, written as
+/// `HUSB` or `WIFE` under the event with a nested `AGE`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FamilyEventDetail {
     pub member: Spouse,
     pub age: Option<String>,
+    /// Free-text description of the age (tag: PHRASE under AGE, GEDCOM 7.0).
+    ///
+    /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#PHRASE>
+    pub age_phrase: Option<String>,
 }
 
 impl FamilyEventDetail {
@@ -30,11 +37,21 @@ impl FamilyEventDetail {
         let mut fe = FamilyEventDetail {
             member: Self::from_tag(tag),
             age: None,
+            age_phrase: None,
         };
         fe.parse(tokenizer, level)?;
         Ok(fe)
     }
 
+    /// Returns the tag (`HUSB` or `WIFE`) that introduces this detail.
+    #[must_use]
+    pub fn tag(&self) -> &'static str {
+        match self.member {
+            Spouse::Spouse1 => "HUSB",
+            Spouse::Spouse2 => "WIFE",
+        }
+    }
+
     /// # Panics
     ///
     /// Panics when encountering an unrecognized tag
@@ -55,6 +72,7 @@ impl Parser for FamilyEventDetail {
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "AGE" => self.age = Some(tokenizer.take_line_value()?),
+                "PHRASE" => self.age_phrase = Some(tokenizer.take_line_value()?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -71,3 +89,35 @@ impl Parser for FamilyEventDetail {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{types::event::spouse::Spouse, Gedcom};
+
+    #[test]
+    fn test_parse_family_event_detail() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @F1@ FAM\n\
+            1 MARR\n\
+            2 HUSB\n\
+            3 AGE 25y\n\
+            2 WIFE\n\
+            3 AGE 22y\n\
+            4 PHRASE Twenty-two\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let details = &data.families[0].events[0].family_event_details;
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].member, Spouse::Spouse1);
+        assert_eq!(details[0].age.as_deref(), Some("25y"));
+        assert_eq!(details[1].tag(), "WIFE");
+        assert_eq!(details[1].age.as_deref(), Some("22y"));
+        assert_eq!(details[1].age_phrase.as_deref(), Some("Twenty-two"));
+    }
+}
@@ -31,7 +31,7 @@ pub trait HasEvents {
         for event in self.events() {
             if let Some(p) = &event.place {
                 if let Some(ref value) = p.value {
-                    names.push(value.clone());
+                    names.push(value.to_string());
                 }
             }
         }
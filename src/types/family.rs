@@ -1,21 +1,26 @@
+pub mod child;
+
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::{Token, Tokenizer},
     types::{
-        custom::UserDefinedTag,
+        custom::{take_vendor_uids, UserDefinedTag},
         date::change_date::ChangeDate,
         event::{detail::Detail, util::HasEvents},
+        family::child::FamilyChild,
         gedcom7::NonEvent,
+        individual::association::Association,
         lds::LdsOrdinance,
         multimedia::Multimedia,
-        note::Note,
+        note::NoteRef,
+        shared_note::ExternalId,
         source::citation::Citation,
         Xref,
     },
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Family fact, representing a relationship between `Individual`s
@@ -27,22 +32,28 @@ use serde::{Deserialize, Serialize};
 ///
 /// In GEDCOM 7.0, families can have:
 /// - `NO` - Non-event assertions (e.g., "NO CHIL" means no children)
+/// - `ASSO` - Associations with other individuals
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#NO>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Family {
     pub xref: Option<Xref>,
     pub individual1: Option<Xref>, // mapped from HUSB
     pub individual2: Option<Xref>, // mapped from WIFE
     pub family_event: Vec<Detail>,
     pub children: Vec<Xref>,
+    /// Substructures of the `CHIL` lines that have any, such as a `PHRASE`
+    /// or the `_FREL`/`_MREL` relationships of Family Tree Maker.
+    pub child_details: Vec<FamilyChild>,
     pub num_children: Option<String>,
     pub change_date: Option<ChangeDate>,
+    /// The date this record was created (tag: CREA, GEDCOM 7.0).
+    pub creation_date: Option<ChangeDate>,
     pub events: Vec<Detail>,
     pub sources: Vec<Citation>,
     pub multimedia: Vec<Multimedia>,
-    pub notes: Vec<Note>,
+    pub notes: Vec<NoteRef>,
     pub custom_data: Vec<Box<UserDefinedTag>>,
     /// Non-event assertions for GEDCOM 7.0.
     ///
@@ -60,6 +71,11 @@ pub struct Family {
     ///
     /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#UID>
     pub uid: Option<String>,
+    /// Vendor unique identifiers (tag: _UID).
+    ///
+    /// GUIDs recorded by desktop applications under the `_UID` extension,
+    /// common in GEDCOM 5.5.1 files.
+    pub vendor_uids: Vec<String>,
     /// Restriction notice (tag: RESN).
     ///
     /// A flag that indicates access to information has been restricted.
@@ -87,7 +103,10 @@ pub struct Family {
     /// External identifiers (tag: EXID, GEDCOM 7.0).
     ///
     /// Identifiers maintained by external authorities that apply to this family.
-    pub external_ids: Vec<String>,
+    pub external_ids: Vec<ExternalId>,
+    /// Associations with individuals outside the family, such as the
+    /// officiator of a marriage (tag: ASSO, GEDCOM 7.0).
+    pub associations: Vec<Association>,
 }
 
 impl Family {
@@ -157,6 +176,12 @@ impl Family {
         self.children.push(xref);
     }
 
+    /// Returns the substructures of the `CHIL` line of a child, if it has any.
+    #[must_use]
+    pub fn child_detail(&self, xref: &str) -> Option<&FamilyChild> {
+        self.child_details.iter().find(|c| c.xref == xref)
+    }
+
     pub fn add_event(&mut self, family_event: Detail) {
         self.events.push(family_event);
     }
@@ -169,7 +194,7 @@ impl Family {
         self.multimedia.push(media);
     }
 
-    pub fn add_note(&mut self, note: Note) {
+    pub fn add_note(&mut self, note: NoteRef) {
         self.notes.push(note);
     }
 
@@ -177,6 +202,17 @@ impl Family {
     pub fn events(&self) -> &[Detail] {
         &self.events
     }
+
+    /// Gets the unique identifiers of the family: the `UID`, then the `_UID`
+    /// values.
+    #[must_use]
+    pub fn uids(&self) -> Vec<&str> {
+        self.uid
+            .iter()
+            .chain(&self.vendor_uids)
+            .map(String::as_str)
+            .collect()
+    }
 }
 
 impl Parser for Family {
@@ -199,13 +235,23 @@ impl Parser for Family {
                 }
                 "HUSB" => self.set_individual1(tokenizer.take_line_value()?, tokenizer.line)?,
                 "WIFE" => self.set_individual2(tokenizer.take_line_value()?, tokenizer.line)?,
-                "CHIL" => self.add_child(tokenizer.take_line_value()?),
+                "CHIL" => {
+                    let child = FamilyChild::new(tokenizer, level + 1)?;
+                    self.add_child(child.xref.clone());
+                    if !child.is_bare() {
+                        self.child_details.push(child);
+                    }
+                }
                 "NCHI" => self.num_children = Some(tokenizer.take_line_value()?),
                 "CHAN" => self.change_date = Some(ChangeDate::new(tokenizer, level + 1)?),
+                "CREA" => self.creation_date = Some(ChangeDate::new(tokenizer, level + 1)?),
                 "SOUR" => self.add_source(Citation::new(tokenizer, level + 1)?),
-                "NOTE" => self.add_note(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.add_note(NoteRef::new(tokenizer, level + 1)?),
                 "OBJE" => self.add_multimedia(Multimedia::new(tokenizer, level + 1, pointer)?),
                 "NO" => self.non_events.push(NonEvent::new(tokenizer, level + 1)?),
+                "ASSO" => self
+                    .associations
+                    .push(Association::new(tokenizer, level + 1)?),
                 // LDS Sealing to Spouse ordinance
                 "SLGS" => {
                     self.lds_ordinances
@@ -223,7 +269,10 @@ impl Parser for Family {
                 // Automated record ID
                 "RIN" => self.automated_record_id = Some(tokenizer.take_line_value()?),
                 // External identifier (GEDCOM 7.0)
-                "EXID" => self.external_ids.push(tokenizer.take_line_value()?),
+                "EXID" => {
+                    self.external_ids
+                        .push(ExternalId::from_tokenizer(tokenizer, level + 1)?);
+                }
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -236,6 +285,7 @@ impl Parser for Family {
         };
 
         self.custom_data = parse_subset(tokenizer, level, handle_subset)?;
+        take_vendor_uids(&mut self.custom_data, &mut self.vendor_uids);
 
         Ok(())
     }
@@ -0,0 +1,214 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    parser::{parse_subset, Parser},
+    tokenizer::Tokenizer,
+    types::{
+        custom::UserDefinedTag,
+        individual::family_link::{adopted::AdoptedByWhichParent, pedigree::Pedigree},
+        Xref,
+    },
+    GedcomError,
+};
+
+/// Substructures of a child pointer (tag: CHIL) in a family record.
+///
+/// Only children whose `CHIL` line has substructures get one; the pointers
+/// themselves are in [`Family::children`](crate::types::family::Family::children).
+///
+/// Family Tree Maker gives the child's relationship to each parent with the
+/// `_FREL` and `_MREL` extensions, with values such as `Natural`, `Adopted`
+/// or `Step`. [`pedigree`](Self::pedigree) reads them as the standard
+/// pedigree of the child's `FAMC` link.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FamilyChild {
+    /// Reference to the child.
+    pub xref: Xref,
+    /// tag: PHRASE (GEDCOM 7.0), free text about the child pointer, such as
+    /// its place among the siblings.
+    pub phrase: Option<String>,
+    /// tag: _FREL, the child's relationship to the father (individual1).
+    pub father_relationship: Option<String>,
+    /// tag: _MREL, the child's relationship to the mother (individual2).
+    pub mother_relationship: Option<String>,
+    /// Other extension tags.
+    pub custom_data: Vec<Box<UserDefinedTag>>,
+}
+
+impl FamilyChild {
+    /// Creates a new `FamilyChild` from a `Tokenizer`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if parsing fails.
+    pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<FamilyChild, GedcomError> {
+        let mut child = FamilyChild {
+            xref: tokenizer.take_line_value()?,
+            ..FamilyChild::default()
+        };
+        child.parse(tokenizer, level)?;
+        Ok(child)
+    }
+
+    /// Returns true if the `CHIL` line has no substructures.
+    #[must_use]
+    pub fn is_bare(&self) -> bool {
+        self.phrase.is_none()
+            && self.father_relationship.is_none()
+            && self.mother_relationship.is_none()
+            && self.custom_data.is_empty()
+    }
+
+    /// Returns the pedigree given by `_FREL` and `_MREL`, and which parent
+    /// adopted the child when it is [`Pedigree::Adopted`].
+    ///
+    /// The relationships must agree, or one of them be absent, except that
+    /// a child adopted by one parent only may be the natural child of the
+    /// other. Returns `None` otherwise, or if neither relationship has a
+    /// standard equivalent (e.g. `Step`).
+    #[must_use]
+    pub fn pedigree(&self) -> Option<(Pedigree, Option<AdoptedByWhichParent>)> {
+        let father = self
+            .father_relationship
+            .as_deref()
+            .and_then(relationship_pedigree);
+        let mother = self
+            .mother_relationship
+            .as_deref()
+            .and_then(relationship_pedigree);
+        match (father, mother) {
+            (Some(Pedigree::Adopted), Some(Pedigree::Adopted)) => {
+                Some((Pedigree::Adopted, Some(AdoptedByWhichParent::Both)))
+            }
+            (Some(Pedigree::Adopted), Some(Pedigree::Birth) | None) => {
+                Some((Pedigree::Adopted, Some(AdoptedByWhichParent::Husband)))
+            }
+            (Some(Pedigree::Birth) | None, Some(Pedigree::Adopted)) => {
+                Some((Pedigree::Adopted, Some(AdoptedByWhichParent::Wife)))
+            }
+            (Some(father), Some(mother)) if father == mother => Some((father, None)),
+            (Some(pedigree), None) | (None, Some(pedigree)) => Some((pedigree, None)),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a `_FREL` or `_MREL` value as a standard pedigree.
+#[must_use]
+pub fn relationship_pedigree(value: &str) -> Option<Pedigree> {
+    match value.trim().to_lowercase().as_str() {
+        "natural" | "birth" | "biological" => Some(Pedigree::Birth),
+        "adopted" => Some(Pedigree::Adopted),
+        "foster" => Some(Pedigree::Foster),
+        "sealing" => Some(Pedigree::Sealing),
+        _ => None,
+    }
+}
+
+impl Parser for FamilyChild {
+    fn parse(&mut self, tokenizer: &mut Tokenizer, level: u8) -> Result<(), GedcomError> {
+        let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
+            match tag {
+                "PHRASE" => self.phrase = Some(tokenizer.take_line_value()?),
+                _ => {
+                    return Err(GedcomError::ParseError {
+                        line: tokenizer.line,
+                        message: format!("Unhandled FamilyChild Tag: {tag}"),
+                    })
+                }
+            }
+            Ok(())
+        };
+
+        let mut custom_data = parse_subset(tokenizer, level, handle_subset)?;
+        custom_data.retain(|tag| match &*tag.tag {
+            "_FREL" if self.father_relationship.is_none() => {
+                self.father_relationship.clone_from(&tag.value);
+                !tag.children.is_empty()
+            }
+            "_MREL" if self.mother_relationship.is_none() => {
+                self.mother_relationship.clone_from(&tag.value);
+                !tag.children.is_empty()
+            }
+            _ => true,
+        });
+        self.custom_data = custom_data;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Gedcom;
+
+    #[test]
+    fn test_parse_child_relationships() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 FAMC @F1@\n\
+            0 @I2@ INDI\n\
+            1 FAMC @F1@\n\
+            2 PEDI foster\n\
+            0 @F1@ FAM\n\
+            1 CHIL @I1@\n\
+            2 _FREL Adopted\n\
+            2 _MREL Natural\n\
+            1 CHIL @I2@\n\
+            2 _FREL Natural\n\
+            1 CHIL @I3@\n\
+            0 TRLR";
+
+        let data = Gedcom::new(sample.chars()).unwrap().parse_data().unwrap();
+        let family = &data.families[0];
+        assert_eq!(family.children.len(), 3);
+        assert_eq!(family.child_details.len(), 2);
+        assert!(family.custom_data.is_empty());
+
+        let first = &family.child_details[0];
+        assert_eq!(first.xref, "@I1@");
+        assert_eq!(first.father_relationship.as_deref(), Some("Adopted"));
+        assert_eq!(first.mother_relationship.as_deref(), Some("Natural"));
+        assert_eq!(
+            first.pedigree(),
+            Some((Pedigree::Adopted, Some(AdoptedByWhichParent::Husband)))
+        );
+
+        // _FREL/_MREL fill in a missing PEDI but do not override one.
+        let link = &data.individuals[0].families[0];
+        assert_eq!(link.pedigree_linkage_type, Some(Pedigree::Adopted));
+        assert_eq!(link.adopted_by, Some(AdoptedByWhichParent::Husband));
+        let link = &data.individuals[1].families[0];
+        assert_eq!(link.pedigree_linkage_type, Some(Pedigree::Foster));
+    }
+
+    #[test]
+    fn test_child_pedigree_combinations() {
+        let child = |father: Option<&str>, mother: Option<&str>| FamilyChild {
+            father_relationship: father.map(str::to_string),
+            mother_relationship: mother.map(str::to_string),
+            ..FamilyChild::default()
+        };
+        assert_eq!(
+            child(Some("Natural"), Some("natural")).pedigree(),
+            Some((Pedigree::Birth, None))
+        );
+        assert_eq!(
+            child(Some("Adopted"), Some("Adopted")).pedigree(),
+            Some((Pedigree::Adopted, Some(AdoptedByWhichParent::Both)))
+        );
+        assert_eq!(
+            child(Some("Step"), Some("Adopted")).pedigree(),
+            Some((Pedigree::Adopted, Some(AdoptedByWhichParent::Wife)))
+        );
+        assert_eq!(child(Some("Foster"), Some("Natural")).pedigree(), None);
+        assert_eq!(child(Some("Step"), None).pedigree(), None);
+        assert!(child(None, None).is_bare());
+    }
+}
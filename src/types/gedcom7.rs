@@ -14,11 +14,11 @@
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{date::Date, note::Note},
+    types::{date::Date, note::NoteRef},
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A sort date structure for GEDCOM 7.0.
@@ -42,7 +42,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SDATE>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SortDate {
     /// The date value used for sorting.
     pub value: Option<String>,
@@ -116,7 +116,7 @@ impl Parser for SortDate {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#CREATION_DATE>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CreationDate {
     /// The date the record was created.
     pub date: Option<Date>,
@@ -179,7 +179,7 @@ impl Parser for CreationDate {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#CROP>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Crop {
     /// The distance from the top of the image to the top of the crop region.
     /// Expressed as a percentage (0-100) of the image height.
@@ -306,7 +306,7 @@ impl Parser for Crop {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#NO>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NonEvent {
     /// The event type that did not occur.
     ///
@@ -318,8 +318,8 @@ pub struct NonEvent {
     /// For example, "BEF 1900" means the event did not occur before 1900.
     pub date: Option<Date>,
 
-    /// A note providing additional context about the non-event.
-    pub note: Option<Note>,
+    /// Notes providing additional context about the non-event.
+    pub notes: Vec<NoteRef>,
 
     /// Source citations supporting the claim that the event did not occur.
     pub source_citations: Vec<crate::types::source::citation::Citation>,
@@ -375,7 +375,7 @@ impl Parser for NonEvent {
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "DATE" => self.date = Some(Date::new(tokenizer, level + 1)?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "SOUR" => {
                     self.source_citations
                         .push(crate::types::source::citation::Citation::new(
@@ -416,7 +416,7 @@ impl Parser for NonEvent {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#PHRASE>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Phrase {
     /// The free-text phrase.
     pub value: String,
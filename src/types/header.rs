@@ -17,7 +17,7 @@ use crate::{
     },
     GedcomError,
 };
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Header (tag: HEAD) containing GEDCOM metadata.
@@ -31,7 +31,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#HEADER>.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Header {
     /// tag: GEDC
     ///
@@ -119,7 +119,8 @@ pub struct Header {
     /// This structure must not have a payload in GEDCOM 7.0.
     pub place: Option<HeadPlac>,
 
-    /// Custom data (extension tags).
+    /// Custom data: extension tags such as `_PROJECT_GUID`, followed by any
+    /// other structure the header does not define.
     pub custom_data: Vec<Box<UserDefinedTag>>,
 }
 
@@ -195,6 +196,7 @@ impl Parser for Header {
         // skip over HEAD tag name
         tokenizer.next_token()?;
 
+        let mut unknown = Vec::new();
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "GEDC" => self.gedcom = Some(HeadMeta::new(tokenizer, level + 1)?),
@@ -210,17 +212,15 @@ impl Parser for Header {
                 "LANG" => self.language = Some(tokenizer.take_line_value()?),
                 "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
                 "PLAC" => self.place = Some(HeadPlac::new(tokenizer, level + 1)?),
-                _ => {
-                    return Err(GedcomError::ParseError {
-                        line: tokenizer.line,
-                        message: format!("Unhandled Header Tag: {tag}"),
-                    })
-                }
+                // Vendors add their own structures to the header; keep them
+                // so they are written back.
+                _ => unknown.push(Box::new(UserDefinedTag::new(tokenizer, level + 1, tag)?)),
             }
             Ok(())
         };
 
         self.custom_data = parse_subset(tokenizer, level, handle_subset)?;
+        self.custom_data.append(&mut unknown);
 
         Ok(())
     }
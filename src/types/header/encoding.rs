@@ -4,13 +4,13 @@ use crate::{
     types::source::citation::Citation,
     GedcomError,
 };
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Encoding (tag: CHAR) is a code value that represents the character set to be used to
 /// interpret this data. See GEDCOM 5.5.1 specification, p. 44
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Encoding {
     pub value: Option<String>,
     /// tag: VERS
@@ -3,14 +3,14 @@ use crate::{
     tokenizer::Tokenizer,
     GedcomError,
 };
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// `GedcomMeta` (tag: GEDC) is a container for information about the entire document. It is
 /// recommended that applications write GEDC with its required subrecord VERS as the first
 /// substructure of a HEAD. See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#GEDC>.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HeadMeta {
     /// tag: VERS
     pub version: Option<String>,
@@ -3,13 +3,13 @@ use crate::{
     tokenizer::Tokenizer,
     GedcomError,
 };
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// `HeadPlace` (tag: PLAC) is is a placeholder for providing a default PLAC.FORM, and must not
 /// have a payload. See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#HEAD-PLAC>.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HeadPlac {
     /// form (tag: FORM) is a comma-separated list of jurisdictional titles (e.g. City, County,
     /// State, Country). It has the same number of elements and in the same order as the PLAC
@@ -22,7 +22,7 @@ use crate::{
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A schema structure containing extension tag definitions.
@@ -33,7 +33,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SCHMA>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Schema {
     /// Tag definitions mapping extension tags to URIs.
     pub tag_definitions: Vec<TagDefinition>,
@@ -55,7 +55,7 @@ pub struct Schema {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#TAG>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TagDefinition {
     /// The extension tag (e.g., `_SKYPEID`).
     ///
@@ -1,12 +1,12 @@
 pub mod data;
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{corporation::Corporation, header::source::data::HeadSourData},
+    types::{corporation::Corporation, custom::UserDefinedTag, header::source::data::HeadSourData},
     GedcomError,
 };
 
@@ -15,7 +15,7 @@ use crate::{
 /// existing identifier is known, it should be used. Otherwise, a URI owned by the product should
 /// be used instead. See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#HEAD-SOUR>.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HeadSour {
     pub value: Option<String>,
     /// tag: VERS
@@ -26,6 +26,8 @@ pub struct HeadSour {
     pub corporation: Option<Corporation>,
     /// tag: DATA
     pub data: Option<HeadSourData>,
+    /// Extension tags, such as Ancestry's `_TREE`.
+    pub custom_data: Vec<Box<UserDefinedTag>>,
 }
 
 impl HeadSour {
@@ -62,7 +64,7 @@ impl Parser for HeadSour {
             Ok(())
         };
 
-        parse_subset(tokenizer, level, handle_subset)?;
+        self.custom_data = parse_subset(tokenizer, level, handle_subset)?;
 
         Ok(())
     }
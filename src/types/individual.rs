@@ -8,7 +8,7 @@ use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
     types::{
-        custom::UserDefinedTag,
+        custom::{take_vendor_uids, UserDefinedTag},
         date::change_date::ChangeDate,
         event::{detail::Detail, util::HasEvents},
         gedcom7::NonEvent,
@@ -20,15 +20,16 @@ use crate::{
             name::Name,
         },
         lds::LdsOrdinance,
-        multimedia::Multimedia,
-        note::Note,
+        multimedia::{user::UserReferenceNumber, Multimedia},
+        note::NoteRef,
+        shared_note::ExternalId,
         source::citation::Citation,
         Xref,
     },
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Individual (tag: INDI) represents a compilation of facts or hypothesized facts about an
@@ -43,10 +44,15 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#NO>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Individual {
     pub xref: Option<Xref>,
-    pub name: Option<Name>,
+    /// Names of the individual (tag: NAME), the primary one first.
+    ///
+    /// An individual can be recorded under several names, such as a birth
+    /// name and a married name, each telling its kind in
+    /// [`name_type`](Name::name_type).
+    pub names: Vec<Name>,
     pub sex: Option<Gender>,
     pub families: Vec<FamilyLink>,
     pub attributes: Vec<AttributeDetail>,
@@ -54,8 +60,10 @@ pub struct Individual {
     pub events: Vec<Detail>,
     pub multimedia: Vec<Multimedia>,
     pub last_updated: Option<String>,
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
     pub change_date: Option<ChangeDate>,
+    /// The date this record was created (tag: CREA, GEDCOM 7.0).
+    pub creation_date: Option<ChangeDate>,
     pub custom_data: Vec<Box<UserDefinedTag>>,
     /// Non-event assertions for GEDCOM 7.0.
     ///
@@ -80,6 +88,11 @@ pub struct Individual {
     ///
     /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#UID>
     pub uid: Option<String>,
+    /// Vendor unique identifiers (tag: _UID).
+    ///
+    /// GUIDs recorded by desktop applications under the `_UID` extension,
+    /// common in GEDCOM 5.5.1 files.
+    pub vendor_uids: Vec<String>,
     /// Restriction notice (tag: RESN).
     ///
     /// A flag that indicates access to information has been restricted.
@@ -90,15 +103,11 @@ pub struct Individual {
     ///
     /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#RESN>
     pub restriction: Option<String>,
-    /// User reference number (tag: REFN).
-    ///
-    /// A user-defined number or text that the submitter uses to identify
-    /// this record. Not guaranteed to be unique.
-    pub user_reference_number: Option<String>,
-    /// User reference type (tag: TYPE under REFN).
+    /// User reference numbers (tag: REFN), each with its optional TYPE.
     ///
-    /// A user-defined type for the reference number.
-    pub user_reference_type: Option<String>,
+    /// User-defined numbers or text that the submitter uses to identify
+    /// this record, in file order. Not guaranteed to be unique.
+    pub user_reference_numbers: Vec<UserReferenceNumber>,
     /// Automated record ID (tag: RIN).
     ///
     /// A unique record identification number assigned to the record by
@@ -128,7 +137,7 @@ pub struct Individual {
     /// External identifiers (tag: EXID, GEDCOM 7.0).
     ///
     /// Identifiers maintained by external authorities that apply to this individual.
-    pub external_ids: Vec<String>,
+    pub external_ids: Vec<ExternalId>,
 }
 
 impl Individual {
@@ -172,6 +181,20 @@ impl Individual {
         self.source.push(sour);
     }
 
+    pub fn add_name(&mut self, name: Name) {
+        self.names.push(name);
+    }
+
+    /// Gets the primary name: the first `NAME` of the record.
+    #[deprecated(
+        since = "0.11.0",
+        note = "an individual can have several names; use `names` instead"
+    )]
+    #[must_use]
+    pub fn name(&self) -> Option<&Name> {
+        self.names.first()
+    }
+
     pub fn add_multimedia(&mut self, multimedia: Multimedia) {
         self.multimedia.push(multimedia);
     }
@@ -189,7 +212,8 @@ impl Individual {
     // Convenience Methods for Common Data Access (Issue #29)
     // ========================================================================
 
-    /// Gets the full name as a formatted string, removing GEDCOM slashes.
+    /// Gets the full primary name as a formatted string, removing GEDCOM
+    /// slashes.
     ///
     /// # Example
     ///
@@ -205,23 +229,41 @@ impl Individual {
     /// ```
     #[must_use]
     pub fn full_name(&self) -> Option<String> {
-        self.name.as_ref().and_then(|n| {
+        self.names.first().and_then(|n| {
             n.value
                 .as_ref()
                 .map(|v| v.replace('/', "").trim().to_string())
         })
     }
 
-    /// Gets the given (first) name if available.
+    /// Gets the given (first) name of the primary name if available.
     #[must_use]
     pub fn given_name(&self) -> Option<&str> {
-        self.name.as_ref().and_then(|n| n.given.as_deref())
+        self.names.first().and_then(|n| n.given.as_deref())
     }
 
-    /// Gets the surname (family name) if available.
+    /// Gets the surname (family name) of the primary name if available.
     #[must_use]
     pub fn surname(&self) -> Option<&str> {
-        self.name.as_ref().and_then(|n| n.surname.as_deref())
+        self.names.first().and_then(|n| n.surname.as_deref())
+    }
+
+    /// Gets the given name from `GIVN`, or else from the name before the surname slashes.
+    pub(crate) fn given_name_or_parsed(&self) -> Option<&str> {
+        self.given_name().or_else(|| {
+            let name = self.names.first()?.value.as_deref()?;
+            let given = name.split('/').next()?.trim();
+            (!given.is_empty()).then_some(given)
+        })
+    }
+
+    /// Gets the surname from `SURN`, or else from between the name's slashes.
+    pub(crate) fn surname_or_parsed(&self) -> Option<&str> {
+        self.surname().or_else(|| {
+            let name = self.names.first()?.value.as_deref()?;
+            let surname = name.split('/').nth(1)?.trim();
+            (!surname.is_empty()).then_some(surname)
+        })
     }
 
     /// Checks if the individual is male.
@@ -308,6 +350,17 @@ impl Individual {
     pub fn has_sources(&self) -> bool {
         !self.source.is_empty()
     }
+
+    /// Gets the unique identifiers of the individual: the `UID`, then the
+    /// `_UID` values.
+    #[must_use]
+    pub fn uids(&self) -> Vec<&str> {
+        self.uid
+            .iter()
+            .chain(&self.vendor_uids)
+            .map(String::as_str)
+            .collect()
+    }
 }
 
 impl HasEvents for Individual {
@@ -332,7 +385,7 @@ impl Parser for Individual {
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 // TODO handle xref
-                "NAME" => self.name = Some(Name::new(tokenizer, level + 1)?),
+                "NAME" => self.add_name(Name::new(tokenizer, level + 1)?),
                 "SEX" => self.sex = Some(Gender::new(tokenizer, level + 1)?),
                 "ADOP" | "BIRT" | "BAPM" | "BARM" | "BASM" | "BLES" | "BURI" | "CENS" | "CHR"
                 | "CHRA" | "CONF" | "CREM" | "DEAT" | "EMIG" | "FCOM" | "GRAD" | "IMMI"
@@ -347,11 +400,12 @@ impl Parser for Individual {
                     self.add_family(FamilyLink::new(tokenizer, level + 1, tag)?);
                 }
                 "CHAN" => self.change_date = Some(ChangeDate::new(tokenizer, level + 1)?),
+                "CREA" => self.creation_date = Some(ChangeDate::new(tokenizer, level + 1)?),
                 "SOUR" => {
                     self.add_source_citation(Citation::new(tokenizer, level + 1)?);
                 }
                 "OBJE" => self.add_multimedia(Multimedia::new(tokenizer, level + 1, None)?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "NO" => self.non_events.push(NonEvent::new(tokenizer, level + 1)?),
                 // LDS Ordinances (INIL is GEDCOM 7.0 only)
                 "BAPL" | "CONL" | "INIL" | "ENDL" | "SLGC" => {
@@ -368,10 +422,9 @@ impl Parser for Individual {
                 // Restriction notice
                 "RESN" => self.restriction = Some(tokenizer.take_line_value()?),
                 // User reference number
-                "REFN" => {
-                    self.user_reference_number = Some(tokenizer.take_line_value()?);
-                    // Note: TYPE substructure would need to be parsed here
-                }
+                "REFN" => self
+                    .user_reference_numbers
+                    .push(UserReferenceNumber::new(tokenizer, level + 1)?),
                 // Automated record ID
                 "RIN" => self.automated_record_id = Some(tokenizer.take_line_value()?),
                 // Ancestral File Number (LDS)
@@ -383,7 +436,10 @@ impl Parser for Individual {
                 // Interest in descendants
                 "DESI" => self.descendant_interest = Some(tokenizer.take_line_value()?),
                 // External identifier (GEDCOM 7.0)
-                "EXID" => self.external_ids.push(tokenizer.take_line_value()?),
+                "EXID" => {
+                    self.external_ids
+                        .push(ExternalId::from_tokenizer(tokenizer, level + 1)?);
+                }
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -396,6 +452,7 @@ impl Parser for Individual {
         };
 
         self.custom_data = parse_subset(tokenizer, level, handle_subset)?;
+        take_vendor_uids(&mut self.custom_data, &mut self.vendor_uids);
 
         Ok(())
     }
@@ -403,7 +460,13 @@ impl Parser for Individual {
 
 #[cfg(test)]
 mod tests {
-    use crate::Gedcom;
+    use crate::{
+        types::{
+            individual::family_link::{child_link::ChildLinkStatus, pedigree::Pedigree},
+            multimedia::user::UserReferenceNumber,
+        },
+        Gedcom,
+    };
 
     #[test]
     fn test_parse_individual_record() {
@@ -421,11 +484,109 @@ mod tests {
 
         let indi = &data.individuals[0];
         assert_eq!(indi.xref.as_ref().unwrap(), "@PERSON1@");
+        assert_eq!(indi.names[0].value.as_ref().unwrap(), "John Doe");
+        assert_eq!(indi.sex.as_ref().unwrap().value.to_string(), "Male");
+    }
+
+    #[test]
+    fn test_parse_identifiers_and_interests() {
+        let sample = "\
+           0 HEAD\n\
+           1 GEDC\n\
+           2 VERS 5.5.1\n\
+           0 @I1@ INDI\n\
+           1 ALIA @I9@\n\
+           1 ANCI @U1@\n\
+           1 DESI @U2@\n\
+           1 REFN 1042\n\
+           2 TYPE Card index\n\
+           1 REFN B-7\n\
+           1 RIN 77\n\
+           0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let indi = &data.individuals[0];
+        assert_eq!(indi.aliases, vec!["@I9@".to_string()]);
+        assert_eq!(indi.ancestor_interest.as_deref(), Some("@U1@"));
+        assert_eq!(indi.descendant_interest.as_deref(), Some("@U2@"));
         assert_eq!(
-            indi.name.as_ref().unwrap().value.as_ref().unwrap(),
-            "John Doe"
+            indi.user_reference_numbers,
+            vec![
+                UserReferenceNumber {
+                    value: Some("1042".to_string()),
+                    user_reference_type: Some("Card index".to_string()),
+                },
+                UserReferenceNumber {
+                    value: Some("B-7".to_string()),
+                    user_reference_type: None,
+                },
+            ]
         );
-        assert_eq!(indi.sex.as_ref().unwrap().value.to_string(), "Male");
+        assert_eq!(indi.automated_record_id.as_deref(), Some("77"));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_primary_name() {
+        let sample = "\
+           0 HEAD\n\
+           1 GEDC\n\
+           2 VERS 5.5.1\n\
+           0 @I1@ INDI\n\
+           1 NAME Mary /Jones/\n\
+           1 NAME Mary /Smith/\n\
+           2 TYPE married\n\
+           0 @I2@ INDI\n\
+           0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let mary = &data.individuals[0];
+        assert_eq!(mary.names.len(), 2);
+        assert_eq!(mary.name(), mary.names.first());
+        assert_eq!(mary.surname_or_parsed(), Some("Jones"));
+        assert_eq!(mary.full_name().as_deref(), Some("Mary Jones"));
+        assert!(data.individuals[1].name().is_none());
+    }
+
+    #[test]
+    fn test_parse_unique_identifiers() {
+        let sample = "\
+           0 HEAD\n\
+           1 GEDC\n\
+           2 VERS 7.0\n\
+           0 @I1@ INDI\n\
+           1 UID 1f3c4d5e-0000-4000-8000-000000000001\n\
+           1 EXID 9ABC-DEF\n\
+           2 TYPE https://www.familysearch.org/tree/person/\n\
+           1 EXID 42\n\
+           1 _UID 4F2A3C1D9E8B4A7C8D6E5F4A3B2C1D0E1234\n\
+           1 _COLOR blue\n\
+           0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let indi = &data.individuals[0];
+        assert_eq!(
+            indi.uids(),
+            vec![
+                "1f3c4d5e-0000-4000-8000-000000000001",
+                "4F2A3C1D9E8B4A7C8D6E5F4A3B2C1D0E1234"
+            ]
+        );
+        assert_eq!(indi.external_ids.len(), 2);
+        assert_eq!(
+            indi.external_ids[0].full_url().as_deref(),
+            Some("https://www.familysearch.org/tree/person/9ABC-DEF")
+        );
+        assert_eq!(indi.external_ids[1].type_uri, None);
+        // Other extensions stay in the custom data.
+        assert_eq!(indi.custom_data.len(), 1);
+        assert_eq!(&*indi.custom_data[0].tag, "_COLOR");
     }
 
     #[test]
@@ -491,6 +652,32 @@ mod tests {
         assert_eq!(famc.adopted_by.as_ref().unwrap().to_string(), "Both");
     }
 
+    #[test]
+    fn test_parse_family_link_phrases() {
+        let sample = "\
+           0 HEAD\n\
+           1 GEDC\n\
+           2 VERS 7.0\n\
+           0 @I1@ INDI\n\
+           1 FAMC @F1@\n\
+           2 PEDI OTHER\n\
+           3 PHRASE Raised by an aunt\n\
+           2 STAT CHALLENGED\n\
+           3 PHRASE Contested by a cousin\n\
+           2 NOTE Letters of 1911\n\
+           0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let famc = &data.individuals[0].families[0];
+        assert_eq!(famc.pedigree_linkage_type, Some(Pedigree::Other));
+        assert_eq!(famc.pedigree_phrase.as_deref(), Some("Raised by an aunt"));
+        assert_eq!(famc.child_linkage_status, Some(ChildLinkStatus::Challenged));
+        assert_eq!(famc.status_phrase.as_deref(), Some("Contested by a cousin"));
+        assert_eq!(famc.notes.len(), 1);
+    }
+
     #[test]
     fn test_parse_name_record() {
         let sample = "\
@@ -506,10 +693,7 @@ mod tests {
 
         let indi = &data.individuals[0];
         assert_eq!(indi.xref.as_ref().unwrap(), "@PERSON1@");
-        assert_eq!(
-            indi.name.as_ref().unwrap().value.as_ref().unwrap(),
-            "John Doe"
-        );
+        assert_eq!(indi.names[0].value.as_ref().unwrap(), "John Doe");
     }
 
     #[test]
@@ -551,7 +735,7 @@ mod tests {
             "31 DEC 1997"
         );
         assert_eq!(
-            attr.place.as_ref().unwrap().value.as_ref().unwrap(),
+            attr.place.as_ref().unwrap().value.as_deref().unwrap(),
             "The place"
         );
 
@@ -571,13 +755,7 @@ mod tests {
             "31 DEC 1900"
         );
         assert_eq!(
-            a_sour
-                .data
-                .as_ref()
-                .unwrap()
-                .text
-                .as_ref()
-                .unwrap()
+            a_sour.data.as_ref().unwrap().texts[0]
                 .value
                 .as_ref()
                 .unwrap(),
@@ -588,7 +766,7 @@ mod tests {
             "Direct"
         );
         assert_eq!(
-            a_sour.note.as_ref().unwrap().value.as_ref().unwrap(),
+            a_sour.notes[0].inline().unwrap().value.as_ref().unwrap(),
             "A note\nNote continued here. The word TEST should not be broken!"
         );
     }
@@ -1,18 +1,145 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{custom::UserDefinedTag, note::Note, Xref},
+    types::{custom::UserDefinedTag, note::NoteRef, source::citation::Citation, Xref},
     GedcomError,
 };
 
+/// The role of an associated individual, or of the individual in a cited
+/// event.
+///
+/// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#enumset-ROLE>
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Role {
+    /// Child
+    Child,
+    /// Religious official in an event that is also a religious ceremony
+    Clergy,
+    /// Father
+    Father,
+    /// Friend
+    Friend,
+    /// Godparent or a similar role in religions other than Christianity
+    Godparent,
+    /// Husband
+    Husband,
+    /// Mother
+    Mother,
+    /// A role not covered by the others for several individuals at once
+    Multiple,
+    /// Neighbor
+    Neighbor,
+    /// Officiator of the event
+    Officiator,
+    /// Parent
+    Parent,
+    /// Spouse
+    Spouse,
+    /// Wife
+    Wife,
+    /// Witness
+    Witness,
+    /// `OTHER`, or a custom role
+    Other(String),
+}
+
+impl Role {
+    /// Parses a role string into a `Role`.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        match value.to_uppercase().as_str() {
+            "CHIL" => Role::Child,
+            "CLERGY" => Role::Clergy,
+            "FATH" => Role::Father,
+            "FRIEND" => Role::Friend,
+            "GODP" => Role::Godparent,
+            "HUSB" => Role::Husband,
+            "MOTH" => Role::Mother,
+            "MULTIPLE" => Role::Multiple,
+            "NGHBR" => Role::Neighbor,
+            "OFFICIATOR" => Role::Officiator,
+            "PARENT" => Role::Parent,
+            "SPOU" => Role::Spouse,
+            "WIFE" => Role::Wife,
+            "WITN" => Role::Witness,
+            _ => Role::Other(value.to_string()),
+        }
+    }
+
+    /// Returns the GEDCOM tag value for this role.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Role::Child => "CHIL",
+            Role::Clergy => "CLERGY",
+            Role::Father => "FATH",
+            Role::Friend => "FRIEND",
+            Role::Godparent => "GODP",
+            Role::Husband => "HUSB",
+            Role::Mother => "MOTH",
+            Role::Multiple => "MULTIPLE",
+            Role::Neighbor => "NGHBR",
+            Role::Officiator => "OFFICIATOR",
+            Role::Parent => "PARENT",
+            Role::Spouse => "SPOU",
+            Role::Wife => "WIFE",
+            Role::Witness => "WITN",
+            Role::Other(s) => s,
+        }
+    }
+
+    /// Reads a `ROLE` and its `PHRASE` from a `Tokenizer` positioned on the
+    /// `ROLE` tag at `level`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if parsing fails.
+    pub(crate) fn from_tokenizer(
+        tokenizer: &mut Tokenizer,
+        level: u8,
+    ) -> Result<(Role, Option<String>), GedcomError> {
+        let role = Role::parse(&tokenizer.take_line_value()?);
+        let mut phrase = None;
+        parse_subset(tokenizer, level, |tag, tokenizer| {
+            if tag != "PHRASE" {
+                return Err(GedcomError::ParseError {
+                    line: tokenizer.line,
+                    message: format!("Unhandled Role Tag: {tag}"),
+                });
+            }
+            phrase = Some(tokenizer.take_line_value()?);
+            Ok(())
+        })?;
+        Ok((role, phrase))
+    }
+
+    /// Returns true for the roles defined by GEDCOM 7.0, including `OTHER`.
+    #[must_use]
+    pub fn is_standard(&self) -> bool {
+        !matches!(self, Role::Other(s) if s != "OTHER")
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Association (tag: ASSO) is an optional pointer to an individual with whom this
-/// individual has some relationship not covered by other standard tags.
+/// individual has some relationship not covered by other standard tags, such
+/// as a godparent or a witness.
 /// See GEDCOM 5.5.1 specification, page 58.
+///
+/// GEDCOM 5.5.1 describes the relationship in words (`RELA`), GEDCOM 7.0 with
+/// a [`Role`] and an optional phrase (`ROLE` and `PHRASE`). The writer turns
+/// one into the other when the target version needs it.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Association {
     /// Reference to associated individual
     pub xref: Xref,
@@ -20,8 +147,17 @@ pub struct Association {
     pub relationship: Option<String>,
     /// tag: TYPE, indicator of the type of association
     pub association_type: Option<String>,
+    /// tag: PHRASE (GEDCOM 7.0), free text about the pointer, such as the
+    /// name of an individual who has no record
+    pub phrase: Option<String>,
+    /// tag: ROLE (GEDCOM 7.0), the role of the associated individual
+    pub role: Option<Role>,
+    /// tag: PHRASE under ROLE (GEDCOM 7.0), the role in words
+    pub role_phrase: Option<String>,
     /// tag: NOTE, additional notes about this association
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
+    /// tag: SOUR, citations supporting this association
+    pub citations: Vec<Citation>,
     /// Custom tags not defined in GEDCOM specification
     pub custom_data: Vec<Box<UserDefinedTag>>,
 }
@@ -37,12 +173,26 @@ impl Association {
             xref: tokenizer.take_line_value()?,
             relationship: None,
             association_type: None,
-            note: None,
+            phrase: None,
+            role: None,
+            role_phrase: None,
+            notes: Vec::new(),
+            citations: Vec::new(),
             custom_data: Vec::new(),
         };
         association.parse(tokenizer, level)?;
         Ok(association)
     }
+
+    /// Returns the relationship in words: the `RELA` value, or else the role
+    /// phrase, or else the role.
+    #[must_use]
+    pub fn description(&self) -> Option<String> {
+        self.relationship
+            .clone()
+            .or_else(|| self.role_phrase.clone())
+            .or_else(|| self.role.as_ref().map(ToString::to_string))
+    }
 }
 
 impl Parser for Association {
@@ -51,7 +201,14 @@ impl Parser for Association {
             match tag {
                 "RELA" => self.relationship = Some(tokenizer.take_line_value()?),
                 "TYPE" => self.association_type = Some(tokenizer.take_line_value()?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "PHRASE" => self.phrase = Some(tokenizer.take_line_value()?),
+                "ROLE" => {
+                    let (role, phrase) = Role::from_tokenizer(tokenizer, level + 1)?;
+                    self.role = Some(role);
+                    self.role_phrase = phrase;
+                }
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
+                "SOUR" => self.citations.push(Citation::new(tokenizer, level + 1)?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -70,6 +227,7 @@ impl Parser for Association {
 
 #[cfg(test)]
 mod tests {
+    use super::Role;
     use crate::Gedcom;
 
     #[test]
@@ -99,4 +257,46 @@ mod tests {
             "COWORKER"
         );
     }
+
+    #[test]
+    fn test_parse_association_role() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 ASSO @I2@\n\
+            2 ROLE GODP\n\
+            3 PHRASE Godmother\n\
+            2 SOUR @S1@\n\
+            3 PAGE Folio 12\n\
+            1 ASSO @VOID@\n\
+            2 PHRASE Mr Smith\n\
+            2 ROLE WITN\n\
+            0 @F1@ FAM\n\
+            1 ASSO @I3@\n\
+            2 ROLE OFFICIATOR\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let associations = &data.individuals[0].associations;
+        assert_eq!(associations.len(), 2);
+        assert_eq!(associations[0].role, Some(Role::Godparent));
+        assert_eq!(associations[0].role_phrase.as_deref(), Some("Godmother"));
+        assert_eq!(associations[0].description().unwrap(), "Godmother");
+        assert_eq!(
+            associations[0].citations[0].page.as_deref(),
+            Some("Folio 12")
+        );
+        assert_eq!(associations[1].phrase.as_deref(), Some("Mr Smith"));
+        assert_eq!(associations[1].description().unwrap(), "WITN");
+
+        let family = &data.families[0];
+        assert_eq!(family.associations[0].xref, "@I3@");
+        assert_eq!(family.associations[0].role, Some(Role::Officiator));
+        assert!(Role::parse("OTHER").is_standard());
+        assert!(!Role::parse("Sponsor").is_standard());
+    }
 }
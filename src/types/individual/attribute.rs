@@ -1,6 +1,6 @@
 pub mod detail;
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// `IndividualAttribute` indicates other attributes or facts are used to describe an individual's
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 /// generally thought of as events. However, they are often described like events because they were
 /// observed at a particular time and/or place. See GEDCOM 5.5 spec, page 33.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum IndividualAttribute {
     CastName,
     PhysicalDescription,
@@ -1,11 +1,11 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::{Token, Tokenizer},
     types::{
-        address::Address, date::Date, individual::attribute::IndividualAttribute, note::Note,
+        address::Address, date::Date, individual::attribute::IndividualAttribute, note::NoteRef,
         place::Place, source::citation::Citation,
     },
     GedcomError,
@@ -19,7 +19,7 @@ use crate::{
 /// handled just a tag and value, can be read as usual by handling the subordinate attribute detail
 /// as an exception. . See GEDCOM 5.5 spec, page 69.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AttributeDetail {
     pub attribute: IndividualAttribute,
     pub value: Option<String>,
@@ -33,7 +33,7 @@ pub struct AttributeDetail {
     pub place: Option<Place>,
     pub date: Option<Date>,
     pub sources: Vec<Citation>,
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
     /// `attribute_type` handles the TYPE tag, a descriptive word or phrase used to further
     /// classify the parent event or attribute tag. This should be used to define what kind of
     /// identification number or fact classification is being defined.
@@ -54,6 +54,14 @@ pub struct AttributeDetail {
     ///
     /// Commonly used with RESI (residence) attributes.
     pub address: Option<Address>,
+    /// Phone number(s) for the attribute (tag: PHON).
+    pub phone: Vec<String>,
+    /// Email address(es) for the attribute (tag: EMAIL).
+    pub email: Vec<String>,
+    /// Fax number(s) for the attribute (tag: FAX).
+    pub fax: Vec<String>,
+    /// Website URL(s) for the attribute (tag: WWW).
+    pub website: Vec<String>,
     /// Cause related to this attribute (tag: CAUS).
     pub cause: Option<String>,
     /// Responsible agency (tag: AGNC).
@@ -77,11 +85,15 @@ impl AttributeDetail {
             value: None,
             date: None,
             sources: Vec::new(),
-            note: None,
+            notes: Vec::new(),
             attribute_type: None,
             restriction: None,
             age: None,
             address: None,
+            phone: Vec::new(),
+            email: Vec::new(),
+            fax: Vec::new(),
+            website: Vec::new(),
             cause: None,
             agency: None,
         };
@@ -146,11 +158,15 @@ impl Parser for AttributeDetail {
                 "DATE" => self.date = Some(Date::new(tokenizer, level + 1)?),
                 "SOUR" => self.add_source_citation(Citation::new(tokenizer, level + 1)?),
                 "PLAC" => self.place = Some(Place::new(tokenizer, level + 1)?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "TYPE" => self.attribute_type = Some(tokenizer.take_continued_text(level + 1)?),
                 "RESN" => self.restriction = Some(tokenizer.take_line_value()?),
                 "AGE" => self.age = Some(tokenizer.take_line_value()?),
                 "ADDR" => self.address = Some(Address::new(tokenizer, level + 1)?),
+                "PHON" => self.phone.push(tokenizer.take_line_value()?),
+                "EMAIL" => self.email.push(tokenizer.take_line_value()?),
+                "FAX" => self.fax.push(tokenizer.take_line_value()?),
+                "WWW" => self.website.push(tokenizer.take_line_value()?),
                 "CAUS" => self.cause = Some(tokenizer.take_continued_text(level + 1)?),
                 "AGNC" => self.agency = Some(tokenizer.take_line_value()?),
                 _ => {
@@ -247,7 +263,7 @@ mod tests {
         let resi = &data.individuals[0].attributes[0];
         assert!(resi.place.is_some());
         let place = resi.place.as_ref().unwrap();
-        assert_eq!(place.value.as_ref().unwrap(), "Paris, France");
+        assert_eq!(place.value.as_deref().unwrap(), "Paris, France");
         assert!(place.has_coordinates());
         assert!((place.latitude().unwrap() - 48.8566).abs() < 0.0001);
         assert!((place.longitude().unwrap() - 2.3522).abs() < 0.0001);
@@ -2,7 +2,7 @@ pub mod adopted;
 pub mod child_link;
 pub mod pedigree;
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -13,7 +13,7 @@ use crate::{
         individual::family_link::{
             adopted::AdoptedByWhichParent, child_link::ChildLinkStatus, pedigree::Pedigree,
         },
-        note::Note,
+        note::NoteRef,
         Xref,
     },
     GedcomError,
@@ -23,7 +23,7 @@ use crate::{
 /// where this person is a child (FAMC tag), or it is pointer to a family where this person is a
 /// spouse or parent (FAMS tag). See GEDCOM 5.5 spec, page 26.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FamilyLinkType {
     Spouse,
     Child,
@@ -40,14 +40,20 @@ impl std::fmt::Display for FamilyLinkType {
 /// family where this person is a child. The FAMS tag provides a pointer to a family where this
 /// person is a spouse or parent. See GEDCOM 5.5 spec, page 26.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FamilyLink {
     pub xref: Xref,
     pub family_link_type: FamilyLinkType,
     pub pedigree_linkage_type: Option<Pedigree>,
+    /// The pedigree in words (tag: PHRASE under PEDI, GEDCOM 7.0).
+    pub pedigree_phrase: Option<String>,
     pub child_linkage_status: Option<ChildLinkStatus>,
+    /// The status in words (tag: PHRASE under STAT, GEDCOM 7.0).
+    pub status_phrase: Option<String>,
     pub adopted_by: Option<AdoptedByWhichParent>,
-    pub note: Option<Note>,
+    /// The adoption in words (tag: PHRASE under ADOP, GEDCOM 7.0).
+    pub adopted_by_phrase: Option<String>,
+    pub notes: Vec<NoteRef>,
     pub custom_data: Vec<Box<UserDefinedTag>>,
 }
 
@@ -73,9 +79,12 @@ impl FamilyLink {
             xref,
             family_link_type: link_type,
             pedigree_linkage_type: None,
+            pedigree_phrase: None,
             child_linkage_status: None,
+            status_phrase: None,
             adopted_by: None,
-            note: None,
+            adopted_by_phrase: None,
+            notes: Vec::new(),
             custom_data: Vec::new(),
         };
         family_link.parse(tokenizer, level)?;
@@ -93,6 +102,7 @@ impl FamilyLink {
             "birth" => Some(Pedigree::Birth),
             "foster" => Some(Pedigree::Foster),
             "sealing" => Some(Pedigree::Sealing),
+            "other" => Some(Pedigree::Other),
             _ => {
                 return Err(GedcomError::ParseError {
                     line,
@@ -165,16 +175,23 @@ impl Parser for FamilyLink {
             match tag {
                 "PEDI" => {
                     self.set_pedigree(tokenizer.take_line_value()?.as_str(), tokenizer.line)?;
+                    self.pedigree_phrase = take_phrase(tokenizer, level + 1, "Pedigree")?;
+                }
+                "STAT" => {
+                    self.set_child_linkage_status(
+                        tokenizer.take_line_value()?.as_str(),
+                        tokenizer.line,
+                    )?;
+                    self.status_phrase = take_phrase(tokenizer, level + 1, "Status")?;
+                }
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
+                "ADOP" => {
+                    self.set_adopted_by_which_parent(
+                        tokenizer.take_line_value()?.as_str(),
+                        tokenizer.line,
+                    )?;
+                    self.adopted_by_phrase = take_phrase(tokenizer, level + 1, "Adoption")?;
                 }
-                "STAT" => self.set_child_linkage_status(
-                    tokenizer.take_line_value()?.as_str(),
-                    tokenizer.line,
-                )?,
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
-                "ADOP" => self.set_adopted_by_which_parent(
-                    tokenizer.take_line_value()?.as_str(),
-                    tokenizer.line,
-                )?,
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -190,3 +207,24 @@ impl Parser for FamilyLink {
         Ok(())
     }
 }
+
+/// Reads the `PHRASE` under a value at `level`, naming the value `what` in
+/// errors.
+fn take_phrase(
+    tokenizer: &mut Tokenizer,
+    level: u8,
+    what: &str,
+) -> Result<Option<String>, GedcomError> {
+    let mut phrase = None;
+    parse_subset(tokenizer, level, |tag, tokenizer| {
+        if tag != "PHRASE" {
+            return Err(GedcomError::ParseError {
+                line: tokenizer.line,
+                message: format!("Unhandled {what} Tag: {tag}"),
+            });
+        }
+        phrase = Some(tokenizer.take_line_value()?);
+        Ok(())
+    })?;
+    Ok(phrase)
+}
@@ -1,10 +1,10 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// `AdoptedByWhichParent` is a code which shows which parent in the associated family record
 /// adopted this person. See GEDCOM 5.5 spec, page 42.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AdoptedByWhichParent {
     /// The `HUSBAND` in the associated family adopted this person.
     Husband,
@@ -14,6 +14,18 @@ pub enum AdoptedByWhichParent {
     Both,
 }
 
+impl AdoptedByWhichParent {
+    /// Gets the code GEDCOM uses for the parent: `HUSB`, `WIFE` or `BOTH`.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdoptedByWhichParent::Husband => "HUSB",
+            AdoptedByWhichParent::Wife => "WIFE",
+            AdoptedByWhichParent::Both => "BOTH",
+        }
+    }
+}
+
 impl std::fmt::Display for AdoptedByWhichParent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -1,10 +1,10 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Pedigree is a code used to indicate the child to family relationship for pedigree navigation
 /// purposes. See GEDCOM 5.5 spec, page 57.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Pedigree {
     /// Adopted indicates adoptive parents.
     Adopted,
@@ -14,6 +14,9 @@ pub enum Pedigree {
     Foster,
     /// Sealing indicates child was sealed to parents other than birth parents.
     Sealing,
+    /// Other indicates a relationship the codes above do not cover, described by a phrase
+    /// (GEDCOM 7.0).
+    Other,
 }
 
 impl std::fmt::Display for Pedigree {
@@ -1,10 +1,12 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use std::sync::Arc;
+
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{custom::UserDefinedTag, note::Note, source::citation::Citation},
+    types::{custom::UserDefinedTag, note::NoteRef, source::citation::Citation},
     GedcomError,
 };
 
@@ -14,9 +16,9 @@ use crate::{
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#enumset-NAME-TYPE>
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NameType {
-    /// Name given at or near birth (AKA, birth name, maiden name)
+    /// Name given at or near birth
     Birth,
     /// Name used in immigration records
     Immigrant,
@@ -39,7 +41,8 @@ impl NameType {
     #[must_use]
     pub fn parse(value: &str) -> Self {
         match value.to_uppercase().as_str() {
-            "BIRTH" | "AKA" | "ALSO KNOWN AS" => NameType::Aka,
+            "BIRTH" => NameType::Birth,
+            "AKA" | "ALSO KNOWN AS" => NameType::Aka,
             "IMMIGRANT" => NameType::Immigrant,
             "PROFESSIONAL" => NameType::Professional,
             "RELIGIOUS" => NameType::Religious,
@@ -71,14 +74,119 @@ impl std::fmt::Display for NameType {
     }
 }
 
-/// A phonetic or romanized variation of a name.
+/// The pieces of a personal name, from its `NAME` payload and name piece
+/// substructures. Returned by [`Name::parse_components`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NameComponents {
+    /// Name prefix, such as "Dr." (tag: NPFX).
+    pub prefix: Option<String>,
+    /// Given names.
+    pub given: Option<String>,
+    /// Surname prefix, such as "van" (tag: SPFX).
+    pub surname_prefix: Option<String>,
+    /// Surname.
+    pub surname: Option<String>,
+    /// Name suffix, such as "Jr." (tag: NSFX).
+    pub suffix: Option<String>,
+}
+
+impl NameComponents {
+    /// Returns the surname with its prefix, as in "van Doe".
+    fn full_surname(&self) -> Option<String> {
+        join([self.surname_prefix.as_deref(), self.surname.as_deref()])
+    }
+
+    /// Renders the name in `style`; see [`NameStyle`].
+    #[must_use]
+    pub fn formatted(&self, style: NameStyle) -> String {
+        let prefix = self.prefix.as_deref();
+        let given = self.given.as_deref();
+        let suffix = self.suffix.as_deref();
+        let surname = self.full_surname();
+        match style {
+            NameStyle::GivenFirst => join([prefix, given, surname.as_deref(), suffix]),
+            NameStyle::SurnameFirst => match (surname, join([prefix, given, suffix])) {
+                (Some(surname), Some(rest)) => Some(format!("{surname}, {rest}")),
+                (surname, rest) => surname.or(rest),
+            },
+            NameStyle::Gedcom => join([
+                prefix,
+                given,
+                surname.map(|surname| format!("/{surname}/")).as_deref(),
+                suffix,
+            ]),
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// Joins the pieces that are present with spaces.
+fn join<const N: usize>(pieces: [Option<&str>; N]) -> Option<String> {
+    let joined = pieces.into_iter().flatten().collect::<Vec<_>>().join(" ");
+    (!joined.is_empty()).then_some(joined)
+}
+
+/// How [`Name::formatted`] orders the pieces of a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NameStyle {
+    /// As spoken: "Dr. John van Doe Jr.".
+    GivenFirst,
+    /// As sorted in an index: "van Doe, Dr. John Jr.".
+    SurnameFirst,
+    /// As a GEDCOM `NAME` payload, with the surname in slashes:
+    /// "Dr. John /van Doe/ Jr.".
+    Gedcom,
+}
+
+/// Splits a `NAME` payload into the text before, inside and after the
+/// surname slashes.
+///
+/// Everything from the first slash to the last one is the surname, so the
+/// double surnames some files write as `/Garcia/ /Lopez/` stay together; a
+/// lone slash runs to the end. Without text before the slashes, the text
+/// after them is taken as the given names of a surname-first name.
+fn split_payload(value: &str) -> NameComponents {
+    let piece = |text: &str| {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!text.is_empty()).then_some(text)
+    };
+    let Some(start) = value.find('/') else {
+        return NameComponents {
+            given: piece(value),
+            ..NameComponents::default()
+        };
+    };
+    let end = value.rfind('/').filter(|&end| end > start);
+    let (inside, after) = match end {
+        Some(end) => (&value[start + 1..end], &value[end + 1..]),
+        None => (&value[start + 1..], ""),
+    };
+    let surname = piece(&inside.replace('/', " "));
+    match piece(&value[..start]) {
+        Some(given) => NameComponents {
+            given: Some(given),
+            surname,
+            suffix: piece(after),
+            ..NameComponents::default()
+        },
+        None => NameComponents {
+            given: piece(after),
+            surname,
+            ..NameComponents::default()
+        },
+    }
+}
+
+/// A phonetic, romanized or translated variation of a name.
 ///
 /// Used to provide alternative representations of names
 /// for internationalization purposes.
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#PERSONAL_NAME_PIECES>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NameVariation {
     /// The full name variation value.
     pub value: String,
@@ -95,6 +203,10 @@ pub struct NameVariation {
     /// - `wadegiles` - Wade-Giles romanization
     pub variation_type: Option<String>,
 
+    /// The language of a translation (tag: LANG, GEDCOM 7.0), as a BCP 47
+    /// tag.
+    pub language: Option<String>,
+
     /// Given name in this variation.
     pub given: Option<String>,
 
@@ -145,6 +257,7 @@ impl Parser for NameVariation {
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "TYPE" => self.variation_type = Some(tokenizer.take_line_value()?),
+                "LANG" => self.language = Some(tokenizer.take_line_value()?),
                 "GIVN" => self.given = Some(tokenizer.take_line_value()?),
                 "SURN" => self.surname = Some(tokenizer.take_line_value()?),
                 "NPFX" => self.prefix = Some(tokenizer.take_line_value()?),
@@ -177,7 +290,7 @@ impl Parser for NameVariation {
 /// permitted for the payload to contain information not present in any name piece substructure.
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#PERSONAL_NAME_STRUCTURE>.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Name {
     /// The full name value with surname in slashes (e.g., "John /Doe/").
     pub value: Option<String>,
@@ -186,7 +299,7 @@ pub struct Name {
     pub given: Option<String>,
 
     /// Surname (family name) (tag: SURN).
-    pub surname: Option<String>,
+    pub surname: Option<Arc<str>>,
 
     /// Name prefix (e.g., "Dr.", "Sir") (tag: NPFX).
     pub prefix: Option<String>,
@@ -194,8 +307,8 @@ pub struct Name {
     /// Surname prefix (e.g., "de", "van", "von") (tag: SPFX).
     pub surname_prefix: Option<String>,
 
-    /// Note about the name.
-    pub note: Option<Note>,
+    /// Notes about the name.
+    pub notes: Vec<NoteRef>,
 
     /// Name suffix (e.g., "Jr.", "III") (tag: NSFX).
     pub suffix: Option<String>,
@@ -213,6 +326,10 @@ pub struct Name {
     /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#NAME-TYPE>
     pub name_type: Option<NameType>,
 
+    /// Free-text description of the name type (tag: PHRASE under TYPE,
+    /// GEDCOM 7.0), as in `TYPE OTHER` with `PHRASE Stage name`.
+    pub type_phrase: Option<String>,
+
     /// Phonetic variations of the name (tag: FONE).
     ///
     /// Used to provide phonetic representations of names
@@ -225,6 +342,10 @@ pub struct Name {
     /// of names originally in non-Latin scripts.
     pub romanized: Vec<NameVariation>,
 
+    /// Translations of the name into other languages or scripts (tag: TRAN,
+    /// GEDCOM 7.0), each with its [`language`](NameVariation::language).
+    pub translations: Vec<NameVariation>,
+
     /// Custom data (extension tags).
     pub custom_data: Vec<Box<UserDefinedTag>>,
 }
@@ -255,6 +376,21 @@ impl Name {
         self.romanized.push(variation);
     }
 
+    /// Adds a translation of the name.
+    pub fn add_translation(&mut self, variation: NameVariation) {
+        self.translations.push(variation);
+    }
+
+    /// Returns the translation of the name into `language`, if there is one.
+    #[must_use]
+    pub fn translation(&self, language: &str) -> Option<&NameVariation> {
+        self.translations.iter().find(|t| {
+            t.language
+                .as_deref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(language))
+        })
+    }
+
     /// Returns the full name with slashes removed.
     ///
     /// This extracts the clean name from the GEDCOM format
@@ -266,6 +402,72 @@ impl Name {
             .map(|v| v.replace('/', "").trim().to_string())
     }
 
+    /// Returns the pieces of the name.
+    ///
+    /// The `GIVN`, `SURN`, `NPFX`, `SPFX` and `NSFX` substructures are used
+    /// where present. The given names, surname and suffix they leave out are
+    /// taken from the payload: the surname is between the slashes, the given
+    /// names before them and the suffix after them. A name prefix or suffix
+    /// recorded in a substructure is not repeated in the pieces taken from
+    /// the payload.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::types::individual::name::{Name, NameStyle};
+    ///
+    /// let name = Name {
+    ///     value: Some("Dr. John Henry /Doe/ Jr.".to_string()),
+    ///     prefix: Some("Dr.".to_string()),
+    ///     ..Name::default()
+    /// };
+    /// let components = name.parse_components();
+    /// assert_eq!(components.given.as_deref(), Some("John Henry"));
+    /// assert_eq!(components.surname.as_deref(), Some("Doe"));
+    /// assert_eq!(components.suffix.as_deref(), Some("Jr."));
+    /// assert_eq!(name.formatted(NameStyle::SurnameFirst), "Doe, Dr. John Henry Jr.");
+    /// ```
+    #[must_use]
+    pub fn parse_components(&self) -> NameComponents {
+        let mut parsed = self.value.as_deref().map(split_payload).unwrap_or_default();
+        // Leave out the prefix and suffix the payload repeats
+        if let (Some(prefix), Some(given)) = (&self.prefix, &parsed.given) {
+            if let Some(rest) = given.strip_prefix(prefix.as_str()) {
+                if rest.is_empty() || rest.starts_with(' ') {
+                    parsed.given = Some(rest.trim_start().to_string()).filter(|g| !g.is_empty());
+                }
+            }
+        }
+        if self.suffix.is_some() {
+            parsed.suffix = None;
+        }
+        if let (Some(surname_prefix), Some(surname)) = (&self.surname_prefix, &parsed.surname) {
+            if let Some(rest) = surname.strip_prefix(surname_prefix.as_str()) {
+                if rest.starts_with(' ') {
+                    parsed.surname = Some(rest.trim_start().to_string());
+                }
+            }
+        }
+
+        NameComponents {
+            prefix: self.prefix.clone(),
+            given: self.given.clone().or(parsed.given),
+            surname_prefix: self.surname_prefix.clone(),
+            surname: self.surname.as_deref().map(String::from).or(parsed.surname),
+            suffix: self.suffix.clone().or(parsed.suffix),
+        }
+    }
+
+    /// Renders the name in `style` from its [`components`](Self::parse_components).
+    ///
+    /// [`NameStyle::Gedcom`] gives back a payload that
+    /// [`parse_components`](Self::parse_components) splits into the same
+    /// pieces.
+    #[must_use]
+    pub fn formatted(&self, style: NameStyle) -> String {
+        self.parse_components().formatted(style)
+    }
+
     /// Returns true if this name has any phonetic variations.
     #[must_use]
     pub fn has_phonetic(&self) -> bool {
@@ -289,13 +491,23 @@ impl Parser for Name {
                 "NPFX" => self.prefix = Some(tokenizer.take_line_value()?),
                 "NSFX" => self.suffix = Some(tokenizer.take_line_value()?),
                 "SPFX" => self.surname_prefix = Some(tokenizer.take_line_value()?),
-                "SURN" => self.surname = Some(tokenizer.take_line_value()?),
+                "SURN" => self.surname = Some(tokenizer.take_shared_value()?),
                 "NICK" => self.nickname = Some(tokenizer.take_line_value()?),
                 "SOUR" => self.add_source_citation(Citation::new(tokenizer, level + 1)?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "TYPE" => {
                     let type_value = tokenizer.take_line_value()?;
                     self.name_type = Some(NameType::parse(&type_value));
+                    let mut phrase = None;
+                    parse_subset(tokenizer, level + 1, |tag, tokenizer| {
+                        if tag == "PHRASE" {
+                            phrase = Some(tokenizer.take_line_value()?);
+                        } else {
+                            tokenizer.take_line_value()?;
+                        }
+                        Ok(())
+                    })?;
+                    self.type_phrase = phrase;
                 }
                 "FONE" => self
                     .phonetic
@@ -303,6 +515,9 @@ impl Parser for Name {
                 "ROMN" => self
                     .romanized
                     .push(NameVariation::new(tokenizer, level + 1)?),
+                "TRAN" => self
+                    .translations
+                    .push(NameVariation::new(tokenizer, level + 1)?),
                 _ => {
                     // Gracefully skip unknown tags instead of failing
                     tokenizer.take_line_value()?;
@@ -323,7 +538,8 @@ mod tests {
 
     #[test]
     fn test_name_type_parse() {
-        assert_eq!(NameType::parse("BIRTH"), NameType::Aka);
+        assert_eq!(NameType::parse("BIRTH"), NameType::Birth);
+        assert_eq!(NameType::parse("aka"), NameType::Aka);
         assert_eq!(NameType::parse("MARRIED"), NameType::Married);
         assert_eq!(NameType::parse("MAIDEN"), NameType::Maiden);
         assert_eq!(
@@ -356,10 +572,10 @@ mod tests {
         let data = doc.parse_data().unwrap();
 
         let indi = &data.individuals[0];
-        let name = indi.name.as_ref().unwrap();
+        let name = &indi.names[0];
         assert_eq!(name.name_type, Some(NameType::Maiden));
         assert_eq!(name.given.as_ref().unwrap(), "Mary");
-        assert_eq!(name.surname.as_ref().unwrap(), "Smith");
+        assert_eq!(name.surname.as_deref().unwrap(), "Smith");
     }
 
     #[test]
@@ -380,7 +596,7 @@ mod tests {
         let data = doc.parse_data().unwrap();
 
         let indi = &data.individuals[0];
-        let name = indi.name.as_ref().unwrap();
+        let name = &indi.names[0];
         assert!(name.has_phonetic());
         assert_eq!(name.phonetic.len(), 1);
         assert_eq!(name.phonetic[0].value, "Yamada /Taro/");
@@ -405,13 +621,50 @@ mod tests {
         let data = doc.parse_data().unwrap();
 
         let indi = &data.individuals[0];
-        let name = indi.name.as_ref().unwrap();
+        let name = &indi.names[0];
         assert!(name.has_romanized());
         assert_eq!(name.romanized.len(), 1);
         assert_eq!(name.romanized[0].value, "Wang /Xiaoming/");
         assert_eq!(name.romanized[0].variation_type, Some("pinyin".to_string()));
     }
 
+    #[test]
+    fn test_parse_several_names_with_translation() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 NAME Мария /Иванова/\n\
+            2 TYPE BIRTH\n\
+            2 TRAN Maria /Ivanova/\n\
+            3 LANG en-Latn\n\
+            3 GIVN Maria\n\
+            1 NAME Mary /Smith/\n\
+            2 TYPE MARRIED\n\
+            1 NAME Queen of Spades\n\
+            2 TYPE OTHER\n\
+            3 PHRASE Stage name\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let names = &data.individuals[0].names;
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[0].name_type, Some(NameType::Birth));
+        let translation = names[0].translation("EN-latn").unwrap();
+        assert_eq!(translation.value, "Maria /Ivanova/");
+        assert_eq!(translation.given.as_deref(), Some("Maria"));
+        assert!(names[0].translation("fr").is_none());
+        assert_eq!(names[1].name_type, Some(NameType::Married));
+        assert_eq!(
+            names[2].name_type,
+            Some(NameType::Other("OTHER".to_string()))
+        );
+        assert_eq!(names[2].type_phrase.as_deref(), Some("Stage name"));
+    }
+
     #[test]
     fn test_name_full_name() {
         let name = Name {
@@ -421,6 +674,102 @@ mod tests {
         assert_eq!(name.full_name(), Some("John Doe".to_string()));
     }
 
+    #[test]
+    fn test_parse_components() {
+        let payload = |value: &str| {
+            Name {
+                value: Some(value.to_string()),
+                ..Default::default()
+            }
+            .parse_components()
+        };
+        let components =
+            |given: Option<&str>, surname: Option<&str>, suffix: Option<&str>| NameComponents {
+                given: given.map(str::to_string),
+                surname: surname.map(str::to_string),
+                suffix: suffix.map(str::to_string),
+                ..Default::default()
+            };
+
+        assert_eq!(
+            payload("John  Henry /Doe/ Jr."),
+            components(Some("John Henry"), Some("Doe"), Some("Jr."))
+        );
+        assert_eq!(
+            payload("Maria /Garcia/ y /Lopez/"),
+            components(Some("Maria"), Some("Garcia y Lopez"), None)
+        );
+        assert_eq!(
+            payload("John /Doe"),
+            components(Some("John"), Some("Doe"), None)
+        );
+        assert_eq!(payload("John"), components(Some("John"), None, None));
+        assert_eq!(payload("John //"), components(Some("John"), None, None));
+        assert_eq!(
+            payload("/Nagy/ J\u{e1}nos"),
+            components(Some("J\u{e1}nos"), Some("Nagy"), None)
+        );
+        assert_eq!(payload(""), NameComponents::default());
+
+        // The substructures win over the payload
+        let name = Name {
+            value: Some("Dr. Jack /van Doe/ III".to_string()),
+            given: Some("John".to_string()),
+            prefix: Some("Dr.".to_string()),
+            surname_prefix: Some("van".to_string()),
+            suffix: Some("Jr.".to_string()),
+            ..Default::default()
+        };
+        let parsed = name.parse_components();
+        assert_eq!(parsed.given.as_deref(), Some("John"));
+        assert_eq!(parsed.surname.as_deref(), Some("Doe"));
+        assert_eq!(parsed.suffix.as_deref(), Some("Jr."));
+    }
+
+    #[test]
+    fn test_name_formatted() {
+        let name = Name {
+            value: Some("Dr. John /van Doe/ Jr.".to_string()),
+            prefix: Some("Dr.".to_string()),
+            surname_prefix: Some("van".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            name.formatted(NameStyle::GivenFirst),
+            "Dr. John van Doe Jr."
+        );
+        assert_eq!(
+            name.formatted(NameStyle::SurnameFirst),
+            "van Doe, Dr. John Jr."
+        );
+        assert_eq!(name.formatted(NameStyle::Gedcom), "Dr. John /van Doe/ Jr.");
+
+        let only_given = Name {
+            value: Some("John".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(only_given.formatted(NameStyle::SurnameFirst), "John");
+        assert_eq!(Name::default().formatted(NameStyle::Gedcom), "");
+
+        // The GEDCOM style round-trips
+        for value in [
+            "John /Doe/ Jr.",
+            "Maria /Garcia Lopez/",
+            "John",
+            "/Nagy/ J\u{e1}nos",
+        ] {
+            let name = Name {
+                value: Some(value.to_string()),
+                ..Default::default()
+            };
+            let reparsed = Name {
+                value: Some(name.formatted(NameStyle::Gedcom)),
+                ..Default::default()
+            };
+            assert_eq!(reparsed.parse_components(), name.parse_components());
+        }
+    }
+
     #[test]
     fn test_name_variation_with_type() {
         let variation = NameVariation::with_type("Tanaka /Hanako/", "romaji");
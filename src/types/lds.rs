@@ -26,16 +26,16 @@
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{date::Date, note::Note, source::citation::Citation},
+    types::{date::Date, note::NoteRef, source::citation::Citation},
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The type of LDS ordinance.
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LdsOrdinanceType {
     /// Baptism (LDS) - Tag: `BAPL`
     Baptism,
@@ -115,7 +115,7 @@ impl std::fmt::Display for LdsOrdinanceType {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#enumset-ord-STAT>
 #[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LdsOrdinanceStatus {
     /// The ordinance was completed but the date is not known.
     BicCompleted,
@@ -208,7 +208,7 @@ impl std::fmt::Display for LdsOrdinanceStatus {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#LDS_INDIVIDUAL_ORDINANCE>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LdsOrdinance {
     /// The type of ordinance.
     pub ordinance_type: Option<LdsOrdinanceType>,
@@ -236,7 +236,7 @@ pub struct LdsOrdinance {
     pub family_xref: Option<String>,
 
     /// Notes about this ordinance.
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
 
     /// Source citations for this ordinance.
     pub source_citations: Vec<Citation>,
@@ -330,7 +330,7 @@ impl Parser for LdsOrdinance {
                     self.status = LdsOrdinanceStatus::parse(&status_str);
                 }
                 "FAMC" => self.family_xref = Some(tokenizer.take_line_value()?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "SOUR" => {
                     self.source_citations
                         .push(Citation::new(tokenizer, level + 1)?);
@@ -5,18 +5,22 @@ pub mod user;
 
 use crate::{
     parser::{parse_subset, Parser},
-    tokenizer::Tokenizer,
+    tokenizer::{Token, Tokenizer},
     types::{
+        custom::take_vendor_uids,
         date::change_date::ChangeDate,
+        gedcom7::Crop,
         multimedia::{file::Reference, format::Format, user::UserReferenceNumber},
-        note::Note,
+        note::NoteRef,
+        shared_note::ExternalId,
         source::citation::Citation,
-        Xref,
+        GedcomData, Xref,
     },
+    util::is_pointer,
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// `MultimediaRecord` refers to 1 or more external digital files, and may provide some
@@ -30,11 +34,13 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#MULTIMEDIA_RECORD>.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Multimedia {
     /// Optional reference to link to this submitter
     pub xref: Option<Xref>,
-    pub file: Option<Reference>,
+    /// The files of the record (tag: FILE), or of an embedded link, in file
+    /// order. Files grouped in one record pertain to the same context.
+    pub files: Vec<Reference>,
     /// The 5.5 spec, page 26, shows FORM as a sub-structure of FILE, but the struct appears as a
     /// sibling in an Ancestry.com export.
     pub form: Option<Format>,
@@ -45,7 +51,28 @@ pub struct Multimedia {
     pub automated_record_id: Option<String>,
     pub source_citation: Option<Citation>,
     pub change_date: Option<ChangeDate>,
-    pub note_structure: Option<Note>,
+    /// The date this record was created (tag: CREA, GEDCOM 7.0).
+    pub creation_date: Option<ChangeDate>,
+    pub notes: Vec<NoteRef>,
+    /// The region of the image to show, on a link to a multimedia record (GEDCOM 7.0).
+    pub crop: Option<Crop>,
+    /// Whether the link points to the preferred picture of the record it is
+    /// in (tag: _PRIM, an extension written by several desktop applications).
+    pub primary: Option<bool>,
+    /// Unique identifier (tag: UID, GEDCOM 7.0).
+    ///
+    /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#UID>
+    pub uid: Option<String>,
+    /// Vendor unique identifiers (tag: _UID).
+    ///
+    /// GUIDs recorded by desktop applications under the `_UID` extension,
+    /// common in GEDCOM 5.5.1 files.
+    pub vendor_uids: Vec<String>,
+    /// External identifiers (tag: EXID, GEDCOM 7.0).
+    ///
+    /// Identifiers maintained by external authorities that apply to this
+    /// multimedia record.
+    pub external_ids: Vec<ExternalId>,
 }
 
 impl Multimedia {
@@ -71,6 +98,59 @@ impl Multimedia {
         obje.parse(tokenizer, level)?;
         Ok(obje)
     }
+
+    /// Gets the first file of the record.
+    #[must_use]
+    pub fn file(&self) -> Option<&Reference> {
+        self.files.first()
+    }
+
+    /// Returns `true` if the record has files and all of them are remote
+    /// resources, named by `http://` or `https://` URLs.
+    ///
+    /// See [`Reference::is_remote`] for a single file.
+    #[must_use]
+    pub fn is_remote(&self) -> bool {
+        !self.files.is_empty() && self.files.iter().all(Reference::is_remote)
+    }
+
+    /// Gets the unique identifiers of the multimedia record: the `UID`, then
+    /// the `_UID` values.
+    #[must_use]
+    pub fn uids(&self) -> Vec<&str> {
+        self.uid
+            .iter()
+            .chain(&self.vendor_uids)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl GedcomData {
+    /// Rewrites the legacy `FORM` values of the multimedia records and
+    /// embedded multimedia links, such as `jpg` or `bmp`, to the media
+    /// types GEDCOM 7.0 uses, and returns how many changed.
+    ///
+    /// See [`Format::normalize`] for the values that are rewritten.
+    pub fn normalize_media_formats(&mut self) -> usize {
+        let mut changed = 0;
+        for_each_multimedia(self, &mut |media| {
+            let forms = media.form.iter_mut();
+            let file_forms = media.files.iter_mut().filter_map(|file| file.form.as_mut());
+            for form in forms.chain(file_forms) {
+                changed += usize::from(form.normalize());
+            }
+        });
+        changed
+    }
+}
+
+/// Calls `f` on every multimedia record of `data`, then on every multimedia
+/// link embedded in its records, wherever the link walk of
+/// [`visit_all_media`](GedcomData::visit_all_media) finds one.
+pub(crate) fn for_each_multimedia(data: &mut GedcomData, f: &mut dyn FnMut(&mut Multimedia)) {
+    data.multimedia.iter_mut().for_each(&mut *f);
+    data.visit_all_media(f);
 }
 
 impl Parser for Multimedia {
@@ -78,9 +158,16 @@ impl Parser for Multimedia {
         // skip current line
         tokenizer.next_token()?;
 
+        // A link to a multimedia record carries the pointer as its line value
+        if let Token::LineValue(value) = &tokenizer.current_token {
+            if self.xref.is_none() && is_pointer(value) {
+                self.xref = Some(value.to_string());
+            }
+        }
+
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
-                "FILE" => self.file = Some(Reference::new(tokenizer, level + 1)?),
+                "FILE" => self.files.push(Reference::new(tokenizer, level + 1)?),
                 "FORM" => self.form = Some(Format::new(tokenizer, level + 1)?),
                 "TITL" => self.title = Some(tokenizer.take_line_value()?),
                 "REFN" => {
@@ -88,9 +175,16 @@ impl Parser for Multimedia {
                         Some(UserReferenceNumber::new(tokenizer, level + 1)?);
                 }
                 "RIN" => self.automated_record_id = Some(tokenizer.take_line_value()?),
-                "NOTE" => self.note_structure = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "SOUR" => self.source_citation = Some(Citation::new(tokenizer, level + 1)?),
                 "CHAN" => self.change_date = Some(ChangeDate::new(tokenizer, level + 1)?),
+                "CREA" => self.creation_date = Some(ChangeDate::new(tokenizer, level + 1)?),
+                "CROP" => self.crop = Some(Crop::new(tokenizer, level + 1)?),
+                "UID" => self.uid = Some(tokenizer.take_line_value()?),
+                "EXID" => {
+                    self.external_ids
+                        .push(ExternalId::from_tokenizer(tokenizer, level + 1)?);
+                }
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -101,7 +195,14 @@ impl Parser for Multimedia {
 
             Ok(())
         };
-        parse_subset(tokenizer, level, handle_subset)?;
+        let mut custom_data = parse_subset(tokenizer, level, handle_subset)?;
+        take_vendor_uids(&mut custom_data, &mut self.vendor_uids);
+        if let Some(primary) = custom_data.iter().find(|tag| &*tag.tag == "_PRIM") {
+            self.primary = primary
+                .value
+                .as_deref()
+                .map(|v| v.eq_ignore_ascii_case("Y"));
+        }
 
         Ok(())
     }
@@ -168,7 +269,7 @@ mod tests {
         let form = obje.form.as_ref().unwrap();
         assert_eq!(form.value.as_ref().unwrap(), "jpg");
 
-        let file = obje.file.as_ref().unwrap();
+        let file = obje.file().unwrap();
         assert_eq!(file.value.as_ref().unwrap(), "http://trees.ancestry.com/rd?f=image&guid=Xxxxxxxx-Xxxx-Xxxx-Xxxx-Xxxxxxxxxxxx&tid=Xxxxxxxx&pid=1");
     }
 
@@ -189,7 +290,7 @@ mod tests {
         let data = doc.parse_data().unwrap();
         assert_eq!(data.multimedia.len(), 1);
 
-        let file = data.multimedia[0].file.as_ref().unwrap();
+        let file = data.multimedia[0].file().unwrap();
         assert_eq!(
             file.value.as_ref().unwrap(),
             "/home/user/media/file_name.bmp"
@@ -218,7 +319,7 @@ mod tests {
         let data = doc.parse_data().unwrap();
         assert_eq!(data.multimedia.len(), 1);
 
-        let file = data.multimedia[0].file.as_ref().unwrap();
+        let file = data.multimedia[0].file().unwrap();
 
         let form = file.form.as_ref().unwrap();
         assert_eq!(form.value.as_ref().unwrap(), "bmp");
@@ -249,4 +350,212 @@ mod tests {
             "User Reference Type"
         );
     }
+
+    #[test]
+    fn test_parse_multimedia_link_pointer() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5\n\
+            0 @I1@ INDI\n\
+            1 OBJE @MEDIA1@\n\
+            1 OBJE\n\
+            2 FILE photo.jpg\n\
+            0 @MEDIA1@ OBJE\n\
+            1 FILE portrait.jpg\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let links = &data.individuals[0].multimedia;
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].xref.as_deref(), Some("@MEDIA1@"));
+        assert!(links[1].xref.is_none());
+        assert_eq!(data.multimedia[0].xref.as_deref(), Some("@MEDIA1@"));
+    }
+
+    #[test]
+    fn test_parse_multimedia_files_and_links() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 OBJE @M1@\n\
+            2 _PRIM Y\n\
+            1 OBJE\n\
+            2 FILE scan.png\n\
+            3 FORM png\n\
+            4 MEDI document\n\
+            2 TITL Scan\n\
+            2 _PRIM N\n\
+            0 @M1@ OBJE\n\
+            1 FILE front.jpg\n\
+            2 FORM jpg\n\
+            3 TYPE photo\n\
+            2 TITL Front\n\
+            1 FILE back.jpg\n\
+            2 FORM jpg\n\
+            2 TITL Back\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        let files = &data.multimedia[0].files;
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].value.as_deref(), Some("front.jpg"));
+        assert_eq!(files[0].title.as_deref(), Some("Front"));
+        assert_eq!(files[1].value.as_deref(), Some("back.jpg"));
+        assert_eq!(files[1].title.as_deref(), Some("Back"));
+
+        let links = &data.individuals[0].multimedia;
+        assert_eq!(links[0].primary, Some(true));
+        assert_eq!(links[1].primary, Some(false));
+        assert_eq!(links[1].title.as_deref(), Some("Scan"));
+        let form = links[1].files[0].form.as_ref().unwrap();
+        assert_eq!(form.source_media_type.as_deref(), Some("document"));
+    }
+
+    #[test]
+    fn test_is_remote() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @M1@ OBJE\n\
+            1 FILE https://example.com/john.jpg\n\
+            1 FILE HTTP://example.com/back.jpg\n\
+            0 @M2@ OBJE\n\
+            1 FILE https://example.com/jane.jpg\n\
+            1 FILE photos/jane.jpg\n\
+            0 @M3@ OBJE\n\
+            1 FILE ftp://example.com/scan.tif\n\
+            0 @M4@ OBJE\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+
+        assert!(data.multimedia[0].is_remote());
+        assert!(data.multimedia[1].files[0].is_remote());
+        assert!(!data.multimedia[1].files[1].is_remote());
+        assert!(!data.multimedia[1].is_remote());
+        assert!(!data.multimedia[2].is_remote());
+        assert!(!data.multimedia[3].is_remote());
+    }
+
+    #[test]
+    fn test_sniff_media_type() {
+        use super::format::{media_type, sniff_media_type};
+
+        assert_eq!(
+            sniff_media_type(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some("image/jpeg")
+        );
+        assert_eq!(sniff_media_type(b"\x89PNG\r\n\x1a\n"), Some("image/png"));
+        assert_eq!(sniff_media_type(b"GIF89a"), Some("image/gif"));
+        assert_eq!(sniff_media_type(b"II*\0"), Some("image/tiff"));
+        assert_eq!(sniff_media_type(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff_media_type(b"RIFF\0\0\0\0WAVEfmt "), Some("audio/wav"));
+        assert_eq!(sniff_media_type(b"\0\0\0\x18ftypmp42"), Some("video/mp4"));
+        let mut bmp = b"BM\x46\0\0\0\0\0\0\0\x36\0\0\0\x28\0\0\0".to_vec();
+        assert_eq!(sniff_media_type(&bmp), Some("image/bmp"));
+        bmp[14] = 0x29;
+        assert_eq!(sniff_media_type(&bmp), None);
+        assert_eq!(sniff_media_type(b"BMX report, page 1"), None);
+        assert_eq!(sniff_media_type(b"BM"), None);
+        assert_eq!(sniff_media_type(b"plain text"), None);
+        assert_eq!(sniff_media_type(b""), None);
+
+        assert_eq!(media_type("JPEG"), Some("image/jpeg"));
+        assert_eq!(media_type(".tif"), Some("image/tiff"));
+        assert_eq!(media_type("Image/PNG"), Some("image/png"));
+        assert_eq!(media_type("ole"), None);
+    }
+
+    #[test]
+    fn test_check_and_fix_form() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @M1@ OBJE\n\
+            1 FILE photo.jpg\n\
+            2 FORM jpg\n\
+            3 TYPE photo\n\
+            1 FILE scan.gif\n\
+            2 FORM gif\n\
+            1 FILE notes.bin\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let mut data = doc.parse_data().unwrap();
+        let png = b"\x89PNG\r\n\x1a\n";
+        let files = &mut data.multimedia[0].files;
+
+        assert_eq!(files[0].check_form(&[0xFF, 0xD8, 0xFF, 0xE0]), None);
+        assert_eq!(files[0].check_form(b"unknown"), None);
+        let mismatch = files[1].check_form(png).unwrap();
+        assert_eq!(mismatch.declared.as_deref(), Some("gif"));
+        assert_eq!(mismatch.detected, "image/png");
+
+        assert!(files[1].fix_form(png));
+        assert!(!files[1].fix_form(png));
+        assert!(files[2].fix_form(png));
+        let form = files[2].form.as_ref().unwrap();
+        assert_eq!(form.value.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn test_normalize_media_formats() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 BIRT\n\
+            2 OBJE\n\
+            3 FILE birth.bmp\n\
+            4 FORM bmp\n\
+            1 NAME John /Doe/\n\
+            2 SOUR @S1@\n\
+            3 OBJE\n\
+            4 FILE name.gif\n\
+            5 FORM gif\n\
+            1 OCCU Weaver\n\
+            2 SOUR @S1@\n\
+            3 OBJE\n\
+            4 FILE occupation.tif\n\
+            5 FORM tif\n\
+            0 @M1@ OBJE\n\
+            1 FILE photo.jpeg\n\
+            2 FORM JPEG\n\
+            3 TYPE photo\n\
+            1 FILE scan.png\n\
+            2 FORM image/png\n\
+            1 FILE legacy.ole\n\
+            2 FORM ole\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let mut data = doc.parse_data().unwrap();
+        assert_eq!(data.normalize_media_formats(), 4);
+
+        let form = |file: &super::Reference| file.form.clone().unwrap();
+        let files = &data.multimedia[0].files;
+        assert_eq!(form(&files[0]).value.as_deref(), Some("image/jpeg"));
+        assert_eq!(form(&files[0]).source_media_type.as_deref(), Some("photo"));
+        assert_eq!(form(&files[1]).value.as_deref(), Some("image/png"));
+        assert_eq!(form(&files[2]).value.as_deref(), Some("ole"));
+        let birth = &data.individuals[0].events[0].multimedia[0].files[0];
+        assert_eq!(form(birth).value.as_deref(), Some("image/bmp"));
+        let individual = &data.individuals[0];
+        let name = &individual.names[0].source[0].multimedia[0].files[0];
+        assert_eq!(form(name).value.as_deref(), Some("image/gif"));
+        let occupation = &individual.attributes[0].sources[0].multimedia[0].files[0];
+        assert_eq!(form(occupation).value.as_deref(), Some("image/tiff"));
+        assert_eq!(data.normalize_media_formats(), 0);
+    }
 }
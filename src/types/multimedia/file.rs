@@ -1,10 +1,16 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{gedcom7::Crop, multimedia::Format},
+    types::{
+        gedcom7::Crop,
+        multimedia::{
+            format::{sniff_media_type, FormMismatch},
+            Format,
+        },
+    },
     GedcomError,
 };
 
@@ -19,7 +25,7 @@ use crate::{
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#MULTIMEDIA_LINK>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Reference {
     pub value: Option<String>,
     pub title: Option<String>,
@@ -42,6 +48,50 @@ impl Reference {
         file.parse(tokenizer, level)?;
         Ok(file)
     }
+
+    /// Returns `true` if the file is a remote resource, named by an
+    /// `http://` or `https://` URL, rather than a local path.
+    #[must_use]
+    pub fn is_remote(&self) -> bool {
+        self.value.as_deref().is_some_and(|value| {
+            let value = value.trim_start().as_bytes();
+            ["http://", "https://"].iter().any(|scheme| {
+                value.len() >= scheme.len()
+                    && value[..scheme.len()].eq_ignore_ascii_case(scheme.as_bytes())
+            })
+        })
+    }
+
+    /// Compares the format `bytes`, the content of the file, are in with the
+    /// one its `FORM` declares, and returns the mismatch if they differ.
+    ///
+    /// Legacy `FORM` values such as `jpg` match their media type. Returns
+    /// `None` if they agree or the format of `bytes` is not recognized.
+    #[must_use]
+    pub fn check_form(&self, bytes: &[u8]) -> Option<FormMismatch> {
+        let detected = sniff_media_type(bytes)?;
+        let declared = self.form.as_ref();
+        if declared.and_then(Format::media_type) == Some(detected) {
+            return None;
+        }
+        Some(FormMismatch {
+            declared: declared.and_then(|form| form.value.clone()),
+            detected: detected.to_string(),
+        })
+    }
+
+    /// Sets the `FORM` of the file to the media type detected from `bytes`,
+    /// the content of the file, if it differs from the declared one, and
+    /// returns whether it changed.
+    ///
+    /// The `MEDI` of the format is kept.
+    pub fn fix_form(&mut self, bytes: &[u8]) -> bool {
+        let Some(mismatch) = self.check_form(bytes) else {
+            return false;
+        };
+        self.form.get_or_insert_with(Format::default).value = Some(mismatch.detected);
+        true
+    }
 }
 
 impl Parser for Reference {
@@ -1,4 +1,4 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -15,7 +15,7 @@ use crate::{
 /// NOTE: The 5.5 spec lists the following seven formats [ bmp | gif | jpg | ole | pcx | tif | wav ].
 /// However, we're leaving this open for emerging formats, `Option<String>`.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Format {
     pub value: Option<String>,
     pub source_media_type: Option<String>,
@@ -32,6 +32,119 @@ impl Format {
         form.parse(tokenizer, level)?;
         Ok(form)
     }
+
+    /// Returns the media type the `FORM` value names, mapping the legacy
+    /// GEDCOM 5.5.1 values such as `jpg` to their media type.
+    #[must_use]
+    pub fn media_type(&self) -> Option<&'static str> {
+        media_type(self.value.as_deref()?)
+    }
+
+    /// Rewrites a legacy `FORM` value, such as `jpeg` or `bmp`, to the media
+    /// type GEDCOM 7.0 uses, and returns whether it changed.
+    ///
+    /// Values that are already media types, and those with no known media
+    /// type, such as `ole`, are left as they are.
+    pub fn normalize(&mut self) -> bool {
+        match (self.value.as_deref(), self.media_type()) {
+            (Some(value), Some(media_type)) if value != media_type => {
+                self.value = Some(media_type.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The `FORM` values of GEDCOM 5.5.1 files and the media types they stand
+/// for.
+const LEGACY_FORMATS: &[(&str, &str)] = &[
+    ("aif", "audio/aiff"),
+    ("avi", "video/x-msvideo"),
+    ("bmp", "image/bmp"),
+    ("gif", "image/gif"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("mov", "video/quicktime"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("mpg", "video/mpeg"),
+    ("pcx", "image/vnd.zbrush.pcx"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("webp", "image/webp"),
+];
+
+/// Returns the media type a `FORM` value names: the value itself, in lower
+/// case, if it is a known media type, or the media type of a legacy
+/// GEDCOM 5.5.1 value such as `jpg`.
+#[must_use]
+pub fn media_type(form: &str) -> Option<&'static str> {
+    let form = form.trim().trim_start_matches('.');
+    LEGACY_FORMATS
+        .iter()
+        .find(|(legacy, media_type)| {
+            form.eq_ignore_ascii_case(legacy) || form.eq_ignore_ascii_case(media_type)
+        })
+        .map(|(_, media_type)| *media_type)
+}
+
+/// Returns the media type of a file from the magic number its bytes start
+/// with, or `None` if the format is not recognized.
+///
+/// The formats of [`media_type`] that have a signature are recognized.
+#[must_use]
+pub fn sniff_media_type(bytes: &[u8]) -> Option<&'static str> {
+    let riff = |kind: &[u8]| bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(kind);
+    let media_type = match bytes {
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'B', b'M', ..] if is_bmp(bytes) => "image/bmp",
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => "image/tiff",
+        [0x0A, 0..=5, 0x01, ..] => "image/vnd.zbrush.pcx",
+        [b'%', b'P', b'D', b'F', ..] => "application/pdf",
+        [b'I', b'D', b'3', ..] | [0xFF, 0xFB | 0xF3 | 0xF2, ..] => "audio/mpeg",
+        [b'F', b'O', b'R', b'M', _, _, _, _, b'A', b'I', b'F', ..] => "audio/aiff",
+        _ if riff(b"WAVE") => "audio/wav",
+        _ if riff(b"WEBP") => "image/webp",
+        _ if riff(b"AVI ") => "video/x-msvideo",
+        _ if bytes.get(4..12) == Some(b"ftypqt  ") => "video/quicktime",
+        _ if bytes.get(4..8) == Some(b"ftyp") => "video/mp4",
+        _ => return None,
+    };
+    Some(media_type)
+}
+
+/// Returns whether `bytes` start with a BMP file header: `BM`, then sizes
+/// and offsets up to a DIB header of one of the known sizes. `BM` alone is
+/// too common a start for text to go by.
+fn is_bmp(bytes: &[u8]) -> bool {
+    let Some(&[a, b, c, d]) = bytes.get(14..18) else {
+        return false;
+    };
+    // The core, info, v2, v3, OS/2 2.x, v4 and v5 headers.
+    matches!(
+        u32::from_le_bytes([a, b, c, d]),
+        12 | 40 | 52 | 56 | 64 | 108 | 124
+    )
+}
+
+/// A file whose content does not match the format its `FORM` declares,
+/// found by [`Reference::check_form`](super::file::Reference::check_form).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FormMismatch {
+    /// The `FORM` value, or `None` if the file declares none.
+    pub declared: Option<String>,
+    /// The media type detected from the content.
+    pub detected: String,
 }
 
 impl Parser for Format {
@@ -40,7 +153,8 @@ impl Parser for Format {
 
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
-                "TYPE" => self.source_media_type = Some(tokenizer.take_line_value()?),
+                // GEDCOM 5.5.1 calls it TYPE, GEDCOM 7.0 MEDI.
+                "TYPE" | "MEDI" => self.source_media_type = Some(tokenizer.take_line_value()?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
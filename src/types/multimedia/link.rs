@@ -1,13 +1,14 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     parser::{parse_subset, Parser},
-    tokenizer::Tokenizer,
+    tokenizer::{Token, Tokenizer},
     types::{
         multimedia::{Format, Reference},
         Xref,
     },
+    util::is_pointer,
     GedcomError,
 };
 
@@ -17,7 +18,7 @@ use crate::{
 /// with genealogical records. This can include photographs, scanned documents, audio recordings,
 /// or any other digital content that supplements the genealogical data.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Link {
     /// Optional reference to link to this submitter
     pub xref: Option<Xref>,
@@ -57,6 +58,13 @@ impl Parser for Link {
         // skip current line
         tokenizer.next_token()?;
 
+        // A link to a multimedia record carries the pointer as its line value
+        if let Token::LineValue(value) = &tokenizer.current_token {
+            if self.xref.is_none() && is_pointer(value) {
+                self.xref = Some(value.to_string());
+            }
+        }
+
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "FILE" => self.file = Some(Reference::new(tokenizer, level + 1)?),
@@ -1,11 +1,12 @@
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{source::Source, translation::Translation},
+    types::{shared_note::SharedNote, source::Source, translation::Translation, GedcomData, Xref},
+    util::is_pointer,
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A `NOTE_STRUCTURE` containing additional information for understanding the enclosing data.
@@ -26,16 +27,15 @@ use serde::{Deserialize, Serialize};
 /// See: <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#NOTE>
 /// See: <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#NOTE_STRUCTURE>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Note {
     pub value: Option<String>,
     /// tag: MIME, indicates the media type of the payload of the superstructure, as defined by BCP
     /// 13. As of version 7.0, only 2 media types are supported by this structure: text/plain and
     /// text/html
     pub mime: Option<String>,
-    /// tag: TRAN, a type of TRAN for unstructured human-readable text, such as is found in NOTE
-    /// and SNOTE payloads.
-    pub translation: Option<Translation>,
+    /// tag: TRAN, translations of the note into other languages or media types, in file order.
+    pub translations: Vec<Translation>,
     /// tag: SOUR, a citation indicating that the pointed-to source record supports the claims made
     /// in the superstructure. See
     /// <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SOURCE_CITATION>.
@@ -57,17 +57,179 @@ impl Note {
         note.parse(tokenizer, level)?;
         Ok(note)
     }
+
+    /// Returns the note's text in a language, if the note is written in it
+    /// or has a translation into it.
+    ///
+    /// Languages are BCP 47 tags compared without regard to case, and a
+    /// general tag matches its regional variants: `de` finds a `de-AT`
+    /// translation. A translation without `LANG` is in the note's own
+    /// language.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::types::{note::Note, translation::Translation};
+    ///
+    /// let note = Note {
+    ///     value: Some("Born at sea".to_string()),
+    ///     language: Some("en".to_string()),
+    ///     translations: vec![Translation {
+    ///         value: Some("Auf See geboren".to_string()),
+    ///         language: Some("de-AT".to_string()),
+    ///         ..Translation::default()
+    ///     }],
+    ///     ..Note::default()
+    /// };
+    /// assert_eq!(note.in_language("de"), Some("Auf See geboren"));
+    /// assert_eq!(note.in_language("EN"), Some("Born at sea"));
+    /// assert_eq!(note.in_language("fr"), None);
+    /// ```
+    #[must_use]
+    pub fn in_language(&self, language: &str) -> Option<&str> {
+        if self
+            .language
+            .as_deref()
+            .is_some_and(|l| language_matches(l, language))
+        {
+            return self.value.as_deref();
+        }
+        self.translations
+            .iter()
+            .find(|t| {
+                t.language
+                    .as_deref()
+                    .or(self.language.as_deref())
+                    .is_some_and(|l| language_matches(l, language))
+            })
+            .and_then(|t| t.value.as_deref())
+    }
+
+    /// Returns the text in the first of `languages` the note is available
+    /// in, or the note's own text if none is.
+    #[must_use]
+    pub fn preferred_text(&self, languages: &[String]) -> Option<&str> {
+        languages
+            .iter()
+            .find_map(|l| self.in_language(l))
+            .or(self.value.as_deref())
+    }
+}
+
+/// A note in a `NOTE_STRUCTURE` position: either the text itself, or a
+/// pointer to a shared note record.
+///
+/// GEDCOM 5.5.1 points to a `NOTE` record with `NOTE @N1@`; GEDCOM 7.0 uses
+/// `SNOTE @N1@` and an `SNOTE` record. Both parse to [`NoteRef::Shared`].
+///
+/// # Example
+///
+/// ```rust
+/// use ged_io::GedcomBuilder;
+///
+/// let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+///     0 @I1@ INDI\n1 NOTE Born at sea\n1 SNOTE @N1@\n\
+///     0 @N1@ SNOTE The family emigrated in 1850.\n0 TRLR";
+/// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+///
+/// let notes = &data.individuals[0].notes;
+/// assert_eq!(notes[0].text(&data), Some("Born at sea"));
+/// assert_eq!(notes[1].shared_xref(), Some("@N1@"));
+/// assert_eq!(notes[1].text(&data), Some("The family emigrated in 1850."));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::large_enum_variant)] // Most notes are inline; boxing them would cost more.
+pub enum NoteRef {
+    /// A note written where it applies.
+    Inline(Note),
+    /// The xref of a shared note record.
+    Shared(Xref),
+}
+
+impl NoteRef {
+    /// Creates a new `NoteRef` from a `Tokenizer` positioned on a `NOTE` or
+    /// `SNOTE` tag.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if parsing fails, including when
+    /// a pointer has substructures, which a shared note cannot carry.
+    pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<NoteRef, GedcomError> {
+        let note = Note::new(tokenizer, level)?;
+        Ok(match note.value {
+            Some(ref value) if is_pointer(value) => NoteRef::Shared(value.clone()),
+            _ => NoteRef::Inline(note),
+        })
+    }
+
+    /// Returns the note, if it is written inline.
+    #[must_use]
+    pub fn inline(&self) -> Option<&Note> {
+        match self {
+            NoteRef::Inline(note) => Some(note),
+            NoteRef::Shared(_) => None,
+        }
+    }
+
+    /// Returns the xref of the shared note, if this points to one.
+    #[must_use]
+    pub fn shared_xref(&self) -> Option<&str> {
+        match self {
+            NoteRef::Inline(_) => None,
+            NoteRef::Shared(xref) => Some(xref),
+        }
+    }
+
+    /// Returns the shared note record this points to in `data`, if any.
+    #[must_use]
+    pub fn shared_note<'a>(&self, data: &'a GedcomData) -> Option<&'a SharedNote> {
+        self.shared_xref()
+            .and_then(|xref| data.find_shared_note(xref))
+    }
+
+    /// Returns the text of the note, looking a shared note up in `data`.
+    ///
+    /// Returns `None` for an empty note or a pointer to a missing record.
+    #[must_use]
+    pub fn text<'a>(&'a self, data: &'a GedcomData) -> Option<&'a str> {
+        match self {
+            NoteRef::Inline(note) => note.value.as_deref(),
+            NoteRef::Shared(_) => self.shared_note(data).map(|note| note.text.as_str()),
+        }
+    }
+}
+
+impl From<Note> for NoteRef {
+    fn from(note: Note) -> Self {
+        NoteRef::Inline(note)
+    }
+}
+
+/// Returns true if the language tag `tag` is `wanted` or one of its
+/// variants, ignoring case.
+pub(crate) fn language_matches(tag: &str, wanted: &str) -> bool {
+    tag.eq_ignore_ascii_case(wanted)
+        || (tag.as_bytes().get(wanted.len()) == Some(&b'-')
+            && tag[..wanted.len()].eq_ignore_ascii_case(wanted))
 }
 
 impl Parser for Note {
     /// parse handles the NOTE tag
     fn parse(&mut self, tokenizer: &mut Tokenizer, level: u8) -> Result<(), GedcomError> {
-        self.value = Some(tokenizer.take_continued_text(level)?);
+        let value = tokenizer.take_continued_text(level)?;
+        // A pointer to a shared note keeps nothing but the xref, so
+        // substructures under it are reported rather than dropped.
+        let pointer = is_pointer(&value);
+        self.value = Some(value);
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
-                "MIME" => self.mime = Some(tokenizer.take_line_value()?),
-                "TRANS" => self.translation = Some(Translation::new(tokenizer, level + 1)?),
-                "LANG" => self.language = Some(tokenizer.take_line_value()?),
+                "MIME" if !pointer => self.mime = Some(tokenizer.take_line_value()?),
+                // Older files written by this crate used `TRANS`.
+                "TRAN" | "TRANS" if !pointer => self
+                    .translations
+                    .push(Translation::new(tokenizer, level + 1)?),
+                "LANG" if !pointer => self.language = Some(tokenizer.take_line_value()?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -86,6 +248,7 @@ impl Parser for Note {
 
 #[cfg(test)]
 mod tests {
+    use super::NoteRef;
     use crate::Gedcom;
 
     #[test]
@@ -118,6 +281,90 @@ mod tests {
         let data = doc.parse_data().unwrap();
 
         let note = data.header.unwrap().note.unwrap();
-        assert_eq!(note.value.unwrap().chars().count(), 1441);
+        assert_eq!(note.value.unwrap().chars().count(), 1438);
+    }
+
+    #[test]
+    fn test_parse_note_translations() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            1 NOTE Born at sea\n\
+            2 LANG en\n\
+            2 TRAN Auf See\n\
+            3 CONT geboren\n\
+            3 LANG de\n\
+            2 TRAN <p>Born at sea</p>\n\
+            3 MIME text/html\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let note = doc.parse_data().unwrap().header.unwrap().note.unwrap();
+
+        assert_eq!(note.translations.len(), 2);
+        assert_eq!(note.in_language("de"), Some("Auf See\ngeboren"));
+        // The HTML translation has no LANG, so it is in English too, but the
+        // note's own text comes first.
+        assert_eq!(note.in_language("en"), Some("Born at sea"));
+        assert_eq!(note.in_language("en-GB"), None);
+        let preferred = ["fr".to_string(), "de".to_string()];
+        assert_eq!(note.preferred_text(&preferred), Some("Auf See\ngeboren"));
+        assert_eq!(note.preferred_text(&[]), Some("Born at sea"));
+    }
+
+    #[test]
+    fn test_parse_note_pointers() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 NOTE @N1@\n\
+            1 NAME John /Doe/\n\
+            2 NOTE @N2@\n\
+            2 NOTE Called Jack\n\
+            0 @N1@ NOTE Emigrated in 1850\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+        assert_eq!(data.shared_notes.len(), 1);
+
+        let john = &data.individuals[0];
+        assert_eq!(john.notes[0], NoteRef::Shared("@N1@".to_string()));
+        assert_eq!(john.notes[0].text(&data), Some("Emigrated in 1850"));
+        assert!(john.notes[0].inline().is_none());
+        let notes = &john.names[0].notes;
+        // A pointer to a missing record resolves to nothing.
+        assert_eq!(notes[0].shared_xref(), Some("@N2@"));
+        assert_eq!(notes[0].text(&data), None);
+        assert!(notes[1].shared_note(&data).is_none());
+        assert_eq!(notes[1].text(&data), Some("Called Jack"));
+    }
+
+    #[test]
+    fn test_parse_note_pointer_substructures() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 SNOTE @N1@\n\
+            2 LANG en\n\
+            0 @N1@ SNOTE Emigrated in 1850\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let err = doc.parse_data().unwrap_err().to_string();
+        assert!(err.contains("Unhandled Note Tag: LANG"), "{err}");
+    }
+
+    #[test]
+    fn test_language_matches() {
+        assert!(super::language_matches("de-AT", "de"));
+        assert!(super::language_matches("zh-Hans", "ZH-hans"));
+        assert!(!super::language_matches("den", "de"));
+        assert!(!super::language_matches("de", "de-AT"));
     }
 }
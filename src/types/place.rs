@@ -13,11 +13,13 @@
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{custom::UserDefinedTag, note::Note, source::citation::Citation},
+    types::{custom::UserDefinedTag, note::NoteRef, source::citation::Citation},
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The principal place in which the superstructure's subject occurred, represented as a List of
@@ -31,11 +33,11 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#PLACE_STRUCTURE>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Place {
     /// The place name value, typically a comma-separated list of jurisdictions
     /// from lowest to highest (e.g., "City, County, State, Country").
-    pub value: Option<String>,
+    pub value: Option<Arc<str>>,
 
     /// The jurisdictional form of the place (tag: FORM).
     ///
@@ -59,7 +61,7 @@ pub struct Place {
     pub romanized: Vec<PlaceVariation>,
 
     /// Notes about the place.
-    pub notes: Vec<Note>,
+    pub notes: Vec<NoteRef>,
 
     /// External identifiers for this place (GEDCOM 7.0).
     pub external_ids: Vec<String>,
@@ -78,7 +80,7 @@ pub struct Place {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#MAP>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MapCoordinates {
     /// Latitude coordinate (tag: LATI).
     ///
@@ -194,7 +196,7 @@ impl Parser for MapCoordinates {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#PLAC-TRAN>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlaceVariation {
     /// The variation text.
     pub value: String,
@@ -266,7 +268,7 @@ impl Place {
     /// Returns an error if parsing fails.
     pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<Place, GedcomError> {
         let mut place = Place {
-            value: Some(tokenizer.take_line_value()?),
+            value: Some(tokenizer.take_shared_value()?),
             ..Default::default()
         };
         place.parse(tokenizer, level)?;
@@ -277,7 +279,7 @@ impl Place {
     #[must_use]
     pub fn with_value(value: &str) -> Self {
         Place {
-            value: Some(value.to_string()),
+            value: Some(Arc::from(value)),
             ..Default::default()
         }
     }
@@ -341,7 +343,7 @@ impl Parser for Place {
                 "ROMN" => self
                     .romanized
                     .push(PlaceVariation::new(tokenizer, level + 1)?),
-                "NOTE" => self.notes.push(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "SOUR" => self.citations.push(Citation::new(tokenizer, level + 1)?),
                 "EXID" => self.external_ids.push(tokenizer.take_line_value()?),
                 _ => {
@@ -405,7 +407,7 @@ mod tests {
     #[test]
     fn test_place_with_value() {
         let place = Place::with_value("New York, New York, USA");
-        assert_eq!(place.value, Some("New York, New York, USA".to_string()));
+        assert_eq!(place.value.as_deref(), Some("New York, New York, USA"));
     }
 
     #[test]
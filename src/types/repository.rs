@@ -4,11 +4,12 @@ use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
     types::{
-        address::Address, custom::UserDefinedTag, date::change_date::ChangeDate, note::Note, Xref,
+        address::Address, custom::UserDefinedTag, date::change_date::ChangeDate, note::NoteRef,
+        shared_note::ExternalId, Xref,
     },
     GedcomError,
 };
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Data repository, the `REPO` tag
@@ -19,7 +20,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#REPOSITORY_RECORD>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Repository {
     /// Optional reference to link to this repo (e.g., `@R1@`).
     pub xref: Option<Xref>,
@@ -51,10 +52,12 @@ pub struct Repository {
     pub website: Vec<String>,
 
     /// Notes about the repository (tag: NOTE).
-    pub notes: Vec<Note>,
+    pub notes: Vec<NoteRef>,
 
     /// Date of the most recent change to this record (tag: CHAN).
     pub change_date: Option<ChangeDate>,
+    /// The date this record was created (tag: CREA, GEDCOM 7.0).
+    pub creation_date: Option<ChangeDate>,
 
     /// User reference number (tag: REFN).
     ///
@@ -84,7 +87,7 @@ pub struct Repository {
     /// External identifiers (tag: EXID, GEDCOM 7.0).
     ///
     /// Identifiers maintained by external authorities that apply to this repository.
-    pub external_ids: Vec<String>,
+    pub external_ids: Vec<ExternalId>,
 
     /// Custom data (extension tags).
     pub custom_data: Vec<Box<UserDefinedTag>>,
@@ -145,7 +148,7 @@ impl Repository {
     }
 
     /// Adds a note to the repository.
-    pub fn add_note(&mut self, note: Note) {
+    pub fn add_note(&mut self, note: NoteRef) {
         self.notes.push(note);
     }
 
@@ -178,15 +181,19 @@ impl Parser for Repository {
                 "EMAIL" => self.email.push(tokenizer.take_line_value()?),
                 "FAX" => self.fax.push(tokenizer.take_line_value()?),
                 "WWW" => self.website.push(tokenizer.take_line_value()?),
-                "NOTE" => self.notes.push(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "CHAN" => self.change_date = Some(ChangeDate::new(tokenizer, level + 1)?),
+                "CREA" => self.creation_date = Some(ChangeDate::new(tokenizer, level + 1)?),
                 "REFN" => {
                     self.user_reference_number = Some(tokenizer.take_line_value()?);
                     // Note: TYPE substructure would need to be parsed here
                 }
                 "RIN" => self.automated_record_id = Some(tokenizer.take_line_value()?),
                 "UID" => self.uid = Some(tokenizer.take_line_value()?),
-                "EXID" => self.external_ids.push(tokenizer.take_line_value()?),
+                "EXID" => {
+                    self.external_ids
+                        .push(ExternalId::from_tokenizer(tokenizer, level + 1)?);
+                }
                 _ => {
                     // Gracefully skip unknown tags
                     tokenizer.take_line_value()?;
@@ -260,6 +267,8 @@ mod tests {
         let repo = &data.repositories[0];
         assert_eq!(repo.notes.len(), 1);
         assert!(repo.notes[0]
+            .inline()
+            .unwrap()
             .value
             .as_ref()
             .unwrap()
@@ -1,13 +1,31 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{custom::UserDefinedTag, note::Note, Xref},
+    types::{custom::UserDefinedTag, note::NoteRef, Xref},
     GedcomError,
 };
 
+/// The media types defined by GEDCOM 5.5.1, which writes them in lower case,
+/// and GEDCOM 7.0, which writes them in upper case and adds `OTHER`.
+pub(crate) const MEDIA_TYPES: [&str; 13] = [
+    "audio",
+    "book",
+    "card",
+    "electronic",
+    "fiche",
+    "film",
+    "magazine",
+    "manuscript",
+    "map",
+    "newspaper",
+    "photo",
+    "tombstone",
+    "video",
+];
+
 /// Citation linking a `Source` to a data `Repository`
 ///
 /// A repository citation indicates that the source material is held at the
@@ -15,7 +33,7 @@ use crate::{
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SOURCE_REPOSITORY_CITATION>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Citation {
     /// Reference to the `Repository`
     pub xref: Xref,
@@ -50,8 +68,12 @@ pub struct Citation {
     /// See GEDCOM 5.5.1 spec, page 62; <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#enumset-MEDI>
     pub media_type: Option<String>,
 
+    /// The medium in words, for a media type of `OTHER` (tag: PHRASE,
+    /// GEDCOM 7.0).
+    pub media_phrase: Option<String>,
+
     /// Notes about this repository citation.
-    pub notes: Vec<Note>,
+    pub notes: Vec<NoteRef>,
 
     /// Custom data (extension tags).
     pub custom_data: Vec<Box<UserDefinedTag>>,
@@ -116,10 +138,27 @@ impl Parser for Citation {
             match tag {
                 "CALN" => {
                     self.call_number = Some(tokenizer.take_line_value()?);
-                    // MEDI can be a substructure of CALN in some GEDCOM versions
+                    let mut medium = None;
+                    parse_subset(tokenizer, level + 1, |tag, tokenizer| {
+                        if tag == "MEDI" {
+                            medium = Some(take_medium(tokenizer, level + 2)?);
+                        } else {
+                            tokenizer.take_line_value()?;
+                        }
+                        Ok(())
+                    })?;
+                    if let Some((media_type, phrase)) = medium {
+                        self.media_type = Some(media_type);
+                        self.media_phrase = phrase;
+                    }
+                }
+                // Some files put MEDI next to CALN rather than under it.
+                "MEDI" => {
+                    let (media_type, phrase) = take_medium(tokenizer, level + 1)?;
+                    self.media_type = Some(media_type);
+                    self.media_phrase = phrase;
                 }
-                "MEDI" => self.media_type = Some(tokenizer.take_line_value()?),
-                "NOTE" => self.notes.push(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 _ => {
                     // Gracefully skip unknown tags
                     tokenizer.take_line_value()?;
@@ -134,9 +173,58 @@ impl Parser for Citation {
     }
 }
 
+/// Reads a `MEDI` and its `PHRASE` from a `Tokenizer` positioned on the
+/// `MEDI` tag at `level`.
+fn take_medium(
+    tokenizer: &mut Tokenizer,
+    level: u8,
+) -> Result<(String, Option<String>), GedcomError> {
+    let medium = tokenizer.take_line_value()?;
+    let mut phrase = None;
+    parse_subset(tokenizer, level, |tag, tokenizer| {
+        if tag == "PHRASE" {
+            phrase = Some(tokenizer.take_line_value()?);
+        } else {
+            tokenizer.take_line_value()?;
+        }
+        Ok(())
+    })?;
+    Ok((medium, phrase))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Gedcom;
+
+    #[test]
+    fn test_parse_call_number() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @S1@ SOUR\n\
+            1 REPO @R1@\n\
+            2 NOTE Reading room only\n\
+            2 CALN HO107/1\n\
+            3 MEDI OTHER\n\
+            4 PHRASE Glass plate\n\
+            1 REPO @R2@\n\
+            2 CALN 929.3\n\
+            2 MEDI book\n\
+            0 TRLR";
+
+        let mut ged = Gedcom::new(sample.chars()).unwrap();
+        let data = ged.parse_data().unwrap();
+        let citations = &data.sources[0].repo_citations;
+
+        assert_eq!(citations[0].call_number.as_deref(), Some("HO107/1"));
+        assert_eq!(citations[0].media_type.as_deref(), Some("OTHER"));
+        assert_eq!(citations[0].media_phrase.as_deref(), Some("Glass plate"));
+        assert_eq!(citations[0].notes.len(), 1);
+        assert_eq!(citations[1].call_number.as_deref(), Some("929.3"));
+        assert_eq!(citations[1].media_type.as_deref(), Some("book"));
+    }
 
     #[test]
     fn test_citation_for_repository() {
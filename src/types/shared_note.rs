@@ -18,11 +18,14 @@
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{custom::UserDefinedTag, date::change_date::ChangeDate, source::citation::Citation},
+    types::{
+        custom::UserDefinedTag, date::change_date::ChangeDate, note::language_matches,
+        source::citation::Citation,
+    },
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// A shared note record (SNOTE).
@@ -35,13 +38,14 @@ use serde::{Deserialize, Serialize};
 /// should only be used if editing the note in one place should edit it in all other
 /// places or if the note itself requires an identifier structure.
 ///
-/// # GEDCOM 7.0 Only
+/// # GEDCOM 5.5.1
 ///
-/// This structure is only valid in GEDCOM 7.0 and later.
+/// Earlier versions have `NOTE` records instead, pointed to with `NOTE @N1@`.
+/// They are read as shared notes, and written back as `NOTE` records.
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SHARED_NOTE_RECORD>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SharedNote {
     /// The cross-reference identifier for this shared note (e.g., `@N1@`).
     pub xref: Option<String>,
@@ -73,6 +77,11 @@ pub struct SharedNote {
     /// Source citations supporting the note content.
     pub source_citations: Vec<Citation>,
 
+    /// A globally unique identifier for this record.
+    ///
+    /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#UID>
+    pub uid: Option<String>,
+
     /// External identifiers for this note.
     pub external_ids: Vec<ExternalId>,
 
@@ -93,7 +102,7 @@ pub struct SharedNote {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#NOTE-TRAN>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NoteTranslation {
     /// The translated text.
     pub text: String,
@@ -114,7 +123,7 @@ pub struct NoteTranslation {
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#EXID>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExternalId {
     /// The external identifier value.
     pub id: String,
@@ -145,6 +154,35 @@ impl ExternalId {
             .as_ref()
             .map(|uri| format!("{}{}", uri, self.id))
     }
+
+    /// Reads an `EXID` structure and its `TYPE` from a `Tokenizer`
+    /// positioned on the `EXID` tag at `level`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if parsing fails.
+    pub(crate) fn from_tokenizer(
+        tokenizer: &mut Tokenizer,
+        level: u8,
+    ) -> Result<ExternalId, GedcomError> {
+        let mut external_id = ExternalId {
+            id: tokenizer.take_line_value()?,
+            type_uri: None,
+        };
+        parse_subset(tokenizer, level, |tag, tokenizer| {
+            match tag {
+                "TYPE" => external_id.type_uri = Some(tokenizer.take_line_value()?),
+                _ => {
+                    return Err(GedcomError::ParseError {
+                        line: tokenizer.line,
+                        message: format!("Unhandled ExternalId Tag: {tag}"),
+                    })
+                }
+            }
+            Ok(())
+        })?;
+        Ok(external_id)
+    }
 }
 
 impl NoteTranslation {
@@ -211,6 +249,40 @@ impl SharedNote {
         self.external_ids.push(external_id);
     }
 
+    /// Returns the note's text in a language, if the note is written in it
+    /// or has a translation into it.
+    ///
+    /// Tags match as in [`Note::in_language`](crate::types::note::Note::in_language).
+    #[must_use]
+    pub fn in_language(&self, language: &str) -> Option<&str> {
+        if self
+            .language
+            .as_deref()
+            .is_some_and(|l| language_matches(l, language))
+        {
+            return Some(&self.text);
+        }
+        self.translations
+            .iter()
+            .find(|t| {
+                t.language
+                    .as_deref()
+                    .or(self.language.as_deref())
+                    .is_some_and(|l| language_matches(l, language))
+            })
+            .map(|t| t.text.as_str())
+    }
+
+    /// Returns the text in the first of `languages` the note is available
+    /// in, or the note's own text if none is.
+    #[must_use]
+    pub fn preferred_text(&self, languages: &[String]) -> &str {
+        languages
+            .iter()
+            .find_map(|l| self.in_language(l))
+            .unwrap_or(&self.text)
+    }
+
     /// Returns true if this note has HTML content.
     #[must_use]
     pub fn is_html(&self) -> bool {
@@ -319,23 +391,35 @@ impl Parser for SharedNote {
                     self.language = Some(tokenizer.take_line_value()?);
                 }
                 "TRAN" => {
-                    let translation = NoteTranslation {
+                    let mut translation = NoteTranslation {
                         text: tokenizer.take_continued_text(level + 1)?,
                         ..Default::default()
                     };
-                    // Parse TRAN substructures would go here
+                    parse_subset(tokenizer, level + 1, |tag, tokenizer| {
+                        match tag {
+                            "MIME" => translation.mime = Some(tokenizer.take_line_value()?),
+                            "LANG" => translation.language = Some(tokenizer.take_line_value()?),
+                            _ => {
+                                return Err(GedcomError::ParseError {
+                                    line: tokenizer.line,
+                                    message: format!("Unhandled NoteTranslation Tag: {tag}"),
+                                })
+                            }
+                        }
+                        Ok(())
+                    })?;
                     self.translations.push(translation);
                 }
                 "SOUR" => {
                     self.source_citations
                         .push(Citation::new(tokenizer, level + 1)?);
                 }
+                "UID" => {
+                    self.uid = Some(tokenizer.take_line_value()?);
+                }
                 "EXID" => {
-                    let id = tokenizer.take_line_value()?;
-                    self.external_ids.push(ExternalId {
-                        id,
-                        type_uri: None, // TYPE substructure would be parsed here
-                    });
+                    self.external_ids
+                        .push(ExternalId::from_tokenizer(tokenizer, level + 1)?);
                 }
                 "CHAN" => {
                     self.change_date = Some(ChangeDate::new(tokenizer, level + 1)?);
@@ -7,14 +7,20 @@ use crate::{
     parser::{parse_subset, Parser},
     tokenizer::{Token, Tokenizer},
     types::{
-        custom::UserDefinedTag, date::change_date::ChangeDate, event::detail::Detail,
-        multimedia::Multimedia, note::Note, repository::citation::Citation, source::data::Data,
-        Xref,
+        custom::{take_vendor_uids, UserDefinedTag},
+        date::change_date::ChangeDate,
+        multimedia::{user::UserReferenceNumber, Multimedia},
+        note::NoteRef,
+        repository::citation::Citation,
+        repository::Repository,
+        shared_note::ExternalId,
+        source::data::Data,
+        GedcomData, Xref,
     },
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Source for genealogy facts
@@ -24,7 +30,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SOURCE_RECORD>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Source {
     pub xref: Option<String>,
     pub data: Data,
@@ -34,8 +40,10 @@ pub struct Source {
     pub publication_facts: Option<String>,
     pub citation_from_source: Option<String>,
     pub change_date: Option<Box<ChangeDate>>,
+    /// The date this record was created (tag: CREA, GEDCOM 7.0).
+    pub creation_date: Option<Box<ChangeDate>>,
     pub multimedia: Vec<Multimedia>,
-    pub notes: Vec<Note>,
+    pub notes: Vec<NoteRef>,
     pub repo_citations: Vec<Citation>,
     /// handles "RFN" tag; found in Ancestry.com export
     pub submitter_registered_rfn: Option<String>,
@@ -47,6 +55,11 @@ pub struct Source {
     ///
     /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#UID>
     pub uid: Option<String>,
+    /// Vendor unique identifiers (tag: _UID).
+    ///
+    /// GUIDs recorded by desktop applications under the `_UID` extension,
+    /// common in GEDCOM 5.5.1 files.
+    pub vendor_uids: Vec<String>,
     /// User reference number (tag: REFN).
     ///
     /// A user-defined number or text that the submitter uses to identify
@@ -64,7 +77,7 @@ pub struct Source {
     /// External identifiers (tag: EXID, GEDCOM 7.0).
     ///
     /// Identifiers maintained by external authorities that apply to this source.
-    pub external_ids: Vec<String>,
+    pub external_ids: Vec<ExternalId>,
 }
 
 impl Source {
@@ -96,13 +109,35 @@ impl Source {
         self.multimedia.push(media);
     }
 
-    pub fn add_note(&mut self, note: Note) {
+    pub fn add_note(&mut self, note: NoteRef) {
         self.notes.push(note);
     }
 
     pub fn add_repo_citation(&mut self, citation: Citation) {
         self.repo_citations.push(citation);
     }
+
+    /// Returns the repositories in `data` that hold this source, each with
+    /// the citation naming it. Citations of repositories that are not in
+    /// `data` are skipped.
+    #[must_use]
+    pub fn repositories<'a>(&'a self, data: &'a GedcomData) -> Vec<(&'a Citation, &'a Repository)> {
+        self.repo_citations
+            .iter()
+            .filter_map(|citation| Some((citation, data.find_repository(&citation.xref)?)))
+            .collect()
+    }
+
+    /// Gets the unique identifiers of the source: the `UID`, then the `_UID`
+    /// values.
+    #[must_use]
+    pub fn uids(&self) -> Vec<&str> {
+        self.uid
+            .iter()
+            .chain(&self.vendor_uids)
+            .map(String::as_str)
+            .collect()
+    }
 }
 
 impl Parser for Source {
@@ -117,17 +152,12 @@ impl Parser for Source {
                 tokenizer.next_token()?;
             }
             match tag {
-                "DATA" => tokenizer.next_token()?,
-                "EVEN" => {
-                    let events_recorded = tokenizer.take_line_value()?;
-                    let mut event = Detail::new(tokenizer, level + 2, "OTHER")?;
-                    event.with_source_data(events_recorded);
-                    self.data.add_event(event);
-                    return Ok(());
-                }
-                "AGNC" => self.data.agency = Some(tokenizer.take_line_value()?),
+                "DATA" => self.data = Data::new(tokenizer, level + 1)?,
                 "ABBR" => self.abbreviation = Some(tokenizer.take_continued_text(level + 1)?),
                 "CHAN" => self.change_date = Some(Box::new(ChangeDate::new(tokenizer, level + 1)?)),
+                "CREA" => {
+                    self.creation_date = Some(Box::new(ChangeDate::new(tokenizer, level + 1)?));
+                }
                 "TITL" => self.title = Some(tokenizer.take_continued_text(level + 1)?),
                 "AUTH" => self.author = Some(tokenizer.take_continued_text(level + 1)?),
                 "PUBL" => self.publication_facts = Some(tokenizer.take_continued_text(level + 1)?),
@@ -135,20 +165,24 @@ impl Parser for Source {
                     self.citation_from_source = Some(tokenizer.take_continued_text(level + 1)?);
                 }
                 "OBJE" => self.add_multimedia(Multimedia::new(tokenizer, level + 1, pointer)?),
-                "NOTE" => self.add_note(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.add_note(NoteRef::new(tokenizer, level + 1)?),
                 "REPO" => self.add_repo_citation(Citation::new(tokenizer, level + 1)?),
                 "RFN" => self.submitter_registered_rfn = Some(tokenizer.take_line_value()?),
                 // Unique identifier (GEDCOM 7.0)
                 "UID" => self.uid = Some(tokenizer.take_line_value()?),
                 // User reference number
                 "REFN" => {
-                    self.user_reference_number = Some(tokenizer.take_line_value()?);
-                    // Note: TYPE substructure would need to be parsed here
+                    let refn = UserReferenceNumber::new(tokenizer, level + 1)?;
+                    self.user_reference_number = refn.value;
+                    self.user_reference_type = refn.user_reference_type;
                 }
                 // Automated record ID
                 "RIN" => self.automated_record_id = Some(tokenizer.take_line_value()?),
                 // External identifier (GEDCOM 7.0)
-                "EXID" => self.external_ids.push(tokenizer.take_line_value()?),
+                "EXID" => {
+                    self.external_ids
+                        .push(ExternalId::from_tokenizer(tokenizer, level + 1)?);
+                }
                 _ => {
                     // Gracefully skip unknown tags
                     tokenizer.take_line_value()?;
@@ -159,6 +193,7 @@ impl Parser for Source {
         };
 
         self.custom_data = parse_subset(tokenizer, level, handle_subset)?;
+        take_vendor_uids(&mut self.custom_data, &mut self.vendor_uids);
 
         Ok(())
     }
@@ -166,7 +201,7 @@ impl Parser for Source {
 
 #[cfg(test)]
 mod tests {
-    use crate::Gedcom;
+    use crate::{types::event::Event, Gedcom};
 
     #[test]
     fn test_parse_source_citation_record() {
@@ -232,7 +267,7 @@ mod tests {
         let citation_data = data.individuals[0].source[0].data.as_ref().unwrap();
 
         assert_eq!(
-            citation_data.text.as_ref().unwrap().value.as_ref().unwrap(),
+            citation_data.texts[0].value.as_ref().unwrap(),
             "a sample text\nSample text continued here. The word TEST should not be broken!"
         );
     }
@@ -259,4 +294,55 @@ mod tests {
 
         assert_eq!(quay.get_int().unwrap(), 1);
     }
+
+    #[test]
+    fn test_parse_source_record_data() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @S1@ SOUR\n\
+            1 DATA\n\
+            2 EVEN BIRT, DEAT\n\
+            3 DATE FROM 1820 TO 1870\n\
+            3 PLAC Salem, Essex, Massachusetts, USA\n\
+            2 EVEN MARR\n\
+            2 AGNC Salem Town Clerk\n\
+            2 NOTE Microfilmed in 1951\n\
+            1 TITL Vital Records of Salem\n\
+            1 PUBL Essex Institute, 1916\n\
+            1 OBJE @M1@\n\
+            1 REFN 1042\n\
+            2 TYPE Shelf\n\
+            1 RIN 7\n\
+            0 TRLR";
+
+        let mut ged = Gedcom::new(sample.chars()).unwrap();
+        let data = ged.parse_data().unwrap();
+        let source = &data.sources[0];
+
+        let events = &source.data.events;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, Event::SourceData("BIRT, DEAT".to_string()));
+        assert_eq!(
+            events[0].date.as_ref().unwrap().value.as_deref(),
+            Some("FROM 1820 TO 1870")
+        );
+        assert_eq!(
+            events[0].place.as_ref().unwrap().value.as_deref(),
+            Some("Salem, Essex, Massachusetts, USA")
+        );
+        assert_eq!(events[1].event, Event::SourceData("MARR".to_string()));
+        assert_eq!(source.data.agency.as_deref(), Some("Salem Town Clerk"));
+        assert_eq!(source.data.notes.len(), 1);
+        assert_eq!(source.title.as_deref(), Some("Vital Records of Salem"));
+        assert_eq!(
+            source.publication_facts.as_deref(),
+            Some("Essex Institute, 1916")
+        );
+        assert_eq!(source.multimedia[0].xref.as_deref(), Some("@M1@"));
+        assert_eq!(source.user_reference_number.as_deref(), Some("1042"));
+        assert_eq!(source.user_reference_type.as_deref(), Some("Shelf"));
+        assert_eq!(source.automated_record_id.as_deref(), Some("7"));
+    }
 }
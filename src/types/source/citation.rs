@@ -1,6 +1,6 @@
 pub mod data;
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -8,25 +8,34 @@ use crate::{
     tokenizer::{Token, Tokenizer},
     types::{
         custom::UserDefinedTag,
+        individual::association::Role,
         multimedia::Multimedia,
-        note::Note,
-        source::{citation::data::SourceCitationData, quay::CertaintyAssessment},
+        note::NoteRef,
+        source::{citation::data::SourceCitationData, quay::CertaintyAssessment, text::Text},
         Xref,
     },
+    util::is_pointer,
     GedcomError,
 };
 
 /// The data provided in the `SourceCitation` structure is source-related information specific to
 /// the data being cited. (See GEDCOM 5.5 Specification page 39.)
-#[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+///
+/// A citation points to a source record, or, in GEDCOM 5.5.1, describes a
+/// source that has no record: its [`description`](Self::description) and
+/// [`texts`](Self::texts) then take the place of the record.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Citation {
-    /// Reference to the `Source`
+    /// Reference to the `Source`, or the description of a source that has no
+    /// record (GEDCOM 5.5.1)
     pub xref: Xref,
+    /// tag: TEXT, text from a source that has no record (GEDCOM 5.5.1)
+    pub texts: Vec<Text>,
     /// Page number of source
     pub page: Option<String>,
     pub data: Option<SourceCitationData>,
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
     pub certainty_assessment: Option<CertaintyAssessment>,
     /// handles "RFN" tag; found in Ancestry.com export
     pub submitter_registered_rfn: Option<String>,
@@ -36,10 +45,15 @@ pub struct Citation {
     ///
     /// Indicates what type of event was cited from the source.
     pub event_type: Option<String>,
+    /// tag: PHRASE under EVEN (GEDCOM 7.0), the cited event in words
+    pub event_phrase: Option<String>,
     /// Role in the cited event (tag: ROLE).
     ///
-    /// Indicates the role the person played in the cited event.
-    pub role: Option<String>,
+    /// Indicates the role the person played in the cited event. A GEDCOM
+    /// 5.5.1 role in words, such as `(Witness)`, is a [`Role::Other`].
+    pub role: Option<Role>,
+    /// tag: PHRASE under ROLE (GEDCOM 7.0), the role in words
+    pub role_phrase: Option<String>,
 }
 
 impl Citation {
@@ -50,16 +64,9 @@ impl Citation {
     /// This function will return an error if parsing fails.
     pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<Citation, GedcomError> {
         let mut citation = Citation {
-            xref: tokenizer.take_line_value()?,
-            page: None,
-            data: None,
-            note: None,
-            certainty_assessment: None,
-            multimedia: Vec::new(),
-            custom_data: Vec::new(),
-            submitter_registered_rfn: None,
-            event_type: None,
-            role: None,
+            // A description of a source may run over several lines.
+            xref: tokenizer.take_continued_text(level)?,
+            ..Citation::default()
         };
         citation.parse(tokenizer, level)?;
         Ok(citation)
@@ -68,6 +75,13 @@ impl Citation {
     pub fn add_multimedia(&mut self, m: Multimedia) {
         self.multimedia.push(m);
     }
+
+    /// Returns the description of the source, if the citation describes one
+    /// rather than pointing to a source record.
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        Some(self.xref.as_str()).filter(|xref| !xref.is_empty() && !is_pointer(xref))
+    }
 }
 
 impl Parser for Citation {
@@ -84,7 +98,7 @@ impl Parser for Citation {
             match tag {
                 "PAGE" => self.page = Some(tokenizer.take_continued_text(level + 1)?),
                 "DATA" => self.data = Some(SourceCitationData::new(tokenizer, level + 1)?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "QUAY" => {
                     self.certainty_assessment =
                         Some(CertaintyAssessment::new(tokenizer, level + 1)?);
@@ -93,10 +107,31 @@ impl Parser for Citation {
                 "OBJE" => self.add_multimedia(Multimedia::new(tokenizer, level + 1, pointer)?),
                 "EVEN" => {
                     self.event_type = Some(tokenizer.take_line_value()?);
-                    // Parse ROLE if it's a substructure of EVEN
-                    // The ROLE tag should be at level + 2 (under EVEN at level + 1)
+                    parse_subset(tokenizer, level + 1, |tag, tokenizer| {
+                        match tag {
+                            "PHRASE" => self.event_phrase = Some(tokenizer.take_line_value()?),
+                            "ROLE" => {
+                                let (role, phrase) = Role::from_tokenizer(tokenizer, level + 2)?;
+                                self.role = Some(role);
+                                self.role_phrase = phrase;
+                            }
+                            _ => {
+                                return Err(GedcomError::ParseError {
+                                    line: tokenizer.line,
+                                    message: format!("Unhandled Event Tag: {tag}"),
+                                })
+                            }
+                        }
+                        Ok(())
+                    })?;
                 }
-                "ROLE" => self.role = Some(tokenizer.take_line_value()?),
+                // Some files put ROLE next to EVEN rather than under it.
+                "ROLE" => {
+                    let (role, phrase) = Role::from_tokenizer(tokenizer, level + 1)?;
+                    self.role = Some(role);
+                    self.role_phrase = phrase;
+                }
+                "TEXT" => self.texts.push(Text::new(tokenizer, level + 1)?),
                 _ => {
                     // Gracefully skip unknown tags instead of failing
                     // This handles non-standard extensions from various GEDCOM generators
@@ -114,6 +149,7 @@ impl Parser for Citation {
 
 #[cfg(test)]
 mod tests {
+    use super::Role;
     use crate::Gedcom;
 
     #[test]
@@ -144,6 +180,74 @@ mod tests {
         assert_eq!(sour.xref, "@S1@");
         assert_eq!(sour.page.as_ref().unwrap(), "Page 42");
         assert_eq!(sour.event_type.as_ref().unwrap(), "BIRT");
-        assert_eq!(sour.role.as_ref().unwrap(), "CHIL");
+        assert_eq!(sour.role, Some(Role::Child));
+    }
+
+    #[test]
+    fn test_parse_full_source_citation() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @S1@ SOUR\n\
+            0 @I1@ INDI\n\
+            1 BIRT\n\
+            2 SOUR @S1@\n\
+            3 PAGE Entry 12\n\
+            3 DATA\n\
+            4 DATE 2 JAN 1900\n\
+            4 TEXT Born to John and Mary\n\
+            5 LANG en\n\
+            4 TEXT Né de John et Mary\n\
+            5 LANG fr\n\
+            3 EVEN BIRT\n\
+            4 PHRASE Baptism record\n\
+            4 ROLE OTHER\n\
+            5 PHRASE Godmother\n\
+            3 OBJE @M1@\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+        let citation = &data.individuals[0].events[0].citations[0];
+
+        let texts = &citation.data.as_ref().unwrap().texts;
+        assert_eq!(texts.len(), 2);
+        assert_eq!(texts[1].value.as_deref(), Some("Né de John et Mary"));
+        assert_eq!(texts[1].language.as_deref(), Some("fr"));
+        assert_eq!(citation.event_phrase.as_deref(), Some("Baptism record"));
+        assert_eq!(citation.role, Some(Role::Other("OTHER".to_string())));
+        assert_eq!(citation.role_phrase.as_deref(), Some("Godmother"));
+        assert_eq!(citation.multimedia[0].xref.as_deref(), Some("@M1@"));
+        assert_eq!(citation.description(), None);
+    }
+
+    #[test]
+    fn test_parse_source_description() {
+        let sample = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 5.5.1\n\
+            0 @I1@ INDI\n\
+            1 SOUR Letter from my aunt,\n\
+            2 CONT dated 1950\n\
+            2 TEXT Your grandfather was bo\n\
+            3 CONC rn in Oslo.\n\
+            2 TEXT He left in 1880.\n\
+            0 TRLR";
+
+        let mut doc = Gedcom::new(sample.chars()).unwrap();
+        let data = doc.parse_data().unwrap();
+        let citation = &data.individuals[0].source[0];
+
+        assert_eq!(
+            citation.description(),
+            Some("Letter from my aunt,\ndated 1950")
+        );
+        assert_eq!(citation.texts.len(), 2);
+        assert_eq!(
+            citation.texts[0].value.as_deref(),
+            Some("Your grandfather was born in Oslo.")
+        );
     }
 }
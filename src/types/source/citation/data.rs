@@ -1,4 +1,4 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -13,10 +13,12 @@ use crate::{
 /// actually recorded in the source, or significant notes written by the recorder, or an applicable
 /// sentence from a letter. This is stored in the SOUR.DATA.TEXT context.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SourceCitationData {
     pub date: Option<Date>,
-    pub text: Option<Text>,
+    /// tag: TEXT, the text of the source, in file order. GEDCOM 7.0 allows one
+    /// per language or media type.
+    pub texts: Vec<Text>,
 }
 
 impl SourceCitationData {
@@ -26,10 +28,7 @@ impl SourceCitationData {
     ///
     /// This function will return an error if parsing fails.
     pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<SourceCitationData, GedcomError> {
-        let mut data = SourceCitationData {
-            date: None,
-            text: None,
-        };
+        let mut data = SourceCitationData::default();
         data.parse(tokenizer, level)?;
         Ok(data)
     }
@@ -42,7 +41,7 @@ impl Parser for SourceCitationData {
         let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
             match tag {
                 "DATE" => self.date = Some(Date::new(tokenizer, level + 1)?),
-                "TEXT" => self.text = Some(Text::new(tokenizer, level + 1)?),
+                "TEXT" => self.texts.push(Text::new(tokenizer, level + 1)?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
@@ -1,18 +1,87 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::types::event::detail::Detail;
+use crate::{
+    parser::{parse_subset, Parser},
+    tokenizer::Tokenizer,
+    types::{
+        event::detail::{Detail, EventValue},
+        note::NoteRef,
+    },
+    GedcomError,
+};
 
+/// The `DATA` of a source record: what the source records, and who is
+/// responsible for it.
+///
+/// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SOURCE_RECORD>
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Data {
-    events: Vec<Detail>,
+    /// tag: EVEN, the kinds of events recorded, such as `BIRT, DEAT`, as an
+    /// [`Event::SourceData`](crate::types::event::Event::SourceData) with
+    /// the period (`DATE`) and the place (`PLAC`) the source covers.
+    pub events: Vec<Detail>,
+    /// tag: AGNC, the organization, institution, corporation, person or other
+    /// entity that is responsible for the source
     pub agency: Option<String>,
+    /// tag: NOTE
+    pub notes: Vec<NoteRef>,
 }
 
 impl Data {
+    /// Creates a new `Data` from a `Tokenizer` positioned on the `DATA` tag.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if parsing fails.
+    pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<Data, GedcomError> {
+        let mut data = Data::default();
+        data.parse(tokenizer, level)?;
+        Ok(data)
+    }
+
     pub fn add_event(&mut self, event: Detail) {
         self.events.push(event);
     }
+
+    /// Returns true if nothing is known about the data of the source.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.agency.is_none() && self.notes.is_empty()
+    }
+}
+
+impl Parser for Data {
+    fn parse(&mut self, tokenizer: &mut Tokenizer, level: u8) -> Result<(), GedcomError> {
+        // skip because this DATA tag should have no line value
+        tokenizer.next_token()?;
+        let handle_subset = |tag: &str, tokenizer: &mut Tokenizer| -> Result<(), GedcomError> {
+            match tag {
+                "EVEN" => {
+                    let mut event = Detail::new(tokenizer, level + 1, "OTHER")?;
+                    let recorded = match std::mem::take(&mut event.value) {
+                        EventValue::Description(recorded) => recorded,
+                        EventValue::Asserted | EventValue::None => String::new(),
+                    };
+                    event.with_source_data(recorded);
+                    self.add_event(event);
+                }
+                "AGNC" => self.agency = Some(tokenizer.take_line_value()?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
+                _ => {
+                    return Err(GedcomError::ParseError {
+                        line: tokenizer.line,
+                        message: format!("Unhandled Data Tag: {tag}"),
+                    })
+                }
+            }
+            Ok(())
+        };
+
+        parse_subset(tokenizer, level, handle_subset)?;
+
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -13,9 +13,14 @@ use crate::{
 /// as opposed to the researcher's interpretation. The word TEXT, in this case, means from the text
 /// which appeared in the source record including labels.
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Text {
     pub value: Option<String>,
+    /// tag: MIME (GEDCOM 7.0), the media type of the text, `text/plain` or
+    /// `text/html`
+    pub mime: Option<String>,
+    /// tag: LANG (GEDCOM 7.0), the language of the text
+    pub language: Option<String>,
 }
 
 impl Text {
@@ -26,7 +31,7 @@ impl Text {
     /// This function will return an error if parsing fails.
     #[allow(clippy::double_must_use)]
     pub fn new(tokenizer: &mut Tokenizer, level: u8) -> Result<Text, GedcomError> {
-        let mut text = Text { value: None };
+        let mut text = Text::default();
         text.parse(tokenizer, level)?;
         Ok(text)
     }
@@ -44,6 +49,8 @@ impl Parser for Text {
                     value.push('\n');
                     value.push_str(&tokenizer.take_line_value()?);
                 }
+                "MIME" => self.mime = Some(tokenizer.take_line_value()?),
+                "LANG" => self.language = Some(tokenizer.take_line_value()?),
                 _ => {
                     return Err(GedcomError::ParseError {
                         line: tokenizer.line,
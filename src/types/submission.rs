@@ -1,11 +1,11 @@
 use crate::{
     parser::{parse_subset, Parser},
     tokenizer::Tokenizer,
-    types::{custom::UserDefinedTag, date::change_date::ChangeDate, note::Note, Xref},
+    types::{custom::UserDefinedTag, date::change_date::ChangeDate, note::NoteRef, Xref},
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// GEDCOM Submission Record Structure
@@ -25,7 +25,7 @@ use serde::{Deserialize, Serialize};
 /// [GEDCOM 5.5.1 specification, page 28](https://gedcom.io/specifications/ged551.pdf)
 /// [GEDCOM 7.0 Specification](gedcom.io/specifications/FamilySearchGEDCOMv7.html)
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Submission {
     /// Cross-reference identifier for this submission record
     /// Format: `@XREF:SUBN@`
@@ -61,7 +61,7 @@ pub struct Submission {
     /// Collection of note structures providing additional information
     /// Can contain multiple notes with various details about the submission
     /// Tag: `NOTE`
-    pub note: Option<Note>,
+    pub notes: Vec<NoteRef>,
     /// When this submission record was last changed (optional) Helps track the history of
     /// modifications to your submission
     /// Tag: `CHAN`
@@ -109,7 +109,7 @@ impl Parser for Submission {
                 "CHAN" => self.change_date = Some(ChangeDate::new(tokenizer, level + 1)?),
                 "DESC" => self.descendant_generations = Some(tokenizer.take_line_value()?),
                 "FAMF" => self.family_file_name = Some(tokenizer.take_line_value()?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "ORDI" => self.ordinance_process_flag = Some(tokenizer.take_line_value()?),
                 "RIN" => self.automated_record_id = Some(tokenizer.take_line_value()?),
                 "SUBM" => self.submitter_ref = Some(tokenizer.take_line_value()?),
@@ -188,11 +188,11 @@ mod tests {
         assert_eq!(date.value.unwrap(), "1 APR 1998");
         assert_eq!(date.time.unwrap(), "12:34:56.789");
 
-        assert_eq!(custom[0].tag, "_MYCUSTOMTAG");
+        assert_eq!(&*custom[0].tag, "_MYCUSTOMTAG");
         assert_eq!(custom[0].value.as_ref().unwrap(), "Some custom data here");
         assert!(custom[0].children.is_empty());
 
-        assert_eq!(custom[1].tag, "_ANOTHER_TAG");
+        assert_eq!(&*custom[1].tag, "_ANOTHER_TAG");
         assert_eq!(
             custom[1].value.as_ref().unwrap(),
             "Another piece of custom data"
@@ -3,12 +3,12 @@ use crate::{
     tokenizer::{Token, Tokenizer},
     types::{
         address::Address, custom::UserDefinedTag, date::change_date::ChangeDate,
-        multimedia::link::Link, note::Note, Xref,
+        multimedia::link::Link, note::NoteRef, Xref,
     },
     GedcomError,
 };
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The submitter record identifies an individual or organization that contributed information
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// See <https://gedcom.io/specifications/FamilySearchGEDCOMv7.html#SUBMITTER_RECORD>
 #[derive(Clone, Debug, Default, PartialEq)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Submitter {
     /// Optional reference to link to this submitter
     pub xref: Option<Xref>,
@@ -39,8 +39,10 @@ pub struct Submitter {
     pub automated_record_id: Option<String>,
     /// Date of the last change to the record
     pub change_date: Option<ChangeDate>,
-    /// Note provided by submitter about the enclosing data
-    pub note: Option<Note>,
+    /// The date this record was created (tag: CREA, GEDCOM 7.0).
+    pub creation_date: Option<ChangeDate>,
+    /// Notes provided by submitter about the enclosing data
+    pub notes: Vec<NoteRef>,
     /// Phone number(s) of the submitter (tag: PHON).
     pub phone: Vec<String>,
     /// Email address(es) of the submitter (tag: EMAIL).
@@ -109,8 +111,9 @@ impl Parser for Submitter {
                 "ADDR" => self.address = Some(Address::new(tokenizer, level + 1)?),
                 "OBJE" => self.add_multimedia(Link::new(tokenizer, level + 1, pointer)?),
                 "LANG" => self.language = Some(tokenizer.take_line_value()?),
-                "NOTE" => self.note = Some(Note::new(tokenizer, level + 1)?),
+                "NOTE" | "SNOTE" => self.notes.push(NoteRef::new(tokenizer, level + 1)?),
                 "CHAN" => self.change_date = Some(ChangeDate::new(tokenizer, level + 1)?),
+                "CREA" => self.creation_date = Some(ChangeDate::new(tokenizer, level + 1)?),
                 "PHON" => self.phone.push(tokenizer.take_line_value()?),
                 "EMAIL" => self.email.push(tokenizer.take_line_value()?),
                 "FAX" => self.fax.push(tokenizer.take_line_value()?),
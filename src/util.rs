@@ -9,8 +9,17 @@
 #![allow(clippy::too_many_lines)]
 #![allow(clippy::trivially_copy_pass_by_ref)]
 
-use std::collections::HashMap;
-use std::sync::RwLock;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::intern::Interner;
 
 /// Macro for displaying `Option`s in debug mode without the text wrapping.
 #[macro_export]
@@ -27,48 +36,42 @@ macro_rules! fmt_optional_value {
 /// A simple string interner for commonly used GEDCOM strings.
 ///
 /// This reduces memory usage by storing only one copy of each unique string
-/// and returning references to interned strings.
+/// and returning shared handles to it. It is a thread-safe [`Interner`], so
+/// that the records of a file parsed on several threads share the same values.
+#[derive(Debug, Default)]
 pub struct StringInterner {
-    strings: RwLock<HashMap<Box<str>, Box<str>>>,
+    strings: RwLock<Interner>,
 }
 
 impl StringInterner {
     /// Creates a new empty string interner.
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            strings: RwLock::new(HashMap::new()),
-        }
+        Self::default()
     }
 
-    /// Interns a string, returning a reference to the interned version.
+    /// Interns a string, returning a shared handle to the interned version.
     ///
     /// If the string has been interned before, returns the existing copy.
-    /// Otherwise, stores the string and returns a reference to it.
+    /// Otherwise, stores the string and returns a handle to it.
     ///
     /// # Panics
     ///
     /// Panics if the internal `RwLock` is poisoned.
     #[inline]
-    pub fn intern(&self, s: &str) -> Box<str> {
+    pub fn intern(&self, s: &str) -> Arc<str> {
         // First, try a read lock to check if the string exists
         {
             let strings = self.strings.read().unwrap();
-            if let Some(interned) = strings.get(s) {
-                return interned.clone();
+            if let Some(symbol) = strings.get(s) {
+                return strings.shared(symbol);
             }
         }
 
         // String not found, acquire write lock and insert
         let mut strings = self.strings.write().unwrap();
-        // Double-check in case another thread added it
-        if let Some(interned) = strings.get(s) {
-            return interned.clone();
-        }
-
-        let boxed: Box<str> = s.into();
-        strings.insert(boxed.clone(), boxed.clone());
-        boxed
+        let symbol = strings.intern(s);
+        strings.shared(symbol)
     }
 
     /// Returns the number of interned strings.
@@ -92,17 +95,12 @@ impl StringInterner {
     }
 }
 
-impl Default for StringInterner {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 /// Common GEDCOM tags that can be matched efficiently.
 ///
 /// Using an enum instead of strings for known tags reduces memory
 /// and allows for faster matching via pattern matching.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(missing_docs)]
 pub enum KnownTag {
     // Header tags
@@ -590,6 +588,96 @@ pub fn needs_at_escaping(value: &str, is_gedcom_7: bool) -> bool {
     }
 }
 
+/// Returns true if `value` is a cross-reference pointer such as `@I1@`.
+#[must_use]
+pub fn is_pointer(value: &str) -> bool {
+    value.len() > 2
+        && value.starts_with('@')
+        && value.ends_with('@')
+        && !value[1..].starts_with('#')
+        && !value[1..value.len() - 1].contains(|c: char| c == '@' || c.is_whitespace())
+}
+
+/// Escapes `@` signs in a line value about to be written.
+///
+/// This applies [`escape_at_signs`] to text, but leaves values that are
+/// pointers (`@I1@`) untouched. In GEDCOM 5.5.1, the `@` delimiters of escape
+/// sequences such as `@#DJULIAN@` are not doubled either.
+///
+/// # Examples
+///
+/// ```
+/// use ged_io::util::escape_line_value;
+///
+/// assert_eq!(escape_line_value("me@example.com", false), "me@@example.com");
+/// assert_eq!(escape_line_value("me@example.com", true), "me@example.com");
+/// assert_eq!(escape_line_value("@I1@", false), "@I1@");
+/// assert_eq!(escape_line_value("@#DJULIAN@ 1 JAN 1700", false), "@#DJULIAN@ 1 JAN 1700");
+/// ```
+#[must_use]
+pub fn escape_line_value(value: &str, is_gedcom_7: bool) -> Cow<'_, str> {
+    if is_pointer(value) || !needs_at_escaping(value, is_gedcom_7) {
+        return Cow::Borrowed(value);
+    }
+    if is_gedcom_7 || !value.contains("@#") {
+        return Cow::Owned(escape_at_signs(value, is_gedcom_7));
+    }
+
+    let mut escaped = String::with_capacity(value.len() + 4);
+    let mut rest = value;
+    while let Some(pos) = rest.find('@') {
+        escaped.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail[1..].starts_with('#') {
+            if let Some(end) = tail[1..].find('@') {
+                escaped.push_str(&tail[..end + 2]);
+                rest = &tail[end + 2..];
+                continue;
+            }
+        }
+        escaped.push_str("@@");
+        rest = &tail[1..];
+    }
+    escaped.push_str(rest);
+    Cow::Owned(escaped)
+}
+
+/// Returns a new random (version 4) UUID as defined by RFC 9562, in its
+/// lowercase hyphenated form.
+///
+/// The random bits come from the standard library's randomly seeded hasher
+/// mixed with a counter and the clock. That is not a cryptographically
+/// secure generator: the UUIDs are unique, but they are not unpredictable,
+/// so they must not be used as secrets, tokens or access keys.
+pub(crate) fn random_uuid() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+
+    let state = RandomState::new();
+    let mut bytes = [0u8; 16];
+    for (half, chunk) in (0u8..).zip(bytes.chunks_mut(8)) {
+        let mut hasher = state.build_hasher();
+        hasher.write_u64(count);
+        hasher.write_u128(nanos);
+        hasher.write_u8(half);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let mut uuid = String::with_capacity(36);
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            uuid.push('-');
+        }
+        let _ = write!(uuid, "{byte:02x}");
+    }
+    uuid
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -601,8 +689,8 @@ mod tests {
         let s1 = interner.intern("test");
         let s2 = interner.intern("test");
 
-        // Both should be equal
-        assert_eq!(s1, s2);
+        // Both should be the same allocation
+        assert!(Arc::ptr_eq(&s1, &s2));
 
         // Interner should have only one unique string
         assert_eq!(interner.len(), 1);
@@ -697,4 +785,42 @@ mod tests {
         assert!(!needs_at_escaping("test@email.com", true));
         assert!(needs_at_escaping("@ref", true));
     }
+
+    #[test]
+    fn test_is_pointer() {
+        assert!(is_pointer("@I1@"));
+        assert!(is_pointer("@VOID@"));
+        assert!(!is_pointer("@@"));
+        assert!(!is_pointer("@#DJULIAN@"));
+        assert!(!is_pointer("@I1@ extra"));
+        assert!(!is_pointer("a@b@"));
+    }
+
+    #[test]
+    fn test_escape_line_value() {
+        assert_eq!(escape_line_value("plain", false), "plain");
+        assert_eq!(escape_line_value("a@b@c", false), "a@@b@@c");
+        assert_eq!(escape_line_value("@start", true), "@@start");
+        assert_eq!(escape_line_value("a@b", true), "a@b");
+        assert_eq!(escape_line_value("@F1@", true), "@F1@");
+        assert_eq!(
+            escape_line_value("@#DFRENCH R@ 1 VEND 1 me@x", false),
+            "@#DFRENCH R@ 1 VEND 1 me@@x"
+        );
+        assert_eq!(escape_line_value("ends with @#", false), "ends with @@#");
+    }
+
+    #[test]
+    fn test_random_uuid() {
+        let uuid = random_uuid();
+        assert_eq!(uuid.len(), 36);
+        let groups: Vec<&str> = uuid.split('-').collect();
+        assert_eq!(
+            groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+        assert!(groups[2].starts_with('4'));
+        assert!(matches!(&groups[3][..1], "8" | "9" | "a" | "b"));
+        assert_ne!(random_uuid(), uuid);
+    }
 }
@@ -22,7 +22,7 @@
 //! - New structures: `EXID`, `MIME`, `CREA`, `SDATE`, `CROP`, `NO`, `INIL`, `TRAN`
 //! - URIs for all structure types
 
-#[cfg(feature = "json")]
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -31,7 +31,7 @@ use std::fmt;
 /// This enum identifies which version of the GEDCOM specification a file conforms to,
 /// which affects parsing behavior and available features.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GedcomVersion {
     /// GEDCOM 5.5.1 - The previous major version, widely supported.
     ///
@@ -52,7 +52,7 @@ pub enum GedcomVersion {
 
 /// A wrapper for version strings from unknown GEDCOM versions.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VersionString(pub String);
 
 impl GedcomVersion {
@@ -297,6 +297,7 @@ pub fn appears_to_be_v7(content: &str) -> bool {
 /// This struct provides a convenient way to check multiple version-dependent
 /// features at once.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(clippy::struct_excessive_bools)]
 pub struct VersionFeatures {
     /// Whether `CONC` tag is supported
@@ -19,45 +19,135 @@
 //! # }
 //! ```
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::dialect::{Dialect, MILITARY_SERVICE};
+use crate::digest::DIGEST_TAG;
+use crate::encoding::{encode_to_bytes, GedcomEncoding};
 use crate::types::{
     address::Address,
-    date::Date,
-    event::detail::Detail as EventDetail,
+    custom::UserDefinedTag,
+    date::{change_date::ChangeDate, Date},
+    event::detail::{Detail as EventDetail, EventValue},
     event::Event,
-    family::Family,
-    gedcom7::{NonEvent, SortDate},
+    family::{
+        child::{relationship_pedigree, FamilyChild},
+        Family,
+    },
+    gedcom7::{Crop, NonEvent, SortDate},
     header::{meta::HeadMeta, schema::Schema, source::HeadSour},
     individual::{
+        association::{Association, Role},
         attribute::detail::AttributeDetail,
+        family_link::{
+            child_link::ChildLinkStatus, pedigree::Pedigree, FamilyLink, FamilyLinkType,
+        },
         gender::{Gender, GenderType},
-        name::Name,
+        name::{Name, NameType, NameVariation},
         Individual,
     },
     lds::LdsOrdinance,
-    multimedia::Multimedia,
-    note::Note,
-    repository::Repository,
-    shared_note::SharedNote,
+    multimedia::{file::Reference, format::Format, Multimedia},
+    note::{Note, NoteRef},
+    repository::{
+        citation::{Citation as RepositoryCitation, MEDIA_TYPES},
+        Repository,
+    },
+    shared_note::{ExternalId, SharedNote},
     source::quay::CertaintyAssessment,
-    source::{citation::Citation, Source},
+    source::{citation::Citation, text::Text, Source},
     submission::Submission,
     submitter::Submitter,
     GedcomData,
 };
+use crate::util::{escape_line_value, random_uuid};
+use crate::GedcomError;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 use std::io;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How [`WriterConfig::max_line_length`] is measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LineLengthMeasure {
+    /// The whole formatted line, in characters: level, tag, delimiters, value and terminator.
+    #[default]
+    Line,
+    /// Only the value after the tag, in UTF-8 bytes.
+    PayloadBytes,
+}
+
+/// Preset writer settings for a target GEDCOM version or importer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WriterProfile {
+    /// GEDCOM 5.5.1: values are wrapped with `CONC` at 255 payload bytes.
+    Gedcom551,
+    /// GEDCOM 7.0: `CONC` no longer exists, so logical lines are never split.
+    Gedcom70,
+    /// GEDCOM 5.5.1 wrapped at 90 payload bytes, for legacy importers that reject longer lines.
+    Compat,
+}
+
+impl WriterProfile {
+    /// Returns the writer configuration for this profile.
+    #[must_use]
+    pub fn config(self) -> WriterConfig {
+        let (gedcom_version, max_line_length) = match self {
+            WriterProfile::Gedcom551 => ("5.5.1", 255),
+            WriterProfile::Gedcom70 => ("7.0", usize::MAX),
+            WriterProfile::Compat => ("5.5.1", 90),
+        };
+        WriterConfig {
+            max_line_length,
+            line_length_measure: LineLengthMeasure::PayloadBytes,
+            gedcom_version: gedcom_version.to_string(),
+            ..WriterConfig::default()
+        }
+    }
+}
 
 /// Configuration options for GEDCOM writing.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct WriterConfig {
     /// Line ending to use (default: "\n")
     pub line_ending: String,
-    /// Maximum line length before CONC/CONT wrapping (default: 255, GEDCOM spec max)
+    /// Maximum line length before CONC/CONT wrapping (default: 255, GEDCOM spec max).
+    ///
+    /// See [`line_length_measure`](Self::line_length_measure) for what is counted.
+    /// GEDCOM 7.0 output has no `CONC`, so the limit is ignored there.
     pub max_line_length: usize,
+    /// What `max_line_length` counts (default: the whole line, in characters).
+    pub line_length_measure: LineLengthMeasure,
     /// Whether to include empty optional fields (default: false)
     pub include_empty_fields: bool,
     /// GEDCOM version to write (default: "5.5.1")
     pub gedcom_version: String,
+    /// Whether GEDCOM 7.0 output gives every record a `UID` (default: false).
+    ///
+    /// Individual, family, source, repository, submitter, multimedia and
+    /// shared note records without one get a new random UUID; see
+    /// [`GedcomWriter::write_to_with_uids`] to keep the assignment.
+    pub generate_uids: bool,
+    /// Whether to embed the [`digest`](GedcomData::digest) of the data in the
+    /// header as a `_SHA256` extension, replacing any already there
+    /// (default: false).
+    pub embed_digest: bool,
+    /// Whether to leave LDS ordinances out of the output (default: false).
+    pub exclude_lds_ordinances: bool,
+    /// The application the output is for (default: `Standard`).
+    ///
+    /// See [`GedcomWriter::dialect`].
+    pub dialect: Dialect,
+    /// The encoding the header `CHAR` line declares, or `None` to copy the
+    /// line from the data (default: `None`).
+    ///
+    /// See [`write_file`] to write the bytes in that encoding too.
+    pub encoding: Option<GedcomEncoding>,
 }
 
 impl Default for WriterConfig {
@@ -65,8 +155,14 @@ impl Default for WriterConfig {
         Self {
             line_ending: "\n".to_string(),
             max_line_length: 255,
+            line_length_measure: LineLengthMeasure::Line,
             include_empty_fields: false,
             gedcom_version: "5.5.1".to_string(),
+            generate_uids: false,
+            embed_digest: false,
+            exclude_lds_ordinances: false,
+            dialect: Dialect::Standard,
+            encoding: None,
         }
     }
 }
@@ -87,20 +183,65 @@ impl Default for WriterConfig {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Only the [`WriterConfig`] is meant to be saved and restored, such as with
+/// serde; [`with_config`](Self::with_config) makes a writer from it.
 #[derive(Debug, Clone, Default)]
 pub struct GedcomWriter {
     config: WriterConfig,
+    /// UIDs assigned to records that have none, by xref.
+    uids: HashMap<String, String>,
+    /// The digest to embed in the header, computed while writing.
+    digest: Option<String>,
+    /// Whether this writer produces the canonical form hashed by
+    /// [`GedcomData::digest`], which leaves out any embedded digest.
+    canonical: bool,
 }
 
 impl GedcomWriter {
     /// Creates a new `GedcomWriter` with default configuration.
     #[must_use]
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a `GedcomWriter` with the given configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomWriter, WriterConfig};
+    ///
+    /// let config = WriterConfig {
+    ///     line_ending: "\r\n".to_string(),
+    ///     ..WriterConfig::default()
+    /// };
+    /// let writer = GedcomWriter::with_config(config);
+    /// assert_eq!(writer.config().line_ending, "\r\n");
+    /// ```
+    #[must_use]
+    pub fn with_config(config: WriterConfig) -> Self {
         Self {
-            config: WriterConfig::default(),
+            config,
+            ..Self::default()
         }
     }
 
+    /// Creates a `GedcomWriter` configured for the given profile.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomWriter, WriterProfile};
+    ///
+    /// let writer = GedcomWriter::with_profile(WriterProfile::Compat);
+    /// assert_eq!(writer.config().max_line_length, 90);
+    /// ```
+    #[must_use]
+    pub fn with_profile(profile: WriterProfile) -> Self {
+        Self::with_config(profile.config())
+    }
+
     /// Sets a custom line ending.
     ///
     /// # Example
@@ -123,6 +264,13 @@ impl GedcomWriter {
         self
     }
 
+    /// Sets how the maximum line length is measured.
+    #[must_use]
+    pub fn line_length_measure(mut self, measure: LineLengthMeasure) -> Self {
+        self.config.line_length_measure = measure;
+        self
+    }
+
     /// Sets whether to include empty optional fields.
     #[must_use]
     pub fn include_empty_fields(mut self, include: bool) -> Self {
@@ -137,6 +285,100 @@ impl GedcomWriter {
         self
     }
 
+    /// Sets whether GEDCOM 7.0 output gives every record a `UID`.
+    ///
+    /// Records that have no UID yet are assigned a random (version 4) UUID
+    /// as defined by RFC 9562. The setting has no effect on GEDCOM 5.5.1
+    /// output.
+    ///
+    /// The UUIDs tell records apart but do not come from a cryptographically
+    /// secure generator, so they may be predictable and must not be used as
+    /// secrets.
+    #[must_use]
+    pub fn generate_uids(mut self, generate: bool) -> Self {
+        self.config.generate_uids = generate;
+        self
+    }
+
+    /// Sets whether to embed the digest of the data in the header.
+    ///
+    /// See [`crate::digest`] for how recipients verify it.
+    #[must_use]
+    pub fn embed_digest(mut self, embed: bool) -> Self {
+        self.config.embed_digest = embed;
+        self
+    }
+
+    /// Sets whether to leave LDS ordinances out of the output.
+    ///
+    /// The data itself is not changed; see
+    /// [`GedcomData::remove_lds_ordinances`] for that.
+    #[must_use]
+    pub fn exclude_lds_ordinances(mut self, exclude: bool) -> Self {
+        self.config.exclude_lds_ordinances = exclude;
+        self
+    }
+
+    /// Sets the encoding the header `CHAR` line declares, whatever the
+    /// data says.
+    ///
+    /// The output of this writer is still a `String`; use [`write_file`] to
+    /// encode it to match. GEDCOM 7.0 output has no `CHAR` line.
+    #[must_use]
+    pub fn encoding(mut self, encoding: GedcomEncoding) -> Self {
+        self.config.encoding = Some(encoding);
+        self
+    }
+
+    /// Sets the application the output is for.
+    ///
+    /// Besides the tag choices of the dialect, this wraps lines at 255
+    /// characters and picks the line ending the program expects; set
+    /// [`max_line_length`](Self::max_line_length) or
+    /// [`line_ending`](Self::line_ending) afterwards to override them. See
+    /// the [`dialect`](crate::dialect) module for what each one changes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{dialect::Dialect, GedcomBuilder, GedcomWriter};
+    ///
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR ANSEL\n\
+    ///     0 @I1@ INDI\n1 NAME Jane /Doe/\n1 NAME Jane /Smith/\n2 TYPE married\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source).unwrap();
+    ///
+    /// let output = GedcomWriter::new()
+    ///     .dialect(Dialect::Legacy)
+    ///     .write_to_string(&data)
+    ///     .unwrap();
+    /// assert!(output.contains("1 CHAR UTF-8\r\n"));
+    /// assert!(output.contains("1 NAME Jane /Doe/\r\n2 _MARNM Smith\r\n"));
+    /// ```
+    #[must_use]
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.config.dialect = dialect;
+        if dialect != Dialect::Standard {
+            self.config.max_line_length = 255;
+            self.config.line_length_measure = LineLengthMeasure::Line;
+            let windows = dialect.quirks().windows_line_endings;
+            self.config.line_ending = if windows { "\r\n" } else { "\n" }.to_string();
+        }
+        self
+    }
+
+    /// Returns a writer producing the canonical form hashed by
+    /// [`GedcomData::digest`].
+    pub(crate) fn canonical() -> Self {
+        Self {
+            config: WriterConfig {
+                max_line_length: usize::MAX,
+                ..WriterConfig::default()
+            },
+            canonical: true,
+            ..Self::default()
+        }
+    }
+
     /// Returns the current writer configuration.
     #[must_use]
     pub fn config(&self) -> &WriterConfig {
@@ -154,12 +396,130 @@ impl GedcomWriter {
         Ok(output)
     }
 
+    /// Writes GEDCOM data as UTF-8 to a byte sink, such as a file or socket.
+    ///
+    /// Output is buffered, and flushed before returning.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the sink if writing or flushing fails.
+    pub fn write_to_io<W: io::Write>(&self, writer: W, data: &GedcomData) -> Result<(), io::Error> {
+        let mut adapter = IoAdapter {
+            inner: io::BufWriter::new(writer),
+            error: None,
+        };
+        let result = self.write_to(&mut adapter, data);
+        if let Some(error) = adapter.error {
+            return Err(error);
+        }
+        result?;
+        io::Write::flush(&mut adapter.inner)
+    }
+
+    /// Writes GEDCOM data to standard output.
+    ///
+    /// Standard output is locked while writing, so the output is not
+    /// interleaved with that of other threads, and goes through a buffer
+    /// rather than being flushed line by line.
+    ///
+    /// # Broken pipes
+    ///
+    /// When the reading end of a pipe is closed early, as with `| head`, writing
+    /// stops with an error of kind [`io::ErrorKind::BrokenPipe`]. Rust programs
+    /// ignore `SIGPIPE`, so the process is not killed; command-line filters
+    /// usually treat this error as the end of their output and exit quietly.
+    ///
+    /// ```rust,no_run
+    /// use std::io::ErrorKind;
+    /// use ged_io::{stream::GedcomStreamParser, types::GedcomData, GedcomWriter};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let data: GedcomData = GedcomStreamParser::from_stdin()?.collect::<Result<_, _>>()?;
+    /// match GedcomWriter::new().write_to_stdout(&data) {
+    ///     Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+    ///     result => Ok(result?),
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to standard output fails.
+    pub fn write_to_stdout(&self, data: &GedcomData) -> Result<(), io::Error> {
+        self.write_to_io(io::stdout().lock(), data)
+    }
+
     /// Writes GEDCOM data to any type implementing `Write`.
     ///
     /// # Errors
     ///
     /// Returns an error if writing fails.
     pub fn write_to<W: Write>(&self, writer: &mut W, data: &GedcomData) -> Result<(), io::Error> {
+        self.write_to_with_uids(writer, data).map(|_| ())
+    }
+
+    /// Writes GEDCOM data and returns the UIDs assigned while writing.
+    ///
+    /// With [`generate_uids`](Self::generate_uids) enabled and GEDCOM 7.0
+    /// output, every record with an xref but no UID is given a new one (see
+    /// [`WriterConfig::generate_uids`] for which record types carry one). The
+    /// returned map goes from those xrefs to their new UIDs, so the
+    /// assignment can be stored and reused the next time the file is synced.
+    /// Otherwise the map is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ged_io::{GedcomBuilder, GedcomWriter};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n0 TRLR";
+    /// let data = GedcomBuilder::new().build_from_str(source)?;
+    ///
+    /// let mut output = String::new();
+    /// let uids = GedcomWriter::new()
+    ///     .generate_uids(true)
+    ///     .write_to_with_uids(&mut output, &data)?;
+    /// assert!(output.contains(&format!("1 UID {}", uids["@I1@"])));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_to_with_uids<W: Write>(
+        &self,
+        writer: &mut W,
+        data: &GedcomData,
+    ) -> Result<BTreeMap<String, String>, io::Error> {
+        let mut writer_for_data = self.for_data(data);
+        if writer_for_data.config.generate_uids && writer_for_data.is_gedcom_7() {
+            writer_for_data.uids = missing_uids(data);
+        }
+        if writer_for_data.config.embed_digest {
+            writer_for_data.digest = Some(data.digest());
+        }
+
+        writer_for_data.write_records(writer, data)?;
+        Ok(writer_for_data.uids.into_iter().collect())
+    }
+
+    /// Returns a copy of this writer set to the GEDCOM version `data` will
+    /// be written in.
+    ///
+    /// A version declared by the header is what ends up in the output, so
+    /// it also decides which `@` escaping rules apply.
+    fn for_data(&self, data: &GedcomData) -> GedcomWriter {
+        let mut writer = self.clone();
+        if let Some(version) = data.header.as_ref().and_then(|h| h.version()) {
+            writer.config.gedcom_version = version.to_string();
+        }
+        writer
+    }
+
+    /// Writes every record of `data`, from the header to the trailer.
+    fn write_records<W: Write>(&self, writer: &mut W, data: &GedcomData) -> Result<(), io::Error> {
         // Write header
         self.write_header(writer, data)?;
 
@@ -224,8 +584,15 @@ impl GedcomWriter {
                 self.write_line(writer, 2, "FORM", Some("LINEAGE-LINKED"))?;
             }
 
-            // Character encoding
-            if let Some(ref encoding) = header.encoding {
+            // Character encoding: the output is UTF-8 whatever the input was,
+            // which importers of a dialect need to be told.
+            if let Some(encoding) = self.config.encoding {
+                if !self.is_gedcom_7() {
+                    self.write_line(writer, 1, "CHAR", Some(encoding.char_value()))?;
+                }
+            } else if self.config.dialect != Dialect::Standard && !self.is_gedcom_7() {
+                self.write_line(writer, 1, "CHAR", Some("UTF-8"))?;
+            } else if let Some(ref encoding) = header.encoding {
                 if let Some(ref value) = encoding.value {
                     self.write_value_or_wrap(writer, 1, "CHAR", Some(value))?;
                 }
@@ -251,6 +618,11 @@ impl GedcomWriter {
                 self.write_line(writer, 1, "SUBM", Some(subm))?;
             }
 
+            // Submission reference
+            if let Some(ref subn) = header.submission_tag {
+                self.write_line(writer, 1, "SUBN", Some(subn))?;
+            }
+
             // File name
             if let Some(ref file) = header.filename {
                 self.write_value_or_wrap(writer, 1, "FILE", Some(file))?;
@@ -268,19 +640,41 @@ impl GedcomWriter {
 
             // Note
             if let Some(ref note) = header.note {
-                self.write_note(writer, 1, note)?;
+                self.write_inline_note(writer, 1, note)?;
+            }
+
+            // Default place form
+            if let Some(ref place) = header.place {
+                self.write_line(writer, 1, "PLAC", None)?;
+                if !place.form.is_empty() {
+                    self.write_value_or_wrap(writer, 2, "FORM", Some(&place.form.join(", ")))?;
+                }
             }
 
             // Schema (GEDCOM 7.0)
             if let Some(ref schema) = header.schema {
                 self.write_schema(writer, schema)?;
             }
+
+            // Vendor extensions and other unknown structures, without a
+            // digest that is being replaced or left out
+            let strip_digest = self.canonical || self.config.embed_digest;
+            for tag in &header.custom_data {
+                if !(strip_digest && &*tag.tag == DIGEST_TAG) {
+                    self.write_custom_data(writer, 1, std::slice::from_ref(tag))?;
+                }
+            }
         } else {
             // Write minimal required header
             self.write_line(writer, 1, "GEDC", None)?;
             self.write_line(writer, 2, "VERS", Some(&self.config.gedcom_version))?;
             self.write_line(writer, 2, "FORM", Some("LINEAGE-LINKED"))?;
-            self.write_value_or_wrap(writer, 1, "CHAR", Some("UTF-8"))?;
+            let encoding = self.config.encoding.unwrap_or(GedcomEncoding::Utf8);
+            self.write_value_or_wrap(writer, 1, "CHAR", Some(encoding.char_value()))?;
+        }
+
+        if let Some(ref digest) = self.digest {
+            self.write_line(writer, 1, DIGEST_TAG, Some(digest))?;
         }
 
         Ok(())
@@ -332,6 +726,11 @@ impl GedcomWriter {
             if let Some(ref addr) = corp.address {
                 self.write_address(writer, 3, addr)?;
             }
+            self.write_contacts(
+                writer,
+                3,
+                [&corp.phone, &corp.email, &corp.fax, &corp.website],
+            )?;
         }
 
         if let Some(ref data) = source.data {
@@ -345,6 +744,8 @@ impl GedcomWriter {
             }
         }
 
+        self.write_custom_data(writer, 2, &source.custom_data)?;
+
         Ok(())
     }
 
@@ -356,8 +757,28 @@ impl GedcomWriter {
     ) -> Result<(), io::Error> {
         self.write_line_with_xref(writer, 0, individual.xref.as_deref(), "INDI", None)?;
 
-        if let Some(ref name) = individual.name {
+        if let Some(ref restriction) = individual.restriction {
+            self.write_value_or_wrap(writer, 1, "RESN", Some(restriction))?;
+        }
+
+        // Dialects with `_MARNM` get plain married names under the first
+        // other name.
+        let primary = individual
+            .names
+            .iter()
+            .position(|name| self.married_surname(name).is_none());
+        for (i, name) in individual.names.iter().enumerate() {
+            if primary.is_some() && self.married_surname(name).is_some() {
+                continue;
+            }
             self.write_name(writer, name)?;
+            if Some(i) == primary {
+                for name in &individual.names {
+                    if let Some(surname) = self.married_surname(name) {
+                        self.write_value_or_wrap(writer, 2, "_MARNM", Some(surname))?;
+                    }
+                }
+            }
         }
 
         if let Some(ref sex) = individual.sex {
@@ -378,13 +799,55 @@ impl GedcomWriter {
         }
 
         // LDS Ordinances (BAPL, CONL, INIL, ENDL, SLGC)
-        for ordinance in &individual.lds_ordinances {
+        for ordinance in self.lds_ordinances(&individual.lds_ordinances) {
             self.write_lds_ordinance(writer, 1, ordinance)?;
         }
 
         for family_link in &individual.families {
             let tag = family_link.family_link_type.to_tag();
             self.write_line(writer, 1, tag, Some(&family_link.xref))?;
+            if family_link.family_link_type == FamilyLinkType::Child {
+                self.write_child_link(writer, family_link)?;
+            }
+            for note in &family_link.notes {
+                self.write_note(writer, 2, note)?;
+            }
+        }
+
+        for association in &individual.associations {
+            self.write_association(writer, 1, association)?;
+        }
+
+        for alias in &individual.aliases {
+            self.write_line(writer, 1, "ALIA", Some(alias))?;
+        }
+
+        if let Some(ref submitter) = individual.ancestor_interest {
+            self.write_line(writer, 1, "ANCI", Some(submitter))?;
+        }
+
+        if let Some(ref submitter) = individual.descendant_interest {
+            self.write_line(writer, 1, "DESI", Some(submitter))?;
+        }
+
+        // GEDCOM 7.0 dropped the Ancestral File and automated record numbers.
+        if !self.is_gedcom_7() {
+            if let Some(ref afn) = individual.ancestral_file_number {
+                self.write_value_or_wrap(writer, 1, "AFN", Some(afn))?;
+            }
+        }
+
+        for refn in &individual.user_reference_numbers {
+            self.write_value_or_wrap(writer, 1, "REFN", refn.value.as_deref())?;
+            if let Some(ref refn_type) = refn.user_reference_type {
+                self.write_value_or_wrap(writer, 2, "TYPE", Some(refn_type))?;
+            }
+        }
+
+        if !self.is_gedcom_7() {
+            if let Some(ref rin) = individual.automated_record_id {
+                self.write_value_or_wrap(writer, 1, "RIN", Some(rin))?;
+            }
         }
 
         for citation in &individual.source {
@@ -395,16 +858,23 @@ impl GedcomWriter {
             self.write_multimedia_link(writer, 1, media)?;
         }
 
-        if let Some(ref note) = individual.note {
+        for note in &individual.notes {
             self.write_note(writer, 1, note)?;
         }
 
-        if let Some(ref change_date) = individual.change_date {
-            self.write_line(writer, 1, "CHAN", None)?;
-            if let Some(ref date) = change_date.date {
-                self.write_date(writer, 2, date)?;
-            }
-        }
+        self.write_identifiers(
+            writer,
+            individual.xref.as_deref(),
+            individual.uid.as_deref(),
+            &individual.vendor_uids,
+            &individual.external_ids,
+        )?;
+
+        self.write_record_dates(
+            writer,
+            individual.change_date.as_ref(),
+            individual.creation_date.as_ref(),
+        )?;
 
         Ok(())
     }
@@ -413,24 +883,34 @@ impl GedcomWriter {
     fn write_name<W: Write>(&self, writer: &mut W, name: &Name) -> Result<(), io::Error> {
         self.write_value_or_wrap(writer, 1, "NAME", name.value.as_deref())?;
 
-        if let Some(ref given) = name.given {
-            self.write_value_or_wrap(writer, 2, "GIVN", Some(given))?;
+        if let Some(ref name_type) = name.name_type {
+            self.write_name_type(writer, name_type, name.type_phrase.as_deref())?;
         }
 
-        if let Some(ref surname) = name.surname {
-            self.write_value_or_wrap(writer, 2, "SURN", Some(surname))?;
+        for (tag, piece) in [
+            ("GIVN", name.given.as_deref()),
+            ("SURN", name.surname.as_deref()),
+            ("NPFX", name.prefix.as_deref()),
+            ("NSFX", name.suffix.as_deref()),
+            ("SPFX", name.surname_prefix.as_deref()),
+            ("NICK", name.nickname.as_deref()),
+        ] {
+            if let Some(piece) = piece {
+                self.write_value_or_wrap(writer, 2, tag, Some(piece))?;
+            }
         }
 
-        if let Some(ref prefix) = name.prefix {
-            self.write_value_or_wrap(writer, 2, "NPFX", Some(prefix))?;
+        for variation in &name.phonetic {
+            self.write_name_variation(writer, "FONE", variation)?;
         }
-
-        if let Some(ref suffix) = name.suffix {
-            self.write_value_or_wrap(writer, 2, "NSFX", Some(suffix))?;
+        for variation in &name.romanized {
+            self.write_name_variation(writer, "ROMN", variation)?;
         }
-
-        if let Some(ref surname_prefix) = name.surname_prefix {
-            self.write_value_or_wrap(writer, 2, "SPFX", Some(surname_prefix))?;
+        // Translations are new in GEDCOM 7.0.
+        if self.is_gedcom_7() {
+            for variation in &name.translations {
+                self.write_name_variation(writer, "TRAN", variation)?;
+            }
         }
 
         // Source citations for name
@@ -438,14 +918,105 @@ impl GedcomWriter {
             self.write_citation(writer, 2, citation)?;
         }
 
-        // Note
-        if let Some(ref note) = name.note {
+        // Notes
+        for note in &name.notes {
             self.write_note(writer, 2, note)?;
         }
 
         Ok(())
     }
 
+    /// Returns true for military service the dialect writes as `_MILT`.
+    fn is_military_service(&self, event: &EventDetail) -> bool {
+        let kind = event.event_type.as_deref().unwrap_or_default();
+        event.event == Event::Event
+            && kind.eq_ignore_ascii_case(MILITARY_SERVICE)
+            && self.config.dialect.quirks().military
+            && !self.is_gedcom_7()
+    }
+
+    /// Returns the surname to write as `_MARNM` for a married name with
+    /// nothing else to it, if the dialect records married names that way.
+    fn married_surname<'a>(&self, name: &'a Name) -> Option<&'a str> {
+        let plain = name.name_type == Some(NameType::Married)
+            && name.type_phrase.is_none()
+            && name.prefix.is_none()
+            && name.surname_prefix.is_none()
+            && name.suffix.is_none()
+            && name.nickname.is_none()
+            && name.notes.is_empty()
+            && name.source.is_empty()
+            && name.phonetic.is_empty()
+            && name.romanized.is_empty()
+            && name.translations.is_empty();
+        if !plain || !self.config.dialect.quirks().married_names || self.is_gedcom_7() {
+            return None;
+        }
+        let surname = match name.surname.as_deref() {
+            Some(surname) => surname,
+            None => name.value.as_deref()?.split('/').nth(1)?,
+        };
+        Some(surname.trim()).filter(|surname| !surname.is_empty())
+    }
+
+    /// Writes the type of a name. GEDCOM 7.0 takes one of a fixed set of
+    /// values, with any other given as `OTHER` and a phrase; GEDCOM 5.5.1
+    /// takes free text.
+    fn write_name_type<W: Write>(
+        &self,
+        writer: &mut W,
+        name_type: &NameType,
+        phrase: Option<&str>,
+    ) -> Result<(), io::Error> {
+        let other = match name_type {
+            NameType::Other(value) if !value.eq_ignore_ascii_case("OTHER") => Some(value.as_str()),
+            _ => None,
+        };
+        if self.is_gedcom_7() {
+            let value = if let NameType::Other(_) = name_type {
+                "OTHER"
+            } else {
+                name_type.as_str()
+            };
+            self.write_line(writer, 2, "TYPE", Some(value))?;
+            if let Some(phrase) = phrase.or(other) {
+                self.write_value_or_wrap(writer, 3, "PHRASE", Some(phrase))?;
+            }
+        } else {
+            let value = match name_type {
+                NameType::Other(_) => phrase.or(other).unwrap_or("OTHER"),
+                _ => name_type.as_str(),
+            };
+            self.write_value_or_wrap(writer, 2, "TYPE", Some(value))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a phonetic, romanized or translated variation of a name.
+    fn write_name_variation<W: Write>(
+        &self,
+        writer: &mut W,
+        tag: &str,
+        variation: &NameVariation,
+    ) -> Result<(), io::Error> {
+        self.write_value_or_wrap(writer, 2, tag, Some(&variation.value))?;
+        for (tag, piece) in [
+            ("TYPE", &variation.variation_type),
+            ("LANG", &variation.language),
+            ("GIVN", &variation.given),
+            ("SURN", &variation.surname),
+            ("NPFX", &variation.prefix),
+            ("NSFX", &variation.suffix),
+            ("SPFX", &variation.surname_prefix),
+            ("NICK", &variation.nickname),
+        ] {
+            if let Some(ref piece) = piece {
+                self.write_value_or_wrap(writer, 3, tag, Some(piece))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Writes a gender record.
     fn write_gender<W: Write>(&self, writer: &mut W, gender: &Gender) -> Result<(), io::Error> {
         let sex_char = match gender.value {
@@ -474,8 +1045,18 @@ impl GedcomWriter {
         level: u8,
         event: &EventDetail,
     ) -> Result<(), io::Error> {
-        let tag = event_to_tag(&event.event);
-        self.write_line(writer, level, tag, event.value.as_deref())?;
+        let military = self.is_military_service(event);
+        let tag = if military {
+            "_MILT"
+        } else {
+            event_to_tag(&event.event)
+        };
+        // The tag alone asserts the event where the version has no `Y` flag for it.
+        let value = match event.value {
+            EventValue::Asserted if !takes_y_flag(&event.event, self.is_gedcom_7()) => None,
+            ref value => value.as_payload(),
+        };
+        self.write_line(writer, level, tag, value)?;
 
         if let Some(ref date) = event.date {
             self.write_date(writer, level + 1, date)?;
@@ -487,42 +1068,31 @@ impl GedcomWriter {
         }
 
         if let Some(ref place) = event.place {
-            self.write_value_or_wrap(writer, level + 1, "PLAC", place.value.as_deref())?;
-            if let Some(ref form) = place.form {
-                self.write_value_or_wrap(writer, level + 2, "FORM", Some(form))?;
-            }
-            if let Some(ref map) = place.map {
-                self.write_line(writer, level + 2, "MAP", None)?;
-                if let Some(ref lat) = map.latitude {
-                    self.write_value_or_wrap(writer, level + 3, "LATI", Some(lat))?;
-                }
-                if let Some(ref lon) = map.longitude {
-                    self.write_value_or_wrap(writer, level + 3, "LONG", Some(lon))?;
-                }
-            }
-            for phonetic in &place.phonetic {
-                self.write_value_or_wrap(writer, level + 2, "FONE", Some(&phonetic.value))?;
-                if let Some(ref vtype) = phonetic.variation_type {
-                    self.write_value_or_wrap(writer, level + 3, "TYPE", Some(vtype))?;
-                }
-            }
-            for romanized in &place.romanized {
-                self.write_value_or_wrap(writer, level + 2, "ROMN", Some(&romanized.value))?;
-                if let Some(ref vtype) = romanized.variation_type {
-                    self.write_value_or_wrap(writer, level + 3, "TYPE", Some(vtype))?;
-                }
-            }
+            self.write_place(writer, level + 1, place)?;
         }
 
         if let Some(ref event_type) = event.event_type {
-            self.write_value_or_wrap(writer, level + 1, "TYPE", Some(event_type))?;
+            if !military {
+                self.write_value_or_wrap(writer, level + 1, "TYPE", Some(event_type))?;
+            }
+        }
+
+        // Spouse ages for family events (HUSB/WIFE with nested AGE)
+        for detail in &event.family_event_details {
+            self.write_line(writer, level + 1, detail.tag(), None)?;
+            if let Some(ref age) = detail.age {
+                self.write_value_or_wrap(writer, level + 2, "AGE", Some(age))?;
+                if let Some(ref phrase) = detail.age_phrase {
+                    self.write_value_or_wrap(writer, level + 3, "PHRASE", Some(phrase))?;
+                }
+            }
         }
 
         for citation in &event.citations {
             self.write_citation(writer, level + 1, citation)?;
         }
 
-        if let Some(ref note) = event.note {
+        for note in &event.notes {
             self.write_note(writer, level + 1, note)?;
         }
 
@@ -547,9 +1117,83 @@ impl GedcomWriter {
             self.write_value_or_wrap(writer, level + 1, "RELI", Some(religion))?;
         }
 
+        if let Some(ref address) = event.address {
+            self.write_address(writer, level + 1, address)?;
+        }
+        self.write_contacts(
+            writer,
+            level + 1,
+            [&event.phone, &event.email, &event.fax, &event.website],
+        )?;
+
+        if let Some(ref link) = event.family_link {
+            self.write_line(writer, level + 1, "FAMC", Some(&link.xref))?;
+            if let Some(ref adopted_by) = link.adopted_by {
+                self.write_line(writer, level + 2, "ADOP", Some(adopted_by.as_str()))?;
+                if let Some(ref phrase) = link.adopted_by_phrase {
+                    if self.is_gedcom_7() {
+                        self.write_value_or_wrap(writer, level + 3, "PHRASE", Some(phrase))?;
+                    }
+                }
+            }
+        }
+
+        // GEDCOM 5.5.1 has no event associations.
+        if self.is_gedcom_7() {
+            for association in &event.associations {
+                self.write_association(writer, level + 1, association)?;
+            }
+        }
+
         Ok(())
     }
 
+    /// Writes an association.
+    ///
+    /// GEDCOM 7.0 output needs a `ROLE`: one without a role is written as
+    /// `OTHER`, with the `RELA` text as its phrase. GEDCOM 5.5.1 output needs
+    /// a `RELA`, which is the role in words when the association has none.
+    fn write_association<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        association: &Association,
+    ) -> Result<(), io::Error> {
+        self.write_line(writer, level, "ASSO", Some(&association.xref))?;
+
+        if self.is_gedcom_7() {
+            if let Some(ref phrase) = association.phrase {
+                self.write_value_or_wrap(writer, level + 1, "PHRASE", Some(phrase))?;
+            }
+            let role = association.role.as_ref().map_or("OTHER", Role::as_str);
+            self.write_line(writer, level + 1, "ROLE", Some(role))?;
+            let phrase = match association.role {
+                Some(_) => association.role_phrase.as_deref(),
+                None => association.relationship.as_deref(),
+            };
+            if let Some(phrase) = phrase {
+                self.write_value_or_wrap(writer, level + 2, "PHRASE", Some(phrase))?;
+            }
+        } else {
+            if let Some(ref relationship) = association.description() {
+                self.write_value_or_wrap(writer, level + 1, "RELA", Some(relationship))?;
+            }
+            if let Some(ref association_type) = association.association_type {
+                self.write_value_or_wrap(writer, level + 1, "TYPE", Some(association_type))?;
+            }
+        }
+
+        for citation in &association.citations {
+            self.write_citation(writer, level + 1, citation)?;
+        }
+
+        for note in &association.notes {
+            self.write_note(writer, level + 1, note)?;
+        }
+
+        self.write_custom_data(writer, level + 1, &association.custom_data)
+    }
+
     /// Writes a place structure.
     fn write_place<W: Write>(
         &self,
@@ -602,11 +1246,20 @@ impl GedcomWriter {
             self.write_place(writer, 2, place)?;
         }
 
+        if let Some(ref address) = attr.address {
+            self.write_address(writer, 2, address)?;
+        }
+        self.write_contacts(
+            writer,
+            2,
+            [&attr.phone, &attr.email, &attr.fax, &attr.website],
+        )?;
+
         for citation in &attr.sources {
             self.write_citation(writer, 2, citation)?;
         }
 
-        if let Some(ref note) = attr.note {
+        for note in &attr.notes {
             self.write_note(writer, 2, note)?;
         }
 
@@ -627,6 +1280,9 @@ impl GedcomWriter {
 
         for child in &family.children {
             self.write_line(writer, 1, "CHIL", Some(child))?;
+            if let Some(detail) = family.child_detail(child) {
+                self.write_family_child(writer, detail)?;
+            }
         }
 
         for event in &family.events {
@@ -638,8 +1294,15 @@ impl GedcomWriter {
             self.write_non_event(writer, 1, non_event)?;
         }
 
+        // GEDCOM 5.5.1 has no family associations.
+        if self.is_gedcom_7() {
+            for association in &family.associations {
+                self.write_association(writer, 1, association)?;
+            }
+        }
+
         // LDS Sealing to Spouse (SLGS)
-        for ordinance in &family.lds_ordinances {
+        for ordinance in self.lds_ordinances(&family.lds_ordinances) {
             self.write_lds_ordinance(writer, 1, ordinance)?;
         }
 
@@ -655,20 +1318,80 @@ impl GedcomWriter {
             self.write_note(writer, 1, note)?;
         }
 
-        if let Some(ref change_date) = family.change_date {
-            self.write_line(writer, 1, "CHAN", None)?;
-            if let Some(ref date) = change_date.date {
-                self.write_date(writer, 2, date)?;
-            }
-        }
+        self.write_identifiers(
+            writer,
+            family.xref.as_deref(),
+            family.uid.as_deref(),
+            &family.vendor_uids,
+            &family.external_ids,
+        )?;
+
+        self.write_record_dates(
+            writer,
+            family.change_date.as_ref(),
+            family.creation_date.as_ref(),
+        )?;
 
         Ok(())
     }
 
+    /// Writes the substructures of a `CHIL` line.
+    ///
+    /// GEDCOM 7.0 output leaves out the `_FREL`/`_MREL` relationships that
+    /// map to a standard pedigree, which the child's `FAMC` link carries as
+    /// `PEDI` instead.
+    fn write_family_child<W: Write>(
+        &self,
+        writer: &mut W,
+        child: &FamilyChild,
+    ) -> Result<(), io::Error> {
+        if self.is_gedcom_7() {
+            if let Some(ref phrase) = child.phrase {
+                self.write_value_or_wrap(writer, 2, "PHRASE", Some(phrase))?;
+            }
+        }
+        let relationships = [
+            ("_FREL", &child.father_relationship),
+            ("_MREL", &child.mother_relationship),
+        ];
+        for (tag, relationship) in relationships {
+            if let Some(value) = relationship {
+                let standard = self.is_gedcom_7() && relationship_pedigree(value).is_some();
+                if !standard && self.writes_custom_tags() {
+                    self.write_value_or_wrap(writer, 2, tag, Some(value))?;
+                }
+            }
+        }
+        self.write_custom_data(writer, 2, &child.custom_data)
+    }
+
     /// Writes a source record.
     fn write_source<W: Write>(&self, writer: &mut W, source: &Source) -> Result<(), io::Error> {
         self.write_line_with_xref(writer, 0, source.xref.as_deref(), "SOUR", None)?;
 
+        if !source.data.is_empty() {
+            self.write_line(writer, 1, "DATA", None)?;
+            for event in &source.data.events {
+                let recorded = match event.event {
+                    Event::SourceData(ref recorded) => recorded.as_str(),
+                    _ => "",
+                };
+                self.write_line(writer, 2, "EVEN", Some(recorded))?;
+                if let Some(ref date) = event.date {
+                    self.write_date(writer, 3, date)?;
+                }
+                if let Some(ref place) = event.place {
+                    self.write_place(writer, 3, place)?;
+                }
+            }
+            if let Some(ref agency) = source.data.agency {
+                self.write_value_or_wrap(writer, 2, "AGNC", Some(agency))?;
+            }
+            for note in &source.data.notes {
+                self.write_note(writer, 2, note)?;
+            }
+        }
+
         if let Some(ref title) = source.title {
             self.write_long_text(writer, 1, "TITL", title)?;
         }
@@ -681,9 +1404,34 @@ impl GedcomWriter {
             self.write_value_or_wrap(writer, 1, "ABBR", Some(abbr))?;
         }
 
+        if let Some(ref publication) = source.publication_facts {
+            self.write_long_text(writer, 1, "PUBL", publication)?;
+        }
+
+        if let Some(ref text) = source.citation_from_source {
+            self.write_long_text(writer, 1, "TEXT", text)?;
+        }
+
         // Repository citations
         for repo in &source.repo_citations {
-            self.write_line(writer, 1, "REPO", Some(&repo.xref))?;
+            self.write_repository_citation(writer, repo)?;
+        }
+
+        if let Some(ref refn) = source.user_reference_number {
+            self.write_value_or_wrap(writer, 1, "REFN", Some(refn))?;
+            if let Some(ref refn_type) = source.user_reference_type {
+                self.write_value_or_wrap(writer, 2, "TYPE", Some(refn_type))?;
+            }
+        }
+
+        if !self.is_gedcom_7() {
+            if let Some(ref rin) = source.automated_record_id {
+                self.write_value_or_wrap(writer, 1, "RIN", Some(rin))?;
+            }
+        }
+
+        for media in &source.multimedia {
+            self.write_multimedia_link(writer, 1, media)?;
         }
 
         // Notes
@@ -691,17 +1439,44 @@ impl GedcomWriter {
             self.write_note(writer, 1, note)?;
         }
 
-        // Change date
-        if let Some(ref change_date) = source.change_date {
-            self.write_line(writer, 1, "CHAN", None)?;
-            if let Some(ref date) = change_date.date {
-                self.write_date(writer, 2, date)?;
-            }
-        }
+        self.write_identifiers(
+            writer,
+            source.xref.as_deref(),
+            source.uid.as_deref(),
+            &source.vendor_uids,
+            &source.external_ids,
+        )?;
+
+        self.write_record_dates(
+            writer,
+            source.change_date.as_deref(),
+            source.creation_date.as_deref(),
+        )?;
 
         Ok(())
     }
 
+    /// Writes a `REPO` citation of a source with its call number, media type
+    /// and notes. The media type is only written under a call number.
+    fn write_repository_citation<W: Write>(
+        &self,
+        writer: &mut W,
+        repo: &RepositoryCitation,
+    ) -> Result<(), io::Error> {
+        self.write_line(writer, 1, "REPO", Some(&repo.xref))?;
+        for note in &repo.notes {
+            self.write_note(writer, 2, note)?;
+        }
+        let Some(ref call_number) = repo.call_number else {
+            return Ok(());
+        };
+        self.write_value_or_wrap(writer, 2, "CALN", Some(call_number))?;
+        if let Some(ref medium) = repo.media_type {
+            self.write_medium(writer, 3, "MEDI", medium, repo.media_phrase.as_deref())?;
+        }
+        Ok(())
+    }
+
     /// Writes a repository record.
     fn write_repository<W: Write>(
         &self,
@@ -717,6 +1492,25 @@ impl GedcomWriter {
         if let Some(ref address) = repo.address {
             self.write_address(writer, 1, address)?;
         }
+        self.write_contacts(
+            writer,
+            1,
+            [&repo.phone, &repo.email, &repo.fax, &repo.website],
+        )?;
+
+        self.write_identifiers(
+            writer,
+            repo.xref.as_deref(),
+            repo.uid.as_deref(),
+            &[],
+            &repo.external_ids,
+        )?;
+
+        self.write_record_dates(
+            writer,
+            repo.change_date.as_ref(),
+            repo.creation_date.as_ref(),
+        )?;
 
         Ok(())
     }
@@ -736,23 +1530,33 @@ impl GedcomWriter {
         if let Some(ref address) = submitter.address {
             self.write_address(writer, 1, address)?;
         }
+        self.write_contacts(
+            writer,
+            1,
+            [
+                &submitter.phone,
+                &submitter.email,
+                &submitter.fax,
+                &submitter.website,
+            ],
+        )?;
 
         if let Some(ref lang) = submitter.language {
             self.write_value_or_wrap(writer, 1, "LANG", Some(lang))?;
         }
 
-        // Note
-        if let Some(ref note) = submitter.note {
+        // Notes
+        for note in &submitter.notes {
             self.write_note(writer, 1, note)?;
         }
 
-        // Change date
-        if let Some(ref change_date) = submitter.change_date {
-            self.write_line(writer, 1, "CHAN", None)?;
-            if let Some(ref date) = change_date.date {
-                self.write_date(writer, 2, date)?;
-            }
-        }
+        self.write_uid(writer, submitter.xref.as_deref(), submitter.uid.as_deref())?;
+
+        self.write_record_dates(
+            writer,
+            submitter.change_date.as_ref(),
+            submitter.creation_date.as_ref(),
+        )?;
 
         Ok(())
     }
@@ -785,6 +1589,12 @@ impl GedcomWriter {
             self.write_value_or_wrap(writer, 1, "DESC", Some(descendants))?;
         }
 
+        for note in &submission.notes {
+            self.write_note(writer, 1, note)?;
+        }
+
+        self.write_record_dates(writer, submission.change_date.as_ref(), None)?;
+
         Ok(())
     }
 
@@ -796,26 +1606,37 @@ impl GedcomWriter {
     ) -> Result<(), io::Error> {
         self.write_line_with_xref(writer, 0, media.xref.as_deref(), "OBJE", None)?;
 
-        if let Some(ref file) = media.file {
-            self.write_value_or_wrap(writer, 1, "FILE", file.value.as_deref())?;
-            if let Some(ref format) = file.form {
-                self.write_value_or_wrap(writer, 2, "FORM", format.value.as_deref())?;
-            }
+        for file in &media.files {
+            self.write_file(writer, 1, file, "TYPE")?;
         }
 
         if let Some(ref form) = media.form {
-            self.write_line(writer, 1, "FORM", form.value.as_deref())?;
+            self.write_format(writer, 1, form, "TYPE")?;
         }
 
         if let Some(ref title) = media.title {
             self.write_value_or_wrap(writer, 1, "TITL", Some(title))?;
         }
 
-        // Note
-        if let Some(ref note) = media.note_structure {
+        // Notes
+        for note in &media.notes {
             self.write_note(writer, 1, note)?;
         }
 
+        self.write_identifiers(
+            writer,
+            media.xref.as_deref(),
+            media.uid.as_deref(),
+            &media.vendor_uids,
+            &media.external_ids,
+        )?;
+
+        self.write_record_dates(
+            writer,
+            media.change_date.as_ref(),
+            media.creation_date.as_ref(),
+        )?;
+
         Ok(())
     }
 
@@ -828,40 +1649,178 @@ impl GedcomWriter {
     ) -> Result<(), io::Error> {
         if let Some(ref xref) = media.xref {
             self.write_line(writer, level, "OBJE", Some(xref))?;
+            // GEDCOM 7.0 lets a link crop and retitle the record it points to.
+            if let Some(ref crop) = media.crop {
+                self.write_crop(writer, level + 1, crop)?;
+            }
+            if let Some(ref title) = media.title {
+                self.write_value_or_wrap(writer, level + 1, "TITL", Some(title))?;
+            }
         } else {
             self.write_line(writer, level, "OBJE", None)?;
-            if let Some(ref file) = media.file {
-                self.write_value_or_wrap(writer, level + 1, "FILE", file.value.as_deref())?;
+            for file in &media.files {
+                self.write_file(writer, level + 1, file, "MEDI")?;
+            }
+            if let Some(ref form) = media.form {
+                self.write_format(writer, level + 1, form, "MEDI")?;
             }
             if let Some(ref title) = media.title {
                 self.write_value_or_wrap(writer, level + 1, "TITL", Some(title))?;
             }
         }
+        if let Some(primary) = media.primary.filter(|_| self.writes_custom_tags()) {
+            let value = if primary { "Y" } else { "N" };
+            self.write_line(writer, level + 1, "_PRIM", Some(value))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a `FILE` with its format and title. `media_tag` is the tag
+    /// GEDCOM 5.5.1 gives the media type there.
+    fn write_file<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        file: &Reference,
+        media_tag: &str,
+    ) -> Result<(), io::Error> {
+        self.write_value_or_wrap(writer, level, "FILE", file.value.as_deref())?;
+        if let Some(ref form) = file.form {
+            self.write_format(writer, level + 1, form, media_tag)?;
+        }
+        if let Some(ref title) = file.title {
+            self.write_value_or_wrap(writer, level + 1, "TITL", Some(title))?;
+        }
+        if let Some(ref crop) = file.crop {
+            self.write_crop(writer, level + 1, crop)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a `FORM` with its media type.
+    fn write_format<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        form: &Format,
+        media_tag: &str,
+    ) -> Result<(), io::Error> {
+        self.write_line(writer, level, "FORM", form.value.as_deref())?;
+        if let Some(ref medium) = form.source_media_type {
+            self.write_medium(writer, level + 1, media_tag, medium, None)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a source media type: in lower case under `tag` in GEDCOM 5.5.1,
+    /// and in upper case under `MEDI` in GEDCOM 7.0, where media types it
+    /// does not define become `OTHER` with a `PHRASE`.
+    fn write_medium<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        tag: &str,
+        medium: &str,
+        phrase: Option<&str>,
+    ) -> Result<(), io::Error> {
+        let standard = MEDIA_TYPES
+            .iter()
+            .find(|m| m.eq_ignore_ascii_case(medium))
+            .copied();
+        if self.is_gedcom_7() {
+            let (value, phrase) = match standard {
+                Some(m) => (m.to_ascii_uppercase(), phrase),
+                None => ("OTHER".to_string(), phrase.or(Some(medium))),
+            };
+            self.write_line(writer, level, "MEDI", Some(&value))?;
+            if let Some(phrase) = phrase {
+                self.write_value_or_wrap(writer, level + 1, "PHRASE", Some(phrase))?;
+            }
+        } else {
+            let value = match (standard, phrase) {
+                (Some(m), _) => m,
+                (None, Some(phrase)) => phrase,
+                (None, None) => medium,
+            };
+            self.write_value_or_wrap(writer, level, tag, Some(value))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a crop region.
+    fn write_crop<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        crop: &Crop,
+    ) -> Result<(), io::Error> {
+        self.write_line(writer, level, "CROP", None)?;
+        for (tag, value) in [
+            ("TOP", crop.top),
+            ("LEFT", crop.left),
+            ("HEIGHT", crop.height),
+            ("WIDTH", crop.width),
+        ] {
+            if let Some(value) = value {
+                self.write_line(writer, level + 1, tag, Some(&value.to_string()))?;
+            }
+        }
         Ok(())
     }
 
     /// Writes a source citation.
+    ///
+    /// GEDCOM 7.0 has no citations of sources without a record: their
+    /// description becomes a note on a `@VOID@` citation, and their text
+    /// moves under `DATA`.
     fn write_citation<W: Write>(
         &self,
         writer: &mut W,
         level: u8,
         citation: &Citation,
     ) -> Result<(), io::Error> {
-        self.write_line(writer, level, "SOUR", Some(&citation.xref))?;
+        let is_gedcom_7 = self.is_gedcom_7();
+        match citation.description() {
+            Some(description) if !is_gedcom_7 => {
+                self.write_long_text(writer, level, "SOUR", description)?;
+            }
+            Some(_) => self.write_line(writer, level, "SOUR", Some("@VOID@"))?,
+            None => self.write_line(writer, level, "SOUR", Some(&citation.xref))?,
+        }
 
         if let Some(ref page) = citation.page {
             self.write_value_or_wrap(writer, level + 1, "PAGE", Some(page))?;
         }
 
-        if let Some(ref data) = citation.data {
-            self.write_line(writer, level + 1, "DATA", None)?;
-            if let Some(ref date) = data.date {
-                self.write_date(writer, level + 2, date)?;
+        if !is_gedcom_7 {
+            for text in &citation.texts {
+                self.write_text(writer, level + 1, text)?;
             }
-            if let Some(ref text) = data.text {
-                if let Some(ref text_value) = text.value {
-                    self.write_long_text(writer, level + 2, "TEXT", text_value)?;
+        }
+
+        let moved_texts: &[Text] = if is_gedcom_7 { &citation.texts } else { &[] };
+        if citation.data.is_some() || !moved_texts.is_empty() {
+            self.write_line(writer, level + 1, "DATA", None)?;
+            if let Some(ref data) = citation.data {
+                if let Some(ref date) = data.date {
+                    self.write_date(writer, level + 2, date)?;
                 }
+                for text in &data.texts {
+                    self.write_text(writer, level + 2, text)?;
+                }
+            }
+            for text in moved_texts {
+                self.write_text(writer, level + 2, text)?;
+            }
+        }
+
+        if let Some(ref event_type) = citation.event_type {
+            self.write_value_or_wrap(writer, level + 1, "EVEN", Some(event_type))?;
+            if let Some(phrase) = citation.event_phrase.as_ref().filter(|_| is_gedcom_7) {
+                self.write_value_or_wrap(writer, level + 2, "PHRASE", Some(phrase))?;
+            }
+            if let Some(ref role) = citation.role {
+                self.write_citation_role(writer, level + 2, role, citation.role_phrase.as_deref())?;
             }
         }
 
@@ -871,13 +1830,83 @@ impl GedcomWriter {
             }
         }
 
-        if let Some(ref note) = citation.note {
+        for media in &citation.multimedia {
+            self.write_multimedia_link(writer, level + 1, media)?;
+        }
+
+        if let Some(description) = citation.description().filter(|_| is_gedcom_7) {
+            self.write_long_text(writer, level + 1, "NOTE", description)?;
+        }
+        for note in &citation.notes {
             self.write_note(writer, level + 1, note)?;
         }
 
         Ok(())
     }
 
+    /// Writes the role of an individual in a cited event.
+    ///
+    /// GEDCOM 5.5.1 gives the roles it does not name in words, in brackets;
+    /// GEDCOM 7.0 as `OTHER` with a phrase.
+    fn write_citation_role<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        role: &Role,
+        phrase: Option<&str>,
+    ) -> Result<(), io::Error> {
+        let words = phrase.unwrap_or_else(|| role.as_str().trim_matches(['(', ')']));
+        if self.is_gedcom_7() {
+            let (value, phrase) = match role {
+                Role::Other(_) => ("OTHER", Some(words)),
+                _ => (role.as_str(), phrase),
+            };
+            self.write_line(writer, level, "ROLE", Some(value))?;
+            if let Some(phrase) = phrase {
+                self.write_value_or_wrap(writer, level + 1, "PHRASE", Some(phrase))?;
+            }
+        } else {
+            let named = matches!(
+                role,
+                Role::Child
+                    | Role::Husband
+                    | Role::Wife
+                    | Role::Mother
+                    | Role::Father
+                    | Role::Spouse
+            );
+            if named {
+                self.write_line(writer, level, "ROLE", Some(role.as_str()))?;
+            } else {
+                self.write_value_or_wrap(writer, level, "ROLE", Some(&format!("({words})")))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a `TEXT` from a source, with its media type and language in
+    /// GEDCOM 7.0.
+    fn write_text<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        text: &Text,
+    ) -> Result<(), io::Error> {
+        match text.value {
+            Some(ref value) => self.write_long_text(writer, level, "TEXT", value)?,
+            None => self.write_line(writer, level, "TEXT", None)?,
+        }
+        if self.is_gedcom_7() {
+            if let Some(ref mime) = text.mime {
+                self.write_value_or_wrap(writer, level + 1, "MIME", Some(mime))?;
+            }
+            if let Some(ref language) = text.language {
+                self.write_value_or_wrap(writer, level + 1, "LANG", Some(language))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Writes a date structure.
     fn write_date<W: Write>(
         &self,
@@ -901,6 +1930,23 @@ impl GedcomWriter {
         Ok(())
     }
 
+    /// Writes extension structures, and their substructures, as they were read.
+    fn write_custom_data<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        tags: &[Box<UserDefinedTag>],
+    ) -> Result<(), io::Error> {
+        if !self.writes_custom_tags() {
+            return Ok(());
+        }
+        for tag in tags {
+            self.write_value_or_wrap(writer, level, &tag.tag, tag.value.as_deref())?;
+            self.write_custom_data(writer, level + 1, &tag.children)?;
+        }
+        Ok(())
+    }
+
     /// Writes a schema structure (GEDCOM 7.0).
     fn write_schema<W: Write>(&self, writer: &mut W, schema: &Schema) -> Result<(), io::Error> {
         self.write_line(writer, 1, "SCHMA", None)?;
@@ -919,7 +1965,20 @@ impl GedcomWriter {
         writer: &mut W,
         note: &SharedNote,
     ) -> Result<(), io::Error> {
-        self.write_line_with_xref(writer, 0, note.xref.as_deref(), "SNOTE", Some(&note.text))?;
+        // Before GEDCOM 7.0, shared notes are `NOTE` records.
+        let xref = Some(note.xref.as_deref().unwrap_or("@X0@"));
+        if !self.is_gedcom_7() {
+            self.write_long_text_with_xref(writer, 0, xref, "NOTE", &note.text)?;
+            for citation in &note.source_citations {
+                self.write_citation(writer, 1, citation)?;
+            }
+            self.write_identifiers(writer, note.xref.as_deref(), note.uid.as_deref(), &[], &[])?;
+            if let Some(ref change_date) = note.change_date {
+                self.write_change_date(writer, "CHAN", change_date)?;
+            }
+            return Ok(());
+        }
+        self.write_long_text_with_xref(writer, 0, xref, "SNOTE", &note.text)?;
 
         if let Some(ref mime) = note.mime {
             self.write_value_or_wrap(writer, 1, "MIME", Some(mime))?;
@@ -939,17 +1998,57 @@ impl GedcomWriter {
             }
         }
 
+        // GEDCOM 7.0 orders citations before identifiers, then dates.
+        for citation in &note.source_citations {
+            self.write_citation(writer, 1, citation)?;
+        }
+
+        self.write_uid(writer, note.xref.as_deref(), note.uid.as_deref())?;
+
         for exid in &note.external_ids {
-            self.write_value_or_wrap(writer, 1, "EXID", Some(&exid.id))?;
-            if let Some(ref type_uri) = exid.type_uri {
-                self.write_value_or_wrap(writer, 2, "TYPE", Some(type_uri))?;
-            }
+            self.write_external_id(writer, exid)?;
         }
 
-        for citation in &note.source_citations {
-            self.write_citation(writer, 1, citation)?;
+        self.write_record_dates(
+            writer,
+            note.change_date.as_ref(),
+            note.creation_date.as_ref(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Writes the change date (`CHAN`) of a record, then its creation date
+    /// (`CREA`), which only GEDCOM 7.0 has.
+    fn write_record_dates<W: Write>(
+        &self,
+        writer: &mut W,
+        change_date: Option<&ChangeDate>,
+        creation_date: Option<&ChangeDate>,
+    ) -> Result<(), io::Error> {
+        if let Some(change_date) = change_date {
+            self.write_change_date(writer, "CHAN", change_date)?;
+        }
+        if let Some(creation_date) = creation_date.filter(|_| self.is_gedcom_7()) {
+            self.write_change_date(writer, "CREA", creation_date)?;
         }
+        Ok(())
+    }
 
+    /// Writes the change (`CHAN`) or creation (`CREA`) date of a record.
+    fn write_change_date<W: Write>(
+        &self,
+        writer: &mut W,
+        tag: &str,
+        change_date: &ChangeDate,
+    ) -> Result<(), io::Error> {
+        self.write_line(writer, 1, tag, None)?;
+        if let Some(ref date) = change_date.date {
+            self.write_date(writer, 2, date)?;
+        }
+        for note in &change_date.notes {
+            self.write_note(writer, 2, note)?;
+        }
         Ok(())
     }
 
@@ -960,8 +2059,15 @@ impl GedcomWriter {
         level: u8,
         sort_date: &SortDate,
     ) -> Result<(), io::Error> {
+        // GEDCOM 5.5.1 has no sort date; some dialects have their own tag.
+        let tag = match self.config.dialect {
+            _ if self.is_gedcom_7() => "SDATE",
+            Dialect::Standard => "SDATE",
+            dialect if dialect.quirks().sort_dates => "_SDATE",
+            _ => return Ok(()),
+        };
         if let Some(ref value) = sort_date.value {
-            self.write_value_or_wrap(writer, level, "SDATE", Some(value))?;
+            self.write_value_or_wrap(writer, level, tag, Some(value))?;
         }
 
         if let Some(ref time) = sort_date.time {
@@ -988,7 +2094,7 @@ impl GedcomWriter {
             self.write_date(writer, level + 1, date)?;
         }
 
-        if let Some(ref note) = non_event.note {
+        for note in &non_event.notes {
             self.write_note(writer, level + 1, note)?;
         }
 
@@ -1029,7 +2135,7 @@ impl GedcomWriter {
             self.write_line(writer, level + 1, "FAMC", Some(famc))?;
         }
 
-        if let Some(ref note) = ordinance.note {
+        for note in &ordinance.notes {
             self.write_note(writer, level + 1, note)?;
         }
 
@@ -1040,6 +2146,56 @@ impl GedcomWriter {
         Ok(())
     }
 
+    /// Writes the phone numbers, email addresses, fax numbers and websites
+    /// that follow an address, in that order.
+    fn write_contacts<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        contacts: [&[String]; 4],
+    ) -> Result<(), io::Error> {
+        for (tag, values) in ["PHON", "EMAIL", "FAX", "WWW"].into_iter().zip(contacts) {
+            for value in values {
+                self.write_value_or_wrap(writer, level, tag, Some(value))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the pedigree and the status of a link from a child to a family.
+    ///
+    /// GEDCOM 5.5.1 has no `OTHER` pedigree nor phrases; they are dropped.
+    fn write_child_link<W: Write>(
+        &self,
+        writer: &mut W,
+        link: &FamilyLink,
+    ) -> Result<(), io::Error> {
+        let gedcom_7 = self.is_gedcom_7();
+        let pedigree = link
+            .pedigree_linkage_type
+            .as_ref()
+            .filter(|p| gedcom_7 || **p != Pedigree::Other)
+            .map(pedigree_to_value);
+        let status = link.child_linkage_status.as_ref().map(status_to_value);
+        for (tag, value, phrase) in [
+            ("PEDI", pedigree, &link.pedigree_phrase),
+            ("STAT", status, &link.status_phrase),
+        ] {
+            let Some(value) = value else {
+                continue;
+            };
+            if gedcom_7 {
+                self.write_line(writer, 2, tag, Some(&value.to_uppercase()))?;
+                if let Some(ref phrase) = phrase {
+                    self.write_value_or_wrap(writer, 3, "PHRASE", Some(phrase))?;
+                }
+            } else {
+                self.write_line(writer, 2, tag, Some(value))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Writes an address structure.
     fn write_address<W: Write>(
         &self,
@@ -1080,8 +2236,25 @@ impl GedcomWriter {
         Ok(())
     }
 
-    /// Writes a note structure.
+    /// Writes a note structure, or a pointer to a shared note: `SNOTE` in
+    /// GEDCOM 7.0, `NOTE` before.
     fn write_note<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        note: &NoteRef,
+    ) -> Result<(), io::Error> {
+        match note {
+            NoteRef::Inline(note) => self.write_inline_note(writer, level, note),
+            NoteRef::Shared(xref) => {
+                let tag = if self.is_gedcom_7() { "SNOTE" } else { "NOTE" };
+                self.write_line(writer, level, tag, Some(xref))
+            }
+        }
+    }
+
+    /// Writes the text of a note structure and its substructures.
+    fn write_inline_note<W: Write>(
         &self,
         writer: &mut W,
         level: u8,
@@ -1093,6 +2266,29 @@ impl GedcomWriter {
             self.write_line(writer, level, "NOTE", None)?;
         }
 
+        // MIME, LANG and TRAN are GEDCOM 7.0 substructures.
+        if !self.is_gedcom_7() {
+            return Ok(());
+        }
+        if let Some(ref mime) = note.mime {
+            self.write_value_or_wrap(writer, level + 1, "MIME", Some(mime))?;
+        }
+        if let Some(ref lang) = note.language {
+            self.write_value_or_wrap(writer, level + 1, "LANG", Some(lang))?;
+        }
+        for translation in &note.translations {
+            match translation.value {
+                Some(ref value) => self.write_long_text(writer, level + 1, "TRAN", value)?,
+                None => self.write_line(writer, level + 1, "TRAN", None)?,
+            }
+            if let Some(ref mime) = translation.mime {
+                self.write_value_or_wrap(writer, level + 2, "MIME", Some(mime))?;
+            }
+            if let Some(ref lang) = translation.language {
+                self.write_value_or_wrap(writer, level + 2, "LANG", Some(lang))?;
+            }
+        }
+
         Ok(())
     }
 
@@ -1116,7 +2312,11 @@ impl GedcomWriter {
     ) -> Result<(), io::Error> {
         match value {
             None => self.write_line(writer, level, tag, None),
-            Some(v) if v.contains('\n') || v.len() > self.config.max_line_length => {
+            Some(v)
+                if v.contains('\n')
+                    || self.payload_width(&escape_line_value(v, self.is_gedcom_7()))
+                        > self.payload_budget(level, tag) =>
+            {
                 self.write_long_text(writer, level, tag, v)
             }
             Some(v) => self.write_line(writer, level, tag, Some(v)),
@@ -1140,7 +2340,8 @@ impl GedcomWriter {
 
         if let Some(v) = value {
             if !v.is_empty() {
-                write!(writer, " {v}").map_err(io_error)?;
+                write!(writer, " {}", escape_line_value(v, self.is_gedcom_7()))
+                    .map_err(io_error)?;
             }
         }
 
@@ -1165,7 +2366,8 @@ impl GedcomWriter {
 
         if let Some(v) = value {
             if !v.is_empty() {
-                write!(writer, " {v}").map_err(io_error)?;
+                write!(writer, " {}", escape_line_value(v, self.is_gedcom_7()))
+                    .map_err(io_error)?;
             }
         }
 
@@ -1175,6 +2377,10 @@ impl GedcomWriter {
     }
 
     /// Writes long text with CONC/CONT continuation lines.
+    ///
+    /// Each newline starts a `CONT` line and overlong lines are split with
+    /// `CONC`. Splits happen between grapheme clusters, so multi-byte
+    /// characters and combining sequences are never broken apart.
     fn write_long_text<W: Write>(
         &self,
         writer: &mut W,
@@ -1182,49 +2388,305 @@ impl GedcomWriter {
         tag: &str,
         text: &str,
     ) -> Result<(), io::Error> {
+        self.write_long_text_with_xref(writer, level, None, tag, text)
+    }
+
+    /// Writes long text like [`write_long_text`](Self::write_long_text),
+    /// with `xref` before the tag of the first line if there is one.
+    fn write_long_text_with_xref<W: Write>(
+        &self,
+        writer: &mut W,
+        level: u8,
+        xref: Option<&str>,
+        tag: &str,
+        text: &str,
+    ) -> Result<(), io::Error> {
+        let conc_budget = self.payload_budget(level + 1, "CONC");
+
         for (i, line) in text.split('\n').enumerate() {
             // Empty continuation lines must still be represented explicitly with `CONT` + an empty value.
             // `CONT` means “new line”, so dropping them would merge lines.
-            let line_value = Some(line);
-            if i == 0 {
-                // First line uses the main tag
-                if line.len() <= self.config.max_line_length {
-                    self.write_line(writer, level, tag, Some(line))?;
-                } else {
-                    // Need to split with CONC
-                    let first_part = &line[..self.config.max_line_length];
-                    self.write_line(writer, level, tag, Some(first_part))?;
-
-                    let mut remaining = &line[self.config.max_line_length..];
-                    while !remaining.is_empty() {
-                        let chunk_len = std::cmp::min(remaining.len(), self.config.max_line_length);
-                        let chunk = &remaining[..chunk_len];
-                        self.write_line(writer, level + 1, "CONC", Some(chunk))?;
-                        remaining = &remaining[chunk_len..];
-                    }
-                }
+            let (line_level, line_tag) = if i == 0 {
+                (level, tag)
             } else {
-                // Subsequent lines use CONT
-                if line.len() <= self.config.max_line_length {
-                    self.write_line(writer, level + 1, "CONT", line_value)?;
-                } else {
-                    // Split with CONT first, then CONC
-                    let first_part = &line[..self.config.max_line_length];
-                    self.write_line(writer, level + 1, "CONT", Some(first_part))?;
-
-                    let mut remaining = &line[self.config.max_line_length..];
-                    while !remaining.is_empty() {
-                        let chunk_len = std::cmp::min(remaining.len(), self.config.max_line_length);
-                        let chunk = &remaining[..chunk_len];
-                        self.write_line(writer, level + 1, "CONC", Some(chunk))?;
-                        remaining = &remaining[chunk_len..];
-                    }
+                (level + 1, "CONT")
+            };
+
+            let budget = match xref {
+                Some(xref) if i == 0 => self.payload_budget(line_level, &format!("{xref} {tag}")),
+                _ => self.payload_budget(line_level, line_tag),
+            };
+            let (head, mut remaining) = split_at_grapheme(
+                line,
+                budget,
+                self.is_gedcom_7(),
+                self.config.line_length_measure,
+            );
+            match xref {
+                Some(_) if i == 0 => {
+                    self.write_line_with_xref(writer, line_level, xref, line_tag, Some(head))?;
                 }
+                _ => self.write_line(writer, line_level, line_tag, Some(head))?,
+            }
+
+            while !remaining.is_empty() {
+                let (chunk, rest) = split_at_grapheme(
+                    remaining,
+                    conc_budget,
+                    self.is_gedcom_7(),
+                    self.config.line_length_measure,
+                );
+                self.write_line(writer, level + 1, "CONC", Some(chunk))?;
+                remaining = rest;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the `UID` of a record, if it has one or was assigned one.
+    /// GEDCOM 5.5.1 has no `UID`.
+    fn write_uid<W: Write>(
+        &self,
+        writer: &mut W,
+        xref: Option<&str>,
+        uid: Option<&str>,
+    ) -> Result<(), io::Error> {
+        if !self.is_gedcom_7() {
+            return Ok(());
+        }
+        let assigned = xref.and_then(|x| self.uids.get(x)).map(String::as_str);
+        if let Some(uid) = uid.or(assigned) {
+            self.write_value_or_wrap(writer, 1, "UID", Some(uid))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the identifiers of a record: the `UID` and `EXID`s in GEDCOM
+    /// 7.0, then the `_UID`s. GEDCOM 5.5.1 output carries the `UID` as a
+    /// `_UID` instead, so the identity of the record survives.
+    fn write_identifiers<W: Write>(
+        &self,
+        writer: &mut W,
+        xref: Option<&str>,
+        uid: Option<&str>,
+        vendor_uids: &[String],
+        external_ids: &[ExternalId],
+    ) -> Result<(), io::Error> {
+        if self.is_gedcom_7() {
+            self.write_uid(writer, xref, uid)?;
+            for exid in external_ids {
+                self.write_external_id(writer, exid)?;
+            }
+        } else if let Some(uid) = uid.filter(|uid| !vendor_uids.iter().any(|v| v == uid)) {
+            if self.writes_custom_tags() {
+                self.write_value_or_wrap(writer, 1, "_UID", Some(uid))?;
             }
         }
+        if self.writes_custom_tags() {
+            for uid in vendor_uids {
+                self.write_value_or_wrap(writer, 1, "_UID", Some(uid))?;
+            }
+        }
+        Ok(())
+    }
 
+    /// Writes an `EXID` and the authority that issued it.
+    fn write_external_id<W: Write>(
+        &self,
+        writer: &mut W,
+        exid: &ExternalId,
+    ) -> Result<(), io::Error> {
+        self.write_value_or_wrap(writer, 1, "EXID", Some(&exid.id))?;
+        if let Some(ref type_uri) = exid.type_uri {
+            self.write_value_or_wrap(writer, 2, "TYPE", Some(type_uri))?;
+        }
         Ok(())
     }
+
+    /// Returns the ordinances to write, none if they are excluded.
+    fn lds_ordinances<'a>(&self, ordinances: &'a [LdsOrdinance]) -> &'a [LdsOrdinance] {
+        if self.config.exclude_lds_ordinances {
+            &[]
+        } else {
+            ordinances
+        }
+    }
+
+    /// Returns true if the output follows GEDCOM 7.0 rules.
+    fn is_gedcom_7(&self) -> bool {
+        self.config.gedcom_version.starts_with('7')
+    }
+
+    /// Returns false if the dialect takes no custom structures.
+    fn writes_custom_tags(&self) -> bool {
+        !self.config.dialect.quirks().no_custom_data
+    }
+
+    /// Returns how much value fits on a line with the given level and tag.
+    ///
+    /// When `max_line_length` covers the whole line, the level, tag,
+    /// delimiters and line terminator are subtracted. At least one unit is
+    /// always allowed so that wrapping makes progress. GEDCOM 7.0 has no
+    /// `CONC`, so its budget is unlimited.
+    fn payload_budget(&self, level: u8, tag: &str) -> usize {
+        if self.is_gedcom_7() {
+            return usize::MAX;
+        }
+        if self.config.line_length_measure == LineLengthMeasure::PayloadBytes {
+            return self.config.max_line_length.max(1);
+        }
+        let overhead = level.to_string().len()
+            + tag.chars().count()
+            + 2
+            + self.config.line_ending.chars().count();
+        self.config.max_line_length.saturating_sub(overhead).max(1)
+    }
+
+    /// Returns the size of an already escaped value in the configured unit.
+    fn payload_width(&self, value: &str) -> usize {
+        match self.config.line_length_measure {
+            LineLengthMeasure::Line => value.chars().count(),
+            LineLengthMeasure::PayloadBytes => value.len(),
+        }
+    }
+}
+
+/// Writes GEDCOM data to the file at `path` in the encoding of `config`,
+/// UTF-8 if it sets none, with a header `CHAR` line that declares it.
+///
+/// The `CHAR` line of the data is replaced, so a file read as ANSEL and
+/// written as UTF-8 no longer claims to be ANSEL. UTF-16 output starts with
+/// the byte order mark readers need to detect it; the other encodings are
+/// written without one. GEDCOM 7.0 has no `CHAR` line and is always UTF-8.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ged_io::{write_file, GedcomBuilder, GedcomEncoding, WriterConfig};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR UTF-8\n0 @I1@ INDI\n1 NAME Zoë /Doe/\n0 TRLR";
+/// let data = GedcomBuilder::new().build_from_str(source)?;
+///
+/// let config = WriterConfig {
+///     encoding: Some(GedcomEncoding::Ansel),
+///     ..WriterConfig::default()
+/// };
+/// // Writes `1 CHAR ANSEL`, and the name in ANSEL.
+/// write_file("family.ged", &data, config)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns `GedcomError::EncodingError` if the data has characters the
+/// encoding cannot represent, or the output is GEDCOM 7.0, as the header of
+/// the data or else the config declares, in an encoding other than UTF-8,
+/// and `GedcomError::IoError` if the file
+/// cannot be written.
+pub fn write_file(
+    path: impl AsRef<std::path::Path>,
+    data: &GedcomData,
+    config: WriterConfig,
+) -> Result<(), GedcomError> {
+    let encoding = config.encoding.unwrap_or(GedcomEncoding::Utf8);
+    let writer = GedcomWriter {
+        config,
+        ..GedcomWriter::default()
+    }
+    .encoding(encoding);
+    if writer.for_data(data).is_gedcom_7() && encoding.char_value() != "UTF-8" {
+        return Err(GedcomError::EncodingError(format!(
+            "GEDCOM 7.0 files are UTF-8, not {encoding}"
+        )));
+    }
+    let bytes = encode_to_bytes(&writer.write_to_string(data)?, encoding)?;
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Splits `text` after at most `budget` characters or bytes, on a grapheme cluster boundary.
+///
+/// Text is measured as it will be written, so `@` signs that
+/// escaping doubles take two. Each part is escaped on its own when written.
+/// Readers drop the whitespace that would start a `CONC` value, so the split
+/// is moved back to avoid leaving a space at the front of the remainder. A
+/// single cluster longer than `budget` is kept whole rather than broken.
+fn split_at_grapheme(
+    text: &str,
+    budget: usize,
+    is_gedcom_7: bool,
+    measure: LineLengthMeasure,
+) -> (&str, &str) {
+    let mut chars = 0;
+    let mut last_safe = 0;
+    let mut split = text.len();
+
+    for (idx, grapheme) in text.grapheme_indices(true) {
+        let starts_with_space = grapheme.starts_with(char::is_whitespace);
+        let mut width = match measure {
+            LineLengthMeasure::Line => grapheme.chars().count(),
+            LineLengthMeasure::PayloadBytes => grapheme.len(),
+        };
+        if grapheme == "@" && (!is_gedcom_7 || idx == 0) {
+            width += 1;
+        }
+        if chars + width > budget && idx > 0 {
+            split = if starts_with_space && last_safe > 0 {
+                last_safe
+            } else {
+                idx
+            };
+            break;
+        }
+        if idx > 0 && !starts_with_space {
+            last_safe = idx;
+        }
+        chars += width;
+    }
+
+    text.split_at(split)
+}
+
+/// Assigns a new UUID to every record that has an xref but no UID.
+fn missing_uids(data: &GedcomData) -> HashMap<String, String> {
+    data.individuals
+        .iter()
+        .map(|i| (&i.xref, &i.uid))
+        .chain(data.families.iter().map(|f| (&f.xref, &f.uid)))
+        .chain(data.sources.iter().map(|s| (&s.xref, &s.uid)))
+        .chain(data.repositories.iter().map(|r| (&r.xref, &r.uid)))
+        .chain(data.submitters.iter().map(|s| (&s.xref, &s.uid)))
+        .chain(data.multimedia.iter().map(|m| (&m.xref, &m.uid)))
+        .chain(data.shared_notes.iter().map(|n| (&n.xref, &n.uid)))
+        .filter(|(_, uid)| uid.is_none())
+        .filter_map(|(xref, _)| xref.clone())
+        .map(|xref| (xref, random_uuid()))
+        .collect()
+}
+
+/// Lets the writer, which formats into `fmt::Write`, send bytes to an
+/// `io::Write`.
+///
+/// `fmt::Error` carries no detail, so the first I/O error is kept aside to
+/// be returned in its place.
+struct IoAdapter<W: io::Write> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> Write for IoAdapter<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.error.is_some() {
+            return Err(std::fmt::Error);
+        }
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            std::fmt::Error
+        })
+    }
 }
 
 /// Converts a `std::fmt::Error` to an `io::Error`.
@@ -1237,7 +2699,7 @@ fn io_error(_: std::fmt::Error) -> io::Error {
 // =============================================================================
 
 /// Converts an event type to its GEDCOM tag.
-fn event_to_tag(event: &Event) -> &'static str {
+pub(crate) fn event_to_tag(event: &Event) -> &'static str {
     match event {
         Event::Adoption => "ADOP",
         Event::Birth => "BIRT",
@@ -1277,8 +2739,20 @@ fn event_to_tag(event: &Event) -> &'static str {
     }
 }
 
+/// Returns true if the event tag takes the `Y` flag in the written version.
+///
+/// GEDCOM 5.5.1 allows it on `BIRT`, `CHR`, `DEAT` and `MARR` only; GEDCOM
+/// 7.0 on every event but `EVEN`, whose payload is text.
+fn takes_y_flag(event: &Event, gedcom_7: bool) -> bool {
+    match event {
+        Event::Birth | Event::Christening | Event::Death | Event::Marriage => true,
+        Event::Event | Event::Other | Event::Residence | Event::SourceData(_) => false,
+        _ => gedcom_7,
+    }
+}
+
 /// Converts an individual attribute type to its GEDCOM tag.
-fn attribute_to_tag(
+pub(crate) fn attribute_to_tag(
     attr: &crate::types::individual::attribute::IndividualAttribute,
 ) -> &'static str {
     use crate::types::individual::attribute::IndividualAttribute;
@@ -1311,6 +2785,26 @@ fn certainty_to_gedcom_value(certainty: &CertaintyAssessment) -> Option<&'static
     }
 }
 
+/// Converts a pedigree to its GEDCOM 5.5.1 value.
+fn pedigree_to_value(pedigree: &Pedigree) -> &'static str {
+    match pedigree {
+        Pedigree::Adopted => "adopted",
+        Pedigree::Birth => "birth",
+        Pedigree::Foster => "foster",
+        Pedigree::Sealing => "sealing",
+        Pedigree::Other => "other",
+    }
+}
+
+/// Converts a child link status to its GEDCOM 5.5.1 value.
+fn status_to_value(status: &ChildLinkStatus) -> &'static str {
+    match status {
+        ChildLinkStatus::Challenged => "challenged",
+        ChildLinkStatus::Disproven => "disproven",
+        ChildLinkStatus::Proven => "proven",
+    }
+}
+
 // =============================================================================
 // Helper trait implementation for family link type
 // =============================================================================
@@ -1399,6 +2893,120 @@ mod tests {
         assert!(output.contains("1 AUTH Test Author"));
     }
 
+    #[test]
+    fn test_write_repository_citations() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @S1@ SOUR\n1 REPO @R1@\n2 NOTE Reading room only\n2 CALN HO107/1\n3 MEDI Film\n\
+            1 REPO @R2@\n2 CALN 929.3\n3 MEDI Glass plate\n1 REPO @R3@\n\
+            0 @R1@ REPO\n1 NAME National Archives\n0 @R2@ REPO\n1 NAME Library\n\
+            0 @R3@ REPO\n1 NAME Museum\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains(
+            "1 REPO @R1@\n2 NOTE Reading room only\n2 CALN HO107/1\n3 MEDI film\n\
+            1 REPO @R2@\n2 CALN 929.3\n3 MEDI Glass plate\n1 REPO @R3@\n"
+        ));
+
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("2 CALN HO107/1\n3 MEDI FILM\n"));
+        assert!(output.contains("2 CALN 929.3\n3 MEDI OTHER\n4 PHRASE Glass plate\n"));
+        let mut reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        let citation = &reparsed.sources[0].repo_citations[1];
+        assert_eq!(citation.media_type.as_deref(), Some("OTHER"));
+        assert_eq!(citation.media_phrase.as_deref(), Some("Glass plate"));
+
+        reparsed.header = None;
+        let output = GedcomWriter::new().write_to_string(&reparsed).unwrap();
+        assert!(output.contains("2 CALN 929.3\n3 MEDI Glass plate\n"));
+    }
+
+    #[test]
+    fn test_write_adoption_family() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 BIRT\n2 FAMC @F1@\n\
+            1 ADOP\n2 FAMC @F2@\n3 ADOP BOTH\n4 PHRASE Adopted by the Smiths\n\
+            0 @F1@ FAM\n0 @F2@ FAM\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 BIRT\n2 FAMC @F1@\n"));
+        assert!(
+            output.contains("1 ADOP\n2 FAMC @F2@\n3 ADOP BOTH\n4 PHRASE Adopted by the Smiths\n")
+        );
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("5.5.1")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("2 FAMC @F2@\n3 ADOP BOTH\n"));
+        assert!(!output.contains("PHRASE"));
+    }
+
+    #[test]
+    fn test_write_contacts() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 GRAD\n2 ADDR Harvard University\n3 CITY Cambridge\n\
+            2 PHON +1-617-495-1000\n2 WWW https://www.harvard.edu\n\
+            1 RESI\n2 ADDR 12 Elm Street\n2 EMAIL john@example.com\n\
+            0 @R1@ REPO\n1 NAME Archives\n1 ADDR Kew\n1 PHON 020 8876 3444\n1 FAX 020 8878 8905\n\
+            0 @U1@ SUBM\n1 NAME Jane\n1 EMAIL jane@example.com\n1 EMAIL jane@example.org\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains(
+            "2 ADDR Harvard University\n3 CITY Cambridge\n\
+            2 PHON +1-617-495-1000\n2 WWW https://www.harvard.edu\n"
+        ));
+        assert!(output.contains("1 RESI\n2 ADDR 12 Elm Street\n2 EMAIL john@@example.com\n"));
+        assert!(output.contains("1 ADDR Kew\n1 PHON 020 8876 3444\n1 FAX 020 8878 8905\n"));
+        // GEDCOM 5.5.1 doubles every @ in a value.
+        assert!(output.contains("1 EMAIL jane@@example.com\n1 EMAIL jane@@example.org\n"));
+
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+        assert_eq!(reparsed.repositories, data.repositories);
+        assert_eq!(reparsed.submitters, data.submitters);
+    }
+
+    #[test]
+    fn test_write_source_record_data() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @S1@ SOUR\n1 DATA\n2 EVEN BIRT, DEAT\n3 DATE FROM 1820 TO 1870\n\
+            3 PLAC Salem, Essex, Massachusetts, USA\n2 AGNC Salem Town Clerk\n\
+            1 TITL Vital Records of Salem\n1 PUBL Essex Institute, 1916\n\
+            1 TEXT Born 3 May 1821\n1 REFN 1042\n2 TYPE Shelf\n1 RIN 7\n1 OBJE @M1@\n\
+            0 @M1@ OBJE\n1 FILE salem.jpg\n2 FORM jpg\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains(
+            "0 @S1@ SOUR\n1 DATA\n2 EVEN BIRT, DEAT\n3 DATE FROM 1820 TO 1870\n\
+            3 PLAC Salem, Essex, Massachusetts, USA\n2 AGNC Salem Town Clerk\n"
+        ));
+        assert!(output.contains("1 REFN 1042\n2 TYPE Shelf\n1 RIN 7\n1 OBJE @M1@\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.sources, data.sources);
+
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("1 REFN 1042\n2 TYPE Shelf\n1 OBJE @M1@\n"));
+    }
+
     #[test]
     fn test_custom_line_ending() {
         let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 TRLR";
@@ -1425,7 +3033,7 @@ mod tests {
         // Compare key data
         assert_eq!(data.individuals.len(), data2.individuals.len());
         assert_eq!(data.individuals[0].xref, data2.individuals[0].xref);
-        assert_eq!(data.individuals[0].name, data2.individuals[0].name);
+        assert_eq!(data.individuals[0].names, data2.individuals[0].names);
     }
 
     #[test]
@@ -1442,4 +3050,807 @@ mod tests {
         assert!(config.include_empty_fields);
         assert_eq!(config.gedcom_version, "5.5.1");
     }
+
+    #[test]
+    fn test_write_long_text_respects_char_and_grapheme_boundaries() {
+        let note =
+            "Ste\u{301}phane e\u{301}tait ne\u{301} a\u{300} Ko\u{308}ln, 日本語のテキスト 👨‍👩‍👧 fin";
+        let source = format!("0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NOTE {note}\n0 TRLR");
+        let data = GedcomBuilder::new().build_from_str(&source).unwrap();
+
+        let writer = GedcomWriter::new().max_line_length(16);
+        let output = writer.write_to_string(&data).unwrap();
+
+        for line in output
+            .lines()
+            .filter(|l| l.starts_with("1 NOTE") || l.starts_with("2 CONC"))
+        {
+            assert!(line.chars().count() < 16, "line too long: {line:?}");
+        }
+        assert!(!output.contains("CONC \u{301}"));
+        assert!(!output.contains("CONC \u{200d}"));
+        assert!(output.contains("2 CONC "));
+
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(
+            reparsed.individuals[0].notes[0]
+                .inline()
+                .unwrap()
+                .value
+                .as_deref(),
+            Some(note)
+        );
+    }
+
+    #[test]
+    fn test_write_long_text_accounts_for_line_overhead() {
+        let note = "a".repeat(40);
+        let source = format!("0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NOTE {note}\n0 TRLR");
+        let data = GedcomBuilder::new().build_from_str(&source).unwrap();
+
+        // "1 NOTE " plus the terminator leaves 12 characters of payload.
+        let writer = GedcomWriter::new().max_line_length(20);
+        let output = writer.write_to_string(&data).unwrap();
+
+        assert!(output.contains(&format!("1 NOTE {}\n", "a".repeat(12))));
+        assert!(output.contains(&format!("2 CONC {}\n", "a".repeat(12))));
+        assert!(output.contains(&format!("2 CONC {}\n", "a".repeat(4))));
+    }
+
+    #[test]
+    fn test_write_family_event_spouse_ages() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n1 MARR\n2 DATE 1 JUN 1900\n2 HUSB\n3 AGE 25\n2 WIFE\n3 AGE 22\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let writer = GedcomWriter::new();
+        let output = writer.write_to_string(&data).unwrap();
+
+        assert!(output.contains("1 MARR\n2 DATE 1 JUN 1900\n2 HUSB\n3 AGE 25\n2 WIFE\n3 AGE 22\n"));
+
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(
+            reparsed.families[0].events[0].family_event_details,
+            data.families[0].events[0].family_event_details
+        );
+        assert_eq!(reparsed.families[0].individual1.as_deref(), Some("@I1@"));
+    }
+
+    #[test]
+    fn test_writer_profiles_wrap_by_payload_bytes() {
+        let note = "é".repeat(60);
+        let source = format!("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NOTE {note}\n0 TRLR");
+        let data = GedcomBuilder::new().build_from_str(&source).unwrap();
+
+        // 60 two-byte characters: one line at 255 bytes, two at 90.
+        let output = GedcomWriter::with_profile(WriterProfile::Gedcom551)
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains(&format!("1 NOTE {note}\n")));
+
+        let output = GedcomWriter::with_profile(WriterProfile::Compat)
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains(&format!(
+            "1 NOTE {}\n2 CONC {}\n",
+            "é".repeat(45),
+            "é".repeat(15)
+        )));
+    }
+
+    #[test]
+    fn test_writer_profile_gedcom_7_never_uses_conc() {
+        let note = "a".repeat(400);
+        let source =
+            format!("0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NOTE {note}\n2 CONT next\n0 TRLR");
+        let data = GedcomBuilder::new().build_from_str(&source).unwrap();
+
+        let writer = GedcomWriter::with_profile(WriterProfile::Gedcom70).max_line_length(20);
+        let output = writer.write_to_string(&data).unwrap();
+
+        assert!(!output.contains("CONC"));
+        assert!(output.contains(&format!("1 NOTE {note}\n2 CONT next\n")));
+    }
+
+    #[test]
+    fn test_write_multimedia_link_crop() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 OBJE @M1@\n2 CROP\n3 TOP 10\n3 WIDTH 40.5\n2 TITL Face\n0 @M1@ OBJE\n1 FILE a.jpg\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 OBJE @M1@\n2 CROP\n3 TOP 10\n3 WIDTH 40.5\n2 TITL Face\n"));
+
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(
+            reparsed.individuals[0].multimedia,
+            data.individuals[0].multimedia
+        );
+    }
+
+    #[test]
+    fn test_write_multimedia_files() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 OBJE @M1@\n2 _PRIM Y\n\
+            1 OBJE\n2 FILE scan.png\n3 FORM png\n4 MEDI Document\n2 TITL Scan\n\
+            0 @M1@ OBJE\n1 FILE front.jpg\n2 FORM jpg\n3 TYPE photo\n2 TITL Front\n\
+            1 FILE back.jpg\n2 FORM jpg\n2 TITL Back\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 OBJE @M1@\n2 _PRIM Y\n"));
+        assert!(
+            output.contains("1 OBJE\n2 FILE scan.png\n3 FORM png\n4 MEDI Document\n2 TITL Scan\n")
+        );
+        assert!(output.contains(
+            "0 @M1@ OBJE\n1 FILE front.jpg\n2 FORM jpg\n3 TYPE photo\n2 TITL Front\n\
+            1 FILE back.jpg\n2 FORM jpg\n2 TITL Back\n"
+        ));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.multimedia, data.multimedia);
+        assert_eq!(reparsed.individuals, data.individuals);
+
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("1 FILE front.jpg\n2 FORM jpg\n3 MEDI PHOTO\n2 TITL Front\n"));
+        assert!(output.contains("3 FORM png\n4 MEDI OTHER\n5 PHRASE Document\n"));
+    }
+
+    #[test]
+    fn test_generate_uids_for_gedcom_7() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 UID 1f3c4d5e-0000-4000-8000-000000000001\n\
+            0 @I2@ INDI\n\
+            0 @F1@ FAM\n\
+            1 HUSB @I1@\n\
+            0 @N1@ SNOTE Shared\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let mut output = String::new();
+        let uids = GedcomWriter::new()
+            .generate_uids(true)
+            .write_to_with_uids(&mut output, &data)
+            .unwrap();
+
+        assert_eq!(
+            uids.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["@F1@", "@I2@", "@N1@"]
+        );
+        assert!(output.contains("0 @I1@ INDI\n1 UID 1f3c4d5e-0000-4000-8000-000000000001\n"));
+        assert!(output.contains(&format!("0 @I2@ INDI\n1 UID {}\n", uids["@I2@"])));
+
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.families[0].uid.as_ref(), Some(&uids["@F1@"]));
+
+        // Without the option, or for 5.5.1 output, nothing is assigned.
+        let mut output = String::new();
+        let uids = GedcomWriter::new()
+            .write_to_with_uids(&mut output, &data)
+            .unwrap();
+        assert!(uids.is_empty());
+        assert_eq!(output.matches("UID").count(), 1);
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let mut output = String::new();
+        let uids = GedcomWriter::new()
+            .generate_uids(true)
+            .write_to_with_uids(&mut output, &data)
+            .unwrap();
+        assert!(uids.is_empty());
+        assert!(!output.contains("UID"));
+    }
+
+    #[test]
+    fn test_write_to_io() {
+        /// A pipe whose reader has gone away.
+        struct ClosedPipe;
+        impl io::Write for ClosedPipe {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n1 NAME John /Doe/\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let writer = GedcomWriter::new();
+
+        let mut bytes = Vec::new();
+        writer.write_to_io(&mut bytes, &data).unwrap();
+        assert_eq!(bytes, writer.write_to_string(&data).unwrap().into_bytes());
+
+        let err = writer.write_to_io(ClosedPipe, &data).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn test_write_note_translations() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 NOTE Born at sea\n\
+            2 LANG en\n\
+            2 TRAN Auf See\n\
+            3 CONT geboren\n\
+            3 LANG de\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains(
+            "1 NOTE Born at sea\n\
+             2 LANG en\n\
+             2 TRAN Auf See\n\
+             3 CONT geboren\n\
+             3 LANG de\n"
+        ));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals[0].notes, data.individuals[0].notes);
+    }
+
+    #[test]
+    fn test_write_shared_note_in_gedcom_7_order() {
+        let source = "\
+            0 HEAD\n\
+            1 GEDC\n\
+            2 VERS 7.0\n\
+            0 @N1@ SNOTE Gordon is a Scottish surname.\n\
+            1 CREA\n\
+            2 DATE 1 JAN 2020\n\
+            1 EXID 123\n\
+            2 TYPE https://example.com/notes/\n\
+            1 TRAN Gordon est un nom écossais.\n\
+            2 LANG fr\n\
+            1 CHAN\n\
+            2 DATE 2 FEB 2021\n\
+            3 TIME 10:00:00\n\
+            1 SOUR @S1@\n\
+            1 MIME text/plain\n\
+            0 @S1@ SOUR\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let note = &data.shared_notes[0];
+        assert_eq!(note.translations[0].language.as_deref(), Some("fr"));
+        assert_eq!(note.mime.as_deref(), Some("text/plain"));
+        assert_eq!(
+            note.external_ids[0].type_uri.as_deref(),
+            Some("https://example.com/notes/")
+        );
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains(
+            "0 @N1@ SNOTE Gordon is a Scottish surname.\n\
+             1 MIME text/plain\n\
+             1 TRAN Gordon est un nom écossais.\n\
+             2 LANG fr\n\
+             1 SOUR @S1@\n\
+             1 EXID 123\n\
+             2 TYPE https://example.com/notes/\n\
+             1 CHAN\n\
+             2 DATE 2 FEB 2021\n\
+             3 TIME 10:00:00\n\
+             1 CREA\n\
+             2 DATE 1 JAN 2020\n"
+        ));
+
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.shared_notes, data.shared_notes);
+    }
+
+    #[test]
+    fn test_write_child_relationships() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 FAMC @F1@\n\
+            0 @F1@ FAM\n1 CHIL @I1@\n2 _FREL Adopted\n2 _MREL Step\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 FAMC @F1@\n2 PEDI adopted\n"));
+        assert!(output.contains("1 CHIL @I1@\n2 _FREL Adopted\n2 _MREL Step\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.families, data.families);
+        assert_eq!(reparsed.individuals, data.individuals);
+
+        let mut v7 = data.clone();
+        v7.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&v7)
+            .unwrap();
+        assert!(output.contains("1 FAMC @F1@\n2 PEDI ADOPTED\n"));
+        assert!(output.contains("1 CHIL @I1@\n2 _MREL Step\n0 "));
+    }
+
+    #[test]
+    fn test_write_child_link_status() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 FAMC @F1@\n2 PEDI FOSTER\n2 STAT PROVEN\n3 PHRASE DNA match\n\
+            2 NOTE Placed in 1931\n\
+            1 FAMC @F2@\n2 PEDI OTHER\n3 PHRASE Raised by an aunt\n\
+            0 @F1@ FAM\n0 @F2@ FAM\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains(
+            "1 FAMC @F1@\n2 PEDI FOSTER\n2 STAT PROVEN\n3 PHRASE DNA match\n\
+            2 NOTE Placed in 1931\n1 FAMC @F2@\n2 PEDI OTHER\n3 PHRASE Raised by an aunt\n"
+        ));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("5.5.1")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains(
+            "1 FAMC @F1@\n2 PEDI foster\n2 STAT proven\n2 NOTE Placed in 1931\n1 FAMC @F2@\n0 "
+        ));
+    }
+
+    #[test]
+    fn test_write_event_values() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 BIRT Y\n1 BURI Y\n1 EVEN Coronation\n2 TYPE Ceremony\n1 CENS\n\
+            0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let values: Vec<&EventValue> = data.individuals[0]
+            .events
+            .iter()
+            .map(|e| &e.value)
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                &EventValue::Asserted,
+                &EventValue::Asserted,
+                &EventValue::Description("Coronation".to_string()),
+                &EventValue::None,
+            ]
+        );
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 BIRT Y\n1 BURI\n1 EVEN Coronation\n"));
+
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("1 BIRT Y\n1 BURI Y\n1 EVEN Coronation\n"));
+        assert!(output.contains("1 CENS\n"));
+    }
+
+    #[test]
+    fn test_exclude_lds_ordinances() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n1 BAPL\n2 DATE 1900\n2 TEMP SLAKE\n1 FAMS @F1@\n\
+            0 @F1@ FAM\n1 HUSB @I1@\n1 SLGS\n2 DATE 1901\n\
+            0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 BAPL\n") && output.contains("1 SLGS\n"));
+
+        let output = GedcomWriter::new()
+            .exclude_lds_ordinances(true)
+            .write_to_string(&data)
+            .unwrap();
+        assert!(!output.contains("BAPL") && !output.contains("SLGS"));
+        assert_eq!(data.individuals[0].lds_ordinances.len(), 1);
+
+        let skipped = GedcomBuilder::new()
+            .skip_lds_ordinances(true)
+            .build_from_str(source)
+            .unwrap();
+        assert!(skipped.individuals[0].lds_ordinances.is_empty());
+        assert!(skipped.families[0].lds_ordinances.is_empty());
+        assert_eq!(
+            GedcomWriter::new().write_to_string(&skipped).unwrap(),
+            output
+        );
+    }
+
+    #[test]
+    fn test_write_creation_date() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 CHAN\n2 DATE 2 JAN 2024\n1 CREA\n2 DATE 1 JAN 2020\n\
+            0 @F1@ FAM\n1 CREA\n2 DATE 1 JAN 2021\n\
+            0 @S1@ SOUR\n1 CHAN\n2 DATE 5 MAR 2024\n3 TIME 14:03:07\n1 CREA\n2 DATE 1 FEB 2021\n\
+            0 @R1@ REPO\n1 NAME Archives\n1 CHAN\n2 DATE 6 MAR 2024\n1 CREA\n2 DATE 1 MAR 2021\n\
+            0 @M1@ OBJE\n1 FILE a.jpg\n2 FORM image/jpeg\n1 CHAN\n2 DATE 7 MAR 2024\n\
+            0 @U1@ SUBM\n1 NAME Jane\n1 CREA\n2 DATE 1 APR 2021\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert!(data.individuals[0].creation_date.is_some());
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 CREA\n2 DATE 1 JAN 2020\n"));
+        assert!(output.contains("1 CREA\n2 DATE 1 JAN 2021\n"));
+        assert!(output
+            .contains("1 CHAN\n2 DATE 5 MAR 2024\n3 TIME 14:03:07\n1 CREA\n2 DATE 1 FEB 2021\n"));
+        assert!(output.contains("1 CHAN\n2 DATE 6 MAR 2024\n1 CREA\n2 DATE 1 MAR 2021\n"));
+        assert!(output.contains("1 CHAN\n2 DATE 7 MAR 2024\n"));
+        assert!(output.contains("1 CREA\n2 DATE 1 APR 2021\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.sources, data.sources);
+        assert_eq!(reparsed.repositories, data.repositories);
+        assert_eq!(reparsed.multimedia, data.multimedia);
+        assert_eq!(reparsed.submitters, data.submitters);
+
+        // Without the header, the default version 5.5.1 is written.
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 CHAN\n") && !output.contains("CREA"));
+    }
+
+    #[test]
+    fn test_write_associations() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 ASSO @I2@\n2 ROLE GODP\n3 PHRASE Godmother\n2 SOUR @S1@\n\
+            1 BAPM\n2 ASSO @I3@\n3 ROLE WITN\n\
+            0 @F1@ FAM\n1 ASSO @I3@\n2 ROLE OFFICIATOR\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 ASSO @I2@\n2 ROLE GODP\n3 PHRASE Godmother\n2 SOUR @S1@\n"));
+        assert!(output.contains("1 BAPM\n2 ASSO @I3@\n3 ROLE WITN\n"));
+        assert!(output.contains("0 @F1@ FAM\n1 ASSO @I3@\n2 ROLE OFFICIATOR\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+        assert_eq!(reparsed.families, data.families);
+
+        // GEDCOM 5.5.1 describes the relationship in words, on individuals only.
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 ASSO @I2@\n2 RELA Godmother\n2 SOUR @S1@\n"));
+        assert_eq!(output.matches("ASSO").count(), 1);
+
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 ASSO @I2@\n2 RELA Best man\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("1 ASSO @I2@\n2 ROLE OTHER\n3 PHRASE Best man\n"));
+    }
+
+    #[test]
+    fn test_write_individual_identifiers() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 ALIA @I2@\n1 ANCI @U1@\n1 DESI @U1@\n1 AFN 12AB-3C\n\
+            1 REFN 1042\n2 TYPE Card index\n1 REFN B-7\n1 RIN 77\n0 @I2@ INDI\n0 @U1@ SUBM\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains(
+            "1 ALIA @I2@\n1 ANCI @U1@\n1 DESI @U1@\n1 AFN 12AB-3C\n\
+            1 REFN 1042\n2 TYPE Card index\n1 REFN B-7\n1 RIN 77\n"
+        ));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+
+        let mut data = data;
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("1 REFN 1042\n2 TYPE Card index\n"));
+        assert!(!output.contains("AFN") && !output.contains("RIN"));
+    }
+
+    #[test]
+    fn test_write_names() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n\
+            1 NAME Мария /Иванова/\n2 TYPE BIRTH\n2 GIVN Мария\n2 NICK Маша\n\
+            2 FONE Mariya /Ivanova/\n3 TYPE romaji\n\
+            2 TRAN Maria /Ivanova/\n3 LANG en-Latn\n3 GIVN Maria\n\
+            1 NAME Mary /Smith/\n2 TYPE MARRIED\n\
+            1 NAME Queen of Spades\n2 TYPE OTHER\n3 PHRASE Stage name\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        data.header = None;
+
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains(
+            "1 NAME Мария /Иванова/\n2 TYPE BIRTH\n2 GIVN Мария\n2 NICK Маша\n\
+            2 FONE Mariya /Ivanova/\n3 TYPE romaji\n\
+            2 TRAN Maria /Ivanova/\n3 LANG en-Latn\n3 GIVN Maria\n\
+            1 NAME Mary /Smith/\n2 TYPE MARRIED\n\
+            1 NAME Queen of Spades\n2 TYPE OTHER\n3 PHRASE Stage name\n"
+        ));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+
+        // GEDCOM 5.5.1 has no translations, and takes the phrase as the type.
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(!output.contains("TRAN"));
+        assert!(output.contains("1 NAME Queen of Spades\n2 TYPE Stage name\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals[0].names.len(), 3);
+        assert_eq!(
+            reparsed.individuals[0].names[2].name_type,
+            Some(NameType::Other("Stage name".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_write_repeated_notes() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NAME John /Doe/\n2 NOTE Spelled Jon in the census\n2 NOTE Nickname Jack\n\
+            1 BIRT\n2 DATE 1900\n2 NOTE Midwife's account\n2 NOTE Parish register\n\
+            2 SOUR @S1@\n3 NOTE Faded\n3 NOTE Second hand\n\
+            1 FAMC @F1@\n2 NOTE Raised by an aunt\n\
+            1 NOTE First note\n1 NOTE Second note\n\
+            1 CHAN\n2 DATE 1 JAN 2020\n2 NOTE Merged duplicates\n\
+            0 @F1@ FAM\n0 @S1@ SOUR\n1 TITL Register\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let john = &data.individuals[0];
+        assert_eq!(john.notes.len(), 2);
+        assert_eq!(john.names[0].notes.len(), 2);
+        assert_eq!(john.events[0].notes.len(), 2);
+        assert_eq!(john.events[0].citations[0].notes.len(), 2);
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("1 NOTE First note\n1 NOTE Second note\n"));
+        assert!(output.contains("3 NOTE Faded\n3 NOTE Second hand\n"));
+        assert!(output.contains("2 NOTE Raised by an aunt\n"));
+        assert!(output.contains("2 NOTE Merged duplicates\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+    }
+
+    #[test]
+    fn test_write_shared_note_pointers() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 NOTE @N1@\n1 BIRT\n2 NOTE @N1@\n2 NOTE Inline\n\
+            0 @N1@ NOTE Emigrated in 1850\n1 CONT with their parents\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert!(!data.is_gedcom_7());
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("0 @N1@ NOTE Emigrated in 1850\n1 CONT with their parents\n"));
+        assert!(output.contains("1 NOTE @N1@\n"));
+        assert!(output.contains("2 NOTE @N1@\n2 NOTE Inline\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+        assert_eq!(reparsed.shared_notes, data.shared_notes);
+
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("0 @N1@ SNOTE Emigrated in 1850\n1 CONT with their parents\n"));
+        assert!(output.contains("1 SNOTE @N1@\n"));
+        assert!(output.contains("2 SNOTE @N1@\n2 NOTE Inline\n"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+        assert_eq!(reparsed.shared_notes, data.shared_notes);
+    }
+
+    #[test]
+    fn test_write_source_citation_substructures() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n\
+            0 @I1@ INDI\n1 BIRT\n\
+            2 SOUR @S1@\n3 PAGE Entry 12\n3 EVEN BIRT\n4 ROLE (Godmother)\n\
+            3 DATA\n4 TEXT Born to John\n4 TEXT and Mary\n3 OBJE @M1@\n\
+            1 SOUR Letter from my aunt\n2 CONT dated 1950\n2 TEXT Born in Oslo\n\
+            0 @S1@ SOUR\n1 TITL Register\n0 @M1@ OBJE\n1 FILE scan.jpg\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("3 EVEN BIRT\n4 ROLE (Godmother)\n"));
+        assert!(output.contains("4 TEXT Born to John\n4 TEXT and Mary\n"));
+        assert!(output.contains("3 OBJE @M1@\n"));
+        assert!(
+            output.contains("1 SOUR Letter from my aunt\n2 CONT dated 1950\n2 TEXT Born in Oslo\n")
+        );
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+
+        data.header = None;
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("3 EVEN BIRT\n4 ROLE OTHER\n5 PHRASE Godmother\n"));
+        assert!(output.contains(
+            "1 SOUR @VOID@\n2 DATA\n3 TEXT Born in Oslo\n\
+             2 NOTE Letter from my aunt\n3 CONT dated 1950\n"
+        ));
+    }
+
+    #[test]
+    fn test_write_record_identifiers() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n\
+            0 @I1@ INDI\n1 UID 1f3c4d5e-0000-4000-8000-000000000001\n\
+            1 EXID 9ABC-DEF\n2 TYPE https://www.familysearch.org/tree/person/\n\
+            1 _UID 4F2A3C1D9E8B4A7C8D6E5F4A3B2C1D0E1234\n\
+            0 @F1@ FAM\n1 _UID 0C8A9B7D6E5F4A3B2C1D0E9F8A7B6C5D4321\n\
+            0 @S1@ SOUR\n1 TITL Register\n1 EXID 77\n\
+            0 @M1@ OBJE\n1 FILE photo.jpg\n2 FORM image/jpeg\n\
+            1 UID 1f3c4d5e-0000-4000-8000-000000000002\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        assert_eq!(data.multimedia[0].uids().len(), 1);
+        data.header = None;
+
+        let output = GedcomWriter::new()
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains(
+            "1 UID 1f3c4d5e-0000-4000-8000-000000000001\n\
+            1 EXID 9ABC-DEF\n2 TYPE https://www.familysearch.org/tree/person/\n\
+            1 _UID 4F2A3C1D9E8B4A7C8D6E5F4A3B2C1D0E1234\n"
+        ));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(reparsed.individuals, data.individuals);
+        assert_eq!(reparsed.families[0].uids(), data.families[0].uids());
+        assert_eq!(
+            reparsed.sources[0].external_ids,
+            data.sources[0].external_ids
+        );
+        assert_eq!(reparsed.multimedia[0].uid, data.multimedia[0].uid);
+
+        // GEDCOM 5.5.1 has no UID or EXID; the UID is kept as a _UID.
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(!output.contains("EXID") && !output.contains("1 UID"));
+        let reparsed = GedcomBuilder::new().build_from_str(&output).unwrap();
+        assert_eq!(
+            reparsed.individuals[0].uids(),
+            vec![
+                "1f3c4d5e-0000-4000-8000-000000000001",
+                "4F2A3C1D9E8B4A7C8D6E5F4A3B2C1D0E1234"
+            ]
+        );
+        assert_eq!(reparsed.multimedia[0].uids(), data.multimedia[0].uids());
+    }
+
+    #[test]
+    fn test_write_dialects() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR ANSEL\n\
+            0 @I1@ INDI\n1 NAME Jane /Doe/\n1 NAME Jane /Smith/\n2 TYPE married\n\
+            1 EVEN Navy\n2 TYPE Military\n2 DATE 1942\n1 BIRT\n2 DATE BEF 1920\n2 SDATE 1919\n\
+            1 _UID 1234\n0 @F1@ FAM\n1 CHIL @I1@\n2 _FREL Adopted\n0 TRLR";
+        let mut data = GedcomBuilder::new().build_from_str(source).unwrap();
+        data.header = None;
+
+        let output = GedcomWriter::new()
+            .dialect(Dialect::FamilyTreeMaker)
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("1 CHAR UTF-8\r\n"));
+        assert!(output.contains("1 NAME Jane /Doe/\r\n2 _MARNM Smith\r\n1 _MILT Navy\r\n"));
+        assert!(output.contains("1 _MILT Navy\r\n2 DATE 1942\r\n1 BIRT"));
+        // Family Tree Maker has no sort date tag.
+        assert!(!output.contains("SDATE"));
+
+        let reparsed = GedcomBuilder::new()
+            .dialect(Dialect::FamilyTreeMaker)
+            .build_from_str(&output)
+            .unwrap();
+        let jane = &reparsed.individuals[0];
+        assert_eq!(jane.names[1].value.as_deref(), Some("Jane /Smith/"));
+        assert_eq!(jane.events.len(), 2);
+        assert_eq!(jane.events[1].event_type.as_deref(), Some("Military"));
+
+        let output = GedcomWriter::new()
+            .dialect(Dialect::RootsMagic)
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("2 _SDATE 1919\r\n"));
+        assert!(output.contains("1 EVEN Navy\r\n2 DATE 1942\r\n2 TYPE Military\r\n"));
+
+        let output = GedcomWriter::new()
+            .dialect(Dialect::FamilySearch)
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("1 NAME Jane /Smith/\n2 TYPE MARRIED\n"));
+        assert!(!output.contains("SDATE"));
+        assert!(!output.contains("_UID"));
+        assert!(!output.contains("_FREL"));
+
+        let output = GedcomWriter::new()
+            .dialect(Dialect::FamilySearch)
+            .gedcom_version("7.0")
+            .write_to_string(&data)
+            .unwrap();
+        assert!(output.contains("2 SDATE 1919\n"));
+
+        let output = GedcomWriter::new().write_to_string(&data).unwrap();
+        assert!(output.contains("2 SDATE 1919\n"));
+        assert!(output.contains("2 _FREL Adopted\n"));
+    }
+
+    #[test]
+    fn test_write_file_encoding() {
+        let source = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR ANSEL\n\
+            0 @I1@ INDI\n1 NAME Zoë /Doe/\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let path = std::env::temp_dir().join("ged_io_write_file.ged");
+
+        write_file(&path, &data, WriterConfig::default()).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.contains("1 CHAR UTF-8\n"));
+        assert!(text.contains("1 NAME Zoë /Doe/\n"));
+
+        let config = WriterConfig {
+            encoding: Some(GedcomEncoding::Utf16Le),
+            ..WriterConfig::default()
+        };
+        write_file(&path, &data, config).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes[..2], [0xFF, 0xFE]);
+        let (text, encoding) = crate::decode_gedcom_bytes(&bytes).unwrap();
+        assert_eq!(encoding, GedcomEncoding::Utf16Le);
+        assert!(text.contains("1 CHAR UNICODE\n"));
+
+        let config = WriterConfig {
+            encoding: Some(GedcomEncoding::Ansel),
+            ..WriterConfig::default()
+        };
+        write_file(&path, &data, config).unwrap();
+        let data = GedcomBuilder::new().build_from_path(&path).unwrap();
+        let header = data.header.as_ref().unwrap();
+        let encoding = header.encoding.as_ref().unwrap();
+        assert_eq!(encoding.value.as_deref(), Some("ANSEL"));
+        // ANSEL stores the diaeresis as a combining mark.
+        let name = data.individuals[0].full_name().unwrap();
+        assert_eq!(name, "Zoe\u{308} Doe");
+
+        let config = WriterConfig {
+            encoding: Some(GedcomEncoding::Ascii),
+            ..WriterConfig::default()
+        };
+        assert!(matches!(
+            write_file(&path, &data, config),
+            Err(GedcomError::EncodingError(_))
+        ));
+        // Without a header, the version comes from the config.
+        let mut headerless = data.clone();
+        headerless.header = None;
+        let config = WriterConfig {
+            encoding: Some(GedcomEncoding::Ansel),
+            ..WriterProfile::Gedcom70.config()
+        };
+        assert!(matches!(
+            write_file(&path, &headerless, config),
+            Err(GedcomError::EncodingError(_))
+        ));
+        // The header version is the one written, whatever the config says.
+        let source = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NAME Zoë /Doe/\n0 TRLR";
+        let data = GedcomBuilder::new().build_from_str(source).unwrap();
+        let config = WriterConfig {
+            encoding: Some(GedcomEncoding::Ansel),
+            ..WriterConfig::default()
+        };
+        assert!(matches!(
+            write_file(&path, &data, config),
+            Err(GedcomError::EncodingError(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
 }
@@ -67,10 +67,7 @@ fn test_default_parsing_behavior_unchanged() {
     // Verify individual data
     let individual = &data.individuals[0];
     assert_eq!(individual.xref.as_ref().unwrap(), "@I1@");
-    assert_eq!(
-        individual.name.as_ref().unwrap().value.as_ref().unwrap(),
-        "John /Doe/"
-    );
+    assert_eq!(individual.names[0].value.as_ref().unwrap(), "John /Doe/");
 }
 
 #[test]
@@ -124,10 +121,7 @@ fn test_individual_access_pattern_unchanged() {
     // Old access patterns must still work
     let indi = &data.individuals[0];
     assert_eq!(indi.xref.as_ref().unwrap(), "@PERSON1@");
-    assert_eq!(
-        indi.name.as_ref().unwrap().value.as_ref().unwrap(),
-        "John Doe"
-    );
+    assert_eq!(indi.names[0].value.as_ref().unwrap(), "John Doe");
     assert_eq!(indi.sex.as_ref().unwrap().value.to_string(), "Male");
 }
 
@@ -223,7 +217,7 @@ fn test_both_apis_produce_same_results() {
     // Individual data should match
     for (old, new) in data_old.individuals.iter().zip(data_new.individuals.iter()) {
         assert_eq!(old.xref, new.xref);
-        assert_eq!(old.name, new.name);
+        assert_eq!(old.names, new.names);
         assert_eq!(old.sex, new.sex);
     }
 
@@ -34,13 +34,7 @@ mod tests {
 
         // names
         assert_eq!(
-            data.individuals[0]
-                .name
-                .as_ref()
-                .unwrap()
-                .value
-                .as_ref()
-                .unwrap(),
+            data.individuals[0].names[0].value.as_ref().unwrap(),
             "/Father/"
         );
 
@@ -86,13 +80,7 @@ mod tests {
 
         // names
         assert_eq!(
-            data.individuals[0]
-                .name
-                .as_ref()
-                .unwrap()
-                .value
-                .as_ref()
-                .unwrap(),
+            data.individuals[0].names[0].value.as_ref().unwrap(),
             "George /Washington/"
         );
 
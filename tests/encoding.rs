@@ -34,7 +34,7 @@ fn test_parse_utf8_without_bom() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /García/");
 }
 
@@ -47,7 +47,7 @@ fn test_parse_utf8_with_bom() {
     let data = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Müller /Schröder/");
 }
 
@@ -59,7 +59,7 @@ fn test_parse_utf8_chinese_characters() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "王 /伟/");
 }
 
@@ -71,7 +71,7 @@ fn test_parse_utf8_cyrillic_characters() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Иван /Петров/");
 }
 
@@ -112,7 +112,7 @@ fn test_parse_iso8859_1_accented_characters() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /García/");
 }
 
@@ -130,7 +130,7 @@ fn test_parse_iso8859_1_german_umlauts() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Müller /Schröder/");
 }
 
@@ -148,7 +148,7 @@ fn test_parse_iso8859_1_french_accents() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "René /François/");
 }
 
@@ -166,7 +166,7 @@ fn test_parse_iso8859_1_nordic_characters() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Søren /Åberg/");
 }
 
@@ -184,7 +184,7 @@ fn test_parse_iso8859_1_with_latin1_tag() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /García/");
 }
 
@@ -207,8 +207,14 @@ fn test_parse_iso8859_15_euro_sign() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let note = data.individuals[0].note.as_ref().unwrap();
-    assert!(note.value.as_ref().unwrap().contains("100€"));
+    let note = &data.individuals[0].notes[0];
+    assert!(note
+        .inline()
+        .unwrap()
+        .value
+        .as_ref()
+        .unwrap()
+        .contains("100€"));
 }
 
 #[test]
@@ -225,7 +231,7 @@ fn test_parse_iso8859_15_oe_ligatures() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Test /Bœuf/");
 }
 
@@ -244,8 +250,14 @@ fn test_parse_iso8859_15_with_latin9_tag() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let note = data.individuals[0].note.as_ref().unwrap();
-    assert!(note.value.as_ref().unwrap().contains("50€"));
+    let note = &data.individuals[0].notes[0];
+    assert!(note
+        .inline()
+        .unwrap()
+        .value
+        .as_ref()
+        .unwrap()
+        .contains("50€"));
 }
 
 // ============================================================================
@@ -260,7 +272,7 @@ fn test_parse_utf16_le_with_bom() {
     let data = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /García/");
 }
 
@@ -272,7 +284,7 @@ fn test_parse_utf16_be_with_bom() {
     let data = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Müller /Schröder/");
 }
 
@@ -284,7 +296,7 @@ fn test_parse_utf16_le_chinese_characters() {
     let data = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "王 /伟/");
 }
 
@@ -296,7 +308,7 @@ fn test_parse_utf16_be_cyrillic_characters() {
     let data = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Иван /Петров/");
 }
 
@@ -377,7 +389,7 @@ fn test_build_with_explicit_utf8_encoding() {
         .unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /García/");
 }
 
@@ -395,7 +407,7 @@ fn test_build_with_explicit_iso8859_1_encoding() {
         .unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /García/");
 }
 
@@ -409,7 +421,7 @@ fn test_build_with_explicit_utf16_le_encoding() {
         .unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /García/");
 }
 
@@ -426,7 +438,7 @@ fn test_roundtrip_utf8_special_characters() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     // Verify
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José María /García López/");
 }
 
@@ -443,7 +455,7 @@ fn test_roundtrip_utf16_le_special_characters() {
 
     // Parse
     let data = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "日本語 /テスト/");
 }
 
@@ -460,7 +472,7 @@ fn test_roundtrip_utf16_be_special_characters() {
 
     // Parse
     let data = GedcomBuilder::new().build_from_bytes(&bytes).unwrap();
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "Ελληνικά /Κείμενο/");
 }
 
@@ -545,13 +557,13 @@ fn test_mixed_encoding_header() {
     let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
 
     assert_eq!(data.individuals.len(), 1);
-    let name = data.individuals[0].name.as_ref().unwrap();
+    let name = &data.individuals[0].names[0];
     assert_eq!(name.value.as_ref().unwrap(), "José /Martínez/");
 }
 
 #[test]
-fn test_ansi_as_ascii() {
-    // Some GEDCOM files use "ANSI" which should be treated as ASCII/ISO-8859-1
+fn test_ansi_as_windows_1252() {
+    // Windows programs write "ANSI" for the Windows-1252 code page
     let bytes: &[u8] = b"0 HEAD\n\
                          1 GEDC\n\
                          2 VERS 5.5.1\n\
@@ -565,6 +577,25 @@ fn test_ansi_as_ascii() {
     assert_eq!(data.individuals.len(), 1);
 }
 
+#[test]
+fn test_ibmpc_as_cp437() {
+    // PAF for DOS wrote "IBMPC" for the code page of the IBM PC
+    let bytes: &[u8] = b"0 HEAD\n\
+                         1 GEDC\n\
+                         2 VERS 5.5\n\
+                         1 CHAR IBMPC\n\
+                         0 @I1@ INDI\n\
+                         1 NAME Fran\x87ois /M\x81ller/\n\
+                         0 TRLR\n";
+
+    let data = GedcomBuilder::new().build_from_bytes(bytes).unwrap();
+
+    assert_eq!(
+        data.individuals[0].full_name().as_deref(),
+        Some("François Müller")
+    );
+}
+
 #[test]
 fn test_unicode_as_utf16() {
     // Some GEDCOM files use "UNICODE" which typically means UTF-16
@@ -623,30 +654,30 @@ fn test_parse_complete_gedcom_with_iso8859_1() {
 
     // Check José's details
     let jose = &data.individuals[0];
-    assert_eq!(
-        jose.name.as_ref().unwrap().value.as_ref().unwrap(),
-        "José /García/"
-    );
+    assert_eq!(jose.names[0].value.as_ref().unwrap(), "José /García/");
 
     // Check birth place encoding
     let birth_event = &jose.events[0];
     assert_eq!(
-        birth_event.place.as_ref().unwrap().value.as_ref().unwrap(),
+        birth_event
+            .place
+            .as_ref()
+            .unwrap()
+            .value
+            .as_deref()
+            .unwrap(),
         "Málaga, España"
     );
 
     // Check María's details
     let maria = &data.individuals[1];
-    assert_eq!(
-        maria.name.as_ref().unwrap().value.as_ref().unwrap(),
-        "María /López/"
-    );
+    assert_eq!(maria.names[0].value.as_ref().unwrap(), "María /López/");
 
     // Check marriage place
     let family = &data.families[0];
     let marriage = &family.events[0];
     assert_eq!(
-        marriage.place.as_ref().unwrap().value.as_ref().unwrap(),
+        marriage.place.as_ref().unwrap().value.as_deref().unwrap(),
         "Sevilla, España"
     );
 }
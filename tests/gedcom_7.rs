@@ -435,7 +435,7 @@ fn test_parse_individual_non_event() {
         non_event.date.as_ref().unwrap().value,
         Some("BEF 1900".to_string())
     );
-    assert!(non_event.note.is_some());
+    assert!(!non_event.notes.is_empty());
 }
 
 /// Test parsing GEDCOM 7.0 with NO (non-event) structure for families.
@@ -464,7 +464,7 @@ fn test_parse_family_non_event() {
 
     let non_event = &data.families[0].non_events[0];
     assert_eq!(non_event.event_type, "CHIL");
-    assert!(non_event.note.is_some());
+    assert!(!non_event.notes.is_empty());
 }
 
 /// Test parsing GEDCOM 7.0 with CROP structure for multimedia.
@@ -487,7 +487,7 @@ fn test_parse_multimedia_crop() {
     let data = gedcom.parse_data().unwrap();
 
     assert_eq!(data.multimedia.len(), 1);
-    let file = data.multimedia[0].file.as_ref().unwrap();
+    let file = data.multimedia[0].file().unwrap();
     assert!(file.crop.is_some());
 
     let crop = file.crop.as_ref().unwrap();
@@ -25,11 +25,11 @@ mod json_feature_tests {
 
         // Check individual names are preserved
         if !data.individuals.is_empty() {
-            let original_name = &data.individuals[0].name;
-            let deser_name = &deserialized.individuals[0].name;
+            let original_name = data.individuals[0].names.first();
+            let deser_name = deserialized.individuals[0].names.first();
             assert_eq!(
-                original_name.as_ref().map(|n| n.value.clone()),
-                deser_name.as_ref().map(|n| n.value.clone())
+                original_name.map(|n| n.value.clone()),
+                deser_name.map(|n| n.value.clone())
             );
         }
     }
@@ -70,4 +70,108 @@ mod json_feature_tests {
         assert_eq!(data.individuals.len(), deserialized.individuals.len());
         assert_eq!(data.families.len(), deserialized.families.len());
     }
+
+    #[test]
+    fn serde_configs_and_diagnostics() {
+        use ged_io::encoding::validate_utf8_strict;
+        use ged_io::export::csv::CsvConfig;
+        use ged_io::intern::Interner;
+        use ged_io::{GedcomBuilder, GedcomError, GedcomWriter, ParserConfig, WriterConfig};
+
+        let config: ParserConfig =
+            serde_json::from_str(r#"{"strict_mode": true, "max_file_size": 1024}"#).unwrap();
+        assert!(config.strict_mode);
+        assert_eq!(config.max_file_size, Some(1024));
+        assert!(config.preserve_formatting);
+        assert!(GedcomBuilder::with_config(config)
+            .build_from_str("0 HEAD\n0 TRLR\n1 BAD")
+            .is_err());
+
+        let config = WriterConfig {
+            gedcom_version: "7.0".to_string(),
+            ..WriterConfig::default()
+        };
+        let reloaded: WriterConfig =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+        assert_eq!(reloaded.gedcom_version, "7.0");
+        assert_eq!(reloaded.max_line_length, config.max_line_length);
+        let writer = GedcomWriter::with_config(reloaded);
+        assert_eq!(writer.config().gedcom_version, "7.0");
+
+        let config = CsvConfig {
+            delimiter: ';',
+            ..CsvConfig::default()
+        };
+        let reloaded: CsvConfig =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+        assert_eq!(reloaded, config);
+
+        let error = GedcomError::InFile {
+            path: "family.ged".to_string(),
+            source: Box::new(GedcomError::ParseError {
+                line: 3,
+                message: "bad level".to_string(),
+            }),
+        };
+        let reloaded: GedcomError =
+            serde_json::from_str(&serde_json::to_string(&error).unwrap()).unwrap();
+        assert_eq!(reloaded.to_string(), error.to_string());
+
+        let report = validate_utf8_strict(b"0 HEAD\n\xFF\n");
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ged_io::encoding::Utf8ValidationReport>(&json).unwrap(),
+            report
+        );
+
+        let mut interner = Interner::new();
+        let salem = interner.intern("Salem");
+        interner.intern("Boston");
+        let json = serde_json::to_string(&interner).unwrap();
+        assert_eq!(json, r#"["Salem","Boston"]"#);
+        let reloaded: Interner = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.get("Salem"), Some(salem));
+    }
+
+    #[test]
+    fn from_json_validated_locates_problems() {
+        use ged_io::types::GedcomData;
+        use ged_io::GedcomError;
+
+        for fixture in ["simple", "sample", "allged", "washington"] {
+            let content = read_relative(&format!("./tests/fixtures/{fixture}.ged"));
+            let data = ged_io::GedcomBuilder::new()
+                .build_from_str(&content)
+                .unwrap();
+            let json = serde_json::to_string(&data).unwrap();
+            assert_eq!(GedcomData::from_json_validated(&json).unwrap(), data);
+        }
+
+        let content = read_relative("./tests/fixtures/simple.ged");
+        let data = ged_io::GedcomBuilder::new()
+            .build_from_str(&content)
+            .unwrap();
+        let mut value = serde_json::to_value(&data).unwrap();
+
+        let path = |json: &str| match GedcomData::from_json_validated(json) {
+            Err(GedcomError::InvalidStructure { path, .. }) => path,
+            other => panic!("expected an invalid structure, got {other:?}"),
+        };
+
+        value["individuals"][1]["xref"] = serde_json::json!(42);
+        assert_eq!(path(&value.to_string()), "$.individuals[1]");
+
+        value["individuals"][1]["xref"] = value["individuals"][0]["xref"].clone();
+        assert_eq!(path(&value.to_string()), "$.individuals[1].xref");
+
+        value["individuals"][1]["xref"] = serde_json::Value::Null;
+        assert_eq!(path(&value.to_string()), "$.individuals[1].xref");
+
+        let Err(GedcomError::ParseError { line, .. }) =
+            GedcomData::from_json_validated("{\n\"individuals\": [,]}")
+        else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!(line, 2);
+    }
 }
@@ -1,6 +1,6 @@
 //! Comprehensive test suite for malformed GEDCOM input handling (Issue #21)
 
-use ged_io::{Gedcom, GedcomBuilder, GedcomError};
+use ged_io::{Gedcom, GedcomBuilder, GedcomError, GedcomStreamParser, ParseWarning};
 
 // ============================================================================
 // Missing/Incomplete Header Tests
@@ -202,3 +202,61 @@ fn test_family_without_members() {
     let data = gedcom.parse_data().unwrap();
     assert_eq!(data.families.len(), 1);
 }
+
+// ============================================================================
+// Trailer Tests
+// ============================================================================
+
+/// Parses with the in-memory and streaming parsers, checking that they agree.
+fn trailer_warnings(sample: &str) -> Vec<ParseWarning> {
+    let data = GedcomBuilder::new().build_from_str(sample).unwrap();
+    let mut parser = GedcomStreamParser::new(sample.as_bytes()).unwrap();
+    assert!(parser.by_ref().all(|record| record.is_ok()));
+    assert_eq!(parser.warnings(), data.warnings.as_slice());
+    data.warnings
+}
+
+#[test]
+fn test_trailer_warnings_agree_between_parsers() {
+    let complete = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 TRLR\n\n\u{1A}";
+    assert!(trailer_warnings(complete).is_empty());
+
+    let missing = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n";
+    assert_eq!(
+        trailer_warnings(missing),
+        vec![ParseWarning::MissingTrailer]
+    );
+
+    let after = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 TRLR\n\n0 @I1@ INDI\n1 NAME Late /Doe/\n";
+    assert_eq!(
+        trailer_warnings(after),
+        vec![ParseWarning::ContentAfterTrailer { line: 6 }]
+    );
+    let data = GedcomBuilder::new().build_from_str(after).unwrap();
+    assert!(data.individuals.is_empty());
+}
+
+#[test]
+fn test_trailer_problems_fail_in_strict_mode() {
+    let missing = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 @I1@ INDI\n";
+    let result = GedcomBuilder::new()
+        .strict_mode(true)
+        .build_from_str(missing);
+    assert!(matches!(result, Err(GedcomError::InvalidFormat(_))));
+
+    let after = "0 HEAD\n1 GEDC\n2 VERS 5.5\n0 TRLR\n0 @I1@ INDI\n";
+    let result = GedcomBuilder::new().strict_mode(true).build_from_str(after);
+    assert!(matches!(
+        result,
+        Err(GedcomError::ParseError { line: 5, .. })
+    ));
+
+    let parser = GedcomStreamParser::new(after.as_bytes())
+        .unwrap()
+        .strict_mode(true);
+    let results: Vec<_> = parser.collect();
+    assert!(matches!(
+        results.last(),
+        Some(Err(GedcomError::ParseError { line: 5, .. }))
+    ));
+}
@@ -43,7 +43,7 @@ fn test_round_trip_individual() {
 
     assert_eq!(data1.individuals.len(), data2.individuals.len());
     assert_eq!(data1.individuals[0].xref, data2.individuals[0].xref);
-    assert_eq!(data1.individuals[0].name, data2.individuals[0].name);
+    assert_eq!(data1.individuals[0].names, data2.individuals[0].names);
     assert_eq!(data1.individuals[0].sex, data2.individuals[0].sex);
 }
 
@@ -304,7 +304,7 @@ fn test_round_trip_complete_gedcom() {
         .enumerate()
     {
         assert_eq!(ind1.xref, ind2.xref, "Individual {} xref mismatch", i);
-        assert_eq!(ind1.name, ind2.name, "Individual {} name mismatch", i);
+        assert_eq!(ind1.names, ind2.names, "Individual {} name mismatch", i);
         assert_eq!(ind1.sex, ind2.sex, "Individual {} sex mismatch", i);
     }
 }
@@ -395,8 +395,8 @@ fn test_round_trip_individual_no_name() {
     let data2 = GedcomBuilder::new().build_from_str(&written).unwrap();
 
     assert_eq!(data1.individuals.len(), data2.individuals.len());
-    assert!(data1.individuals[0].name.is_none());
-    assert!(data2.individuals[0].name.is_none());
+    assert!(data1.individuals[0].names.is_empty());
+    assert!(data2.individuals[0].names.is_empty());
 }
 
 #[test]
@@ -478,3 +478,89 @@ fn test_written_output_contains_expected_tags() {
     assert!(written.contains("1 HUSB @I1@"), "Missing HUSB tag");
     assert!(written.contains("0 TRLR"), "Missing TRLR tag");
 }
+
+#[test]
+fn test_round_trip_at_signs_gedcom_5() {
+    let original = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n0 @I1@ INDI\n1 NOTE Write to jo@@example.com\n1 FAMS @F1@\n0 @F1@ FAM\n1 HUSB @I1@\n0 TRLR";
+
+    let data1 = GedcomBuilder::new().build_from_str(original).unwrap();
+    let note = &data1.individuals[0].notes[0];
+    assert_eq!(
+        note.inline().unwrap().value.as_deref(),
+        Some("Write to jo@example.com")
+    );
+
+    let written = GedcomWriter::new().write_to_string(&data1).unwrap();
+    assert!(written.contains("1 NOTE Write to jo@@example.com\n"));
+    assert!(written.contains("1 HUSB @I1@\n"));
+
+    let data2 = GedcomBuilder::new().build_from_str(&written).unwrap();
+    assert_eq!(data1.individuals[0].notes, data2.individuals[0].notes);
+}
+
+#[test]
+fn test_round_trip_at_signs_gedcom_7() {
+    let original =
+        "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @I1@ INDI\n1 NOTE @@handle at jo@example.com\n0 TRLR\n";
+
+    let data1 = GedcomBuilder::new().build_from_str(original).unwrap();
+    let note = &data1.individuals[0].notes[0];
+    assert_eq!(
+        note.inline().unwrap().value.as_deref(),
+        Some("@handle at jo@example.com")
+    );
+
+    // The header's 7.0 version takes precedence over the writer default.
+    let written = GedcomWriter::new().write_to_string(&data1).unwrap();
+    assert!(written.contains("1 NOTE @@handle at jo@example.com\n"));
+
+    let data2 = GedcomBuilder::new().build_from_str(&written).unwrap();
+    assert_eq!(data1.individuals[0].notes, data2.individuals[0].notes);
+}
+
+// =============================================================================
+// Vendor Header Round-Trip Tests
+// =============================================================================
+
+#[test]
+fn test_round_trip_vendor_headers() {
+    let mut paths: Vec<_> = std::fs::read_dir("tests/fixtures/vendor_headers")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    for path in paths {
+        let original = std::fs::read_to_string(&path).unwrap();
+        let data1 = GedcomBuilder::new().build_from_str(&original).unwrap();
+
+        let written = GedcomWriter::new().write_to_string(&data1).unwrap();
+        let data2 = GedcomBuilder::new().build_from_str(&written).unwrap();
+        assert_eq!(data1.header, data2.header, "{}", path.display());
+
+        for line in original.lines().filter(|l| l.contains(" _")) {
+            assert!(
+                written.lines().any(|w| w == line),
+                "{}: {line:?} was not written back",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_round_trip_header_custom_tags() {
+    let original = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 SOUR APP\n2 _TREE Tree\n3 RIN 1\n1 _PROJECT_GUID ABC\n0 TRLR";
+    let data = GedcomBuilder::new().build_from_str(original).unwrap();
+
+    let header = data.header.as_ref().unwrap();
+    assert_eq!(&*header.custom_data[0].tag, "_PROJECT_GUID");
+    let source = header.source.as_ref().unwrap();
+    assert_eq!(&*source.custom_data[0].tag, "_TREE");
+    assert_eq!(&*source.custom_data[0].children[0].tag, "RIN");
+
+    let written = GedcomWriter::new().write_to_string(&data).unwrap();
+    assert!(written.contains("1 SOUR APP\n2 _TREE Tree\n3 RIN 1\n"));
+    assert!(written.contains("1 _PROJECT_GUID ABC\n"));
+}
@@ -0,0 +1,151 @@
+#!/usr/bin/env python3
+"""Generates src/encoding/normalize/tables.rs from the Unicode Character Database.
+
+Download UnicodeData.txt and CompositionExclusions.txt for the version named
+in UCD_VERSION from https://www.unicode.org/Public/<version>/ucd/ into one
+directory, then run from the repository root:
+
+    python3 tools/gen_normalize_tables.py <ucd-dir> > src/encoding/normalize/tables.rs
+
+To move to a newer Unicode version, bump UCD_VERSION, download that
+version's files and regenerate.
+"""
+
+import os
+import sys
+
+UCD_VERSION = "14.0.0"
+UCD_URL = f"https://www.unicode.org/Public/{UCD_VERSION}/ucd/"
+
+
+def read_unicode_data(ucd_dir):
+    """Returns the combining class and canonical decomposition mapping of
+    every character that has them."""
+    classes = {}
+    mappings = {}
+    with open(os.path.join(ucd_dir, "UnicodeData.txt"), encoding="utf-8") as f:
+        for line in f:
+            fields = line.rstrip("\n").split(";")
+            code = int(fields[0], 16)
+            if fields[3] != "0":
+                classes[code] = int(fields[3])
+            # Compatibility mappings start with a <tag>; only canonical
+            # mappings take part in NFC and NFD.
+            if fields[5] and not fields[5].startswith("<"):
+                mappings[code] = [int(c, 16) for c in fields[5].split()]
+    return classes, mappings
+
+
+def read_exclusions(ucd_dir):
+    """Returns the characters listed in CompositionExclusions.txt."""
+    excluded = set()
+    path = os.path.join(ucd_dir, "CompositionExclusions.txt")
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            line = line.split("#", 1)[0].strip()
+            if line:
+                excluded.add(int(line, 16))
+    return excluded
+
+
+def full_decomposition(code, mappings, classes):
+    """Returns the full canonical decomposition of `code`, in canonical
+    order."""
+    if code not in mappings:
+        return [code]
+    chars = []
+    for c in mappings[code]:
+        chars.extend(full_decomposition(c, mappings, classes))
+    # A stable sort by combining class within each run of non-starters.
+    start = 0
+    while start < len(chars):
+        end = start
+        while end < len(chars) and classes.get(chars[end], 0) != 0:
+            end += 1
+        chars[start:end] = sorted(chars[start:end], key=lambda c: classes[c])
+        start = end + 1
+    return chars
+
+
+def combining_class_ranges(classes):
+    """Returns runs of consecutive characters with the same non-zero class."""
+    ranges = []
+    for code in sorted(classes):
+        if ranges and ranges[-1][1] == code - 1 and ranges[-1][2] == classes[code]:
+            ranges[-1][1] = code
+        else:
+            ranges.append([code, code, classes[code]])
+    return ranges
+
+
+def compositions(mappings, classes, excluded):
+    """Returns the primary composites as (first, second, composite), sorted
+    by pair."""
+    pairs = []
+    for code, mapping in mappings.items():
+        # Singletons, non-starter decompositions and the listed exclusions
+        # make up the full composition exclusions.
+        if len(mapping) != 2 or code in excluded:
+            continue
+        if classes.get(code, 0) != 0 or classes.get(mapping[0], 0) != 0:
+            continue
+        pairs.append((mapping[0], mapping[1], code))
+    return sorted(pairs)
+
+
+def rust_char(code):
+    if 0x20 <= code < 0x7F and chr(code) not in "'\"\\":
+        return chr(code)
+    return f"\\u{{{code:X}}}"
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit(f"usage: {sys.argv[0]} <ucd-dir>")
+    ucd_dir = sys.argv[1]
+    classes, mappings = read_unicode_data(ucd_dir)
+    excluded = read_exclusions(ucd_dir)
+    major, minor, _ = UCD_VERSION.split(".")
+
+    out = sys.stdout
+    out.write(
+        "//! Unicode tables for [`super`], generated from the Unicode Character\n"
+        f"//! Database {major}.{minor}.\n"
+        "//!\n"
+        "//! Generated by `tools/gen_normalize_tables.py` from `UnicodeData.txt`\n"
+        "//! and `CompositionExclusions.txt` at\n"
+        f"//! <{UCD_URL}>. Do not edit by hand;\n"
+        "//! regenerate instead.\n"
+        "\n"
+        "/// The canonical combining class of each character that has a non-zero\n"
+        "/// one, as ranges of characters with the same class.\n"
+        "pub(super) const COMBINING_CLASS: &[(char, char, u8)] = &[\n"
+    )
+    for first, last, class_ in combining_class_ranges(classes):
+        out.write(f"    ('{rust_char(first)}', '{rust_char(last)}', {class_}),\n")
+    out.write(
+        "];\n"
+        "\n"
+        "/// The full canonical decomposition of each character that has one, except\n"
+        "/// the Hangul syllables, whose decomposition is computed.\n"
+        "pub(super) const DECOMPOSITION: &[(char, &str)] = &[\n"
+    )
+    for code in sorted(mappings):
+        chars = "".join(rust_char(c) for c in full_decomposition(code, mappings, classes))
+        out.write(f"    ('{rust_char(code)}', \"{chars}\"),\n")
+    out.write(
+        "];\n"
+        "\n"
+        "/// The primary composite of each pair of characters that composes, except\n"
+        "/// the Hangul syllables, sorted by pair.\n"
+        "pub(super) const COMPOSITION: &[(char, char, char)] = &[\n"
+    )
+    for first, second, composite in compositions(mappings, classes, excluded):
+        out.write(
+            f"    ('{rust_char(first)}', '{rust_char(second)}', '{rust_char(composite)}'),\n"
+        )
+    out.write("];\n")
+
+
+if __name__ == "__main__":
+    main()